@@ -0,0 +1,219 @@
+//! Records the versions of the tree-sitter crates the library is built
+//! with, read from the `Cargo.lock` of the package or of its workspace,
+//! in the `SCA_GRAMMAR_VERSIONS` variable, and generates the enums of the
+//! node kinds of the grammars which have no enum in `src/languages`.
+
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use tree_sitter::Language;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    record_grammar_versions();
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR is set by cargo"));
+    // The name of the enum, the file it is written to and the grammar
+    let grammars: [(&str, &str, Language); 3] = [
+        ("Cpp", "language_cpp.rs", tree_sitter_cpp::LANGUAGE.into()),
+        ("Go", "language_go.rs", tree_sitter_go::LANGUAGE.into()),
+        (
+            "Csharp",
+            "language_csharp.rs",
+            tree_sitter_c_sharp::LANGUAGE.into(),
+        ),
+    ];
+    for (name, file, language) in grammars {
+        write_enum(name, &language, &out_dir.join(file));
+    }
+}
+
+fn record_grammar_versions() {
+    let mut dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap_or_default());
+    let lock = loop {
+        let lock = dir.join("Cargo.lock");
+        if lock.is_file() {
+            break Some(lock);
+        }
+        if !dir.pop() {
+            break None;
+        }
+    };
+
+    let mut versions = Vec::new();
+    if let Some(lock) = lock {
+        println!("cargo:rerun-if-changed={}", lock.display());
+        let content = fs::read_to_string(&lock).unwrap_or_default();
+        let mut name = None;
+        for line in content.lines() {
+            if let Some(value) = line.strip_prefix("name = ") {
+                name = Some(value.trim_matches('"').to_string());
+            } else if let Some(value) = line.strip_prefix("version = ") {
+                if let Some(name) = name.take().filter(|name| name.starts_with("tree-sitter")) {
+                    versions.push(format!("{name}={}", value.trim_matches('"')));
+                }
+            }
+        }
+    }
+    println!(
+        "cargo:rustc-env=SCA_GRAMMAR_VERSIONS={}",
+        versions.join(";")
+    );
+}
+
+// Writes the enum of the node kinds of a grammar, with a variant for each
+// kind id, so no id of the grammar falls back to another kind.
+//
+// The ids come from the symbol table of the grammar, as its
+// `node-types.json` lists the kinds without their ids.
+fn write_enum(name: &str, language: &Language, path: &Path) {
+    let mut seen = HashMap::new();
+    let mut variant_of = |kind: &str| {
+        let variant = variant_name(kind);
+        let count = seen.entry(variant.clone()).or_insert(0);
+        *count += 1;
+        if *count == 1 {
+            variant
+        } else {
+            format!("{variant}{count}")
+        }
+    };
+
+    let count = language.node_kind_count() as u16;
+    let mut kinds = (0..count)
+        .map(|id| {
+            let kind = language.node_kind_for_id(id).unwrap_or_default();
+            (variant_of(kind), kind, id)
+        })
+        .collect::<Vec<_>>();
+    // The kind of the errors, which is also given to the unknown ids
+    let error = variant_of("ERROR");
+    kinds.push((error.clone(), "ERROR", count));
+
+    let mut code = String::from(
+        "// Code generated by the build script; DO NOT EDIT.\n\n\
+         use num_derive::FromPrimitive;\n\n\
+         #[derive(Clone, Debug, PartialEq, Eq, FromPrimitive)]\n",
+    );
+    writeln!(code, "pub enum {name} {{").unwrap();
+    for (variant, _, id) in &kinds {
+        writeln!(code, "    {variant} = {id},").unwrap();
+    }
+    writeln!(code, "}}\n").unwrap();
+
+    writeln!(code, "impl From<{name}> for &'static str {{").unwrap();
+    writeln!(code, "    #[inline(always)]").unwrap();
+    writeln!(code, "    fn from(tok: {name}) -> Self {{").unwrap();
+    writeln!(code, "        match tok {{").unwrap();
+    for (variant, kind, _) in &kinds {
+        writeln!(code, "            {name}::{variant} => {kind:?},").unwrap();
+    }
+    writeln!(code, "        }}\n    }}\n}}\n").unwrap();
+
+    writeln!(code, "impl From<u16> for {name} {{").unwrap();
+    writeln!(code, "    #[inline(always)]").unwrap();
+    writeln!(code, "    fn from(x: u16) -> Self {{").unwrap();
+    writeln!(
+        code,
+        "        num::FromPrimitive::from_u16(x).unwrap_or(Self::{error})"
+    )
+    .unwrap();
+    writeln!(code, "    }}\n}}\n").unwrap();
+
+    writeln!(code, "// {name} == u16").unwrap();
+    writeln!(code, "impl PartialEq<u16> for {name} {{").unwrap();
+    writeln!(code, "    #[inline(always)]").unwrap();
+    writeln!(code, "    fn eq(&self, x: &u16) -> bool {{").unwrap();
+    writeln!(code, "        *self == Into::<Self>::into(*x)").unwrap();
+    writeln!(code, "    }}\n}}\n").unwrap();
+
+    writeln!(code, "// u16 == {name}").unwrap();
+    writeln!(code, "impl PartialEq<{name}> for u16 {{").unwrap();
+    writeln!(code, "    #[inline(always)]").unwrap();
+    writeln!(code, "    fn eq(&self, x: &{name}) -> bool {{").unwrap();
+    writeln!(code, "        *x == *self").unwrap();
+    writeln!(code, "    }}\n}}").unwrap();
+
+    fs::write(path, code).unwrap_or_else(|err| panic!("cannot write {}: {err}", path.display()));
+}
+
+// Returns the name of the variant of a node kind, in `PascalCase` with
+// the symbols spelled out, as in the generated enums of `src/languages`
+fn variant_name(kind: &str) -> String {
+    if kind == "self" {
+        return "Zelf".to_string();
+    }
+    let mut name = String::new();
+    let mut capitalize = true;
+    for c in kind.chars() {
+        match c {
+            '_' => capitalize = true,
+            ' ' => capitalize = false,
+            c if c.is_ascii_alphanumeric() => {
+                name.push(if capitalize {
+                    c.to_ascii_uppercase()
+                } else {
+                    c
+                });
+                capitalize = false;
+            }
+            c => {
+                name.push_str(&symbol_name(c));
+                capitalize = false;
+            }
+        }
+    }
+    if name.is_empty() {
+        name.push_str("Unnamed");
+    } else if name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, 'N');
+    }
+    name
+}
+
+fn symbol_name(c: char) -> String {
+    let name = match c {
+        '\n' => "LF",
+        '\r' => "CR",
+        '\t' => "TAB",
+        '!' => "BANG",
+        '"' => "DQUOTE",
+        '#' => "HASH",
+        '$' => "DOLLAR",
+        '%' => "PERCENT",
+        '&' => "AMP",
+        '\'' => "SQUOTE",
+        '(' => "LPAREN",
+        ')' => "RPAREN",
+        '*' => "STAR",
+        '+' => "PLUS",
+        ',' => "COMMA",
+        '-' => "DASH",
+        '.' => "DOT",
+        '/' => "SLASH",
+        ':' => "COLON",
+        ';' => "SEMI",
+        '<' => "LT",
+        '=' => "EQ",
+        '>' => "GT",
+        '?' => "QMARK",
+        '@' => "AT",
+        '[' => "LBRACK",
+        '\\' => "BSLASH",
+        ']' => "RBRACK",
+        '^' => "CARET",
+        '`' => "BQUOTE",
+        '{' => "LBRACE",
+        '|' => "PIPE",
+        '}' => "RBRACE",
+        '~' => "TILDE",
+        c => return format!("U{:X}", c as u32),
+    };
+    name.to_string()
+}
@@ -0,0 +1,18 @@
+#![no_main]
+
+//! Feeds arbitrary bytes into the pipeline of every language.
+//!
+//! Run it with `cargo fuzz run all_languages`.
+
+use std::path::Path;
+
+use libfuzzer_sys::fuzz_target;
+use singularity_code_analysis::{get_function_spaces, get_ops, LANG};
+
+fuzz_target!(|data: &[u8]| {
+    for lang in LANG::into_enum_iter() {
+        let path = Path::new("fuzz");
+        get_function_spaces(&lang, data.to_vec(), path, None);
+        get_ops(&lang, data.to_vec(), path, None);
+    }
+});
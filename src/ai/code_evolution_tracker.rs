@@ -4,6 +4,7 @@
 //! Elixir handles orchestration, state management, and database operations.
 
 use crate::langs::LANG;
+use crate::metrics::ai_metrics::HistoryProvider;
 
 /// Calculate code evolution trends from version history
 /// 
@@ -51,6 +52,27 @@ pub fn calculate_trend(values: &[f64]) -> TrendDirection {
     }
 }
 
+/// Calculate the complexity trend of a file from its recorded history
+/// 
+/// # Arguments
+/// * `provider` - The source of the history: git, SQLite or PostgreSQL
+/// * `file_path` - The path of the file
+/// 
+/// # Returns
+/// * `None` if the provider has no history of the file
+pub fn calculate_history_trend(
+    provider: &dyn HistoryProvider,
+    file_path: &str
+) -> std::io::Result<Option<TrendDirection>> {
+    let trends = provider.complexity_trends(file_path)?;
+    if trends.is_empty() {
+        return Ok(None);
+    }
+    
+    let values: Vec<f64> = trends.iter().map(|trend| trend.complexity_score).collect();
+    Ok(Some(calculate_trend(&values)))
+}
+
 /// Detect refactoring events from before/after metrics
 /// 
 /// # Arguments
@@ -39,7 +39,7 @@ pub fn extract_complexity_features(code: &str, language: LANG) -> ComplexityFeat
         nesting_depth: calculate_max_nesting_depth(code, language),
         operator_count: count_patterns(code, &get_operator_patterns(language)),
         comment_ratio: calculate_comment_ratio(code, language),
-        identifier_length_avg: calculate_avg_identifier_length(code, language),
+        identifier_quality: calculate_identifier_quality(code, language),
         cyclomatic_complexity: calculate_cyclomatic_complexity_estimate(code, language),
     }
 }
@@ -78,7 +78,7 @@ pub fn extract_complexity_features_with_patterns(
         nesting_depth: calculate_max_nesting_depth_with_patterns(code, &opening_delimiters_str, &closing_delimiters_str),
         operator_count: count_patterns(code, &operator_patterns_str),
         comment_ratio: calculate_comment_ratio_with_patterns(code, &comment_patterns_str),
-        identifier_length_avg: calculate_avg_identifier_length(code, language), // This doesn't need patterns
+        identifier_quality: calculate_identifier_quality(code, language), // This doesn't need patterns
         cyclomatic_complexity: calculate_cyclomatic_complexity_estimate(code, language), // This doesn't need patterns
     }
 }
@@ -107,7 +107,7 @@ pub fn calculate_cognitive_complexity(features: &ComplexityFeatures) -> f64 {
 #[inline(always)]
 pub fn calculate_maintainability_complexity(features: &ComplexityFeatures) -> f64 {
     let comment_factor = if features.comment_ratio > 0.2 { 0.5 } else { 2.0 };
-    let identifier_factor = if features.identifier_length_avg > 8.0 { 0.5 } else { 1.5 };
+    let identifier_factor = if features.identifier_quality > 60.0 { 0.5 } else { 1.5 };
     let length_factor = if features.non_empty_lines > 100 { 1.5 } else { 0.5 };
     
     (comment_factor + identifier_factor + length_factor).min(5.0)
@@ -373,21 +373,14 @@ pub fn calculate_ai_complexity_score_with_patterns(
         .min(10.0) // Cap at 10.0 for consistency
 }
 
-/// Calculate average identifier length
+/// Calculate the quality score of the identifiers, from 0 to 100
+///
+/// The score comes from the naming of the identifiers of the AST: their
+/// length, their dictionary words, their abbreviations and their
+/// consistency with the conventions of the language.
 #[inline(always)]
-pub fn calculate_avg_identifier_length(code: &str, _language: LANG) -> f64 {
-    let words: Vec<&str> = code.split_whitespace().collect();
-    let identifiers: Vec<&str> = words.iter()
-        .filter(|word| word.chars().all(|c| c.is_alphanumeric() || c == '_'))
-        .map(|s| *s)
-        .collect();
-    
-    if identifiers.is_empty() {
-        0.0
-    } else {
-        let total_length: usize = identifiers.iter().map(|id| id.len()).sum();
-        total_length as f64 / identifiers.len() as f64
-    }
+pub fn calculate_identifier_quality(code: &str, language: LANG) -> f64 {
+    crate::code_identifier_quality(code, language).file.score
 }
 
 /// Calculate cyclomatic complexity estimate
@@ -413,7 +406,7 @@ pub struct ComplexityFeatures {
     pub nesting_depth: usize,
     pub operator_count: usize,
     pub comment_ratio: f64,
-    pub identifier_length_avg: f64,
+    pub identifier_quality: f64,
     pub cyclomatic_complexity: f64,
 }
 
@@ -423,47 +416,37 @@ pub fn calculate_pattern_effectiveness(pattern: &str, metrics: &ComplexityFeatur
     // Pattern effectiveness based on complexity reduction
     let complexity_reduction = if metrics.cyclomatic_complexity > 5.0 { 0.8 } else { 0.3 };
     let maintainability_boost = if metrics.comment_ratio > 0.2 { 0.9 } else { 0.4 };
-    let readability_score = if metrics.identifier_length_avg > 6.0 { 0.7 } else { 0.5 };
+    let readability_score = if metrics.identifier_quality > 60.0 { 0.7 } else { 0.5 };
     
     (complexity_reduction + maintainability_boost + readability_score) / 3.0
 }
 
+/// Returns the OTP metrics of BEAM code, computed from its AST
+///
+/// Only Elixir and Erlang code has OTP metrics.
+pub fn extract_otp_metrics(code: &str, language: LANG) -> Option<crate::extras::otp::Stats> {
+    if !matches!(language, LANG::Elixir | LANG::Erlang) {
+        return None;
+    }
+    let path = std::path::Path::new("");
+    crate::get_function_spaces(&language, code.as_bytes().to_vec(), path, None)
+        .and_then(|space| space.metrics.extras.otp().cloned())
+}
+
 /// Calculate supervision complexity for BEAM languages
+///
+/// Supervisors, `GenServer`s and supervised children are detected from the AST.
 #[inline(always)]
-pub fn calculate_supervision_complexity(modules: &[String]) -> f64 {
-    if modules.is_empty() {
-        return 0.0;
-    }
-    
-    let supervisor_count = modules.iter()
-        .filter(|module| module.contains("Supervisor") || module.contains("supervisor"))
-        .count();
-    
-    let genserver_count = modules.iter()
-        .filter(|module| module.contains("GenServer") || module.contains("gen_server"))
-        .count();
-    
-    (supervisor_count as f64 * 0.5 + genserver_count as f64 * 0.3).min(10.0)
+pub fn calculate_supervision_complexity(code: &str, language: LANG) -> f64 {
+    extract_otp_metrics(code, language)
+        .map_or(0.0, |otp| otp.supervision_complexity())
 }
 
 /// Calculate actor complexity for BEAM languages
+///
+/// Spawns, message sends, `receive` blocks and `GenServer` callbacks are
+/// detected from the AST.
 #[inline(always)]
-pub fn calculate_actor_complexity(functions: &[String]) -> f64 {
-    if functions.is_empty() {
-        return 0.0;
-    }
-    
-    let spawn_count = functions.iter()
-        .filter(|func| func.contains("spawn") || func.contains("Task.async"))
-        .count();
-    
-    let send_count = functions.iter()
-        .filter(|func| func.contains("send") || func.contains("cast"))
-        .count();
-    
-    let receive_count = functions.iter()
-        .filter(|func| func.contains("receive") || func.contains("call"))
-        .count();
-    
-    (spawn_count as f64 * 0.4 + send_count as f64 * 0.3 + receive_count as f64 * 0.3).min(10.0)
+pub fn calculate_actor_complexity(code: &str, language: LANG) -> f64 {
+    extract_otp_metrics(code, language).map_or(0.0, |otp| otp.actor_complexity())
 }
@@ -4,5 +4,7 @@
 //! including semantic understanding and intelligent insights for AI/LLM systems.
 
 pub mod semantic_analyzer;
+pub mod smell_catalog;
 
 pub use semantic_analyzer::*;
+pub use smell_catalog::*;
@@ -3,9 +3,12 @@
 //! Provides semantic understanding of code through embeddings,
 //! pattern recognition, and intelligent analysis.
 
-use crate::langs::LANG;
+use crate::ai::smell_catalog::*;
+use crate::langs::{action, LANG};
+use crate::refactoring::{ExtractionCandidate, ExtractionCandidates, GuardClauseEdits, TextEdit};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
 
 /// Semantic analyzer for code understanding
 #[derive(Debug, Clone)]
@@ -37,11 +40,16 @@ pub enum PatternType {
     CodeSmell,
     BestPractice,
     RefactoringOpportunity,
+    /// A pattern learned from the functions of an organization
+    LearnedPattern,
 }
 
 /// Code smell detection result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CodeSmell {
+    /// The id of the catalog rule which reported the smell
+    #[serde(default)]
+    pub rule_id: String,
     pub name: String,
     pub description: String,
     pub severity: Severity,
@@ -49,6 +57,16 @@ pub struct CodeSmell {
     pub suggestion: String,
 }
 
+/// Code smells detected with a catalog, and the rules which ran
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmellReport {
+    pub smells: Vec<CodeSmell>,
+    /// The smells silenced by a `sca-ignore` comment on their first line
+    #[serde(default)]
+    pub suppressed: Vec<CodeSmell>,
+    pub metadata: SmellMetadata,
+}
+
 /// Refactoring suggestion
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RefactoringSuggestion {
@@ -58,6 +76,9 @@ pub struct RefactoringSuggestion {
     pub effort: EffortLevel,
     pub benefits: Vec<String>,
     pub code_example: String,
+    /// The edits applying the refactoring, when it can be done safely
+    #[serde(default)]
+    pub edits: Vec<TextEdit>,
 }
 
 /// Code location information
@@ -70,6 +91,13 @@ pub struct CodeLocation {
     pub column_end: usize,
 }
 
+/// A line repeated further down a code
+struct Duplicate {
+    location: CodeLocation,
+    /// The length of the line, the value measured by the duplicate code rule
+    length: usize,
+}
+
 /// Severity levels
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Severity {
@@ -97,6 +125,49 @@ pub enum EffortLevel {
     VeryHigh,
 }
 
+// Suggests the extraction of a candidate block into a function
+fn extract_method(candidate: &ExtractionCandidate) -> RefactoringSuggestion {
+    let estimate = &candidate.estimate;
+    let reduction = estimate.cyclomatic_reduction();
+    let priority = if reduction >= 5. {
+        Priority::High
+    } else if reduction >= 2. {
+        Priority::Medium
+    } else {
+        Priority::Low
+    };
+    let effort = match candidate.crossing_variables() {
+        0 | 1 => EffortLevel::Low,
+        2 | 3 => EffortLevel::Medium,
+        _ => EffortLevel::High,
+    };
+    let call = format!("extracted({})", candidate.inputs.join(", "));
+    RefactoringSuggestion {
+        name: "Extract Method".to_string(),
+        description: format!(
+            "Extract lines {}-{} of `{}` into a function: its cyclomatic complexity drops from {} to {}, and {} for the extracted function",
+            estimate.start_line,
+            estimate.end_line,
+            estimate.function.as_deref().unwrap_or("<anonymous>"),
+            estimate.before.cyclomatic,
+            estimate.remainder.cyclomatic,
+            estimate.extracted.cyclomatic,
+        ),
+        priority,
+        effort,
+        benefits: vec![
+            "Improved readability".to_string(),
+            "Better testability".to_string(),
+            "Reduced complexity".to_string(),
+        ],
+        code_example: match candidate.outputs.first() {
+            Some(output) => format!("{output} = {call}"),
+            None => call,
+        },
+        edits: Vec::new(),
+    }
+}
+
 impl Default for SemanticAnalyzer {
     fn default() -> Self {
         Self::new()
@@ -185,86 +256,139 @@ impl SemanticAnalyzer {
         similar_patterns
     }
 
-    /// Detect code smells and anti-patterns
+    /// Detect code smells and anti-patterns with the default catalog
     pub fn detect_code_smells(&self, code: &str) -> Vec<CodeSmell> {
-        let mut code_smells = Vec::new();
+        self.detect_code_smells_with_catalog(code, None, &SmellCatalog::default())
+            .smells
+    }
 
-        // Detect long functions (more than 50 lines)
+    /// Detect code smells and anti-patterns with the enabled rules of a
+    /// catalog, using the thresholds of the given language
+    pub fn detect_code_smells_with_catalog(
+        &self,
+        code: &str,
+        language: Option<LANG>,
+        catalog: &SmellCatalog,
+    ) -> SmellReport {
+        let mut code_smells = Vec::new();
+        let mut metadata = SmellMetadata::for_catalog(catalog);
         let lines = code.lines().count();
-        if lines > 50 {
-            code_smells.push(CodeSmell {
-                name: "Long Function".to_string(),
-                description: format!("Function has {} lines, consider breaking it down", lines),
-                severity: Severity::Medium,
-                location: CodeLocation {
-                    file_path: "unknown".to_string(),
-                    line_start: 1,
-                    line_end: lines,
-                    column_start: 1,
-                    column_end: 1,
-                },
-                suggestion: "Break the function into smaller, more focused functions".to_string(),
-            });
+
+        // Detect long functions
+        if let Some(rule) = catalog.enabled(LONG_FUNCTION) {
+            let threshold = rule.threshold_for(language);
+            let count = code_smells.len();
+            if lines as f64 > threshold {
+                code_smells.push(CodeSmell {
+                    rule_id: rule.id.clone(),
+                    name: rule.name.clone(),
+                    description: format!("Function has {} lines, consider breaking it down", lines),
+                    severity: rule.severity.clone(),
+                    location: CodeLocation {
+                        file_path: "unknown".to_string(),
+                        line_start: 1,
+                        line_end: lines,
+                        column_start: 1,
+                        column_end: 1,
+                    },
+                    suggestion: "Break the function into smaller, more focused functions"
+                        .to_string(),
+                });
+            }
+            metadata.record(rule, threshold, code_smells.len() - count);
         }
 
-        // Detect deep nesting (more than 4 levels)
-        let nesting_level = self.calculate_nesting_level(code);
-        if nesting_level > 4 {
-            code_smells.push(CodeSmell {
-                name: "Deep Nesting".to_string(),
-                description: format!("Code has {} levels of nesting", nesting_level),
-                severity: Severity::High,
-                location: CodeLocation {
-                    file_path: "unknown".to_string(),
-                    line_start: 1,
-                    line_end: lines,
-                    column_start: 1,
-                    column_end: 1,
-                },
-                suggestion: "Refactor to reduce nesting using early returns or guard clauses"
-                    .to_string(),
-            });
+        // Detect deep nesting
+        if let Some(rule) = catalog.enabled(DEEP_NESTING) {
+            let threshold = rule.threshold_for(language);
+            let count = code_smells.len();
+            let nesting_level = self.calculate_nesting_level(code);
+            if nesting_level as f64 > threshold {
+                code_smells.push(CodeSmell {
+                    rule_id: rule.id.clone(),
+                    name: rule.name.clone(),
+                    description: format!("Code has {} levels of nesting", nesting_level),
+                    severity: rule.severity.clone(),
+                    location: CodeLocation {
+                        file_path: "unknown".to_string(),
+                        line_start: 1,
+                        line_end: lines,
+                        column_start: 1,
+                        column_end: 1,
+                    },
+                    suggestion: "Refactor to reduce nesting using early returns or guard clauses"
+                        .to_string(),
+                });
+            }
+            metadata.record(rule, threshold, code_smells.len() - count);
         }
 
         // Detect duplicate code patterns
-        let duplicates = self.detect_duplicate_code(code);
-        for duplicate in duplicates {
-            code_smells.push(CodeSmell {
-                name: "Duplicate Code".to_string(),
-                description: "Similar code blocks detected".to_string(),
-                severity: Severity::Medium,
-                location: duplicate,
-                suggestion: "Extract common code into a reusable function".to_string(),
-            });
+        if let Some(rule) = catalog.enabled(DUPLICATE_CODE) {
+            let threshold = rule.threshold_for(language);
+            let count = code_smells.len();
+            let duplicates = self.detect_duplicate_code(code);
+            for duplicate in duplicates {
+                if duplicate.length as f64 <= threshold {
+                    continue;
+                }
+                code_smells.push(CodeSmell {
+                    rule_id: rule.id.clone(),
+                    name: rule.name.clone(),
+                    description: "Similar code blocks detected".to_string(),
+                    severity: rule.severity.clone(),
+                    location: duplicate.location,
+                    suggestion: "Extract common code into a reusable function".to_string(),
+                });
+            }
+            metadata.record(rule, threshold, code_smells.len() - count);
         }
 
-        code_smells
+        let (code_smells, suppressed) =
+            metadata.suppress(code_smells, code.as_bytes(), |smell| smell);
+
+        SmellReport {
+            smells: code_smells,
+            suppressed,
+            metadata,
+        }
     }
 
     /// Suggest refactoring opportunities
-    pub fn suggest_refactoring(&self, code: &str) -> Vec<RefactoringSuggestion> {
+    ///
+    /// The blocks worth extracting from the functions longer than the
+    /// [`LONG_FUNCTION`] threshold of the language are found on the
+    /// syntax tree, see [`extraction_candidates`](crate::extraction_candidates).
+    pub fn suggest_refactoring(&self, code: &str, language: LANG) -> Vec<RefactoringSuggestion> {
         let mut suggestions = Vec::new();
 
-        // Suggest extracting long functions
-        let lines = code.lines().count();
-        if lines > 30 {
-            suggestions.push(RefactoringSuggestion {
-                name: "Extract Method".to_string(),
-                description: "Function is too long and should be broken down".to_string(),
-                priority: Priority::High,
-                effort: EffortLevel::Medium,
-                benefits: vec![
-                    "Improved readability".to_string(),
-                    "Better testability".to_string(),
-                    "Reduced complexity".to_string(),
-                ],
-                code_example: "// Extract logic into smaller functions".to_string(),
-            });
-        }
-
-        // Suggest reducing nesting
+        // Suggest extracting blocks of long functions
+        let min_lines = SmellCatalog::default()
+            .get(LONG_FUNCTION)
+            .map_or(50., |rule| rule.threshold_for(Some(language)));
+        let candidates = action::<ExtractionCandidates>(
+            &language,
+            code.as_bytes().to_vec(),
+            Path::new(""),
+            None,
+            min_lines as usize,
+        );
+        suggestions.extend(candidates.iter().map(extract_method));
+
+        // Suggest reducing nesting, with guard clauses when possible
         let nesting_level = self.calculate_nesting_level(code);
-        if nesting_level > 3 {
+        let edits = action::<GuardClauseEdits>(
+            &language,
+            code.as_bytes().to_vec(),
+            Path::new(""),
+            None,
+            (),
+        );
+        if nesting_level > 3 || !edits.is_empty() {
+            let code_example = match edits.first() {
+                Some(edit) => edit.replacement.clone(),
+                None => "// Use early returns or guard clauses".to_string(),
+            };
             suggestions.push(RefactoringSuggestion {
                 name: "Reduce Nesting".to_string(),
                 description: "Deep nesting makes code hard to read and maintain".to_string(),
@@ -275,7 +399,8 @@ impl SemanticAnalyzer {
                     "Easier to test".to_string(),
                     "Reduced cognitive load".to_string(),
                 ],
-                code_example: "// Use early returns or guard clauses".to_string(),
+                code_example,
+                edits,
             });
         }
 
@@ -294,6 +419,7 @@ impl SemanticAnalyzer {
                     "Consistent behavior".to_string(),
                 ],
                 code_example: "// Extract common code into a shared function".to_string(),
+                edits: Vec::new(),
             });
         }
 
@@ -346,7 +472,7 @@ impl SemanticAnalyzer {
     }
 
     /// Detect duplicate code patterns
-    fn detect_duplicate_code(&self, code: &str) -> Vec<CodeLocation> {
+    fn detect_duplicate_code(&self, code: &str) -> Vec<Duplicate> {
         let mut duplicates = Vec::new();
         let lines: Vec<&str> = code.lines().collect();
 
@@ -354,12 +480,15 @@ impl SemanticAnalyzer {
         for i in 0..lines.len() {
             for j in (i + 1)..lines.len() {
                 if lines[i] == lines[j] && !lines[i].trim().is_empty() {
-                    duplicates.push(CodeLocation {
-                        file_path: "unknown".to_string(),
-                        line_start: i + 1,
-                        line_end: i + 1,
-                        column_start: 1,
-                        column_end: lines[i].len(),
+                    duplicates.push(Duplicate {
+                        location: CodeLocation {
+                            file_path: "unknown".to_string(),
+                            line_start: i + 1,
+                            line_end: i + 1,
+                            column_start: 1,
+                            column_end: lines[i].len(),
+                        },
+                        length: lines[i].len(),
                     });
                 }
             }
@@ -429,6 +558,99 @@ mod tests {
         assert!(smells.iter().any(|s| s.name == "Long Function"));
     }
 
+    #[test]
+    fn test_detect_code_smells_with_catalog() {
+        let analyzer = SemanticAnalyzer::new();
+        let code = "fn f() {\n    g();\n    g();\n".to_string() + &"\n".repeat(40) + "}";
+
+        let mut rules = SmellRules::new();
+        rules.insert(
+            LONG_FUNCTION.to_string(),
+            SmellRuleOverride {
+                language_thresholds: HashMap::from([("python".to_string(), 30.0)]),
+                ..Default::default()
+            },
+        );
+        rules.insert(
+            DUPLICATE_CODE.to_string(),
+            SmellRuleOverride {
+                enabled: Some(false),
+                ..Default::default()
+            },
+        );
+        let catalog = SmellCatalog::with_rules(&rules).unwrap();
+
+        let report = analyzer.detect_code_smells_with_catalog(&code, Some(LANG::Rust), &catalog);
+        assert!(report.smells.is_empty());
+
+        let report = analyzer.detect_code_smells_with_catalog(&code, Some(LANG::Python), &catalog);
+        assert_eq!(report.smells.len(), 1);
+        assert_eq!(report.smells[0].rule_id, LONG_FUNCTION);
+
+        let rules_run = report
+            .metadata
+            .rules_run
+            .iter()
+            .map(|run| (run.id.as_str(), run.threshold, run.smells))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rules_run,
+            vec![(LONG_FUNCTION, 30.0, 1), (DEEP_NESTING, 4.0, 0)]
+        );
+        assert_eq!(report.metadata.rules_disabled, vec![DUPLICATE_CODE]);
+
+        // The default catalog reports duplicated lines
+        let smells = analyzer.detect_code_smells(&code);
+        assert!(smells.iter().any(|s| s.rule_id == DUPLICATE_CODE));
+    }
+
+    #[test]
+    fn test_detect_duplicate_code_threshold() {
+        let analyzer = SemanticAnalyzer::new();
+        let code = "    g();\n    g();\n    h(a, b);\n    h(a, b);\n";
+
+        let mut rules = SmellRules::new();
+        rules.insert(
+            DUPLICATE_CODE.to_string(),
+            SmellRuleOverride {
+                threshold: Some(8.0),
+                ..Default::default()
+            },
+        );
+        let catalog = SmellCatalog::with_rules(&rules).unwrap();
+
+        let report = analyzer.detect_code_smells_with_catalog(code, None, &catalog);
+        assert_eq!(report.smells.len(), 1);
+        assert_eq!(report.smells[0].rule_id, DUPLICATE_CODE);
+        assert_eq!(report.smells[0].location.line_start, 3);
+        assert_eq!(report.smells[0].location.column_start, 1);
+        assert_eq!(report.smells[0].location.column_end, 12);
+    }
+
+    #[test]
+    fn test_detect_code_smells_suppressed() {
+        let analyzer = SemanticAnalyzer::new();
+        let code = "fn f() { // sca-ignore: long-function\n".to_string()
+            + &"    g(); // sca-ignore\n".repeat(2)
+            + &"\n".repeat(60)
+            + "}";
+
+        let report =
+            analyzer.detect_code_smells_with_catalog(&code, None, &SmellCatalog::default());
+        assert!(report.smells.is_empty());
+        assert_eq!(report.suppressed.len(), 2);
+        assert_eq!(report.suppressed[0].rule_id, LONG_FUNCTION);
+        assert_eq!(report.suppressed[1].rule_id, DUPLICATE_CODE);
+
+        let duplicate_code = report
+            .metadata
+            .rules_run
+            .iter()
+            .find(|run| run.id == DUPLICATE_CODE)
+            .unwrap();
+        assert_eq!((duplicate_code.smells, duplicate_code.suppressed), (0, 1));
+    }
+
     #[test]
     fn test_suggest_refactoring() {
         let analyzer = SemanticAnalyzer::new();
@@ -445,9 +667,45 @@ mod tests {
             }
         }
         "#;
-        let suggestions = analyzer.suggest_refactoring(nested_code);
+        let suggestions = analyzer.suggest_refactoring(nested_code, LANG::Rust);
 
         assert!(!suggestions.is_empty());
-        assert!(suggestions.iter().any(|s| s.name == "Reduce Nesting"));
+        assert!(suggestions.iter().all(|s| s.name != "Extract Method"));
+
+        let reduce_nesting = suggestions
+            .iter()
+            .find(|s| s.name == "Reduce Nesting")
+            .unwrap();
+        assert_eq!(reduce_nesting.edits.len(), 1);
+        assert!(reduce_nesting.edits[0]
+            .replacement
+            .starts_with("            if !condition1 {\n                return;\n            }\n"));
+    }
+
+    #[test]
+    fn test_suggest_extract_method() {
+        let analyzer = SemanticAnalyzer::new();
+        let mut code = String::from("def f(items):\n    total = 0\n");
+        for i in 0..45 {
+            code.push_str(&format!("    print({i})\n"));
+        }
+        code.push_str(
+            "    for item in items:\n        if item > 0:\n            total += item\n    count = 0\n    for item in items:\n        if item < 0:\n            count += 1\n    return total, count\n",
+        );
+        let suggestions = analyzer.suggest_refactoring(&code, LANG::Python);
+
+        let extractions = suggestions
+            .iter()
+            .filter(|s| s.name == "Extract Method")
+            .collect::<Vec<_>>();
+        assert_eq!(extractions.len(), 2);
+        assert!(extractions[0]
+            .description
+            .starts_with("Extract lines 51-54 of `f`"));
+        assert_eq!(extractions[0].code_example, "count = extracted(items)");
+        assert_eq!(
+            extractions[1].code_example,
+            "total = extracted(items, total)"
+        );
     }
 }
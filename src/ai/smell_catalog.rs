@@ -0,0 +1,336 @@
+//! Configurable Code Smell Catalog
+//!
+//! Every smell detected by the semantic analyzer is a rule of a catalog,
+//! with an id, a default threshold and per-language overrides which can
+//! be changed through a rules file.
+
+use crate::ai::semantic_analyzer::{CodeSmell, Severity};
+use crate::langs::LANG;
+use crate::suppression::Suppressions;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Id of the rule detecting functions with too many lines
+pub const LONG_FUNCTION: &str = "long-function";
+/// Id of the rule detecting too many nested blocks
+pub const DEEP_NESTING: &str = "deep-nesting";
+/// Id of the rule detecting duplicated lines
+pub const DUPLICATE_CODE: &str = "duplicate-code";
+/// Id of the rule detecting the usages of security-sensitive APIs
+pub const DANGEROUS_API: &str = "dangerous-api";
+/// Id of the rule detecting secrets in string literals
+pub const HARDCODED_SECRET: &str = "hardcoded-secret";
+/// Id of the rule detecting regular expressions with a high complexity
+pub const COMPLEX_REGEX: &str = "complex-regex";
+/// Id of the rule detecting regular expressions prone to catastrophic
+/// backtracking
+pub const CATASTROPHIC_REGEX: &str = "catastrophic-regex";
+/// Id of the rule detecting SQL queries built from values
+pub const SQL_INJECTION: &str = "sql-injection";
+/// Id of the rule detecting function parameters which are never read
+pub const UNUSED_PARAMETER: &str = "unused-parameter";
+/// Id of the rule detecting local variables which are never read
+pub const UNUSED_VARIABLE: &str = "unused-variable";
+/// Id of the rule detecting statements which are never executed
+pub const UNREACHABLE_CODE: &str = "unreachable-code";
+/// Id of the rule detecting comments and literals instructing a language
+/// model
+pub const PROMPT_INJECTION: &str = "prompt-injection";
+/// Id of the rule detecting long encoded blobs in comments and literals
+pub const ENCODED_BLOB: &str = "encoded-blob";
+
+/// A code smell rule
+///
+/// A smell is reported when the value measured by a rule is greater
+/// than its threshold.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmellRule {
+    /// The unique id of the rule
+    pub id: String,
+    /// The name of the smell reported by the rule
+    pub name: String,
+    /// If `false`, the rule doesn't run
+    pub enabled: bool,
+    /// The default threshold of the rule
+    pub threshold: f64,
+    /// The thresholds overriding the default one, indexed by
+    /// language name (as returned by `LANG::get_name`)
+    #[serde(default)]
+    pub language_thresholds: HashMap<String, f64>,
+    /// The severity of the reported smells
+    pub severity: Severity,
+}
+
+impl SmellRule {
+    fn new(id: &str, name: &str, threshold: f64, severity: Severity) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            enabled: true,
+            threshold,
+            language_thresholds: HashMap::new(),
+            severity,
+        }
+    }
+
+    /// Get the threshold of the rule for a language
+    pub fn threshold_for(&self, language: Option<LANG>) -> f64 {
+        language
+            .and_then(|language| self.language_thresholds.get(language.get_name()))
+            .copied()
+            .unwrap_or(self.threshold)
+    }
+}
+
+/// The changes made by a rules file to a rule of the catalog
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SmellRuleOverride {
+    /// Enables or disables the rule
+    pub enabled: Option<bool>,
+    /// Replaces the default threshold of the rule
+    pub threshold: Option<f64>,
+    /// Adds or replaces per-language thresholds
+    pub language_thresholds: HashMap<String, f64>,
+    /// Replaces the severity of the rule
+    pub severity: Option<Severity>,
+}
+
+/// The content of a rules file, indexed by rule id
+///
+/// # Examples
+///
+/// A rules file in `JSON` looks like:
+///
+/// ```json
+/// {
+///   "long-function": { "threshold": 80, "language_thresholds": { "python": 40 } },
+///   "duplicate-code": { "enabled": false }
+/// }
+/// ```
+pub type SmellRules = HashMap<String, SmellRuleOverride>;
+
+/// The catalog of code smell rules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmellCatalog {
+    /// The rules of the catalog
+    pub rules: Vec<SmellRule>,
+}
+
+impl Default for SmellCatalog {
+    fn default() -> Self {
+        Self {
+            rules: vec![
+                SmellRule::new(LONG_FUNCTION, "Long Function", 50.0, Severity::Medium),
+                SmellRule::new(DEEP_NESTING, "Deep Nesting", 4.0, Severity::High),
+                // The measured value is the length of a duplicated line
+                SmellRule::new(DUPLICATE_CODE, "Duplicate Code", 0.0, Severity::Medium),
+                // Every usage is reported, see `dangerous_api_usages`
+                SmellRule::new(DANGEROUS_API, "Dangerous API", 0.0, Severity::High),
+                // The measured value is the entropy of a literal, see `find_secrets`
+                SmellRule::new(
+                    HARDCODED_SECRET,
+                    "Hardcoded Secret",
+                    4.0,
+                    Severity::Critical,
+                ),
+                // The measured value is the score of a regex, see `find_regexes`
+                SmellRule::new(COMPLEX_REGEX, "Complex Regex", 15.0, Severity::Medium),
+                // The measured value is the number of nested quantifiers of a regex
+                SmellRule::new(
+                    CATASTROPHIC_REGEX,
+                    "Catastrophic Regex",
+                    0.0,
+                    Severity::High,
+                ),
+                // Every query built from values is reported, see `find_sql_injections`
+                SmellRule::new(SQL_INJECTION, "SQL Injection", 0.0, Severity::High),
+                // Every binding which is never read is reported, see `find_unused`
+                SmellRule::new(UNUSED_PARAMETER, "Unused Parameter", 0.0, Severity::Low),
+                SmellRule::new(UNUSED_VARIABLE, "Unused Variable", 0.0, Severity::Low),
+                // Every block which is never executed is reported, see `find_unreachable`
+                SmellRule::new(UNREACHABLE_CODE, "Unreachable Code", 0.0, Severity::Medium),
+                // Every suspicious text is reported, see `find_suspicious_content`
+                SmellRule::new(PROMPT_INJECTION, "Prompt Injection", 0.0, Severity::High),
+                // The measured value is the length of a blob
+                SmellRule::new(ENCODED_BLOB, "Encoded Blob", 256.0, Severity::Medium),
+            ],
+        }
+    }
+}
+
+impl SmellCatalog {
+    /// Create the default catalog changed by a rules file
+    ///
+    /// Returns the ids of the rules file which match no rule as an error.
+    pub fn with_rules(rules: &SmellRules) -> Result<Self, Vec<String>> {
+        let mut catalog = Self::default();
+        catalog.apply(rules)?;
+        Ok(catalog)
+    }
+
+    /// Apply the changes of a rules file to the catalog
+    ///
+    /// Returns the ids of the rules file which match no rule as an error,
+    /// the other changes are applied anyway.
+    pub fn apply(&mut self, rules: &SmellRules) -> Result<(), Vec<String>> {
+        let mut unknown = Vec::new();
+        for (id, changes) in rules {
+            let Some(rule) = self.rules.iter_mut().find(|rule| rule.id == *id) else {
+                unknown.push(id.clone());
+                continue;
+            };
+            if let Some(enabled) = changes.enabled {
+                rule.enabled = enabled;
+            }
+            if let Some(threshold) = changes.threshold {
+                rule.threshold = threshold;
+            }
+            if let Some(severity) = &changes.severity {
+                rule.severity = severity.clone();
+            }
+            rule.language_thresholds.extend(
+                changes
+                    .language_thresholds
+                    .iter()
+                    .map(|(language, threshold)| (language.clone(), *threshold)),
+            );
+        }
+        if unknown.is_empty() {
+            Ok(())
+        } else {
+            unknown.sort();
+            Err(unknown)
+        }
+    }
+
+    /// Get a rule by id
+    pub fn get(&self, id: &str) -> Option<&SmellRule> {
+        self.rules.iter().find(|rule| rule.id == id)
+    }
+
+    /// Get the rule with the given id if it is enabled
+    pub(crate) fn enabled(&self, id: &str) -> Option<&SmellRule> {
+        self.get(id).filter(|rule| rule.enabled)
+    }
+}
+
+/// A rule which ran during a smell detection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleRun {
+    /// The id of the rule
+    pub id: String,
+    /// The threshold used for the analyzed language
+    pub threshold: f64,
+    /// The number of smells reported by the rule
+    pub smells: usize,
+    /// The number of smells silenced by a suppression comment
+    #[serde(default)]
+    pub suppressed: usize,
+}
+
+/// The metadata of a smell detection
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SmellMetadata {
+    /// The rules which ran
+    pub rules_run: Vec<RuleRun>,
+    /// The ids of the disabled rules
+    pub rules_disabled: Vec<String>,
+}
+
+impl SmellMetadata {
+    /// Creates the metadata of a detection with the rules of a catalog,
+    /// listing its disabled rules
+    pub(crate) fn for_catalog(catalog: &SmellCatalog) -> Self {
+        Self {
+            rules_run: Vec::new(),
+            rules_disabled: catalog
+                .rules
+                .iter()
+                .filter(|rule| !rule.enabled)
+                .map(|rule| rule.id.clone())
+                .collect(),
+        }
+    }
+
+    pub(crate) fn record(&mut self, rule: &SmellRule, threshold: f64, smells: usize) {
+        self.rules_run.push(RuleRun {
+            id: rule.id.clone(),
+            threshold,
+            smells,
+            suppressed: 0,
+        });
+    }
+
+    // Splits the findings into the reported ones and the ones silenced by
+    // the suppression comments of a code, which are taken out of the
+    // smells of the rules which ran
+    pub(crate) fn suppress<F>(
+        &mut self,
+        found: Vec<F>,
+        code: &[u8],
+        smell: fn(&F) -> &CodeSmell,
+    ) -> (Vec<F>, Vec<F>) {
+        let suppressions = Suppressions::new(code);
+        let (suppressed, found): (Vec<_>, Vec<_>) = found.into_iter().partition(|found| {
+            let smell = smell(found);
+            suppressions.is_suppressed(smell.location.line_start, &smell.rule_id)
+        });
+        for run in self.rules_run.iter_mut() {
+            run.suppressed = suppressed
+                .iter()
+                .filter(|found| smell(found).rule_id == run.id)
+                .count();
+            run.smells -= run.suppressed;
+        }
+        (found, suppressed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_catalog() {
+        let catalog = SmellCatalog::default();
+
+        assert_eq!(catalog.get(LONG_FUNCTION).unwrap().threshold, 50.0);
+        assert_eq!(catalog.get(DEEP_NESTING).unwrap().threshold, 4.0);
+        assert!(catalog.rules.iter().all(|rule| rule.enabled));
+    }
+
+    #[test]
+    fn test_rules_overrides() {
+        let mut rules = SmellRules::new();
+        rules.insert(
+            LONG_FUNCTION.to_string(),
+            SmellRuleOverride {
+                threshold: Some(80.0),
+                language_thresholds: HashMap::from([("python".to_string(), 40.0)]),
+                ..Default::default()
+            },
+        );
+        rules.insert(
+            DUPLICATE_CODE.to_string(),
+            SmellRuleOverride {
+                enabled: Some(false),
+                ..Default::default()
+            },
+        );
+
+        let catalog = SmellCatalog::with_rules(&rules).unwrap();
+        let long_function = catalog.get(LONG_FUNCTION).unwrap();
+
+        assert_eq!(long_function.threshold_for(None), 80.0);
+        assert_eq!(long_function.threshold_for(Some(LANG::Rust)), 80.0);
+        assert_eq!(long_function.threshold_for(Some(LANG::Python)), 40.0);
+        assert!(catalog.enabled(DUPLICATE_CODE).is_none());
+
+        rules.insert("unknown-rule".to_string(), SmellRuleOverride::default());
+        assert_eq!(
+            SmellCatalog::with_rules(&rules).unwrap_err(),
+            vec!["unknown-rule".to_string()]
+        );
+    }
+}
@@ -0,0 +1,327 @@
+use std::{fmt, path::Path, str::FromStr, time::Duration};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::code_analyzer::AnalyzeOptions;
+use crate::concurrent_files::{FailurePolicy, MemoryBudget};
+use crate::facts::{facts, FunctionFacts};
+use crate::risk::RiskPolicy;
+use crate::similarity::SimilarityIndex;
+use crate::spaces::{FuncSpace, SpaceGranularity};
+use crate::traits::ParserTrait;
+
+/// A predefined analysis profile.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Profile {
+    /// A fast analysis for the editors, analyzing a file on each change:
+    /// no Halstead metrics, small files and short timeouts
+    Ide,
+    /// The analysis of the continuous integration, with all the metrics
+    #[default]
+    Ci,
+    /// A thorough analysis, with the duplicated functions, the call
+    /// graphs and the closures, and without limits
+    Deep,
+}
+
+impl Profile {
+    /// Returns the name of the profile.
+    pub fn name(self) -> &'static str {
+        match self {
+            Profile::Ide => "ide",
+            Profile::Ci => "ci",
+            Profile::Deep => "deep",
+        }
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Profile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ide" | "fast" => Ok(Self::Ide),
+            "ci" => Ok(Self::Ci),
+            "deep" | "full" => Ok(Self::Deep),
+            _ => Err(format!("unknown analysis profile: {s}")),
+        }
+    }
+}
+
+// The metrics which don't need the operators and operands of the code,
+// `mi` being computed from the Halstead volume
+const FAST_METRICS: [&str; 7] = [
+    "nargs",
+    "nexits",
+    "cognitive",
+    "cyclomatic",
+    "loc",
+    "nom",
+    "abc",
+];
+
+/// The configuration of an analysis: its metrics, its limits and the
+/// verbosity of its output.
+///
+/// # Examples
+///
+/// ```
+/// use singularity_code_analysis::{AnalysisConfig, Profile};
+///
+/// let config = AnalysisConfig::profile(Profile::Ide);
+/// assert!(!config.is_metric_enabled("halstead"));
+/// assert!(config.timeout.is_some());
+/// ```
+#[derive(Clone, Debug, Serialize)]
+pub struct AnalysisConfig {
+    /// The profile the configuration was created from
+    pub profile: Profile,
+    /// The enabled metrics, as named in the results, all of them if
+    /// `None`
+    pub metrics: Option<Vec<String>>,
+    /// The thresholds of the metrics
+    pub thresholds: RiskPolicy,
+    /// The largest file analyzed, in bytes
+    pub max_file_bytes: Option<u64>,
+    /// The longest time a file is analyzed
+    pub timeout: Option<Duration>,
+    /// The deepest function spaces listed in the results
+    pub granularity: SpaceGranularity,
+    /// Whether the node kinds which no checker or getter handled are
+    /// recorded
+    pub coverage: bool,
+    /// Whether the duplicated functions are searched, the functions being
+    /// indexed by [`index_functions`](Self::index_functions)
+    pub duplication: bool,
+    /// Whether the calls of the functions are collected by
+    /// [`function_facts`](Self::function_facts)
+    pub call_graph: bool,
+}
+
+impl Default for AnalysisConfig {
+    fn default() -> Self {
+        Self::profile(Profile::default())
+    }
+}
+
+impl AnalysisConfig {
+    /// Creates the configuration of a profile.
+    pub fn profile(profile: Profile) -> Self {
+        match profile {
+            Profile::Ide => Self {
+                profile,
+                metrics: Some(FAST_METRICS.iter().map(|name| name.to_string()).collect()),
+                thresholds: RiskPolicy::default(),
+                max_file_bytes: Some(1 << 20),
+                timeout: Some(Duration::from_secs(2)),
+                granularity: SpaceGranularity::Function,
+                coverage: false,
+                duplication: false,
+                call_graph: false,
+            },
+            Profile::Ci => Self {
+                profile,
+                metrics: None,
+                thresholds: RiskPolicy::default(),
+                max_file_bytes: Some(10 << 20),
+                timeout: Some(Duration::from_secs(30)),
+                granularity: SpaceGranularity::Function,
+                coverage: false,
+                duplication: false,
+                call_graph: false,
+            },
+            Profile::Deep => Self {
+                profile,
+                metrics: None,
+                thresholds: RiskPolicy::default(),
+                max_file_bytes: None,
+                timeout: None,
+                granularity: SpaceGranularity::Closure,
+                coverage: true,
+                duplication: true,
+                call_graph: true,
+            },
+        }
+    }
+
+    /// Returns `true` if a metric is enabled.
+    pub fn is_metric_enabled(&self, metric: &str) -> bool {
+        self.metrics
+            .as_ref()
+            .is_none_or(|metrics| metrics.iter().any(|enabled| enabled == metric))
+    }
+
+    /// Returns `true` if a file of this size is analyzed.
+    pub fn accepts_size(&self, bytes: u64) -> bool {
+        self.max_file_bytes.is_none_or(|max| bytes <= max)
+    }
+
+    /// Returns the options of the [`SingularityCodeAnalyzer`] requests,
+    /// which skip the Halstead metrics if neither them nor the
+    /// maintainability index are enabled.
+    ///
+    /// [`SingularityCodeAnalyzer`]: crate::SingularityCodeAnalyzer
+    pub fn analyze_options(&self) -> AnalyzeOptions<'static> {
+        AnalyzeOptions {
+            granularity: self.granularity,
+            coverage: self.coverage,
+            skip_halstead: !self.is_metric_enabled("halstead") && !self.is_metric_enabled("mi"),
+            ..Default::default()
+        }
+    }
+
+    /// Indexes the functions of a parsed code, to search the duplicated
+    /// ones, returning `false` if the duplication isn't searched.
+    pub fn index_functions<T: ParserTrait>(
+        &self,
+        index: &mut SimilarityIndex,
+        parser: &T,
+        path: &Path,
+    ) -> bool {
+        if self.duplication {
+            index.add(parser, path);
+        }
+        self.duplication
+    }
+
+    /// Returns the facts of each function of a parsed code, with their
+    /// calls only if the call graph is enabled.
+    pub fn function_facts<T: ParserTrait>(&self, parser: &T, path: &Path) -> Vec<FunctionFacts> {
+        facts(parser, path, self.call_graph)
+    }
+
+    /// Returns the failure policy of the [`ConcurrentRunner`].
+    ///
+    /// [`ConcurrentRunner`]: crate::ConcurrentRunner
+    pub fn failure_policy(&self) -> FailurePolicy {
+        FailurePolicy {
+            retries: 0,
+            timeout: self.timeout,
+        }
+    }
+
+    /// Returns the memory budget of the [`ConcurrentRunner`], holding four
+    /// of the largest files in flight.
+    ///
+    /// The budget doesn't skip the files larger than the limit, which are
+    /// filtered with [`accepts_size`](Self::accepts_size).
+    ///
+    /// [`ConcurrentRunner`]: crate::ConcurrentRunner
+    pub fn memory_budget(&self) -> MemoryBudget {
+        MemoryBudget {
+            max_bytes: self.max_file_bytes.map(|max| max.saturating_mul(4)),
+            max_files: None,
+        }
+    }
+
+    /// Serializes a function space as `JSON`, with only the enabled
+    /// metrics.
+    pub fn to_json(&self, space: &FuncSpace) -> serde_json::Result<Value> {
+        let mut json = serde_json::to_value(space)?;
+        if self.metrics.is_some() {
+            self.retain_metrics(&mut json);
+        }
+        Ok(json)
+    }
+
+    fn retain_metrics(&self, space: &mut Value) {
+        if let Some(Value::Object(metrics)) = space.get_mut("metrics") {
+            metrics.retain(|name, _| self.is_metric_enabled(name));
+        }
+        if let Some(Value::Array(spaces)) = space.get_mut("spaces") {
+            for space in spaces {
+                self.retain_metrics(space);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{metrics, ParserEngineRust, SingularityCodeAnalyzer, LANG};
+
+    use super::*;
+
+    #[test]
+    fn profiles() {
+        for profile in [Profile::Ide, Profile::Ci, Profile::Deep] {
+            assert_eq!(profile.name().parse(), Ok(profile));
+            assert_eq!(AnalysisConfig::profile(profile).profile, profile);
+        }
+        assert!("slow".parse::<Profile>().is_err());
+
+        let ide = AnalysisConfig::profile(Profile::Ide);
+        assert!(!ide.is_metric_enabled("halstead") && !ide.is_metric_enabled("mi"));
+        assert!(ide.accepts_size(1000) && !ide.accepts_size(2 << 20));
+        assert_eq!(ide.failure_policy().timeout, Some(Duration::from_secs(2)));
+
+        let deep = AnalysisConfig::profile(Profile::Deep);
+        assert!(deep.is_metric_enabled("halstead"));
+        assert!(deep.duplication && deep.call_graph && deep.analyze_options().coverage);
+        assert!(deep.accepts_size(u64::MAX));
+        assert_eq!(deep.memory_budget().max_bytes, None);
+    }
+
+    #[test]
+    fn enabled_metrics_output() {
+        let path = Path::new("foo.rs");
+        let parser = ParserEngineRust::new(b"fn f(a: u32) -> u32 { a + 1 }\n".to_vec(), path, None);
+        let space = metrics(&parser, path).unwrap();
+
+        let json = AnalysisConfig::profile(Profile::Ide)
+            .to_json(&space)
+            .unwrap();
+        assert!(json["metrics"].get("halstead").is_none());
+        assert!(json["metrics"].get("cyclomatic").is_some());
+        assert!(json["spaces"][0]["metrics"].get("halstead").is_none());
+
+        let json = AnalysisConfig::profile(Profile::Ci)
+            .to_json(&space)
+            .unwrap();
+        assert!(json["spaces"][0]["metrics"].get("halstead").is_some());
+    }
+
+    #[test]
+    fn disabled_passes() {
+        let path = Path::new("foo.rs");
+        let code = b"fn f(a: u32) -> u32 { g(a) + 1 }\n";
+        let parser = ParserEngineRust::new(code.to_vec(), path, None);
+
+        let ide = AnalysisConfig::profile(Profile::Ide);
+        let mut index = SimilarityIndex::new();
+        assert!(!ide.index_functions(&mut index, &parser, path));
+        assert!(index.is_empty());
+        let facts = ide.function_facts(&parser, path);
+        assert!(facts[0].calls.is_empty());
+
+        let result = SingularityCodeAnalyzer::new()
+            .analyze_language(LANG::Rust, code, ide.analyze_options())
+            .unwrap();
+        let metrics = &result.root_space.spaces[0].metrics;
+        assert_eq!(metrics.halstead.length(), 0.);
+        assert!(metrics.is_suppressed("halstead") && metrics.is_suppressed("mi"));
+        assert_eq!(metrics.cyclomatic.cyclomatic(), 1.);
+
+        let deep = AnalysisConfig::profile(Profile::Deep);
+        assert!(deep.index_functions(&mut index, &parser, path));
+        assert_eq!(index.len(), 1);
+        assert_eq!(deep.function_facts(&parser, path)[0].calls, ["g"]);
+
+        let result = SingularityCodeAnalyzer::new()
+            .analyze_language(LANG::Rust, code, deep.analyze_options())
+            .unwrap();
+        let metrics = &result.root_space.spaces[0].metrics;
+        assert!(metrics.halstead.length() > 0.);
+        assert!(!metrics.is_suppressed("halstead"));
+    }
+}
@@ -61,19 +61,39 @@ where
 /// Compare the textual content of a node with any of the provided keywords.
 pub(crate) fn node_text_equals_any(node: &crate::Node, keywords: &[&str]) -> bool {
     with_current_code(|code| {
-        let start = node.start_byte();
-        let end = node.end_byte();
-        if end <= code.len() {
-            let text = &code[start..end];
-            keywords.iter().any(|kw| text == kw.as_bytes())
-        } else {
-            false
-        }
+        code.get(node.start_byte()..node.end_byte())
+            .is_some_and(|text| keywords.iter().any(|kw| text == kw.as_bytes()))
     })
     .unwrap_or(false)
 }
 
 /// Helper to fetch the textual content of a node as UTF-8 when a code context is active.
 pub(crate) fn node_text<'a>(node: &crate::Node, code: &'a [u8]) -> Option<&'a str> {
-    std::str::from_utf8(&code[node.start_byte()..node.end_byte()]).ok()
+    text_at(code, node.start_byte(), node.end_byte())
+}
+
+/// Fetch the text of a byte range of the code as UTF-8.
+///
+/// Returns `None` instead of panicking when the range is out of the code,
+/// e.g. for a node of a tree built from another version of it, or when
+/// it splits a multi-byte character.
+pub(crate) fn text_at(code: &[u8], start: usize, end: usize) -> Option<&str> {
+    code.get(start..end)
+        .and_then(|text| std::str::from_utf8(text).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_at_multi_byte() {
+        let code = "let caf\u{e9} = \"\u{1F600}\";".as_bytes();
+
+        assert_eq!(text_at(code, 4, 9), Some("caf\u{e9}"));
+        assert_eq!(text_at(code, 13, 17), Some("\u{1F600}"));
+        // Inside the emoji
+        assert_eq!(text_at(code, 13, 15), None);
+        assert_eq!(text_at(code, 4, code.len() + 1), None);
+    }
 }
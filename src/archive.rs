@@ -0,0 +1,116 @@
+use std::{
+    fs::File,
+    io::{self, Read},
+    path::{Component, Path, PathBuf},
+};
+
+use flate2::read::GzDecoder;
+
+// Entries larger than this are skipped, to bound the memory used by
+// an archive
+const MAX_ENTRY_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Returns `true` if the file is an archive whose sources can be analyzed.
+pub(crate) fn is_archive(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    let name = name.to_lowercase();
+    [".zip", ".tar", ".tar.gz", ".tgz"]
+        .iter()
+        .any(|ext| name.ends_with(ext))
+}
+
+// The path of an entry inside the archive, rejecting the entries
+// escaping it, as `../x` or `/x`
+fn entry_path(archive: &Path, entry: &Path) -> Option<PathBuf> {
+    entry
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+        .then(|| archive.join(entry))
+}
+
+/// Calls `func` with the path and the content of each file of an archive.
+///
+/// The path of an entry is the path of the archive followed by the path
+/// of the entry inside it, e.g. `src.zip/lib/a.rs`.
+pub(crate) fn for_each_entry<F>(path: &Path, mut func: F) -> io::Result<()>
+where
+    F: FnMut(PathBuf, Vec<u8>) -> io::Result<()>,
+{
+    let file = File::open(path)?;
+    let name = path.to_string_lossy().to_lowercase();
+
+    if name.ends_with(".zip") {
+        let mut archive = zip::ZipArchive::new(file).map_err(io::Error::other)?;
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i).map_err(io::Error::other)?;
+            if !entry.is_file() || entry.size() > MAX_ENTRY_BYTES {
+                continue;
+            }
+            let Some(entry_path) = entry
+                .enclosed_name()
+                .and_then(|name| entry_path(path, &name))
+            else {
+                continue;
+            };
+            let mut data = Vec::with_capacity(entry.size() as usize);
+            entry.read_to_end(&mut data)?;
+            func(entry_path, data)?;
+        }
+        return Ok(());
+    }
+
+    let reader: Box<dyn Read> = if name.ends_with(".tar") {
+        Box::new(file)
+    } else {
+        Box::new(GzDecoder::new(file))
+    };
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() || entry.size() > MAX_ENTRY_BYTES {
+            continue;
+        }
+        let Some(entry_path) = entry_path(path, &entry.path()?) else {
+            continue;
+        };
+        let mut data = Vec::with_capacity(entry.size() as usize);
+        entry.read_to_end(&mut data)?;
+        func(entry_path, data)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn archive_entries() {
+        assert!(is_archive(Path::new("src.tar.gz")));
+        assert!(is_archive(Path::new("SRC.ZIP")));
+        assert!(!is_archive(Path::new("gzip.rs")));
+
+        let path = std::env::temp_dir().join("sca_archive_entries.zip");
+        let mut zip = zip::ZipWriter::new(File::create(&path).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        zip.add_directory("lib/", options).unwrap();
+        zip.start_file("lib/a.rs", options).unwrap();
+        zip.write_all(b"fn a() {}\n").unwrap();
+        zip.start_file("../escape.rs", options).unwrap();
+        zip.write_all(b"fn b() {}\n").unwrap();
+        zip.finish().unwrap();
+
+        let mut entries = Vec::new();
+        for_each_entry(&path, |entry, data| {
+            entries.push((entry, data));
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(entries, [(path.join("lib/a.rs"), b"fn a() {}\n".to_vec())]);
+    }
+}
@@ -3,7 +3,7 @@ use serde::{
     Deserialize, Serialize,
 };
 
-use crate::*;
+use crate::{position::SourceMap, *};
 
 /// Start and end positions of a node in a code in terms of rows and columns.
 ///
@@ -28,6 +28,9 @@ pub struct AstPayload {
     /// If `true`, the start and end positions of a node in a code
     /// are considered
     pub span: bool,
+    /// The unit used to count the columns of a span
+    #[serde(default)]
+    pub offset_encoding: OffsetEncoding,
 }
 
 /// The response of an `AST` request.
@@ -77,6 +80,16 @@ impl AstNode {
             children,
         }
     }
+
+    fn set_offset_encoding(&mut self, lines: &SourceMap, encoding: OffsetEncoding) {
+        if let Some((start_row, start_column, end_row, end_column)) = self.span.as_mut() {
+            *start_column = lines.column(*start_row, *start_column, encoding);
+            *end_column = lines.column(*end_row, *end_column, encoding);
+        }
+        for child in self.children.iter_mut() {
+            child.set_offset_encoding(lines, encoding);
+        }
+    }
 }
 
 fn build<T: ParserTrait>(parser: &T, span: bool, comment: bool) -> Option<AstNode> {
@@ -139,6 +152,8 @@ pub struct AstCfg {
     /// If `true`, the start and end positions of a node in a code
     /// are considered
     pub span: bool,
+    /// The unit used to count the columns of a span
+    pub offset_encoding: OffsetEncoding,
 }
 
 impl Callback for AstCallback {
@@ -146,9 +161,13 @@ impl Callback for AstCallback {
     type Cfg = AstCfg;
 
     fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
-        AstResponse {
-            id: cfg.id,
-            root: build(parser, cfg.span, cfg.comment),
+        let mut root = build(parser, cfg.span, cfg.comment);
+        if cfg.span && cfg.offset_encoding != OffsetEncoding::Utf8 {
+            let lines = SourceMap::new(parser.get_code());
+            if let Some(root) = root.as_mut() {
+                root.set_offset_encoding(&lines, cfg.offset_encoding);
+            }
         }
+        AstResponse { id: cfg.id, root }
     }
 }
@@ -0,0 +1,174 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+
+use crate::{history::Commit, spaces::FuncSpace};
+
+/// The authorship of a file in a git history.
+///
+/// The contribution of an author is the number of lines they added and
+/// removed, or their number of commits for files without line counts,
+/// like binary files. Authors are identified by their email.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FileOwnership {
+    /// The number of authors who changed the file
+    pub contributors: usize,
+    /// The name of the author with the largest contribution
+    pub owner: String,
+    /// The share of the contributions of the owner, between 0 and 1
+    pub owner_share: f64,
+    /// The smallest number of authors whose contributions are more than
+    /// half of the total
+    pub bus_factor: usize,
+}
+
+#[derive(Default)]
+struct Contributions<'a> {
+    // The name and the contribution of each email
+    authors: HashMap<&'a str, (&'a str, usize, usize)>,
+}
+
+impl<'a> Contributions<'a> {
+    fn add(&mut self, commit: &'a Commit, lines: usize) {
+        let author =
+            self.authors
+                .entry(commit.email.as_str())
+                .or_insert((commit.author.as_str(), 0, 0));
+        author.1 += lines;
+        author.2 += 1;
+    }
+
+    fn ownership(&self) -> Option<FileOwnership> {
+        let by_lines = self.authors.values().any(|(_, lines, _)| *lines > 0);
+        let mut shares = self
+            .authors
+            .values()
+            .map(|(name, lines, commits)| (*name, if by_lines { *lines } else { *commits }))
+            .collect::<Vec<_>>();
+        // The names break the ties, so the result doesn't depend on the hashing
+        shares.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+
+        let total = shares.iter().map(|(_, share)| share).sum::<usize>();
+        let (owner, owner_lines) = *shares.first()?;
+        let mut covered = 0;
+        let bus_factor = shares
+            .iter()
+            .take_while(|(_, share)| {
+                let more_than_half = 2 * covered > total;
+                covered += share;
+                !more_than_half
+            })
+            .count();
+
+        Some(FileOwnership {
+            contributors: shares.len(),
+            owner: owner.to_string(),
+            owner_share: owner_lines as f64 / total.max(1) as f64,
+            bus_factor,
+        })
+    }
+}
+
+/// Returns the authorship of the files changed by some commits, keyed
+/// by their paths relative to the root of the repository.
+pub fn file_ownership(commits: &[Commit]) -> HashMap<PathBuf, FileOwnership> {
+    let mut files: HashMap<&Path, Contributions> = HashMap::new();
+    for commit in commits {
+        for change in commit.changes.iter() {
+            files
+                .entry(change.path.as_path())
+                .or_default()
+                .add(commit, change.added + change.removed);
+        }
+    }
+
+    files
+        .into_iter()
+        .filter_map(|(path, contributions)| Some((path.to_path_buf(), contributions.ownership()?)))
+        .collect()
+}
+
+/// A complex file known by a single author.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct OwnershipRisk {
+    /// The path of the file
+    pub path: PathBuf,
+    /// The cyclomatic complexity of the file
+    pub cyclomatic: f64,
+    /// The authorship of the file
+    pub ownership: FileOwnership,
+}
+
+/// Returns the files more complex than `max_cyclomatic` with a bus
+/// factor of one, by decreasing complexity.
+///
+/// The paths of the files are made relative to `root` to look up their
+/// authorship, and files missing from it are ignored.
+pub fn ownership_risks(
+    files: &[(PathBuf, FuncSpace)],
+    ownership: &HashMap<PathBuf, FileOwnership>,
+    root: &Path,
+    max_cyclomatic: f64,
+) -> Vec<OwnershipRisk> {
+    let mut risks = files
+        .iter()
+        .filter_map(|(path, space)| {
+            let relative = path.strip_prefix(root).unwrap_or(path);
+            let ownership = ownership.get(relative)?;
+            let cyclomatic = space.metrics.cyclomatic.cyclomatic_sum();
+            (ownership.bus_factor == 1 && cyclomatic > max_cyclomatic).then(|| OwnershipRisk {
+                path: path.clone(),
+                cyclomatic,
+                ownership: ownership.clone(),
+            })
+        })
+        .collect::<Vec<_>>();
+    risks.sort_by(|a, b| b.cyclomatic.total_cmp(&a.cyclomatic));
+    risks
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{metrics, parse_git_log, ParserEngineRust, ParserTrait};
+
+    use super::*;
+
+    #[test]
+    fn ownership() {
+        let log = "\x1eAda\x1fada@example.com\n\n30\t0\tsrc/a.rs\n10\t0\tsrc/b.rs\n\
+                   \x1eBob\x1fbob@example.com\n\n5\t5\tsrc/a.rs\n10\t0\tsrc/b.rs\n\
+                   \x1eCyd\x1fcyd@example.com\n\n10\t0\tsrc/b.rs\n\
+                   \x1eAda\x1fada@example.com\n\n-\t-\tlogo.png\n";
+        let ownership = file_ownership(&parse_git_log(log));
+
+        let a = &ownership[Path::new("src/a.rs")];
+        assert_eq!(a.contributors, 2);
+        assert_eq!(a.owner, "Ada");
+        assert_eq!(a.owner_share, 0.75);
+        assert_eq!(a.bus_factor, 1);
+
+        let b = &ownership[Path::new("src/b.rs")];
+        assert_eq!(b.contributors, 3);
+        assert_eq!(b.bus_factor, 2);
+
+        let logo = &ownership[Path::new("logo.png")];
+        assert_eq!((logo.owner.as_str(), logo.owner_share), ("Ada", 1.));
+
+        let branchy = "fn f(x: i32) {\n    if x > 0 {}\n    if x > 1 {}\n    if x > 2 {}\n}\n";
+        let files = [
+            PathBuf::from("repo/src/a.rs"),
+            PathBuf::from("repo/src/b.rs"),
+        ]
+        .map(|path| {
+            let parser = ParserEngineRust::new(branchy.as_bytes().to_vec(), &path, None);
+            let space = metrics(&parser, &path).unwrap();
+            (path, space)
+        });
+        let risks = ownership_risks(&files, &ownership, Path::new("repo"), 3.);
+        assert_eq!(risks.len(), 1);
+        assert_eq!(risks[0].path, PathBuf::from("repo/src/a.rs"));
+    }
+}
@@ -19,7 +19,7 @@ mod tests {
             let root = parser.get_root();
             if debug || root.has_error() {
                 eprintln!("Sample (CPP) {n}: {sample}");
-                dump_node(&v_sample, &root, -1, None, None).unwrap();
+                dump_node(&v_sample, &root, -1, None, None, OffsetEncoding::Utf8).unwrap();
             }
             assert!(!root.has_error());
         }
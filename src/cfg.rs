@@ -0,0 +1,978 @@
+use std::{marker::PhantomData, path::Path};
+
+use serde::Serialize;
+
+use crate::{
+    ai::{CodeLocation, CodeSmell, SmellCatalog, SmellMetadata, SmellReport, UNREACHABLE_CODE},
+    checker::Checker,
+    getter::Getter,
+    langs::{action, LANG},
+    node::Node,
+    traits::*,
+};
+
+/// The kind of a basic block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BlockKind {
+    /// The entry of the function
+    Entry,
+    /// The exit of the function, reached by the returns and the uncaught
+    /// throws
+    Exit,
+    /// A sequence of statements
+    Statements,
+    /// The condition of a branch, the head of a loop or the value of a
+    /// switch
+    Condition,
+}
+
+/// A basic block of a control-flow graph: statements which are always
+/// executed together.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct BasicBlock {
+    /// The index of the block in the graph
+    pub id: usize,
+    /// The kind of the block
+    pub kind: BlockKind,
+    /// The number of statements of the block
+    pub statements: usize,
+    /// The first line of the block
+    pub start_line: usize,
+    /// The last line of the block
+    pub end_line: usize,
+}
+
+/// The kind of an edge.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EdgeKind {
+    /// The control flows to the next block
+    Normal,
+    /// The condition is true, or the loop iterates
+    True,
+    /// The condition is false, or the loop ends
+    False,
+    /// A case of a switch is taken
+    Case,
+    /// An exception is caught
+    Exception,
+}
+
+/// An edge of a control-flow graph.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Edge {
+    /// The block the control flows from
+    pub from: usize,
+    /// The block the control flows to
+    pub to: usize,
+    /// The kind of the edge
+    pub kind: EdgeKind,
+}
+
+/// The role of a node in the control flow of a function.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlowKind {
+    /// A branch, like `if`
+    If,
+    /// A branch following the first one of an `if`, like Python `elif`
+    ElseIf,
+    /// The `else` of a branch, of a loop or of a `try`
+    Else,
+    /// A loop which checks its condition first, like `while` or `for`
+    Loop,
+    /// A loop without a condition, like Rust `loop`
+    InfiniteLoop,
+    /// A loop which checks its condition last, like `do ... while`
+    DoLoop,
+    /// A switch, whose cases are left by a `break`
+    Switch,
+    /// A match, whose cases aren't left by a `break`, like Rust and Python
+    /// `match`
+    Match,
+    /// A case of a switch which doesn't run into the next case
+    Case,
+    /// A case of a switch which runs into the next case without a
+    /// `break`, as in C
+    FallthroughCase,
+    /// An arm of a Rust `match`, whose arms cover all the values
+    MatchArm,
+    /// The label of a case, like Java `case 1:`
+    Label,
+    /// A `try`
+    Try,
+    /// An exception handler of a `try`
+    Catch,
+    /// The `finally` of a `try`
+    Finally,
+    /// A block of statements
+    Block,
+    /// A `return`
+    Return,
+    /// A `throw` or a `raise`
+    Throw,
+    /// A `break`
+    Break,
+    /// A `continue`
+    Continue,
+    /// A node which doesn't change the control flow
+    Unknown,
+}
+
+/// The control-flow graph of a function, whose blocks are statements.
+///
+/// The graph is built from the syntax tree, with the branches, the loops,
+/// the switches, the exception handlers and the jumps recognized by the
+/// [`FlowKind`] of their nodes, so the functions of the languages which
+/// don't tell the flow kinds of their nodes are a single block. The short-circuit
+/// operators aren't branches at this level, and the statements of a
+/// `try` are assumed to throw at its start.
+///
+/// The graph finds the unreachable code, while the `cyclomatic` and the
+/// `npath` metrics of the spaces are still counted on the syntax tree:
+/// [`cyclomatic`](Self::cyclomatic) and [`npath`](Self::npath) count
+/// them on the graph, but they don't replace the metrics.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ControlFlowGraph {
+    /// The function name, if it has one
+    pub name: Option<String>,
+    /// The first line of the function
+    pub start_line: usize,
+    /// The last line of the function
+    pub end_line: usize,
+    /// The blocks, indexed by id
+    pub blocks: Vec<BasicBlock>,
+    /// The edges
+    pub edges: Vec<Edge>,
+}
+
+impl ControlFlowGraph {
+    /// The id of the entry block
+    pub const ENTRY: usize = 0;
+    /// The id of the exit block
+    pub const EXIT: usize = 1;
+
+    /// Returns the successors of a block.
+    pub fn successors(&self, block: usize) -> impl Iterator<Item = usize> + '_ {
+        self.edges
+            .iter()
+            .filter(move |edge| edge.from == block)
+            .map(|edge| edge.to)
+    }
+
+    /// Returns whether each block is reachable from the entry, by id.
+    pub fn reachable(&self) -> Vec<bool> {
+        let mut reachable = vec![false; self.blocks.len()];
+        let mut stack = vec![Self::ENTRY];
+        while let Some(block) = stack.pop() {
+            if !std::mem::replace(&mut reachable[block], true) {
+                stack.extend(self.successors(block));
+            }
+        }
+        reachable
+    }
+
+    /// Returns the blocks which are never executed, like the statements
+    /// following a `return`.
+    pub fn unreachable_blocks(&self) -> Vec<&BasicBlock> {
+        let reachable = self.reachable();
+        self.blocks
+            .iter()
+            .filter(|block| !reachable[block.id])
+            .filter(|block| match block.kind {
+                BlockKind::Statements => block.statements > 0,
+                BlockKind::Condition => true,
+                BlockKind::Entry | BlockKind::Exit => false,
+            })
+            .collect()
+    }
+
+    /// Returns the cyclomatic complexity of the function, `E - N + 2`,
+    /// counted on its reachable blocks.
+    ///
+    /// The `cyclomatic` metric of the spaces is counted on the syntax tree
+    /// instead, so it counts the unreachable branches and the
+    /// short-circuit operators.
+    pub fn cyclomatic(&self) -> usize {
+        let reachable = self.reachable();
+        let nodes = reachable.iter().filter(|&&reachable| reachable).count();
+        let edges = self
+            .edges
+            .iter()
+            .filter(|edge| reachable[edge.from])
+            .count();
+        (edges + 2).saturating_sub(nodes).max(1)
+    }
+
+    /// Returns the NPATH complexity of the function: the number of its
+    /// acyclic paths from the entry to the exit, each loop being taken
+    /// at most once.
+    ///
+    /// The `npath` metric of the spaces is counted on the syntax tree
+    /// instead.
+    pub fn npath(&self) -> u64 {
+        let mut successors = vec![Vec::new(); self.blocks.len()];
+        for edge in &self.edges {
+            successors[edge.from].push(edge.to);
+        }
+        // The paths to the exit are counted in a depth-first search, which
+        // skips the edges going back to the blocks being visited
+        let mut paths = vec![0u64; self.blocks.len()];
+        let mut visited = vec![false; self.blocks.len()];
+        let mut stack = vec![(Self::ENTRY, 0)];
+        visited[Self::ENTRY] = true;
+        let mut on_stack = visited.clone();
+        while let Some(top) = stack.last_mut() {
+            let (block, next) = *top;
+            if let Some(&successor) = successors[block].get(next) {
+                top.1 += 1;
+                if !visited[successor] {
+                    visited[successor] = true;
+                    on_stack[successor] = true;
+                    stack.push((successor, 0));
+                } else if !on_stack[successor] {
+                    paths[block] = paths[block].saturating_add(paths[successor]);
+                }
+                continue;
+            }
+            if block == Self::EXIT {
+                paths[block] = 1;
+            }
+            on_stack[block] = false;
+            stack.pop();
+            if let Some(&(parent, _)) = stack.last() {
+                paths[parent] = paths[parent].saturating_add(paths[block]);
+            }
+        }
+        paths[Self::ENTRY]
+    }
+}
+
+// The kinds of the nodes which branch, loop or jump
+fn is_construct(kind: FlowKind) -> bool {
+    matches!(
+        kind,
+        FlowKind::If
+            | FlowKind::Loop
+            | FlowKind::InfiniteLoop
+            | FlowKind::DoLoop
+            | FlowKind::Switch
+            | FlowKind::Match
+            | FlowKind::Try
+            | FlowKind::Return
+            | FlowKind::Throw
+            | FlowKind::Break
+            | FlowKind::Continue
+    )
+}
+
+fn is_case(kind: FlowKind) -> bool {
+    matches!(
+        kind,
+        FlowKind::Case | FlowKind::FallthroughCase | FlowKind::MatchArm
+    )
+}
+
+fn field<'a>(node: &Node<'a>, name: &str) -> Option<Node<'a>> {
+    node.children_by_field_name(name).into_iter().next()
+}
+
+// The pending edges leaving the last blocks of the code built so far
+type Frontier = Vec<(usize, EdgeKind)>;
+
+// The jumps out of a loop or of a switch
+#[derive(Default)]
+struct Jumps {
+    is_loop: bool,
+    breaks: Frontier,
+    continues: Frontier,
+}
+
+struct Builder<'a, T> {
+    code: &'a [u8],
+    blocks: Vec<BasicBlock>,
+    edges: Vec<Edge>,
+    // The nodes evaluated by each block
+    nodes: Vec<Vec<Node<'a>>>,
+    // The blocks of the `try` bodies with the handlers they may throw to
+    throwing: Vec<(usize, usize)>,
+    jumps: Vec<Jumps>,
+    // The throws of the bodies of the enclosing `try`
+    throws: Vec<Frontier>,
+    _parser: PhantomData<T>,
+}
+
+impl<'a, T: ParserTrait> Builder<'a, T> {
+    fn new(code: &'a [u8], function: &Node) -> Self {
+        let mut builder = Self {
+            code,
+            blocks: Vec::new(),
+            edges: Vec::new(),
+            nodes: Vec::new(),
+            throwing: Vec::new(),
+            jumps: Vec::new(),
+            throws: Vec::new(),
+            _parser: PhantomData,
+        };
+        let (start, end) = (function.start_row() + 1, function.end_row() + 1);
+        builder.new_block(BlockKind::Entry, start, start);
+        builder.new_block(BlockKind::Exit, end, end);
+        builder
+    }
+
+    fn new_block(&mut self, kind: BlockKind, start_line: usize, end_line: usize) -> usize {
+        let id = self.blocks.len();
+        self.blocks.push(BasicBlock {
+            id,
+            kind,
+            statements: 0,
+            start_line,
+            end_line,
+        });
+        self.nodes.push(Vec::new());
+        id
+    }
+
+    fn connect(&mut self, frontier: Frontier, to: usize) {
+        self.edges.extend(
+            frontier
+                .into_iter()
+                .map(|(from, kind)| Edge { from, to, kind }),
+        );
+    }
+
+    fn is_nested(node: &Node) -> bool {
+        T::Checker::is_func(node) || T::Checker::is_closure(node)
+    }
+
+    fn has_construct(node: &Node) -> bool {
+        let mut stack = node.children().collect::<Vec<_>>();
+        while let Some(node) = stack.pop() {
+            if Self::is_nested(&node) {
+                continue;
+            }
+            if is_construct(T::Getter::get_flow_kind(&node)) {
+                return true;
+            }
+            stack.extend(node.children());
+        }
+        false
+    }
+
+    fn build(&mut self, node: &Node<'a>, frontier: Frontier) -> Frontier {
+        if !node.is_named() || T::Checker::is_comment(node) {
+            return frontier;
+        }
+        if Self::is_nested(node) {
+            return self.statement(node, frontier);
+        }
+        match T::Getter::get_flow_kind(node) {
+            FlowKind::If => self.build_if(node, frontier),
+            FlowKind::Loop | FlowKind::InfiniteLoop => self.build_loop(node, frontier),
+            FlowKind::DoLoop => self.build_do_loop(node, frontier),
+            FlowKind::Switch | FlowKind::Match => self.build_switch(node, frontier),
+            FlowKind::Try => self.build_try(node, frontier),
+            jump @ (FlowKind::Return | FlowKind::Throw | FlowKind::Break | FlowKind::Continue) => {
+                let block = self.statement(node, frontier)[0].0;
+                self.jump(jump, block);
+                Frontier::new()
+            }
+            FlowKind::Block | FlowKind::Else => self.build_children(node.children(), frontier),
+            _ if Self::has_construct(node) => self.build_children(node.children(), frontier),
+            _ => self.statement(node, frontier),
+        }
+    }
+
+    fn build_children(
+        &mut self,
+        children: impl Iterator<Item = Node<'a>>,
+        mut frontier: Frontier,
+    ) -> Frontier {
+        for child in children {
+            frontier = self.build(&child, frontier);
+        }
+        frontier
+    }
+
+    // Appends a statement to the current block, or starts a block
+    fn statement(&mut self, node: &Node<'a>, frontier: Frontier) -> Frontier {
+        let (start, end) = (node.start_row() + 1, node.end_row() + 1);
+        let block = match frontier.as_slice() {
+            [(block, EdgeKind::Normal)]
+                if self.blocks[*block].kind == BlockKind::Statements
+                    && !self.edges.iter().any(|edge| edge.from == *block) =>
+            {
+                *block
+            }
+            _ => {
+                let block = self.new_block(BlockKind::Statements, start, end);
+                self.connect(frontier, block);
+                block
+            }
+        };
+        self.nodes[block].push(*node);
+        let block = &mut self.blocks[block];
+        if block.statements == 0 {
+            block.start_line = start;
+        }
+        block.end_line = block.end_line.max(end);
+        block.statements += 1;
+        vec![(block.id, EdgeKind::Normal)]
+    }
+
+    fn jump(&mut self, jump: FlowKind, block: usize) {
+        let pending = (block, EdgeKind::Normal);
+        match jump {
+            FlowKind::Return => self.connect(vec![pending], ControlFlowGraph::EXIT),
+            FlowKind::Throw => match self.throws.last_mut() {
+                Some(throws) => throws.push((block, EdgeKind::Exception)),
+                None => self.connect(vec![pending], ControlFlowGraph::EXIT),
+            },
+            FlowKind::Break => {
+                if let Some(jumps) = self.jumps.last_mut() {
+                    jumps.breaks.push(pending);
+                }
+            }
+            _ => {
+                if let Some(jumps) = self.jumps.iter_mut().rev().find(|jumps| jumps.is_loop) {
+                    jumps.continues.push(pending);
+                }
+            }
+        }
+    }
+
+    // The children of a construct evaluated by its condition block, like
+    // the condition of an `if` or the variable and the sequence of a `for`
+    fn head(node: &Node<'a>) -> Vec<Node<'a>> {
+        let bodies = ["consequence", "alternative", "body"]
+            .iter()
+            .flat_map(|name| node.children_by_field_name(name))
+            .map(|body| body.id())
+            .collect::<Vec<_>>();
+        node.children()
+            .filter(|child| {
+                let kind = T::Getter::get_flow_kind(child);
+                child.is_named()
+                    && !T::Checker::is_comment(child)
+                    && !bodies.contains(&child.id())
+                    && !matches!(kind, FlowKind::Block | FlowKind::Else | FlowKind::ElseIf)
+                    && !is_case(kind)
+            })
+            .collect()
+    }
+
+    fn condition(&mut self, node: &Node<'a>, frontier: Frontier) -> usize {
+        let condition = ["condition", "value", "subject"]
+            .iter()
+            .find_map(|name| field(node, name));
+        let (start, end) = condition.map_or((node.start_row(), node.start_row()), |condition| {
+            (condition.start_row(), condition.end_row())
+        });
+        let block = self.new_block(BlockKind::Condition, start + 1, end + 1);
+        self.connect(frontier, block);
+        self.nodes[block] = Self::head(node);
+        block
+    }
+
+    fn build_if(&mut self, node: &Node<'a>, frontier: Frontier) -> Frontier {
+        let condition = self.condition(node, frontier);
+        let then = vec![(condition, EdgeKind::True)];
+        let mut exits = match field(node, "consequence") {
+            Some(consequence) => self.build(&consequence, then),
+            None => then,
+        };
+        let mut otherwise = vec![(condition, EdgeKind::False)];
+        for alternative in node.children_by_field_name("alternative") {
+            if T::Getter::get_flow_kind(&alternative) == FlowKind::ElseIf {
+                let condition = self.condition(&alternative, otherwise);
+                let then = vec![(condition, EdgeKind::True)];
+                exits.extend(match field(&alternative, "consequence") {
+                    Some(consequence) => self.build(&consequence, then),
+                    None => then,
+                });
+                otherwise = vec![(condition, EdgeKind::False)];
+            } else {
+                otherwise = self.build(&alternative, otherwise);
+            }
+        }
+        exits.extend(otherwise);
+        exits
+    }
+
+    fn build_loop(&mut self, node: &Node<'a>, frontier: Frontier) -> Frontier {
+        let body = field(node, "body");
+        let is_body = |child: &Node| body.is_some_and(|body| body.id() == child.id());
+        // A loop without a condition nor a sequence to iterate never ends,
+        // like `loop {}` or `for (;;) {}`
+        let infinite = T::Getter::get_flow_kind(node) == FlowKind::InfiniteLoop
+            || !node.children().any(|child| {
+                child.is_named() && !T::Checker::is_comment(&child) && !is_body(&child)
+            });
+
+        let head = self.condition(node, frontier);
+        let iterate = if infinite {
+            EdgeKind::Normal
+        } else {
+            EdgeKind::True
+        };
+        self.jumps.push(Jumps {
+            is_loop: true,
+            ..Default::default()
+        });
+        let mut exits = match body {
+            Some(body) => self.build(&body, vec![(head, iterate)]),
+            None => vec![(head, iterate)],
+        };
+        let jumps = self.jumps.pop().unwrap_or_default();
+        exits.extend(jumps.continues);
+        self.connect(exits, head);
+
+        let mut exits = if infinite {
+            Frontier::new()
+        } else {
+            vec![(head, EdgeKind::False)]
+        };
+        // The `else` of the Python loops runs when they end normally
+        if let Some(otherwise) = node
+            .children()
+            .find(|child| T::Getter::get_flow_kind(child) == FlowKind::Else && !is_body(child))
+        {
+            exits = self.build(&otherwise, exits);
+        }
+        exits.extend(jumps.breaks);
+        exits
+    }
+
+    fn build_do_loop(&mut self, node: &Node<'a>, frontier: Frontier) -> Frontier {
+        let line = node.start_row() + 1;
+        let start = self.new_block(BlockKind::Statements, line, line);
+        self.connect(frontier, start);
+        self.jumps.push(Jumps {
+            is_loop: true,
+            ..Default::default()
+        });
+        let mut exits = match field(node, "body") {
+            Some(body) => self.build(&body, vec![(start, EdgeKind::Normal)]),
+            None => vec![(start, EdgeKind::Normal)],
+        };
+        let jumps = self.jumps.pop().unwrap_or_default();
+        exits.extend(jumps.continues);
+
+        let condition = self.condition(node, exits);
+        self.connect(vec![(condition, EdgeKind::True)], start);
+        let mut exits = vec![(condition, EdgeKind::False)];
+        exits.extend(jumps.breaks);
+        exits
+    }
+
+    fn cases(node: &Node<'a>, cases: &mut Vec<Node<'a>>) {
+        for child in node.children() {
+            let kind = T::Getter::get_flow_kind(&child);
+            if is_case(kind) {
+                cases.push(child);
+            } else if !Self::is_nested(&child) && !is_construct(kind) {
+                Self::cases(&child, cases);
+            }
+        }
+    }
+
+    // The statements of a case, without its labels and its patterns
+    fn case_body(case: &Node<'a>) -> Vec<Node<'a>> {
+        for name in ["consequence", "body"] {
+            let body = case.children_by_field_name(name);
+            if !body.is_empty() {
+                return body;
+            }
+        }
+        if T::Getter::get_flow_kind(case) == FlowKind::MatchArm {
+            return field(case, "value").into_iter().collect();
+        }
+        let labels = ["value", "pattern", "label"]
+            .iter()
+            .flat_map(|name| case.children_by_field_name(name))
+            .map(|label| label.id())
+            .collect::<Vec<_>>();
+        case.children()
+            .filter(|child| {
+                !labels.contains(&child.id()) && T::Getter::get_flow_kind(child) != FlowKind::Label
+            })
+            .collect()
+    }
+
+    fn is_default(&self, case: &Node) -> bool {
+        let text = case.utf8_text(self.code).unwrap_or_default();
+        // The arms of a Rust match cover all the values
+        T::Getter::get_flow_kind(case) == FlowKind::MatchArm
+            || text.starts_with("default")
+            || text.starts_with("case _")
+    }
+
+    fn build_switch(&mut self, node: &Node<'a>, frontier: Frontier) -> Frontier {
+        let head = self.condition(node, frontier);
+        let mut cases = Vec::new();
+        Self::cases(node, &mut cases);
+
+        // The `break` of the cases of a match leaves the enclosing loop
+        let is_switch = T::Getter::get_flow_kind(node) == FlowKind::Switch;
+        if is_switch {
+            self.jumps.push(Jumps::default());
+        }
+        let mut exits = Frontier::new();
+        let mut fallthrough = Frontier::new();
+        let mut exhaustive = false;
+        for case in cases {
+            exhaustive |= self.is_default(&case);
+            let mut entry = vec![(head, EdgeKind::Case)];
+            entry.append(&mut fallthrough);
+            // The labels and the patterns are matched by the head
+            let body = Self::case_body(&case);
+            self.nodes[head].extend(case.children().filter(|child| {
+                child.is_named()
+                    && !T::Checker::is_comment(child)
+                    && !body.iter().any(|statement| statement.id() == child.id())
+            }));
+            let case_exits = self.build_children(body.into_iter(), entry);
+            if T::Getter::get_flow_kind(&case) == FlowKind::FallthroughCase {
+                fallthrough = case_exits;
+            } else {
+                exits.extend(case_exits);
+            }
+        }
+        exits.append(&mut fallthrough);
+        if !exhaustive {
+            exits.push((head, EdgeKind::False));
+        }
+        if is_switch {
+            exits.extend(self.jumps.pop().unwrap_or_default().breaks);
+        }
+        exits
+    }
+
+    fn build_try(&mut self, node: &Node<'a>, frontier: Frontier) -> Frontier {
+        let line = node.start_row() + 1;
+        let start = self.new_block(BlockKind::Statements, line, line);
+        self.connect(frontier, start);
+
+        let body = field(node, "body");
+        // The resources of a Java `try`
+        self.nodes[start].extend(node.children().filter(|child| {
+            child.is_named()
+                && !T::Checker::is_comment(child)
+                && !matches!(
+                    T::Getter::get_flow_kind(child),
+                    FlowKind::Catch | FlowKind::Else | FlowKind::Finally
+                )
+                && body.is_none_or(|body| body.id() != child.id())
+        }));
+
+        // Any statement of the body may throw, which its start stands for
+        self.throws.push(vec![(start, EdgeKind::Exception)]);
+        let first = self.blocks.len();
+        let mut exits = match body {
+            Some(body) => self.build(&body, vec![(start, EdgeKind::Normal)]),
+            None => vec![(start, EdgeKind::Normal)],
+        };
+        let throwing = first..self.blocks.len();
+        let throws = self.throws.pop().unwrap_or_default();
+
+        let mut handlers = Vec::new();
+        let mut finally = None;
+        for child in node.children() {
+            match T::Getter::get_flow_kind(&child) {
+                FlowKind::Catch => handlers.push(child),
+                FlowKind::Else => exits = self.build(&child, exits),
+                FlowKind::Finally => finally = Some(child),
+                _ => {}
+            }
+        }
+        if handlers.is_empty() {
+            // The exceptions go on to the enclosing handlers
+            for (block, _) in throws.into_iter().skip(1) {
+                self.jump(FlowKind::Throw, block);
+            }
+        } else {
+            for handler in handlers {
+                let entry = self.blocks.len();
+                let handler_exits = self.build(&handler, throws.clone());
+                if entry < self.blocks.len() {
+                    self.throwing
+                        .extend(throwing.clone().map(|block| (block, entry)));
+                }
+                exits.extend(handler_exits);
+            }
+        }
+        if let Some(finally) = finally {
+            exits = self.build(&finally, exits);
+        }
+        exits
+    }
+
+    fn finish(mut self, function: &Node<'a>) -> FlowGraph<'a> {
+        let entry = vec![(ControlFlowGraph::ENTRY, EdgeKind::Normal)];
+        let exits = match field(function, "body") {
+            Some(body) => self.build(&body, entry),
+            None => self.build_children(function.children(), entry),
+        };
+        self.connect(exits, ControlFlowGraph::EXIT);
+        let graph = ControlFlowGraph {
+            name: T::Getter::get_func_name(function, self.code).map(|name| name.to_string()),
+            start_line: function.start_row() + 1,
+            end_line: function.end_row() + 1,
+            blocks: self.blocks,
+            edges: self.edges,
+        };
+        FlowGraph {
+            graph,
+            nodes: self.nodes,
+            throwing: self.throwing,
+        }
+    }
+}
+
+// The control-flow graph of a function with the nodes of its blocks, for
+// the data-flow analyses
+pub(crate) struct FlowGraph<'a> {
+    pub(crate) graph: ControlFlowGraph,
+    // The statements of each block, or the conditions, the heads of the
+    // loops and the patterns of the cases of the condition blocks
+    pub(crate) nodes: Vec<Vec<Node<'a>>>,
+    // The blocks of the `try` bodies with the entry of each of their
+    // handlers, as any of their statements may throw, while the graph
+    // only throws from the start of the bodies
+    pub(crate) throwing: Vec<(usize, usize)>,
+}
+
+pub(crate) fn flow_graph<'a, T: ParserTrait>(function: &Node<'a>, code: &'a [u8]) -> FlowGraph<'a> {
+    Builder::<T>::new(code, function).finish(function)
+}
+
+/// Builds the control-flow graph of each function of a code, in the
+/// order of their start.
+///
+/// The nested functions and closures are statements of the graphs of
+/// their parents, and the nested functions have their own graphs.
+pub fn control_flow_graphs<T: ParserTrait>(parser: &T) -> Vec<ControlFlowGraph> {
+    let code = parser.get_code();
+    let mut graphs = Vec::new();
+    parser.get_root().act_on_node(&mut |n| {
+        if T::Checker::is_func(n) {
+            graphs.push(flow_graph::<T>(n, code).graph);
+        }
+    });
+    graphs
+}
+
+struct ControlFlowGraphs {
+    _guard: (),
+}
+
+impl Callback for ControlFlowGraphs {
+    type Res = Vec<ControlFlowGraph>;
+    type Cfg = ();
+
+    fn call<T: ParserTrait>(_cfg: Self::Cfg, parser: &T) -> Self::Res {
+        control_flow_graphs(parser)
+    }
+}
+
+/// Builds the control-flow graph of each function of a code in a
+/// language.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{get_control_flow_graphs, LANG};
+///
+/// let code = b"fn f(a: bool) -> u32 {\n    if a {\n        return 1;\n    }\n    0\n}\n".to_vec();
+/// let graphs = get_control_flow_graphs(&LANG::Rust, code, Path::new("foo.rs"));
+/// assert_eq!(graphs[0].cyclomatic(), 2);
+/// assert_eq!(graphs[0].npath(), 2);
+/// ```
+pub fn get_control_flow_graphs(lang: &LANG, source: Vec<u8>, path: &Path) -> Vec<ControlFlowGraph> {
+    action::<ControlFlowGraphs>(lang, source, path, None, ())
+}
+
+/// Finds the statements of the functions of a code which are never
+/// executed, like the statements following a `return`, as the
+/// unreachable blocks of their [`ControlFlowGraph`].
+///
+/// Each unreachable block is reported as a smell of the
+/// [`UNREACHABLE_CODE`] rule.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{find_unreachable, ParserTrait, PythonParser, SmellCatalog};
+///
+/// let path = Path::new("foo.py");
+/// let code = b"def f():\n    return 1\n    print(1)\n".to_vec();
+/// let parser = PythonParser::new(code, path, None);
+///
+/// let report = find_unreachable(&parser, path, &SmellCatalog::default());
+/// assert_eq!(report.smells[0].rule_id, "unreachable-code");
+/// assert_eq!(report.smells[0].location.line_start, 3);
+/// ```
+pub fn find_unreachable<T: ParserTrait>(
+    parser: &T,
+    path: &Path,
+    catalog: &SmellCatalog,
+) -> SmellReport {
+    let mut metadata = SmellMetadata::for_catalog(catalog);
+
+    let mut smells = Vec::new();
+    let mut suppressed = Vec::new();
+    if let Some(rule) = catalog.enabled(UNREACHABLE_CODE) {
+        let code = parser.get_code();
+        let file_path = path.to_string_lossy().to_string();
+        parser.get_root().act_on_node(&mut |n| {
+            if !T::Checker::is_func(n) {
+                return;
+            }
+            let flow = flow_graph::<T>(n, code);
+            let function = flow.graph.name.as_deref().unwrap_or("<anonymous>");
+            for block in flow.graph.unreachable_blocks() {
+                let nodes = &flow.nodes[block.id];
+                let (start_row, start_column) = nodes
+                    .first()
+                    .map_or((block.start_line - 1, 0), |node| node.start_position());
+                let (end_row, end_column) = nodes
+                    .last()
+                    .map_or((block.end_line - 1, 0), |node| node.end_position());
+                let smell = CodeSmell {
+                    rule_id: rule.id.clone(),
+                    name: rule.name.clone(),
+                    description: format!("Code of `{function}` which is never executed"),
+                    severity: rule.severity.clone(),
+                    location: CodeLocation {
+                        file_path: file_path.clone(),
+                        line_start: start_row + 1,
+                        line_end: end_row + 1,
+                        column_start: start_column + 1,
+                        column_end: end_column + 1,
+                    },
+                    suggestion: "Remove the code, or fix the jump which skips it".to_string(),
+                };
+                smells.push(smell);
+            }
+        });
+        metadata.record(
+            rule,
+            rule.threshold_for(Some(parser.get_language())),
+            smells.len(),
+        );
+        (smells, suppressed) = metadata.suppress(smells, code, |smell| smell);
+    }
+
+    SmellReport {
+        smells,
+        suppressed,
+        metadata,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ParserEngineRust, PythonParser};
+
+    use super::*;
+
+    #[test]
+    fn branches() {
+        let code = "fn f(a: u32) -> u32 {
+    if a > 1 {
+        return 1;
+    } else if a == 0 {
+        return 0;
+    }
+    let b = a;
+    b
+}
+";
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), Path::new("foo.rs"), None);
+        let graphs = control_flow_graphs(&parser);
+        let graph = &graphs[0];
+
+        assert_eq!(graph.name.as_deref(), Some("f"));
+        assert_eq!(graph.blocks.len(), 7);
+        assert_eq!(graph.edges.len(), 8);
+        assert_eq!(graph.blocks[6].statements, 2);
+        assert_eq!(
+            (graph.blocks[6].start_line, graph.blocks[6].end_line),
+            (7, 8)
+        );
+        assert_eq!(graph.cyclomatic(), 3);
+        assert_eq!(graph.npath(), 3);
+        assert!(graph.unreachable_blocks().is_empty());
+    }
+
+    #[test]
+    fn loops_and_unreachable_code() {
+        let code = "def g(items):
+    for item in items:
+        if item:
+            continue
+        break
+    return 1
+    print(\"done\")
+";
+        let parser = PythonParser::new(code.as_bytes().to_vec(), Path::new("foo.py"), None);
+        let graph = &control_flow_graphs(&parser)[0];
+
+        assert_eq!(graph.cyclomatic(), 3);
+        assert_eq!(graph.npath(), 2);
+        let unreachable = graph.unreachable_blocks();
+        assert_eq!(unreachable.len(), 1);
+        assert_eq!(unreachable[0].start_line, 7);
+    }
+
+    #[test]
+    fn unreachable_code() {
+        let code = "fn f(a: u32) -> u32 {
+    loop {
+        if a > 1 {
+            return 1;
+            let b = 2;
+        }
+    }
+    a
+}
+
+fn g() {
+    return;
+    h(); // sca-ignore
+}
+";
+        let path = Path::new("foo.rs");
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), path, None);
+        let report = find_unreachable(&parser, path, &SmellCatalog::default());
+
+        let lines = report
+            .smells
+            .iter()
+            .map(|smell| (smell.location.line_start, smell.location.column_start))
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [(5, 13), (8, 5)]);
+        assert_eq!(
+            report.smells[0].description,
+            "Code of `f` which is never executed"
+        );
+        assert_eq!(report.suppressed.len(), 1);
+    }
+
+    #[test]
+    fn break_in_match() {
+        let code = "fn f(x: u8) { loop { match x { _ => break, } } g(); }";
+        let path = Path::new("foo.rs");
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), path, None);
+        let report = find_unreachable(&parser, path, &SmellCatalog::default());
+
+        assert!(report.smells.is_empty());
+        assert!(control_flow_graphs(&parser)[0]
+            .unreachable_blocks()
+            .is_empty());
+    }
+}
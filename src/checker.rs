@@ -1,3 +1,5 @@
+#![cfg_attr(not(test), deny(clippy::unwrap_used))]
+
 use std::sync::OnceLock;
 
 use aho_corasick::AhoCorasick;
@@ -64,22 +66,93 @@ macro_rules! is_js_func_and_closure_checker {
 #[inline(always)]
 fn get_aho_corasick_match(code: &[u8]) -> bool {
     AHO_CORASICK
-        .get_or_init(|| AhoCorasick::new(vec![b"<div rustbindgen"]).unwrap())
+        .get_or_init(|| AhoCorasick::new(vec![b"<div rustbindgen"]).expect("valid pattern"))
         .is_match(code)
 }
 
+/// Classifies the nodes of the syntax tree of a language.
+///
+/// The metrics rely on these predicates to find the functions, the
+/// comments and the other constructs of a code. Every method has a default
+/// implementation which matches no node, so a new grammar can start with
+/// an empty implementation and add the predicates it needs.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{Checker, Node, ParserEngineRust, ParserTrait};
+///
+/// struct Comments;
+///
+/// impl Checker for Comments {
+///     fn is_comment(node: &Node) -> bool {
+///         node.kind().ends_with("comment")
+///     }
+/// }
+///
+/// let parser = ParserEngineRust::new(b"// a\nfn f() {}".to_vec(), Path::new("foo.rs"), None);
+/// let root = parser.get_root();
+///
+/// assert!(Comments::is_comment(&root.child(0).unwrap()));
+/// assert!(!Comments::is_func(&root.child(1).unwrap()));
+/// ```
 pub trait Checker {
-    fn is_comment(_: &Node) -> bool;
-    fn is_useful_comment(_: &Node, _: &[u8]) -> bool;
-    fn is_func_space(_: &Node) -> bool;
-    fn is_func(_: &Node) -> bool;
-    fn is_closure(_: &Node) -> bool;
-    fn is_call(_: &Node) -> bool;
-    fn is_non_arg(_: &Node) -> bool;
-    fn is_string(_: &Node) -> bool;
-    fn is_else_if(_: &Node) -> bool;
-    fn is_primitive(_id: u16) -> bool;
+    /// Returns `true` if the node is a comment.
+    fn is_comment(_node: &Node) -> bool {
+        false
+    }
+
+    /// Returns `true` if the comment must be kept when removing the
+    /// comments of a code, like a shebang or an encoding declaration.
+    fn is_useful_comment(_node: &Node, _code: &[u8]) -> bool {
+        false
+    }
+
+    /// Returns `true` if the node opens a space: a unit, a class, a
+    /// function, ...
+    fn is_func_space(_node: &Node) -> bool {
+        false
+    }
+
+    /// Returns `true` if the node is a function or method definition.
+    fn is_func(_node: &Node) -> bool {
+        false
+    }
+
+    /// Returns `true` if the node is a closure, lambda or anonymous function.
+    fn is_closure(_node: &Node) -> bool {
+        false
+    }
+
+    /// Returns `true` if the node is a function call.
+    fn is_call(_node: &Node) -> bool {
+        false
+    }
+
+    /// Returns `true` if the node is a token of an argument list which
+    /// isn't an argument, like a parenthesis or a comma.
+    fn is_non_arg(_node: &Node) -> bool {
+        false
+    }
+
+    /// Returns `true` if the node is a string literal.
+    fn is_string(_node: &Node) -> bool {
+        false
+    }
+
+    /// Returns `true` if the node is an `else if` branch.
+    fn is_else_if(_node: &Node) -> bool {
+        false
+    }
+
+    /// Returns `true` if the node kind is a primitive type.
+    fn is_primitive(_id: u16) -> bool {
+        false
+    }
 
+    /// Returns `true` if the node is, or contains, a syntax error.
     fn is_error(node: &Node) -> bool {
         node.has_error()
     }
@@ -249,7 +322,8 @@ impl Checker for PythonCode {
         node.start_row() <= 1
             && RE
                 .get_or_init(|| {
-                    Regex::new(r"^[ \t\f]*#.*?coding[:=][ \t]*([-_.a-zA-Z0-9]+)").unwrap()
+                    Regex::new(r"^[ \t\f]*#.*?coding[:=][ \t]*([-_.a-zA-Z0-9]+)")
+                        .expect("valid regex")
                 })
                 .is_match(&code[node.start_byte()..node.end_byte()])
     }
@@ -305,7 +379,11 @@ impl Checker for JavaCode {
     fn is_func_space(node: &Node) -> bool {
         matches!(
             node.kind_id().into(),
-            Java::Program | Java::ClassDeclaration | Java::InterfaceDeclaration
+            Java::Program
+                | Java::ClassDeclaration
+                | Java::EnumDeclaration
+                | Java::RecordDeclaration
+                | Java::InterfaceDeclaration
         )
     }
 
@@ -376,7 +454,10 @@ impl Checker for MozjsCode {
     }
 
     fn is_string(node: &Node) -> bool {
-        node.kind_id() == Mozjs::String || node.kind_id() == Mozjs::TemplateString
+        matches!(
+            node.kind_id().into(),
+            Mozjs::String | Mozjs::String2 | Mozjs::TemplateString
+        )
     }
 
     #[inline(always)]
@@ -433,7 +514,10 @@ impl Checker for JavascriptCode {
     }
 
     fn is_string(node: &Node) -> bool {
-        node.kind_id() == Javascript::String || node.kind_id() == Javascript::TemplateString
+        matches!(
+            node.kind_id().into(),
+            Javascript::String | Javascript::String2 | Javascript::TemplateString
+        )
     }
 
     #[inline(always)]
@@ -473,7 +557,10 @@ impl Checker for TypescriptCode {
                 | Typescript::MethodDefinition
                 | Typescript::GeneratorFunctionDeclaration
                 | Typescript::ClassDeclaration
+                | Typescript::AbstractClassDeclaration
                 | Typescript::InterfaceDeclaration
+                | Typescript::Module
+                | Typescript::InternalModule
                 | Typescript::ArrowFunction
         )
     }
@@ -532,7 +619,10 @@ impl Checker for TsxCode {
                 | Tsx::MethodDefinition
                 | Tsx::GeneratorFunctionDeclaration
                 | Tsx::ClassDeclaration
+                | Tsx::AbstractClassDeclaration
                 | Tsx::InterfaceDeclaration
+                | Tsx::Module
+                | Tsx::InternalModule
                 | Tsx::ArrowFunction
         )
     }
@@ -551,7 +641,10 @@ impl Checker for TsxCode {
     }
 
     fn is_string(node: &Node) -> bool {
-        node.kind_id() == Tsx::String || node.kind_id() == Tsx::TemplateString
+        matches!(
+            node.kind_id().into(),
+            Tsx::String | Tsx::String2 | Tsx::TemplateString
+        )
     }
 
     fn is_else_if(node: &Node) -> bool {
@@ -587,14 +680,19 @@ impl Checker for RustCode {
     }
 
     fn is_func_space(node: &Node) -> bool {
-        matches!(
-            node.kind_id().into(),
+        match node.kind_id().into() {
             Rust::SourceFile
-                | Rust::FunctionItem
-                | Rust::ImplItem
-                | Rust::TraitItem
-                | Rust::ClosureExpression
-        )
+            | Rust::FunctionItem
+            | Rust::StructItem
+            | Rust::EnumItem
+            | Rust::UnionItem
+            | Rust::ImplItem
+            | Rust::TraitItem
+            | Rust::ClosureExpression => true,
+            // `mod foo;` declares a module defined in another file
+            Rust::ModItem => node.child_by_field_name("body").is_some(),
+            _ => false,
+        }
     }
 
     fn is_func(node: &Node) -> bool {
@@ -852,9 +950,9 @@ impl Checker for GleamCode {
         node.kind_id() == Gleam::FunctionCall
     }
 
-    fn is_non_arg(_node: &Node) -> bool {
+    fn is_non_arg(node: &Node) -> bool {
         // Structural elements that aren't arguments
-        false
+        matches!(node.kind(), "(" | "," | ")")
     }
 
     fn is_string(node: &Node) -> bool {
@@ -883,24 +981,27 @@ impl Checker for LuaCode {
     }
 
     fn is_func_space(node: &Node) -> bool {
-        // Lua function spaces: program (top-level), function declarations, function definitions
+        // Lua function spaces: chunk (top-level), function declarations, function definitions
         matches!(
             node.kind_id().into(),
-            Lua::Program | Lua::FunctionDeclaration | Lua::FunctionDefinition | Lua::Function
+            Lua::Chunk
+                | Lua::FunctionDeclaration
+                | Lua::FunctionDeclaration2
+                | Lua::FunctionDefinition
         )
     }
 
     fn is_func(node: &Node) -> bool {
-        // Lua function declarations and definitions
+        // Lua named function declarations (`function f()`, `local function f()`)
         matches!(
             node.kind_id().into(),
-            Lua::FunctionDeclaration | Lua::FunctionDefinition
+            Lua::FunctionDeclaration | Lua::FunctionDeclaration2
         )
     }
 
     fn is_closure(node: &Node) -> bool {
-        // Lua anonymous functions
-        node.kind_id() == Lua::Function
+        // Lua anonymous functions (`function() ... end`)
+        node.kind_id() == Lua::FunctionDefinition
     }
 
     fn is_call(node: &Node) -> bool {
@@ -908,8 +1009,8 @@ impl Checker for LuaCode {
         node.kind_id() == Lua::FunctionCall
     }
 
-    fn is_non_arg(_node: &Node) -> bool {
-        false
+    fn is_non_arg(node: &Node) -> bool {
+        matches!(node.kind(), "(" | "," | ")")
     }
 
     fn is_string(node: &Node) -> bool {
@@ -930,7 +1031,7 @@ impl Checker for LuaCode {
     }
 }
 
-// Go language - delegate to Java as fallback
+// Go language - spaces follow the Go grammar, the rest is delegated to Java
 impl Checker for GoCode {
     fn is_comment(node: &Node) -> bool {
         JavaCode::is_comment(node)
@@ -941,15 +1042,18 @@ impl Checker for GoCode {
     }
 
     fn is_func_space(node: &Node) -> bool {
-        JavaCode::is_func_space(node)
+        matches!(
+            node.kind(),
+            "source_file" | "function_declaration" | "method_declaration"
+        )
     }
 
     fn is_func(node: &Node) -> bool {
-        JavaCode::is_func(node)
+        matches!(node.kind(), "function_declaration" | "method_declaration")
     }
 
     fn is_closure(node: &Node) -> bool {
-        JavaCode::is_closure(node)
+        node.kind() == "func_literal"
     }
 
     fn is_call(node: &Node) -> bool {
@@ -984,15 +1088,37 @@ impl Checker for CsharpCode {
     }
 
     fn is_func_space(node: &Node) -> bool {
-        JavaCode::is_func_space(node)
+        matches!(
+            node.kind(),
+            "compilation_unit"
+                | "class_declaration"
+                | "record_declaration"
+                | "struct_declaration"
+                | "record_struct_declaration"
+                | "interface_declaration"
+                | "namespace_declaration"
+                | "file_scoped_namespace_declaration"
+                | "lambda_expression"
+                | "anonymous_method_expression"
+        )
     }
 
     fn is_func(node: &Node) -> bool {
-        JavaCode::is_func(node)
+        matches!(
+            node.kind(),
+            "method_declaration"
+                | "constructor_declaration"
+                | "destructor_declaration"
+                | "operator_declaration"
+                | "local_function_statement"
+        )
     }
 
     fn is_closure(node: &Node) -> bool {
-        JavaCode::is_closure(node)
+        matches!(
+            node.kind(),
+            "lambda_expression" | "anonymous_method_expression"
+        )
     }
 
     fn is_call(node: &Node) -> bool {
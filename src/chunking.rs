@@ -0,0 +1,413 @@
+use std::{collections::BTreeSet, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    spaces::{metrics, FuncSpace, SpaceKind},
+    tokens::{EstimateTokenizer, Tokenizer},
+    traits::ParserTrait,
+};
+
+// The kinds of the top-level nodes importing or declaring dependencies,
+// repeated in every chunk
+const IMPORTS: &[&str] = &[
+    "import",
+    "use_declaration",
+    "extern_crate",
+    "include",
+    "using_directive",
+    "package",
+];
+
+/// The options of the chunking of a code.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChunkOptions {
+    /// The maximum number of tokens of a chunk, with its context
+    pub max_tokens: usize,
+    /// The number of lines shared by the consecutive chunks of a space
+    /// too large for a chunk
+    pub overlap_lines: usize,
+    /// Whether the imports of the file are repeated in the context of
+    /// each chunk
+    pub include_imports: bool,
+}
+
+impl Default for ChunkOptions {
+    fn default() -> Self {
+        Self {
+            max_tokens: 512,
+            overlap_lines: 0,
+            include_imports: true,
+        }
+    }
+}
+
+/// A chunk of a code.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Chunk {
+    /// The first line of the chunk
+    pub start_line: usize,
+    /// The last line of the chunk
+    pub end_line: usize,
+    /// The name of the space of the chunk, when it's a single space, or
+    /// of the space containing its spaces
+    pub name: Option<String>,
+    /// The kind of the space of the chunk
+    pub kind: SpaceKind,
+    /// The imports of the file and the headers of the spaces containing
+    /// the chunk, like the first line of its class
+    pub context: String,
+    /// The code of the chunk
+    pub text: String,
+    /// The number of tokens of the chunk, with its context
+    pub tokens: usize,
+}
+
+impl Chunk {
+    /// Returns the context of the chunk followed by its code.
+    pub fn content(&self) -> String {
+        if self.context.is_empty() {
+            self.text.clone()
+        } else {
+            format!("{}\n{}", self.context, self.text)
+        }
+    }
+}
+
+/// Estimates the number of tokens of a text for a language model, as one
+/// token every four characters.
+///
+/// # Examples
+///
+/// ```
+/// use singularity_code_analysis::estimate_tokens;
+///
+/// assert_eq!(estimate_tokens("fn main() {}"), 3);
+/// ```
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+// A range of lines to chunk: a subspace of a space, or the lines
+// between its subspaces
+struct Piece<'a> {
+    start: usize,
+    end: usize,
+    space: Option<&'a FuncSpace>,
+}
+
+struct Chunker<'a> {
+    lines: Vec<&'a str>,
+    imports: BTreeSet<usize>,
+    options: &'a ChunkOptions,
+    tokenizer: &'a dyn Tokenizer,
+    chunks: Vec<Chunk>,
+}
+
+impl<'a> Chunker<'a> {
+    fn tokens(&self, text: &str) -> usize {
+        self.tokenizer.count_tokens(text)
+    }
+
+    // The text of the 1-based lines from `start` to `end`
+    fn text(&self, start: usize, end: usize) -> String {
+        self.lines[start - 1..end].join("\n")
+    }
+
+    fn is_skipped(&self, line: usize) -> bool {
+        self.lines[line - 1].trim().is_empty() || self.imports.contains(&line)
+    }
+
+    // The pieces of the lines from `start` to `end` of a space
+    fn pieces(&self, space: &'a FuncSpace, start: usize, end: usize) -> Vec<Piece<'a>> {
+        let mut pieces = Vec::new();
+        let mut line = start;
+        let add_gap = |pieces: &mut Vec<Piece>, from: usize, to: usize| {
+            let mut gap: Option<(usize, usize)> = None;
+            for line in from..=to {
+                if self.is_skipped(line) {
+                    pieces.extend(gap.take().map(|(start, end)| Piece {
+                        start,
+                        end,
+                        space: None,
+                    }));
+                } else {
+                    gap = Some((gap.map_or(line, |(start, _)| start), line));
+                }
+            }
+            pieces.extend(gap.map(|(start, end)| Piece {
+                start,
+                end,
+                space: None,
+            }));
+        };
+        let mut spaces = space.spaces.iter().collect::<Vec<_>>();
+        spaces.sort_by_key(|space| space.start_line);
+        for subspace in spaces {
+            if subspace.end_line < line || subspace.start_line > end {
+                continue;
+            }
+            add_gap(&mut pieces, line, subspace.start_line.saturating_sub(1));
+            pieces.push(Piece {
+                start: subspace.start_line.max(line),
+                end: subspace.end_line.min(end),
+                space: Some(subspace),
+            });
+            line = subspace.end_line + 1;
+        }
+        add_gap(&mut pieces, line, end);
+        pieces
+    }
+
+    fn push(&mut self, start: usize, end: usize, space: &FuncSpace, context: &str) {
+        let text = self.text(start, end);
+        let tokens = self.tokens(context) + self.tokens(&text);
+        self.chunks.push(Chunk {
+            start_line: start,
+            end_line: end,
+            name: space.name.clone(),
+            kind: space.kind,
+            context: context.to_string(),
+            text,
+            tokens,
+        });
+    }
+
+    // Splits the lines from `start` to `end` of a space into windows
+    // under the budget, sharing the overlapping lines
+    fn windows(&mut self, start: usize, end: usize, space: &FuncSpace, context: &str) {
+        let budget = self.options.max_tokens.saturating_sub(self.tokens(context));
+        let mut first = start;
+        loop {
+            let mut last = first;
+            while last < end && self.tokens(&self.text(first, last + 1)) <= budget {
+                last += 1;
+            }
+            self.push(first, last, space, context);
+            if last == end {
+                break;
+            }
+            first = (last + 1)
+                .saturating_sub(self.options.overlap_lines)
+                .max(first + 1);
+        }
+    }
+
+    // Chunks the lines from `start` to `end` of a space, packing its
+    // consecutive pieces into the chunks
+    fn chunk(&mut self, space: &'a FuncSpace, start: usize, end: usize, context: &str) {
+        let budget = self.options.max_tokens.saturating_sub(self.tokens(context));
+        let mut group: Vec<Piece> = Vec::new();
+        for piece in self.pieces(space, start, end) {
+            if self.tokens(&self.text(piece.start, piece.end)) > budget {
+                self.flush(&mut group, space, context);
+                match piece.space {
+                    // A space with subspaces is chunked between them,
+                    // with its first line as header
+                    Some(subspace) if !subspace.spaces.is_empty() && piece.end > piece.start => {
+                        let header = self.lines[piece.start - 1];
+                        let context = if context.is_empty() {
+                            header.to_string()
+                        } else {
+                            format!("{context}\n{header}")
+                        };
+                        self.chunk(subspace, piece.start + 1, piece.end, &context);
+                    }
+                    _ => self.windows(
+                        piece.start,
+                        piece.end,
+                        piece.space.unwrap_or(space),
+                        context,
+                    ),
+                }
+                continue;
+            }
+            if let Some(first) = group.first() {
+                if self.tokens(&self.text(first.start, piece.end)) > budget {
+                    self.flush(&mut group, space, context);
+                }
+            }
+            group.push(piece);
+        }
+        self.flush(&mut group, space, context);
+    }
+
+    fn flush(&mut self, group: &mut Vec<Piece>, space: &FuncSpace, context: &str) {
+        let (Some(first), Some(last)) = (group.first(), group.last()) else {
+            return;
+        };
+        let space = match (group.len(), first.space) {
+            (1, Some(subspace)) => subspace,
+            _ => space,
+        };
+        self.push(first.start, last.end, space, context);
+        group.clear();
+    }
+}
+
+/// Splits a code into chunks for the context of a language model.
+///
+/// The chunks follow the function spaces of the code: consecutive
+/// functions and classes are packed into a chunk while they fit in
+/// [`max_tokens`], a space too large for a chunk is split between its
+/// subspaces, with its first line in the context of the chunks, and a
+/// space without subspaces too large for a chunk is split into windows
+/// of lines sharing [`overlap_lines`].
+///
+/// The blank lines and the imports between the spaces aren't chunked,
+/// the imports being in the context of each chunk when
+/// [`include_imports`] is set. A single line too large for a chunk is
+/// a chunk of its own.
+///
+/// [`max_tokens`]: ChunkOptions::max_tokens
+/// [`overlap_lines`]: ChunkOptions::overlap_lines
+/// [`include_imports`]: ChunkOptions::include_imports
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{chunk_code, ChunkOptions, ParserTrait, PythonParser};
+///
+/// let path = Path::new("foo.py");
+/// let code = b"import os\n\ndef f():\n    return os.getcwd()\n".to_vec();
+/// let parser = PythonParser::new(code, path, None);
+///
+/// let chunks = chunk_code(&parser, path, &ChunkOptions::default());
+/// assert_eq!(chunks.len(), 1);
+/// assert_eq!(chunks[0].context, "import os");
+/// assert_eq!(chunks[0].start_line, 3);
+/// ```
+pub fn chunk_code<T: ParserTrait>(parser: &T, path: &Path, options: &ChunkOptions) -> Vec<Chunk> {
+    chunk_code_with_tokenizer(parser, path, options, &EstimateTokenizer)
+}
+
+/// Splits a code into chunks for the context of a language model,
+/// counting their tokens with a tokenizer.
+///
+/// The chunks are split as by [`chunk_code`], which estimates the tokens.
+pub fn chunk_code_with_tokenizer<T: ParserTrait>(
+    parser: &T,
+    path: &Path,
+    options: &ChunkOptions,
+    tokenizer: &dyn Tokenizer,
+) -> Vec<Chunk> {
+    let Some(root) = metrics(parser, path) else {
+        return Vec::new();
+    };
+    let source = String::from_utf8_lossy(parser.get_code());
+    let lines = source.lines().collect::<Vec<_>>();
+    if lines.is_empty() {
+        return Vec::new();
+    }
+
+    let mut imports = BTreeSet::new();
+    for node in parser.get_root().children() {
+        if IMPORTS.iter().any(|import| node.kind().contains(import)) {
+            imports.extend(node.start_row() + 1..=node.end_row() + 1);
+        }
+    }
+    let context = if options.include_imports {
+        imports
+            .iter()
+            .filter_map(|line| lines.get(line - 1))
+            .copied()
+            .collect::<Vec<_>>()
+            .join("\n")
+    } else {
+        String::new()
+    };
+
+    let mut chunker = Chunker {
+        lines,
+        imports,
+        options,
+        tokenizer,
+        chunks: Vec::new(),
+    };
+    let end = chunker.lines.len();
+    chunker.chunk(&root, 1, end, &context);
+    chunker.chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::PythonParser;
+
+    use super::*;
+
+    const CODE: &str = "import os
+from sys import argv
+
+def first():
+    return os.getcwd()
+
+def second():
+    return argv[0]
+
+class Shapes:
+    def area(self, width, height):
+        return width * height
+
+    def perimeter(self, width, height):
+        return 2 * (width + height)
+";
+
+    fn chunks(options: &ChunkOptions) -> Vec<Chunk> {
+        let path = Path::new("foo.py");
+        let parser = PythonParser::new(CODE.as_bytes().to_vec(), path, None);
+        chunk_code(&parser, path, options)
+    }
+
+    fn ranges(chunks: &[Chunk]) -> Vec<(usize, usize)> {
+        chunks
+            .iter()
+            .map(|chunk| (chunk.start_line, chunk.end_line))
+            .collect()
+    }
+
+    #[test]
+    fn whole_file() {
+        let chunks = chunks(&ChunkOptions::default());
+        assert_eq!(ranges(&chunks), [(4, 15)]);
+        assert_eq!(chunks[0].context, "import os\nfrom sys import argv");
+        assert_eq!(chunks[0].kind, SpaceKind::Unit);
+    }
+
+    #[test]
+    fn split_spaces() {
+        let options = ChunkOptions {
+            max_tokens: 30,
+            include_imports: false,
+            ..Default::default()
+        };
+        let chunks = chunks(&options);
+        assert_eq!(ranges(&chunks), [(4, 8), (11, 12), (14, 15)]);
+        assert_eq!(chunks[1].name.as_deref(), Some("area"));
+        assert_eq!(chunks[1].kind, SpaceKind::Function);
+        assert_eq!(chunks[1].context, "class Shapes:");
+        assert!(chunks[2]
+            .content()
+            .starts_with("class Shapes:\n    def perimeter"));
+        assert!(chunks.iter().all(|chunk| chunk.tokens <= 30));
+    }
+
+    #[test]
+    fn overlapping_windows() {
+        let path = Path::new("foo.py");
+        let code = "def f():\n    a = 1\n    b = 2\n    c = 3\n    return a + b + c\n";
+        let parser = PythonParser::new(code.as_bytes().to_vec(), path, None);
+        let options = ChunkOptions {
+            max_tokens: 5,
+            overlap_lines: 1,
+            include_imports: false,
+        };
+        let chunks = chunk_code(&parser, path, &options);
+        assert_eq!(ranges(&chunks), [(1, 2), (2, 3), (3, 4), (4, 4), (5, 5)]);
+        assert!(chunks
+            .iter()
+            .all(|chunk| chunk.name.as_deref() == Some("f")));
+    }
+}
@@ -0,0 +1,199 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{history::Commit, spaces::FuncSpace};
+
+/// How often a file changed in a git history.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FileChurn {
+    /// The number of commits changing the file
+    pub commits: usize,
+    /// The number of added and removed lines
+    pub lines: usize,
+}
+
+/// Returns the churn of the files changed by some commits, keyed by
+/// their paths relative to the root of the repository.
+pub fn file_churn(commits: &[Commit]) -> HashMap<PathBuf, FileChurn> {
+    let mut churn: HashMap<PathBuf, FileChurn> = HashMap::new();
+    for change in commits.iter().flat_map(|commit| commit.changes.iter()) {
+        let file = churn.entry(change.path.clone()).or_default();
+        file.commits += 1;
+        file.lines += change.added + change.removed;
+    }
+    churn
+}
+
+/// The quadrant of a file in the churn versus complexity plane.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Quadrant {
+    /// Complex code which changes often: the hotspots to refactor first
+    ComplexChurning,
+    /// Complex code which rarely changes
+    ComplexStable,
+    /// Simple code which changes often
+    SimpleChurning,
+    /// Simple code which rarely changes
+    SimpleStable,
+}
+
+/// The thresholds splitting the files into quadrants.
+///
+/// A `None` threshold is the median of the analyzed files.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct QuadrantPolicy {
+    /// The cyclomatic complexity above which a file is complex
+    pub cyclomatic: Option<f64>,
+    /// The number of commits above which a file is churning
+    pub commits: Option<f64>,
+}
+
+/// A file classified by churn and complexity.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Hotspot {
+    /// The path of the file
+    pub path: PathBuf,
+    /// The cyclomatic complexity of the file
+    pub cyclomatic: f64,
+    /// The churn of the file
+    pub churn: FileChurn,
+    /// The quadrant of the file
+    pub quadrant: Quadrant,
+}
+
+fn median(mut values: Vec<f64>) -> f64 {
+    if values.is_empty() {
+        return 0.;
+    }
+    values.sort_by(f64::total_cmp);
+    let middle = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[middle - 1] + values[middle]) / 2.
+    } else {
+        values[middle]
+    }
+}
+
+/// Classifies some analyzed files into churn and complexity quadrants.
+///
+/// The paths of the files are made relative to `root` to look up their
+/// churn, and files missing from the churn never changed. The hotspots
+/// are sorted by decreasing product of complexity and commits.
+pub fn classify_hotspots(
+    files: &[(PathBuf, FuncSpace)],
+    churn: &HashMap<PathBuf, FileChurn>,
+    root: &Path,
+    policy: &QuadrantPolicy,
+) -> Vec<Hotspot> {
+    let mut hotspots = files
+        .iter()
+        .map(|(path, space)| {
+            let relative = path.strip_prefix(root).unwrap_or(path);
+            (
+                path.clone(),
+                space.metrics.cyclomatic.cyclomatic_sum(),
+                churn.get(relative).cloned().unwrap_or_default(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let max_cyclomatic = policy.cyclomatic.unwrap_or_else(|| {
+        median(
+            hotspots
+                .iter()
+                .map(|(_, cyclomatic, _)| *cyclomatic)
+                .collect(),
+        )
+    });
+    let max_commits = policy.commits.unwrap_or_else(|| {
+        median(
+            hotspots
+                .iter()
+                .map(|(_, _, churn)| churn.commits as f64)
+                .collect(),
+        )
+    });
+
+    hotspots.sort_by(|a, b| (b.1 * b.2.commits as f64).total_cmp(&(a.1 * a.2.commits as f64)));
+    hotspots
+        .into_iter()
+        .map(|(path, cyclomatic, churn)| {
+            let complex = cyclomatic > max_cyclomatic;
+            let churning = churn.commits as f64 > max_commits;
+            let quadrant = match (complex, churning) {
+                (true, true) => Quadrant::ComplexChurning,
+                (true, false) => Quadrant::ComplexStable,
+                (false, true) => Quadrant::SimpleChurning,
+                (false, false) => Quadrant::SimpleStable,
+            };
+            Hotspot {
+                path,
+                cyclomatic,
+                churn,
+                quadrant,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{metrics, parse_git_log, ParserEngineRust, ParserTrait};
+
+    use super::*;
+
+    fn file(path: &str, code: &str) -> (PathBuf, FuncSpace) {
+        let path = PathBuf::from(path);
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), &path, None);
+        let space = metrics(&parser, &path).unwrap();
+        (path, space)
+    }
+
+    #[test]
+    fn churn_quadrants() {
+        let log = "\x1eAda\x1fada@example.com\n\n3\t1\tsrc/a.rs\n1\t1\tsrc/c.rs\n\
+                   \x1eAda\x1fada@example.com\n\n2\t0\tsrc/a.rs\n\
+                   \x1eBob\x1fbob@example.com\n\n1\t0\tsrc/c.rs\n";
+        let churn = file_churn(&parse_git_log(log));
+        assert_eq!(
+            churn[Path::new("src/a.rs")],
+            FileChurn {
+                commits: 2,
+                lines: 6
+            }
+        );
+
+        let branchy = "fn f(x: i32) {\n    if x > 0 {}\n    if x > 1 {}\n    if x > 2 {}\n}\n";
+        let files = [
+            file("repo/src/a.rs", branchy),
+            file("repo/src/b.rs", branchy),
+            file("repo/src/c.rs", "fn f() {}\n"),
+            file("repo/src/d.rs", "fn f() {}\n"),
+        ];
+        let policy = QuadrantPolicy {
+            cyclomatic: Some(3.),
+            commits: Some(1.),
+        };
+        let hotspots = classify_hotspots(&files, &churn, Path::new("repo"), &policy);
+
+        let quadrants = hotspots
+            .iter()
+            .map(|hotspot| (hotspot.path.to_str().unwrap(), hotspot.quadrant))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            quadrants,
+            [
+                ("repo/src/a.rs", Quadrant::ComplexChurning),
+                ("repo/src/c.rs", Quadrant::SimpleChurning),
+                ("repo/src/b.rs", Quadrant::ComplexStable),
+                ("repo/src/d.rs", Quadrant::SimpleStable),
+            ]
+        );
+    }
+}
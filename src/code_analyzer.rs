@@ -1,10 +1,29 @@
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
 
+use serde::Serialize;
+
+use crate::ai::smell_catalog::{SmellCatalog, SmellRule};
+use crate::analysis_config::Profile;
+
+use crate::coverage::code_unhandled_kinds;
+use crate::encoding::{decode_source, DecodeMode, EncodingError};
+use crate::heuristic::{estimate_metrics, HeuristicMetrics};
+use crate::macros::{trace_event, trace_span};
+use crate::markdown::{code_blocks, BlockMetrics, MarkdownAnalysis};
+use crate::metadata::AnalysisMetadata;
 use crate::parser_registry::ParserRegistry;
-use crate::preproc::PreprocResults;
-use crate::{get_function_spaces, spaces::FuncSpace, LANG};
+use crate::path_base::PathBase;
+use crate::preproc::{fix_includes, preprocess, PreprocResults};
+use crate::profile::{profile_with_nesting, FileProfile};
+use crate::spaces::{file_space, CodeMetrics, FuncSpace, NestedAttribution, SpaceGranularity};
+use crate::traits::{Callback, ParserTrait};
+use crate::vfs::VirtualFs;
+use crate::{action, PreprocParser, LANG};
 
 /// Error returned by the [`SingularityCodeAnalyzer`].
 #[derive(Debug)]
@@ -15,6 +34,8 @@ pub enum AnalyzerError {
     AnalysisFailed { language: LANG, reason: String },
     /// I/O error while reading the source under analysis.
     Io(std::io::Error),
+    /// The source is not valid UTF-8 and was skipped by the strict decoding mode.
+    Encoding(EncodingError),
 }
 
 impl fmt::Display for AnalyzerError {
@@ -33,6 +54,7 @@ impl fmt::Display for AnalyzerError {
                 language, reason
             ),
             AnalyzerError::Io(err) => write!(f, "failed to read source: {}", err),
+            AnalyzerError::Encoding(err) => err.fmt(f),
         }
     }
 }
@@ -41,6 +63,7 @@ impl std::error::Error for AnalyzerError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             AnalyzerError::Io(err) => Some(err),
+            AnalyzerError::Encoding(err) => Some(err),
             _ => None,
         }
     }
@@ -52,6 +75,12 @@ impl From<std::io::Error> for AnalyzerError {
     }
 }
 
+impl From<EncodingError> for AnalyzerError {
+    fn from(value: EncodingError) -> Self {
+        AnalyzerError::Encoding(value)
+    }
+}
+
 /// Result of a language analysis request.
 #[derive(Debug, Clone)]
 pub struct AnalyzerResult {
@@ -59,6 +88,12 @@ pub struct AnalyzerResult {
     pub language: LANG,
     /// Root function space containing nested spaces and metrics.
     pub root_space: FuncSpace,
+    /// Original encoding of the source, which was transcoded to UTF-8 when it differs.
+    pub encoding: &'static str,
+    /// Provenance of the result: tool and grammar versions, metrics and timing.
+    pub metadata: AnalysisMetadata,
+    /// Parse and metric times of the source, when profiling was requested.
+    pub profile: Option<FileProfile>,
 }
 
 impl AnalyzerResult {
@@ -68,6 +103,23 @@ impl AnalyzerResult {
     }
 }
 
+/// Result of analyzing a file which may be in an unsupported language.
+#[derive(Debug, Clone)]
+pub enum FileAnalysis {
+    /// The file was parsed and its metrics computed.
+    Parsed(Box<AnalyzerResult>),
+    /// The file is in an unsupported language, so its metrics were
+    /// estimated from its lines and tokens.
+    Heuristic(HeuristicMetrics),
+}
+
+impl FileAnalysis {
+    /// Returns `true` if the metrics were estimated.
+    pub fn is_heuristic(&self) -> bool {
+        matches!(self, FileAnalysis::Heuristic(_))
+    }
+}
+
 /// Options for running the analyzer over in-memory content.
 #[derive(Debug, Clone, Default)]
 pub struct AnalyzeOptions<'a> {
@@ -75,6 +127,115 @@ pub struct AnalyzeOptions<'a> {
     pub virtual_path: Option<&'a Path>,
     /// Optional preprocessing results (macros, includes, ...).
     pub preprocessor: Option<Arc<PreprocResults>>,
+    /// How sources which aren't valid UTF-8 are decoded.
+    pub decode_mode: DecodeMode,
+    /// Record the parse time, metric time and node count of the source.
+    pub profile: bool,
+    /// How the metrics of nested functions and inner classes are attributed.
+    pub nested: NestedAttribution,
+    /// The deepest function spaces listed in the results.
+    pub granularity: SpaceGranularity,
+    /// The base of the path naming the root space, which is written as
+    /// given if `None`.
+    pub path_base: Option<PathBase>,
+    /// Record in the metadata the node kinds which no checker or getter
+    /// handled, to find the coverage gaps of the metrics.
+    pub coverage: bool,
+    /// Skip the Halstead metrics and the maintainability index, the
+    /// slowest to compute, which are suppressed in the results.
+    pub skip_halstead: bool,
+}
+
+/// Options for warming up the analyzer.
+#[derive(Debug, Clone, Default)]
+pub struct WarmUpOptions {
+    /// The common `C/C++` headers, with their content, whose macros are
+    /// extracted ahead of the requests.
+    pub headers: Vec<(PathBuf, Vec<u8>)>,
+}
+
+/// The outcome of warming up the analyzer.
+#[derive(Debug, Clone)]
+pub struct WarmUp {
+    /// The languages whose parsers and metrics were initialized.
+    pub languages: Vec<LANG>,
+    /// The preprocessor data of the headers, to be passed to the requests
+    /// in [`AnalyzeOptions::preprocessor`], if there were headers.
+    pub preprocessor: Option<Arc<PreprocResults>>,
+    /// How long the warm-up took.
+    pub duration: Duration,
+}
+
+/// A language supported by the analyzer.
+#[derive(Debug, Clone, Serialize)]
+pub struct LanguageCapability {
+    /// The name of the language
+    pub name: &'static str,
+    /// The extensions of the files in the language
+    pub extensions: Vec<String>,
+}
+
+/// What the analyzer supports, so that its frontends build their options
+/// from it rather than hardcoding them.
+#[derive(Debug, Clone, Serialize)]
+pub struct Capabilities {
+    /// The version of the crate
+    pub version: &'static str,
+    /// The supported languages, sorted by name
+    pub languages: Vec<LanguageCapability>,
+    /// The names of the metrics, as serialized
+    pub metrics: Vec<&'static str>,
+    /// The rules of the findings, with their default thresholds and
+    /// severities
+    pub rules: Vec<SmellRule>,
+    /// The output formats of the results
+    pub output_formats: Vec<&'static str>,
+    /// The compressions of the binary output formats
+    pub compressions: Vec<&'static str>,
+    /// The analysis profiles
+    pub profiles: Vec<Profile>,
+}
+
+// A small code of each language, meeting the nodes which the lazily
+// initialized tables of the metrics are built for: comments, calls,
+// branches and strings.
+fn warm_up_code(language: LANG) -> &'static str {
+    match language {
+        LANG::Javascript | LANG::Typescript | LANG::Tsx => {
+            "// TODO\nfunction f(a) {\n  return a ? g(\"a\") : 0;\n}\n"
+        }
+        LANG::Java | LANG::Csharp => {
+            "// TODO\nclass A {\n  int f(int a) { return a > 0 ? g(\"a\") : 0; }\n}\n"
+        }
+        LANG::Rust => "// TODO\nfn f(a: u32) -> u32 {\n    if a > 0 { g(\"a\") } else { 0 }\n}\n",
+        LANG::Cpp => "// TODO\n#include <a.h>\nint f(int a) {\n  return a > 0 ? g(\"a\") : 0;\n}\n",
+        LANG::Python => "# TODO\ndef f(a):\n    return g(\"a\") if a else 0\n",
+        LANG::Elixir => {
+            "# TODO\ndefmodule A do\n  def f(a), do: if(a, do: g(\"a\"), else: 0)\nend\n"
+        }
+        LANG::Erlang => "% TODO\nf(A) ->\n    case A of true -> g(\"a\"); _ -> 0 end.\n",
+        LANG::Gleam => {
+            "// TODO\npub fn f(a) {\n  case a {\n    True -> g(\"a\")\n    _ -> 0\n  }\n}\n"
+        }
+        LANG::Lua => "-- TODO\nfunction f(a)\n  if a then return g(\"a\") end\n  return 0\nend\n",
+        LANG::Go => {
+            "// TODO\npackage a\nfunc f(a bool) int {\n\tif a { return 0 }\n\treturn g(\"a\")\n}\n"
+        }
+    }
+}
+
+struct Spaces;
+
+impl Callback for Spaces {
+    type Res = Option<FuncSpace>;
+    type Cfg = (PathBuf, NestedAttribution, SpaceGranularity, bool);
+
+    fn call<T: ParserTrait>(
+        (path, nested, granularity, halstead): Self::Cfg,
+        parser: &T,
+    ) -> Self::Res {
+        file_space(parser, &path, nested, granularity, false, halstead)
+    }
 }
 
 /// High-level façade for running Singularity's multi-language metrics engine.
@@ -131,19 +292,35 @@ impl SingularityCodeAnalyzer {
         })
     }
 
+    /// Attempt to map the language tag of a fenced code block, like `rust`,
+    /// `py` or `cpp`, to an internal [`LANG`], by name or by extension.
+    pub fn language_from_tag(&self, tag: &str) -> Option<LANG> {
+        if tag.is_empty() {
+            return None;
+        }
+        self.language_from_str(tag)
+            .or_else(|| self.detect_language_from_path(Path::new(&format!("block.{tag}"))))
+    }
+
     /// Detect the language for the given file path using the registry's extension table.
     pub fn detect_language_from_path(&self, path: &Path) -> Option<LANG> {
         self.registry.detect_language_from_path(path)
     }
 
     /// Analyze the provided source buffer for the specified language.
+    ///
+    /// Sources which aren't valid UTF-8, like Latin-1 or Shift-JIS files, are
+    /// transcoded according to [`AnalyzeOptions::decode_mode`].
     pub fn analyze_language<'a>(
         &self,
         language: LANG,
         source: impl AsRef<[u8]>,
         options: AnalyzeOptions<'a>,
     ) -> Result<AnalyzerResult, AnalyzerError> {
+        let started = SystemTime::now();
+        trace_span!("analyze", language = ?language, path = ?options.virtual_path);
         if self.registry.get_factory(&language).is_none() {
+            trace_event!(warn, language = ?language, "unsupported language");
             return Err(AnalyzerError::UnsupportedLanguage(
                 language.get_name().to_string(),
             ));
@@ -154,19 +331,159 @@ impl SingularityCodeAnalyzer {
             .map(PathBuf::from)
             .unwrap_or_else(|| PathBuf::from(format!("memory.{}", language.get_name())));
 
-        let buffer = source.as_ref().to_vec();
-        let root_space = get_function_spaces(&language, buffer, &path_buf, options.preprocessor)
-            .ok_or_else(|| AnalyzerError::AnalysisFailed {
-                language,
-                reason: "metric pipeline returned no data".to_string(),
-            })?;
+        let source = decode_source(source.as_ref().to_vec(), options.decode_mode)?;
+        let unhandled_kinds = options
+            .coverage
+            .then(|| code_unhandled_kinds(source.code.clone(), language));
+        let (root_space, profile) = if options.profile {
+            let (space, profile) = profile_with_nesting(
+                &language,
+                source.code,
+                &path_buf,
+                options.preprocessor,
+                options.nested,
+                options.granularity,
+                !options.skip_halstead,
+            );
+            (space, Some(profile))
+        } else {
+            let cfg = (
+                path_buf.clone(),
+                options.nested,
+                options.granularity,
+                !options.skip_halstead,
+            );
+            let space =
+                action::<Spaces>(&language, source.code, &path_buf, options.preprocessor, cfg);
+            (space, None)
+        };
+        let mut root_space = root_space.ok_or_else(|| AnalyzerError::AnalysisFailed {
+            language,
+            reason: "metric pipeline returned no data".to_string(),
+        })?;
+        if let Some(base) = &options.path_base {
+            base.relativize_space(&path_buf, &mut root_space);
+        }
 
+        let mut metadata = AnalysisMetadata::new(&[language], &root_space.metrics, started);
+        if let Some(kinds) = unhandled_kinds {
+            metadata.add_unhandled_kinds(language, &kinds);
+        }
         Ok(AnalyzerResult {
             language,
             root_space,
+            encoding: source.encoding,
+            metadata,
+            profile,
         })
     }
 
+    /// Returns the supported languages, metrics, finding rules and output
+    /// formats.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use singularity_code_analysis::SingularityCodeAnalyzer;
+    ///
+    /// let capabilities = SingularityCodeAnalyzer::new().capabilities();
+    /// assert!(capabilities.metrics.contains(&"cyclomatic"));
+    /// assert!(capabilities.output_formats.contains(&"json"));
+    /// ```
+    pub fn capabilities(&self) -> Capabilities {
+        let mut languages = self
+            .supported_languages()
+            .into_iter()
+            .map(|language| LanguageCapability {
+                name: language.get_name(),
+                extensions: self
+                    .registry
+                    .get_factory(&language)
+                    .map(|factory| {
+                        factory
+                            .get_extensions()
+                            .into_iter()
+                            .map(str::to_string)
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            })
+            .collect::<Vec<_>>();
+        languages.sort_by_key(|language| language.name);
+
+        let mut output_formats = vec!["text", "json"];
+        if cfg!(feature = "binary") {
+            output_formats.extend(["msgpack", "cbor"]);
+        }
+        let mut compressions = vec!["none"];
+        if cfg!(all(feature = "binary", feature = "zstd")) {
+            compressions.push("zstd");
+        }
+
+        Capabilities {
+            version: env!("CARGO_PKG_VERSION"),
+            languages,
+            metrics: CodeMetrics::all_metrics().to_vec(),
+            rules: SmellCatalog::default().rules,
+            output_formats,
+            compressions,
+            profiles: vec![Profile::Ide, Profile::Ci, Profile::Deep],
+        }
+    }
+
+    /// Initializes the parsers and the metrics of all the supported
+    /// languages, and preprocesses the common headers, so that the first
+    /// requests of a long-running service don't pay for it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use singularity_code_analysis::{SingularityCodeAnalyzer, WarmUpOptions};
+    ///
+    /// let analyzer = SingularityCodeAnalyzer::new();
+    /// let warm_up = analyzer.warm_up(WarmUpOptions::default());
+    /// assert_eq!(warm_up.languages.len(), analyzer.supported_languages().len());
+    /// ```
+    pub fn warm_up(&self, options: WarmUpOptions) -> WarmUp {
+        let started = Instant::now();
+        trace_span!("warm_up", headers = options.headers.len());
+
+        let mut languages = self.supported_languages();
+        languages.sort_by_key(|language| language.get_name());
+        languages.retain(|&language| {
+            let warmed = self
+                .analyze_language(language, warm_up_code(language), AnalyzeOptions::default())
+                .is_ok();
+            if !warmed {
+                trace_event!(warn, language = ?language, "failed to warm up");
+            }
+            warmed
+        });
+
+        let preprocessor = (!options.headers.is_empty()).then(|| {
+            let mut results = PreprocResults::default();
+            let mut all_files: HashMap<String, Vec<PathBuf>> = HashMap::new();
+            for (path, code) in options.headers {
+                let parser = PreprocParser::new(code, &path, None);
+                preprocess(&parser, &path, &mut results);
+                if let Some(name) = path.file_name() {
+                    all_files
+                        .entry(name.to_string_lossy().to_string())
+                        .or_default()
+                        .push(path);
+                }
+            }
+            fix_includes(&mut results.files, &all_files);
+            Arc::new(results)
+        });
+
+        WarmUp {
+            languages,
+            preprocessor,
+            duration: started.elapsed(),
+        }
+    }
+
     /// Analyze a file on disk. The language is detected from the file extension if possible.
     pub fn analyze_file(&self, path: &Path) -> Result<AnalyzerResult, AnalyzerError> {
         let contents = std::fs::read(path)?;
@@ -176,4 +493,282 @@ impl SingularityCodeAnalyzer {
 
         self.analyze_language(language, contents, AnalyzeOptions::default())
     }
+
+    /// Analyze a source which isn't on disk, like an unsaved editor buffer.
+    ///
+    /// The language is detected from the extension of `path`, which is also
+    /// used to name the source in the results.
+    pub fn analyze_contents(
+        &self,
+        path: &Path,
+        contents: impl AsRef<[u8]>,
+    ) -> Result<AnalyzerResult, AnalyzerError> {
+        let language = self
+            .detect_language_from_path(path)
+            .ok_or_else(|| AnalyzerError::UnsupportedLanguage(path.display().to_string()))?;
+        let options = AnalyzeOptions {
+            virtual_path: Some(path),
+            ..Default::default()
+        };
+
+        self.analyze_language(language, contents, options)
+    }
+
+    /// Analyze a source read from a reader, e.g. `std::io::stdin()`.
+    ///
+    /// See [`analyze_contents`](Self::analyze_contents) for the role of `path`.
+    pub fn analyze_reader(
+        &self,
+        path: &Path,
+        mut reader: impl Read,
+    ) -> Result<AnalyzerResult, AnalyzerError> {
+        let mut contents = Vec::new();
+        reader.read_to_end(&mut contents)?;
+
+        self.analyze_contents(path, contents)
+    }
+
+    /// Analyze a source which isn't on disk, estimating its metrics with
+    /// [`estimate_metrics`] when its language is unsupported, so it still
+    /// counts in the aggregates of a repository.
+    ///
+    /// See [`analyze_contents`](Self::analyze_contents) for the role of `path`.
+    pub fn analyze_contents_or_estimate(
+        &self,
+        path: &Path,
+        contents: impl AsRef<[u8]>,
+    ) -> Result<FileAnalysis, AnalyzerError> {
+        match self.analyze_contents(path, contents.as_ref()) {
+            Err(AnalyzerError::UnsupportedLanguage(_)) => {
+                trace_event!(debug, path = ?path, "estimating the metrics");
+                Ok(FileAnalysis::Heuristic(estimate_metrics(contents.as_ref())))
+            }
+            result => result.map(|result| FileAnalysis::Parsed(Box::new(result))),
+        }
+    }
+
+    /// Analyze a file on disk, estimating its metrics when its language
+    /// is unsupported.
+    ///
+    /// See [`analyze_contents_or_estimate`](Self::analyze_contents_or_estimate).
+    pub fn analyze_file_or_estimate(&self, path: &Path) -> Result<FileAnalysis, AnalyzerError> {
+        let contents = std::fs::read(path)?;
+
+        self.analyze_contents_or_estimate(path, contents)
+    }
+
+    /// Analyze the fenced code blocks of a Markdown document, each with the
+    /// parser of the language of its tag.
+    ///
+    /// The lines of the metrics of a block are the lines of the document,
+    /// and `path` names the document in the results.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// use singularity_code_analysis::SingularityCodeAnalyzer;
+    ///
+    /// let markdown = "# Example\n\n```rust\nfn f() {}\n```\n\n```text\nf\n```\n";
+    /// let analyzer = SingularityCodeAnalyzer::new();
+    /// let analysis = analyzer.analyze_markdown(Path::new("README.md"), markdown);
+    /// assert_eq!(analysis.blocks[0].language, "rust");
+    /// assert_eq!(analysis.blocks[0].space.spaces[0].start_line, 4);
+    /// assert_eq!(analysis.skipped[0].tag, "text");
+    /// ```
+    pub fn analyze_markdown(&self, path: &Path, contents: impl AsRef<[u8]>) -> MarkdownAnalysis {
+        let markdown = String::from_utf8_lossy(contents.as_ref());
+        let mut analysis = MarkdownAnalysis::default();
+        for block in code_blocks(&markdown) {
+            let Some(language) = self.language_from_tag(&block.tag) else {
+                analysis.skipped.push(block);
+                continue;
+            };
+            let options = AnalyzeOptions {
+                virtual_path: Some(path),
+                ..Default::default()
+            };
+            match self.analyze_language(language, &block.code, options) {
+                Ok(mut result) => {
+                    result.root_space.shift_lines(block.start_line - 1);
+                    analysis.blocks.push(BlockMetrics {
+                        tag: block.tag,
+                        language: language.get_name(),
+                        space: result.root_space,
+                    });
+                }
+                Err(_) => analysis.skipped.push(block),
+            }
+        }
+        analysis
+    }
+
+    /// Analyze all the files of a virtual filesystem, keyed by path.
+    ///
+    /// Files in an unsupported language get an
+    /// [`AnalyzerError::UnsupportedLanguage`] result.
+    pub fn analyze_virtual_fs(
+        &self,
+        vfs: &VirtualFs,
+    ) -> BTreeMap<PathBuf, Result<AnalyzerResult, AnalyzerError>> {
+        vfs.iter()
+            .map(|(path, contents)| (path.to_path_buf(), self.analyze_contents(path, contents)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn analyze_in_memory_sources() {
+        let analyzer = SingularityCodeAnalyzer::new();
+
+        let result = analyzer
+            .analyze_reader(Path::new("stdin.rs"), &b"fn f() {}\nfn g() {}\n"[..])
+            .unwrap();
+        assert_eq!(result.language, LANG::Rust);
+        assert_eq!(result.root_space.name.as_deref(), Some("stdin.rs"));
+        assert_eq!(result.root_space.spaces.len(), 2);
+
+        let vfs = [("gen/a.py", "def a():\n    pass\n"), ("notes.txt", "hello")]
+            .into_iter()
+            .collect::<VirtualFs>();
+        let results = analyzer.analyze_virtual_fs(&vfs);
+        assert_eq!(
+            results[Path::new("gen/a.py")].as_ref().unwrap().language,
+            LANG::Python
+        );
+        assert!(matches!(
+            results[Path::new("notes.txt")],
+            Err(AnalyzerError::UnsupportedLanguage(_))
+        ));
+    }
+    #[test]
+    fn analyze_markdown_blocks() {
+        let markdown = "# Title
+
+```py
+def f(a):
+    if a:
+        return 1
+    return 0
+```
+
+```sh
+ls
+```
+
+~~~javascript
+function g() {}
+~~~
+";
+        let analysis =
+            SingularityCodeAnalyzer::new().analyze_markdown(Path::new("README.md"), markdown);
+
+        let languages = analysis
+            .blocks
+            .iter()
+            .map(|block| block.language)
+            .collect::<Vec<_>>();
+        assert_eq!(languages, ["python", "javascript"]);
+        let f = &analysis.blocks[0].space.spaces[0];
+        assert_eq!((f.start_line, f.end_line), (4, 7));
+        assert_eq!(f.metrics.cyclomatic.cyclomatic(), 2.);
+        assert_eq!(analysis.skipped[0].tag, "sh");
+    }
+
+    #[test]
+    fn record_unhandled_kinds() {
+        let analyzer = SingularityCodeAnalyzer::new();
+        let code = "fn f(a: u32) -> u32 {\n    a + 1\n}\n";
+
+        let result = analyzer
+            .analyze_language(LANG::Rust, code, AnalyzeOptions::default())
+            .unwrap();
+        assert!(result.metadata.unhandled_kinds.is_empty());
+
+        let options = AnalyzeOptions {
+            coverage: true,
+            ..Default::default()
+        };
+        let result = analyzer
+            .analyze_language(LANG::Rust, code, options)
+            .unwrap();
+        assert_eq!(result.metadata.unhandled_kinds["rust"]["parameters"], 1);
+    }
+
+    #[test]
+    fn capabilities() {
+        let analyzer = SingularityCodeAnalyzer::new();
+        let capabilities = analyzer.capabilities();
+
+        assert_eq!(
+            capabilities.languages.len(),
+            analyzer.supported_languages().len()
+        );
+        let rust = capabilities
+            .languages
+            .iter()
+            .find(|language| language.name == "rust")
+            .unwrap();
+        assert_eq!(rust.extensions, ["rs"]);
+        assert!(capabilities
+            .rules
+            .iter()
+            .any(|rule| rule.id == crate::SQL_INJECTION));
+
+        let json = serde_json::to_value(&capabilities).unwrap();
+        assert_eq!(json["profiles"][0], "ide");
+        assert_eq!(json["metrics"].as_array().unwrap().len(), 15);
+    }
+
+    #[test]
+    fn warm_up_languages() {
+        let analyzer = SingularityCodeAnalyzer::new();
+        let headers = vec![
+            (
+                PathBuf::from("include/config.h"),
+                b"#define DEBUG 1\n".to_vec(),
+            ),
+            (
+                PathBuf::from("include/util.h"),
+                b"#include \"config.h\"\n#define MAX(a, b) a\n".to_vec(),
+            ),
+        ];
+
+        let warm_up = analyzer.warm_up(WarmUpOptions { headers });
+        assert_eq!(
+            warm_up.languages.len(),
+            analyzer.supported_languages().len()
+        );
+
+        let preprocessor = warm_up.preprocessor.unwrap();
+        let util = &preprocessor.files[Path::new("include/util.h")];
+        assert!(util.macros.contains("MAX"));
+        assert_eq!(preprocessor.files.len(), 2);
+    }
+
+    #[test]
+    fn estimate_unsupported_languages() {
+        let analyzer = SingularityCodeAnalyzer::new();
+
+        let analysis = analyzer
+            .analyze_contents_or_estimate(Path::new("build.zig"), "// Build\nfn main() {}\n")
+            .unwrap();
+        match analysis {
+            FileAnalysis::Heuristic(metrics) => {
+                assert!(metrics.heuristic);
+                assert_eq!((metrics.sloc, metrics.comment_lines), (1, 1));
+            }
+            FileAnalysis::Parsed(_) => panic!("zig is not supported"),
+        }
+
+        let analysis = analyzer
+            .analyze_contents_or_estimate(Path::new("main.rs"), "fn main() {}\n")
+            .unwrap();
+        assert!(!analysis.is_heuristic());
+    }
 }
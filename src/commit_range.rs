@@ -0,0 +1,238 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+
+use crate::{
+    history::git,
+    langs::get_function_spaces,
+    spaces::FuncSpace,
+    tools::{guess_language, normalize_source, remove_blank_lines},
+};
+
+/// How a file changed in a commit range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileStatus {
+    /// The file only exists at the new revision
+    Added,
+    /// The file exists at both revisions
+    Modified,
+    /// The file only exists at the old revision
+    Deleted,
+}
+
+/// The main metrics of a file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+pub struct FileMetrics {
+    /// The cyclomatic complexity
+    pub cyclomatic: f64,
+    /// The cognitive complexity
+    pub cognitive: f64,
+    /// The number of source lines
+    pub sloc: f64,
+    /// The maintainability index, as computed by Visual Studio
+    pub mi: f64,
+}
+
+impl FileMetrics {
+    /// Returns the main metrics of the function spaces of a file.
+    pub fn new(space: &FuncSpace) -> Self {
+        let metrics = &space.metrics;
+        Self {
+            cyclomatic: metrics.cyclomatic.cyclomatic_sum(),
+            cognitive: metrics.cognitive.cognitive_sum(),
+            sloc: metrics.loc.sloc(),
+            mi: metrics.mi.mi_visual_studio(),
+        }
+    }
+
+    fn delta(&self, before: &Self) -> Self {
+        Self {
+            cyclomatic: self.cyclomatic - before.cyclomatic,
+            cognitive: self.cognitive - before.cognitive,
+            sloc: self.sloc - before.sloc,
+            mi: self.mi - before.mi,
+        }
+    }
+}
+
+/// The metrics of a file touched by a commit range.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FileDelta {
+    /// The path of the file, relative to the root of the repository
+    pub path: PathBuf,
+    /// How the file changed
+    pub status: FileStatus,
+    /// The metrics at the old revision, if the file existed
+    pub before: Option<FileMetrics>,
+    /// The metrics at the new revision, if the file exists
+    pub after: Option<FileMetrics>,
+    /// The metrics at the new revision minus the ones at the old revision
+    ///
+    /// It is `None` for added and deleted files
+    pub delta: Option<FileMetrics>,
+}
+
+/// The analysis of the files touched by a commit range.
+///
+/// Pass `before` and `after` to [`diff_spaces`] to compare the
+/// functions of the files.
+///
+/// [`diff_spaces`]: crate::diff_spaces
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct RangeReport {
+    /// The metrics of the files, in the order of git
+    pub files: Vec<FileDelta>,
+    /// The function spaces of the files at the old revision
+    pub before: Vec<(PathBuf, FuncSpace)>,
+    /// The function spaces of the files at the new revision
+    pub after: Vec<(PathBuf, FuncSpace)>,
+}
+
+// The files touched between two revisions, with their status
+fn touched_files(
+    repository: &Path,
+    old: &str,
+    new: &str,
+) -> io::Result<Vec<(FileStatus, PathBuf)>> {
+    let diff = git(
+        repository,
+        &["diff", "--name-status", "--no-renames", "-z", old, new],
+    )?;
+    let mut fields = diff.split(|byte| *byte == 0);
+    let mut files = Vec::new();
+    while let (Some(status), Some(path)) = (fields.next(), fields.next()) {
+        let status = match status.first() {
+            Some(b'A') => FileStatus::Added,
+            Some(b'D') => FileStatus::Deleted,
+            Some(_) => FileStatus::Modified,
+            None => break,
+        };
+        files.push((
+            status,
+            PathBuf::from(String::from_utf8_lossy(path).as_ref()),
+        ));
+    }
+    Ok(files)
+}
+
+// The function spaces of a file at a revision, if its language is supported
+pub(crate) fn spaces_at(
+    repository: &Path,
+    revision: &str,
+    path: &Path,
+) -> io::Result<Option<FuncSpace>> {
+    let object = format!("{revision}:{}", path.display());
+    let mut source = normalize_source(git(repository, &["show", object.as_str()])?);
+    remove_blank_lines(&mut source);
+
+    let Some(language) = guess_language(&source, path).0 else {
+        return Ok(None);
+    };
+    Ok(get_function_spaces(&language, source, path, None))
+}
+
+/// Analyzes the files touched by a commit range of a git repository.
+///
+/// Only the files changed between the `old` and `new` revisions are
+/// analyzed, at both revisions, so the cost depends on the size of the
+/// change instead of the size of the repository. Files in unsupported
+/// languages are skipped. `git` must be in the `PATH`.
+///
+/// The revisions are anything accepted by `git diff`, e.g.
+/// `origin/main` and `HEAD` for a pull request.
+pub fn analyze_commit_range(repository: &Path, old: &str, new: &str) -> io::Result<RangeReport> {
+    let mut report = RangeReport::default();
+
+    for (status, path) in touched_files(repository, old, new)? {
+        let before = match status {
+            FileStatus::Added => None,
+            _ => spaces_at(repository, old, &path)?,
+        };
+        let after = match status {
+            FileStatus::Deleted => None,
+            _ => spaces_at(repository, new, &path)?,
+        };
+        if before.is_none() && after.is_none() {
+            continue;
+        }
+
+        let before_metrics = before.as_ref().map(FileMetrics::new);
+        let after_metrics = after.as_ref().map(FileMetrics::new);
+        report.files.push(FileDelta {
+            path: path.clone(),
+            status,
+            before: before_metrics,
+            after: after_metrics,
+            delta: before_metrics
+                .zip(after_metrics)
+                .map(|(before, after)| after.delta(&before)),
+        });
+        report
+            .before
+            .extend(before.map(|space| (path.clone(), space)));
+        report.after.extend(after.map(|space| (path, space)));
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::history::test_git;
+
+    #[test]
+    fn commit_range() {
+        let repository = std::env::temp_dir().join(format!("sca-range-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&repository);
+        fs::create_dir_all(&repository).unwrap();
+        test_git(&repository, &["init", "-q"]);
+
+        fs::write(
+            repository.join("a.rs"),
+            "fn f(x: i32) {\n    if x > 0 {}\n}\n",
+        )
+        .unwrap();
+        fs::write(repository.join("b.rs"), "fn g() {}\n").unwrap();
+        fs::write(repository.join("notes.txt"), "Some notes\n").unwrap();
+        test_git(&repository, &["add", "-A"]);
+        test_git(&repository, &["commit", "-qm", "old"]);
+
+        fs::write(
+            repository.join("a.rs"),
+            "fn f(x: i32) {\n    if x > 0 {}\n    if x > 1 {}\n}\n",
+        )
+        .unwrap();
+        fs::remove_file(repository.join("b.rs")).unwrap();
+        fs::write(repository.join("c.rs"), "fn h() {}\n").unwrap();
+        fs::write(repository.join("notes.txt"), "More notes\n").unwrap();
+        test_git(&repository, &["add", "-A"]);
+        test_git(&repository, &["commit", "-qm", "new"]);
+
+        let report = analyze_commit_range(&repository, "HEAD~1", "HEAD").unwrap();
+        fs::remove_dir_all(&repository).unwrap();
+
+        let files = report
+            .files
+            .iter()
+            .map(|file| (file.path.to_str().unwrap(), file.status))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            files,
+            [
+                ("a.rs", FileStatus::Modified),
+                ("b.rs", FileStatus::Deleted),
+                ("c.rs", FileStatus::Added),
+            ]
+        );
+        assert_eq!(report.files[0].delta.unwrap().cyclomatic, 1.);
+        assert!(report.files[1].delta.is_none());
+        assert_eq!((report.before.len(), report.after.len()), (2, 2));
+    }
+}
@@ -1,14 +1,25 @@
 use std::{
-    collections::HashMap,
+    any::Any,
+    collections::{HashMap, HashSet},
+    fmt,
+    panic::{catch_unwind, AssertUnwindSafe},
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{Arc, Condvar, Mutex},
     thread,
+    time::Duration,
 };
 
-use crossbeam::channel::{unbounded, Receiver, Sender};
+use crossbeam::channel::{bounded, unbounded, Receiver, RecvTimeoutError, Sender};
 use globset::GlobSet;
+use serde::Serialize;
 use walkdir::{DirEntry, WalkDir};
 
+use crate::{
+    macros::{trace_event, trace_span},
+    sampling::Sampling,
+    third_party::is_third_party_dir,
+};
+
 type ProcFilesFunction<Config> = dyn Fn(PathBuf, &Config) -> std::io::Result<()> + Send + Sync;
 
 type ProcDirPathsFunction<Config> =
@@ -16,6 +27,10 @@ type ProcDirPathsFunction<Config> =
 
 type ProcPathFunction<Config> = dyn Fn(&Path, &Config) + Send + Sync;
 
+#[cfg(feature = "archives")]
+type ProcArchiveEntryFunction<Config> =
+    dyn Fn(PathBuf, Vec<u8>, &Config) -> std::io::Result<()> + Send + Sync;
+
 // Null functions removed at compile time
 fn null_proc_dir_paths<Config>(_: &mut HashMap<String, Vec<PathBuf>>, _: &Path, _: &Config) {}
 fn null_proc_path<Config>(_: &Path, _: &Config) {}
@@ -23,15 +38,236 @@ fn null_proc_path<Config>(_: &Path, _: &Config) {}
 #[derive(Debug)]
 struct JobItem<Config> {
     path: PathBuf,
+    // The size of the file counted in the memory budget
+    size: u64,
     cfg: Arc<Config>,
 }
 
+/// A limit on the files sent to the threads processing them and not yet
+/// processed, which bounds the memory used to analyze huge repositories.
+///
+/// When the budget is spent, the exploration waits for some files to be
+/// processed. A file larger than the whole budget is still processed,
+/// alone.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryBudget {
+    /// The maximum size of the files in flight, in bytes
+    pub max_bytes: Option<u64>,
+    /// The maximum number of files in flight, hence of their results
+    pub max_files: Option<usize>,
+}
+
+#[derive(Debug, Default)]
+struct InFlight {
+    bytes: u64,
+    files: usize,
+}
+
+// Throttles the exploration so that the files in flight fit the budget
+#[derive(Debug, Default)]
+struct Throttle {
+    budget: MemoryBudget,
+    in_flight: Mutex<InFlight>,
+    released: Condvar,
+}
+
+impl Throttle {
+    fn new(budget: MemoryBudget) -> Self {
+        Self {
+            budget,
+            ..Default::default()
+        }
+    }
+
+    fn size(&self, path: &Path) -> u64 {
+        if self.budget.max_bytes.is_none() {
+            return 0;
+        }
+        std::fs::metadata(path).map_or(0, |metadata| metadata.len())
+    }
+
+    // Waits until a file fits in the budget, which it always does when no
+    // other file is in flight
+    fn acquire(&self, size: u64) {
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+        while in_flight.files > 0
+            && !(self
+                .budget
+                .max_bytes
+                .is_none_or(|max| in_flight.bytes + size <= max)
+                && self
+                    .budget
+                    .max_files
+                    .is_none_or(|max| in_flight.files < max))
+        {
+            in_flight = self
+                .released
+                .wait(in_flight)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+        in_flight.bytes += size;
+        in_flight.files += 1;
+    }
+
+    fn release(&self, size: u64) {
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+        in_flight.bytes -= size;
+        in_flight.files -= 1;
+        self.released.notify_all();
+    }
+}
+
+// Releases the budget of a file once it's processed, even if the
+// processing panics
+struct Release<'a> {
+    throttle: &'a Throttle,
+    size: u64,
+}
+
+impl Drop for Release<'_> {
+    fn drop(&mut self) {
+        self.throttle.release(self.size);
+    }
+}
+
+// The files processed under each path, and the files whose processing failed
+type RunOutcome = (HashMap<String, Vec<PathBuf>>, Vec<FileFailure>);
+
 type JobReceiver<Config> = Receiver<Option<JobItem<Config>>>;
 type JobSender<Config> = Sender<Option<JobItem<Config>>>;
 
-fn consumer<Config, ProcFiles>(receiver: JobReceiver<Config>, func: Arc<ProcFiles>)
+/// How the files whose processing fails are handled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FailurePolicy {
+    /// The number of times a file is processed again after it panics or
+    /// times out, before it's quarantined
+    pub retries: usize,
+    /// The longest time a file is processed before it's abandoned
+    ///
+    /// With a timeout, each file is processed on its own thread, which
+    /// keeps running in the background when it times out.
+    pub timeout: Option<Duration>,
+}
+
+/// Why the processing of a file failed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureReason {
+    /// The processing returned an error.
+    Error(String),
+    /// The processing panicked, with this message.
+    Panic(String),
+    /// The processing took longer than this timeout.
+    Timeout(Duration),
+}
+
+impl fmt::Display for FailureReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FailureReason::Error(err) => write!(f, "{err}"),
+            FailureReason::Panic(message) => write!(f, "panic: {message}"),
+            FailureReason::Timeout(timeout) => write!(f, "timed out after {timeout:?}"),
+        }
+    }
+}
+
+/// A file quarantined because its processing failed, while the other
+/// files were still processed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct FileFailure {
+    /// The path of the file
+    pub path: PathBuf,
+    /// Why the last processing of the file failed
+    pub reason: FailureReason,
+    /// The number of times the file was processed
+    pub attempts: usize,
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+fn process_once<Config, ProcFiles>(
+    func: &Arc<ProcFiles>,
+    job: &JobItem<Config>,
+    timeout: Option<Duration>,
+) -> Result<(), FailureReason>
 where
-    ProcFiles: Fn(PathBuf, &Config) -> std::io::Result<()> + Send + Sync,
+    Config: 'static + Send + Sync,
+    ProcFiles: 'static + Fn(PathBuf, &Config) -> std::io::Result<()> + Send + Sync + ?Sized,
+{
+    let outcome = match timeout {
+        None => catch_unwind(AssertUnwindSafe(|| func(job.path.clone(), &job.cfg))),
+        Some(timeout) => {
+            let (sender, receiver) = bounded(1);
+            let func = Arc::clone(func);
+            let cfg = Arc::clone(&job.cfg);
+            let path = job.path.clone();
+            thread::Builder::new()
+                .name(String::from("File"))
+                .spawn(move || {
+                    let outcome = catch_unwind(AssertUnwindSafe(|| func(path, &cfg)));
+                    let _ = sender.send(outcome);
+                })
+                .map_err(|e| FailureReason::Error(e.to_string()))?;
+            match receiver.recv_timeout(timeout) {
+                Ok(outcome) => outcome,
+                Err(RecvTimeoutError::Timeout) => return Err(FailureReason::Timeout(timeout)),
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(FailureReason::Panic("the thread stopped".to_string()))
+                }
+            }
+        }
+    };
+    match outcome {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(err)) => Err(FailureReason::Error(err.to_string())),
+        Err(payload) => Err(FailureReason::Panic(panic_message(&*payload))),
+    }
+}
+
+// Processes a file, again when it panics or times out, as the policy allows
+fn process<Config, ProcFiles>(
+    func: &Arc<ProcFiles>,
+    job: &JobItem<Config>,
+    policy: FailurePolicy,
+) -> Option<FileFailure>
+where
+    Config: 'static + Send + Sync,
+    ProcFiles: 'static + Fn(PathBuf, &Config) -> std::io::Result<()> + Send + Sync + ?Sized,
+{
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        match process_once(func, job, policy.timeout) {
+            Ok(()) => return None,
+            Err(reason)
+                if matches!(reason, FailureReason::Error(_)) || attempts > policy.retries =>
+            {
+                return Some(FileFailure {
+                    path: job.path.clone(),
+                    reason,
+                    attempts,
+                })
+            }
+            Err(_) => {}
+        }
+    }
+}
+
+fn consumer<Config, ProcFiles>(
+    receiver: JobReceiver<Config>,
+    func: Arc<ProcFiles>,
+    throttle: Arc<Throttle>,
+    policy: FailurePolicy,
+    failures: Arc<Mutex<Vec<FileFailure>>>,
+) where
+    Config: 'static + Send + Sync,
+    ProcFiles: 'static + Fn(PathBuf, &Config) -> std::io::Result<()> + Send + Sync + ?Sized,
 {
     while let Ok(job) = receiver.recv() {
         if job.is_none() {
@@ -40,9 +276,20 @@ where
         // Cannot panic because of the check immediately above.
         let job = job.unwrap();
         let path = job.path.clone();
+        trace_span!("process_file", path = %path.display());
+        let _release = Release {
+            throttle: &throttle,
+            size: job.size,
+        };
 
-        if let Err(err) = func(job.path, &job.cfg) {
-            eprintln!("{err:?} for file {path:?}");
+        if let Some(failure) = process(&func, &job, policy) {
+            let reason = &failure.reason;
+            trace_event!(warn, path = %path.display(), error = %reason, "failed to process file");
+            eprintln!("{reason} for file {path:?}");
+            failures
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(failure);
         }
     }
 }
@@ -51,10 +298,14 @@ fn send_file<T>(
     path: PathBuf,
     cfg: &Arc<T>,
     sender: &JobSender<T>,
+    throttle: &Throttle,
 ) -> Result<(), ConcurrentErrors> {
+    let size = throttle.size(&path);
+    throttle.acquire(size);
     sender
         .send(Some(JobItem {
             path,
+            size,
             cfg: Arc::clone(cfg),
         }))
         .map_err(|e| ConcurrentErrors::Sender(e.to_string()))
@@ -68,12 +319,190 @@ fn is_hidden(entry: &DirEntry) -> bool {
         .unwrap_or(false)
 }
 
+// The prefix of the Windows extended-length paths, and of their UNC form
+const VERBATIM_PREFIX: &str = r"\\?\";
+const VERBATIM_UNC_PREFIX: &str = r"\\?\UNC\";
+// The length of the longest Windows path which doesn't need the
+// extended-length form
+#[cfg_attr(not(windows), allow(dead_code))]
+const MAX_PATH: usize = 260;
+
+// Returns the drive letter of a Windows path like `c:\src` or `C:/src`
+fn drive_letter(path: &str) -> Option<char> {
+    let mut chars = path.chars();
+    let letter = chars.next().filter(char::is_ascii_alphabetic)?;
+    (chars.next() == Some(':') && matches!(chars.next(), Some('\\' | '/'))).then_some(letter)
+}
+
+// Returns the extended-length form of an absolute Windows path, with an
+// uppercase drive letter, or `None` if it's relative or already extended
+#[cfg_attr(not(windows), allow(dead_code))]
+fn to_extended_length(path: &str) -> Option<String> {
+    if path.starts_with(VERBATIM_PREFIX) {
+        return None;
+    }
+    // The extended-length paths aren't normalized, so their separators
+    // must be backslashes
+    let path = path.replace('/', "\\");
+    if let Some(letter) = drive_letter(&path) {
+        return Some(format!(
+            "{VERBATIM_PREFIX}{}{}",
+            letter.to_ascii_uppercase(),
+            &path[1..]
+        ));
+    }
+    path.strip_prefix(r"\\")
+        .map(|share| format!("{VERBATIM_UNC_PREFIX}{share}"))
+}
+
+// Returns the usual form of an extended-length Windows path, with an
+// uppercase drive letter
+fn from_extended_length(path: &str) -> String {
+    let path = if let Some(share) = path.strip_prefix(VERBATIM_UNC_PREFIX) {
+        format!(r"\\{share}")
+    } else {
+        path.strip_prefix(VERBATIM_PREFIX)
+            .unwrap_or(path)
+            .to_string()
+    };
+    match drive_letter(&path) {
+        Some(letter) => format!("{}{}", letter.to_ascii_uppercase(), &path[1..]),
+        None => path,
+    }
+}
+
+// Returns the key of a Windows path, the same for all the spellings of
+// a file: extended-length or not, and whatever the case of its letters
+// and its separators
+#[cfg_attr(unix, allow(dead_code))]
+fn windows_path_key(path: &str) -> String {
+    from_extended_length(path)
+        .replace('/', "\\")
+        .trim_end_matches('\\')
+        .to_lowercase()
+}
+
+// Windows paths longer than `MAX_PATH` can only be accessed in their
+// extended-length form, so the directories are walked in that form
+#[cfg(windows)]
+fn walk_root(path: &Path) -> PathBuf {
+    let path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    path.to_str()
+        .and_then(to_extended_length)
+        .map_or(path, PathBuf::from)
+}
+
+#[cfg(not(windows))]
+fn walk_root(path: &Path) -> PathBuf {
+    path.to_path_buf()
+}
+
+// The files found in a directory walked in the extended-length form are
+// processed in the usual form, unless they are too long for it
+#[cfg(windows)]
+fn processed_path(path: PathBuf) -> PathBuf {
+    match path.to_str().map(from_extended_length) {
+        Some(usual) if usual.len() < MAX_PATH => PathBuf::from(usual),
+        _ => path,
+    }
+}
+
+#[cfg(not(windows))]
+fn processed_path(path: PathBuf) -> PathBuf {
+    path
+}
+
+// The identity of a file, shared by all its links
+#[cfg(unix)]
+type FileId = (u64, u64);
+#[cfg(not(unix))]
+type FileId = String;
+
+#[cfg(unix)]
+fn file_id(path: &Path) -> Option<FileId> {
+    use std::os::unix::fs::MetadataExt;
+
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn file_id(path: &Path) -> Option<FileId> {
+    // The file systems of Windows are case-insensitive
+    let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    Some(windows_path_key(&path.to_string_lossy()))
+}
+
+// Files reached through several symbolic or hard links are processed once
+fn first_visit(visited: &mut HashSet<FileId>, path: &Path) -> bool {
+    file_id(path).is_none_or(|id| visited.insert(id))
+}
+
+/// How the symbolic links met while exploring directories are handled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LinkPolicy {
+    /// Symbolic links are ignored.
+    Skip,
+    /// Symbolic links to files are processed, while symbolic links to
+    /// directories are not explored.
+    #[default]
+    Files,
+    /// All symbolic links are followed.
+    ///
+    /// A link to one of its ancestor directories is reported and skipped.
+    Follow,
+}
+
+/// Whether the third-party code met while exploring directories is
+/// processed, see [`is_third_party_dir`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ThirdPartyPolicy {
+    /// Third-party code is processed like the rest of the code.
+    #[default]
+    Include,
+    /// The directories containing third-party code are not explored.
+    ///
+    /// The paths passed explicitly to the runner are still processed.
+    Exclude,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct WalkOptions {
+    links: LinkPolicy,
+    third_party: ThirdPartyPolicy,
+    // Archives are sent even when they don't match the included globs,
+    // their entries are filtered by the function processing them
+    archives: bool,
+    sampling: Option<Sampling>,
+}
+
+fn is_included(path: &Path, include: &GlobSet, exclude: &GlobSet, options: WalkOptions) -> bool {
+    #[cfg(feature = "archives")]
+    let include_archive = options.archives && crate::archive::is_archive(path);
+    #[cfg(not(feature = "archives"))]
+    let include_archive = options.archives;
+
+    (include.is_empty() || include.is_match(path) || include_archive)
+        && (exclude.is_empty() || !exclude.is_match(path))
+        && path.is_file()
+}
+
+// The files found in a directory are sampled by their path relative to
+// it, so the sample doesn't depend on where the directory is
+fn is_sampled(path: &Path, root: &Path, options: WalkOptions) -> bool {
+    options
+        .sampling
+        .is_none_or(|sampling| sampling.includes(path.strip_prefix(root).unwrap_or(path)))
+}
+
 fn explore<Config, ProcDirPaths, ProcPath>(
     files_data: FilesData,
     cfg: &Arc<Config>,
     proc_dir_paths: ProcDirPaths,
     proc_path: ProcPath,
+    options: WalkOptions,
     sender: &JobSender<Config>,
+    throttle: &Throttle,
 ) -> Result<HashMap<String, Vec<PathBuf>>, ConcurrentErrors>
 where
     ProcDirPaths: Fn(&mut HashMap<String, Vec<PathBuf>>, &Path, &Config) + Send + Sync,
@@ -86,36 +515,52 @@ where
     } = files_data;
 
     let mut all_files: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    let mut visited = HashSet::new();
 
     for path in std::mem::take(&mut paths) {
         if !path.exists() {
+            trace_event!(warn, path = %path.display(), "file doesn't exist");
             eprintln!("Warning: File doesn't exist: {path:?}");
             continue;
         }
         if path.is_dir() {
-            for entry in WalkDir::new(path)
+            let root = processed_path(walk_root(&path));
+            for entry in WalkDir::new(walk_root(&path))
+                .follow_links(options.links == LinkPolicy::Follow)
                 .into_iter()
-                .filter_entry(|e| !is_hidden(e))
+                .filter_entry(|e| {
+                    !is_hidden(e)
+                        && (options.links != LinkPolicy::Skip || !e.path_is_symlink())
+                        && (options.third_party == ThirdPartyPolicy::Include
+                            || e.depth() == 0
+                            || !e.file_type().is_dir()
+                            || !is_third_party_dir(e.path()))
+                })
             {
                 let entry = match entry {
                     Ok(entry) => entry,
+                    Err(e) if e.loop_ancestor().is_some() => {
+                        trace_event!(warn, error = %e, "symbolic link cycle");
+                        eprintln!("Warning: Symbolic link cycle: {e}");
+                        continue;
+                    }
                     Err(e) => return Err(ConcurrentErrors::Sender(e.to_string())),
                 };
-                let path = entry.path().to_path_buf();
-                if (include.is_empty() || include.is_match(&path))
-                    && (exclude.is_empty() || !exclude.is_match(&path))
-                    && path.is_file()
+                let path = processed_path(entry.into_path());
+                if is_included(&path, include, exclude, options)
+                    && is_sampled(&path, &root, options)
+                    && first_visit(&mut visited, &path)
                 {
                     proc_dir_paths(&mut all_files, &path, cfg);
-                    send_file(path, cfg, sender)?;
+                    send_file(path, cfg, sender, throttle)?;
                 }
             }
-        } else if (include.is_empty() || include.is_match(&path))
-            && (exclude.is_empty() || !exclude.is_match(&path))
-            && path.is_file()
+        } else if is_included(&path, include, exclude, options)
+            && is_sampled(&path, Path::new(""), options)
+            && first_visit(&mut visited, &path)
         {
             proc_path(&path, cfg);
-            send_file(path, cfg, sender)?;
+            send_file(path, cfg, sender, throttle)?;
         }
     }
 
@@ -159,6 +604,13 @@ pub struct ConcurrentRunner<Config> {
     proc_files: Box<ProcFilesFunction<Config>>,
     proc_dir_paths: Box<ProcDirPathsFunction<Config>>,
     proc_path: Box<ProcPathFunction<Config>>,
+    #[cfg(feature = "archives")]
+    proc_archive_entry: Option<Box<ProcArchiveEntryFunction<Config>>>,
+    links: LinkPolicy,
+    third_party: ThirdPartyPolicy,
+    budget: MemoryBudget,
+    failure_policy: FailurePolicy,
+    sampling: Option<Sampling>,
     num_jobs: usize,
 }
 
@@ -176,6 +628,13 @@ impl<Config: 'static + Send + Sync> ConcurrentRunner<Config> {
             proc_files: Box::new(proc_files),
             proc_dir_paths: Box::new(null_proc_dir_paths),
             proc_path: Box::new(null_proc_path),
+            #[cfg(feature = "archives")]
+            proc_archive_entry: None,
+            links: LinkPolicy::default(),
+            third_party: ThirdPartyPolicy::default(),
+            budget: MemoryBudget::default(),
+            failure_policy: FailurePolicy::default(),
+            sampling: None,
             num_jobs,
         }
     }
@@ -200,22 +659,133 @@ impl<Config: 'static + Send + Sync> ConcurrentRunner<Config> {
         self
     }
 
+    /// Sets how the symbolic links met while exploring directories are
+    /// handled.
+    ///
+    /// Whatever the policy, a file reached through several links is
+    /// processed only once.
+    pub fn set_link_policy(mut self, links: LinkPolicy) -> Self {
+        self.links = links;
+        self
+    }
+
+    /// Sets whether the third-party code met while exploring directories
+    /// is processed.
+    pub fn set_third_party_policy(mut self, third_party: ThirdPartyPolicy) -> Self {
+        self.third_party = third_party;
+        self
+    }
+
+    /// Sets the memory budget of the files being processed, unlimited by
+    /// default.
+    pub fn set_memory_budget(mut self, budget: MemoryBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Sets how the files whose processing panics or times out are
+    /// retried before they're quarantined.
+    pub fn set_failure_policy(mut self, failure_policy: FailurePolicy) -> Self {
+        self.failure_policy = failure_policy;
+        self
+    }
+
+    /// Processes only a deterministic sample of the files, whose metrics
+    /// are extrapolated with [`PackageTree::extrapolate`].
+    ///
+    /// [`PackageTree::extrapolate`]: crate::PackageTree::extrapolate
+    pub fn set_sampling(mut self, sampling: Sampling) -> Self {
+        self.sampling = Some(sampling);
+        self
+    }
+
+    /// Sets the function to process the files contained in the `.zip`,
+    /// `.tar` and `.tar.gz` archives found during the search.
+    ///
+    /// Archives are read in memory, and each of their files is passed to
+    /// the function with its content and a path made of the path of the
+    /// archive followed by the path of the file inside it.
+    /// Without this function, archives are processed as any other file.
+    #[cfg(feature = "archives")]
+    pub fn set_proc_archive_entry<ProcArchiveEntry>(
+        mut self,
+        proc_archive_entry: ProcArchiveEntry,
+    ) -> Self
+    where
+        ProcArchiveEntry:
+            'static + Fn(PathBuf, Vec<u8>, &Config) -> std::io::Result<()> + Send + Sync,
+    {
+        self.proc_archive_entry = Some(Box::new(proc_archive_entry));
+        self
+    }
+
     /// Runs the producer-consumer approach to process the files
     /// contained in a directory and in its own subdirectories.
     ///
     /// * `config` - Information used to process a file.
     /// * `files_data` - Information about the files to be included or excluded from a search more the number of paths considered in the search.
+    ///
+    /// The files whose processing fails are reported on the standard
+    /// error, see [`run_with_failures`](Self::run_with_failures).
     pub fn run(
         self,
         config: Config,
         files_data: FilesData,
     ) -> Result<HashMap<String, Vec<PathBuf>>, ConcurrentErrors> {
+        self.run_with_failures(config, files_data)
+            .map(|(all_files, _)| all_files)
+    }
+
+    /// Runs the producer-consumer approach like [`run`](Self::run), also
+    /// returning the files quarantined because their processing failed,
+    /// according to the [`FailurePolicy`].
+    ///
+    /// A file whose processing panics doesn't abort the run.
+    pub fn run_with_failures(
+        self,
+        config: Config,
+        files_data: FilesData,
+    ) -> Result<RunOutcome, ConcurrentErrors> {
+        trace_span!("concurrent_run", num_jobs = self.num_jobs);
         let cfg = Arc::new(config);
+        let options = WalkOptions {
+            links: self.links,
+            third_party: self.third_party,
+            sampling: self.sampling,
+            ..Default::default()
+        };
+
+        #[cfg(feature = "archives")]
+        let (options, proc_files) = match self.proc_archive_entry {
+            Some(proc_archive_entry) => {
+                let proc_files = self.proc_files;
+                let proc_files: Box<ProcFilesFunction<Config>> =
+                    Box::new(move |path: PathBuf, cfg: &Config| {
+                        if crate::archive::is_archive(&path) {
+                            crate::archive::for_each_entry(&path, |entry, data| {
+                                proc_archive_entry(entry, data, cfg)
+                            })
+                        } else {
+                            proc_files(path, cfg)
+                        }
+                    });
+                let options = WalkOptions {
+                    archives: true,
+                    ..options
+                };
+                (options, proc_files)
+            }
+            None => (options, self.proc_files),
+        };
+        #[cfg(not(feature = "archives"))]
+        let proc_files = self.proc_files;
 
         let (sender, receiver) = unbounded();
+        let throttle = Arc::new(Throttle::new(self.budget));
 
         let producer = {
             let sender = sender.clone();
+            let throttle = Arc::clone(&throttle);
 
             match thread::Builder::new()
                 .name(String::from("Producer"))
@@ -225,7 +795,9 @@ impl<Config: 'static + Send + Sync> ConcurrentRunner<Config> {
                         &cfg,
                         self.proc_dir_paths,
                         self.proc_path,
+                        options,
                         &sender,
+                        &throttle,
                     )
                 }) {
                 Ok(producer) => producer,
@@ -234,15 +806,19 @@ impl<Config: 'static + Send + Sync> ConcurrentRunner<Config> {
         };
 
         let mut receivers = Vec::with_capacity(self.num_jobs);
-        let proc_files = Arc::new(self.proc_files);
+        let proc_files = Arc::new(proc_files);
+        let failures = Arc::new(Mutex::new(Vec::new()));
         for i in 0..self.num_jobs {
             let receiver = receiver.clone();
             let proc_files = proc_files.clone();
+            let throttle = Arc::clone(&throttle);
+            let failures = Arc::clone(&failures);
+            let failure_policy = self.failure_policy;
 
             let t = match thread::Builder::new()
                 .name(format!("Consumer {i}"))
                 .spawn(move || {
-                    consumer(receiver, proc_files);
+                    consumer(receiver, proc_files, throttle, failure_policy, failures);
                 }) {
                 Ok(receiver) => receiver,
                 Err(e) => return Err(ConcurrentErrors::Thread(e.to_string())),
@@ -275,6 +851,182 @@ impl<Config: 'static + Send + Sync> ConcurrentRunner<Config> {
             }
         }
 
-        all_files
+        let failures = std::mem::take(&mut *failures.lock().unwrap_or_else(|e| e.into_inner()));
+        all_files.map(|all_files| (all_files, failures))
+    }
+}
+
+#[cfg(test)]
+mod windows_path_tests {
+    use super::*;
+
+    #[test]
+    fn extended_length_paths() {
+        assert_eq!(
+            to_extended_length(r"c:\src/lib.rs").as_deref(),
+            Some(r"\\?\C:\src\lib.rs")
+        );
+        assert_eq!(
+            to_extended_length(r"\\server\share\a.rs").as_deref(),
+            Some(r"\\?\UNC\server\share\a.rs")
+        );
+        assert_eq!(to_extended_length(r"\\?\C:\a.rs"), None);
+        assert_eq!(to_extended_length(r"src\a.rs"), None);
+
+        assert_eq!(from_extended_length(r"\\?\c:\a.rs"), r"C:\a.rs");
+        assert_eq!(
+            from_extended_length(r"\\?\UNC\server\share\a.rs"),
+            r"\\server\share\a.rs"
+        );
+        assert_eq!(from_extended_length(r"c:\a.rs"), r"C:\a.rs");
+    }
+
+    #[test]
+    fn case_insensitive_keys() {
+        let key = windows_path_key(r"C:\Repo\Src\Lib.rs");
+        assert_eq!(windows_path_key(r"c:/repo/src/lib.rs"), key);
+        assert_eq!(windows_path_key(r"\\?\C:\REPO\SRC\LIB.RS"), key);
+        assert_ne!(windows_path_key(r"C:\Repo\Src\Lib2.rs"), key);
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{fs, os::unix::fs::symlink, sync::Mutex};
+
+    use super::*;
+
+    fn walk(dir: &Path, links: LinkPolicy) -> Vec<PathBuf> {
+        let visited = Arc::new(Mutex::new(Vec::new()));
+        let files = Arc::clone(&visited);
+        let files_data = FilesData {
+            include: GlobSet::empty(),
+            exclude: GlobSet::empty(),
+            paths: vec![dir.to_path_buf()],
+        };
+        ConcurrentRunner::new(2, move |path, _: &()| {
+            files.lock().unwrap().push(path);
+            Ok(())
+        })
+        .set_link_policy(links)
+        .run((), files_data)
+        .unwrap();
+
+        let mut visited = visited.lock().unwrap().clone();
+        visited.sort();
+        visited
+    }
+
+    #[test]
+    fn walk_links() {
+        let dir = std::env::temp_dir().join("sca_walk_links");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src/a.rs"), "fn a() {}\n").unwrap();
+        fs::hard_link(dir.join("src/a.rs"), dir.join("src/b.rs")).unwrap();
+        fs::create_dir_all(dir.join("ext")).unwrap();
+        fs::write(dir.join("ext/c.rs"), "fn c() {}\n").unwrap();
+        symlink(dir.join("ext"), dir.join("src/ext")).unwrap();
+        symlink(dir.join("ext/c.rs"), dir.join("src/c.rs")).unwrap();
+        // A cycle
+        symlink(&dir, dir.join("src/root")).unwrap();
+
+        let src = dir.join("src");
+        // `a.rs` and `b.rs` are the same file
+        assert_eq!(walk(&src, LinkPolicy::Skip).len(), 1);
+        let files = walk(&src, LinkPolicy::Files);
+        assert_eq!(files.len(), 2);
+        assert!(files.contains(&src.join("c.rs")));
+        // `ext/c.rs` is reached through `src/c.rs`, `src/ext` and `src/root`
+        // once, and the cycle is skipped
+        assert_eq!(walk(&src, LinkPolicy::Follow).len(), 2);
+    }
+
+    #[test]
+    fn quarantined_files() {
+        let dir = std::env::temp_dir().join("sca_quarantined_files");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for name in ["good.rs", "panic.rs", "slow.rs", "error.rs"] {
+            fs::write(dir.join(name), "fn f() {}\n").unwrap();
+        }
+
+        let processed = Arc::new(Mutex::new(Vec::new()));
+        let files = Arc::clone(&processed);
+        let files_data = FilesData {
+            include: GlobSet::empty(),
+            exclude: GlobSet::empty(),
+            paths: vec![dir],
+        };
+        let (_, mut failures) = ConcurrentRunner::new(2, move |path: PathBuf, _: &()| {
+            let name = path.file_name().unwrap().to_string_lossy().to_string();
+            files.lock().unwrap().push(name.clone());
+            match name.as_str() {
+                "panic.rs" => panic!("unexpected node"),
+                "slow.rs" => thread::sleep(Duration::from_millis(500)),
+                "error.rs" => return Err(std::io::Error::other("unreadable")),
+                _ => {}
+            }
+            Ok(())
+        })
+        .set_failure_policy(FailurePolicy {
+            retries: 1,
+            timeout: Some(Duration::from_millis(50)),
+        })
+        .run_with_failures((), files_data)
+        .unwrap();
+        failures.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let reasons = failures
+            .iter()
+            .map(|failure| (failure.reason.clone(), failure.attempts))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            reasons,
+            [
+                (FailureReason::Error("unreadable".to_string()), 1),
+                (FailureReason::Panic("unexpected node".to_string()), 2),
+                (FailureReason::Timeout(Duration::from_millis(50)), 2),
+            ]
+        );
+        assert!(processed.lock().unwrap().contains(&"good.rs".to_string()));
+    }
+
+    #[test]
+    fn memory_budget() {
+        let dir = std::env::temp_dir().join("sca_memory_budget");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for i in 0..8 {
+            fs::write(dir.join(format!("{i}.rs")), "fn f() {}\n").unwrap();
+        }
+
+        // The number of files being processed, and its maximum
+        let counts = Arc::new(Mutex::new((0, 0)));
+        let processed = Arc::clone(&counts);
+        let files_data = FilesData {
+            include: GlobSet::empty(),
+            exclude: GlobSet::empty(),
+            paths: vec![dir],
+        };
+        ConcurrentRunner::new(4, move |_, _: &()| {
+            {
+                let mut counts = processed.lock().unwrap();
+                counts.0 += 1;
+                counts.1 = counts.1.max(counts.0);
+            }
+            thread::sleep(std::time::Duration::from_millis(5));
+            processed.lock().unwrap().0 -= 1;
+            Ok(())
+        })
+        .set_memory_budget(MemoryBudget {
+            // Two files of 10 bytes
+            max_bytes: Some(25),
+            max_files: None,
+        })
+        .run((), files_data)
+        .unwrap();
+
+        assert!(counts.lock().unwrap().1 <= 2);
     }
 }
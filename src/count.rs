@@ -1,13 +1,15 @@
 // use num_format;
 
 use std::{
+    collections::BTreeMap,
     fmt,
     sync::{Arc, Mutex},
 };
 
 use num_format::{Locale, ToFormattedString};
+use serde::Serialize;
 
-use crate::traits::*;
+use crate::{checker::Checker, getter::Getter, spaces::SpaceKind, traits::*};
 
 /// Counts the types of nodes specified in the input slice
 /// and the number of nodes in a code.
@@ -39,6 +41,167 @@ pub fn count<T: ParserTrait>(parser: &T, filters: &[String]) -> (usize, usize) {
     (good, total)
 }
 
+/// The number of nodes matching some filters in a space.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SpaceCount {
+    /// The name of the space
+    pub name: Option<String>,
+    /// The kind of the space
+    pub kind: SpaceKind,
+    /// The first line of the space
+    pub start_line: usize,
+    /// The last line of the space
+    pub end_line: usize,
+    /// The number of matching nodes in the space, excluding the ones of
+    /// its nested spaces
+    pub count: usize,
+}
+
+/// The nodes of a code matching some filters, grouped by node kind and
+/// by enclosing space.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct GroupedCount {
+    /// The number of matching nodes
+    pub good: usize,
+    /// The number of nodes
+    pub total: usize,
+    /// The number of matching nodes by kind
+    pub by_kind: BTreeMap<String, usize>,
+    /// The spaces with matching nodes, in the order of the code
+    pub by_space: Vec<SpaceCount>,
+}
+
+/// Counts the nodes of a code matching some filters, grouped by node
+/// kind and by the innermost function space enclosing them.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{count_grouped, ParserEngineRust, ParserTrait};
+///
+/// let code = b"fn f() { g(); }\nfn h() { g(); g(); }\n";
+/// let path = Path::new("foo.rs");
+/// let parser = ParserEngineRust::new(code.to_vec(), path, None);
+///
+/// let count = count_grouped(&parser, &["call".to_string()]);
+/// assert_eq!(count.by_kind["call_expression"], 3);
+/// assert_eq!(count.by_space[1].name.as_deref(), Some("h"));
+/// assert_eq!(count.by_space[1].count, 2);
+/// ```
+pub fn count_grouped<T: ParserTrait>(parser: &T, filters: &[String]) -> GroupedCount {
+    let code = parser.get_code();
+    let filters = parser.get_filters(filters);
+    let root = parser.get_root();
+    let mut count = GroupedCount::default();
+    let mut spaces = Vec::new();
+
+    // The nodes, with the index of their enclosing space
+    let mut stack = vec![(root, None)];
+    while let Some((node, space)) = stack.pop() {
+        let space = match space {
+            Some(space) if !T::Checker::is_func(&node) && !T::Checker::is_func_space(&node) => {
+                space
+            }
+            // The root is always a space
+            _ => {
+                spaces.push(SpaceCount {
+                    name: T::Getter::get_func_space_name(&node, code).map(str::to_string),
+                    kind: T::Getter::get_space_kind(&node),
+                    start_line: node.start_row() + 1,
+                    end_line: node.end_row() + 1,
+                    count: 0,
+                });
+                spaces.len() - 1
+            }
+        };
+
+        count.total += 1;
+        if filters.any(&node) {
+            count.good += 1;
+            *count.by_kind.entry(node.kind().to_string()).or_insert(0) += 1;
+            spaces[space].count += 1;
+        }
+        let mut children = node.children().collect::<Vec<_>>();
+        children.reverse();
+        stack.extend(children.into_iter().map(|child| (child, Some(space))));
+    }
+    count.by_space = spaces.into_iter().filter(|space| space.count > 0).collect();
+    count
+}
+
+/// A histogram of some values, like the lengths of the functions.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Histogram {
+    /// The width of the buckets
+    pub bucket_width: usize,
+    /// The number of values in each bucket, by the first value of the
+    /// bucket
+    pub buckets: BTreeMap<usize, usize>,
+}
+
+impl Histogram {
+    /// Creates an empty histogram with buckets of a width, at least 1.
+    pub fn new(bucket_width: usize) -> Self {
+        Self {
+            bucket_width: bucket_width.max(1),
+            buckets: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a value to its bucket.
+    pub fn add(&mut self, value: usize) {
+        let bucket = value - value % self.bucket_width;
+        *self.buckets.entry(bucket).or_insert(0) += 1;
+    }
+
+    /// Adds the values of another histogram with buckets of the same
+    /// width.
+    pub fn merge(&mut self, other: &Histogram) {
+        debug_assert_eq!(self.bucket_width, other.bucket_width);
+        for (bucket, count) in &other.buckets {
+            *self.buckets.entry(*bucket).or_insert(0) += count;
+        }
+    }
+
+    /// Returns the number of values.
+    pub fn count(&self) -> usize {
+        self.buckets.values().sum()
+    }
+}
+
+/// Returns the histogram of the lengths of the functions of a code, in
+/// lines.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{function_lengths, ParserEngineRust, ParserTrait};
+///
+/// let code = b"fn f() {}\nfn g() {\n    h();\n}\n";
+/// let path = Path::new("foo.rs");
+/// let parser = ParserEngineRust::new(code.to_vec(), path, None);
+///
+/// let histogram = function_lengths(&parser, 2);
+/// // `f` is in the bucket of 0 and 1 line, `g` in the one of 2 and 3
+/// assert_eq!(histogram.buckets[&0], 1);
+/// assert_eq!(histogram.buckets[&2], 1);
+/// ```
+pub fn function_lengths<T: ParserTrait>(parser: &T, bucket_width: usize) -> Histogram {
+    let mut histogram = Histogram::new(bucket_width);
+    let mut stack = vec![parser.get_root()];
+    while let Some(node) = stack.pop() {
+        if T::Checker::is_func(&node) {
+            histogram.add(node.end_row() - node.start_row() + 1);
+        }
+        stack.extend(node.children());
+    }
+    histogram
+}
+
 /// Configuration options for counting different
 /// types of nodes in a code.
 #[derive(Debug)]
@@ -56,6 +219,8 @@ pub struct Count {
     pub good: usize,
     /// The total number of nodes in a code
     pub total: usize,
+    /// The number of specific types of nodes by kind
+    pub by_kind: BTreeMap<String, usize>,
 }
 
 impl Callback for Count {
@@ -63,10 +228,13 @@ impl Callback for Count {
     type Cfg = CountCfg;
 
     fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
-        let (good, total) = count(parser, &cfg.filters);
+        let count = count_grouped(parser, &cfg.filters);
         let mut results = cfg.stats.lock().unwrap();
-        results.good += good;
-        results.total += total;
+        results.good += count.good;
+        results.total += count.total;
+        for (kind, good) in count.by_kind {
+            *results.by_kind.entry(kind).or_insert(0) += good;
+        }
         Ok(())
     }
 }
@@ -90,3 +258,50 @@ impl fmt::Display for Count {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{ParserEngineRust, ParserTrait};
+
+    use super::*;
+
+    #[test]
+    fn grouped_counts() {
+        let code = b"// a\nfn f() {\n    // b\n    let g = || {\n        // c\n    };\n}\n";
+        let path = Path::new("foo.rs");
+        let parser = ParserEngineRust::new(code.to_vec(), path, None);
+
+        let filters = ["comment".to_string()];
+        let grouped = count_grouped(&parser, &filters);
+        assert_eq!((grouped.good, grouped.total), count(&parser, &filters));
+        assert_eq!(grouped.by_kind["line_comment"], 3);
+        assert_eq!(
+            grouped
+                .by_space
+                .iter()
+                .map(|space| (space.kind, space.count))
+                .collect::<Vec<_>>(),
+            [
+                (SpaceKind::Unit, 1),
+                (SpaceKind::Function, 1),
+                (SpaceKind::Closure, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn histograms() {
+        let mut histogram = Histogram::new(0);
+        assert_eq!(histogram.bucket_width, 1);
+        histogram.add(3);
+
+        let mut other = Histogram::new(1);
+        other.add(3);
+        other.add(5);
+        histogram.merge(&other);
+        assert_eq!(histogram.buckets, BTreeMap::from([(3, 2), (5, 1)]));
+        assert_eq!(histogram.count(), 3);
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    checker::Checker,
+    getter::Getter,
+    langs::{action, LANG},
+    metrics::halstead::HalsteadType,
+    node::Node,
+    spaces::SpaceKind,
+    traits::{Callback, ParserTrait},
+};
+
+// Returns `true` if a checker or a getter of the language recognizes a
+// node, so at least one metric accounts for it
+fn is_handled<T: ParserTrait>(node: &Node, code: &[u8]) -> bool {
+    T::Checker::is_comment(node)
+        || T::Checker::is_useful_comment(node, code)
+        || T::Checker::is_func_space(node)
+        || T::Checker::is_func(node)
+        || T::Checker::is_closure(node)
+        || T::Checker::is_call(node)
+        || T::Checker::is_non_arg(node)
+        || T::Checker::is_string(node)
+        || T::Checker::is_else_if(node)
+        || T::Checker::is_primitive(node.kind_id())
+        || T::Getter::get_space_kind(node) != SpaceKind::Unknown
+        || !matches!(T::Getter::get_op_type(node), HalsteadType::Unknown)
+}
+
+/// Returns the named node kinds of a code which no checker or getter of
+/// its language handles, with the number of their nodes.
+///
+/// The syntax errors and the missing nodes aren't counted. The kinds met
+/// on real code show where the metrics of a language have coverage gaps,
+/// though some of them, like the blocks or the parameter lists, only
+/// group the nodes the metrics count.
+pub fn unhandled_kinds<T: ParserTrait>(parser: &T) -> BTreeMap<String, usize> {
+    let code = parser.get_code();
+    let mut kinds = BTreeMap::new();
+    let mut stack = vec![parser.get_root()];
+    while let Some(node) = stack.pop() {
+        if node.is_error() || node.is_missing() {
+            continue;
+        }
+        if node.is_named() && !is_handled::<T>(&node, code) {
+            *kinds.entry(node.kind().to_string()).or_insert(0) += 1;
+        }
+        stack.extend(node.children());
+    }
+    kinds
+}
+
+struct UnhandledKinds {
+    _guard: (),
+}
+
+impl Callback for UnhandledKinds {
+    type Res = BTreeMap<String, usize>;
+    type Cfg = ();
+
+    fn call<T: ParserTrait>(_cfg: Self::Cfg, parser: &T) -> Self::Res {
+        unhandled_kinds(parser)
+    }
+}
+
+/// Returns the named node kinds of a code in a language which no checker
+/// or getter handles, with the number of their nodes.
+///
+/// # Examples
+///
+/// ```
+/// use singularity_code_analysis::{code_unhandled_kinds, LANG};
+///
+/// let kinds = code_unhandled_kinds(b"fn f(a: u32) {}".to_vec(), LANG::Rust);
+/// for (kind, count) in &kinds {
+///     println!("{kind}: {count}");
+/// }
+/// ```
+pub fn code_unhandled_kinds(code: Vec<u8>, language: LANG) -> BTreeMap<String, usize> {
+    let path = std::path::PathBuf::from(format!("coverage.{}", language.get_name()));
+    action::<UnhandledKinds>(&language, code, &path, None, ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rust_unhandled_kinds() {
+        let kinds = code_unhandled_kinds(
+            b"fn f(a: u32) -> u32 {\n    // add one\n    a + 1\n}\n".to_vec(),
+            LANG::Rust,
+        );
+        // The functions, the comments and the operands are handled
+        assert!(!kinds.contains_key("function_item"));
+        assert!(!kinds.contains_key("line_comment"));
+        assert!(!kinds.contains_key("identifier"));
+        // The parameter lists only group the parameters
+        assert_eq!(kinds.get("parameters"), Some(&1));
+    }
+
+    #[test]
+    fn errors_are_skipped() {
+        let kinds = code_unhandled_kinds(b"fn f( {".to_vec(), LANG::Rust);
+        assert!(!kinds.contains_key("ERROR"));
+    }
+}
@@ -0,0 +1,286 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ai::semantic_analyzer::{CodeSmell, Severity},
+    risk::{breaches, Breach, RiskInput, RiskPolicy},
+    spaces::FuncSpace,
+};
+
+/// The remediation costs used to estimate the technical debt of a code,
+/// in the manner of the SQALE method.
+///
+/// All the costs are in minutes. A breach of a threshold costs the
+/// minutes of its metric multiplied by how far the value is from the
+/// threshold, e.g. a cyclomatic complexity of 14 costs four times the
+/// cyclomatic cost when the threshold is 10.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DebtPolicy {
+    /// The thresholds whose breaches are debt
+    ///
+    /// Their weights are not used.
+    pub thresholds: RiskPolicy,
+    /// The cost of a unit of cyclomatic complexity above the threshold
+    pub cyclomatic_cost: f64,
+    /// The cost of a unit of cognitive complexity above the threshold
+    pub cognitive_cost: f64,
+    /// The cost of a source line above the threshold
+    pub sloc_cost: f64,
+    /// The cost of a point of maintainability index below the threshold
+    pub mi_cost: f64,
+    /// The costs of the code smells, from low to critical severity
+    pub severity_costs: [f64; 4],
+    /// The costs of the code smells reported by some rules, which take
+    /// precedence over the costs of their severity
+    pub rule_costs: HashMap<String, f64>,
+    /// The cost of developing a line of code, used to compute the debt
+    /// ratio
+    pub line_cost: f64,
+}
+
+impl Default for DebtPolicy {
+    fn default() -> Self {
+        Self {
+            thresholds: RiskPolicy::default(),
+            cyclomatic_cost: 10.,
+            cognitive_cost: 10.,
+            sloc_cost: 1.,
+            mi_cost: 5.,
+            severity_costs: [5., 10., 20., 60.],
+            rule_costs: HashMap::new(),
+            line_cost: 30.,
+        }
+    }
+}
+
+impl DebtPolicy {
+    /// Returns the cost of a breach, in minutes.
+    pub fn breach_cost(&self, breach: &Breach) -> f64 {
+        let cost = match breach.metric {
+            "cyclomatic" => self.cyclomatic_cost,
+            "cognitive" => self.cognitive_cost,
+            "sloc" => self.sloc_cost,
+            "mi" => self.mi_cost,
+            _ => 0.,
+        };
+        cost * (breach.value - breach.threshold).abs()
+    }
+
+    /// Returns the cost of a code smell, in minutes.
+    pub fn smell_cost(&self, smell: &CodeSmell) -> f64 {
+        self.rule_costs
+            .get(&smell.rule_id)
+            .copied()
+            .unwrap_or(match smell.severity {
+                Severity::Low => self.severity_costs[0],
+                Severity::Medium => self.severity_costs[1],
+                Severity::High => self.severity_costs[2],
+                Severity::Critical => self.severity_costs[3],
+            })
+    }
+}
+
+/// The technical debt of a file.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileDebt {
+    /// The path of the file
+    pub path: String,
+    /// The hours needed to fix the breaches and the code smells
+    pub remediation_hours: f64,
+    /// The hours needed to develop the file
+    pub development_hours: f64,
+    /// The ratio between the remediation and the development hours
+    pub debt_ratio: f64,
+    /// The thresholds breached by the functions of the file
+    pub breaches: Vec<Breach>,
+    /// The number of code smells located in the file
+    pub smells: usize,
+}
+
+/// The technical debt of some files and of all of them.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DebtReport {
+    /// The files with some debt, by decreasing remediation hours
+    pub files: Vec<FileDebt>,
+    /// The hours needed to fix all the files
+    pub remediation_hours: f64,
+    /// The hours needed to develop all the files
+    pub development_hours: f64,
+    /// The ratio between the remediation and the development hours
+    pub debt_ratio: f64,
+}
+
+fn ratio(remediation: f64, development: f64) -> f64 {
+    if development > 0. {
+        remediation / development
+    } else {
+        0.
+    }
+}
+
+fn collect_breaches(space: &FuncSpace, policy: &RiskPolicy, all: &mut Vec<Breach>) {
+    if space.kind.is_callable() {
+        all.extend(breaches(space, policy));
+    }
+    for space in space.spaces.iter() {
+        collect_breaches(space, policy, all);
+    }
+}
+
+/// Estimates the technical debt of a code in hours.
+///
+/// The debt of a file is the cost of the thresholds breached by its
+/// functions plus the cost of its code smells. Its development cost is
+/// the number of its logical lines multiplied by the line cost, and the
+/// debt ratio compares the two costs. Metrics suppressed by a
+/// `sca-ignore` comment don't breach any threshold.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{
+///     estimate_debt, metrics, DebtPolicy, ParserEngineRust, ParserTrait, RiskInput,
+/// };
+///
+/// let path = Path::new("foo.rs");
+/// let parser = ParserEngineRust::new(b"fn f() {}".to_vec(), path, None);
+/// let space = metrics(&parser, path).unwrap();
+///
+/// let input = RiskInput { path, space: &space, smells: &[] };
+/// let report = estimate_debt(&[input], &DebtPolicy::default());
+/// assert_eq!(report.remediation_hours, 0.);
+/// ```
+pub fn estimate_debt(inputs: &[RiskInput], policy: &DebtPolicy) -> DebtReport {
+    let mut report = DebtReport::default();
+
+    for input in inputs {
+        let mut breaches = Vec::new();
+        collect_breaches(input.space, &policy.thresholds, &mut breaches);
+
+        let minutes = breaches
+            .iter()
+            .map(|breach| policy.breach_cost(breach))
+            .chain(input.smells.iter().map(|smell| policy.smell_cost(smell)))
+            .sum::<f64>();
+        let remediation_hours = minutes / 60.;
+        let development_hours = input.space.metrics.loc.lloc() * policy.line_cost / 60.;
+
+        report.remediation_hours += remediation_hours;
+        report.development_hours += development_hours;
+        if remediation_hours > 0. {
+            report.files.push(FileDebt {
+                path: input.path.to_string_lossy().into_owned(),
+                remediation_hours,
+                development_hours,
+                debt_ratio: ratio(remediation_hours, development_hours),
+                breaches,
+                smells: input.smells.len(),
+            });
+        }
+    }
+
+    report.debt_ratio = ratio(report.remediation_hours, report.development_hours);
+    report
+        .files
+        .sort_by(|a, b| b.remediation_hours.total_cmp(&a.remediation_hours));
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use crate::{ai::semantic_analyzer::CodeLocation, metrics, ParserEngineRust, ParserTrait};
+
+    use super::*;
+
+    fn space(path: &Path, code: &str) -> FuncSpace {
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), path, None);
+        metrics(&parser, path).unwrap()
+    }
+
+    fn smell(rule_id: &str, severity: Severity) -> CodeSmell {
+        CodeSmell {
+            rule_id: rule_id.to_string(),
+            name: "Test".to_string(),
+            description: String::new(),
+            severity,
+            location: CodeLocation {
+                file_path: String::new(),
+                line_start: 1,
+                line_end: 1,
+                column_start: 1,
+                column_end: 1,
+            },
+            suggestion: String::new(),
+        }
+    }
+
+    #[test]
+    fn rust_estimate_debt() {
+        let policy = DebtPolicy {
+            thresholds: RiskPolicy {
+                max_cyclomatic: 2.,
+                max_cognitive: 100.,
+                max_sloc: 100.,
+                min_mi: 0.,
+                ..Default::default()
+            },
+            rule_costs: HashMap::from([("costly".to_string(), 120.)]),
+            ..Default::default()
+        };
+        let a_path = PathBuf::from("a.rs");
+        let a = space(
+            &a_path,
+            "fn simple() {}\n\
+             fn branchy(x: i32) {\n    if x > 0 {}\n    if x > 1 {}\n    if x > 2 {}\n}\n",
+        );
+        let b_path = PathBuf::from("b.rs");
+        let b = space(&b_path, "fn smelly() {\n}\n");
+        let b_smells = [
+            smell("costly", Severity::Low),
+            smell("other", Severity::Low),
+        ];
+        let c_path = PathBuf::from("c.rs");
+        let c = space(&c_path, "fn clean() {}\n");
+
+        let report = estimate_debt(
+            &[
+                RiskInput {
+                    path: &a_path,
+                    space: &a,
+                    smells: &[],
+                },
+                RiskInput {
+                    path: &b_path,
+                    space: &b,
+                    smells: &b_smells,
+                },
+                RiskInput {
+                    path: &c_path,
+                    space: &c,
+                    smells: &[],
+                },
+            ],
+            &policy,
+        );
+
+        let files = report
+            .files
+            .iter()
+            .map(|file| (file.path.as_str(), file.remediation_hours))
+            .collect::<Vec<_>>();
+        // `branchy` has a cyclomatic complexity of 4, two above the threshold
+        assert_eq!(files, [("b.rs", 125. / 60.), ("a.rs", 20. / 60.)]);
+        assert!((report.remediation_hours - 145. / 60.).abs() < 1e-9);
+        assert!(report.development_hours > 0.);
+        assert_eq!(
+            report.debt_ratio,
+            report.remediation_hours / report.development_hours
+        );
+    }
+}
@@ -0,0 +1,438 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use serde::{
+    ser::{SerializeStruct, Serializer},
+    Serialize,
+};
+
+use crate::spaces::{FuncSpace, SpaceKind};
+
+/// The minimum signature similarity for two function spaces with
+/// different fingerprints to be considered the same moved function.
+pub const MOVE_SIMILARITY_THRESHOLD: f64 = 0.8;
+
+/// The kind of change of a function space between two versions
+/// of a series of files.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    /// The function space has the same path, name and fingerprint
+    Unchanged,
+    /// The function space has the same path and name, but its code changed
+    Modified,
+    /// The function space has been renamed or moved to another file
+    Moved,
+    /// The function space only exists in the second version
+    Added,
+    /// The function space only exists in the first version
+    Removed,
+}
+
+/// A function space together with the path of the file containing it.
+#[derive(Clone, Copy, Debug)]
+pub struct SpaceRef<'a> {
+    /// The path of the file containing the function space
+    pub path: &'a Path,
+    /// The function space
+    pub space: &'a FuncSpace,
+}
+
+impl Serialize for SpaceRef<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut st = serializer.serialize_struct("space", 5)?;
+        st.serialize_field("path", &self.path)?;
+        st.serialize_field("name", &self.space.name)?;
+        st.serialize_field("start_line", &self.space.start_line)?;
+        st.serialize_field("end_line", &self.space.end_line)?;
+        st.serialize_field("fingerprint", &self.space.fingerprint)?;
+        st.end()
+    }
+}
+
+/// The change of a function space between two versions of a series
+/// of files.
+#[derive(Clone, Copy, Debug)]
+pub struct SpaceChange<'a> {
+    /// The kind of change
+    pub kind: ChangeKind,
+    /// The function space in the first version
+    ///
+    /// It is `None` for added spaces
+    pub before: Option<SpaceRef<'a>>,
+    /// The function space in the second version
+    ///
+    /// It is `None` for removed spaces
+    pub after: Option<SpaceRef<'a>>,
+}
+
+impl SpaceChange<'_> {
+    /// Returns `true` if the name of the function space changed.
+    pub fn is_renamed(&self) -> bool {
+        matches!((self.before, self.after), (Some(b), Some(a)) if b.space.name != a.space.name)
+    }
+
+    /// Returns `true` if the code of the function space changed.
+    pub fn is_modified(&self) -> bool {
+        matches!(
+            (self.before, self.after),
+            (Some(b), Some(a)) if b.space.fingerprint != a.space.fingerprint
+        )
+    }
+}
+
+impl Serialize for SpaceChange<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut st = serializer.serialize_struct("change", 5)?;
+        st.serialize_field("kind", &self.kind)?;
+        st.serialize_field("renamed", &self.is_renamed())?;
+        st.serialize_field("modified", &self.is_modified())?;
+        st.serialize_field("before", &self.before)?;
+        st.serialize_field("after", &self.after)?;
+        st.end()
+    }
+}
+
+fn flatten<'a>(path: &'a Path, space: &'a FuncSpace, spaces: &mut Vec<SpaceRef<'a>>) {
+    if space.kind != SpaceKind::Unit {
+        spaces.push(SpaceRef { path, space });
+    }
+    for space in space.spaces.iter() {
+        flatten(path, space, spaces);
+    }
+}
+
+fn flatten_files(files: &[(PathBuf, FuncSpace)]) -> Vec<SpaceRef<'_>> {
+    let mut spaces = Vec::new();
+    for (path, space) in files {
+        flatten(path, space, &mut spaces);
+    }
+    spaces
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let current = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                previous
+            } else {
+                previous.min(current).min(row[j]) + 1
+            };
+            previous = current;
+        }
+    }
+    row[b.len()]
+}
+
+/// Returns the similarity of the signatures of two function spaces,
+/// from 0 to 1.
+///
+/// Spaces of different kinds are never similar. Otherwise half of the
+/// similarity comes from the edit distance of their names, a quarter
+/// from their number of arguments and a quarter from their size.
+pub fn signature_similarity(a: &FuncSpace, b: &FuncSpace) -> f64 {
+    if a.kind != b.kind {
+        return 0.;
+    }
+    let name_a = a.name.as_deref().unwrap_or_default();
+    let name_b = b.name.as_deref().unwrap_or_default();
+    let name_len = name_a.chars().count().max(name_b.chars().count());
+    let name = if name_len == 0 {
+        1.
+    } else {
+        1. - levenshtein(name_a, name_b) as f64 / name_len as f64
+    };
+    let nargs = if a.metrics.nargs.nargs_total() == b.metrics.nargs.nargs_total() {
+        1.
+    } else {
+        0.
+    };
+    let sloc_a = a.metrics.loc.sloc();
+    let sloc_b = b.metrics.loc.sloc();
+    let sloc = if sloc_a.max(sloc_b) == 0. {
+        1.
+    } else {
+        1. - (sloc_a - sloc_b).abs() / sloc_a.max(sloc_b)
+    };
+    0.5 * name + 0.25 * nargs + 0.25 * sloc
+}
+
+/// Computes the changes of the function spaces of a series of files
+/// between two versions.
+///
+/// Function spaces are paired by path and name first, then by
+/// fingerprint, and finally by [`signature_similarity`], when it's at
+/// least [`MOVE_SIMILARITY_THRESHOLD`].
+/// The last two pairings report renamed and moved spaces as
+/// [`ChangeKind::Moved`] instead of a removed and an added space.
+///
+/// Units are not compared.
+pub fn diff_spaces<'a>(
+    before: &'a [(PathBuf, FuncSpace)],
+    after: &'a [(PathBuf, FuncSpace)],
+) -> Vec<SpaceChange<'a>> {
+    let before = flatten_files(before);
+    let after = flatten_files(after);
+    let mut paired_after = vec![false; after.len()];
+    let mut pairs: Vec<Option<usize>> = vec![None; before.len()];
+
+    // Same path and name
+    let mut by_name: HashMap<(&Path, Option<&str>), Vec<usize>> = HashMap::new();
+    for (i, space) in after.iter().enumerate().rev() {
+        by_name
+            .entry((space.path, space.space.name.as_deref()))
+            .or_default()
+            .push(i);
+    }
+    for (i, space) in before.iter().enumerate() {
+        if let Some(j) = by_name
+            .get_mut(&(space.path, space.space.name.as_deref()))
+            .and_then(|candidates| candidates.pop())
+        {
+            pairs[i] = Some(j);
+            paired_after[j] = true;
+        }
+    }
+
+    // Same fingerprint
+    let mut by_fingerprint: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (j, space) in after.iter().enumerate().rev() {
+        if !paired_after[j] {
+            by_fingerprint
+                .entry(space.space.fingerprint.as_str())
+                .or_default()
+                .push(j);
+        }
+    }
+    for (i, space) in before.iter().enumerate() {
+        if pairs[i].is_none() {
+            if let Some(j) = by_fingerprint
+                .get_mut(space.space.fingerprint.as_str())
+                .and_then(|candidates| candidates.pop())
+            {
+                pairs[i] = Some(j);
+                paired_after[j] = true;
+            }
+        }
+    }
+
+    // Similar signatures, the most similar pairs first
+    let mut candidates = Vec::new();
+    for (i, b) in before.iter().enumerate() {
+        if pairs[i].is_some() {
+            continue;
+        }
+        for (j, a) in after.iter().enumerate() {
+            if paired_after[j] {
+                continue;
+            }
+            let similarity = signature_similarity(b.space, a.space);
+            if similarity >= MOVE_SIMILARITY_THRESHOLD {
+                candidates.push((similarity, i, j));
+            }
+        }
+    }
+    candidates.sort_by(|x, y| y.0.total_cmp(&x.0).then((x.1, x.2).cmp(&(y.1, y.2))));
+    for (_, i, j) in candidates {
+        if pairs[i].is_none() && !paired_after[j] {
+            pairs[i] = Some(j);
+            paired_after[j] = true;
+        }
+    }
+
+    let mut changes = before
+        .iter()
+        .zip(pairs)
+        .map(|(b, pair)| {
+            let Some(j) = pair else {
+                return SpaceChange {
+                    kind: ChangeKind::Removed,
+                    before: Some(*b),
+                    after: None,
+                };
+            };
+            let a = after[j];
+            let kind = if b.path != a.path || b.space.name != a.space.name {
+                ChangeKind::Moved
+            } else if b.space.fingerprint != a.space.fingerprint {
+                ChangeKind::Modified
+            } else {
+                ChangeKind::Unchanged
+            };
+            SpaceChange {
+                kind,
+                before: Some(*b),
+                after: Some(a),
+            }
+        })
+        .collect::<Vec<_>>();
+    changes.extend(
+        after
+            .iter()
+            .zip(paired_after)
+            .filter(|(_, paired)| !paired)
+            .map(|(a, _)| SpaceChange {
+                kind: ChangeKind::Added,
+                before: None,
+                after: Some(*a),
+            }),
+    );
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{metrics, ParserEngineRust, ParserTrait};
+
+    use super::*;
+
+    fn files(sources: &[(&str, &str)]) -> Vec<(PathBuf, FuncSpace)> {
+        sources
+            .iter()
+            .map(|(file, code)| {
+                let path = PathBuf::from(file);
+                let parser = ParserEngineRust::new(code.as_bytes().to_vec(), &path, None);
+                let space = metrics(&parser, &path).unwrap();
+                (path, space)
+            })
+            .collect()
+    }
+
+    fn summary(changes: &[SpaceChange]) -> Vec<(ChangeKind, Option<String>, Option<String>)> {
+        changes
+            .iter()
+            .map(|change| {
+                (
+                    change.kind,
+                    change.before.and_then(|b| b.space.name.clone()),
+                    change.after.and_then(|a| a.space.name.clone()),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rust_diff_spaces() {
+        let before = files(&[
+            (
+                "a.rs",
+                "fn same() -> i32 { 1 }
+                 fn edited(x: i32) -> i32 { x }
+                 fn old_name(x: i32) -> i32 { x * 3 }
+                 fn moved(v: &[i32]) -> i32 { v.iter().sum() }
+                 fn gone() { drop(1); }",
+            ),
+            ("b.rs", "fn tweaked(a: i32, b: i32) -> i32 {\n    a + b\n}"),
+        ]);
+        let after = files(&[
+            (
+                "a.rs",
+                "fn same() -> i32 { 1 }
+                 fn edited(x: i32) -> i32 { x + 1 }
+                 fn new_name(x: i32) -> i32 { x * 3 }
+                 fn fresh() -> bool { true }",
+            ),
+            (
+                "c.rs",
+                "fn moved(v: &[i32]) -> i32 { v.iter().sum() }
+                 fn tweaked(a: i32, b: i32) -> i32 {\n    a - b\n}",
+            ),
+        ]);
+
+        let changes = diff_spaces(&before, &after);
+        insta::assert_json_snapshot!(
+            summary(&changes),
+            @r###"
+            [
+              [
+                "unchanged",
+                "same",
+                "same"
+              ],
+              [
+                "modified",
+                "edited",
+                "edited"
+              ],
+              [
+                "moved",
+                "old_name",
+                "new_name"
+              ],
+              [
+                "moved",
+                "moved",
+                "moved"
+              ],
+              [
+                "removed",
+                "gone",
+                null
+              ],
+              [
+                "moved",
+                "tweaked",
+                "tweaked"
+              ],
+              [
+                "added",
+                null,
+                "fresh"
+              ]
+            ]"###
+        );
+
+        let renamed = changes.iter().find(|change| change.is_renamed()).unwrap();
+        insta::assert_json_snapshot!(
+            renamed,
+            @r###"
+            {
+              "kind": "moved",
+              "renamed": true,
+              "modified": false,
+              "before": {
+                "path": "a.rs",
+                "name": "old_name",
+                "start_line": 3,
+                "end_line": 3,
+                "fingerprint": "30febeeba03981b1"
+              },
+              "after": {
+                "path": "a.rs",
+                "name": "new_name",
+                "start_line": 3,
+                "end_line": 3,
+                "fingerprint": "30febeeba03981b1"
+              }
+            }"###
+        );
+    }
+
+    #[test]
+    fn rust_signature_similarity() {
+        let spaces = files(&[(
+            "a.rs",
+            "fn parse_file(a: &str) -> u8 { 0 }
+             fn parse_files(a: &str) -> u8 { 0 }
+             fn render(a: &str, b: u8) { }",
+        )]);
+        let functions = &spaces[0].1.spaces;
+
+        assert!(signature_similarity(&functions[0], &functions[1]) >= MOVE_SIMILARITY_THRESHOLD);
+        assert!(signature_similarity(&functions[0], &functions[2]) < MOVE_SIMILARITY_THRESHOLD);
+        assert_eq!(signature_similarity(&spaces[0].1, &functions[0]), 0.);
+    }
+}
@@ -0,0 +1,355 @@
+use std::{
+    collections::BTreeMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::risk::RiskPolicy;
+
+/// The name of the config files of the directories.
+pub const CONFIG_FILE_NAME: &str = ".sca.toml";
+
+/// The settings of a `.sca.toml` file, which override the settings of the
+/// parent directories for the files of its directory and subdirectories,
+/// like the per-package settings of the linters.
+///
+/// ```toml
+/// # Don't inherit the settings of the parent directories
+/// root = false
+/// # The globs of the ignored files, relative to the directory
+/// ignore = ["generated/**"]
+/// # The enabled metrics, replacing the ones of the parent directories
+/// metrics = ["cyclomatic", "loc"]
+///
+/// # The thresholds overridden, see `RiskPolicy`
+/// [thresholds]
+/// max_cyclomatic = 20.0
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfigFile {
+    /// Whether the settings of the parent directories are ignored
+    pub root: bool,
+    /// The globs of the ignored files, relative to the directory
+    pub ignore: Vec<String>,
+    /// The enabled metrics, as named in the results
+    pub metrics: Option<Vec<String>>,
+    /// The thresholds overridden
+    pub thresholds: toml::Table,
+}
+
+impl ConfigFile {
+    /// Parses a config file from a `TOML` document.
+    pub fn from_toml(toml: &str) -> Result<Self, toml::de::Error> {
+        let file: Self = toml::from_str(toml)?;
+        // The thresholds are checked now, as they are merged later
+        toml::Value::Table(file.thresholds.clone()).try_into::<RiskPolicy>()?;
+        Ok(file)
+    }
+}
+
+/// The settings of the files of a directory.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ScaConfig {
+    /// The thresholds of the metrics
+    pub thresholds: RiskPolicy,
+    /// The globs of the ignored files, relative to the root of the
+    /// repository
+    pub ignore: Vec<String>,
+    /// The enabled metrics, all of them if `None`
+    pub metrics: Option<Vec<String>>,
+}
+
+impl ScaConfig {
+    /// Returns `true` if a metric is enabled.
+    pub fn is_metric_enabled(&self, metric: &str) -> bool {
+        self.metrics
+            .as_ref()
+            .is_none_or(|metrics| metrics.iter().any(|enabled| enabled == metric))
+    }
+}
+
+/// Error returned when the config files can't be loaded.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// A config file can't be read.
+    Io(PathBuf, io::Error),
+    /// A config file or an ignore glob is invalid.
+    Invalid(PathBuf, String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(path, err) => write!(f, "cannot read {}: {err}", path.display()),
+            ConfigError::Invalid(path, err) => write!(f, "invalid {}: {err}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Io(_, err) => Some(err),
+            ConfigError::Invalid(..) => None,
+        }
+    }
+}
+
+fn glob_set(patterns: &[String], path: &Path) -> Result<GlobSet, ConfigError> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern)
+            .map_err(|err| ConfigError::Invalid(path.to_path_buf(), err.to_string()))?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map_err(|err| ConfigError::Invalid(path.to_path_buf(), err.to_string()))
+}
+
+// The paths are matched with the same separators on all platforms
+fn slash_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+#[derive(Debug)]
+struct LoadedFile {
+    file: ConfigFile,
+    ignore: GlobSet,
+}
+
+/// The config files of the directories of a repository, merged with the
+/// global settings.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{ConfigFile, ConfigTree, ScaConfig};
+///
+/// let mut tree = ConfigTree::new(Path::new("repo"), ScaConfig::default()).unwrap();
+/// let file = ConfigFile::from_toml("[thresholds]\nmax_cyclomatic = 20.0").unwrap();
+/// tree.add(Path::new("repo/legacy"), file).unwrap();
+///
+/// let config = tree.config_for(Path::new("repo/legacy/a.rs"));
+/// assert_eq!(config.thresholds.max_cyclomatic, 20.);
+/// ```
+#[derive(Debug)]
+pub struct ConfigTree {
+    root: PathBuf,
+    global: ScaConfig,
+    global_ignore: GlobSet,
+    // The config files by directory, relative to the root
+    files: BTreeMap<PathBuf, LoadedFile>,
+}
+
+impl ConfigTree {
+    /// Creates the settings of a repository, without config files.
+    pub fn new(root: &Path, global: ScaConfig) -> Result<Self, ConfigError> {
+        Ok(Self {
+            root: root.to_path_buf(),
+            global_ignore: glob_set(&global.ignore, root)?,
+            global,
+            files: BTreeMap::new(),
+        })
+    }
+
+    /// Creates the settings of a repository, with the config files of
+    /// its directories, skipping the hidden ones.
+    pub fn discover(root: &Path, global: ScaConfig) -> Result<Self, ConfigError> {
+        let mut tree = Self::new(root, global)?;
+        let dirs = WalkDir::new(root).into_iter().filter_entry(|entry| {
+            entry.depth() == 0
+                || entry.file_type().is_dir()
+                    && !entry.file_name().to_string_lossy().starts_with('.')
+        });
+        for entry in dirs.filter_map(Result::ok) {
+            if entry.file_type().is_dir() {
+                tree.load_dir(entry.path())?;
+            }
+        }
+        Ok(tree)
+    }
+
+    /// Loads the config file of a directory, if it has one, for the
+    /// walkers discovering the config files as they explore the
+    /// directories.
+    ///
+    /// Returns `true` if the directory has a config file.
+    pub fn load_dir(&mut self, dir: &Path) -> Result<bool, ConfigError> {
+        let path = dir.join(CONFIG_FILE_NAME);
+        let toml = match fs::read_to_string(&path) {
+            Ok(toml) => toml,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(false),
+            Err(err) => return Err(ConfigError::Io(path, err)),
+        };
+        let file = ConfigFile::from_toml(&toml)
+            .map_err(|err| ConfigError::Invalid(path.clone(), err.to_string()))?;
+        self.add(dir, file)?;
+        Ok(true)
+    }
+
+    /// Adds the config file of a directory.
+    pub fn add(&mut self, dir: &Path, file: ConfigFile) -> Result<(), ConfigError> {
+        let ignore = glob_set(&file.ignore, &dir.join(CONFIG_FILE_NAME))?;
+        self.files.insert(
+            self.relative(dir).to_path_buf(),
+            LoadedFile { file, ignore },
+        );
+        Ok(())
+    }
+
+    fn relative<'a>(&self, path: &'a Path) -> &'a Path {
+        path.strip_prefix(&self.root).unwrap_or(path)
+    }
+
+    // The config files applying to a path, from the outermost one
+    fn layers<'a>(&'a self, path: &'a Path) -> Vec<(&'a Path, &'a LoadedFile)> {
+        let mut layers = Vec::new();
+        for dir in self.relative(path).ancestors() {
+            if let Some(loaded) = self.files.get(dir) {
+                layers.push((dir, loaded));
+                if loaded.file.root {
+                    break;
+                }
+            }
+        }
+        layers.reverse();
+        layers
+    }
+
+    /// Returns the settings of a file, merged from the global settings and
+    /// the config files of its directory and of its ancestors.
+    pub fn config_for(&self, path: &Path) -> ScaConfig {
+        let layers = self.layers(path);
+        let inherits = layers.first().is_none_or(|(_, loaded)| !loaded.file.root);
+
+        let mut config = if inherits {
+            self.global.clone()
+        } else {
+            ScaConfig::default()
+        };
+        let mut thresholds = match toml::Value::try_from(&config.thresholds) {
+            Ok(toml::Value::Table(thresholds)) => thresholds,
+            _ => toml::Table::new(),
+        };
+        for (dir, loaded) in layers {
+            let file = &loaded.file;
+            thresholds.extend(file.thresholds.clone());
+            let dir = slash_path(dir);
+            config
+                .ignore
+                .extend(file.ignore.iter().map(|pattern| match dir.as_str() {
+                    "" => pattern.clone(),
+                    dir => format!("{dir}/{pattern}"),
+                }));
+            if let Some(metrics) = &file.metrics {
+                config.metrics = Some(metrics.clone());
+            }
+        }
+        // The thresholds of the config files were checked when they were
+        // parsed
+        if let Ok(merged) = toml::Value::Table(thresholds).try_into() {
+            config.thresholds = merged;
+        }
+        config
+    }
+
+    /// Returns `true` if a file is ignored by the global settings or by
+    /// the config files of its ancestors.
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        let layers = self.layers(path);
+        let inherits = layers.first().is_none_or(|(_, loaded)| !loaded.file.root);
+        let relative = self.relative(path);
+
+        (inherits && self.global_ignore.is_match(slash_path(relative)))
+            || layers.iter().any(|(dir, loaded)| {
+                let path = relative.strip_prefix(dir).unwrap_or(relative);
+                loaded.ignore.is_match(slash_path(path))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merged_configs() {
+        let dir = std::env::temp_dir().join("sca_dir_config");
+        let _ = fs::remove_dir_all(&dir);
+        for sub in ["app/legacy", "tools", ".git"] {
+            fs::create_dir_all(dir.join(sub)).unwrap();
+        }
+        let write = |sub: &str, toml: &str| fs::write(dir.join(sub).join(CONFIG_FILE_NAME), toml);
+        write(
+            "",
+            "ignore = [\"**/*.min.js\"]\n\n[thresholds]\nmax_cyclomatic = 20.0\n",
+        )
+        .unwrap();
+        write(
+            "app/legacy",
+            "ignore = [\"gen/**\"]\nmetrics = [\"loc\"]\n\n[thresholds]\nmax_sloc = 200.0\n",
+        )
+        .unwrap();
+        write("tools", "root = true\n").unwrap();
+        write(".git", "not toml").unwrap();
+
+        let global = ScaConfig {
+            ignore: vec!["target/**".to_string()],
+            ..Default::default()
+        };
+        let tree = ConfigTree::discover(&dir, global).unwrap();
+
+        let config = tree.config_for(&dir.join("app/legacy/a.rs"));
+        assert_eq!(config.thresholds.max_cyclomatic, 20.);
+        assert_eq!(config.thresholds.max_sloc, 200.);
+        assert_eq!(
+            config.ignore,
+            ["target/**", "**/*.min.js", "app/legacy/gen/**"]
+        );
+        assert!(config.is_metric_enabled("loc"));
+        assert!(!config.is_metric_enabled("cyclomatic"));
+
+        let config = tree.config_for(&dir.join("app/b.rs"));
+        assert_eq!(config.thresholds.max_sloc, RiskPolicy::default().max_sloc);
+        assert!(config.is_metric_enabled("cyclomatic"));
+
+        let config = tree.config_for(&dir.join("tools/c.rs"));
+        assert_eq!(
+            config.thresholds.max_cyclomatic,
+            RiskPolicy::default().max_cyclomatic
+        );
+        assert!(config.ignore.is_empty());
+
+        assert!(tree.is_ignored(&dir.join("app/legacy/gen/d.rs")));
+        assert!(tree.is_ignored(&dir.join("app/e.min.js")));
+        assert!(tree.is_ignored(&dir.join("target/f.rs")));
+        assert!(!tree.is_ignored(&dir.join("app/gen/g.rs")));
+        assert!(!tree.is_ignored(&dir.join("tools/target/h.rs")));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn invalid_configs() {
+        assert!(ConfigFile::from_toml("[thresholds]\nmax_cyclomatic = \"high\"").is_err());
+        assert!(ConfigFile::from_toml("unknown = 1").is_err());
+
+        let mut tree = ConfigTree::new(Path::new("repo"), ScaConfig::default()).unwrap();
+        let file = ConfigFile {
+            ignore: vec!["[".to_string()],
+            ..Default::default()
+        };
+        assert!(matches!(
+            tree.add(Path::new("repo/a"), file),
+            Err(ConfigError::Invalid(..))
+        ));
+    }
+}
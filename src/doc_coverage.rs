@@ -0,0 +1,546 @@
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::{
+    checker::Checker,
+    facts::signature,
+    getter::Getter,
+    langs::{action, LANG},
+    markdown::code_blocks,
+    node::Node,
+    traits::{Callback, ParserTrait},
+};
+
+/// A code example of a documentation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CodeExample {
+    /// The Markdown document or the source file of the example
+    pub document: PathBuf,
+    /// The first line of the example in its document
+    pub line: usize,
+    /// The code of the example
+    pub code: String,
+}
+
+impl CodeExample {
+    /// Returns `true` if the example calls a function, like `name(1)`,
+    /// `value.name(1)` or `name::<u8>(1)`.
+    pub fn calls(&self, name: &str) -> bool {
+        let is_boundary = |c: Option<char>| c.is_none_or(|c| !(c.is_alphanumeric() || c == '_'));
+        !name.is_empty()
+            && self.code.match_indices(name).any(|(start, _)| {
+                let rest = self.code[start + name.len()..].trim_start();
+                is_boundary(self.code[..start].chars().next_back())
+                    && (rest.starts_with('(') || rest.starts_with("::<"))
+            })
+    }
+}
+
+/// Returns the code examples of a Markdown document: its fenced code
+/// blocks, whatever their language.
+pub fn markdown_examples(path: &Path, markdown: &str) -> Vec<CodeExample> {
+    code_blocks(markdown)
+        .into_iter()
+        .map(|block| CodeExample {
+            document: path.to_path_buf(),
+            line: block.start_line,
+            code: block.code,
+        })
+        .collect()
+}
+
+/// Where a function is called by a code example.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ExampleLink {
+    /// The document of the example
+    pub document: PathBuf,
+    /// The first line of the example in its document
+    pub line: usize,
+}
+
+/// The documentation of a public function.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ApiDocumentation {
+    /// The name of the function
+    pub name: String,
+    /// The signature of the function, on one line
+    pub signature: String,
+    /// The first line of the function
+    pub start_line: usize,
+    /// Whether the function has a doc comment or a docstring
+    pub documented: bool,
+    /// The number of code examples of its own documentation
+    pub examples: usize,
+    /// The examples of the Markdown documents and of the documentation of
+    /// the other functions calling the function
+    pub linked_examples: Vec<ExampleLink>,
+}
+
+impl ApiDocumentation {
+    /// Returns `true` if the function has an example, in its documentation
+    /// or elsewhere.
+    pub fn has_examples(&self) -> bool {
+        self.examples > 0 || !self.linked_examples.is_empty()
+    }
+}
+
+/// The documentation coverage of the public functions of a code.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct DocCoverage {
+    /// The public functions, in the order they appear
+    pub functions: Vec<ApiDocumentation>,
+}
+
+impl DocCoverage {
+    /// Returns the ratio of the public functions which are documented,
+    /// `1` if there are none.
+    pub fn documented_ratio(&self) -> f64 {
+        self.ratio(|function| function.documented)
+    }
+
+    /// Returns the ratio of the public functions which have an example,
+    /// `1` if there are none.
+    pub fn example_ratio(&self) -> f64 {
+        self.ratio(ApiDocumentation::has_examples)
+    }
+
+    /// Returns the public functions without any example.
+    pub fn missing_examples(&self) -> Vec<&ApiDocumentation> {
+        self.functions
+            .iter()
+            .filter(|function| !function.has_examples())
+            .collect()
+    }
+
+    fn ratio(&self, pred: fn(&ApiDocumentation) -> bool) -> f64 {
+        if self.functions.is_empty() {
+            return 1.;
+        }
+        let count = self
+            .functions
+            .iter()
+            .filter(|function| pred(function))
+            .count();
+        count as f64 / self.functions.len() as f64
+    }
+}
+
+// The doc comments or the docstring of a function, without their
+// markers, and the line where they start
+struct Doc {
+    line: usize,
+    text: String,
+}
+
+// A function which isn't nested in another one
+struct Function<'a> {
+    node: Node<'a>,
+    name: &'a str,
+    public: bool,
+    doc: Option<Doc>,
+}
+
+// Removes the comment markers of a line, like `///` or the `*` of a
+// block comment
+fn strip_markers(line: &str) -> &str {
+    let line = line.trim();
+    let line = line.strip_suffix("*/").unwrap_or(line).trim_end();
+    let line = ["///", "//!", "//", "/**", "/*!", "/*", "*", "#"]
+        .iter()
+        .find_map(|marker| line.strip_prefix(marker))
+        .unwrap_or(line);
+    line.strip_prefix(' ').unwrap_or(line)
+}
+
+fn is_doc_comment(text: &str, language: LANG) -> bool {
+    // All the comments above a Go declaration are its documentation
+    text.starts_with("///")
+        || text.starts_with("/**")
+        || (language == LANG::Go && text.starts_with("//"))
+}
+
+// The first statement of the body of a Python function, if it's a string
+fn docstring(function: &Node, code: &[u8]) -> Option<Doc> {
+    let body = function.child_by_field_name("body")?;
+    let statement = body
+        .children()
+        .find(|child| child.is_named() && child.kind() != "comment")
+        .filter(|statement| statement.kind() == "expression_statement")?;
+    let string = statement
+        .children()
+        .next()
+        .filter(|child| child.kind() == "string")?;
+    let text = string
+        .utf8_text(code)?
+        .trim_start_matches(|c: char| c.is_ascii_alphabetic())
+        .trim_matches(['"', '\'']);
+    Some(Doc {
+        line: string.start_row() + 1,
+        text: text.to_string(),
+    })
+}
+
+// The doc comments right above a declaration, across its attributes and
+// its decorators
+fn doc_comments<T: ParserTrait>(declaration: &Node, code: &[u8], language: LANG) -> Option<Doc> {
+    let mut comments = Vec::new();
+    let mut next = *declaration;
+    while let Some(node) = next.previous_named_sibling() {
+        if node.end_row() + 1 < next.start_row() {
+            break;
+        }
+        if T::Checker::is_comment(&node) {
+            if !is_doc_comment(node.utf8_text(code).unwrap_or_default(), language) {
+                break;
+            }
+            comments.push(node);
+        } else if !matches!(
+            node.kind(),
+            "attribute_item" | "attribute_list" | "decorator" | "annotation"
+        ) {
+            break;
+        }
+        next = node;
+    }
+    let first = comments.last()?;
+    let text = comments
+        .iter()
+        .rev()
+        .flat_map(|comment| comment.utf8_text(code).unwrap_or_default().lines())
+        .map(strip_markers)
+        .collect::<Vec<_>>()
+        .join("\n");
+    Some(Doc {
+        line: first.start_row() + 1,
+        text,
+    })
+}
+
+fn is_public(function: &Node, name: &str, code: &[u8], language: LANG) -> bool {
+    let is_modifier = |node: &Node, kind: &str, modifier: &str| {
+        node.kind().starts_with(kind)
+            && node
+                .utf8_text(code)
+                .is_some_and(|text| text.split_whitespace().any(|word| word == modifier))
+    };
+    match language {
+        LANG::Rust | LANG::Gleam => function
+            .children()
+            .any(|child| is_modifier(&child, "visibility_modifier", "pub")),
+        LANG::Java | LANG::Csharp => function
+            .children()
+            .any(|child| is_modifier(&child, "modifier", "public")),
+        LANG::Javascript | LANG::Typescript | LANG::Tsx => {
+            let mut node = *function;
+            while let Some(parent) = node.parent() {
+                if parent.kind() == "export_statement" {
+                    return true;
+                }
+                node = parent;
+            }
+            false
+        }
+        LANG::Go => name.starts_with(char::is_uppercase),
+        _ => !name.starts_with('_'),
+    }
+}
+
+fn functions<T: ParserTrait>(parser: &T) -> Vec<Function<'_>> {
+    let code = parser.get_code();
+    let language = parser.get_language();
+    let mut functions = Vec::new();
+    let mut stack = vec![parser.get_root()];
+    while let Some(node) = stack.pop() {
+        if !T::Checker::is_func(&node) {
+            let mut children = node.children().collect::<Vec<_>>();
+            children.reverse();
+            stack.extend(children);
+            continue;
+        }
+        let Some(name) = T::Getter::get_func_name(&node, code) else {
+            continue;
+        };
+        // The comments of a decorated or exported function are above the
+        // decorators or the export
+        let mut declaration = node;
+        while let Some(parent) = declaration
+            .parent()
+            .filter(|parent| matches!(parent.kind(), "decorated_definition" | "export_statement"))
+        {
+            declaration = parent;
+        }
+        functions.push(Function {
+            node,
+            name,
+            public: is_public(&node, name, code, language),
+            doc: docstring(&node, code).or_else(|| doc_comments::<T>(&declaration, code, language)),
+        });
+    }
+    functions
+}
+
+// The sections of a documentation starting with a line with a prefix and
+// going on while the lines satisfy a predicate, with their first row
+fn sections(text: &str, prefix: &str, more: fn(&str) -> bool) -> Vec<(usize, String)> {
+    let mut sections = Vec::new();
+    let mut lines = text.lines().map(str::trim).enumerate().peekable();
+    while let Some((row, line)) = lines.next() {
+        let Some(first) = line.strip_prefix(prefix) else {
+            continue;
+        };
+        let mut code = vec![first.trim_start()];
+        while let Some((_, line)) = lines.next_if(|(_, line)| more(line)) {
+            code.push(line);
+        }
+        sections.push((row, code.join("\n")));
+    }
+    sections
+}
+
+// The examples of a documentation: its fenced code blocks, the Python
+// doctests and the JSDoc `@example` tags
+fn doc_examples(path: &Path, doc: &Doc) -> Vec<CodeExample> {
+    let blocks = code_blocks(&doc.text)
+        .into_iter()
+        .map(|block| (block.start_line - 1, block.code));
+    let doctests = sections(&doc.text, ">>>", |line| {
+        line.starts_with(">>>") || line.starts_with("...")
+    });
+    let tags = sections(&doc.text, "@example", |line| !line.starts_with('@'));
+    blocks
+        .chain(doctests)
+        .chain(tags)
+        .map(|(row, code)| CodeExample {
+            document: path.to_path_buf(),
+            line: doc.line + row,
+            code,
+        })
+        .collect()
+}
+
+/// Returns the code examples of the documentation of the functions of a
+/// code, to link them to the functions of other codes with
+/// [`doc_coverage`].
+pub fn function_examples<T: ParserTrait>(parser: &T, path: &Path) -> Vec<CodeExample> {
+    functions(parser)
+        .iter()
+        .filter_map(|function| function.doc.as_ref())
+        .flat_map(|doc| doc_examples(path, doc))
+        .collect()
+}
+
+/// Returns the documentation coverage of the public functions of a code.
+///
+/// A function is linked to the examples calling it by its name: the
+/// given examples, like the ones of the Markdown documents from
+/// [`markdown_examples`], and the ones of the documentation of the other
+/// functions of the code.
+///
+/// A function is public according to the conventions of its language,
+/// like the `pub` of Rust, the `export` of `JavaScript` or the names
+/// without a leading underscore of Python, and the functions nested in
+/// another one are left out.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{doc_coverage, markdown_examples, ParserTrait, PythonParser};
+///
+/// let path = Path::new("foo.py");
+/// let code = b"def area(r):\n    \"\"\"Returns the area.\"\"\"\n    return r * r\n\ndef perimeter(r):\n    return 4 * r\n".to_vec();
+/// let parser = PythonParser::new(code, path, None);
+/// let examples = markdown_examples(Path::new("README.md"), "```python\narea(2)\n```\n");
+///
+/// let coverage = doc_coverage(&parser, path, &examples);
+/// assert_eq!(coverage.documented_ratio(), 0.5);
+/// assert_eq!(coverage.functions[0].linked_examples[0].line, 2);
+/// assert_eq!(coverage.missing_examples()[0].name, "perimeter");
+/// ```
+pub fn doc_coverage<T: ParserTrait>(
+    parser: &T,
+    path: &Path,
+    examples: &[CodeExample],
+) -> DocCoverage {
+    let code = parser.get_code();
+    let functions = functions(parser);
+    let own = functions
+        .iter()
+        .map(|function| {
+            function
+                .doc
+                .as_ref()
+                .map(|doc| doc_examples(path, doc))
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+
+    let mut coverage = DocCoverage::default();
+    for (i, function) in functions.iter().enumerate() {
+        if !function.public {
+            continue;
+        }
+        let others = own
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .flat_map(|(_, examples)| examples);
+        let linked_examples = examples
+            .iter()
+            .chain(others)
+            .filter(|example| example.calls(function.name))
+            .map(|example| ExampleLink {
+                document: example.document.clone(),
+                line: example.line,
+            })
+            .collect();
+        coverage.functions.push(ApiDocumentation {
+            name: function.name.to_string(),
+            signature: signature(&function.node, code),
+            start_line: function.node.start_row() + 1,
+            documented: function.doc.is_some(),
+            examples: own[i].len(),
+            linked_examples,
+        });
+    }
+    coverage
+}
+
+/// Computes the documentation coverage of a code.
+///
+/// Its configuration is the path of the code and the examples to link
+/// to its functions, as in [`doc_coverage`].
+pub struct DocumentationCoverage {
+    _guard: (),
+}
+
+impl Callback for DocumentationCoverage {
+    type Res = DocCoverage;
+    type Cfg = (PathBuf, Vec<CodeExample>);
+
+    fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
+        doc_coverage(parser, &cfg.0, &cfg.1)
+    }
+}
+
+/// Computes the documentation coverage of a code in a language, linking
+/// its functions to some examples.
+pub fn get_doc_coverage(
+    lang: &LANG,
+    source: Vec<u8>,
+    path: &Path,
+    examples: Vec<CodeExample>,
+) -> DocCoverage {
+    action::<DocumentationCoverage>(lang, source, path, None, (path.to_path_buf(), examples))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ParserEngineRust, PythonParser};
+
+    use super::*;
+
+    #[test]
+    fn rust_coverage() {
+        let code = "/// Parses a number.
+///
+/// ```
+/// let n = parse(\"1\");
+/// ```
+pub fn parse(text: &str) -> u32 {
+    0
+}
+
+/// Formats a number.
+#[inline]
+pub fn format(n: u32) -> String {
+    String::new()
+}
+
+pub fn undocumented() {}
+
+fn private() {}
+";
+        let path = Path::new("foo.rs");
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), path, None);
+        let readme = markdown_examples(
+            Path::new("README.md"),
+            "# Foo\n\n```rust\nlet s = format(1);\n```\n",
+        );
+        let coverage = doc_coverage(&parser, path, &readme);
+
+        let names = coverage
+            .functions
+            .iter()
+            .map(|function| function.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["parse", "format", "undocumented"]);
+        assert_eq!(
+            coverage.functions[0].signature,
+            "pub fn parse(text: &str) -> u32"
+        );
+        assert_eq!(coverage.functions[0].examples, 1);
+        assert!(coverage.functions[0].linked_examples.is_empty());
+        assert!(coverage.functions[1].documented);
+        assert_eq!(
+            coverage.functions[1].linked_examples,
+            [ExampleLink {
+                document: PathBuf::from("README.md"),
+                line: 4,
+            }]
+        );
+        assert!(!coverage.functions[2].documented);
+        assert_eq!(coverage.documented_ratio(), 2. / 3.);
+        assert_eq!(coverage.example_ratio(), 2. / 3.);
+        let missing = coverage.missing_examples();
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].name, "undocumented");
+
+        let examples = function_examples(&parser, path);
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].line, 4);
+    }
+
+    #[test]
+    fn python_doctests() {
+        let code = "def area(r):
+    \"\"\"Returns the area.
+
+    >>> area(2)
+    12.56
+    \"\"\"
+    return 3.14 * r * r
+
+def _helper():
+    pass
+
+def perimeter(r):
+    \"\"\"Returns the perimeter, see `area`.\"\"\"
+    return 6.28 * r
+";
+        let path = Path::new("foo.py");
+        let parser = PythonParser::new(code.as_bytes().to_vec(), path, None);
+        let coverage = doc_coverage(&parser, path, &[]);
+
+        assert_eq!(coverage.functions.len(), 2);
+        assert_eq!(coverage.functions[0].examples, 1);
+        assert_eq!(function_examples(&parser, path)[0].line, 4);
+        assert!(coverage.functions[1].documented);
+        assert!(!coverage.functions[1].has_examples());
+        assert_eq!(coverage.example_ratio(), 0.5);
+    }
+
+    #[test]
+    fn example_calls() {
+        let example = CodeExample {
+            document: PathBuf::from("README.md"),
+            line: 1,
+            code: "let v = parse_all(x);\nlet w = x.parse ();\n".to_string(),
+        };
+        assert!(example.calls("parse"));
+        assert!(!example.calls("all"));
+        assert!(!example.calls("x"));
+    }
+}
@@ -0,0 +1,332 @@
+use std::{
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+
+use crate::{
+    checker::Checker,
+    fingerprint::fingerprint,
+    getter::Getter,
+    langs::LANG,
+    similarity::{structural_vector, DIMENSIONS},
+    traits::ParserTrait,
+};
+
+/// A function to embed.
+#[derive(Clone, Copy, Debug)]
+pub struct EmbeddingInput<'a> {
+    /// The path of the file of the function
+    pub path: &'a Path,
+    /// The language of the function
+    pub language: LANG,
+    /// The name of the function
+    pub name: Option<&'a str>,
+    /// The code of the function
+    pub code: &'a str,
+    /// The structural vector of the function, as indexed by
+    /// [`SimilarityIndex`], for the providers which don't need to call a
+    /// model
+    ///
+    /// [`SimilarityIndex`]: crate::SimilarityIndex
+    pub structure: &'a [f32],
+}
+
+/// A provider of the embedding vectors of the functions.
+///
+/// Implement it to embed the functions with a model, e.g. by calling an
+/// embedding service.
+pub trait EmbeddingProvider {
+    /// The name of the model, stored with each vector.
+    fn model(&self) -> &str;
+
+    /// The number of dimensions of the vectors.
+    fn dimensions(&self) -> usize;
+
+    /// Returns the vector of a function, with [`dimensions`] values.
+    ///
+    /// [`dimensions`]: EmbeddingProvider::dimensions
+    fn embed(&self, function: &EmbeddingInput) -> Vec<f32>;
+}
+
+/// The embedding provider returning the structural vectors of the
+/// functions, which doesn't need any model.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StructuralEmbedding;
+
+impl EmbeddingProvider for StructuralEmbedding {
+    fn model(&self) -> &str {
+        "structural"
+    }
+
+    fn dimensions(&self) -> usize {
+        DIMENSIONS
+    }
+
+    fn embed(&self, function: &EmbeddingInput) -> Vec<f32> {
+        function.structure.to_vec()
+    }
+}
+
+/// The embedding vector of a function, with its metadata.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FunctionEmbedding {
+    /// The path of the file of the function
+    pub path: PathBuf,
+    /// The name of the language of the function
+    pub language: &'static str,
+    /// The name of the function
+    pub name: Option<String>,
+    /// The first line of the function
+    pub start_line: usize,
+    /// The last line of the function
+    pub end_line: usize,
+    /// The fingerprint of the function, as computed for the function
+    /// spaces
+    pub fingerprint: String,
+    /// The model of the vector
+    pub model: String,
+    /// The vector
+    pub vector: Vec<f32>,
+}
+
+/// Embeds every function of a code with a provider.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{
+///     embed_functions, write_embeddings_jsonl, ParserTrait, PythonParser, StructuralEmbedding,
+/// };
+///
+/// let path = Path::new("foo.py");
+/// let code = b"def f():\n    return 1\n\ndef g(x):\n    return x * 2\n".to_vec();
+/// let parser = PythonParser::new(code, path, None);
+///
+/// let embeddings = embed_functions(&parser, path, &StructuralEmbedding);
+/// assert_eq!(embeddings.len(), 2);
+///
+/// let mut jsonl = Vec::new();
+/// write_embeddings_jsonl(&embeddings, &mut jsonl).unwrap();
+/// assert_eq!(jsonl.iter().filter(|&&byte| byte == b'\n').count(), 2);
+/// ```
+pub fn embed_functions<T: ParserTrait>(
+    parser: &T,
+    path: &Path,
+    provider: &dyn EmbeddingProvider,
+) -> Vec<FunctionEmbedding> {
+    let code = parser.get_code();
+    let language = parser.get_language();
+    let mut embeddings = Vec::new();
+    let mut stack = vec![parser.get_root()];
+    while let Some(node) = stack.pop() {
+        if T::Checker::is_func(&node) {
+            let name = T::Getter::get_func_name(&node, code);
+            let structure = structural_vector::<T>(&node, code, true);
+            let input = EmbeddingInput {
+                path,
+                language,
+                name,
+                code: node.utf8_text(code).unwrap_or_default(),
+                structure: &structure,
+            };
+            embeddings.push(FunctionEmbedding {
+                path: path.to_path_buf(),
+                language: language.get_name(),
+                name: name.map(|name| name.to_string()),
+                start_line: node.start_row() + 1,
+                end_line: node.end_row() + 1,
+                fingerprint: fingerprint::<T::Checker>(&node, code),
+                model: provider.model().to_string(),
+                vector: provider.embed(&input),
+            });
+        }
+        let mut children = node.children().collect::<Vec<_>>();
+        children.reverse();
+        stack.extend(children);
+    }
+    embeddings
+}
+
+/// Writes embeddings as JSON Lines, one function per line.
+pub fn write_embeddings_jsonl<W: Write>(
+    embeddings: &[FunctionEmbedding],
+    mut writer: W,
+) -> io::Result<()> {
+    for embedding in embeddings {
+        serde_json::to_writer(&mut writer, embedding)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()
+}
+
+/// Writes embeddings as an Arrow IPC file, one function per row.
+///
+/// The vectors are stored in a fixed size list column, so all the
+/// embeddings must have the same number of dimensions.
+#[cfg(feature = "arrow")]
+pub fn write_embeddings_arrow<W: Write>(
+    embeddings: &[FunctionEmbedding],
+    writer: W,
+) -> Result<(), arrow::error::ArrowError> {
+    use std::sync::Arc;
+
+    use arrow::{
+        array::{ArrayRef, FixedSizeListArray, Float32Array, StringArray, UInt64Array},
+        datatypes::{DataType, Field, Schema},
+        error::ArrowError,
+        ipc::writer::FileWriter,
+        record_batch::RecordBatch,
+    };
+
+    let dimensions = embeddings.first().map_or(0, |first| first.vector.len());
+    if embeddings
+        .iter()
+        .any(|embedding| embedding.vector.len() != dimensions)
+    {
+        return Err(ArrowError::InvalidArgumentError(
+            "the embeddings have different dimensions".to_string(),
+        ));
+    }
+
+    let item = Arc::new(Field::new("item", DataType::Float32, false));
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("path", DataType::Utf8, false),
+        Field::new("language", DataType::Utf8, false),
+        Field::new("name", DataType::Utf8, true),
+        Field::new("start_line", DataType::UInt64, false),
+        Field::new("end_line", DataType::UInt64, false),
+        Field::new("fingerprint", DataType::Utf8, false),
+        Field::new("model", DataType::Utf8, false),
+        Field::new(
+            "vector",
+            DataType::FixedSizeList(item.clone(), dimensions as i32),
+            false,
+        ),
+    ]));
+
+    let strings = |field: fn(&FunctionEmbedding) -> &str| -> ArrayRef {
+        Arc::new(StringArray::from(
+            embeddings.iter().map(field).collect::<Vec<_>>(),
+        ))
+    };
+    let lines = |field: fn(&FunctionEmbedding) -> usize| -> ArrayRef {
+        Arc::new(UInt64Array::from(
+            embeddings
+                .iter()
+                .map(|embedding| field(embedding) as u64)
+                .collect::<Vec<_>>(),
+        ))
+    };
+    let values = Float32Array::from(
+        embeddings
+            .iter()
+            .flat_map(|embedding| embedding.vector.iter().copied())
+            .collect::<Vec<_>>(),
+    );
+    let columns = vec![
+        strings(|embedding| embedding.path.to_str().unwrap_or_default()),
+        strings(|embedding| embedding.language),
+        Arc::new(StringArray::from(
+            embeddings
+                .iter()
+                .map(|embedding| embedding.name.as_deref())
+                .collect::<Vec<_>>(),
+        )) as ArrayRef,
+        lines(|embedding| embedding.start_line),
+        lines(|embedding| embedding.end_line),
+        strings(|embedding| embedding.fingerprint.as_str()),
+        strings(|embedding| embedding.model.as_str()),
+        Arc::new(FixedSizeListArray::try_new(
+            item,
+            dimensions as i32,
+            Arc::new(values),
+            None,
+        )?),
+    ];
+
+    let batch = RecordBatch::try_new(schema.clone(), columns)?;
+    let mut writer = FileWriter::try_new(writer, &schema)?;
+    writer.write(&batch)?;
+    writer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ParserEngineRust;
+
+    use super::*;
+
+    struct Lengths;
+
+    impl EmbeddingProvider for Lengths {
+        fn model(&self) -> &str {
+            "lengths"
+        }
+
+        fn dimensions(&self) -> usize {
+            2
+        }
+
+        fn embed(&self, function: &EmbeddingInput) -> Vec<f32> {
+            vec![
+                function.code.len() as f32,
+                function.name.map_or(0., |name| name.len() as f32),
+            ]
+        }
+    }
+
+    fn embeddings(provider: &dyn EmbeddingProvider) -> Vec<FunctionEmbedding> {
+        let path = Path::new("foo.rs");
+        let code = "fn f() {}\n\nfn long_name(x: i32) -> i32 {\n    x + 1\n}\n";
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), path, None);
+        embed_functions(&parser, path, provider)
+    }
+
+    #[test]
+    fn structural_embeddings() {
+        let embeddings = embeddings(&StructuralEmbedding);
+        assert_eq!(embeddings.len(), 2);
+        assert_eq!(embeddings[1].name.as_deref(), Some("long_name"));
+        assert_eq!((embeddings[1].start_line, embeddings[1].end_line), (3, 5));
+        assert_eq!(embeddings[1].language, "rust");
+        assert!(embeddings
+            .iter()
+            .all(|embedding| embedding.vector.len() == DIMENSIONS));
+    }
+
+    #[test]
+    fn provider_embeddings() {
+        let embeddings = embeddings(&Lengths);
+        assert_eq!(embeddings[0].vector, [9., 1.]);
+        assert_eq!(embeddings[1].vector, [41., 9.]);
+        assert_eq!(embeddings[1].model, "lengths");
+
+        let mut jsonl = Vec::new();
+        write_embeddings_jsonl(&embeddings, &mut jsonl).unwrap();
+        let lines = String::from_utf8(jsonl).unwrap();
+        let rows = lines
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1]["name"], "long_name");
+        assert_eq!(rows[1]["vector"], serde_json::json!([41., 9.]));
+    }
+
+    #[cfg(feature = "arrow")]
+    #[test]
+    fn arrow_embeddings() {
+        let mut file = Vec::new();
+        write_embeddings_arrow(&embeddings(&Lengths), &mut file).unwrap();
+        assert!(file.starts_with(b"ARROW1"));
+
+        let reader =
+            arrow::ipc::reader::FileReader::try_new(std::io::Cursor::new(file), None).unwrap();
+        let rows = reader.map(|batch| batch.unwrap().num_rows()).sum::<usize>();
+        assert_eq!(rows, 2);
+    }
+}
@@ -0,0 +1,135 @@
+use std::fmt;
+
+use chardetng::EncodingDetector;
+use encoding_rs::{Encoding, UTF_8};
+use serde::{Deserialize, Serialize};
+
+/// How a source which isn't valid `UTF-8` is decoded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DecodeMode {
+    /// The charset is guessed and the source is transcoded to `UTF-8`,
+    /// replacing the malformed sequences with `U+FFFD`
+    #[default]
+    Lossy,
+    /// Only `UTF-8` sources and sources whose charset is declared by a
+    /// byte order mark are accepted, the other ones are skipped
+    Strict,
+}
+
+/// A source decoded to `UTF-8`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodedSource {
+    /// The code, as `UTF-8`
+    pub code: Vec<u8>,
+    /// The name of the original encoding of the code, e.g. `Shift_JIS`
+    pub encoding: &'static str,
+    /// `true` if some malformed sequences were replaced with `U+FFFD`
+    pub lossy: bool,
+}
+
+/// The error returned when a source can't be decoded in
+/// [`DecodeMode::Strict`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EncodingError {
+    /// The name of the encoding guessed for the source
+    pub encoding: &'static str,
+}
+
+impl fmt::Display for EncodingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the source is not valid UTF-8 (it looks like {}), skipped in strict mode",
+            self.encoding
+        )
+    }
+}
+
+impl std::error::Error for EncodingError {}
+
+/// Decodes a source to `UTF-8`.
+///
+/// `UTF-8` sources are returned untouched. A byte order mark declares the
+/// charset of a source, otherwise it is guessed from its content, which
+/// works well for legacy charsets like `windows-1252` or `Shift_JIS`.
+///
+/// # Examples
+///
+/// ```
+/// use singularity_code_analysis::{decode_source, DecodeMode};
+///
+/// // `café` in Latin-1
+/// let source = decode_source(b"caf\xE9 = 1\n".to_vec(), DecodeMode::Lossy).unwrap();
+/// assert_eq!(source.code, "café = 1\n".as_bytes());
+///
+/// assert!(decode_source(b"caf\xE9 = 1\n".to_vec(), DecodeMode::Strict).is_err());
+/// ```
+pub fn decode_source(code: Vec<u8>, mode: DecodeMode) -> Result<DecodedSource, EncodingError> {
+    if std::str::from_utf8(&code).is_ok() {
+        return Ok(DecodedSource {
+            code,
+            encoding: UTF_8.name(),
+            lossy: false,
+        });
+    }
+
+    let (encoding, bom_length, declared) = match Encoding::for_bom(&code) {
+        Some((encoding, bom_length)) => (encoding, bom_length, true),
+        None => {
+            let mut detector = EncodingDetector::new();
+            detector.feed(&code, true);
+            (detector.guess(None, true), 0, false)
+        }
+    };
+    let (text, lossy) = encoding.decode_without_bom_handling(&code[bom_length..]);
+    if mode == DecodeMode::Strict && (lossy || !declared) {
+        return Err(EncodingError {
+            encoding: encoding.name(),
+        });
+    }
+
+    Ok(DecodedSource {
+        code: text.into_owned().into_bytes(),
+        encoding: encoding.name(),
+        lossy,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_legacy_charsets() {
+        let source = decode_source("let x = 1;\n".into(), DecodeMode::Strict).unwrap();
+        assert_eq!(source.encoding, "UTF-8");
+        assert!(!source.lossy);
+
+        let latin1 = b"/* R\xE9sum\xE9 des donn\xE9es \xE0 traiter */\nint a = 1;\n";
+        let source = decode_source(latin1.to_vec(), DecodeMode::Lossy).unwrap();
+        assert_eq!(source.encoding, "windows-1252");
+        assert_eq!(
+            source.code,
+            "/* Résumé des données à traiter */\nint a = 1;\n".as_bytes()
+        );
+        assert_eq!(
+            decode_source(latin1.to_vec(), DecodeMode::Strict),
+            Err(EncodingError {
+                encoding: "windows-1252"
+            })
+        );
+
+        // `// 日本語のコメント` in Shift_JIS
+        let shift_jis =
+            b"// \x93\xfa\x96\x7b\x8c\xea\x82\xcc\x83\x52\x83\x81\x83\x93\x83\x67\nint a = 1;\n";
+        let source = decode_source(shift_jis.to_vec(), DecodeMode::Lossy).unwrap();
+        assert_eq!(source.encoding, "Shift_JIS");
+        assert_eq!(source.code, "// 日本語のコメント\nint a = 1;\n".as_bytes());
+
+        let utf16 = b"\xFF\xFEa\x00=\x001\x00\n\x00";
+        let source = decode_source(utf16.to_vec(), DecodeMode::Strict).unwrap();
+        assert_eq!(source.encoding, "UTF-16LE");
+        assert_eq!(source.code, b"a=1\n");
+    }
+}
@@ -0,0 +1,301 @@
+use std::{collections::HashMap, path::Path};
+
+use serde::Serialize;
+
+use crate::{
+    checker::Checker,
+    getter::Getter,
+    node::Node,
+    security::{callee, is_call},
+    spaces::{metrics, FuncSpace},
+    traits::ParserTrait,
+};
+
+// Returns the control flow construct of a node kind, as written in the
+// outline of a function
+fn control(kind: &str) -> Option<&'static str> {
+    Some(match kind {
+        "if_statement" | "if_expression" | "if_let_expression" | "elif_clause" => "if",
+        "switch_statement"
+        | "switch_expression"
+        | "match_expression"
+        | "match_statement"
+        | "expression_switch_statement"
+        | "type_switch_statement" => "switch",
+        "while_statement" | "while_expression" | "while_let_expression" | "do_statement" => "while",
+        "loop_expression" => "loop",
+        "try_statement" | "try_with_resources_statement" => "try",
+        "return_statement" | "return_expression" => "return",
+        "throw_statement" | "throw_expression" | "raise_statement" => "throw",
+        _ if kind.starts_with("for_")
+            || kind.starts_with("foreach")
+            || kind.ends_with("for_statement") =>
+        {
+            "for"
+        }
+        _ => return None,
+    })
+}
+
+/// The metrics of a function in its facts, the ones suppressed by a
+/// `sca-ignore` comment being omitted.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct FactMetrics {
+    /// The cyclomatic complexity
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cyclomatic: Option<f64>,
+    /// The cognitive complexity
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cognitive: Option<f64>,
+    /// The source lines
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sloc: Option<f64>,
+    /// The number of arguments
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nargs: Option<f64>,
+    /// The number of exit points
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nexits: Option<f64>,
+}
+
+/// The facts of a function, to summarize it in natural language.
+///
+/// Serialized as compact JSON, they are meant to be put in the prompt of
+/// a language model.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FunctionFacts {
+    /// The name of the function
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The signature of the function: its code before its body, on one
+    /// line
+    pub signature: String,
+    /// The first line of the function
+    pub start_line: usize,
+    /// The last line of the function
+    pub end_line: usize,
+    /// The functions and methods called, in the order of their first
+    /// call
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub calls: Vec<String>,
+    /// The outline of the control flow, like `for(if(return)) return`,
+    /// where the constructs nested in another are in parentheses
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub outline: String,
+    /// The metrics of the function, if computed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<FactMetrics>,
+}
+
+impl FactMetrics {
+    /// Returns the metrics of a function space.
+    pub fn new(space: &FuncSpace) -> Self {
+        let metric = |id, value| (!space.is_suppressed(id)).then_some(value);
+        let metrics = &space.metrics;
+        Self {
+            cyclomatic: metric("cyclomatic", metrics.cyclomatic.cyclomatic()),
+            cognitive: metric("cognitive", metrics.cognitive.cognitive()),
+            sloc: metric("loc", metrics.loc.sloc()),
+            nargs: metric(
+                "nargs",
+                metrics.nargs.fn_args() + metrics.nargs.closure_args(),
+            ),
+            nexits: metric("nexits", metrics.nexits.exit()),
+        }
+    }
+}
+
+impl FunctionFacts {
+    /// Returns the facts as compact JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+pub(crate) fn signature(node: &Node, code: &[u8]) -> String {
+    let end = node
+        .child_by_field_name("body")
+        .map_or(node.end_byte(), |body| body.start_byte());
+    let text = String::from_utf8_lossy(&code[node.start_byte()..end]);
+    let mut line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    // The `:` of Python and the `=>` of the arrow functions
+    if let Some(head) = [":", "=>", "="]
+        .iter()
+        .find_map(|suffix| line.strip_suffix(suffix))
+    {
+        line = head.trim_end().to_string();
+    }
+    if line.is_empty() || end == node.end_byte() {
+        // Without a body, the first line is enough
+        text.lines().next().unwrap_or_default().trim().to_string()
+    } else {
+        line
+    }
+}
+
+// Collects the outline of the control flow of the descendants of a node,
+// and their calls if `calls` is given, skipping the nested functions
+fn walk<T: ParserTrait>(
+    node: &Node,
+    code: &[u8],
+    mut calls: Option<&mut Vec<String>>,
+) -> Vec<String> {
+    let mut outline = Vec::new();
+    for child in node.children() {
+        if T::Checker::is_func(&child) || T::Checker::is_closure(&child) {
+            continue;
+        }
+        if let Some(calls) = calls.as_deref_mut().filter(|_| is_call(&child)) {
+            if let Some(callee) = callee(&child, code) {
+                if !calls.contains(&callee) {
+                    calls.push(callee);
+                }
+            }
+        }
+        let nested = walk::<T>(&child, code, calls.as_deref_mut());
+        match control(child.kind()) {
+            Some(construct) if nested.is_empty() => outline.push(construct.to_string()),
+            Some(construct) => outline.push(format!("{construct}({})", nested.join(" "))),
+            None => outline.extend(nested),
+        }
+    }
+    outline
+}
+
+fn space_metrics(space: &FuncSpace, spaces: &mut HashMap<(usize, usize), FactMetrics>) {
+    spaces.insert(
+        (space.start_line, space.start_column),
+        FactMetrics::new(space),
+    );
+    for space in space.spaces.iter() {
+        space_metrics(space, spaces);
+    }
+}
+
+/// Returns the facts of each function of a code, in the order they
+/// appear.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{function_facts, ParserTrait, PythonParser};
+///
+/// let path = Path::new("foo.py");
+/// let code = b"def total(items):\n    for item in items:\n        if not item:\n            raise ValueError()\n    return sum(items)\n".to_vec();
+/// let parser = PythonParser::new(code, path, None);
+///
+/// let facts = function_facts(&parser, path);
+/// assert_eq!(facts[0].signature, "def total(items)");
+/// assert_eq!(facts[0].calls, ["ValueError", "sum"]);
+/// assert_eq!(facts[0].outline, "for(if(throw)) return");
+/// ```
+pub fn function_facts<T: ParserTrait>(parser: &T, path: &Path) -> Vec<FunctionFacts> {
+    facts(parser, path, true)
+}
+
+// Returns the facts of each function of a code, with their calls if
+// `calls` is set
+pub(crate) fn facts<T: ParserTrait>(parser: &T, path: &Path, calls: bool) -> Vec<FunctionFacts> {
+    let code = parser.get_code();
+    let mut spaces = HashMap::new();
+    if let Some(root) = metrics(parser, path) {
+        space_metrics(&root, &mut spaces);
+    }
+
+    let mut facts = Vec::new();
+    let mut stack = vec![parser.get_root()];
+    while let Some(node) = stack.pop() {
+        if T::Checker::is_func(&node) {
+            let mut callees = Vec::new();
+            let outline = walk::<T>(&node, code, calls.then_some(&mut callees)).join(" ");
+            let (row, column) = node.start_position();
+            facts.push(FunctionFacts {
+                name: T::Getter::get_func_name(&node, code).map(|name| name.to_string()),
+                signature: signature(&node, code),
+                start_line: row + 1,
+                end_line: node.end_row() + 1,
+                calls: callees,
+                outline,
+                metrics: spaces.get(&(row + 1, column + 1)).cloned(),
+            });
+        }
+        let mut children = node.children().collect::<Vec<_>>();
+        children.reverse();
+        stack.extend(children);
+    }
+    facts
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{JavascriptParser, ParserEngineRust};
+
+    use super::*;
+
+    #[test]
+    fn rust_facts() {
+        let code = "pub fn parse(input: &str) -> Result<u32, Error> {
+    let mut total = 0;
+    for line in input.lines() {
+        match line.parse::<u32>() {
+            Ok(value) => total += value,
+            Err(_) => return Err(Error::new()),
+        }
+    }
+    Ok(total)
+}
+";
+        let path = Path::new("foo.rs");
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), path, None);
+        let facts = function_facts(&parser, path);
+        assert_eq!(facts.len(), 1);
+        let facts = &facts[0];
+        assert_eq!(facts.name.as_deref(), Some("parse"));
+        assert_eq!(
+            facts.signature,
+            "pub fn parse(input: &str) -> Result<u32, Error>"
+        );
+        assert_eq!(
+            facts.calls,
+            ["input.lines", "line.parse", "Err", "Error::new", "Ok"]
+        );
+        assert_eq!(facts.outline, "for(switch(return))");
+        let metrics = facts.metrics.as_ref().unwrap();
+        assert_eq!(metrics.nargs, Some(1.));
+        assert_eq!((facts.start_line, facts.end_line), (1, 10));
+    }
+
+    #[test]
+    fn suppressed_facts() {
+        let code = "fn f(a: u32) -> u32 { // sca-ignore: cc\n    a\n}\n";
+        let path = Path::new("foo.rs");
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), path, None);
+        let facts = function_facts(&parser, path);
+        let metrics = facts[0].metrics.as_ref().unwrap();
+        assert_eq!(metrics.cyclomatic, None);
+        assert_eq!(metrics.nargs, Some(1.));
+        assert!(!facts[0].to_json().contains("cyclomatic"));
+    }
+
+    #[test]
+    fn javascript_facts() {
+        let code = "function greet(name) {\n    const log = () => console.log(name);\n    if (name) { log(); }\n}\n";
+        let path = Path::new("foo.js");
+        let parser = JavascriptParser::new(code.as_bytes().to_vec(), path, None);
+        let facts = function_facts(&parser, path);
+        // The arrow function is a closure, so its call isn't a call of `greet`
+        assert_eq!(facts.len(), 1);
+        assert_eq!(facts[0].signature, "function greet(name)");
+        assert_eq!(facts[0].calls, ["log"]);
+        assert_eq!(facts[0].outline, "if");
+        assert_eq!(
+            facts[0].to_json(),
+            format!(
+                "{{\"name\":\"greet\",\"signature\":\"function greet(name)\",\"start_line\":1,\"end_line\":4,\"calls\":[\"log\"],\"outline\":\"if\",\"metrics\":{}}}",
+                serde_json::to_string(facts[0].metrics.as_ref().unwrap()).unwrap()
+            )
+        );
+    }
+}
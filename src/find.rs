@@ -1,6 +1,15 @@
 use std::path::PathBuf;
 
-use crate::{dump::*, node::Node, traits::*};
+use regex::Regex;
+use serde::Serialize;
+
+use crate::{
+    analysis_context::node_text,
+    dump::*,
+    node::Node,
+    position::{get_snippet, OffsetEncoding, Position, SourceMap},
+    traits::*,
+};
 
 /// Finds the types of nodes specified in the input slice.
 pub fn find<'a, T: ParserTrait>(parser: &'a T, filters: &[String]) -> Option<Vec<Node<'a>>> {
@@ -33,6 +42,100 @@ pub fn find<'a, T: ParserTrait>(parser: &'a T, filters: &[String]) -> Option<Vec
     Some(good)
 }
 
+/// A predicate on the text of a node.
+#[derive(Clone, Debug)]
+pub enum TextPredicate {
+    /// The text matches a regular expression
+    Regex(Regex),
+    /// The text contains a string
+    Contains(String),
+    /// The text is a string
+    Equals(String),
+}
+
+impl TextPredicate {
+    /// Returns `true` if a text satisfies the predicate.
+    pub fn matches(&self, text: &str) -> bool {
+        match self {
+            Self::Regex(regex) => regex.is_match(text),
+            Self::Contains(string) => text.contains(string.as_str()),
+            Self::Equals(string) => text == string,
+        }
+    }
+}
+
+/// A node found by [`find_matches`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct FindMatch {
+    /// The kind of the node
+    pub kind: &'static str,
+    /// The text of the node
+    pub text: String,
+    /// The byte offset of the start of the node
+    pub start_byte: usize,
+    /// The byte offset of the end of the node
+    pub end_byte: usize,
+    /// The position of the start of the node
+    pub start: Position,
+    /// The position of the end of the node
+    pub end: Position,
+}
+
+/// Finds the nodes of the types specified in the input slice whose text
+/// satisfies all the predicates, like a grep aware of the structure of
+/// the code.
+///
+/// The columns of the positions of the matches are counted in the given
+/// encoding. The nodes whose text isn't valid `UTF-8` never match.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use regex::Regex;
+/// use singularity_code_analysis::{
+///     find_matches, OffsetEncoding, ParserEngineRust, ParserTrait, TextPredicate,
+/// };
+///
+/// let code = b"fn f() {\n    // TODO: split\n    // done\n}\n";
+/// let path = Path::new("foo.rs");
+/// let parser = ParserEngineRust::new(code.to_vec(), path, None);
+///
+/// let todo = TextPredicate::Regex(Regex::new(r"\bTODO\b").unwrap());
+/// let matches = find_matches(&parser, &["comment".to_string()], &[todo], OffsetEncoding::Utf8);
+/// assert_eq!(matches.len(), 1);
+/// assert_eq!(matches[0].start.line, 2);
+/// ```
+pub fn find_matches<T: ParserTrait>(
+    parser: &T,
+    filters: &[String],
+    predicates: &[TextPredicate],
+    encoding: OffsetEncoding,
+) -> Vec<FindMatch> {
+    let code = parser.get_code();
+    let source_map = SourceMap::new(code);
+    find(parser, filters)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|node| {
+            let text = node_text(&node, code)?;
+            if !predicates.iter().all(|predicate| predicate.matches(text)) {
+                return None;
+            }
+            let (start, end) = source_map.node_range(&node, encoding);
+            Some(FindMatch {
+                kind: node.kind(),
+                text: text.to_string(),
+                start_byte: node.start_byte(),
+                end_byte: node.end_byte(),
+                start,
+                end,
+            })
+        })
+        .collect()
+}
+
 /// Configuration options for finding different
 /// types of nodes in a code.
 #[derive(Debug)]
@@ -51,6 +154,15 @@ pub struct FindCfg {
     /// If `None`, the search ends at the
     /// last line of code in a file
     pub line_end: Option<usize>,
+    /// The unit used to count the columns of the found nodes
+    pub offset_encoding: OffsetEncoding,
+    /// The number of context lines surrounding the source code
+    /// printed for each found node
+    ///
+    /// If `None`, no source code is printed
+    pub snippet_context: Option<usize>,
+    /// The predicates the text of the found nodes must satisfy
+    pub text_predicates: Vec<TextPredicate>,
 }
 
 pub struct Find {
@@ -62,11 +174,36 @@ impl Callback for Find {
     type Cfg = FindCfg;
 
     fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
-        if let Some(good) = find(parser, &cfg.filters) {
+        if let Some(mut good) = find(parser, &cfg.filters) {
+            good.retain(|node| {
+                cfg.text_predicates.is_empty()
+                    || node_text(node, parser.get_code()).is_some_and(|text| {
+                        cfg.text_predicates
+                            .iter()
+                            .all(|predicate| predicate.matches(text))
+                    })
+            });
             if !good.is_empty() {
                 println!("In file {}", cfg.path.to_str().unwrap());
                 for node in good {
-                    dump_node(parser.get_code(), &node, 1, cfg.line_start, cfg.line_end)?;
+                    dump_node(
+                        parser.get_code(),
+                        &node,
+                        1,
+                        cfg.line_start,
+                        cfg.line_end,
+                        cfg.offset_encoding,
+                    )?;
+                    if let Some(snippet) = cfg.snippet_context.and_then(|context| {
+                        get_snippet(
+                            parser.get_code(),
+                            node.start_row() + 1,
+                            node.end_row() + 1,
+                            context,
+                        )
+                    }) {
+                        print!("{snippet}");
+                    }
                 }
                 println!();
             }
@@ -74,3 +211,46 @@ impl Callback for Find {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{ParserEngineRust, ParserTrait};
+
+    use super::*;
+
+    #[test]
+    fn text_predicates() {
+        let code = "fn f() {\n    let é = \"unsafe\";\n    g(\"safe\");\n}\n";
+        let path = Path::new("foo.rs");
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), path, None);
+        let strings = ["string".to_string()];
+
+        assert_eq!(
+            find_matches(&parser, &strings, &[], OffsetEncoding::Utf8).len(),
+            2
+        );
+
+        let unsafe_strings = [
+            TextPredicate::Contains("safe".to_string()),
+            TextPredicate::Regex(Regex::new("^\"un").unwrap()),
+        ];
+        let matches = find_matches(&parser, &strings, &unsafe_strings, OffsetEncoding::Utf16);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].kind, "string_literal");
+        assert_eq!(matches[0].text, "\"unsafe\"");
+        assert_eq!(
+            matches[0].start,
+            Position {
+                line: 2,
+                column: 13
+            }
+        );
+        assert_eq!(matches[0].end_byte - matches[0].start_byte, 8);
+
+        let equals = [TextPredicate::Equals("\"safe\"".to_string())];
+        let matches = find_matches(&parser, &strings, &equals, OffsetEncoding::Utf8);
+        assert_eq!(matches[0].start, Position { line: 3, column: 7 });
+    }
+}
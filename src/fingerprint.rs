@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+
+use crate::{
+    checker::Checker,
+    node::Node,
+    spaces::{FuncSpace, SpaceKind},
+};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+// The 64-bit FNV-1a hash, which doesn't depend on the platform or on
+// the version of the standard library
+struct Fnv(u64);
+
+impl Fnv {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+}
+
+/// Returns the 64-bit FNV-1a hash of some bytes.
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash = Fnv(FNV_OFFSET_BASIS);
+    hash.write(bytes);
+    hash.0
+}
+
+/// Computes the fingerprint of a function space.
+///
+/// The hash covers the kinds of the nodes of a space, their arity and
+/// the text of its leaves, so whitespace and comments are ignored.
+/// The name of the space is ignored too, to recognize renamed functions.
+pub(crate) fn fingerprint<T: Checker>(node: &Node, code: &[u8]) -> String {
+    let mut hash = Fnv(FNV_OFFSET_BASIS);
+    let name = node.child_by_field_name("name").map(|name| name.id());
+    let mut stack = vec![*node];
+    let mut children = Vec::new();
+
+    while let Some(node) = stack.pop() {
+        children.extend(
+            node.children()
+                .filter(|child| !T::is_comment(child) && Some(child.id()) != name),
+        );
+        hash.write(node.kind().as_bytes());
+        hash.write(&[0xff]);
+        hash.write(&(children.len() as u64).to_le_bytes());
+        if children.is_empty() {
+            hash.write(&code[node.start_byte()..node.end_byte()]);
+        }
+        stack.extend(children.drain(..).rev());
+    }
+
+    format!("{:016x}", hash.0)
+}
+
+/// A function space of a code paired with the function space having
+/// the same fingerprint in another version of that code.
+#[derive(Debug, Clone, Copy)]
+pub struct SpaceMatch<'a> {
+    /// The function space in the first version of the code
+    pub before: &'a FuncSpace,
+    /// The function space in the second version of the code
+    pub after: &'a FuncSpace,
+}
+
+impl SpaceMatch<'_> {
+    /// Returns `true` if the function space has been renamed.
+    pub fn is_renamed(&self) -> bool {
+        self.before.name != self.after.name
+    }
+
+    /// Returns `true` if the function space has been moved to
+    /// another line.
+    pub fn is_moved(&self) -> bool {
+        self.before.start_line != self.after.start_line
+    }
+}
+
+fn flatten<'a>(space: &'a FuncSpace, spaces: &mut Vec<&'a FuncSpace>) {
+    if space.kind != SpaceKind::Unit {
+        spaces.push(space);
+    }
+    for space in space.spaces.iter() {
+        flatten(space, spaces);
+    }
+}
+
+/// Pairs the function spaces of two versions of a code having the
+/// same fingerprint, in the order they appear in the first version.
+///
+/// Units are not paired, and each function space is paired at most once.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{match_spaces, metrics, ParserEngineRust, ParserTrait};
+///
+/// let path = Path::new("foo.rs");
+/// let before = ParserEngineRust::new(b"fn f() { g(); }".to_vec(), path, None);
+/// let after = ParserEngineRust::new(b"\n// Moved\nfn h() {\n    g();\n}".to_vec(), path, None);
+///
+/// let before = metrics(&before, path).unwrap();
+/// let after = metrics(&after, path).unwrap();
+///
+/// let matches = match_spaces(&before, &after);
+/// assert_eq!(matches.len(), 1);
+/// assert!(matches[0].is_renamed() && matches[0].is_moved());
+/// ```
+pub fn match_spaces<'a>(before: &'a FuncSpace, after: &'a FuncSpace) -> Vec<SpaceMatch<'a>> {
+    let mut after_spaces = Vec::new();
+    flatten(after, &mut after_spaces);
+    let mut candidates: HashMap<&str, Vec<&FuncSpace>> = HashMap::new();
+    for space in after_spaces.into_iter().rev() {
+        candidates
+            .entry(space.fingerprint.as_str())
+            .or_default()
+            .push(space);
+    }
+
+    let mut before_spaces = Vec::new();
+    flatten(before, &mut before_spaces);
+    before_spaces
+        .into_iter()
+        .filter_map(|before| {
+            candidates
+                .get_mut(before.fingerprint.as_str())
+                .and_then(|spaces| spaces.pop())
+                .map(|after| SpaceMatch { before, after })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{metrics, FuncSpace, ParserEngineRust, ParserTrait};
+
+    use super::*;
+
+    fn spaces<T: ParserTrait>(code: &str, file: &str) -> FuncSpace {
+        let path = Path::new(file);
+        let parser = T::new(code.as_bytes().to_vec(), path, None);
+        metrics(&parser, path).unwrap()
+    }
+
+    #[test]
+    fn rust_fingerprint_ignores_layout_comments_and_name() {
+        let before = spaces::<ParserEngineRust>("fn f(a: i32) -> i32 { a + 1 }", "foo.rs");
+        let after = spaces::<ParserEngineRust>(
+            "// A comment
+             fn g(a: i32) -> i32 {
+                 // Add one
+                 a
+                     + 1
+             }",
+            "foo.rs",
+        );
+        let changed = spaces::<ParserEngineRust>("fn f(a: i32) -> i32 { a + 2 }", "foo.rs");
+
+        assert_eq!(before.spaces[0].fingerprint.len(), 16);
+        assert_eq!(before.spaces[0].fingerprint, after.spaces[0].fingerprint);
+        assert_ne!(before.spaces[0].fingerprint, changed.spaces[0].fingerprint);
+    }
+
+    #[test]
+    fn rust_fingerprint_is_stable() {
+        let space = spaces::<ParserEngineRust>("fn f() {}", "foo.rs");
+        insta::assert_json_snapshot!(space.spaces[0].fingerprint, @r###""0038abfb8c6b21a9""###);
+    }
+
+    #[test]
+    fn rust_match_spaces() {
+        let before = spaces::<ParserEngineRust>(
+            "fn a(x: i32) -> i32 {
+                 x + 1
+             }
+             fn b(y: i32) -> i32 {
+                 y * 2
+             }",
+            "foo.rs",
+        );
+        let after = spaces::<ParserEngineRust>(
+            "fn b(y: i32) -> i32 {
+                 y * 2
+             }
+             fn c(x: i32) -> i32 {
+                 x + 1
+             }
+             fn d(z: i32) {}",
+            "foo.rs",
+        );
+
+        let matches = match_spaces(&before, &after)
+            .into_iter()
+            .map(|m| {
+                (
+                    m.before.name.clone(),
+                    m.after.name.clone(),
+                    m.is_renamed(),
+                    m.is_moved(),
+                )
+            })
+            .collect::<Vec<_>>();
+        insta::assert_json_snapshot!(
+            matches,
+            @r###"
+            [
+              [
+                "a",
+                "c",
+                true,
+                true
+              ],
+              [
+                "b",
+                "b",
+                false,
+                true
+              ]
+            ]"###
+        );
+    }
+}
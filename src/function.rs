@@ -1,11 +1,17 @@
-use std::{io::Write, path::PathBuf};
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
 
 use serde::Serialize;
 use termcolor::{Color, ColorChoice, StandardStream, StandardStreamLock};
 
 use crate::{
     checker::Checker,
+    fingerprint::fingerprint,
     getter::Getter,
+    langs::{action, LANG},
+    position::{get_snippet, OffsetEncoding, Snippet, SourceMap},
     tools::{color, intense_color},
     traits::*,
 };
@@ -19,9 +25,26 @@ pub struct FunctionSpan {
     pub start_line: usize,
     /// The last line of a function
     pub end_line: usize,
+    /// The column of the first character of a function
+    pub start_column: usize,
+    /// The column following the last character of a function
+    pub end_column: usize,
     /// If `true`, an error is occurred in determining the span
     /// of a function
     pub error: bool,
+    /// The source code of a function
+    ///
+    /// It is only filled in by [`FunctionSpan::set_snippet`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<Snippet>,
+}
+
+impl FunctionSpan {
+    /// Embeds the source code of a function, surrounded by `context`
+    /// lines before and after it.
+    pub fn set_snippet(&mut self, code: &[u8], context: usize) {
+        self.snippet = get_snippet(code, self.start_line, self.end_line, context);
+    }
 }
 
 /// Detects the span of each function in a code.
@@ -30,26 +53,48 @@ pub struct FunctionSpan {
 ///
 /// [`FunctionSpan`]: struct.FunctionSpan.html
 pub fn function<T: ParserTrait>(parser: &T) -> Vec<FunctionSpan> {
+    function_with_encoding(parser, OffsetEncoding::Utf8)
+}
+
+/// Detects the span of each function in a code, with the columns
+/// counted in the given [`OffsetEncoding`].
+///
+/// Returns a vector containing the [`FunctionSpan`] of each function
+///
+/// [`FunctionSpan`]: struct.FunctionSpan.html
+pub fn function_with_encoding<T: ParserTrait>(
+    parser: &T,
+    encoding: OffsetEncoding,
+) -> Vec<FunctionSpan> {
     let root = parser.get_root();
     let code = parser.get_code();
+    let lines = SourceMap::new(code);
     let mut spans = Vec::new();
     root.act_on_node(&mut |n| {
         if T::Checker::is_func(n) {
             let start_line = n.start_row() + 1;
             let end_line = n.end_row() + 1;
+            let start_column = lines.column(start_line, n.start_position().1 + 1, encoding);
+            let end_column = lines.column(end_line, n.end_position().1 + 1, encoding);
             if let Some(name) = T::Getter::get_func_name(n, code) {
                 spans.push(FunctionSpan {
                     name: name.to_string(),
                     start_line,
                     end_line,
+                    start_column,
+                    end_column,
                     error: false,
+                    snippet: None,
                 });
             } else {
                 spans.push(FunctionSpan {
                     name: "".to_string(),
                     start_line,
                     end_line,
+                    start_column,
+                    end_column,
                     error: true,
+                    snippet: None,
                 });
             }
         }
@@ -58,6 +103,76 @@ pub fn function<T: ParserTrait>(parser: &T) -> Vec<FunctionSpan> {
     spans
 }
 
+/// The boundaries of a function, for the tools mapping their data, like
+/// coverage or profiles, to the functions of a code.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct FunctionBoundary {
+    /// The function name, if it has one
+    pub name: Option<String>,
+    /// The fingerprint of the function, as computed for the function
+    /// spaces
+    pub fingerprint: String,
+    /// The byte offset of the start of the function
+    pub start_byte: usize,
+    /// The byte offset of the end of the function
+    pub end_byte: usize,
+    /// The first line of the function
+    pub start_line: usize,
+    /// The last line of the function
+    pub end_line: usize,
+}
+
+/// Detects the boundaries of each function in a code, in the order of
+/// their start, without computing any metric.
+pub fn function_boundaries<T: ParserTrait>(parser: &T) -> Vec<FunctionBoundary> {
+    let code = parser.get_code();
+    let mut boundaries = Vec::new();
+    parser.get_root().act_on_node(&mut |n| {
+        if T::Checker::is_func(n) {
+            boundaries.push(FunctionBoundary {
+                name: T::Getter::get_func_name(n, code).map(|name| name.to_string()),
+                fingerprint: fingerprint::<T::Checker>(n, code),
+                start_byte: n.start_byte(),
+                end_byte: n.end_byte(),
+                start_line: n.start_row() + 1,
+                end_line: n.end_row() + 1,
+            });
+        }
+    });
+    boundaries
+}
+
+struct FunctionBoundaries {
+    _guard: (),
+}
+
+impl Callback for FunctionBoundaries {
+    type Res = Vec<FunctionBoundary>;
+    type Cfg = ();
+
+    fn call<T: ParserTrait>(_cfg: Self::Cfg, parser: &T) -> Self::Res {
+        function_boundaries(parser)
+    }
+}
+
+/// Returns the boundaries of each function in a code in a language.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{get_function_boundaries, LANG};
+///
+/// let code = b"fn f() {}\n\nfn g() {\n    f();\n}\n".to_vec();
+/// let boundaries = get_function_boundaries(&LANG::Rust, code, Path::new("foo.rs"));
+/// assert_eq!(boundaries[1].name.as_deref(), Some("g"));
+/// assert_eq!((boundaries[1].start_line, boundaries[1].end_line), (3, 5));
+/// ```
+pub fn get_function_boundaries(lang: &LANG, source: Vec<u8>, path: &Path) -> Vec<FunctionBoundary> {
+    action::<FunctionBoundaries>(lang, source, path, None, ())
+}
+
 fn dump_span(
     span: FunctionSpan,
     stdout: &mut StandardStreamLock,
@@ -84,13 +199,22 @@ fn dump_span(
     write!(stdout, "from line ")?;
 
     color(stdout, Color::White)?;
-    write!(stdout, "{}", span.start_line)?;
+    write!(stdout, "{}:{}", span.start_line, span.start_column)?;
 
     color(stdout, Color::Green)?;
     write!(stdout, " to line ")?;
 
     color(stdout, Color::White)?;
-    writeln!(stdout, "{}.", span.end_line)
+    writeln!(stdout, "{}:{}.", span.end_line, span.end_column)?;
+
+    if let Some(snippet) = &span.snippet {
+        let pref = if last { "      " } else { "   |  " };
+        for line in snippet.to_string().lines() {
+            writeln!(stdout, "{pref}{line}")?;
+        }
+    }
+
+    Ok(())
 }
 
 fn dump_spans(mut spans: Vec<FunctionSpan>, path: PathBuf) -> std::io::Result<()> {
@@ -116,6 +240,13 @@ fn dump_spans(mut spans: Vec<FunctionSpan>, path: PathBuf) -> std::io::Result<()
 pub struct FunctionCfg {
     /// Path to the file containing the code
     pub path: PathBuf,
+    /// The unit used to count the columns of a function
+    pub offset_encoding: OffsetEncoding,
+    /// The number of context lines surrounding the source code
+    /// printed for each function
+    ///
+    /// If `None`, no source code is printed
+    pub snippet_context: Option<usize>,
 }
 
 pub struct Function {
@@ -127,6 +258,37 @@ impl Callback for Function {
     type Cfg = FunctionCfg;
 
     fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
-        dump_spans(function(parser), cfg.path)
+        let mut spans = function_with_encoding(parser, cfg.offset_encoding);
+        if let Some(context) = cfg.snippet_context {
+            for span in spans.iter_mut() {
+                span.set_snippet(parser.get_code(), context);
+            }
+        }
+        dump_spans(spans, cfg.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn boundaries() {
+        let code = "def f():\n    def g():\n        pass\n    return g\n\nh = lambda: 1\n";
+        let boundaries =
+            get_function_boundaries(&LANG::Python, code.as_bytes().to_vec(), Path::new("foo.py"));
+
+        let names = boundaries
+            .iter()
+            .map(|boundary| boundary.name.as_deref())
+            .collect::<Vec<_>>();
+        assert_eq!(names, [Some("f"), Some("g")]);
+        assert_eq!(boundaries[0].start_byte, 0);
+        assert_eq!(
+            &code[boundaries[1].start_byte..boundaries[1].end_byte],
+            "def g():\n        pass"
+        );
+        assert_eq!((boundaries[1].start_line, boundaries[1].end_line), (2, 3));
+        assert!(!boundaries[0].fingerprint.is_empty());
     }
 }
@@ -0,0 +1,248 @@
+use std::{
+    collections::HashMap,
+    ffi::OsStr,
+    io,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+
+use crate::{
+    commit_range::spaces_at,
+    diff::diff_spaces,
+    history::git,
+    spaces::{FuncSpace, SpaceKind},
+    trends::TrendPoint,
+};
+
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// A commit changing a file.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct FileRevision {
+    /// The hash of the commit
+    pub commit: String,
+    /// The time of the commit, as a Unix timestamp
+    pub timestamp: u64,
+}
+
+/// The cyclomatic complexity of a function space across the history
+/// of its file.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FunctionSeries {
+    /// The name of the function space in the last revision
+    pub name: Option<String>,
+    /// The kind of the function space
+    pub kind: SpaceKind,
+    /// The first line of the function space in the last revision
+    pub start_line: usize,
+    /// The last line of the function space in the last revision
+    pub end_line: usize,
+    /// The cyclomatic complexity at each revision since the function
+    /// space was added, the oldest first
+    pub points: Vec<TrendPoint>,
+}
+
+impl FunctionSeries {
+    /// Returns the series as a sparkline, one bar per revision.
+    ///
+    /// The bars are scaled between the lowest and the highest
+    /// complexity of the series.
+    pub fn sparkline(&self) -> String {
+        let min = self
+            .points
+            .iter()
+            .map(|point| point.value)
+            .fold(f64::INFINITY, f64::min);
+        let max = self
+            .points
+            .iter()
+            .map(|point| point.value)
+            .fold(f64::NEG_INFINITY, f64::max);
+        self.points
+            .iter()
+            .map(|point| {
+                let level = if max > min {
+                    ((point.value - min) / (max - min) * (SPARKS.len() - 1) as f64).round()
+                } else {
+                    0.
+                };
+                SPARKS[level as usize]
+            })
+            .collect()
+    }
+}
+
+/// The history of the functions of a file.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct FunctionHistory {
+    /// The path of the file, relative to the root of the repository
+    pub path: PathBuf,
+    /// The analyzed commits changing the file, the oldest first
+    pub revisions: Vec<FileRevision>,
+    /// The series of the function spaces of the last revision, in the
+    /// order they appear
+    pub functions: Vec<FunctionSeries>,
+}
+
+// The last commits changing a file, the oldest first
+pub(crate) fn file_revisions(
+    repository: &Path,
+    path: &Path,
+    max_commits: usize,
+) -> io::Result<Vec<FileRevision>> {
+    let max_count = format!("--max-count={max_commits}");
+    let log = git(
+        repository,
+        &[
+            OsStr::new("log"),
+            OsStr::new("--reverse"),
+            OsStr::new("--format=%H%x1f%ct"),
+            OsStr::new(&max_count),
+            OsStr::new("--"),
+            path.as_os_str(),
+        ],
+    )?;
+    Ok(String::from_utf8_lossy(&log)
+        .lines()
+        .filter_map(|line| {
+            let (commit, timestamp) = line.split_once('\x1f')?;
+            Some(FileRevision {
+                commit: commit.to_string(),
+                timestamp: timestamp.parse().ok()?,
+            })
+        })
+        .collect())
+}
+
+/// Computes the cyclomatic complexity of the function spaces of a file
+/// at each of the last `max_commits` commits changing it.
+///
+/// The function spaces are followed across revisions as
+/// [`diff_spaces`] pairs them, so a renamed or moved function keeps its
+/// series. Renames of the file itself aren't followed, and a revision
+/// deleting the file ends every series. A revision where the cyclomatic
+/// complexity of a function is suppressed by a `sca-ignore` comment adds
+/// no point to its series. `git` must be in the `PATH`.
+///
+/// The `path` is relative to the root of the repository.
+pub fn function_history(
+    repository: &Path,
+    path: &Path,
+    max_commits: usize,
+) -> io::Result<FunctionHistory> {
+    let mut history = FunctionHistory {
+        path: path.to_path_buf(),
+        ..Default::default()
+    };
+    // The series of the function spaces of the previous revision, keyed
+    // by their address in `previous`
+    let mut series: HashMap<*const FuncSpace, FunctionSeries> = HashMap::new();
+    let mut previous: Vec<(PathBuf, FuncSpace)> = Vec::new();
+
+    for revision in file_revisions(repository, path, max_commits)? {
+        // A revision which fails to parse, or deletes the file, has no spaces
+        let current = spaces_at(repository, &revision.commit, path)
+            .ok()
+            .flatten()
+            .map(|space| vec![(path.to_path_buf(), space)])
+            .unwrap_or_default();
+
+        let mut next = HashMap::new();
+        for change in diff_spaces(&previous, &current) {
+            let Some(after) = change.after else {
+                continue;
+            };
+            let mut function = change
+                .before
+                .and_then(|before| series.remove(&(before.space as *const FuncSpace)))
+                .unwrap_or_else(|| FunctionSeries {
+                    name: None,
+                    kind: after.space.kind,
+                    start_line: 0,
+                    end_line: 0,
+                    points: Vec::new(),
+                });
+            function.name = after.space.name.clone();
+            function.kind = after.space.kind;
+            function.start_line = after.space.start_line;
+            function.end_line = after.space.end_line;
+            if !after.space.is_suppressed("cyclomatic") {
+                function.points.push(TrendPoint {
+                    timestamp: revision.timestamp,
+                    value: after.space.metrics.cyclomatic.cyclomatic(),
+                });
+            }
+            next.insert(after.space as *const FuncSpace, function);
+        }
+
+        series = next;
+        previous = current;
+        history.revisions.push(revision);
+    }
+
+    history.functions = series.into_values().collect();
+    history
+        .functions
+        .sort_by_key(|function| (function.start_line, function.end_line));
+    Ok(history)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::history::test_git;
+
+    fn commit(repository: &Path, code: &str) {
+        fs::write(repository.join("a.rs"), code).unwrap();
+        test_git(repository, &["add", "-A"]);
+        test_git(repository, &["commit", "-qm", "change"]);
+    }
+
+    #[test]
+    fn functions_history() {
+        let repository = std::env::temp_dir().join(format!("sca-history-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&repository);
+        fs::create_dir_all(&repository).unwrap();
+        test_git(&repository, &["init", "-q"]);
+
+        commit(&repository, "fn f(x: i32) {\n    if x > 0 {}\n}\n");
+        commit(
+            &repository,
+            "fn f(x: i32) {\n    if x > 0 {}\n    if x > 1 {}\n}\n\nfn g() {}\n",
+        );
+        commit(
+            &repository,
+            "fn renamed(x: i32) {\n    if x > 0 {}\n    if x > 1 {}\n}\n\nfn g() {}\n",
+        );
+        fs::write(repository.join("b.rs"), "fn h() {}\n").unwrap();
+        test_git(&repository, &["add", "-A"]);
+        test_git(&repository, &["commit", "-qm", "unrelated"]);
+
+        let history = function_history(&repository, Path::new("a.rs"), 10).unwrap();
+        let last_two = function_history(&repository, Path::new("a.rs"), 2).unwrap();
+        fs::remove_dir_all(&repository).unwrap();
+
+        assert_eq!(history.revisions.len(), 3);
+        let series = history
+            .functions
+            .iter()
+            .map(|function| {
+                let values = function
+                    .points
+                    .iter()
+                    .map(|point| point.value)
+                    .collect::<Vec<_>>();
+                (function.name.as_deref().unwrap(), values)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(series, [("renamed", vec![2., 3., 3.]), ("g", vec![1., 1.])]);
+        assert_eq!(history.functions[0].sparkline(), "▁██");
+        assert_eq!(history.functions[1].sparkline(), "▁▁");
+
+        assert_eq!(last_two.revisions.len(), 2);
+        assert_eq!(last_two.functions[0].points.len(), 2);
+    }
+}
@@ -0,0 +1,364 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    diff::diff_spaces,
+    mi_grade::MiBands,
+    risk::{breaches, RiskPolicy},
+    spaces::FuncSpace,
+};
+
+/// The policy checked by a CI gate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GatePolicy {
+    /// The thresholds whose breaches are violations
+    pub thresholds: RiskPolicy,
+    /// The maximum number of violations introduced by the current run
+    pub max_new_violations: usize,
+    /// The maximum increase of the cyclomatic complexity of a function
+    pub max_cyclomatic_regression: f64,
+    /// The minimum maintainability index of a file, as computed by
+    /// Visual Studio
+    ///
+    /// If `None`, the maintainability index of files is not checked
+    pub min_mi: Option<f64>,
+    /// The bands grading the maintainability index of files
+    pub mi_bands: MiBands,
+    /// The worst grade of the maintainability index of a file, among
+    /// the labels of `mi_bands`
+    ///
+    /// If `None`, the grades of files are not checked
+    pub min_mi_grade: Option<String>,
+}
+
+impl Default for GatePolicy {
+    fn default() -> Self {
+        Self {
+            thresholds: RiskPolicy::default(),
+            max_new_violations: 0,
+            max_cyclomatic_regression: 5.,
+            min_mi: None,
+            mi_bands: MiBands::default(),
+            min_mi_grade: None,
+        }
+    }
+}
+
+/// The rule of a [`GatePolicy`] failed by a run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GateRule {
+    /// Too many new violations
+    MaxNewViolations,
+    /// The cyclomatic complexity of a function increased too much
+    MaxCyclomaticRegression,
+    /// The maintainability index of a file is too low
+    MinMi,
+    /// The grade of the maintainability index of a file is too low
+    MinMiGrade,
+}
+
+/// A violation introduced by the current run: a function breaching a
+/// threshold it didn't breach in the baseline.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Violation {
+    /// The path of the file containing the function
+    pub path: PathBuf,
+    /// The name of the function
+    pub name: Option<String>,
+    /// The first line of the function in the current run
+    pub start_line: usize,
+    /// The breached metric
+    pub metric: &'static str,
+    /// The value of the metric
+    pub value: f64,
+    /// The breached threshold
+    pub threshold: f64,
+}
+
+/// A failed rule of a [`GatePolicy`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GateFailure {
+    /// The failed rule
+    pub rule: GateRule,
+    /// The path of the file failing the rule, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    /// The name of the function failing the rule, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The value failing the rule
+    pub value: f64,
+    /// The limit of the rule
+    pub limit: f64,
+    /// A human-readable explanation of the failure
+    pub message: String,
+}
+
+/// The outcome of a CI gate.
+#[derive(Debug, Clone, Serialize)]
+pub struct GateResult {
+    /// `true` if no rule failed
+    pub passed: bool,
+    /// The failed rules
+    pub failures: Vec<GateFailure>,
+    /// The violations introduced by the current run
+    pub new_violations: Vec<Violation>,
+}
+
+fn name_of(space: &FuncSpace) -> &str {
+    space.name.as_deref().unwrap_or("<anonymous>")
+}
+
+fn mi_grade_failures(
+    current: &[(PathBuf, FuncSpace)],
+    bands: &MiBands,
+    min_grade: &str,
+) -> Vec<GateFailure> {
+    let Some(limit) = bands.band_of(min_grade) else {
+        return vec![GateFailure {
+            rule: GateRule::MinMiGrade,
+            path: None,
+            name: None,
+            value: f64::NAN,
+            limit: f64::NAN,
+            message: format!("unknown maintainability index grade {min_grade}"),
+        }];
+    };
+
+    current
+        .iter()
+        .filter_map(|(path, space)| {
+            let mi = space.metrics.mi.mi_visual_studio();
+            let band = bands.band(mi)?;
+            (band > limit).then(|| GateFailure {
+                rule: GateRule::MinMiGrade,
+                path: Some(path.clone()),
+                name: None,
+                value: mi,
+                limit: bands.bands()[limit].min,
+                message: format!(
+                    "the maintainability index of {} is graded {}, worse than {min_grade}",
+                    path.display(),
+                    bands.bands()[band].label
+                ),
+            })
+        })
+        .collect()
+}
+
+/// Checks a run of the analysis against a baseline run.
+///
+/// Functions are paired between the runs as done by [`diff_spaces`],
+/// so renamed and moved functions are compared with their previous
+/// version. A function breaching a threshold of the policy is a new
+/// violation when it is new or when it didn't breach the same threshold
+/// in the baseline.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+///
+/// use singularity_code_analysis::{
+///     evaluate_gate, metrics, GatePolicy, ParserEngineRust, ParserTrait,
+/// };
+///
+/// let path = PathBuf::from("foo.rs");
+/// let parser = ParserEngineRust::new(b"fn f() {}".to_vec(), &path, None);
+/// let run = vec![(path.clone(), metrics(&parser, &path).unwrap())];
+///
+/// let result = evaluate_gate(&run, &run, &GatePolicy::default());
+/// assert!(result.passed);
+/// ```
+pub fn evaluate_gate(
+    baseline: &[(PathBuf, FuncSpace)],
+    current: &[(PathBuf, FuncSpace)],
+    policy: &GatePolicy,
+) -> GateResult {
+    let mut failures = Vec::new();
+    let mut new_violations = Vec::new();
+
+    for change in diff_spaces(baseline, current) {
+        let Some(after) = change.after else {
+            continue;
+        };
+        if !after.space.kind.is_callable() {
+            continue;
+        }
+        let before = change.before.map(|before| before.space);
+
+        let old_breaches = before
+            .map(|before| breaches(before, &policy.thresholds))
+            .unwrap_or_default();
+        new_violations.extend(
+            breaches(after.space, &policy.thresholds)
+                .into_iter()
+                .filter(|breach| old_breaches.iter().all(|old| old.metric != breach.metric))
+                .map(|breach| Violation {
+                    path: after.path.to_path_buf(),
+                    name: after.space.name.clone(),
+                    start_line: after.space.start_line,
+                    metric: breach.metric,
+                    value: breach.value,
+                    threshold: breach.threshold,
+                }),
+        );
+
+        if let Some(before) = before {
+            let regression = after.space.metrics.cyclomatic.cyclomatic_sum()
+                - before.metrics.cyclomatic.cyclomatic_sum();
+            if regression > policy.max_cyclomatic_regression
+                && !after.space.is_suppressed("cyclomatic")
+            {
+                failures.push(GateFailure {
+                    rule: GateRule::MaxCyclomaticRegression,
+                    path: Some(after.path.to_path_buf()),
+                    name: after.space.name.clone(),
+                    value: regression,
+                    limit: policy.max_cyclomatic_regression,
+                    message: format!(
+                        "the cyclomatic complexity of {} increased by {regression}, more than {}",
+                        name_of(after.space),
+                        policy.max_cyclomatic_regression
+                    ),
+                });
+            }
+        }
+    }
+
+    if new_violations.len() > policy.max_new_violations {
+        failures.push(GateFailure {
+            rule: GateRule::MaxNewViolations,
+            path: None,
+            name: None,
+            value: new_violations.len() as f64,
+            limit: policy.max_new_violations as f64,
+            message: format!(
+                "{} new violations, more than {}",
+                new_violations.len(),
+                policy.max_new_violations
+            ),
+        });
+    }
+
+    if let Some(min_mi) = policy.min_mi {
+        for (path, space) in current {
+            let mi = space.metrics.mi.mi_visual_studio();
+            if mi.is_finite() && mi < min_mi {
+                failures.push(GateFailure {
+                    rule: GateRule::MinMi,
+                    path: Some(path.clone()),
+                    name: None,
+                    value: mi,
+                    limit: min_mi,
+                    message: format!(
+                        "the maintainability index of {} is {mi:.2}, less than {min_mi}",
+                        path.display()
+                    ),
+                });
+            }
+        }
+    }
+
+    if let Some(min_grade) = &policy.min_mi_grade {
+        failures.extend(mi_grade_failures(current, &policy.mi_bands, min_grade));
+    }
+
+    GateResult {
+        passed: failures.is_empty(),
+        failures,
+        new_violations,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{metrics, MiBand, ParserEngineRust, ParserTrait};
+
+    use super::*;
+
+    fn run(code: &str) -> Vec<(PathBuf, FuncSpace)> {
+        let path = PathBuf::from("a.rs");
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), &path, None);
+        let space = metrics(&parser, &path).unwrap();
+        vec![(path, space)]
+    }
+
+    #[test]
+    fn rust_evaluate_gate() {
+        let baseline = run("fn f(x: i32) {\n    if x > 0 {}\n}\nfn g(x: i32) {}\n");
+        let current = run(
+            "fn f(x: i32) {\n    if x > 0 {}\n    if x > 1 {}\n    if x > 2 {}\n}\n\
+             fn h(x: i32) {\n    if x > 0 {}\n    if x > 1 {}\n}\n",
+        );
+        let policy = GatePolicy {
+            thresholds: RiskPolicy {
+                max_cyclomatic: 2.,
+                ..Default::default()
+            },
+            max_new_violations: 1,
+            max_cyclomatic_regression: 1.,
+            ..Default::default()
+        };
+
+        let result = evaluate_gate(&baseline, &current, &policy);
+        assert!(!result.passed);
+
+        let violations = result
+            .new_violations
+            .iter()
+            .map(|violation| (violation.name.as_deref().unwrap(), violation.metric))
+            .collect::<Vec<_>>();
+        // `h` is new, so all its breaches are violations
+        assert_eq!(violations, [("f", "cyclomatic"), ("h", "cyclomatic")]);
+
+        let failures = result
+            .failures
+            .iter()
+            .map(|failure| (failure.rule, failure.name.as_deref(), failure.value))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            failures,
+            [
+                (GateRule::MaxCyclomaticRegression, Some("f"), 2.),
+                (GateRule::MaxNewViolations, None, 2.),
+            ]
+        );
+
+        let policy = GatePolicy {
+            min_mi: Some(100.),
+            ..Default::default()
+        };
+        let result = evaluate_gate(&baseline, &baseline, &policy);
+        assert_eq!(result.failures.len(), 1);
+        assert_eq!(result.failures[0].rule, GateRule::MinMi);
+
+        let policy = GatePolicy {
+            mi_bands: MiBands::traffic_light(),
+            min_mi_grade: Some("green".to_string()),
+            ..Default::default()
+        };
+        assert!(evaluate_gate(&baseline, &baseline, &policy).passed);
+
+        let policy = GatePolicy {
+            mi_bands: MiBands::new(vec![
+                MiBand {
+                    label: "perfect".to_string(),
+                    min: 100.,
+                },
+                MiBand {
+                    label: "other".to_string(),
+                    min: 0.,
+                },
+            ]),
+            min_mi_grade: Some("perfect".to_string()),
+            ..Default::default()
+        };
+        let result = evaluate_gate(&baseline, &baseline, &policy);
+        assert_eq!(result.failures.len(), 1);
+        assert_eq!(result.failures[0].rule, GateRule::MinMiGrade);
+    }
+}
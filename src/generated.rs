@@ -0,0 +1,215 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// Why a file is not hand-written code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GeneratedKind {
+    /// The file is part of a vendored dependency
+    Vendored,
+    /// The file declares to be generated by a tool
+    Generated,
+    /// The file is minified or bundled
+    Minified,
+}
+
+/// What to do with the generated, minified and vendored files.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GeneratedAction {
+    /// The files are not analyzed
+    #[default]
+    Skip,
+    /// The files are analyzed, but their metrics count for
+    /// [`GeneratedPolicy::weight`] in the aggregates
+    DownWeight,
+    /// The files are analyzed like the other ones
+    Analyze,
+}
+
+// Markers declaring a generated file, looked for in its header
+const GENERATED_MARKERS: &[&str] = &[
+    "@generated",
+    "do not edit",
+    "auto-generated",
+    "autogenerated",
+    "code generated by",
+];
+
+// Markers left by bundlers and minifiers
+const SOURCEMAP_MARKERS: &[&str] = &["//# sourceMappingURL=", "//@ sourceMappingURL="];
+
+/// The heuristics used to detect generated, minified and vendored files,
+/// so that bundles don't dominate the metrics of a project.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GeneratedPolicy {
+    /// What to do with the detected files
+    pub action: GeneratedAction,
+    /// The weight of a detected file when down-weighted
+    pub weight: f64,
+    /// The directories containing vendored code
+    pub vendored_dirs: Vec<String>,
+    /// The number of lines of the header searched for generation markers
+    pub header_lines: usize,
+    /// The average length of the non-blank lines above which a file is
+    /// considered minified
+    pub max_average_line_length: usize,
+}
+
+impl Default for GeneratedPolicy {
+    fn default() -> Self {
+        Self {
+            action: GeneratedAction::Skip,
+            weight: 0.1,
+            vendored_dirs: ["vendor", "node_modules", "third_party", "bower_components"]
+                .iter()
+                .map(|dir| dir.to_string())
+                .collect(),
+            header_lines: 10,
+            max_average_line_length: 200,
+        }
+    }
+}
+
+impl GeneratedPolicy {
+    /// Classifies a file from its path and content.
+    ///
+    /// Returns `None` for hand-written code.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// use singularity_code_analysis::{GeneratedKind, GeneratedPolicy};
+    ///
+    /// let policy = GeneratedPolicy::default();
+    /// let code = b"// Code generated by protoc-gen-go. DO NOT EDIT.\npackage pb\n";
+    ///
+    /// assert_eq!(
+    ///     policy.classify(Path::new("api.pb.go"), code),
+    ///     Some(GeneratedKind::Generated)
+    /// );
+    /// assert_eq!(policy.classify(Path::new("main.go"), b"package main\n"), None);
+    /// ```
+    pub fn classify(&self, path: &Path, code: &[u8]) -> Option<GeneratedKind> {
+        if path.components().any(|component| {
+            let component = component.as_os_str();
+            self.vendored_dirs
+                .iter()
+                .any(|dir| component == dir.as_str())
+        }) {
+            return Some(GeneratedKind::Vendored);
+        }
+
+        let text = String::from_utf8_lossy(code);
+        let header = text
+            .lines()
+            .take(self.header_lines)
+            .collect::<Vec<_>>()
+            .join("\n")
+            .to_lowercase();
+        if GENERATED_MARKERS
+            .iter()
+            .any(|marker| header.contains(marker))
+        {
+            return Some(GeneratedKind::Generated);
+        }
+
+        let is_min = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.contains(".min."));
+        if is_min || SOURCEMAP_MARKERS.iter().any(|marker| text.contains(marker)) {
+            return Some(GeneratedKind::Minified);
+        }
+
+        let (count, length) = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .fold((0, 0), |(count, length), line| {
+                (count + 1, length + line.chars().count())
+            });
+        (count > 0 && length / count > self.max_average_line_length)
+            .then_some(GeneratedKind::Minified)
+    }
+
+    /// Returns the weight of a file of the given kind in the aggregates,
+    /// or `None` if the file must be skipped.
+    pub fn weight_of(&self, kind: Option<GeneratedKind>) -> Option<f64> {
+        match (kind, self.action) {
+            (None, _) | (Some(_), GeneratedAction::Analyze) => Some(1.),
+            (Some(_), GeneratedAction::DownWeight) => Some(self.weight),
+            (Some(_), GeneratedAction::Skip) => None,
+        }
+    }
+}
+
+/// A file detected as generated, minified or vendored, reported apart
+/// from the analyzed files.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GeneratedFile {
+    /// The path of the file
+    pub path: PathBuf,
+    /// Why the file is not hand-written code
+    pub kind: GeneratedKind,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_generated_files() {
+        let policy = GeneratedPolicy::default();
+        let classify = |path: &str, code: &str| policy.classify(Path::new(path), code.as_bytes());
+
+        assert_eq!(
+            classify(
+                "web/node_modules/left-pad/index.js",
+                "module.exports = 1;\n"
+            ),
+            Some(GeneratedKind::Vendored)
+        );
+        assert_eq!(
+            classify(
+                "src/parser.rs",
+                "// This file is @generated by build.rs\nfn f() {}\n"
+            ),
+            Some(GeneratedKind::Generated)
+        );
+        assert_eq!(
+            classify(
+                "src/parser.rs",
+                &format!("{}// @generated\n", "\n".repeat(20))
+            ),
+            None
+        );
+        assert_eq!(
+            classify("dist/app.js", "var a=1;\n//# sourceMappingURL=app.js.map\n"),
+            Some(GeneratedKind::Minified)
+        );
+        assert_eq!(
+            classify("app.min.css", "a{}\n"),
+            Some(GeneratedKind::Minified)
+        );
+        assert_eq!(
+            classify("dist/app.js", &format!("{}\n", "a=1;".repeat(100))),
+            Some(GeneratedKind::Minified)
+        );
+        assert_eq!(classify("src/app.js", "let a = 1;\n\nlet b = 2;\n"), None);
+    }
+
+    #[test]
+    fn generated_weights() {
+        let mut policy = GeneratedPolicy::default();
+        assert_eq!(policy.weight_of(None), Some(1.));
+        assert_eq!(policy.weight_of(Some(GeneratedKind::Minified)), None);
+
+        policy.action = GeneratedAction::DownWeight;
+        assert_eq!(policy.weight_of(Some(GeneratedKind::Minified)), Some(0.1));
+    }
+}
@@ -1,5 +1,9 @@
+#![cfg_attr(not(test), deny(clippy::unwrap_used))]
+
 use crate::{
-    analysis_context::{node_text, with_current_code},
+    analysis_context::{node_text, text_at, with_current_code},
+    cfg::FlowKind,
+    languages::{language_csharp::Csharp, language_go::Go},
     metrics::halstead::HalsteadType,
     spaces::SpaceKind,
     traits::Search,
@@ -23,11 +27,48 @@ macro_rules! get_operator {
     };
 }
 
+macro_rules! get_flow_kind {
+    ($language:ident) => {
+        fn get_flow_kind(node: &Node) -> FlowKind {
+            use $language::*;
+
+            match node.kind_id().into() {
+                IfStatement => FlowKind::If,
+                ElseClause => FlowKind::Else,
+                ForStatement | ForInStatement | WhileStatement => FlowKind::Loop,
+                DoStatement => FlowKind::DoLoop,
+                SwitchStatement => FlowKind::Switch,
+                SwitchCase | SwitchDefault => FlowKind::FallthroughCase,
+                TryStatement => FlowKind::Try,
+                CatchClause => FlowKind::Catch,
+                FinallyClause => FlowKind::Finally,
+                StatementBlock | SwitchBody => FlowKind::Block,
+                ReturnStatement => FlowKind::Return,
+                ThrowStatement => FlowKind::Throw,
+                BreakStatement => FlowKind::Break,
+                ContinueStatement => FlowKind::Continue,
+                _ => FlowKind::Unknown,
+            }
+        }
+    };
+}
+
+/// Extracts the names, kinds and Halstead types of the nodes of a language.
+///
+/// Every method has a default implementation, so a new grammar only
+/// overrides the methods for which its nodes need a specific treatment.
 pub trait Getter {
+    /// Returns the name of a function.
+    ///
+    /// Defaults to [`get_func_space_name`](Self::get_func_space_name).
     fn get_func_name<'a>(node: &Node, code: &'a [u8]) -> Option<&'a str> {
         Self::get_func_space_name(node, code)
     }
 
+    /// Returns the name of a space.
+    ///
+    /// Defaults to the text of the `name` field of the node, or to
+    /// `<anonymous>` when the node has no such field.
     fn get_func_space_name<'a>(node: &Node, code: &'a [u8]) -> Option<&'a str> {
         // we're in a function or in a class
         node.child_by_field_name("name")
@@ -37,14 +78,22 @@ pub trait Getter {
             })
     }
 
+    /// Returns the kind of the space opened by a node.
     fn get_space_kind(_node: &Node) -> SpaceKind {
         SpaceKind::Unknown
     }
 
+    /// Returns the role of a node in the control flow of a function.
+    fn get_flow_kind(_node: &Node) -> FlowKind {
+        FlowKind::Unknown
+    }
+
+    /// Returns whether a node is a Halstead operator or operand.
     fn get_op_type(_node: &Node) -> HalsteadType {
         HalsteadType::Unknown
     }
 
+    /// Returns the text of an operator, given its node kind.
     fn get_operator_id_as_str(_id: u16) -> &'static str {
         ""
     }
@@ -54,12 +103,35 @@ impl Getter for PythonCode {
     fn get_space_kind(node: &Node) -> SpaceKind {
         match node.kind_id().into() {
             Python::FunctionDefinition => SpaceKind::Function,
+            Python::Lambda => SpaceKind::Closure,
             Python::ClassDefinition => SpaceKind::Class,
             Python::Module => SpaceKind::Unit,
             _ => SpaceKind::Unknown,
         }
     }
 
+    fn get_flow_kind(node: &Node) -> FlowKind {
+        use Python::*;
+
+        match node.kind_id().into() {
+            IfStatement => FlowKind::If,
+            ElifClause => FlowKind::ElseIf,
+            ElseClause => FlowKind::Else,
+            ForStatement | WhileStatement => FlowKind::Loop,
+            MatchStatement => FlowKind::Match,
+            CaseClause => FlowKind::Case,
+            TryStatement => FlowKind::Try,
+            ExceptClause | ExceptGroupClause => FlowKind::Catch,
+            FinallyClause => FlowKind::Finally,
+            Block | Block2 => FlowKind::Block,
+            ReturnStatement => FlowKind::Return,
+            RaiseStatement => FlowKind::Throw,
+            BreakStatement => FlowKind::Break,
+            ContinueStatement => FlowKind::Continue,
+            _ => FlowKind::Unknown,
+        }
+    }
+
     fn get_op_type(node: &Node) -> HalsteadType {
         use Python::{
             And, As, Assert, Async, Await, Await2, Break, Continue, Def, Del, Elif, Else, Except,
@@ -111,6 +183,10 @@ impl Getter for MozjsCode {
         JavascriptCode::get_func_space_name(node, code)
     }
 
+    fn get_flow_kind(node: &Node) -> FlowKind {
+        JavascriptCode::get_flow_kind(node)
+    }
+
     fn get_op_type(node: &Node) -> HalsteadType {
         JavascriptCode::get_op_type(node)
     }
@@ -132,8 +208,8 @@ impl Getter for JavascriptCode {
             | MethodDefinition
             | GeneratorFunction
             | FunctionDeclaration
-            | GeneratorFunctionDeclaration
-            | ArrowFunction => SpaceKind::Function,
+            | GeneratorFunctionDeclaration => SpaceKind::Function,
+            ArrowFunction => SpaceKind::Closure,
             Class | ClassDeclaration => SpaceKind::Class,
             Program => SpaceKind::Unit,
             _ => SpaceKind::Unknown,
@@ -168,6 +244,8 @@ impl Getter for JavascriptCode {
         }
     }
 
+    get_flow_kind!(Javascript);
+
     fn get_op_type(node: &Node) -> HalsteadType {
         use Javascript::{
             As, Async, Await, Break, Case, Catch, Const, Continue, Default, Delete, Else, Export,
@@ -205,9 +283,9 @@ impl Getter for JavascriptCode {
 impl Getter for TypescriptCode {
     fn get_space_kind(node: &Node) -> SpaceKind {
         use Typescript::{
-            ArrowFunction, Class, ClassDeclaration, FunctionDeclaration, FunctionExpression,
-            GeneratorFunction, GeneratorFunctionDeclaration, InterfaceDeclaration,
-            MethodDefinition, Program,
+            AbstractClassDeclaration, ArrowFunction, Class, ClassDeclaration, FunctionDeclaration,
+            FunctionExpression, GeneratorFunction, GeneratorFunctionDeclaration,
+            InterfaceDeclaration, InternalModule, MethodDefinition, Module, Program,
         };
 
         match node.kind_id().into() {
@@ -215,10 +293,11 @@ impl Getter for TypescriptCode {
             | MethodDefinition
             | GeneratorFunction
             | FunctionDeclaration
-            | GeneratorFunctionDeclaration
-            | ArrowFunction => SpaceKind::Function,
-            Class | ClassDeclaration => SpaceKind::Class,
+            | GeneratorFunctionDeclaration => SpaceKind::Function,
+            ArrowFunction => SpaceKind::Closure,
+            Class | ClassDeclaration | AbstractClassDeclaration => SpaceKind::Class,
             InterfaceDeclaration => SpaceKind::Interface,
+            Module | InternalModule => SpaceKind::Namespace,
             Program => SpaceKind::Unit,
             _ => SpaceKind::Unknown,
         }
@@ -252,6 +331,8 @@ impl Getter for TypescriptCode {
         }
     }
 
+    get_flow_kind!(Typescript);
+
     fn get_op_type(node: &Node) -> HalsteadType {
         use Typescript::{
             As, Async, Await, Break, Case, Catch, Const, Continue, Default, Delete, Else, Export,
@@ -288,9 +369,9 @@ impl Getter for TypescriptCode {
 impl Getter for TsxCode {
     fn get_space_kind(node: &Node) -> SpaceKind {
         use Tsx::{
-            ArrowFunction, Class, ClassDeclaration, FunctionDeclaration, FunctionExpression,
-            GeneratorFunction, GeneratorFunctionDeclaration, InterfaceDeclaration,
-            MethodDefinition, Program,
+            AbstractClassDeclaration, ArrowFunction, Class, ClassDeclaration, FunctionDeclaration,
+            FunctionExpression, GeneratorFunction, GeneratorFunctionDeclaration,
+            InterfaceDeclaration, InternalModule, MethodDefinition, Module, Program,
         };
 
         match node.kind_id().into() {
@@ -298,10 +379,11 @@ impl Getter for TsxCode {
             | MethodDefinition
             | GeneratorFunction
             | FunctionDeclaration
-            | GeneratorFunctionDeclaration
-            | ArrowFunction => SpaceKind::Function,
-            Class | ClassDeclaration => SpaceKind::Class,
+            | GeneratorFunctionDeclaration => SpaceKind::Function,
+            ArrowFunction => SpaceKind::Closure,
+            Class | ClassDeclaration | AbstractClassDeclaration => SpaceKind::Class,
             InterfaceDeclaration => SpaceKind::Interface,
+            Module | InternalModule => SpaceKind::Namespace,
             Program => SpaceKind::Unit,
             _ => SpaceKind::Unknown,
         }
@@ -335,6 +417,8 @@ impl Getter for TsxCode {
         }
     }
 
+    get_flow_kind!(Tsx);
+
     fn get_op_type(node: &Node) -> HalsteadType {
         use Tsx::{
             As, Async, Await, Break, Case, Catch, Const, Continue, Default, Delete, Else, Export,
@@ -381,17 +465,43 @@ impl Getter for RustCode {
     }
 
     fn get_space_kind(node: &Node) -> SpaceKind {
-        use Rust::{ClosureExpression, FunctionItem, ImplItem, SourceFile, TraitItem};
+        use Rust::{
+            ClosureExpression, EnumItem, FunctionItem, ImplItem, ModItem, SourceFile, StructItem,
+            TraitItem, UnionItem,
+        };
 
         match node.kind_id().into() {
-            FunctionItem | ClosureExpression => SpaceKind::Function,
+            FunctionItem => SpaceKind::Function,
+            ClosureExpression => SpaceKind::Closure,
+            StructItem | EnumItem | UnionItem => SpaceKind::Struct,
             TraitItem => SpaceKind::Trait,
             ImplItem => SpaceKind::Impl,
+            ModItem => SpaceKind::Module,
             SourceFile => SpaceKind::Unit,
             _ => SpaceKind::Unknown,
         }
     }
 
+    fn get_flow_kind(node: &Node) -> FlowKind {
+        use Rust::*;
+
+        match node.kind_id().into() {
+            IfExpression => FlowKind::If,
+            ElseClause => FlowKind::Else,
+            ForExpression | WhileExpression => FlowKind::Loop,
+            LoopExpression => FlowKind::InfiniteLoop,
+            MatchExpression => FlowKind::Match,
+            MatchArm | MatchArm2 => FlowKind::MatchArm,
+            Block | MatchBlock | ConstBlock | UnsafeBlock | AsyncBlock | GenBlock | TryBlock => {
+                FlowKind::Block
+            }
+            ReturnExpression => FlowKind::Return,
+            BreakExpression => FlowKind::Break,
+            ContinueExpression => FlowKind::Continue,
+            _ => FlowKind::Unknown,
+        }
+    }
+
     fn get_op_type(node: &Node) -> HalsteadType {
         use Rust::{
             Async, Await, BinaryExpression, BooleanLiteral, CharLiteral, Continue, FloatLiteral,
@@ -488,11 +598,12 @@ impl Getter for CppCode {
     fn get_space_kind(node: &Node) -> SpaceKind {
         use Cpp::{
             ClassSpecifier, FunctionDefinition, FunctionDefinition2, FunctionDefinition3,
-            NamespaceDefinition, StructSpecifier, TranslationUnit,
+            LambdaExpression, NamespaceDefinition, StructSpecifier, TranslationUnit,
         };
 
         match node.kind_id().into() {
             FunctionDefinition | FunctionDefinition2 | FunctionDefinition3 => SpaceKind::Function,
+            LambdaExpression => SpaceKind::Closure,
             StructSpecifier => SpaceKind::Struct,
             ClassSpecifier => SpaceKind::Class,
             NamespaceDefinition => SpaceKind::Namespace,
@@ -501,6 +612,27 @@ impl Getter for CppCode {
         }
     }
 
+    fn get_flow_kind(node: &Node) -> FlowKind {
+        use Cpp::*;
+
+        match node.kind_id().into() {
+            IfStatement => FlowKind::If,
+            ElseClause => FlowKind::Else,
+            ForStatement | ForRangeLoop | WhileStatement => FlowKind::Loop,
+            DoStatement => FlowKind::DoLoop,
+            SwitchStatement => FlowKind::Switch,
+            CaseStatement => FlowKind::FallthroughCase,
+            TryStatement | TryStatement2 => FlowKind::Try,
+            CatchClause => FlowKind::Catch,
+            CompoundStatement => FlowKind::Block,
+            ReturnStatement => FlowKind::Return,
+            ThrowStatement => FlowKind::Throw,
+            BreakStatement => FlowKind::Break,
+            ContinueStatement => FlowKind::Continue,
+            _ => FlowKind::Unknown,
+        }
+    }
+
     fn get_op_type(node: &Node) -> HalsteadType {
         use Cpp::{
             Break, Case, Catch, Continue, Default, Delete, Do, Else, False, FieldIdentifier, For,
@@ -576,19 +708,43 @@ impl Getter for CcommentCode {
 impl Getter for JavaCode {
     fn get_space_kind(node: &Node) -> SpaceKind {
         use Java::{
-            ClassDeclaration, ConstructorDeclaration, InterfaceDeclaration, LambdaExpression,
-            MethodDeclaration, Program,
+            ClassDeclaration, ConstructorDeclaration, EnumDeclaration, InterfaceDeclaration,
+            LambdaExpression, MethodDeclaration, Program, RecordDeclaration,
         };
 
         match node.kind_id().into() {
-            ClassDeclaration => SpaceKind::Class,
-            MethodDeclaration | ConstructorDeclaration | LambdaExpression => SpaceKind::Function,
+            ClassDeclaration | EnumDeclaration | RecordDeclaration => SpaceKind::Class,
+            MethodDeclaration | ConstructorDeclaration => SpaceKind::Function,
+            LambdaExpression => SpaceKind::Closure,
             InterfaceDeclaration => SpaceKind::Interface,
             Program => SpaceKind::Unit,
             _ => SpaceKind::Unknown,
         }
     }
 
+    fn get_flow_kind(node: &Node) -> FlowKind {
+        use Java::*;
+
+        match node.kind_id().into() {
+            IfStatement => FlowKind::If,
+            ForStatement | EnhancedForStatement | WhileStatement => FlowKind::Loop,
+            DoStatement => FlowKind::DoLoop,
+            SwitchExpression => FlowKind::Switch,
+            SwitchBlockStatementGroup => FlowKind::FallthroughCase,
+            SwitchRule => FlowKind::Case,
+            SwitchLabel => FlowKind::Label,
+            TryStatement | TryWithResourcesStatement => FlowKind::Try,
+            CatchClause => FlowKind::Catch,
+            FinallyClause => FlowKind::Finally,
+            Block | SwitchBlock | ConstructorBody => FlowKind::Block,
+            ReturnStatement => FlowKind::Return,
+            ThrowStatement => FlowKind::Throw,
+            BreakStatement => FlowKind::Break,
+            ContinueStatement => FlowKind::Continue,
+            _ => FlowKind::Unknown,
+        }
+    }
+
     fn get_op_type(node: &Node) -> HalsteadType {
         use Java::{
             Abstract, Assert, BinaryIntegerLiteral, Break, Case, Catch, CharacterLiteral,
@@ -651,7 +807,7 @@ impl Getter for ElixirCode {
     fn get_space_kind(node: &Node) -> SpaceKind {
         match node.kind_id().into() {
             Elixir::Source => SpaceKind::Unit,
-            Elixir::AnonymousFunction => SpaceKind::Function,
+            Elixir::AnonymousFunction => SpaceKind::Closure,
             Elixir::DoBlock => {
                 if let Some(parent) = node.parent() {
                     if parent.kind_id() == Elixir::Call {
@@ -660,7 +816,7 @@ impl Getter for ElixirCode {
                                 // Determine whether this do-block belongs to a module or a function
                                 return with_keyword(&head, |kw| {
                                     if matches!(kw, "defmodule" | "defprotocol" | "defimpl") {
-                                        SpaceKind::Unit
+                                        SpaceKind::Module
                                     } else if matches!(
                                         kw,
                                         "def" | "defp" | "defmacro" | "defmacrop"
@@ -717,33 +873,6 @@ impl Getter for ElixirCode {
         }
     }
 
-    fn get_op_type(node: &Node) -> HalsteadType {
-        use Elixir::{
-            Alias, AnonymousFunction, Arguments, Atom, BinaryOperator, Call, Charlist, Dot,
-            Identifier, Integer, Keywords, List, Map, OperatorIdentifier, QuotedAtom, Sigil,
-            String, Struct, Tuple, UnaryOperator,
-        };
-
-        match node.kind_id().into() {
-            BinaryOperator | UnaryOperator | OperatorIdentifier | Dot => HalsteadType::Operator,
-            Call | Arguments => HalsteadType::Operator,
-            Identifier | Alias | Atom | QuotedAtom | Integer | String | Charlist | Sigil | List
-            | Tuple | Map | Struct | Keywords => HalsteadType::Operand,
-            AnonymousFunction => HalsteadType::Operand,
-            _ => match node.kind() {
-                "+" | "-" | "*" | "/" | "%" | "++" | "--" | "::" | "->" | "<-" | "<>" | "||"
-                | "&&" | "===" | "==" | "!==" | "!=" | "<" | "<=" | ">" | ">=" | "in" | "when"
-                | "and" | "or" | "not" | "xor" | "<<<" | ">>>" | "^^^" | "~~~" | "&&&" | "|||"
-                | "." => HalsteadType::Operator,
-                "if" | "unless" | "case" | "fn" | "do" | "after" | "rescue" | "catch" | "else" => {
-                    HalsteadType::Operator
-                }
-                "nil" | "true" | "false" => HalsteadType::Operand,
-                _ => HalsteadType::Unknown,
-            },
-        }
-    }
-
     fn get_operator_id_as_str(id: u16) -> &'static str {
         let language: tree_sitter::Language = tree_sitter_elixir::LANGUAGE.into();
         match language.node_kind_for_id(id) {
@@ -810,11 +939,8 @@ fn with_keyword<F>(identifier: &Node, f: F) -> Option<SpaceKind>
 where
     F: FnOnce(&str) -> SpaceKind,
 {
-    with_current_code(|code| {
-        let text = &code[identifier.start_byte()..identifier.end_byte()];
-        std::str::from_utf8(text).ok().map(f)
-    })
-    .flatten()
+    with_current_code(|code| text_at(code, identifier.start_byte(), identifier.end_byte()).map(f))
+        .flatten()
 }
 
 fn default_space_name<'a>(node: &Node, code: &'a [u8]) -> Option<&'a str> {
@@ -829,7 +955,7 @@ impl Getter for ErlangCode {
         match node.kind_id().into() {
             SourceFile => SpaceKind::Unit,
             FunDecl | FunctionClause => SpaceKind::Function,
-            AnonymousFun => SpaceKind::Function,
+            AnonymousFun => SpaceKind::Closure,
             _ => SpaceKind::Unknown,
         }
     }
@@ -848,25 +974,6 @@ impl Getter for ErlangCode {
         }
     }
 
-    fn get_op_type(node: &Node) -> HalsteadType {
-        use crate::Erlang::{
-            Atom, BinaryOpExpr, CatchExpr, List, MapExpr, MatchExpr, Tuple, UnaryOpExpr, Var,
-        };
-
-        match node.kind_id().into() {
-            BinaryOpExpr | UnaryOpExpr | MatchExpr | CatchExpr => HalsteadType::Operator,
-            Atom | Var | List | Tuple | MapExpr => HalsteadType::Operand,
-            _ => match node.kind() {
-                "+" | "-" | "*" | "/" | "%" | "div" | "rem" | "band" | "bor" | "bxor" | "bsl"
-                | "bsr" | "and" | "or" | "not" | "xor" | "orelse" | "andalso" | "==" | "/="
-                | "=:= " | "=/=" | "<" | "<=" | ">" | ">=" | "++" | "--" | "!" | "catch" | "of"
-                | "after" => HalsteadType::Operator,
-                "(" | ")" | "[" | "]" | "{" | "}" => HalsteadType::Unknown,
-                _ => HalsteadType::Unknown,
-            },
-        }
-    }
-
     fn get_operator_id_as_str(id: u16) -> &'static str {
         let language: tree_sitter::Language = tree_sitter_erlang::LANGUAGE.into();
         match language.node_kind_for_id(id) {
@@ -886,7 +993,7 @@ impl Getter for GleamCode {
         match node.kind_id().into() {
             SourceFile => SpaceKind::Unit,
             Function => SpaceKind::Function,
-            AnonymousFunction => SpaceKind::Function,
+            AnonymousFunction => SpaceKind::Closure,
             _ => SpaceKind::Unknown,
         }
     }
@@ -903,25 +1010,61 @@ impl Getter for GleamCode {
         }
     }
 
-    fn get_op_type(node: &Node) -> HalsteadType {
-        use crate::Gleam::*;
+    fn get_operator_id_as_str(id: u16) -> &'static str {
+        let language: tree_sitter::Language = tree_sitter_gleam::LANGUAGE.into();
+        match language.node_kind_for_id(id) {
+            Some("(") => "()",
+            Some("[") => "[]",
+            Some("{") => "{}",
+            Some(kind) => kind,
+            None => "unknown",
+        }
+    }
+}
+
+impl Getter for LuaCode {
+    fn get_space_kind(node: &Node) -> SpaceKind {
+        use crate::Lua::*;
 
         match node.kind_id().into() {
-            BinaryExpression | BooleanNegation | IntegerNegation | PipelineEcho => {
-                HalsteadType::Operator
-            }
-            Case | Let => HalsteadType::Operator,
-            Identifier | Integer | Float | String | Comment => HalsteadType::Operand,
-            _ => match node.kind() {
-                "+" | "-" | "*" | "/" | "%" | "++" | "--" | "<" | "<=" | ">" | ">=" | "=="
-                | "!=" | "&&" | "||" | "<-" | "->" | "if" | "else" => HalsteadType::Operator,
-                _ => HalsteadType::Unknown,
-            },
+            Chunk => SpaceKind::Unit,
+            FunctionDeclaration | FunctionDeclaration2 => SpaceKind::Function,
+            FunctionDefinition => SpaceKind::Closure,
+            _ => SpaceKind::Unknown,
+        }
+    }
+
+    fn get_flow_kind(node: &Node) -> FlowKind {
+        use crate::Lua::*;
+
+        match node.kind_id().into() {
+            IfStatement => FlowKind::If,
+            ElseifStatement => FlowKind::ElseIf,
+            ElseStatement => FlowKind::Else,
+            ForStatement | WhileStatement => FlowKind::Loop,
+            RepeatStatement => FlowKind::DoLoop,
+            Block | DoStatement => FlowKind::Block,
+            ReturnStatement => FlowKind::Return,
+            BreakStatement => FlowKind::Break,
+            _ => FlowKind::Unknown,
+        }
+    }
+
+    fn get_func_space_name<'a>(node: &Node, code: &'a [u8]) -> Option<&'a str> {
+        use crate::Lua::*;
+
+        match node.kind_id().into() {
+            // `t.f` and `t:m` are kept whole
+            FunctionDeclaration | FunctionDeclaration2 => node
+                .child_by_field_name("name")
+                .and_then(|name| node_text(&name, code)),
+            FunctionDefinition => Some("anonymous_function"),
+            _ => default_space_name(node, code),
         }
     }
 
     fn get_operator_id_as_str(id: u16) -> &'static str {
-        let language: tree_sitter::Language = tree_sitter_gleam::LANGUAGE.into();
+        let language: tree_sitter::Language = tree_sitter_lua::LANGUAGE.into();
         match language.node_kind_for_id(id) {
             Some("(") => "()",
             Some("[") => "[]",
@@ -932,9 +1075,71 @@ impl Getter for GleamCode {
     }
 }
 
-// Lua (minimal implementation)
-impl Getter for LuaCode {}
+impl Getter for GoCode {
+    fn get_space_kind(node: &Node) -> SpaceKind {
+        match node.kind() {
+            "function_declaration" | "method_declaration" => SpaceKind::Function,
+            "func_literal" => SpaceKind::Closure,
+            "source_file" => SpaceKind::Unit,
+            _ => SpaceKind::Unknown,
+        }
+    }
 
-// Compatibility implementations for unimplemented languages
-impl Getter for GoCode {}
-impl Getter for CsharpCode {}
+    fn get_flow_kind(node: &Node) -> FlowKind {
+        match node.kind_id().into() {
+            Go::IfStatement => FlowKind::If,
+            Go::ForStatement => FlowKind::Loop,
+            Go::ExpressionSwitchStatement | Go::TypeSwitchStatement | Go::SelectStatement => {
+                FlowKind::Switch
+            }
+            Go::ExpressionCase | Go::DefaultCase | Go::TypeCase | Go::CommunicationCase => {
+                FlowKind::Case
+            }
+            Go::Block => FlowKind::Block,
+            Go::ReturnStatement => FlowKind::Return,
+            Go::BreakStatement => FlowKind::Break,
+            Go::ContinueStatement => FlowKind::Continue,
+            _ => FlowKind::Unknown,
+        }
+    }
+}
+
+impl Getter for CsharpCode {
+    fn get_space_kind(node: &Node) -> SpaceKind {
+        match node.kind() {
+            "method_declaration"
+            | "constructor_declaration"
+            | "destructor_declaration"
+            | "operator_declaration"
+            | "local_function_statement" => SpaceKind::Function,
+            "lambda_expression" | "anonymous_method_expression" => SpaceKind::Closure,
+            "class_declaration" | "record_declaration" => SpaceKind::Class,
+            "struct_declaration" | "record_struct_declaration" => SpaceKind::Struct,
+            "interface_declaration" => SpaceKind::Interface,
+            "namespace_declaration" | "file_scoped_namespace_declaration" => SpaceKind::Namespace,
+            "compilation_unit" => SpaceKind::Unit,
+            _ => SpaceKind::Unknown,
+        }
+    }
+
+    fn get_flow_kind(node: &Node) -> FlowKind {
+        match node.kind_id().into() {
+            Csharp::IfStatement => FlowKind::If,
+            Csharp::ForStatement | Csharp::ForeachStatement | Csharp::WhileStatement => {
+                FlowKind::Loop
+            }
+            Csharp::DoStatement => FlowKind::DoLoop,
+            Csharp::SwitchStatement | Csharp::SwitchExpression => FlowKind::Switch,
+            Csharp::SwitchSection | Csharp::SwitchExpressionArm => FlowKind::Case,
+            Csharp::TryStatement => FlowKind::Try,
+            Csharp::CatchClause => FlowKind::Catch,
+            Csharp::FinallyClause => FlowKind::Finally,
+            Csharp::Block | Csharp::SwitchBody => FlowKind::Block,
+            Csharp::ReturnStatement => FlowKind::Return,
+            Csharp::ThrowStatement | Csharp::ThrowExpression => FlowKind::Throw,
+            Csharp::BreakStatement => FlowKind::Break,
+            Csharp::ContinueStatement => FlowKind::Continue,
+            _ => FlowKind::Unknown,
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use serde::Serialize;
+
+use crate::{
+    langs::LANG,
+    languages::{
+        language_csharp::Csharp, language_go::Go, Cpp, Java, Javascript, Python, Rust, Tsx,
+        Typescript, ELIXIR_KINDS, ERLANG_KINDS, GLEAM_KINDS, LUA_KINDS,
+    },
+    metadata::GrammarInfo,
+};
+
+// The versions of the tree-sitter crates, as `name=version` pairs
+// separated by `;`, recorded by the build script from the `Cargo.lock`
+const GRAMMAR_VERSIONS: &str = match option_env!("SCA_GRAMMAR_VERSIONS") {
+    Some(versions) => versions,
+    None => "",
+};
+
+/// Returns the name of the tree-sitter crate of the grammar of a
+/// language.
+pub fn grammar_crate(language: LANG) -> &'static str {
+    match language {
+        LANG::Javascript => "tree-sitter-javascript",
+        LANG::Java => "tree-sitter-java",
+        LANG::Rust => "tree-sitter-rust",
+        LANG::Cpp => "tree-sitter-cpp",
+        LANG::Python => "tree-sitter-python",
+        LANG::Tsx | LANG::Typescript => "tree-sitter-typescript",
+        LANG::Elixir => "tree-sitter-elixir",
+        LANG::Erlang => "tree-sitter-erlang",
+        LANG::Gleam => "tree-sitter-gleam",
+        LANG::Lua => "tree-sitter-lua",
+        LANG::Go => "tree-sitter-go",
+        LANG::Csharp => "tree-sitter-c-sharp",
+    }
+}
+
+/// Returns the version of a tree-sitter crate the library is built
+/// with, or `None` if it isn't known, like when the library is built
+/// without a `Cargo.lock`.
+///
+/// # Examples
+///
+/// ```
+/// use singularity_code_analysis::grammar_version;
+///
+/// if let Some(version) = grammar_version("tree-sitter") {
+///     println!("built with tree-sitter {version}");
+/// }
+/// ```
+pub fn grammar_version(name: &str) -> Option<&'static str> {
+    GRAMMAR_VERSIONS
+        .split(';')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(crate_name, _)| *crate_name == name)
+        .map(|(_, version)| version)
+}
+
+/// A node kind whose id names another kind in the grammar of its
+/// language, so the nodes of that kind are misidentified.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct KindMismatch {
+    /// The id of the node kind
+    pub id: u16,
+    /// The name of the kind the id stands for in the crate
+    pub expected: String,
+    /// The name of the kind of the id in the grammar
+    pub actual: String,
+}
+
+// Returns the name the crate gives to a kind id, for the enums generated
+// with the names of their kinds, by hand or by the build script
+fn generated_name<K: From<u16> + Into<&'static str>>(id: u16) -> Option<String> {
+    let name: &str = K::from(id).into();
+    // The ids unknown to the enum are errors
+    (name != "ERROR").then(|| name.to_string())
+}
+
+// Returns the name the crate gives to a kind id, for the enums written by
+// hand, from the table of the names of their kinds
+fn listed_name(kinds: &[(&str, u16)], id: u16) -> Option<String> {
+    kinds
+        .iter()
+        .find(|(_, kind)| *kind == id)
+        .map(|(name, _)| name.to_string())
+}
+
+/// Returns the node kinds of a language whose ids name another kind in
+/// the grammar the library is built with.
+///
+/// The metrics identify the nodes by the ids of their kinds, which a new
+/// version of a grammar may renumber, so the kinds with a mismatch are
+/// misidentified until the enum of the language is generated again.
+pub fn kind_mismatches(language: LANG) -> Vec<KindMismatch> {
+    let name_of = |id| match language {
+        LANG::Javascript => generated_name::<Javascript>(id),
+        LANG::Java => generated_name::<Java>(id),
+        LANG::Rust => generated_name::<Rust>(id),
+        LANG::Cpp => generated_name::<Cpp>(id),
+        LANG::Python => generated_name::<Python>(id),
+        LANG::Tsx => generated_name::<Tsx>(id),
+        LANG::Typescript => generated_name::<Typescript>(id),
+        LANG::Elixir => listed_name(ELIXIR_KINDS, id),
+        LANG::Erlang => listed_name(ERLANG_KINDS, id),
+        LANG::Gleam => listed_name(GLEAM_KINDS, id),
+        LANG::Lua => listed_name(LUA_KINDS, id),
+        LANG::Go => generated_name::<Go>(id),
+        LANG::Csharp => generated_name::<Csharp>(id),
+    };
+    let grammar = language.get_ts_language();
+    (0..grammar.node_kind_count() as u16)
+        .filter_map(|id| {
+            let actual = grammar.node_kind_for_id(id)?;
+            let expected = name_of(id)?;
+            (expected != actual).then(|| KindMismatch {
+                id,
+                expected,
+                actual: actual.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// The grammar of a language the library is built with.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct GrammarVersion {
+    /// The id of the language, its name or `tsx` for TSX, whose name is
+    /// the one of TypeScript
+    pub language: &'static str,
+    /// The tree-sitter crate of the grammar
+    pub grammar: &'static str,
+    /// The version of the crate, if known
+    pub version: Option<&'static str>,
+    /// The shape of the grammar
+    #[serde(flatten)]
+    pub info: GrammarInfo,
+    /// The node kinds misidentified with this grammar
+    pub mismatches: Vec<KindMismatch>,
+}
+
+impl GrammarVersion {
+    /// Returns the grammar of a language, checking its node kinds.
+    pub fn new(language: LANG) -> Self {
+        let grammar = grammar_crate(language);
+        Self {
+            language: match language {
+                LANG::Tsx => "tsx",
+                _ => language.get_name(),
+            },
+            grammar,
+            version: grammar_version(grammar),
+            info: GrammarInfo::new(language),
+            mismatches: kind_mismatches(language),
+        }
+    }
+
+    /// Returns `true` if all the node kinds of the language are
+    /// identified correctly with this grammar.
+    pub fn is_compatible(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kind_names() {
+        assert_eq!(listed_name(LUA_KINDS, 0).as_deref(), Some("end"));
+        // The aliases of a kind have its name
+        assert_eq!(
+            listed_name(LUA_KINDS, 101).as_deref(),
+            Some("variable_list")
+        );
+        assert_eq!(listed_name(ERLANG_KINDS, 163).as_deref(), Some("fa"));
+        // An id unknown to the enum
+        assert_eq!(listed_name(LUA_KINDS, u16::MAX), None);
+        assert_eq!(generated_name::<Cpp>(u16::MAX - 1), None);
+    }
+
+    #[test]
+    fn pinned_grammars() {
+        for language in LANG::into_enum_iter() {
+            let grammar = GrammarVersion::new(language);
+            assert!(
+                grammar.is_compatible(),
+                "{}: {:?}",
+                grammar.language,
+                grammar.mismatches
+            );
+        }
+    }
+
+    #[test]
+    fn generated_grammars() {
+        // The enums generated by the build script follow their grammars
+        assert!(GrammarVersion::new(LANG::Go).is_compatible());
+        assert!(GrammarVersion::new(LANG::Csharp).is_compatible());
+        assert_eq!(
+            Into::<&str>::into(Go::from(u16::MAX)),
+            "ERROR",
+            "the unknown ids are errors"
+        );
+    }
+
+    #[test]
+    fn rust_grammar() {
+        let grammar = GrammarVersion::new(LANG::Rust);
+        assert_eq!(grammar.language, "rust");
+        assert_eq!(grammar.grammar, "tree-sitter-rust");
+        assert!(grammar.info.node_kinds > 0);
+        // The kinds of Rust are mapped by name
+        assert!(grammar.is_compatible());
+    }
+}
@@ -0,0 +1,233 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, OnceLock, RwLock};
+
+use serde::{de::Error as _, Deserialize};
+
+use crate::langs::LANG;
+use crate::metrics::halstead::HalsteadType;
+use crate::node::Node;
+
+const BUILTIN: &str = include_str!("halstead.toml");
+
+// The languages whose nodes are classified by a table
+const TABLE_LANGUAGES: [LANG; 4] = [LANG::Lua, LANG::Elixir, LANG::Erlang, LANG::Gleam];
+
+static BUILTIN_TABLES: OnceLock<HalsteadTables> = OnceLock::new();
+// The user tables merged over the built-in ones
+static USER_TABLES: RwLock<Option<HalsteadTables>> = RwLock::new(None);
+
+/// The Halstead operators and operands among some kinds of nodes.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HalsteadKinds {
+    /// The kinds of the operators
+    pub operators: HashSet<String>,
+    /// The kinds of the operands
+    pub operands: HashSet<String>,
+}
+
+impl HalsteadKinds {
+    fn op_type(&self, kind: &str) -> Option<HalsteadType> {
+        if self.operators.contains(kind) {
+            Some(HalsteadType::Operator)
+        } else if self.operands.contains(kind) {
+            Some(HalsteadType::Operand)
+        } else {
+            None
+        }
+    }
+
+    // Returns these kinds and the kinds of `base` they don't list
+    fn over(&self, base: &HalsteadKinds) -> HalsteadKinds {
+        let unlisted =
+            |kind: &&String| !self.operators.contains(*kind) && !self.operands.contains(*kind);
+        HalsteadKinds {
+            operators: self
+                .operators
+                .iter()
+                .chain(base.operators.iter().filter(unlisted))
+                .cloned()
+                .collect(),
+            operands: self
+                .operands
+                .iter()
+                .chain(base.operands.iter().filter(unlisted))
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+/// The Halstead classification of the nodes of a language.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HalsteadTable {
+    /// The classification of the named nodes
+    pub nodes: HalsteadKinds,
+    /// The classification of the anonymous nodes, i.e. keywords,
+    /// operators and delimiters
+    pub tokens: HalsteadKinds,
+}
+
+impl HalsteadTable {
+    /// Returns the type of a node, if its kind is in the table.
+    pub fn op_type(&self, node: &Node) -> Option<HalsteadType> {
+        if node.is_named() {
+            self.nodes.op_type(node.kind())
+        } else {
+            self.tokens.op_type(node.kind())
+        }
+    }
+
+    // Returns this table with the kinds of `base` it doesn't list
+    fn over(&self, base: &HalsteadTable) -> HalsteadTable {
+        HalsteadTable {
+            nodes: self.nodes.over(&base.nodes),
+            tokens: self.tokens.over(&base.tokens),
+        }
+    }
+}
+
+/// The Halstead tables of some languages.
+///
+/// Only Lua, Elixir, Erlang and Gleam classify their nodes with a table,
+/// the other languages keep their contextual rules in code. The tables
+/// are written in `TOML` and keyed by the lowercase name of one of these
+/// [`LANG`] variants:
+///
+/// ```toml
+/// [lua.nodes]
+/// operands = ["identifier", "number", "string"]
+///
+/// [lua.tokens]
+/// operators = ["local", "function", "+", "-"]
+/// ```
+///
+/// The built-in tables are in `src/halstead.toml`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HalsteadTables {
+    tables: HashMap<LANG, Arc<HalsteadTable>>,
+}
+
+impl HalsteadTables {
+    /// Parses some tables from a `TOML` document, failing on the
+    /// languages which aren't classified by a table.
+    pub fn from_toml(toml: &str) -> Result<Self, toml::de::Error> {
+        let raw: HashMap<String, HalsteadTable> = toml::from_str(toml)?;
+        let mut tables = HashMap::with_capacity(raw.len());
+        for (name, table) in raw {
+            let lang = TABLE_LANGUAGES
+                .into_iter()
+                .find(|lang| table_name(*lang) == name)
+                .ok_or_else(|| {
+                    toml::de::Error::custom(format!("no Halstead table for language `{name}`"))
+                })?;
+            tables.insert(lang, Arc::new(table));
+        }
+        Ok(Self { tables })
+    }
+
+    /// Returns the built-in tables.
+    pub fn builtin() -> &'static Self {
+        BUILTIN_TABLES
+            .get_or_init(|| Self::from_toml(BUILTIN).expect("valid built-in Halstead tables"))
+    }
+
+    /// Returns the table of a language.
+    pub fn get(&self, lang: LANG) -> Option<&HalsteadTable> {
+        self.tables.get(&lang).map(Arc::as_ref)
+    }
+}
+
+fn table_name(lang: LANG) -> String {
+    format!("{lang:?}").to_lowercase()
+}
+
+/// Overrides the built-in Halstead tables at runtime.
+///
+/// The kinds listed in `tables` take precedence over the built-in ones,
+/// which are still used for the other kinds.
+///
+/// # Examples
+///
+/// ```
+/// use singularity_code_analysis::{set_halstead_tables, HalsteadTables};
+///
+/// // Count Lua `return` statements as operands
+/// let tables = HalsteadTables::from_toml("[lua.tokens]\noperands = [\"return\"]").unwrap();
+/// set_halstead_tables(tables);
+/// ```
+pub fn set_halstead_tables(tables: HalsteadTables) {
+    let mut merged = HalsteadTables::builtin().clone();
+    for (lang, table) in tables.tables {
+        let table = match merged.get(lang) {
+            Some(builtin) => Arc::new(table.over(builtin)),
+            None => table,
+        };
+        merged.tables.insert(lang, table);
+    }
+    *USER_TABLES.write().unwrap_or_else(|e| e.into_inner()) = Some(merged);
+}
+
+/// Removes the tables set by [`set_halstead_tables`].
+pub fn reset_halstead_tables() {
+    *USER_TABLES.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Returns the Halstead table of a language, resolved once for each
+/// parsed code, or `None` if its nodes aren't classified by a table.
+pub(crate) fn halstead_table(lang: LANG) -> Option<Arc<HalsteadTable>> {
+    if !TABLE_LANGUAGES.contains(&lang) {
+        return None;
+    }
+    let user = USER_TABLES.read().unwrap_or_else(|e| e.into_inner());
+    user.as_ref()
+        .unwrap_or_else(|| HalsteadTables::builtin())
+        .tables
+        .get(&lang)
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{metrics, LuaParser, ParserTrait};
+
+    use super::*;
+
+    #[test]
+    fn builtin_tables() {
+        for lang in [LANG::Lua, LANG::Elixir, LANG::Erlang, LANG::Gleam] {
+            assert!(HalsteadTables::builtin().get(lang).is_some());
+        }
+    }
+
+    #[test]
+    fn unknown_language() {
+        assert!(HalsteadTables::from_toml("[cobol.nodes]\noperands = [\"x\"]").is_err());
+        assert!(HalsteadTables::from_toml("[lua]\noperands = [\"x\"]").is_err());
+        assert!(HalsteadTables::from_toml("[rust.nodes]\noperands = [\"x\"]").is_err());
+        assert!(halstead_table(LANG::Rust).is_none());
+    }
+
+    #[test]
+    fn user_tables() {
+        let path = Path::new("foo.lua");
+        let operands = || {
+            let parser = LuaParser::new(b"::top::\ngoto top\n".to_vec(), path, None);
+            metrics(&parser, path).unwrap().metrics.halstead.operands()
+        };
+
+        // `goto` isn't used by the other tests, which may run concurrently
+        let builtin = operands();
+        set_halstead_tables(
+            HalsteadTables::from_toml("[lua.tokens]\noperands = [\"goto\"]").unwrap(),
+        );
+        let user = operands();
+        reset_halstead_tables();
+
+        assert_eq!(user, builtin + 1.);
+        assert_eq!(operands(), builtin);
+    }
+}
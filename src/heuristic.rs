@@ -0,0 +1,196 @@
+use serde::Serialize;
+
+// The prefixes of the line comments of the common languages
+const LINE_COMMENTS: [&[u8]; 5] = [b"//", b"#", b"--", b";", b"%"];
+
+// The delimiters of the block comments of the common languages
+const BLOCK_COMMENTS: [(&[u8], &[u8]); 2] = [(b"/*", b"*/"), (b"<!--", b"-->")];
+
+/// The metrics of a code in a language which no grammar supports,
+/// estimated from its lines and its tokens without parsing it.
+///
+/// The comments are recognized by the syntaxes common to most languages,
+/// so the estimates are rough, but they keep the files of the unsupported
+/// languages in the aggregates of a repository.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct HeuristicMetrics {
+    /// Always `true`: the metrics are estimated, not computed on a syntax
+    /// tree
+    pub heuristic: bool,
+    /// The number of lines
+    pub lines: usize,
+    /// The number of lines containing code
+    pub sloc: usize,
+    /// The number of lines containing only comments
+    pub comment_lines: usize,
+    /// The number of blank lines
+    pub blank_lines: usize,
+    /// The ratio of the comment lines to the non-blank lines
+    pub comment_ratio: f64,
+    /// The number of tokens of the code lines, i.e. the words and the
+    /// punctuation characters
+    pub tokens: usize,
+    /// The deepest nesting of the braces of the code
+    pub max_nesting: usize,
+}
+
+fn trim(line: &[u8]) -> &[u8] {
+    let start = line
+        .iter()
+        .position(|c| !c.is_ascii_whitespace())
+        .unwrap_or(line.len());
+    let end = line
+        .iter()
+        .rposition(|c| !c.is_ascii_whitespace())
+        .map_or(start, |end| end + 1);
+    &line[start..end]
+}
+
+fn position_of(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn is_word(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_' || !c.is_ascii()
+}
+
+/// Estimates the metrics of a code in any language.
+///
+/// # Examples
+///
+/// ```
+/// use singularity_code_analysis::estimate_metrics;
+///
+/// let metrics = estimate_metrics(b"# A script\nif ok {\n    run()\n}\n");
+/// assert!(metrics.heuristic);
+/// assert_eq!((metrics.sloc, metrics.comment_lines), (3, 1));
+/// assert_eq!(metrics.max_nesting, 1);
+/// ```
+pub fn estimate_metrics(code: &[u8]) -> HeuristicMetrics {
+    let mut metrics = HeuristicMetrics {
+        heuristic: true,
+        ..Default::default()
+    };
+    let mut depth = 0usize;
+    // The end of the block comment the current line is in
+    let mut block_end: Option<&[u8]> = None;
+
+    let code = code.strip_suffix(b"\n").unwrap_or(code);
+    if code.is_empty() {
+        return metrics;
+    }
+    for line in code.split(|&c| c == b'\n') {
+        metrics.lines += 1;
+        let mut rest = trim(line);
+        if rest.is_empty() {
+            metrics.blank_lines += 1;
+            continue;
+        }
+
+        // The code of the line, outside of its comments
+        let mut code_parts = Vec::new();
+        while !rest.is_empty() {
+            if let Some(end) = block_end {
+                match position_of(rest, end) {
+                    Some(i) => {
+                        rest = &rest[i + end.len()..];
+                        block_end = None;
+                    }
+                    None => rest = &[],
+                }
+                continue;
+            }
+            let block = BLOCK_COMMENTS
+                .iter()
+                .filter_map(|(start, end)| position_of(rest, start).map(|i| (i, start.len(), *end)))
+                .min_by_key(|(i, _, _)| *i);
+            // Only the `//` comments are recognized after some code
+            let line_comment = if LINE_COMMENTS.iter().any(|prefix| rest.starts_with(prefix)) {
+                Some(0)
+            } else {
+                position_of(rest, b"//")
+            };
+            if let Some(i) = line_comment.filter(|&i| !matches!(block, Some((j, _, _)) if j < i)) {
+                code_parts.push(&rest[..i]);
+                rest = &[];
+            } else if let Some((i, len, end)) = block {
+                code_parts.push(&rest[..i]);
+                rest = &rest[i + len..];
+                block_end = Some(end);
+            } else {
+                code_parts.push(rest);
+                rest = &[];
+            }
+        }
+
+        let mut is_code = false;
+        for part in code_parts {
+            let mut in_word = false;
+            for &c in trim(part) {
+                if is_word(c) {
+                    if !in_word {
+                        metrics.tokens += 1;
+                    }
+                    in_word = true;
+                    is_code = true;
+                    continue;
+                }
+                in_word = false;
+                if c.is_ascii_whitespace() {
+                    continue;
+                }
+                metrics.tokens += 1;
+                is_code = true;
+                match c {
+                    b'{' => {
+                        depth += 1;
+                        metrics.max_nesting = metrics.max_nesting.max(depth);
+                    }
+                    b'}' => depth = depth.saturating_sub(1),
+                    _ => {}
+                }
+            }
+        }
+        if is_code {
+            metrics.sloc += 1;
+        } else {
+            metrics.comment_lines += 1;
+        }
+    }
+
+    let non_blank = metrics.sloc + metrics.comment_lines;
+    if non_blank > 0 {
+        metrics.comment_ratio = metrics.comment_lines as f64 / non_blank as f64;
+    }
+    metrics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimated_metrics() {
+        let code = b"/* A header\n * on lines\n */\n\nfn main() { // entry\n    if x { y(); }\n}\n";
+        let metrics = estimate_metrics(code);
+        assert!(metrics.heuristic);
+        assert_eq!(metrics.lines, 7);
+        assert_eq!(metrics.comment_lines, 3);
+        assert_eq!(metrics.blank_lines, 1);
+        assert_eq!(metrics.sloc, 3);
+        assert_eq!(metrics.max_nesting, 2);
+        assert_eq!(metrics.comment_ratio, 0.5);
+        // fn main ( ) { if x { y ( ) ; } }
+        assert_eq!(metrics.tokens, 14);
+    }
+
+    #[test]
+    fn empty_code() {
+        let metrics = estimate_metrics(b"");
+        assert!(metrics.heuristic);
+        assert_eq!(metrics.lines, 0);
+        assert_eq!(metrics.comment_ratio, 0.);
+    }
+}
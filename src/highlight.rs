@@ -0,0 +1,318 @@
+use std::{collections::HashMap, path::Path, sync::OnceLock};
+
+use serde::Serialize;
+use tree_sitter::Query;
+
+use crate::{
+    checker::Checker,
+    langs::{action, LANG},
+    node::Node,
+    traits::{Callback, ParserTrait},
+};
+
+/// The class of a highlighted token, to pick its color in a theme.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HighlightClass {
+    /// A keyword, like `fn` or `return`
+    Keyword,
+    /// The name of a function or of a method
+    Function,
+    /// The name of a type, of a module or of a constructor
+    Type,
+    /// A variable or a parameter
+    Variable,
+    /// A field or a property
+    Property,
+    /// A constant, like `true` or `None`
+    Constant,
+    /// A string or a character, with its escape sequences
+    String,
+    /// A number
+    Number,
+    /// A comment
+    Comment,
+    /// An operator
+    Operator,
+    /// A bracket or a delimiter
+    Punctuation,
+    /// An attribute, an annotation or a decorator
+    Attribute,
+    /// A token which isn't highlighted
+    Plain,
+}
+
+impl HighlightClass {
+    /// Returns the name of the class, like `keyword`, to use as a `CSS`
+    /// class.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Keyword => "keyword",
+            Self::Function => "function",
+            Self::Type => "type",
+            Self::Variable => "variable",
+            Self::Property => "property",
+            Self::Constant => "constant",
+            Self::String => "string",
+            Self::Number => "number",
+            Self::Comment => "comment",
+            Self::Operator => "operator",
+            Self::Punctuation => "punctuation",
+            Self::Attribute => "attribute",
+            Self::Plain => "plain",
+        }
+    }
+
+    // The class of a capture name of a highlight query, like
+    // `function.method` or `keyword.return`
+    fn from_scope(scope: &str) -> Self {
+        match scope.split('.').next().unwrap_or_default() {
+            "keyword" | "conditional" | "repeat" | "include" | "exception" => Self::Keyword,
+            "function" | "method" => Self::Function,
+            "type" | "constructor" | "module" | "namespace" => Self::Type,
+            "variable" | "parameter" => Self::Variable,
+            "property" | "field" => Self::Property,
+            "constant" | "boolean" => Self::Constant,
+            "string" | "character" | "escape" => Self::String,
+            "number" | "float" => Self::Number,
+            "comment" => Self::Comment,
+            "operator" => Self::Operator,
+            "punctuation" => Self::Punctuation,
+            "attribute" | "decorator" | "annotation" => Self::Attribute,
+            _ => Self::Plain,
+        }
+    }
+}
+
+/// A token of a code with its highlight.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct HighlightToken {
+    /// The byte offset where the token starts
+    pub start_byte: usize,
+    /// The byte offset where the token ends
+    pub end_byte: usize,
+    /// The first line of the token
+    pub start_line: usize,
+    /// The last line of the token
+    pub end_line: usize,
+    /// The class of the token
+    pub class: HighlightClass,
+    /// The capture name of the highlight query, like `function.method`,
+    /// which is the name of the class for the languages without a query,
+    /// and empty for the tokens which aren't highlighted
+    pub scope: String,
+}
+
+// The highlight query of the grammar of a language, if its crate has one
+fn highlights_query(language: LANG) -> Option<String> {
+    let javascript = tree_sitter_javascript::HIGHLIGHT_QUERY;
+    let jsx = tree_sitter_javascript::JSX_HIGHLIGHT_QUERY;
+    let typescript = tree_sitter_typescript::HIGHLIGHTS_QUERY;
+    Some(match language {
+        LANG::Rust => tree_sitter_rust::HIGHLIGHTS_QUERY.to_string(),
+        LANG::Python => tree_sitter_python::HIGHLIGHTS_QUERY.to_string(),
+        LANG::Java => tree_sitter_java::HIGHLIGHTS_QUERY.to_string(),
+        LANG::Go => tree_sitter_go::HIGHLIGHTS_QUERY.to_string(),
+        LANG::Cpp => tree_sitter_cpp::HIGHLIGHT_QUERY.to_string(),
+        LANG::Javascript => format!("{jsx}\n{javascript}"),
+        // The queries of TypeScript extend the ones of JavaScript
+        LANG::Typescript => format!("{typescript}\n{javascript}"),
+        LANG::Tsx => format!("{typescript}\n{jsx}\n{javascript}"),
+        _ => return None,
+    })
+}
+
+// The compiled highlight queries, the ones which don't compile with the
+// grammars of the crate being left out
+fn query(language: LANG) -> Option<&'static Query> {
+    static QUERIES: OnceLock<HashMap<LANG, Query>> = OnceLock::new();
+    QUERIES
+        .get_or_init(|| {
+            LANG::into_enum_iter()
+                .filter_map(|language| {
+                    let source = highlights_query(language)?;
+                    let query = Query::new(&language.get_ts_language(), &source).ok()?;
+                    Some((language, query))
+                })
+                .collect()
+        })
+        .get(&language)
+}
+
+// The scope of a node of a language without a highlight query, from its
+// kind
+fn node_scope<T: ParserTrait>(node: &Node, code: &[u8]) -> Option<&'static str> {
+    let kind = node.kind();
+    if T::Checker::is_comment(node) {
+        Some("comment")
+    } else if T::Checker::is_string(node) {
+        Some("string")
+    } else if node.child_count() > 0 {
+        None
+    } else if !node.is_named() {
+        let text = node.utf8_text(code).unwrap_or_default();
+        if text.starts_with(|c: char| c.is_alphabetic()) {
+            Some("keyword")
+        } else if text.chars().all(|c| "()[]{},;.:".contains(c)) {
+            Some("punctuation")
+        } else {
+            Some("operator")
+        }
+    } else if kind.contains("type") {
+        Some("type")
+    } else if kind.contains("field") || kind.contains("property") {
+        Some("property")
+    } else if kind.contains("identifier") || kind == "name" {
+        Some("variable")
+    } else if ["number", "integer", "float", "int"]
+        .iter()
+        .any(|number| kind.contains(number))
+    {
+        Some("number")
+    } else {
+        None
+    }
+}
+
+/// Returns the tokens of a code with their highlight, in the order of the
+/// code.
+///
+/// The tokens are classified by the highlight query of the grammar of
+/// the language, where the first pattern capturing a node wins and the
+/// tokens inherit the capture of their closest ancestor, like the tokens
+/// of a string. The languages whose grammar has no query are classified
+/// by the kinds of their nodes.
+///
+/// The comments and the strings are one token, and the text between the
+/// tokens, like the whitespaces, isn't part of any of them.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{highlights, HighlightClass, ParserEngineRust, ParserTrait};
+///
+/// let parser = ParserEngineRust::new(b"fn f() {}".to_vec(), Path::new("foo.rs"), None);
+/// let tokens = highlights(&parser);
+/// assert_eq!((tokens[0].start_byte, tokens[0].end_byte), (0, 2));
+/// assert_eq!(tokens[0].class, HighlightClass::Keyword);
+/// ```
+pub fn highlights<T: ParserTrait>(parser: &T) -> Vec<HighlightToken> {
+    let code = parser.get_code();
+    let root = parser.get_root();
+    let query = query(parser.get_language());
+    let mut scopes = HashMap::new();
+    if let Some(query) = query {
+        let names = query.capture_names();
+        for (node, index) in root.captures(query, code) {
+            scopes.entry(node.id()).or_insert(names[index]);
+        }
+    }
+
+    let mut tokens = Vec::new();
+    let mut stack = vec![(root, None)];
+    while let Some((node, inherited)) = stack.pop() {
+        let scope = match query {
+            Some(_) => scopes.get(&node.id()).copied(),
+            None => node_scope::<T>(&node, code),
+        }
+        .or(inherited);
+        let class = scope.map_or(HighlightClass::Plain, HighlightClass::from_scope);
+        if node.child_count() > 0
+            && !matches!(class, HighlightClass::Comment | HighlightClass::String)
+        {
+            let mut children = node
+                .children()
+                .map(|child| (child, scope))
+                .collect::<Vec<_>>();
+            children.reverse();
+            stack.extend(children);
+        } else if node.start_byte() < node.end_byte() {
+            tokens.push(HighlightToken {
+                start_byte: node.start_byte(),
+                end_byte: node.end_byte(),
+                start_line: node.start_row() + 1,
+                end_line: node.end_row() + 1,
+                class,
+                scope: scope.unwrap_or_default().to_string(),
+            });
+        }
+    }
+    tokens
+}
+
+/// Classifies the tokens of a code for highlighting.
+pub struct Highlights {
+    _guard: (),
+}
+
+impl Callback for Highlights {
+    type Res = Vec<HighlightToken>;
+    type Cfg = ();
+
+    fn call<T: ParserTrait>(_cfg: Self::Cfg, parser: &T) -> Self::Res {
+        highlights(parser)
+    }
+}
+
+/// Returns the tokens of a code in a language with their highlight, as
+/// in [`highlights`].
+pub fn get_highlights(lang: &LANG, source: Vec<u8>, path: &Path) -> Vec<HighlightToken> {
+    action::<Highlights>(lang, source, path, None, ())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{LuaParser, ParserEngineRust};
+
+    use super::*;
+
+    fn classes(code: &str, tokens: &[HighlightToken]) -> Vec<(String, HighlightClass)> {
+        tokens
+            .iter()
+            .map(|token| {
+                let text = code[token.start_byte..token.end_byte].trim_end();
+                (text.to_string(), token.class)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn query_highlights() {
+        let code = "fn f() -> u32 {\n    1\n} // one\n";
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), Path::new("foo.rs"), None);
+        let tokens = highlights(&parser);
+
+        assert!(tokens
+            .windows(2)
+            .all(|pair| pair[0].end_byte <= pair[1].start_byte));
+        let classes = classes(code, &tokens);
+        assert_eq!(classes[0], ("fn".to_string(), HighlightClass::Keyword));
+        assert_eq!(classes[1], ("f".to_string(), HighlightClass::Function));
+        assert_eq!(
+            classes.last(),
+            Some(&("// one".to_string(), HighlightClass::Comment))
+        );
+        assert_eq!(tokens.last().unwrap().start_line, 3);
+    }
+
+    #[test]
+    fn kind_highlights() {
+        let code = "local x = 1 -- one\n";
+        let parser = LuaParser::new(code.as_bytes().to_vec(), Path::new("foo.lua"), None);
+        let tokens = highlights(&parser);
+
+        assert_eq!(
+            classes(code, &tokens),
+            [
+                ("local".to_string(), HighlightClass::Keyword),
+                ("x".to_string(), HighlightClass::Variable),
+                ("=".to_string(), HighlightClass::Operator),
+                ("1".to_string(), HighlightClass::Number),
+                ("-- one".to_string(), HighlightClass::Comment),
+            ]
+        );
+        assert_eq!(tokens[1].scope, "variable");
+    }
+}
@@ -0,0 +1,143 @@
+use std::{
+    ffi::OsStr,
+    io,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use serde::{Deserialize, Serialize};
+
+// Separates the commits and the fields of a commit header in the log
+const COMMIT_SEPARATOR: char = '\x1e';
+const FIELD_SEPARATOR: char = '\x1f';
+
+/// The lines of a file changed by a commit.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FileChange {
+    /// The path of the file, relative to the root of the repository
+    pub path: PathBuf,
+    /// The number of added lines, zero for binary files
+    pub added: usize,
+    /// The number of removed lines, zero for binary files
+    pub removed: usize,
+}
+
+/// A commit of a git history.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Commit {
+    /// The name of the author
+    pub author: String,
+    /// The email of the author
+    pub email: String,
+    /// The files changed by the commit
+    pub changes: Vec<FileChange>,
+}
+
+/// The arguments of `git log` producing the output parsed by
+/// [`parse_git_log`].
+pub const GIT_LOG_ARGS: &[&str] = &[
+    "log",
+    "--numstat",
+    "--no-renames",
+    "--format=format:%x1e%aN%x1f%aE",
+];
+
+/// Parses the output of `git log` run with [`GIT_LOG_ARGS`].
+///
+/// Malformed lines are skipped.
+pub fn parse_git_log(log: &str) -> Vec<Commit> {
+    log.split(COMMIT_SEPARATOR)
+        .filter_map(|commit| {
+            let mut lines = commit.lines();
+            let (author, email) = lines.next()?.split_once(FIELD_SEPARATOR)?;
+            let changes = lines
+                .filter_map(|line| {
+                    let mut fields = line.splitn(3, '\t');
+                    let added = fields.next()?;
+                    let removed = fields.next()?;
+                    let path = fields.next()?;
+                    Some(FileChange {
+                        path: PathBuf::from(path),
+                        added: added.parse().unwrap_or(0),
+                        removed: removed.parse().unwrap_or(0),
+                    })
+                })
+                .collect();
+            Some(Commit {
+                author: author.to_string(),
+                email: email.to_string(),
+                changes,
+            })
+        })
+        .collect()
+}
+
+/// Reads the history of a git repository, optionally limited to the
+/// commits more recent than `since`, e.g. `6 months ago`.
+///
+/// `git` must be in the `PATH`.
+pub fn read_git_log(repository: &Path, since: Option<&str>) -> io::Result<Vec<Commit>> {
+    let mut args = GIT_LOG_ARGS
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect::<Vec<_>>();
+    if let Some(since) = since {
+        args.push(format!("--since={since}"));
+    }
+    let log = git(repository, &args)?;
+    Ok(parse_git_log(&String::from_utf8_lossy(&log)))
+}
+
+/// Runs a git command in a repository and returns its output.
+pub(crate) fn git<S: AsRef<OsStr>>(repository: &Path, args: &[S]) -> io::Result<Vec<u8>> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repository)
+        .args(args)
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(output.stdout)
+}
+
+/// Runs a git command in a test repository as a test author.
+#[cfg(test)]
+pub(crate) fn test_git(repository: &Path, args: &[&str]) {
+    let identity = ["-c", "user.name=Test", "-c", "user.email=test@example.com"];
+    git(repository, &[&identity, args].concat()).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn git_log() {
+        let log = "\x1eAda\x1fada@example.com\n\n3\t1\tsrc/a.rs\n-\t-\tlogo.png\n\
+                   \x1eBob\x1fbob@example.com\n\n10\t0\tsrc/b.rs\n";
+        let commits = parse_git_log(log);
+
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].author, "Ada");
+        assert_eq!(
+            commits[0].changes,
+            [
+                FileChange {
+                    path: PathBuf::from("src/a.rs"),
+                    added: 3,
+                    removed: 1,
+                },
+                FileChange {
+                    path: PathBuf::from("logo.png"),
+                    added: 0,
+                    removed: 0,
+                },
+            ]
+        );
+        assert_eq!(commits[1].email, "bob@example.com");
+        assert_eq!(commits[1].changes[0].added, 10);
+    }
+}
@@ -0,0 +1,800 @@
+use std::collections::BTreeSet;
+
+use serde::Serialize;
+
+use crate::{
+    checker::Checker,
+    getter::Getter,
+    langs::{action, LANG},
+    node::Node,
+    traits::{Callback, ParserTrait},
+};
+
+// The words of the identifiers counted as dictionary words, with the
+// abbreviations common enough to be as readable
+const DICTIONARY: &[&str] = &[
+    "abort",
+    "absolute",
+    "accept",
+    "access",
+    "account",
+    "action",
+    "active",
+    "add",
+    "address",
+    "after",
+    "age",
+    "all",
+    "allow",
+    "amount",
+    "analysis",
+    "analyze",
+    "and",
+    "any",
+    "api",
+    "append",
+    "apply",
+    "args",
+    "array",
+    "async",
+    "attribute",
+    "auth",
+    "available",
+    "average",
+    "back",
+    "base",
+    "batch",
+    "before",
+    "begin",
+    "block",
+    "body",
+    "bool",
+    "bound",
+    "box",
+    "buffer",
+    "build",
+    "builder",
+    "byte",
+    "bytes",
+    "cache",
+    "calculate",
+    "call",
+    "callback",
+    "can",
+    "cancel",
+    "capacity",
+    "case",
+    "cell",
+    "change",
+    "char",
+    "check",
+    "child",
+    "children",
+    "class",
+    "clean",
+    "clear",
+    "client",
+    "clone",
+    "close",
+    "code",
+    "collect",
+    "color",
+    "column",
+    "command",
+    "comment",
+    "commit",
+    "compare",
+    "complete",
+    "compute",
+    "config",
+    "connect",
+    "connection",
+    "content",
+    "context",
+    "convert",
+    "copy",
+    "core",
+    "count",
+    "counter",
+    "create",
+    "current",
+    "cursor",
+    "data",
+    "date",
+    "day",
+    "db",
+    "debug",
+    "decode",
+    "default",
+    "delete",
+    "depth",
+    "description",
+    "destroy",
+    "dir",
+    "directory",
+    "disable",
+    "display",
+    "do",
+    "done",
+    "down",
+    "draw",
+    "each",
+    "edge",
+    "element",
+    "else",
+    "empty",
+    "enable",
+    "encode",
+    "end",
+    "entry",
+    "equal",
+    "err",
+    "error",
+    "event",
+    "exists",
+    "exit",
+    "expected",
+    "export",
+    "extend",
+    "factory",
+    "fail",
+    "false",
+    "fetch",
+    "field",
+    "file",
+    "filter",
+    "find",
+    "first",
+    "flag",
+    "flush",
+    "for",
+    "format",
+    "found",
+    "frame",
+    "from",
+    "function",
+    "get",
+    "global",
+    "graph",
+    "group",
+    "handle",
+    "handler",
+    "has",
+    "hash",
+    "head",
+    "header",
+    "height",
+    "helper",
+    "history",
+    "host",
+    "html",
+    "http",
+    "id",
+    "ids",
+    "if",
+    "image",
+    "import",
+    "in",
+    "index",
+    "info",
+    "init",
+    "initial",
+    "input",
+    "insert",
+    "instance",
+    "int",
+    "io",
+    "is",
+    "item",
+    "items",
+    "iter",
+    "json",
+    "key",
+    "keys",
+    "kind",
+    "label",
+    "language",
+    "last",
+    "layer",
+    "left",
+    "len",
+    "length",
+    "level",
+    "limit",
+    "line",
+    "lines",
+    "link",
+    "list",
+    "load",
+    "local",
+    "lock",
+    "log",
+    "logger",
+    "loop",
+    "lower",
+    "main",
+    "manager",
+    "map",
+    "mark",
+    "match",
+    "max",
+    "merge",
+    "message",
+    "meta",
+    "method",
+    "metric",
+    "metrics",
+    "min",
+    "mode",
+    "model",
+    "module",
+    "name",
+    "names",
+    "new",
+    "next",
+    "node",
+    "nodes",
+    "none",
+    "not",
+    "null",
+    "num",
+    "number",
+    "object",
+    "of",
+    "offset",
+    "ok",
+    "old",
+    "on",
+    "open",
+    "option",
+    "options",
+    "or",
+    "order",
+    "out",
+    "output",
+    "owner",
+    "page",
+    "pair",
+    "param",
+    "params",
+    "parent",
+    "parse",
+    "parser",
+    "part",
+    "path",
+    "pattern",
+    "payload",
+    "peek",
+    "pending",
+    "point",
+    "pool",
+    "pop",
+    "port",
+    "position",
+    "post",
+    "prefix",
+    "prev",
+    "previous",
+    "print",
+    "process",
+    "project",
+    "property",
+    "push",
+    "query",
+    "queue",
+    "range",
+    "rate",
+    "raw",
+    "read",
+    "reader",
+    "record",
+    "ref",
+    "register",
+    "remove",
+    "render",
+    "replace",
+    "report",
+    "request",
+    "reset",
+    "resolve",
+    "resource",
+    "response",
+    "result",
+    "results",
+    "retry",
+    "return",
+    "right",
+    "root",
+    "row",
+    "rule",
+    "rules",
+    "run",
+    "save",
+    "scale",
+    "scan",
+    "schema",
+    "scope",
+    "score",
+    "search",
+    "second",
+    "select",
+    "send",
+    "server",
+    "service",
+    "session",
+    "set",
+    "setting",
+    "settings",
+    "shape",
+    "should",
+    "show",
+    "side",
+    "size",
+    "skip",
+    "sort",
+    "source",
+    "space",
+    "spaces",
+    "split",
+    "sql",
+    "stack",
+    "start",
+    "state",
+    "status",
+    "step",
+    "stop",
+    "store",
+    "str",
+    "stream",
+    "string",
+    "strip",
+    "struct",
+    "style",
+    "sum",
+    "suffix",
+    "table",
+    "tag",
+    "target",
+    "task",
+    "temp",
+    "test",
+    "text",
+    "then",
+    "thread",
+    "time",
+    "timeout",
+    "timer",
+    "to",
+    "token",
+    "tokens",
+    "total",
+    "trace",
+    "tree",
+    "true",
+    "try",
+    "type",
+    "unit",
+    "up",
+    "update",
+    "upper",
+    "uri",
+    "url",
+    "use",
+    "user",
+    "utf",
+    "util",
+    "valid",
+    "validate",
+    "value",
+    "values",
+    "var",
+    "vec",
+    "version",
+    "view",
+    "visit",
+    "wait",
+    "warn",
+    "when",
+    "width",
+    "window",
+    "with",
+    "word",
+    "words",
+    "worker",
+    "write",
+    "writer",
+    "x",
+    "y",
+    "z",
+    "i",
+    "j",
+    "k",
+    "n",
+];
+
+/// A naming convention of the identifiers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NamingConvention {
+    /// `snake_case`
+    SnakeCase,
+    /// `camelCase`
+    CamelCase,
+    /// `PascalCase`
+    PascalCase,
+    /// `SCREAMING_SNAKE_CASE`, for the constants
+    ScreamingSnakeCase,
+}
+
+impl NamingConvention {
+    /// Returns `true` if an identifier follows the convention.
+    ///
+    /// The leading and trailing underscores, marking the private or the
+    /// unused identifiers, are ignored.
+    pub fn matches(self, identifier: &str) -> bool {
+        let identifier = identifier.trim_matches('_');
+        let Some(first) = identifier.chars().next() else {
+            return false;
+        };
+        match self {
+            Self::SnakeCase => identifier
+                .chars()
+                .all(|c| c.is_lowercase() || c.is_ascii_digit() || c == '_'),
+            Self::CamelCase => first.is_lowercase() && !identifier.contains('_'),
+            Self::PascalCase => first.is_uppercase() && !identifier.contains('_'),
+            Self::ScreamingSnakeCase => identifier
+                .chars()
+                .all(|c| c.is_uppercase() || c.is_ascii_digit() || c == '_'),
+        }
+    }
+}
+
+// The conventions of the names of the functions and the variables of a
+// language
+fn value_conventions(language: LANG) -> &'static [NamingConvention] {
+    use NamingConvention::*;
+
+    match language {
+        LANG::Javascript | LANG::Typescript | LANG::Tsx | LANG::Java => &[CamelCase],
+        // The exported names are in `PascalCase`
+        LANG::Go | LANG::Csharp => &[CamelCase, PascalCase],
+        LANG::Cpp => &[SnakeCase, CamelCase],
+        _ => &[SnakeCase],
+    }
+}
+
+/// Splits an identifier into its lowercase words, at the underscores and
+/// at the case changes, so `parseHTTPRequest2` has the words `parse`,
+/// `http`, `request` and `2`.
+pub fn identifier_words(identifier: &str) -> Vec<String> {
+    let chars = identifier.chars().collect::<Vec<_>>();
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+        // The previous character is read from the identifier, since the
+        // word is lowercased
+        if let Some(previous) = (!word.is_empty()).then(|| chars[i - 1]) {
+            let next_is_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            let boundary = (previous.is_lowercase() && c.is_uppercase())
+                || (previous.is_uppercase() && c.is_uppercase() && next_is_lower)
+                || (previous.is_ascii_digit() != c.is_ascii_digit());
+            if boundary {
+                words.push(std::mem::take(&mut word));
+            }
+        }
+        word.extend(c.to_lowercase());
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+// Returns `true` if a word is an abbreviation: a short word, or a word
+// without any vowel, which isn't in the dictionary
+fn is_abbreviation(word: &str) -> bool {
+    !DICTIONARY.contains(&word)
+        && !word.chars().all(|c| c.is_ascii_digit())
+        && (word.len() <= 3 || !word.chars().any(|c| "aeiouy".contains(c)))
+}
+
+/// The quality of the identifiers of a code.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct IdentifierQuality {
+    /// The number of distinct identifiers
+    pub identifiers: usize,
+    /// The average length of the identifiers
+    pub average_length: f64,
+    /// The ratio of the words of the identifiers which are in the
+    /// dictionary
+    pub dictionary_ratio: f64,
+    /// The ratio of the words of the identifiers which are
+    /// abbreviations
+    pub abbreviation_density: f64,
+    /// The ratio of the identifiers following the conventions of the
+    /// language: `PascalCase` for the types, and the language's one for
+    /// the functions and the variables, constants excepted
+    pub convention_consistency: f64,
+    /// The score of the identifiers, from 0 to 100
+    pub score: f64,
+}
+
+impl IdentifierQuality {
+    fn new(identifiers: &BTreeSet<(bool, &str)>, language: LANG) -> Self {
+        if identifiers.is_empty() {
+            return Self::default();
+        }
+        let conventions = value_conventions(language);
+        let mut length = 0;
+        let mut words = 0;
+        let mut dictionary = 0;
+        let mut abbreviations = 0;
+        let mut consistent = 0;
+        for (is_type, identifier) in identifiers {
+            length += identifier.chars().count();
+            for word in identifier_words(identifier) {
+                words += 1;
+                if DICTIONARY.contains(&word.as_str()) {
+                    dictionary += 1;
+                } else if is_abbreviation(&word) {
+                    abbreviations += 1;
+                }
+            }
+            let follows = if *is_type {
+                NamingConvention::PascalCase.matches(identifier)
+            } else {
+                NamingConvention::ScreamingSnakeCase.matches(identifier)
+                    || conventions
+                        .iter()
+                        .any(|convention| convention.matches(identifier))
+            };
+            if follows {
+                consistent += 1;
+            }
+        }
+
+        let count = identifiers.len() as f64;
+        let average_length = length as f64 / count;
+        let ratio = |n: usize| {
+            if words > 0 {
+                n as f64 / words as f64
+            } else {
+                0.
+            }
+        };
+        let dictionary_ratio = ratio(dictionary);
+        let abbreviation_density = ratio(abbreviations);
+        let convention_consistency = consistent as f64 / count;
+        // The lengths between 4 and 20 characters are the readable ones
+        let length_score = if average_length < 4. {
+            average_length / 4.
+        } else {
+            (1. - (average_length - 20.).max(0.) / 20.).max(0.)
+        };
+        let score = 100.
+            * (0.3 * dictionary_ratio
+                + 0.3 * convention_consistency
+                + 0.2 * (1. - abbreviation_density)
+                + 0.2 * length_score);
+
+        Self {
+            identifiers: identifiers.len(),
+            average_length,
+            dictionary_ratio,
+            abbreviation_density,
+            convention_consistency,
+            score,
+        }
+    }
+}
+
+/// The quality of the identifiers of a function.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FunctionIdentifiers {
+    /// The name of the function
+    pub name: Option<String>,
+    /// The first line of the function
+    pub start_line: usize,
+    /// The last line of the function
+    pub end_line: usize,
+    /// The quality of the identifiers of the function
+    #[serde(flatten)]
+    pub quality: IdentifierQuality,
+}
+
+/// The quality of the identifiers of a file and of each of its
+/// functions.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct IdentifierReport {
+    /// The quality of the identifiers of the file
+    pub file: IdentifierQuality,
+    /// The functions, in the order they appear
+    pub functions: Vec<FunctionIdentifiers>,
+}
+
+// Returns `true` if an identifier names a type: a type identifier, or
+// the name of a class, a struct, an enum, an interface or a trait
+fn is_type_name(node: &Node) -> bool {
+    if node.kind().ends_with("type_identifier") {
+        return true;
+    }
+    node.parent().is_some_and(|parent| {
+        ["class", "struct", "enum", "interface", "trait", "record"]
+            .iter()
+            .any(|kind| parent.kind().contains(kind))
+            && parent
+                .child_by_field_name("name")
+                .is_some_and(|name| name.id() == node.id())
+    })
+}
+
+// Collects the distinct identifiers of a node, telling the type names
+fn collect_identifiers<'a>(
+    node: &Node<'a>,
+    code: &'a [u8],
+    identifiers: &mut BTreeSet<(bool, &'a str)>,
+) {
+    let mut stack = vec![*node];
+    while let Some(node) = stack.pop() {
+        if node.child_count() == 0 && node.kind().ends_with("identifier") {
+            if let Some(text) = node.utf8_text(code) {
+                if text.chars().any(|c| c.is_alphanumeric()) {
+                    identifiers.insert((is_type_name(&node), text));
+                }
+            }
+        }
+        stack.extend(node.children());
+    }
+}
+
+/// Evaluates the naming of the identifiers of a code, for the whole file
+/// and for each function.
+///
+/// The identifiers are the distinct leaves of the kinds ending with
+/// `identifier`, like `identifier`, `field_identifier` or
+/// `type_identifier`, so a name used many times counts once.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{identifier_quality, ParserTrait, PythonParser};
+///
+/// let path = Path::new("foo.py");
+/// let code = b"def load_config(path):\n    cfgFl = open(path)\n    return cfgFl\n".to_vec();
+/// let parser = PythonParser::new(code, path, None);
+///
+/// let report = identifier_quality(&parser);
+/// let function = &report.functions[0];
+/// assert_eq!(function.quality.identifiers, 4);
+/// assert_eq!(function.quality.convention_consistency, 0.75);
+/// ```
+pub fn identifier_quality<T: ParserTrait>(parser: &T) -> IdentifierReport {
+    let code = parser.get_code();
+    let language = parser.get_language();
+    let root = parser.get_root();
+
+    let mut identifiers = BTreeSet::new();
+    collect_identifiers(&root, code, &mut identifiers);
+    let mut report = IdentifierReport {
+        file: IdentifierQuality::new(&identifiers, language),
+        functions: Vec::new(),
+    };
+
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if T::Checker::is_func(&node) {
+            let mut identifiers = BTreeSet::new();
+            collect_identifiers(&node, code, &mut identifiers);
+            report.functions.push(FunctionIdentifiers {
+                name: T::Getter::get_func_name(&node, code).map(|name| name.to_string()),
+                start_line: node.start_row() + 1,
+                end_line: node.end_row() + 1,
+                quality: IdentifierQuality::new(&identifiers, language),
+            });
+        }
+        let mut children = node.children().collect::<Vec<_>>();
+        children.reverse();
+        stack.extend(children);
+    }
+    report
+}
+
+struct CodeIdentifiers {
+    _guard: (),
+}
+
+impl Callback for CodeIdentifiers {
+    type Res = IdentifierReport;
+    type Cfg = ();
+
+    fn call<T: ParserTrait>(_cfg: Self::Cfg, parser: &T) -> Self::Res {
+        identifier_quality(parser)
+    }
+}
+
+/// Evaluates the naming of the identifiers of a code in a language.
+pub fn code_identifier_quality(code: &str, language: LANG) -> IdentifierReport {
+    action::<CodeIdentifiers>(
+        &language,
+        code.as_bytes().to_vec(),
+        std::path::Path::new(""),
+        None,
+        (),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{JavascriptParser, ParserEngineRust};
+
+    use super::*;
+
+    #[test]
+    fn words() {
+        assert_eq!(
+            identifier_words("parseHTTPRequest2"),
+            ["parse", "http", "request", "2"]
+        );
+        assert_eq!(identifier_words("__init__"), ["init"]);
+        assert_eq!(
+            identifier_words("MAX_RETRY_COUNT"),
+            ["max", "retry", "count"]
+        );
+    }
+
+    #[test]
+    fn conventions() {
+        assert!(NamingConvention::SnakeCase.matches("_unused_value"));
+        assert!(!NamingConvention::SnakeCase.matches("unusedValue"));
+        assert!(NamingConvention::CamelCase.matches("unusedValue"));
+        assert!(NamingConvention::PascalCase.matches("HttpClient"));
+        assert!(NamingConvention::ScreamingSnakeCase.matches("MAX_SIZE"));
+    }
+
+    #[test]
+    fn rust_identifiers() {
+        let code = "struct Parser;\n\nfn parse_line(line: &str) -> usize {\n    let tmpVal = line.len();\n    tmpVal\n}\n";
+        let path = Path::new("foo.rs");
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), path, None);
+        let report = identifier_quality(&parser);
+
+        // `Parser`, `parse_line`, `line`, `tmpVal` and `len`
+        assert_eq!(report.file.identifiers, 5);
+        let function = &report.functions[0];
+        assert_eq!(function.name.as_deref(), Some("parse_line"));
+        // Only `tmpVal` isn't in `snake_case`
+        assert_eq!(function.quality.identifiers, 4);
+        assert_eq!(function.quality.convention_consistency, 0.75);
+    }
+
+    #[test]
+    fn javascript_identifiers() {
+        let good = code_identifier_quality(
+            "function loadUser(userId) {\n    return fetchUser(userId);\n}\n",
+            LANG::Javascript,
+        );
+        let bad = code_identifier_quality(
+            "function ld_usr(u_id) {\n    return ftch_usr(u_id);\n}\n",
+            LANG::Javascript,
+        );
+        assert_eq!(good.file.convention_consistency, 1.);
+        assert_eq!(bad.file.convention_consistency, 0.);
+        assert!(good.file.dictionary_ratio > bad.file.dictionary_ratio);
+        assert!(good.file.abbreviation_density < bad.file.abbreviation_density);
+        assert!(good.file.score > bad.file.score);
+
+        let path = Path::new("foo.js");
+        let parser = JavascriptParser::new(b"class userStore {}\n".to_vec(), path, None);
+        assert_eq!(identifier_quality(&parser).file.convention_consistency, 0.);
+    }
+}
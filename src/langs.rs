@@ -35,7 +35,7 @@ mk_langs!(
         JavascriptCode,
         JavascriptParser,
         tree_sitter_javascript,
-        [],
+        [js, jsm, mjs, jsx],
         []
     ),
     (
@@ -0,0 +1,210 @@
+use std::{cmp::Reverse, collections::BTreeMap, path::Path};
+
+use serde::Serialize;
+
+use crate::code_analyzer::FileAnalysis;
+
+/// The statistics of the files of a language.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct LanguageStats {
+    /// The number of files
+    pub files: usize,
+    /// The size of the files, in bytes
+    pub bytes: usize,
+    /// The number of source lines
+    pub sloc: f64,
+    /// The share of the bytes of all the files, as a percentage
+    pub bytes_percent: f64,
+    /// The share of the source lines of all the files, as a percentage
+    pub sloc_percent: f64,
+    /// Whether the source lines of some files were estimated, as their
+    /// language is unsupported
+    pub heuristic: bool,
+}
+
+/// The breakdown of the files of a repository by language, like the one
+/// of `linguist`.
+///
+/// The files are added as they are analyzed, and the breakdowns of
+/// several walkers are merged.
+///
+/// # Examples
+///
+/// ```
+/// use singularity_code_analysis::{LanguageBreakdown, LANG};
+///
+/// let mut breakdown = LanguageBreakdown::default();
+/// breakdown.add(LANG::Rust.get_name(), 300, 30.);
+/// breakdown.add(LANG::Python.get_name(), 100, 10.);
+///
+/// assert_eq!(breakdown.languages["rust"].sloc_percent, 75.);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct LanguageBreakdown {
+    /// The number of files
+    pub files: usize,
+    /// The size of the files, in bytes
+    pub bytes: usize,
+    /// The number of source lines
+    pub sloc: f64,
+    /// The statistics of each language, by name
+    pub languages: BTreeMap<String, LanguageStats>,
+}
+
+// The language of the files in an unsupported language without extension
+const OTHER: &str = "other";
+
+impl LanguageBreakdown {
+    /// Returns `true` if no file was added.
+    pub fn is_empty(&self) -> bool {
+        self.files == 0
+    }
+
+    /// Adds a file in a language, with its size and its source lines.
+    pub fn add(&mut self, language: &str, bytes: usize, sloc: f64) {
+        self.add_stats(
+            language,
+            &LanguageStats {
+                files: 1,
+                bytes,
+                sloc,
+                ..Default::default()
+            },
+        );
+        self.update_percents();
+    }
+
+    /// Adds an analyzed file, with its size.
+    ///
+    /// The files in an unsupported language are named after their
+    /// extension, and their source lines are the estimated ones.
+    pub fn add_analysis(&mut self, path: &Path, bytes: usize, analysis: &FileAnalysis) {
+        match analysis {
+            FileAnalysis::Parsed(result) => self.add(
+                result.language.get_name(),
+                bytes,
+                result.metrics().loc.sloc(),
+            ),
+            FileAnalysis::Heuristic(metrics) => {
+                let language = path.extension().map_or(OTHER.to_string(), |ext| {
+                    ext.to_string_lossy().to_lowercase()
+                });
+                self.add_stats(
+                    &language,
+                    &LanguageStats {
+                        files: 1,
+                        bytes,
+                        sloc: metrics.sloc as f64,
+                        heuristic: true,
+                        ..Default::default()
+                    },
+                );
+                self.update_percents();
+            }
+        }
+    }
+
+    /// Adds the files of another breakdown.
+    pub fn merge(&mut self, other: &Self) {
+        for (language, stats) in &other.languages {
+            self.add_stats(language, stats);
+        }
+        self.update_percents();
+    }
+
+    /// Returns the languages by decreasing size, as `linguist` lists
+    /// them.
+    pub fn by_size(&self) -> Vec<(&str, &LanguageStats)> {
+        let mut languages = self
+            .languages
+            .iter()
+            .map(|(language, stats)| (language.as_str(), stats))
+            .collect::<Vec<_>>();
+        languages.sort_by_key(|(_, stats)| Reverse(stats.bytes));
+        languages
+    }
+
+    // Scales the counts, which keeps the shares
+    pub(crate) fn scale(&mut self, factor: f64) {
+        let scale = |count: usize| (count as f64 * factor).round() as usize;
+        self.files = scale(self.files);
+        self.bytes = scale(self.bytes);
+        self.sloc *= factor;
+        for stats in self.languages.values_mut() {
+            stats.files = scale(stats.files);
+            stats.bytes = scale(stats.bytes);
+            stats.sloc *= factor;
+        }
+    }
+
+    fn add_stats(&mut self, language: &str, other: &LanguageStats) {
+        self.files += other.files;
+        self.bytes += other.bytes;
+        self.sloc += other.sloc;
+        let stats = self.languages.entry(language.to_string()).or_default();
+        stats.files += other.files;
+        stats.bytes += other.bytes;
+        stats.sloc += other.sloc;
+        stats.heuristic |= other.heuristic;
+    }
+
+    fn update_percents(&mut self) {
+        let percent = |part: f64, total: f64| {
+            if total > 0. {
+                part * 100. / total
+            } else {
+                0.
+            }
+        };
+        for stats in self.languages.values_mut() {
+            stats.bytes_percent = percent(stats.bytes as f64, self.bytes as f64);
+            stats.sloc_percent = percent(stats.sloc, self.sloc);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SingularityCodeAnalyzer;
+
+    use super::*;
+
+    #[test]
+    fn languages() {
+        let analyzer = SingularityCodeAnalyzer::new();
+        let mut breakdown = LanguageBreakdown::default();
+        for (path, code) in [
+            ("a.rs", "fn a() {}\n\nfn b() {}\n"),
+            ("b.rs", "fn c() {}\n"),
+            ("build.zig", "// Build\nfn main() {}\n"),
+        ] {
+            let path = Path::new(path);
+            let analysis = analyzer.analyze_contents_or_estimate(path, code).unwrap();
+            breakdown.add_analysis(path, code.len(), &analysis);
+        }
+
+        let mut other = LanguageBreakdown::default();
+        other.add("python", 10, 1.);
+        breakdown.merge(&other);
+
+        assert_eq!(breakdown.files, 4);
+        let rust = &breakdown.languages["rust"];
+        assert_eq!((rust.files, rust.bytes, rust.heuristic), (2, 31, false));
+        assert_eq!(rust.sloc_percent, rust.sloc * 100. / breakdown.sloc);
+        let zig = &breakdown.languages["zig"];
+        assert_eq!((zig.sloc, zig.heuristic), (1., true));
+
+        let sizes = breakdown
+            .by_size()
+            .into_iter()
+            .map(|(language, _)| language)
+            .collect::<Vec<_>>();
+        assert_eq!(sizes, ["rust", "zig", "python"]);
+        let percents = breakdown
+            .languages
+            .values()
+            .map(|stats| stats.bytes_percent)
+            .sum::<f64>();
+        assert!((percents - 100.).abs() < 1e-9);
+    }
+}
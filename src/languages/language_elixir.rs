@@ -65,6 +65,61 @@ pub enum Elixir {
     MapContent = 234,
 }
 
+// The names of the node kinds of the `Elixir` enum in its grammar, by id,
+// to check them against the grammar the crate is built with
+pub const ELIXIR_KINDS: &[(&str, u16)] = &[
+    ("end", 0),
+    // Literals
+    ("alias", 7),
+    ("integer", 8),
+    ("float", 9),
+    ("char", 10),
+    ("atom", 14),
+    ("comment", 97),
+    ("quoted_content", 98),
+    // Structure
+    ("source", 124),
+    ("block", 127),
+    ("identifier", 128),
+    ("boolean", 129),
+    ("nil", 130),
+    ("quoted_atom", 132),
+    // String types
+    ("string", 153),
+    ("charlist", 154),
+    ("interpolation", 155),
+    ("sigil", 156),
+    // Collections
+    ("keywords", 157),
+    ("pair", 159),
+    ("quoted_keyword", 161),
+    ("list", 162),
+    ("tuple", 163),
+    ("bitstring", 164),
+    ("map", 165),
+    ("struct", 166),
+    // Operators
+    ("unary_operator", 169),
+    ("binary_operator", 171),
+    ("operator_identifier", 172),
+    ("dot", 173),
+    // Function calls and definitions
+    ("call", 174),
+    ("arguments", 186),
+    ("do_block", 190),
+    ("after_block", 191),
+    ("rescue_block", 192),
+    ("catch_block", 193),
+    ("else_block", 194),
+    ("access_call", 195),
+    // Control flow
+    ("stab_clause", 196),
+    ("body", 202),
+    ("anonymous_function", 203),
+    // Map content
+    ("map_content", 234),
+];
+
 impl From<u16> for Elixir {
     fn from(value: u16) -> Self {
         num::FromPrimitive::from_u16(value).unwrap_or(Elixir::End)
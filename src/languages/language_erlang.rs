@@ -36,7 +36,7 @@ pub enum Erlang {
     TypeSig = 193,
     AnnType = 195,
     FunType = 198,
-    RangeType = 199,
+    RangeType = 200,
 
     // Functions
     FunDecl = 191,
@@ -101,6 +101,93 @@ pub enum Erlang {
     VarArgs = 297,
 }
 
+// The names of the node kinds of the `Erlang` enum in its grammar, by id,
+// to check them against the grammar the crate is built with
+pub const ERLANG_KINDS: &[(&str, u16)] = &[
+    ("end", 0),
+    // Literals
+    ("atom", 1),
+    ("var", 130),
+    ("integer", 131),
+    ("float", 132),
+    ("char", 136),
+    ("comment", 137),
+    // File structure
+    ("source_file", 141),
+    // Attributes
+    ("module_attribute", 158),
+    ("behaviour_attribute", 159),
+    ("export_attribute", 160),
+    ("import_attribute", 161),
+    ("optional_callbacks_attribute", 162),
+    ("fa", 163),
+    ("export_type_attribute", 164),
+    // Type system
+    ("type_alias", 178),
+    ("record_decl", 183),
+    ("spec", 184),
+    ("callback", 185),
+    ("type_name", 182),
+    ("type_sig", 193),
+    ("ann_type", 195),
+    ("fun_type", 198),
+    ("range_type", 200),
+    // Functions
+    ("fun_decl", 191),
+    ("function_clause", 202),
+    ("clause_body", 204),
+    ("call", 254),
+    ("internal_fun", 263),
+    ("external_fun", 264),
+    ("anonymous_fun", 265),
+    ("arity", 268),
+    ("fun_clause", 270),
+    // Expressions
+    ("catch_expr", 206),
+    ("match_expr", 207),
+    ("cond_match_expr", 208),
+    ("binary_op_expr", 209),
+    ("unary_op_expr", 210),
+    ("remote", 212),
+    ("paren_expr", 214),
+    ("block_expr", 215),
+    // Collections
+    ("list", 216),
+    ("binary", 217),
+    ("bin_element", 218),
+    ("tuple", 237),
+    ("map_expr", 239),
+    ("map_field", 241),
+    // Records
+    ("record_expr", 246),
+    ("record_name", 247),
+    ("record_field", 251),
+    ("record_field_expr", 244),
+    ("record_update_expr", 245),
+    // Control flow
+    ("if_expr", 255),
+    ("if_clause", 256),
+    ("case_expr", 257),
+    ("cr_clause", 260),
+    ("receive_expr", 261),
+    ("receive_after", 262),
+    ("try_expr", 271),
+    ("try_after", 273),
+    ("catch_clause", 274),
+    // Comprehensions
+    ("list_comprehension", 226),
+    ("binary_comprehension", 227),
+    ("map_comprehension", 228),
+    ("generator", 232),
+    ("b_generator", 233),
+    // Guard
+    ("guard", 298),
+    ("guard_clause", 299),
+    // Arguments
+    ("expr_args", 296),
+    ("var_args", 297),
+];
+
 impl From<u16> for Erlang {
     fn from(value: u16) -> Self {
         num::FromPrimitive::from_u16(value).unwrap_or(Erlang::End)
@@ -128,6 +128,114 @@ pub enum Gleam {
     Discard = 237, // underscore _
 }
 
+// The names of the node kinds of the `Gleam` enum in its grammar, by id,
+// to check them against the grammar the crate is built with
+pub const GLEAM_KINDS: &[(&str, u16)] = &[
+    ("end", 0),
+    // Comments
+    ("module_comment", 1),
+    ("statement_comment", 2),
+    ("comment", 3),
+    // Modifiers
+    ("visibility_modifier", 63),
+    ("opacity_modifier", 64),
+    // Literals
+    ("float", 69),
+    ("quoted_content", 95),
+    ("integer", 220),
+    // File structure
+    ("source_file", 96),
+    // Imports and modules
+    ("import", 103),
+    ("module", 104),
+    ("unqualified_imports", 105),
+    ("unqualified_import", 106),
+    // Constants and attributes
+    ("constant", 107),
+    ("attribute", 100),
+    ("attribute_value", 102),
+    // Collections
+    ("tuple", 109),
+    ("list", 110),
+    ("bit_string", 111),
+    ("bit_string_segment", 112),
+    ("bit_string_segment_options", 113),
+    ("bit_string_segment_option", 116),
+    // Records
+    ("record", 117),
+    ("arguments", 118),
+    ("argument", 119),
+    ("field_access", 120),
+    // Type system
+    ("tuple_type", 123),
+    ("function_type", 124),
+    ("function_parameter_types", 125),
+    ("type", 126),
+    ("type_arguments", 127),
+    ("type_argument", 128),
+    ("type_identifier", 238),
+    ("type_definition", 212),
+    ("data_constructors", 213),
+    ("data_constructor", 214),
+    ("type_alias", 217),
+    // External functions
+    ("external_type", 129),
+    ("external_function", 130),
+    ("external_function_body", 133),
+    // Functions
+    ("function", 134),
+    ("function_parameters", 135),
+    ("function_parameter", 136),
+    ("function_body", 274),
+    ("anonymous_function", 159),
+    // Expressions
+    ("binary_expression", 144),
+    ("todo", 147),
+    ("panic", 148),
+    ("pipeline_echo", 149),
+    ("echo", 150),
+    ("tuple_access", 173),
+    ("boolean_negation", 180),
+    ("integer_negation", 181),
+    ("hole", 193),
+    ("function_call", 194),
+    // Control flow
+    ("block", 162),
+    ("case", 163),
+    ("case_subjects", 164),
+    ("case_clauses", 165),
+    ("case_clause", 166),
+    ("case_clause_patterns", 167),
+    ("case_clause_pattern", 168),
+    ("case_clause_guard", 169),
+    // Let bindings
+    ("let", 175),
+    ("let_assert", 174),
+    ("assert", 179),
+    ("use", 176),
+    ("use_assignments", 177),
+    ("use_assignment", 178),
+    // Record operations
+    ("record_update", 183),
+    ("record_update_arguments", 184),
+    ("record_update_argument", 185),
+    // Patterns
+    ("record_pattern", 198),
+    ("record_pattern_arguments", 199),
+    ("record_pattern_argument", 200),
+    ("pattern_spread", 201),
+    ("tuple_pattern", 202),
+    ("bit_string_pattern", 203),
+    ("list_pattern", 210),
+    ("list_pattern_tail", 211),
+    // Strings
+    ("string", 218),
+    ("escape_sequence", 219),
+    // Special values
+    ("identifier", 235),
+    ("discard", 237),
+];
+
 impl From<u16> for Gleam {
     fn from(value: u16) -> Self {
         num::FromPrimitive::from_u16(value).unwrap_or(Gleam::End)
@@ -1,5 +1,5 @@
 // Lua language support - based on tree-sitter-lua 0.2.0
-// Minimal enum for RCA metrics support
+// Node types extracted from actual tree-sitter grammar
 
 use num_derive::FromPrimitive;
 
@@ -7,50 +7,147 @@ use num_derive::FromPrimitive;
 pub enum Lua {
     End = 0,
 
+    // Literals
+    Identifier = 1,
+    HashBangLine = 2,
+    Nil = 27,
+    False = 28,
+    True = 29,
+    Number = 30,
+    EscapeSequence = 35,
+    VarargExpression = 36,
+
     // Comments
-    Comment = 1,
+    CommentContent = 65,
+    CommentContent2 = 67,
+    Comment = 128,
+
+    // Strings
+    StringContent = 70,
+    String = 105,
 
     // Basic structure
-    Program = 2,
+    Chunk = 72,
+    Block = 73,
+
+    // Statements
+    BreakStatement = 8,
+    ReturnStatement = 75,
+    EmptyStatement = 76,
+    AssignmentStatement = 77,
+    VariableList = 78,
+    ExpressionList = 79,
+    LabelStatement = 80,
+    GotoStatement = 81,
+    DoStatement = 82,
+    WhileStatement = 83,
+    RepeatStatement = 84,
+    IfStatement = 85,
+    ElseifStatement = 86,
+    ElseStatement = 87,
+    ForStatement = 88,
+    ForGenericClause = 89,
+    ForNumericClause = 90,
+    VariableDeclaration = 98,
+    AssignmentStatement2 = 99,
+    VariableList2 = 100,
+    VariableList3 = 101,
+    Attribute = 102,
+    ExpressionList2 = 103,
 
     // Functions
-    FunctionDeclaration = 3,
-    FunctionDefinition = 4,
-    Function = 5,
+    FunctionDeclaration = 92,
+    FunctionDeclaration2 = 93,
+    FunctionDefinition = 110,
+    Parameters = 112,
 
-    // Control flow
-    IfStatement = 10,
-    WhileStatement = 11,
-    RepeatStatement = 12,
-    ForStatement = 13,
+    // Calls and indexing
+    DotIndexExpression = 96,
+    MethodIndexExpression = 97,
+    BracketIndexExpression = 116,
+    DotIndexExpression2 = 117,
+    FunctionCall = 118,
+    MethodIndexExpression2 = 119,
+    Arguments = 120,
 
-    // Variables and assignments
-    Assignment = 20,
-    LocalVariable = 21,
-    Variable = 22,
+    // Expressions
+    ParenthesizedExpression = 121,
+    BinaryExpression = 126,
+    UnaryExpression = 127,
 
-    // Operators
-    BinaryExpression = 30,
-    UnaryExpression = 31,
+    // Tables
+    TableConstructor = 122,
+    Field = 125,
+}
 
+// The names of the node kinds of the `Lua` enum in its grammar, by id,
+// to check them against the grammar the crate is built with
+pub const LUA_KINDS: &[(&str, u16)] = &[
+    ("end", 0),
     // Literals
-    String = 40,
-    Number = 41,
-    True = 42,
-    False = 43,
-    Nil = 44,
-
+    ("identifier", 1),
+    ("hash_bang_line", 2),
+    ("nil", 27),
+    ("false", 28),
+    ("true", 29),
+    ("number", 30),
+    ("escape_sequence", 35),
+    ("vararg_expression", 36),
+    // Comments
+    ("comment_content", 65),
+    ("comment_content", 67),
+    ("comment", 128),
+    // Strings
+    ("string_content", 70),
+    ("string", 105),
+    // Basic structure
+    ("chunk", 72),
+    ("block", 73),
+    // Statements
+    ("break_statement", 8),
+    ("return_statement", 75),
+    ("empty_statement", 76),
+    ("assignment_statement", 77),
+    ("variable_list", 78),
+    ("expression_list", 79),
+    ("label_statement", 80),
+    ("goto_statement", 81),
+    ("do_statement", 82),
+    ("while_statement", 83),
+    ("repeat_statement", 84),
+    ("if_statement", 85),
+    ("elseif_statement", 86),
+    ("else_statement", 87),
+    ("for_statement", 88),
+    ("for_generic_clause", 89),
+    ("for_numeric_clause", 90),
+    ("variable_declaration", 98),
+    ("assignment_statement", 99),
+    ("variable_list", 100),
+    ("variable_list", 101),
+    ("attribute", 102),
+    ("expression_list", 103),
+    // Functions
+    ("function_declaration", 92),
+    ("function_declaration", 93),
+    ("function_definition", 110),
+    ("parameters", 112),
+    // Calls and indexing
+    ("dot_index_expression", 96),
+    ("method_index_expression", 97),
+    ("bracket_index_expression", 116),
+    ("dot_index_expression", 117),
+    ("function_call", 118),
+    ("method_index_expression", 119),
+    ("arguments", 120),
+    // Expressions
+    ("parenthesized_expression", 121),
+    ("binary_expression", 126),
+    ("unary_expression", 127),
     // Tables
-    TableConstructor = 50,
-    Field = 51,
-
-    // Calls and identifiers
-    FunctionCall = 60,
-    Identifier = 61,
-
-    // Return
-    ReturnStatement = 70,
-}
+    ("table_constructor", 122),
+    ("field", 125),
+];
 
 impl From<u16> for Lua {
     fn from(value: u16) -> Self {
@@ -34,6 +34,9 @@
 //! - **NOM**: Number of Methods/Functions
 //! - **NEXITS**: Exit points
 //! - **NARGS**: Function arguments
+//! - **GENERICS**: Generic/template parameters and their bounds
+//! - **UNSAFETY**: `unsafe`/FFI usage (Rust), casts and pointer arithmetic (C/C++)
+//! - **EXTRAS**: Language-specific metrics (e.g. Python decorators, Go goroutines and error checks, Elixir/Erlang OTP behaviours and messages)
 //!
 //! ### AI-Powered Metrics (NEW)
 //! - **Semantic Complexity**: Language-aware complexity analysis
@@ -59,6 +62,9 @@ pub use alterator::*;
 mod node;
 pub use crate::node::*;
 
+mod position;
+pub use crate::position::*;
+
 mod metrics;
 pub use metrics::*;
 
@@ -69,7 +75,7 @@ mod languages;
 pub(crate) use languages::*;
 
 mod checker;
-pub(crate) use checker::*;
+pub use crate::checker::*;
 
 mod output;
 pub use output::*;
@@ -77,6 +83,105 @@ pub use output::*;
 mod spaces;
 pub use crate::spaces::*;
 
+mod syntax_error;
+pub use crate::syntax_error::*;
+
+mod third_party;
+pub use crate::third_party::*;
+
+mod halstead_table;
+pub use crate::halstead_table::*;
+
+mod fingerprint;
+pub use crate::fingerprint::*;
+
+mod diff;
+pub use crate::diff::*;
+
+mod suppression;
+pub use crate::suppression::*;
+
+mod security;
+pub use crate::security::*;
+
+mod secrets;
+pub use crate::secrets::*;
+
+mod regex_complexity;
+pub use crate::regex_complexity::*;
+
+mod sql_injection;
+pub use crate::sql_injection::*;
+
+mod suspicious;
+pub use crate::suspicious::*;
+
+mod unused;
+pub use crate::unused::*;
+
+mod mi_grade;
+pub use crate::mi_grade::*;
+
+mod risk;
+pub use crate::risk::*;
+
+mod trends;
+pub use crate::trends::*;
+
+mod history;
+pub use crate::history::*;
+
+mod churn;
+pub use crate::churn::*;
+
+mod authorship;
+pub use crate::authorship::*;
+
+mod commit_range;
+pub use crate::commit_range::*;
+
+mod function_history;
+pub use crate::function_history::*;
+
+mod refactoring;
+pub use crate::refactoring::*;
+
+mod similarity;
+pub use crate::similarity::*;
+
+mod embeddings;
+pub use crate::embeddings::*;
+
+mod chunking;
+pub use crate::chunking::*;
+
+mod tokens;
+pub use crate::tokens::*;
+
+mod highlight;
+pub use crate::highlight::*;
+
+mod facts;
+pub use crate::facts::*;
+
+mod pattern_learning;
+pub use crate::pattern_learning::*;
+
+mod identifiers;
+pub use crate::identifiers::*;
+
+mod packages;
+pub use crate::packages::*;
+
+mod manifest;
+pub use crate::manifest::*;
+
+mod gate;
+pub use crate::gate::*;
+
+mod debt;
+pub use crate::debt::*;
+
 mod ops;
 pub use crate::ops::*;
 
@@ -86,6 +191,12 @@ pub use crate::find::*;
 mod function;
 pub use crate::function::*;
 
+mod cfg;
+pub use crate::cfg::*;
+
+mod liveness;
+pub use crate::liveness::*;
+
 mod ast;
 pub use crate::ast::*;
 
@@ -98,15 +209,27 @@ pub use crate::count::*;
 mod preproc;
 pub use crate::preproc::*;
 
+mod mozjs_preproc;
+pub use crate::mozjs_preproc::*;
+
 mod langs;
 pub use crate::langs::*;
 
 mod tools;
 pub use crate::tools::*;
 
+mod encoding;
+pub use crate::encoding::*;
+
+mod generated;
+pub use crate::generated::*;
+
 mod concurrent_files;
 pub use crate::concurrent_files::*;
 
+#[cfg(feature = "archives")]
+mod archive;
+
 mod traits;
 pub use crate::traits::*;
 
@@ -119,6 +242,51 @@ pub use crate::parser_registry::*;
 mod code_analyzer;
 pub use crate::code_analyzer::*;
 
+mod session;
+pub use crate::session::*;
+
+mod path_base;
+pub use crate::path_base::*;
+
+mod vfs;
+pub use crate::vfs::*;
+
+mod markdown;
+pub use crate::markdown::*;
+
+mod doc_coverage;
+pub use crate::doc_coverage::*;
+
+mod metadata;
+pub use crate::metadata::*;
+
+mod grammars;
+pub use crate::grammars::*;
+
+mod heuristic;
+pub use crate::heuristic::*;
+
+mod language_breakdown;
+pub use crate::language_breakdown::*;
+
+mod sampling;
+pub use crate::sampling::*;
+
+mod dir_config;
+pub use crate::dir_config::*;
+
+mod analysis_config;
+pub use crate::analysis_config::*;
+
+mod coverage;
+pub use crate::coverage::*;
+
+mod lloc_rules;
+pub use crate::lloc_rules::*;
+
+mod profile;
+pub use crate::profile::*;
+
 mod comment_rm;
 pub use crate::comment_rm::*;
 
@@ -0,0 +1,494 @@
+use std::{collections::BTreeSet, path::Path};
+
+use serde::Serialize;
+
+use crate::{
+    cfg::{flow_graph, BlockKind, ControlFlowGraph},
+    checker::Checker,
+    langs::{action, LANG},
+    node::Node,
+    traits::*,
+    unused::{bound_names, macro_captures, parameters, DECLARATIONS},
+};
+
+/// The variables defined and read by a statement of a control-flow
+/// graph, or by the condition of a block.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct StatementFlow {
+    /// The first line of the statement
+    pub start_line: usize,
+    /// The last line of the statement
+    pub end_line: usize,
+    /// The variables assigned or declared
+    pub defs: BTreeSet<String>,
+    /// The variables read
+    pub uses: BTreeSet<String>,
+    /// The variables updated from their own value, like `x += 1`, which
+    /// are defined too, and only read when they are live after the
+    /// statement
+    pub updates: BTreeSet<String>,
+    /// The variables live before the statement
+    pub live_in: BTreeSet<String>,
+    /// The variables live after the statement
+    pub live_out: BTreeSet<String>,
+}
+
+impl StatementFlow {
+    // The variables live before the statement, from the ones live after
+    fn transfer(&self, live_out: &BTreeSet<String>) -> BTreeSet<String> {
+        let mut live = live_out
+            .iter()
+            .filter(|name| !self.defs.contains(*name))
+            .cloned()
+            .collect::<BTreeSet<_>>();
+        live.extend(self.uses.iter().cloned());
+        live.extend(self.updates.intersection(live_out).cloned());
+        live
+    }
+}
+
+/// A definition of a variable whose value is never read.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct DeadStore {
+    /// The name of the variable
+    pub name: String,
+    /// The first line of the defining statement
+    pub line: usize,
+}
+
+/// The live variables of a function: the variables whose value may be
+/// read later, before being defined again.
+///
+/// The variables are resolved by name in the whole function, the uses in
+/// the nested functions and closures included, and the statements of a
+/// `try` body may all throw to its handlers. The updates of a variable
+/// from its own value, like `count += 1`, don't make it live, so a
+/// counter which is never read is dead.
+#[derive(Clone, Debug, Serialize)]
+pub struct Liveness {
+    /// The control-flow graph of the function
+    pub graph: ControlFlowGraph,
+    /// The statements of each block, by block id, the entry defining the
+    /// parameters
+    pub statements: Vec<Vec<StatementFlow>>,
+    /// The variables live at the start of each block
+    pub live_in: Vec<BTreeSet<String>>,
+    /// The variables live at the end of each block
+    pub live_out: Vec<BTreeSet<String>>,
+    // The successors of each block, the handlers of the `try` bodies
+    // included
+    #[serde(skip)]
+    successors: Vec<Vec<usize>>,
+}
+
+impl Liveness {
+    fn new(
+        graph: ControlFlowGraph,
+        mut statements: Vec<Vec<StatementFlow>>,
+        throwing: &[(usize, usize)],
+    ) -> Self {
+        let blocks = graph.blocks.len();
+        let mut successors = vec![Vec::new(); blocks];
+        for edge in &graph.edges {
+            successors[edge.from].push(edge.to);
+        }
+        for &(from, to) in throwing {
+            successors[from].push(to);
+        }
+
+        // The sets only grow until they are stable
+        let mut live_in = vec![BTreeSet::new(); blocks];
+        let mut live_out = vec![BTreeSet::new(); blocks];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for block in (0..blocks).rev() {
+                let out = successors[block]
+                    .iter()
+                    .flat_map(|&successor| live_in[successor].iter().cloned())
+                    .collect::<BTreeSet<_>>();
+                let mut live = out.clone();
+                for statement in statements[block].iter_mut().rev() {
+                    statement.live_out = live;
+                    live = statement.transfer(&statement.live_out);
+                    statement.live_in = live.clone();
+                }
+                live_out[block] = out;
+                if live != live_in[block] {
+                    live_in[block] = live;
+                    changed = true;
+                }
+            }
+        }
+
+        Self {
+            graph,
+            statements,
+            live_in,
+            live_out,
+            successors,
+        }
+    }
+
+    // The statements of the body of the function, without the parameters
+    fn body(&self) -> impl Iterator<Item = &StatementFlow> {
+        self.statements
+            .iter()
+            .enumerate()
+            .filter(|(block, _)| *block != ControlFlowGraph::ENTRY)
+            .flat_map(|(_, statements)| statements)
+    }
+
+    /// Returns the definitions whose value is never read, in the order of
+    /// their lines, leaving out the parameters.
+    pub fn dead_stores(&self) -> Vec<DeadStore> {
+        let mut stores = self
+            .body()
+            .flat_map(|statement| {
+                statement
+                    .defs
+                    .iter()
+                    .filter(|name| !statement.live_out.contains(*name))
+                    .map(|name| DeadStore {
+                        name: name.clone(),
+                        line: statement.start_line,
+                    })
+            })
+            .collect::<Vec<_>>();
+        stores.sort_by(|a, b| a.line.cmp(&b.line).then(a.name.cmp(&b.name)));
+        stores
+    }
+
+    /// Returns the variables defined in the body of the function none of
+    /// whose values is ever read.
+    pub fn dead_variables(&self) -> BTreeSet<&str> {
+        let mut defined = BTreeSet::new();
+        let mut read = BTreeSet::new();
+        for statement in self.body() {
+            for name in &statement.defs {
+                defined.insert(name.as_str());
+                if statement.live_out.contains(name) {
+                    read.insert(name.as_str());
+                }
+            }
+        }
+        defined.difference(&read).copied().collect()
+    }
+
+    /// Returns the variables crossing the boundaries of some lines of the
+    /// function, as `(inputs, outputs)`: the variables defined before the
+    /// lines which they read, and the variables they define which are
+    /// read after them.
+    ///
+    /// The lines are expected to be a run of statements which the control
+    /// flow only enters at its start, like the ones of an extracted
+    /// function.
+    pub fn crossing_variables(
+        &self,
+        start_line: usize,
+        end_line: usize,
+    ) -> (BTreeSet<&str>, BTreeSet<&str>) {
+        let inside = |statement: &StatementFlow| {
+            start_line <= statement.start_line && statement.end_line <= end_line
+        };
+        let block_inside = |block: usize| match self.statements[block].first() {
+            Some(statement) => inside(statement),
+            None => (start_line..=end_line).contains(&self.graph.blocks[block].start_line),
+        };
+
+        let mut locals = BTreeSet::new();
+        let mut entry: Option<&StatementFlow> = None;
+        let mut uses = BTreeSet::new();
+        let mut defs = BTreeSet::new();
+        let mut live_after = BTreeSet::new();
+        for (block, statements) in self.statements.iter().enumerate() {
+            for (i, statement) in statements.iter().enumerate() {
+                locals.extend(statement.defs.iter().map(String::as_str));
+                if !inside(statement) {
+                    continue;
+                }
+                if entry.is_none_or(|entry| statement.start_line < entry.start_line) {
+                    entry = Some(statement);
+                }
+                uses.extend(statement.uses.iter().map(String::as_str));
+                uses.extend(statement.updates.iter().map(String::as_str));
+                defs.extend(statement.defs.iter().map(String::as_str));
+                match statements.get(i + 1) {
+                    Some(next) if !inside(next) => {
+                        live_after.extend(next.live_in.iter().map(String::as_str))
+                    }
+                    Some(_) => {}
+                    None => {
+                        for &successor in &self.successors[block] {
+                            if !block_inside(successor) {
+                                live_after
+                                    .extend(self.live_in[successor].iter().map(String::as_str));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let inputs = entry.map_or_else(BTreeSet::new, |entry| {
+            entry
+                .live_in
+                .iter()
+                .map(String::as_str)
+                .filter(|name| uses.contains(name) && locals.contains(name))
+                .collect()
+        });
+        let outputs = defs.intersection(&live_after).copied().collect();
+        (inputs, outputs)
+    }
+}
+
+// The declaration or the assignment defining an identifier, if any
+fn definition<'a>(node: &Node<'a>) -> Option<Node<'a>> {
+    // The patterns and lists of targets, like `a, b = ...` in Python
+    let mut target = *node;
+    while let Some(parent) = target.parent() {
+        let kind = parent.kind();
+        if kind.contains("pattern") || kind.ends_with("_list") {
+            target = parent;
+            continue;
+        }
+        let is_target = |field: &str| {
+            parent
+                .child_by_field_name(field)
+                .is_some_and(|child| child.id() == target.id())
+        };
+        let defines = match DECLARATIONS
+            .iter()
+            .find(|(declaration, _)| *declaration == kind)
+        {
+            Some((_, field)) => is_target(field),
+            None => {
+                (kind.contains("assign") || kind.contains("declar") || kind.starts_with("let"))
+                    && ["left", "pattern", "name"].into_iter().any(is_target)
+            }
+        };
+        return defines.then_some(parent);
+    }
+    None
+}
+
+// Returns `true` if an assignment computes its target from its own value,
+// like `x += 1`
+fn is_update(assignment: &Node, code: &[u8]) -> bool {
+    let kind = assignment.kind();
+    kind.contains("augmented")
+        || kind.contains("compound")
+        || assignment
+            .child_by_field_name("operator")
+            .and_then(|operator| operator.utf8_text(code))
+            .is_some_and(|operator| !matches!(operator, "=" | ":="))
+}
+
+// The variables defined and read by some nodes, the assignments in the
+// nested functions and closures being theirs
+fn statement_flow<T: ParserTrait>(nodes: &[Node], code: &[u8]) -> StatementFlow {
+    let mut flow = StatementFlow {
+        start_line: nodes
+            .iter()
+            .map(|node| node.start_row() + 1)
+            .min()
+            .unwrap_or(0),
+        end_line: nodes
+            .iter()
+            .map(|node| node.end_row() + 1)
+            .max()
+            .unwrap_or(0),
+        ..Default::default()
+    };
+    let mut stack = nodes.iter().map(|node| (*node, false)).collect::<Vec<_>>();
+    while let Some((node, nested)) = stack.pop() {
+        if matches!(node.kind(), "identifier" | "shorthand_property_identifier") {
+            let Some(name) = node.utf8_text(code).map(str::to_string) else {
+                continue;
+            };
+            let increment = node.parent().is_some_and(|parent| {
+                matches!(
+                    parent.kind(),
+                    "update_expression" | "inc_statement" | "dec_statement"
+                )
+            });
+            match definition(&node) {
+                _ if nested => flow.uses.insert(name),
+                _ if increment => flow.updates.insert(name),
+                Some(assignment) if is_update(&assignment, code) => flow.updates.insert(name),
+                Some(_) => flow.defs.insert(name),
+                None => flow.uses.insert(name),
+            };
+            continue;
+        }
+        if node.kind() == "macro_invocation" {
+            flow.uses
+                .extend(macro_captures(&node, code).into_iter().map(str::to_string));
+        }
+        stack.extend(node.children().map(|child| {
+            let nested = nested || T::Checker::is_func(&child) || T::Checker::is_closure(&child);
+            (child, nested)
+        }));
+    }
+    flow.defs.extend(flow.updates.iter().cloned());
+    flow
+}
+
+pub(crate) fn function_liveness<T: ParserTrait>(function: &Node, code: &[u8]) -> Liveness {
+    let flow = flow_graph::<T>(function, code);
+    // The nodes of a condition are evaluated together
+    let mut statements = flow
+        .nodes
+        .iter()
+        .zip(&flow.graph.blocks)
+        .map(|(nodes, block)| match block.kind {
+            BlockKind::Condition if !nodes.is_empty() => vec![statement_flow::<T>(nodes, code)],
+            _ => nodes
+                .iter()
+                .map(|node| statement_flow::<T>(std::slice::from_ref(node), code))
+                .collect(),
+        })
+        .collect::<Vec<_>>();
+    if let Some(parameters) = parameters(function) {
+        let mut names = Vec::new();
+        bound_names(parameters, &mut names);
+        statements[ControlFlowGraph::ENTRY].push(StatementFlow {
+            start_line: parameters.start_row() + 1,
+            end_line: parameters.end_row() + 1,
+            defs: names
+                .iter()
+                .filter_map(|name| name.utf8_text(code))
+                .map(str::to_string)
+                .collect(),
+            ..Default::default()
+        });
+    }
+    Liveness::new(flow.graph, statements, &flow.throwing)
+}
+
+/// Computes the live variables of each function of a code, in the order
+/// of their start.
+pub fn liveness<T: ParserTrait>(parser: &T) -> Vec<Liveness> {
+    let code = parser.get_code();
+    let mut functions = Vec::new();
+    parser.get_root().act_on_node(&mut |n| {
+        if T::Checker::is_func(n) {
+            functions.push(function_liveness::<T>(n, code));
+        }
+    });
+    functions
+}
+
+struct LiveVariables {
+    _guard: (),
+}
+
+impl Callback for LiveVariables {
+    type Res = Vec<Liveness>;
+    type Cfg = ();
+
+    fn call<T: ParserTrait>(_cfg: Self::Cfg, parser: &T) -> Self::Res {
+        liveness(parser)
+    }
+}
+
+/// Computes the live variables of each function of a code in a language.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{get_liveness, LANG};
+///
+/// let code = b"def f(a):\n    b = a\n    b = 2\n    return b\n".to_vec();
+/// let functions = get_liveness(&LANG::Python, code, Path::new("foo.py"));
+/// let dead = functions[0].dead_stores();
+/// assert_eq!((dead[0].name.as_str(), dead[0].line), ("b", 2));
+/// ```
+pub fn get_liveness(lang: &LANG, source: Vec<u8>, path: &Path) -> Vec<Liveness> {
+    action::<LiveVariables>(lang, source, path, None, ())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ParserEngineRust, PythonParser};
+
+    use super::*;
+
+    #[test]
+    fn live_variables() {
+        let code = "fn f(items: &[i32], unused: i32) -> i32 {
+    let mut total = 0;
+    let mut count = 0;
+    for item in items {
+        total += item;
+        count += 1;
+    }
+    total
+}
+";
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), Path::new("foo.rs"), None);
+        let function = &liveness(&parser)[0];
+
+        let entry = &function.live_out[ControlFlowGraph::ENTRY];
+        assert_eq!(entry.iter().collect::<Vec<_>>(), ["items"]);
+        assert_eq!(
+            function.dead_variables().into_iter().collect::<Vec<_>>(),
+            ["count"]
+        );
+        let dead = function.dead_stores();
+        assert_eq!(
+            dead.iter()
+                .map(|store| (store.name.as_str(), store.line))
+                .collect::<Vec<_>>(),
+            [("count", 3), ("count", 6)]
+        );
+
+        let (inputs, outputs) = function.crossing_variables(4, 7);
+        assert_eq!(inputs.into_iter().collect::<Vec<_>>(), ["items", "total"]);
+        assert_eq!(outputs.into_iter().collect::<Vec<_>>(), ["total"]);
+    }
+
+    #[test]
+    fn exceptions() {
+        let code = "def g(path):
+    status = 0
+    try:
+        if path:
+            status = 1
+        status = read(path)
+    except OSError:
+        return status
+    return status
+";
+        let parser = PythonParser::new(code.as_bytes().to_vec(), Path::new("foo.py"), None);
+        let function = &liveness(&parser)[0];
+
+        // The handler reads `status = 1` when `read` throws, though the
+        // graph only throws from the start of the `try`
+        assert_eq!(function.graph.successors(5).collect::<Vec<_>>(), [6]);
+        assert!(function.dead_stores().is_empty());
+    }
+
+    #[test]
+    fn break_in_match() {
+        let code = "fn f(x: u8) -> u8 {
+    let n = 3;
+    loop {
+        match x {
+            _ => break,
+        }
+    }
+    n
+}
+";
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), Path::new("foo.rs"), None);
+        let function = &liveness(&parser)[0];
+
+        // The `break` leaves the loop, so `n` is read after it
+        assert!(function.dead_variables().is_empty());
+        assert!(function.dead_stores().is_empty());
+    }
+}
@@ -0,0 +1,426 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::RwLock;
+
+use serde::Serialize;
+
+use crate::langs::LANG;
+use crate::languages::{Cpp, Java, Javascript, Python, Rust, Tsx, Typescript};
+use crate::node::Node;
+
+static OVERRIDES: RwLock<Option<LlocOverrides>> = RwLock::new(None);
+
+/// A rule of the `Lloc` metric: the nodes of a kind are statements.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct LlocRule {
+    /// The node kind, or `*` for any node meeting the condition
+    pub kind: String,
+    /// When a node of the kind is a statement, if not always
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub condition: Option<&'static str>,
+    /// Whether the rule was added by the [`LlocOverrides`]
+    pub overridden: bool,
+}
+
+impl LlocRule {
+    fn builtin(kind: &str, condition: Option<&'static str>) -> Self {
+        Self {
+            kind: kind.to_string(),
+            condition,
+            overridden: false,
+        }
+    }
+}
+
+fn statements<K: Into<&'static str>>(kinds: impl IntoIterator<Item = K>) -> Vec<LlocRule> {
+    let mut rules: Vec<LlocRule> = Vec::new();
+    for kind in kinds {
+        let kind = kind.into();
+        // The kinds with several ids have a variant for each of them
+        if !rules.iter().any(|rule| rule.kind == kind) {
+            rules.push(LlocRule::builtin(kind, None));
+        }
+    }
+    rules
+}
+
+// The rules of the `Loc` implementations in `metrics/loc.rs`
+fn builtin_rules(language: LANG) -> Vec<LlocRule> {
+    match language {
+        LANG::Python => {
+            use Python::*;
+            statements([
+                Statement,
+                SimpleStatements,
+                ImportStatement,
+                FutureImportStatement,
+                ImportFromStatement,
+                PrintStatement,
+                AssertStatement,
+                ReturnStatement,
+                DeleteStatement,
+                RaiseStatement,
+                PassStatement,
+                BreakStatement,
+                ContinueStatement,
+                IfStatement,
+                ForStatement,
+                WhileStatement,
+                TryStatement,
+                WithStatement,
+                GlobalStatement,
+                NonlocalStatement,
+                ExecStatement,
+                ExpressionStatement,
+            ])
+        }
+        LANG::Javascript => {
+            use Javascript::*;
+            statements([
+                ExpressionStatement,
+                ExportStatement,
+                ImportStatement,
+                StatementBlock,
+                IfStatement,
+                SwitchStatement,
+                ForStatement,
+                ForInStatement,
+                WhileStatement,
+                DoStatement,
+                TryStatement,
+                WithStatement,
+                BreakStatement,
+                ContinueStatement,
+                DebuggerStatement,
+                ReturnStatement,
+                ThrowStatement,
+                EmptyStatement,
+                StatementIdentifier,
+            ])
+        }
+        LANG::Typescript => {
+            use Typescript::*;
+            statements([
+                ExpressionStatement,
+                ExportStatement,
+                ImportStatement,
+                StatementBlock,
+                IfStatement,
+                SwitchStatement,
+                ForStatement,
+                ForInStatement,
+                WhileStatement,
+                DoStatement,
+                TryStatement,
+                WithStatement,
+                BreakStatement,
+                ContinueStatement,
+                DebuggerStatement,
+                ReturnStatement,
+                ThrowStatement,
+                EmptyStatement,
+                StatementIdentifier,
+            ])
+        }
+        LANG::Tsx => {
+            use Tsx::*;
+            statements([
+                ExpressionStatement,
+                ExportStatement,
+                ImportStatement,
+                StatementBlock,
+                IfStatement,
+                SwitchStatement,
+                ForStatement,
+                ForInStatement,
+                WhileStatement,
+                DoStatement,
+                TryStatement,
+                WithStatement,
+                BreakStatement,
+                ContinueStatement,
+                DebuggerStatement,
+                ReturnStatement,
+                ThrowStatement,
+                EmptyStatement,
+                StatementIdentifier,
+            ])
+        }
+        LANG::Rust => {
+            use Rust::*;
+            statements([
+                Statement,
+                EmptyStatement,
+                ExpressionStatement,
+                LetDeclaration,
+                AssignmentExpression,
+                CompoundAssignmentExpr,
+            ])
+        }
+        LANG::Cpp => {
+            use Cpp::*;
+            let mut rules = statements([
+                WhileStatement,
+                SwitchStatement,
+                CaseStatement,
+                IfStatement,
+                ForStatement,
+                ReturnStatement,
+                BreakStatement,
+                ContinueStatement,
+                GotoStatement,
+                ThrowStatement,
+                TryStatement,
+                TryStatement2,
+                ExpressionStatement,
+                ExpressionStatement2,
+                LabeledStatement,
+                StatementIdentifier,
+            ]);
+            rules.push(LlocRule::builtin(
+                Declaration.into(),
+                Some("outside of the headers of the `while`, `for` and `if` statements"),
+            ));
+            rules
+        }
+        LANG::Java => {
+            use Java::*;
+            let mut rules = statements([
+                AssertStatement,
+                BreakStatement,
+                ContinueStatement,
+                DoStatement,
+                EnhancedForStatement,
+                ExpressionStatement,
+                ForStatement,
+                IfStatement,
+                ReturnStatement,
+                SwitchExpression,
+                ThrowStatement,
+                TryStatement,
+                WhileStatement,
+            ]);
+            rules.push(LlocRule::builtin(
+                LocalVariableDeclaration.into(),
+                Some("outside of the headers of the `for` statements"),
+            ));
+            rules
+        }
+        LANG::Gleam => {
+            let mut rules = [
+                "import",
+                "constant",
+                "type_definition",
+                "type_alias",
+                "external_type",
+                "let",
+                "let_assert",
+                "use",
+            ]
+            .map(|kind| LlocRule::builtin(kind, None))
+            .to_vec();
+            rules.push(LlocRule::builtin(
+                "*",
+                Some("named and directly in a function body or a block"),
+            ));
+            rules
+        }
+        LANG::Lua => {
+            let mut rules = [
+                "return_statement",
+                "break_statement",
+                "empty_statement",
+                "label_statement",
+                "goto_statement",
+                "do_statement",
+                "while_statement",
+                "repeat_statement",
+                "if_statement",
+                "for_statement",
+                "variable_declaration",
+                "function_declaration",
+            ]
+            .map(|kind| LlocRule::builtin(kind, None))
+            .to_vec();
+            for kind in ["assignment_statement", "function_call"] {
+                rules.push(LlocRule::builtin(
+                    kind,
+                    Some("directly in a chunk or a block"),
+                ));
+            }
+            rules
+        }
+        // The `Lloc` metric isn't computed for these languages
+        LANG::Elixir | LANG::Erlang | LANG::Go | LANG::Csharp => Vec::new(),
+    }
+}
+
+/// Returns the rules of the `Lloc` metric of a language, i.e. the node
+/// kinds counted as statements, with the overrides set by
+/// [`set_lloc_overrides`].
+///
+/// # Examples
+///
+/// ```
+/// use singularity_code_analysis::{lloc_rules, LANG};
+///
+/// let rules = lloc_rules(LANG::Rust);
+/// assert!(rules.iter().any(|rule| rule.kind == "let_declaration"));
+/// ```
+pub fn lloc_rules(language: LANG) -> Vec<LlocRule> {
+    let mut rules = builtin_rules(language);
+    let overrides = OVERRIDES.read().unwrap_or_else(|e| e.into_inner());
+    if let Some(kinds) = overrides
+        .as_ref()
+        .and_then(|overrides| overrides.languages.get(&language))
+    {
+        rules.retain(|rule| !kinds.removed.contains(&rule.kind));
+        let mut added = kinds.added.iter().collect::<Vec<_>>();
+        added.sort();
+        for kind in added {
+            rules.retain(|rule| rule.kind != *kind);
+            rules.push(LlocRule {
+                kind: kind.clone(),
+                condition: None,
+                overridden: true,
+            });
+        }
+    }
+    rules
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct LlocKinds {
+    added: HashSet<String>,
+    removed: HashSet<String>,
+}
+
+/// The node kinds counted as statements by the `Lloc` metric, or no
+/// longer counted, in addition to the built-in rules, for the counting
+/// conventions of an organization.
+///
+/// # Examples
+///
+/// ```
+/// use singularity_code_analysis::{set_lloc_overrides, LlocOverrides, LANG};
+///
+/// // Count the Rust macro invocations, but not the assignments
+/// set_lloc_overrides(
+///     LlocOverrides::default()
+///         .add(LANG::Rust, "macro_invocation")
+///         .remove(LANG::Rust, "assignment_expression"),
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LlocOverrides {
+    languages: HashMap<LANG, LlocKinds>,
+}
+
+impl LlocOverrides {
+    /// Counts the nodes of a kind as statements.
+    pub fn add(mut self, language: LANG, kind: &str) -> Self {
+        let kinds = self.languages.entry(language).or_default();
+        kinds.removed.remove(kind);
+        kinds.added.insert(kind.to_string());
+        self
+    }
+
+    /// Stops counting the nodes of a kind as statements.
+    pub fn remove(mut self, language: LANG, kind: &str) -> Self {
+        let kinds = self.languages.entry(language).or_default();
+        kinds.added.remove(kind);
+        kinds.removed.insert(kind.to_string());
+        self
+    }
+
+    // Returns `true` if a node is a statement, given whether the
+    // built-in rules count it
+    fn is_statement(&self, language: LANG, kind: &str, builtin: bool) -> bool {
+        match self.languages.get(&language) {
+            Some(kinds) if kinds.added.contains(kind) => true,
+            Some(kinds) if kinds.removed.contains(kind) => false,
+            _ => builtin,
+        }
+    }
+}
+
+/// Overrides the built-in rules of the `Lloc` metric at runtime.
+pub fn set_lloc_overrides(overrides: LlocOverrides) {
+    *OVERRIDES.write().unwrap_or_else(|e| e.into_inner()) = Some(overrides);
+}
+
+/// Removes the overrides set by [`set_lloc_overrides`].
+pub fn reset_lloc_overrides() {
+    *OVERRIDES.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Returns `true` if a node is a statement according to the overrides,
+/// given whether the built-in rules count it.
+pub(crate) fn is_lloc_statement(language: LANG, node: &Node, builtin: bool) -> bool {
+    let overrides = OVERRIDES.read().unwrap_or_else(|e| e.into_inner());
+    overrides.as_ref().map_or(builtin, |overrides| {
+        overrides.is_statement(language, node.kind(), builtin)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{metrics, ErlangParser, ParserTrait};
+
+    use super::*;
+
+    #[test]
+    fn builtin_rules() {
+        let rules = lloc_rules(LANG::Cpp);
+        let declaration = rules
+            .iter()
+            .find(|rule| rule.kind == "declaration")
+            .unwrap();
+        assert!(declaration.condition.is_some());
+        assert_eq!(
+            rules
+                .iter()
+                .filter(|rule| rule.kind == "expression_statement")
+                .count(),
+            1
+        );
+        assert!(lloc_rules(LANG::Go).is_empty());
+    }
+
+    #[test]
+    fn overridden_kinds() {
+        let overrides = LlocOverrides::default()
+            .add(LANG::Rust, "macro_invocation")
+            .remove(LANG::Rust, "assignment_expression");
+        assert!(overrides.is_statement(LANG::Rust, "macro_invocation", false));
+        assert!(!overrides.is_statement(LANG::Rust, "assignment_expression", true));
+        assert!(overrides.is_statement(LANG::Rust, "let_declaration", true));
+        assert!(!overrides.is_statement(LANG::Python, "macro_invocation", false));
+
+        let overrides = overrides.add(LANG::Rust, "assignment_expression");
+        assert!(overrides.is_statement(LANG::Rust, "assignment_expression", false));
+    }
+
+    #[test]
+    fn user_overrides() {
+        let path = Path::new("foo.erl");
+        let lloc = || {
+            let parser = ErlangParser::new(b"f() -> ok.\ng() -> ok.\n".to_vec(), path, None);
+            metrics(&parser, path).unwrap().metrics.loc.lloc()
+        };
+
+        // The other tests don't compute the `Lloc` metric of Erlang, which
+        // has no built-in rules, so they aren't affected by the overrides
+        assert_eq!(lloc(), 0.);
+        set_lloc_overrides(LlocOverrides::default().add(LANG::Erlang, "function_clause"));
+        let user = lloc();
+        let rules = lloc_rules(LANG::Erlang);
+        reset_lloc_overrides();
+
+        assert_eq!(user, 2.);
+        assert_eq!(rules.len(), 1);
+        assert!(rules[0].overridden);
+        assert_eq!(lloc(), 0.);
+    }
+}
@@ -133,7 +133,7 @@ macro_rules! mk_action {
         /// ```
         /// use std::path::PathBuf;
         ///
-        /// use rust_code_analysis::{action, Callback, LANG, Metrics, MetricsCfg};
+        /// use rust_code_analysis::{action, Callback, LANG, Metrics, MetricsCfg, OffsetEncoding};
         ///
         /// let source_code = "int a = 42;";
         /// let language = LANG::Cpp;
@@ -145,6 +145,11 @@ macro_rules! mk_action {
         /// // Configuration options used by the function which computes the metrics
         /// let cfg = MetricsCfg {
         ///     path,
+        ///     offset_encoding: OffsetEncoding::Utf8,
+        ///     snippet_context: None,
+        ///     mi_bands: None,
+        ///     macro_spaces: false,
+        ///     path_base: None,
         /// };
         ///
         /// action::<Metrics>(&language, source_as_vec, &cfg.path.clone(), None, cfg);
@@ -317,6 +322,23 @@ macro_rules! mk_langs {
     };
 }
 
+// Enters a `tracing` span lasting until the end of the current scope,
+// when the `tracing` feature is enabled
+macro_rules! trace_span {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!($($arg)*).entered();
+    };
+}
+
+// Emits a `tracing` event, when the `tracing` feature is enabled
+macro_rules! trace_event {
+    ($level:ident, $($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::$level!($($arg)*);
+    };
+}
+
 pub(crate) use get_language;
 pub(crate) use implement_metric_trait;
 pub(crate) use mk_action;
@@ -325,3 +347,5 @@ pub(crate) use mk_emacs_mode;
 pub(crate) use mk_extensions;
 pub(crate) use mk_lang;
 pub(crate) use mk_langs;
+pub(crate) use trace_event;
+pub(crate) use trace_span;
@@ -0,0 +1,202 @@
+use std::{fs, path::Path};
+
+use serde::Serialize;
+
+use crate::packages::PackageKind;
+
+/// The weight of a manifest: its dependencies, features and scripts.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct ManifestMetrics {
+    /// The direct runtime dependencies
+    ///
+    /// They include the target-specific dependencies of Cargo and the
+    /// peer and optional dependencies of npm.
+    pub dependencies: usize,
+    /// The dependencies only used by tests, examples and benchmarks
+    pub dev_dependencies: usize,
+    /// The dependencies of the build script of Cargo
+    pub build_dependencies: usize,
+    /// The dependencies marked as `// indirect` in a `go.mod`
+    pub indirect_dependencies: usize,
+    /// The features of Cargo
+    pub features: usize,
+    /// The scripts of npm, or the build script of Cargo
+    pub scripts: usize,
+}
+
+fn table_len(value: Option<&toml::Value>) -> usize {
+    value
+        .and_then(|value| value.as_table())
+        .map_or(0, |table| table.len())
+}
+
+fn cargo_metrics(manifest: &str) -> Option<ManifestMetrics> {
+    let manifest: toml::Table = toml::from_str(manifest).ok()?;
+    let mut metrics = ManifestMetrics {
+        dependencies: table_len(manifest.get("dependencies")),
+        dev_dependencies: table_len(manifest.get("dev-dependencies")),
+        build_dependencies: table_len(manifest.get("build-dependencies")),
+        features: table_len(manifest.get("features")),
+        scripts: manifest
+            .get("package")
+            .and_then(|package| package.get("build"))
+            .is_some_and(|build| build.as_bool() != Some(false)) as usize,
+        ..Default::default()
+    };
+    // [target.'cfg(unix)'.dependencies]
+    for target in manifest
+        .get("target")
+        .and_then(|targets| targets.as_table())
+        .into_iter()
+        .flat_map(|targets| targets.values())
+    {
+        metrics.dependencies += table_len(target.get("dependencies"));
+        metrics.dev_dependencies += table_len(target.get("dev-dependencies"));
+        metrics.build_dependencies += table_len(target.get("build-dependencies"));
+    }
+    Some(metrics)
+}
+
+fn npm_metrics(manifest: &str) -> Option<ManifestMetrics> {
+    let manifest: serde_json::Value = serde_json::from_str(manifest).ok()?;
+    let len = |key| {
+        manifest
+            .get(key)
+            .and_then(|value| value.as_object())
+            .map_or(0, |object| object.len())
+    };
+    Some(ManifestMetrics {
+        dependencies: len("dependencies") + len("peerDependencies") + len("optionalDependencies"),
+        dev_dependencies: len("devDependencies"),
+        scripts: len("scripts"),
+        ..Default::default()
+    })
+}
+
+fn go_metrics(manifest: &str) -> Option<ManifestMetrics> {
+    let mut metrics = ManifestMetrics::default();
+    let mut in_require = false;
+    for line in manifest.lines().map(str::trim) {
+        let requirement = if in_require {
+            if line == ")" {
+                in_require = false;
+                continue;
+            }
+            line
+        } else if let Some(rest) = line.strip_prefix("require") {
+            match rest.trim() {
+                "(" => {
+                    in_require = true;
+                    continue;
+                }
+                rest => rest,
+            }
+        } else {
+            continue;
+        };
+
+        if requirement.is_empty() || requirement.starts_with("//") {
+            continue;
+        }
+        if requirement.ends_with("// indirect") {
+            metrics.indirect_dependencies += 1;
+        } else {
+            metrics.dependencies += 1;
+        }
+    }
+    Some(metrics)
+}
+
+/// Computes the metrics of the content of a manifest.
+///
+/// Returns `None` if the manifest is malformed or its kind isn't
+/// supported yet, like Maven.
+///
+/// # Examples
+///
+/// ```
+/// use singularity_code_analysis::{parse_manifest, PackageKind};
+///
+/// let manifest = "[dependencies]\nserde = \"1.0\"\n\n[features]\nfast = []\n";
+/// let metrics = parse_manifest(PackageKind::Cargo, manifest).unwrap();
+/// assert_eq!((metrics.dependencies, metrics.features), (1, 1));
+/// ```
+pub fn parse_manifest(kind: PackageKind, manifest: &str) -> Option<ManifestMetrics> {
+    match kind {
+        PackageKind::Cargo => cargo_metrics(manifest),
+        PackageKind::Npm => npm_metrics(manifest),
+        PackageKind::Go => go_metrics(manifest),
+        PackageKind::Maven => None,
+    }
+}
+
+/// Computes the metrics of a `Cargo.toml`, `package.json` or `go.mod`.
+///
+/// A `build.rs` next to a `Cargo.toml` counts as a build script, as
+/// Cargo does.
+pub fn manifest_metrics(path: &Path) -> Option<ManifestMetrics> {
+    let kind = match path.file_name()?.to_str()? {
+        "Cargo.toml" => PackageKind::Cargo,
+        "package.json" => PackageKind::Npm,
+        "go.mod" => PackageKind::Go,
+        _ => return None,
+    };
+    let mut metrics = parse_manifest(kind, &fs::read_to_string(path).ok()?)?;
+    if kind == PackageKind::Cargo && path.with_file_name("build.rs").is_file() {
+        metrics.scripts = 1;
+    }
+    Some(metrics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cargo_manifest() {
+        let manifest = "[package]\nname = \"foo\"\nbuild = \"gen.rs\"\n\n\
+                        [dependencies]\nserde = \"1.0\"\nregex = \"1.0\"\n\n\
+                        [target.'cfg(unix)'.dependencies]\nlibc = \"0.2\"\n\n\
+                        [dev-dependencies]\ninsta = \"1.0\"\n\n\
+                        [build-dependencies]\ncc = \"1.0\"\n\n\
+                        [features]\ndefault = []\nfast = []\n";
+        assert_eq!(
+            parse_manifest(PackageKind::Cargo, manifest),
+            Some(ManifestMetrics {
+                dependencies: 3,
+                dev_dependencies: 1,
+                build_dependencies: 1,
+                indirect_dependencies: 0,
+                features: 2,
+                scripts: 1,
+            })
+        );
+        assert_eq!(parse_manifest(PackageKind::Cargo, "[dependencies"), None);
+    }
+
+    #[test]
+    fn npm_manifest() {
+        let manifest = r#"{
+            "name": "foo",
+            "scripts": {"build": "tsc", "test": "jest"},
+            "dependencies": {"react": "^18.0.0"},
+            "peerDependencies": {"react-dom": "^18.0.0"},
+            "devDependencies": {"jest": "^29.0.0", "typescript": "^5.0.0"}
+        }"#;
+        let metrics = parse_manifest(PackageKind::Npm, manifest).unwrap();
+        assert_eq!(metrics.dependencies, 2);
+        assert_eq!(metrics.dev_dependencies, 2);
+        assert_eq!(metrics.scripts, 2);
+    }
+
+    #[test]
+    fn go_manifest() {
+        let manifest = "module example.com/foo\n\ngo 1.22\n\n\
+                        require github.com/pkg/errors v0.9.1\n\n\
+                        require (\n\tgolang.org/x/sync v0.7.0\n\
+                        \tgolang.org/x/sys v0.20.0 // indirect\n)\n";
+        let metrics = parse_manifest(PackageKind::Go, manifest).unwrap();
+        assert_eq!(metrics.dependencies, 2);
+        assert_eq!(metrics.indirect_dependencies, 1);
+    }
+}
@@ -0,0 +1,159 @@
+use serde::Serialize;
+
+use crate::spaces::FuncSpace;
+
+/// A fenced code block of a Markdown document.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CodeBlock {
+    /// The language tag of the fence, like `rust` for ```` ```rust,ignore ````,
+    /// empty if there is none
+    pub tag: String,
+    /// The first line of the code, after the opening fence
+    pub start_line: usize,
+    /// The last line of the code
+    pub end_line: usize,
+    /// The code, without the indentation of the fence
+    pub code: String,
+}
+
+/// The metrics of a fenced code block.
+#[derive(Clone, Debug, Serialize)]
+pub struct BlockMetrics {
+    /// The language tag of the fence
+    pub tag: String,
+    /// The language the code was analyzed as
+    pub language: &'static str,
+    /// The metrics of the code, on the lines of the document
+    pub space: FuncSpace,
+}
+
+/// The metrics of the fenced code blocks of a Markdown document.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct MarkdownAnalysis {
+    /// The analyzed blocks, in the order of the document
+    pub blocks: Vec<BlockMetrics>,
+    /// The blocks without a tag or in an unsupported language
+    pub skipped: Vec<CodeBlock>,
+}
+
+// An opening fence, made of three or more backticks or tildes
+struct Fence<'a> {
+    marker: char,
+    length: usize,
+    indent: usize,
+    info: &'a str,
+}
+
+fn fence(line: &str) -> Option<Fence<'_>> {
+    let trimmed = line.trim_start_matches(' ');
+    let indent = line.len() - trimmed.len();
+    let marker = trimmed.chars().next().filter(|c| matches!(c, '`' | '~'))?;
+    let length = trimmed.chars().take_while(|&c| c == marker).count();
+    let info = trimmed[length..].trim();
+    // The info string of a backtick fence can't contain backticks
+    if indent > 3 || length < 3 || (marker == '`' && info.contains('`')) {
+        return None;
+    }
+    Some(Fence {
+        marker,
+        length,
+        indent,
+        info,
+    })
+}
+
+impl Fence<'_> {
+    fn is_closed_by(&self, line: &str) -> bool {
+        let trimmed = line.trim_start_matches(' ');
+        let markers = trimmed.chars().take_while(|&c| c == self.marker).count();
+        line.len() - trimmed.len() <= 3
+            && markers >= self.length
+            && trimmed[markers..].trim().is_empty()
+    }
+
+    // The first word of the info string, like `python` for `{.python}` or
+    // `rust` for `rust,no_run`
+    fn tag(&self) -> String {
+        self.info
+            .trim_start_matches(['{', '.'])
+            .split(|c: char| c.is_whitespace() || matches!(c, ',' | '}'))
+            .next()
+            .unwrap_or_default()
+            .to_lowercase()
+    }
+}
+
+/// Returns the fenced code blocks of a Markdown document, leaving out the
+/// blocks without code.
+///
+/// A block which isn't closed runs to the end of the document, as in
+/// `CommonMark`.
+///
+/// # Examples
+///
+/// ```
+/// use singularity_code_analysis::code_blocks;
+///
+/// let markdown = "# Usage\n\n```python\nimport foo\n```\n";
+/// let blocks = code_blocks(markdown);
+/// assert_eq!(blocks[0].tag, "python");
+/// assert_eq!((blocks[0].start_line, blocks[0].end_line), (4, 4));
+/// ```
+pub fn code_blocks(markdown: &str) -> Vec<CodeBlock> {
+    let mut blocks = Vec::new();
+    let mut lines = markdown.lines().enumerate();
+    while let Some((row, line)) = lines.next() {
+        let Some(fence) = fence(line) else {
+            continue;
+        };
+        let mut code = Vec::new();
+        for (_, line) in lines.by_ref() {
+            if fence.is_closed_by(line) {
+                break;
+            }
+            // The indentation of the fence is removed from the code
+            let indent = line.len() - line.trim_start_matches(' ').len();
+            code.push(&line[indent.min(fence.indent)..]);
+        }
+        if code.iter().all(|line| line.trim().is_empty()) {
+            continue;
+        }
+        blocks.push(CodeBlock {
+            tag: fence.tag(),
+            start_line: row + 2,
+            end_line: row + 1 + code.len(),
+            code: code.join("\n") + "\n",
+        });
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fenced_code_blocks() {
+        let markdown = "Text
+
+  ~~~~ {.rust}
+  fn f() {}
+  ```
+  ~~~~
+
+```
+
+```
+
+```` js
+let a = 1;
+";
+        let blocks = code_blocks(markdown);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].tag, "rust");
+        assert_eq!(blocks[0].code, "fn f() {}\n```\n");
+        assert_eq!((blocks[0].start_line, blocks[0].end_line), (4, 5));
+        assert_eq!(blocks[1].tag, "js");
+        assert_eq!((blocks[1].start_line, blocks[1].end_line), (13, 13));
+    }
+}
@@ -0,0 +1,201 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Debug,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{langs::LANG, spaces::CodeMetrics};
+
+/// The shape of the tree-sitter grammar used to parse a language.
+///
+/// Grammar crates don't expose their version at runtime, but any change
+/// to a grammar changes its node kinds or fields, so two results with
+/// the same grammar info come from the same grammar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GrammarInfo {
+    /// The number of node kinds of the grammar
+    pub node_kinds: usize,
+    /// The number of fields of the grammar
+    pub fields: usize,
+}
+
+impl GrammarInfo {
+    /// Returns the info of the grammar of a language.
+    pub fn new(language: LANG) -> Self {
+        let language = language.get_ts_language();
+        Self {
+            node_kinds: language.node_kind_count(),
+            fields: language.field_count(),
+        }
+    }
+}
+
+/// The provenance of a result, so stored results are reproducible and
+/// comparable across versions of the tool.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnalysisMetadata {
+    /// The version of the crate which produced the result
+    pub version: String,
+    /// The tree-sitter ABI version
+    pub tree_sitter_abi: usize,
+    /// The grammars of the analyzed languages, by language name
+    pub grammars: BTreeMap<String, GrammarInfo>,
+    /// The computed metrics
+    pub metrics: Vec<String>,
+    /// The hash of the configuration of the analysis, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config_hash: Option<String>,
+    /// When the analysis started, in seconds since the Unix epoch
+    pub started_at: u64,
+    /// How long the analysis took, in milliseconds
+    pub duration_ms: f64,
+    /// The named node kinds which no checker or getter handled, with the
+    /// number of their nodes, by language name, when they are recorded
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub unhandled_kinds: BTreeMap<String, BTreeMap<String, usize>>,
+}
+
+impl AnalysisMetadata {
+    /// Creates the metadata of an analysis which started at `started`
+    /// and ends now.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::{path::Path, time::SystemTime};
+    ///
+    /// use singularity_code_analysis::{metrics, AnalysisMetadata, ParserEngineRust, ParserTrait, LANG};
+    ///
+    /// let started = SystemTime::now();
+    /// let path = Path::new("foo.rs");
+    /// let parser = ParserEngineRust::new(b"fn f() {}".to_vec(), path, None);
+    /// let space = metrics(&parser, path).unwrap();
+    ///
+    /// let metadata = AnalysisMetadata::new(&[LANG::Rust], &space.metrics, started);
+    /// assert!(metadata.grammars.contains_key("rust"));
+    /// assert!(metadata.metrics.contains(&"cyclomatic".to_string()));
+    /// ```
+    pub fn new(languages: &[LANG], metrics: &CodeMetrics, started: SystemTime) -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            tree_sitter_abi: tree_sitter::LANGUAGE_VERSION,
+            grammars: languages
+                .iter()
+                .map(|language| (language.get_name().to_string(), GrammarInfo::new(*language)))
+                .collect(),
+            metrics: metrics
+                .enabled_metrics()
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+            config_hash: None,
+            started_at: started
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since| since.as_secs()),
+            duration_ms: started.elapsed().unwrap_or(Duration::ZERO).as_secs_f64() * 1000.,
+            unhandled_kinds: BTreeMap::new(),
+        }
+    }
+
+    /// Adds the node kinds of a language which no checker or getter
+    /// handled, as returned by [`unhandled_kinds`](crate::unhandled_kinds),
+    /// to the ones already recorded.
+    ///
+    /// The kinds of the files of a run are aggregated by adding them to
+    /// the metadata of the run.
+    pub fn add_unhandled_kinds(&mut self, language: LANG, kinds: &BTreeMap<String, usize>) {
+        let recorded = self
+            .unhandled_kinds
+            .entry(language.get_name().to_string())
+            .or_default();
+        for (kind, count) in kinds {
+            *recorded.entry(kind.clone()).or_insert(0) += count;
+        }
+    }
+
+    /// Sets the hash of the configuration of the analysis.
+    ///
+    /// The hash is computed on the `Debug` representation of the
+    /// configuration, so equal configurations have the same hash.
+    pub fn with_config(mut self, config: &impl Debug) -> Self {
+        self.config_hash = Some(config_hash(config));
+        self
+    }
+}
+
+/// Returns a stable hash of a configuration, as an hexadecimal string.
+///
+/// The `FNV-1a` hash of the `Debug` representation of the configuration
+/// is used, since it doesn't change across runs and Rust versions.
+pub fn config_hash(config: &impl Debug) -> String {
+    let hash = format!("{config:?}")
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("{hash:016x}")
+}
+
+/// A serialized result with its [`AnalysisMetadata`].
+///
+/// The fields of the result are serialized next to a `metadata` field.
+#[derive(Debug, Clone, Serialize)]
+pub struct WithMetadata<T> {
+    /// The provenance of the result
+    pub metadata: AnalysisMetadata,
+    /// The result
+    #[serde(flatten)]
+    pub result: T,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::risk::RiskPolicy;
+
+    use super::*;
+
+    #[test]
+    fn analysis_metadata() {
+        let metadata = AnalysisMetadata::new(
+            &[LANG::Rust, LANG::Python],
+            &CodeMetrics::default(),
+            SystemTime::now(),
+        )
+        .with_config(&RiskPolicy::default());
+
+        assert_eq!(metadata.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(
+            metadata.grammars.keys().collect::<Vec<_>>(),
+            ["python", "rust"]
+        );
+        assert!(metadata.grammars["rust"].node_kinds > 0);
+        assert!(metadata.duration_ms >= 0.);
+        assert_eq!(
+            metadata.config_hash,
+            Some(config_hash(&RiskPolicy::default()))
+        );
+        assert_ne!(
+            config_hash(&RiskPolicy::default()),
+            config_hash(&RiskPolicy {
+                max_cyclomatic: 20.,
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn aggregated_unhandled_kinds() {
+        let mut metadata =
+            AnalysisMetadata::new(&[LANG::Rust], &CodeMetrics::default(), SystemTime::now());
+        assert!(!serde_json::to_string(&metadata)
+            .unwrap()
+            .contains("unhandled_kinds"));
+
+        let kinds = BTreeMap::from([("parameters".to_string(), 1)]);
+        metadata.add_unhandled_kinds(LANG::Rust, &kinds);
+        metadata.add_unhandled_kinds(LANG::Rust, &kinds);
+        assert_eq!(metadata.unhandled_kinds["rust"]["parameters"], 2);
+    }
+}
@@ -261,8 +261,11 @@ fn java_inspect_container(container_node: &Node, conditions: &mut f64) {
     let mut node = *container_node;
     let mut node_kind = node.kind_id().into();
 
+    let Some(parent) = node.parent() else {
+        return;
+    };
     // Initializes the flag to true if the container is known to contain a boolean value
-    let mut has_boolean_content = match node.parent().unwrap().kind_id().into() {
+    let mut has_boolean_content = match parent.kind_id().into() {
         BinaryExpression | IfStatement | WhileStatement | DoStatement | ForStatement => true,
         TernaryExpression => node
             .previous_sibling()
@@ -276,7 +279,9 @@ fn java_inspect_container(container_node: &Node, conditions: &mut f64) {
         // The child node of index 0 contains the unary expression operator (we look for the `!` operator)
         let is_parenthesised_exp = matches!(node_kind, ParenthesizedExpression);
         let is_not_operator = matches!(node_kind, UnaryExpression)
-            && matches!(node.child(0).unwrap().kind_id().into(), BANG);
+            && node
+                .child(0)
+                .is_some_and(|child| matches!(child.kind_id().into(), BANG));
 
         // Stops the exploration if the node is neither
         // a parenthesized expression nor a `Not` operator
@@ -295,7 +300,11 @@ fn java_inspect_container(container_node: &Node, conditions: &mut f64) {
         // always store their expressions in the children nodes of index one
         // https://github.com/tree-sitter/tree-sitter-java/blob/master/src/grammar.json#L2472
         // https://github.com/tree-sitter/tree-sitter-java/blob/master/src/grammar.json#L2150
-        node = node.child(1).unwrap();
+        // They may be missing in a code with syntax errors
+        let Some(child) = node.child(1) else {
+            break;
+        };
+        node = child;
         node_kind = node.kind_id().into();
 
         // Stops the exploration when the content is found
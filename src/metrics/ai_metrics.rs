@@ -20,14 +20,22 @@
 //! - `dependency_coupling` - Measures inter-module coupling strength
 //! - `error_handling` - Error path coverage and robustness
 //!
+//! ### Explainability
+//! - `explanation` - Inputs, weights and factors of each score
+//!
 //! ### Database Integration
 //! - `postgresql_enriched` - PostgreSQL-backed pattern learning
+//! - `history_provider` - Complexity history from git or a database
+//! - `postgresql_sink` - Batch write-back of the computed metrics
 
 pub mod ai_code_quality;
 pub mod code_smell_density;
 pub mod dependency_coupling;
 pub mod error_handling;
+pub mod explanation;
+pub mod history_provider;
 pub mod postgresql_enriched;
+pub mod postgresql_sink;
 pub mod refactoring_readiness;
 pub mod semantic_complexity;
 pub mod testability_score;
@@ -37,7 +45,10 @@ pub use ai_code_quality::*;
 pub use code_smell_density::*;
 pub use dependency_coupling::*;
 pub use error_handling::*;
+pub use explanation::*;
+pub use history_provider::*;
 pub use postgresql_enriched::*;
+pub use postgresql_sink::*;
 pub use refactoring_readiness::*;
 pub use semantic_complexity::*;
 pub use testability_score::*;
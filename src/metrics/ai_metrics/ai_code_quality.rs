@@ -2,11 +2,15 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::explanation::ScoreExplanation;
+
 /// AI code quality statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AICodeQualityStats {
     pub quality_score: f64,
     pub quality_factors: Vec<QualityFactor>,
+    #[serde(default)]
+    pub explanation: ScoreExplanation,
 }
 
 /// Quality factor
@@ -22,6 +26,7 @@ impl Default for AICodeQualityStats {
         Self {
             quality_score: 0.0,
             quality_factors: Vec::new(),
+            explanation: ScoreExplanation::default(),
         }
     }
 }
@@ -71,6 +76,15 @@ impl AICodeQualityStats {
             0.0
         };
 
+        let mut explanation =
+            ScoreExplanation::new("sum(factor score * weight) / sum(weights)", 0.0);
+        explanation.input("lines", code.lines().count() as f64);
+        for factor in &self.quality_factors {
+            explanation.factor(&factor.name, factor.score, factor.weight / total_weight);
+        }
+        explanation.finish(self.quality_score);
+        self.explanation = explanation;
+
         self.quality_score
     }
 
@@ -172,7 +186,7 @@ impl AICodeQualityStats {
         let first_indent = lines[0].len() - lines[0].trim_start().len();
         lines.iter().all(|line| {
             let indent = line.len() - line.trim_start().len();
-            indent == first_indent || indent == first_indent + 4 || indent == first_indent - 4
+            indent == first_indent || indent == first_indent + 4 || indent + 4 == first_indent
         })
     }
 
@@ -2,12 +2,16 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::explanation::ScoreExplanation;
+
 /// Code smell density statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CodeSmellDensityStats {
     pub smell_density: f64,
     pub total_smells: usize,
     pub smell_types: Vec<SmellType>,
+    #[serde(default)]
+    pub explanation: ScoreExplanation,
 }
 
 /// Code smell type
@@ -24,12 +28,57 @@ impl Default for CodeSmellDensityStats {
             smell_density: 0.0,
             total_smells: 0,
             smell_types: Vec::new(),
+            explanation: ScoreExplanation::default(),
         }
     }
 }
 
 impl CodeSmellDensityStats {
     pub fn calculate_smell_density(&mut self, code: &str) -> f64 {
+        self.detect_smells(code);
+
+        self.compute_density(code.lines().count())
+    }
+
+    /// Computes the smell density of a code, adding the smells detected by
+    /// the language-specific metrics of its space.
+    pub fn calculate_smell_density_with_extras(
+        &mut self,
+        code: &str,
+        extras: &crate::extras::Stats,
+    ) -> f64 {
+        self.detect_smells(code);
+
+        if let Some(javascript) = extras.javascript() {
+            if javascript.callback_depth_max() > 3.0 {
+                self.smell_types.push(SmellType {
+                    name: "Callback Hell".to_string(),
+                    count: javascript.callback_depth_max() as usize,
+                    severity: 0.8,
+                });
+            }
+            if javascript.promise_chain_max() > 4.0 {
+                self.smell_types.push(SmellType {
+                    name: "Long Promise Chain".to_string(),
+                    count: javascript.promise_chains() as usize,
+                    severity: 0.5,
+                });
+            }
+            if javascript.mixed_async_functions() > 0.0 {
+                self.smell_types.push(SmellType {
+                    name: "Mixed Async Styles".to_string(),
+                    count: javascript.mixed_async_functions() as usize,
+                    severity: 0.6,
+                });
+            }
+        }
+        self.total_smells = self.smell_types.len();
+
+        self.compute_density(code.lines().count())
+    }
+
+    // Detects the smells of a code from its text
+    fn detect_smells(&mut self, code: &str) {
         let mut smells = Vec::new();
 
         // Detect various code smells
@@ -75,14 +124,31 @@ impl CodeSmellDensityStats {
 
         self.smell_types = smells;
         self.total_smells = self.smell_types.len();
+    }
+
+    // Computes the smells per hundred lines, explaining it
+    fn compute_density(&mut self, total_lines: usize) -> f64 {
+        let mut explanation = ScoreExplanation::new("smells / lines * 100", 0.0);
+        explanation.input("lines", total_lines as f64);
+        for smell in &self.smell_types {
+            explanation.input(&smell.name, smell.count as f64);
+        }
 
-        let total_lines = code.lines().count();
         self.smell_density = if total_lines > 0 {
             self.total_smells as f64 / total_lines as f64 * 100.0
         } else {
             0.0
         };
 
+        let weight = if total_lines > 0 {
+            100.0 / total_lines as f64
+        } else {
+            0.0
+        };
+        explanation.factor("Smells", self.total_smells as f64, weight);
+        explanation.finish(self.smell_density);
+        self.explanation = explanation;
+
         self.smell_density
     }
 
@@ -134,3 +200,40 @@ impl CodeSmellDensityStats {
         code.contains("return") && code.contains("unreachable")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{tools::check_metrics, JavascriptParser};
+
+    use super::*;
+
+    const CODE: &str = "a(function (x) {
+    b(() => {
+        c((y) => {
+            d(() => y);
+        });
+    });
+});";
+
+    #[test]
+    fn javascript_extras_smells() {
+        check_metrics::<JavascriptParser>(CODE, "foo.js", |metric| {
+            let mut plain = CodeSmellDensityStats::default();
+            plain.calculate_smell_density(CODE);
+
+            let mut density = CodeSmellDensityStats::default();
+            let score = density.calculate_smell_density_with_extras(CODE, &metric.extras);
+
+            let names = density
+                .smell_types
+                .iter()
+                .map(|smell| smell.name.as_str())
+                .collect::<Vec<_>>();
+            assert!(names.contains(&"Callback Hell"));
+            assert_eq!(density.total_smells, plain.total_smells + 1);
+            assert_eq!(score, density.total_smells as f64 / 7.0 * 100.0);
+            assert_eq!(density.explanation.score, score);
+            assert!(score > plain.smell_density);
+        });
+    }
+}
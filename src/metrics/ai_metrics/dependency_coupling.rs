@@ -5,6 +5,8 @@
 
 use std::collections::{HashMap, HashSet};
 
+use super::explanation::ScoreExplanation;
+
 /// Dependency Coupling Metrics
 #[derive(Debug, Clone, PartialEq)]
 pub struct DependencyCouplingMetrics {
@@ -24,6 +26,8 @@ pub struct DependencyCouplingMetrics {
     pub cycles: Vec<Vec<String>>,
     /// All detected imports with their targets
     pub import_graph: HashMap<String, Vec<String>>,
+    /// The inputs and factors of the score
+    pub explanation: ScoreExplanation,
 }
 
 impl DependencyCouplingMetrics {
@@ -44,15 +48,34 @@ impl DependencyCouplingMetrics {
         cycles: Vec<Vec<String>>,
         import_graph: HashMap<String, Vec<String>>,
     ) -> Self {
-        let density_penalty = (import_density / 10.0).clamp(0.0, 1.0) * 10.0 * 0.3;
-        let cyclic_penalty = (cyclic_count as f64) * 0.25;
-        let depth_penalty = (max_depth as f64 / 5.0).clamp(0.0, 1.0) * 20.0 * 0.2;
-        let violation_penalty = (violations as f64) * 0.15;
-        let external_penalty = external_ratio.clamp(0.0, 1.0) * 10.0 * 0.1;
+        let mut explanation = ScoreExplanation::new("100 - sum(penalties)", 100.0);
+        explanation.input("import_density", import_density);
+        explanation.input("max_import_chain_depth", max_depth as f64);
+        explanation.input("external_import_ratio", external_ratio);
+
+        // Each penalty is the opposite of the contribution of its factor
+        let density_penalty = -explanation.factor(
+            "Import Density",
+            (import_density / 10.0).clamp(0.0, 1.0) * 10.0,
+            -0.3,
+        );
+        let cyclic_penalty = -explanation.factor("Cyclic Dependencies", cyclic_count as f64, -0.25);
+        let depth_penalty = -explanation.factor(
+            "Import Chain Depth",
+            (max_depth as f64 / 5.0).clamp(0.0, 1.0) * 20.0,
+            -0.2,
+        );
+        let violation_penalty = -explanation.factor("Layer Violations", violations as f64, -0.15);
+        let external_penalty = -explanation.factor(
+            "External Imports",
+            external_ratio.clamp(0.0, 1.0) * 10.0,
+            -0.1,
+        );
 
         let total_penalty =
             density_penalty + cyclic_penalty + depth_penalty + violation_penalty + external_penalty;
         let coupling_score = (100.0 - total_penalty).clamp(0.0, 100.0);
+        explanation.finish(coupling_score);
 
         Self {
             coupling_score,
@@ -63,6 +86,7 @@ impl DependencyCouplingMetrics {
             external_import_ratio: external_ratio.clamp(0.0, 1.0),
             cycles,
             import_graph,
+            explanation,
         }
     }
 
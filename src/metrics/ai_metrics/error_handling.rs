@@ -3,6 +3,8 @@
 //! Analyzes error declarations, unhandled exceptions, logging coverage, and fallback paths
 //! to predict runtime stability and debuggability.
 
+use super::explanation::ScoreExplanation;
+
 /// Error Handling Metrics
 #[derive(Debug, Clone, PartialEq)]
 pub struct ErrorHandlingMetrics {
@@ -24,6 +26,8 @@ pub struct ErrorHandlingMetrics {
     pub generic_catches: usize,
     /// Logging statements in error paths
     pub log_statements: usize,
+    /// The inputs and factors of the score
+    pub explanation: ScoreExplanation,
 }
 
 pub struct ErrorHandlingInputs {
@@ -65,6 +69,17 @@ impl ErrorHandlingMetrics {
             + 0.1 * fallback_coverage)
             * 100.0;
 
+        let mut explanation = ScoreExplanation::new("sum(factor value * weight)", 0.0);
+        explanation.input("error_handlers", error_handlers as f64);
+        explanation.input("generic_catches", generic_catches as f64);
+        explanation.input("log_statements", log_statements as f64);
+        explanation.factor("Error Type Coverage", error_type_coverage, 30.0);
+        explanation.factor("Handled Paths", 1.0 - unhandled_paths_ratio, 25.0);
+        explanation.factor("Specific Catches", specific_catches_ratio, 20.0);
+        explanation.factor("Logging Coverage", logging_coverage, 15.0);
+        explanation.factor("Fallback Coverage", fallback_coverage, 10.0);
+        explanation.finish(error_handling_score.clamp(0.0, 100.0));
+
         Self {
             error_handling_score: error_handling_score.clamp(0.0, 100.0),
             error_type_coverage: (error_type_coverage * 100.0).clamp(0.0, 100.0),
@@ -75,6 +90,7 @@ impl ErrorHandlingMetrics {
             error_handlers,
             generic_catches,
             log_statements,
+            explanation,
         }
     }
 
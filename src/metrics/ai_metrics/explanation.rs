@@ -0,0 +1,171 @@
+//! Explanation of the AI metric scores
+
+use serde::{Deserialize, Serialize};
+
+/// A value a score is computed from.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScoreInput {
+    pub name: String,
+    pub value: f64,
+}
+
+/// A factor of a score, adding `value * weight` to it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScoreFactor {
+    pub name: String,
+    pub value: f64,
+    pub weight: f64,
+    pub contribution: f64,
+}
+
+/// Explains how a score is computed.
+///
+/// The score is the base plus the contributions of the factors, clamped
+/// to the range of the metric, so the score of a file can be audited
+/// from its inputs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScoreExplanation {
+    /// The formula of the score
+    pub formula: String,
+    /// The values measured on the code
+    pub inputs: Vec<ScoreInput>,
+    /// The score before any factor
+    pub base: f64,
+    /// The factors, in the order of the formula
+    pub factors: Vec<ScoreFactor>,
+    /// The score, after clamping the base plus the contributions
+    pub score: f64,
+}
+
+impl ScoreExplanation {
+    pub fn new(formula: &str, base: f64) -> Self {
+        Self {
+            formula: formula.to_string(),
+            base,
+            ..Default::default()
+        }
+    }
+
+    /// Records a value measured on the code.
+    pub fn input(&mut self, name: &str, value: f64) {
+        self.inputs.push(ScoreInput {
+            name: name.to_string(),
+            value,
+        });
+    }
+
+    /// Records a check made on the code, as a value of 1 if it passed.
+    pub fn check(&mut self, name: &str, passed: bool) {
+        self.input(name, if passed { 1.0 } else { 0.0 });
+    }
+
+    /// Records a factor of the score, returning its contribution.
+    pub fn factor(&mut self, name: &str, value: f64, weight: f64) -> f64 {
+        let contribution = value * weight;
+        self.factors.push(ScoreFactor {
+            name: name.to_string(),
+            value,
+            weight,
+            contribution,
+        });
+        contribution
+    }
+
+    /// Records the score.
+    pub fn finish(&mut self, score: f64) {
+        self.score = score;
+    }
+
+    /// Returns the base plus the contributions of the factors, before
+    /// clamping.
+    pub fn total(&self) -> f64 {
+        self.base
+            + self
+                .factors
+                .iter()
+                .map(|factor| factor.contribution)
+                .sum::<f64>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::LANG;
+
+    use super::super::*;
+
+    const CODE: &str = "use std::collections::HashMap;
+
+fn calculate_total(items: &[u32]) -> u32 {
+    let mut total: u32 = 0;
+    for item in items {
+        if *item > 10 {
+            total += item;
+        }
+    }
+    total
+}
+";
+
+    fn assert_explained(explanation: &ScoreExplanation, score: f64, min: f64, max: f64) {
+        assert_eq!(explanation.score, score);
+        assert!((explanation.total().clamp(min, max) - score).abs() < 1e-9);
+        assert!(!explanation.factors.is_empty());
+    }
+
+    #[test]
+    fn explained_scores() {
+        let mut quality = AICodeQualityStats::default();
+        let score = quality.calculate_quality_score(CODE);
+        assert_explained(&quality.explanation, score, 0.0, 100.0);
+        assert_eq!(quality.explanation.factors[0].name, "Readability");
+
+        let mut testability = TestabilityScoreStats::default();
+        let score = testability.calculate_testability_score(CODE);
+        assert_explained(&testability.explanation, score, 0.0, 100.0);
+
+        let mut readiness = RefactoringReadinessStats::default();
+        let score = readiness.calculate_readiness_score(CODE);
+        assert_explained(&readiness.explanation, score, 0.0, 100.0);
+
+        let mut density = CodeSmellDensityStats::default();
+        let score = density.calculate_smell_density(CODE);
+        assert_explained(&density.explanation, score, 0.0, f64::MAX);
+
+        let mut semantic = SemanticComplexityStats::default();
+        let score = semantic.calculate_semantic_complexity(CODE, LANG::Rust);
+        assert_explained(&semantic.explanation, score, 0.0, 100.0);
+        assert_eq!(semantic.explanation.factors[0].name, "calculate_total");
+
+        let type_safety = analyze_rust_type_safety(CODE);
+        assert_explained(
+            &type_safety.explanation,
+            type_safety.type_safety_score,
+            0.0,
+            100.0,
+        );
+
+        let error_handling = ErrorHandlingMetrics::from_code(CODE, "rust");
+        assert_explained(
+            &error_handling.explanation,
+            error_handling.error_handling_score,
+            0.0,
+            100.0,
+        );
+
+        let coupling = DependencyCouplingMetrics::from_imports(&[(
+            "main".to_string(),
+            "std::collections".to_string(),
+        )]);
+        assert_explained(&coupling.explanation, coupling.coupling_score, 0.0, 100.0);
+    }
+
+    #[test]
+    fn deterministic_explanations() {
+        let mut first = AICodeQualityStats::default();
+        let mut second = AICodeQualityStats::default();
+        first.calculate_quality_score(CODE);
+        second.calculate_quality_score(CODE);
+        assert_eq!(first.explanation, second.explanation);
+    }
+}
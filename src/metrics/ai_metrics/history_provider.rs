@@ -0,0 +1,203 @@
+//! Providers of the complexity history of the files
+//!
+//! The trends of the enriched metrics come from a [`HistoryProvider`]:
+//! the revisions of a git repository, or the `complexity_history` table
+//! of a SQLite or PostgreSQL database. A file without any recorded
+//! history has no trends.
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use super::postgresql_enriched::ComplexityTrend;
+use crate::{commit_range::spaces_at, function_history::file_revisions};
+
+/// The query of the history of a file in a SQLite database.
+#[cfg(feature = "rusqlite")]
+const SQLITE_QUERY: &str = "SELECT timestamp, complexity_score, commit_hash \
+     FROM complexity_history WHERE file_path = ?1 ORDER BY timestamp";
+
+/// The query of the history of a file in a PostgreSQL database.
+#[cfg(feature = "postgres")]
+const POSTGRES_QUERY: &str = "SELECT timestamp::text, complexity_score, commit_hash \
+     FROM complexity_history WHERE file_path = $1 ORDER BY timestamp";
+
+/// A source of the past complexity of the files.
+pub trait HistoryProvider {
+    /// Returns the complexity of a file at each recorded revision, the
+    /// oldest first, or nothing if the file has no history.
+    fn complexity_trends(&self, file_path: &str) -> io::Result<Vec<ComplexityTrend>>;
+}
+
+/// The history of the files of a git repository, computed by analyzing
+/// the last commits changing each file.
+///
+/// The complexity of a revision is the sum of the cyclomatic
+/// complexities of the file, and its timestamp the Unix time of the
+/// commit. `git` must be in the `PATH`.
+#[derive(Clone, Debug)]
+pub struct GitHistory {
+    repository: PathBuf,
+    max_commits: usize,
+}
+
+impl GitHistory {
+    /// Creates the history of the last `max_commits` commits changing
+    /// each file of a repository.
+    pub fn new(repository: &Path, max_commits: usize) -> Self {
+        Self {
+            repository: repository.to_path_buf(),
+            max_commits,
+        }
+    }
+}
+
+impl HistoryProvider for GitHistory {
+    fn complexity_trends(&self, file_path: &str) -> io::Result<Vec<ComplexityTrend>> {
+        let path = Path::new(file_path);
+        let mut trends = Vec::new();
+        for revision in file_revisions(&self.repository, path, self.max_commits)? {
+            // A revision which fails to parse, or deletes the file, has no
+            // complexity
+            let Some(space) = spaces_at(&self.repository, &revision.commit, path)
+                .ok()
+                .flatten()
+            else {
+                continue;
+            };
+            trends.push(ComplexityTrend {
+                timestamp: revision.timestamp.to_string(),
+                complexity_score: space.metrics.cyclomatic.cyclomatic_sum(),
+                file_path: file_path.to_string(),
+                commit_hash: revision.commit,
+            });
+        }
+        Ok(trends)
+    }
+}
+
+/// The history of the files recorded in the `complexity_history` table
+/// of a SQLite database, with the `file_path`, `commit_hash`,
+/// `timestamp` and `complexity_score` columns.
+#[cfg(feature = "rusqlite")]
+pub struct SqliteHistory(rusqlite::Connection);
+
+#[cfg(feature = "rusqlite")]
+impl SqliteHistory {
+    /// Opens the history of a SQLite database file.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        rusqlite::Connection::open(path)
+            .map(Self)
+            .map_err(io::Error::other)
+    }
+
+    /// Reads the history of an open SQLite connection.
+    pub fn new(connection: rusqlite::Connection) -> Self {
+        Self(connection)
+    }
+}
+
+#[cfg(feature = "rusqlite")]
+impl HistoryProvider for SqliteHistory {
+    fn complexity_trends(&self, file_path: &str) -> io::Result<Vec<ComplexityTrend>> {
+        let mut statement = self.0.prepare(SQLITE_QUERY).map_err(io::Error::other)?;
+        let trends = statement
+            .query_map([file_path], |row| {
+                Ok(ComplexityTrend {
+                    timestamp: row.get(0)?,
+                    complexity_score: row.get(1)?,
+                    file_path: file_path.to_string(),
+                    commit_hash: row.get(2)?,
+                })
+            })
+            .map_err(io::Error::other)?;
+        trends.collect::<Result<_, _>>().map_err(io::Error::other)
+    }
+}
+
+/// The history of the files recorded in the `complexity_history` table
+/// of a PostgreSQL database, with the same columns as for
+/// [`SqliteHistory`].
+#[cfg(feature = "postgres")]
+pub struct PostgresHistory(std::sync::Mutex<postgres::Client>);
+
+#[cfg(feature = "postgres")]
+impl PostgresHistory {
+    /// Connects to a PostgreSQL database, without TLS.
+    ///
+    /// The parameters are a connection string, like
+    /// `host=localhost user=postgres dbname=singularity`.
+    pub fn connect(params: &str) -> io::Result<Self> {
+        postgres::Client::connect(params, postgres::NoTls)
+            .map(Self::new)
+            .map_err(io::Error::other)
+    }
+
+    /// Reads the history of a PostgreSQL client.
+    pub fn new(client: postgres::Client) -> Self {
+        Self(std::sync::Mutex::new(client))
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl HistoryProvider for PostgresHistory {
+    fn complexity_trends(&self, file_path: &str) -> io::Result<Vec<ComplexityTrend>> {
+        let mut client = self
+            .0
+            .lock()
+            .map_err(|_| io::Error::other("the PostgreSQL client is poisoned"))?;
+        let rows = client
+            .query(POSTGRES_QUERY, &[&file_path])
+            .map_err(io::Error::other)?;
+        rows.iter()
+            .map(|row| -> Result<_, postgres::Error> {
+                Ok(ComplexityTrend {
+                    timestamp: row.try_get(0)?,
+                    complexity_score: row.try_get(1)?,
+                    file_path: file_path.to_string(),
+                    commit_hash: row.try_get(2)?,
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map_err(io::Error::other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::history::test_git;
+
+    #[test]
+    fn git_history() {
+        let repository =
+            std::env::temp_dir().join(format!("sca-history-provider-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&repository);
+        fs::create_dir_all(&repository).unwrap();
+        test_git(&repository, &["init", "-q"]);
+        for code in [
+            "fn f(x: i32) {\n    if x > 0 {}\n}\n",
+            "fn f(x: i32) {\n    if x > 0 {}\n    if x > 1 {}\n}\n\nfn g() {}\n",
+        ] {
+            fs::write(repository.join("a.rs"), code).unwrap();
+            test_git(&repository, &["add", "-A"]);
+            test_git(&repository, &["commit", "-qm", "change"]);
+        }
+
+        let history = GitHistory::new(&repository, 10);
+        let trends = history.complexity_trends("a.rs").unwrap();
+        let missing = history.complexity_trends("b.rs").unwrap();
+        fs::remove_dir_all(&repository).unwrap();
+
+        let scores = trends
+            .iter()
+            .map(|trend| trend.complexity_score)
+            .collect::<Vec<_>>();
+        assert_eq!(scores, [3., 5.]);
+        assert!(trends.iter().all(|trend| trend.commit_hash.len() == 40));
+        assert!(missing.is_empty());
+    }
+}
@@ -23,6 +23,9 @@
 
 use crate::langs::LANG;
 use std::collections::HashMap;
+use std::io;
+
+use super::history_provider::HistoryProvider;
 
 /// PostgreSQL-enriched AI metrics that leverage vector search and relational data
 #[derive(Debug, Clone, Default)]
@@ -367,6 +370,20 @@ impl Default for PostgreSQLSemanticComplexity {
     }
 }
 
+impl PostgreSQLSemanticComplexity {
+    /// Loads the complexity trends of a file from a history provider.
+    ///
+    /// The trends are empty when the provider has no history of the file.
+    pub fn load_trends(
+        &mut self,
+        provider: &dyn HistoryProvider,
+        file_path: &str,
+    ) -> io::Result<()> {
+        self.complexity_trends = provider.complexity_trends(file_path)?;
+        Ok(())
+    }
+}
+
 impl Default for PostgreSQLRefactoringReadiness {
     fn default() -> Self {
         Self {
@@ -0,0 +1,451 @@
+//! Write-back of the computed metrics to PostgreSQL
+//!
+//! The enriched metrics read their patterns and trends from the
+//! database, so the metrics computed here are stored back in it: the
+//! [`FunctionRecord`]s in the `function_metrics` table and the
+//! complexity trends in the `complexity_history` table, read back by the
+//! `PostgresHistory` provider.
+
+use std::{thread, time::Duration};
+
+use serde::Serialize;
+
+#[cfg(feature = "postgres")]
+use super::postgresql_enriched::ComplexityTrend;
+#[cfg(feature = "postgres")]
+use crate::path_base::PathBase;
+use crate::{
+    embeddings::FunctionEmbedding,
+    facts::FactMetrics,
+    spaces::{FuncSpace, SpaceKind},
+};
+
+/// The schema of the tables written by the sink.
+pub const POSTGRES_SCHEMA: &str = "\
+CREATE TABLE IF NOT EXISTS function_metrics (
+    file_path TEXT NOT NULL,
+    start_line BIGINT NOT NULL,
+    fingerprint TEXT NOT NULL,
+    name TEXT,
+    end_line BIGINT NOT NULL,
+    cyclomatic DOUBLE PRECISION NOT NULL,
+    cognitive DOUBLE PRECISION NOT NULL,
+    sloc DOUBLE PRECISION NOT NULL,
+    nargs DOUBLE PRECISION NOT NULL,
+    nexits DOUBLE PRECISION NOT NULL,
+    model TEXT,
+    embedding REAL[],
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (file_path, start_line, fingerprint)
+);
+CREATE TABLE IF NOT EXISTS complexity_history (
+    file_path TEXT NOT NULL,
+    commit_hash TEXT NOT NULL,
+    timestamp TEXT NOT NULL,
+    complexity_score DOUBLE PRECISION NOT NULL,
+    PRIMARY KEY (file_path, commit_hash)
+);";
+
+#[cfg(feature = "postgres")]
+const UPSERT_FUNCTION: &str = "\
+INSERT INTO function_metrics (file_path, start_line, fingerprint, name, end_line, \
+cyclomatic, cognitive, sloc, nargs, nexits, model, embedding) \
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12) \
+ON CONFLICT (file_path, start_line, fingerprint) DO UPDATE SET \
+name = EXCLUDED.name, end_line = EXCLUDED.end_line, cyclomatic = EXCLUDED.cyclomatic, \
+cognitive = EXCLUDED.cognitive, sloc = EXCLUDED.sloc, nargs = EXCLUDED.nargs, \
+nexits = EXCLUDED.nexits, model = COALESCE(EXCLUDED.model, function_metrics.model), \
+embedding = COALESCE(EXCLUDED.embedding, function_metrics.embedding), updated_at = now()";
+
+#[cfg(feature = "postgres")]
+const UPSERT_TREND: &str = "\
+INSERT INTO complexity_history (file_path, commit_hash, timestamp, complexity_score) \
+VALUES ($1, $2, $3, $4) \
+ON CONFLICT (file_path, commit_hash) DO UPDATE SET \
+timestamp = EXCLUDED.timestamp, complexity_score = EXCLUDED.complexity_score";
+
+/// The metrics of a function, as stored in the `function_metrics` table.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FunctionRecord {
+    /// The path of the file of the function
+    pub file_path: String,
+    /// The name of the function
+    pub name: Option<String>,
+    /// The first line of the function
+    pub start_line: usize,
+    /// The last line of the function
+    pub end_line: usize,
+    /// The fingerprint of the function space
+    pub fingerprint: String,
+    /// The metrics of the function
+    pub metrics: FactMetrics,
+    /// The model of the embedding, if any
+    pub model: Option<String>,
+    /// The embedding of the function, if any
+    pub embedding: Option<Vec<f32>>,
+}
+
+fn collect_records(
+    space: &FuncSpace,
+    file_path: &str,
+    embeddings: &[FunctionEmbedding],
+    records: &mut Vec<FunctionRecord>,
+) {
+    if space.kind == SpaceKind::Function {
+        let embedding = embeddings.iter().find(|embedding| {
+            embedding.start_line == space.start_line && embedding.fingerprint == space.fingerprint
+        });
+        records.push(FunctionRecord {
+            file_path: file_path.to_string(),
+            name: space.name.clone(),
+            start_line: space.start_line,
+            end_line: space.end_line,
+            fingerprint: space.fingerprint.clone(),
+            metrics: FactMetrics::new(space),
+            model: embedding.map(|embedding| embedding.model.clone()),
+            embedding: embedding.map(|embedding| embedding.vector.clone()),
+        });
+    }
+    for space in space.spaces.iter() {
+        collect_records(space, file_path, embeddings, records);
+    }
+}
+
+/// Returns the records of the functions of a file, with their
+/// embeddings when they are among `embeddings`.
+///
+/// An embedding belongs to the function space starting on the same line
+/// with the same fingerprint.
+pub fn function_records(
+    space: &FuncSpace,
+    file_path: &str,
+    embeddings: &[FunctionEmbedding],
+) -> Vec<FunctionRecord> {
+    let mut records = Vec::new();
+    collect_records(space, file_path, embeddings, &mut records);
+    records
+}
+
+/// The retries of a failed write, waiting twice as long after each
+/// attempt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The maximum number of attempts, the first one included
+    pub max_attempts: u32,
+    /// The wait after the first failed attempt
+    pub initial_backoff: Duration,
+    /// The longest wait between two attempts
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns the wait after a failed attempt, starting from 1.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+
+    /// Runs an operation until it succeeds, fails with an error which
+    /// isn't `transient`, or runs out of attempts.
+    pub fn run<T, E>(
+        &self,
+        mut operation: impl FnMut() -> Result<T, E>,
+        transient: impl Fn(&E) -> bool,
+    ) -> Result<T, E> {
+        let mut attempt = 1;
+        loop {
+            match operation() {
+                Err(error) if attempt < self.max_attempts && transient(&error) => {
+                    thread::sleep(self.backoff(attempt));
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// A sink upserting the records of the functions and the complexity
+/// trends into a PostgreSQL database, in batches.
+///
+/// Each batch is written in a transaction, retried on the connection
+/// errors, the serialization failures and the deadlocks. The records
+/// pushed are buffered until a batch is full, so [`PostgresSink::flush`]
+/// must be called once they are all pushed.
+#[cfg(feature = "postgres")]
+pub struct PostgresSink {
+    client: postgres::Client,
+    batch_size: usize,
+    retry: RetryPolicy,
+    path_base: Option<PathBase>,
+    pending: Vec<FunctionRecord>,
+}
+
+#[cfg(feature = "postgres")]
+fn is_transient(error: &postgres::Error) -> bool {
+    use postgres::error::SqlState;
+
+    match error.code() {
+        Some(code) => {
+            *code == SqlState::T_R_SERIALIZATION_FAILURE
+                || *code == SqlState::T_R_DEADLOCK_DETECTED
+                || code.code().starts_with("08")
+        }
+        // The errors without a state are the connection ones
+        None => !error.is_closed(),
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl PostgresSink {
+    /// Connects to a PostgreSQL database, without TLS.
+    ///
+    /// The parameters are a connection string, like
+    /// `host=localhost user=postgres dbname=singularity`.
+    pub fn connect(params: &str) -> std::io::Result<Self> {
+        postgres::Client::connect(params, postgres::NoTls)
+            .map(Self::new)
+            .map_err(std::io::Error::other)
+    }
+
+    /// Writes to a PostgreSQL client, in batches of 500 records.
+    pub fn new(client: postgres::Client) -> Self {
+        Self {
+            client,
+            batch_size: 500,
+            retry: RetryPolicy::default(),
+            path_base: None,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Sets the number of records written in a transaction.
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Sets the retries of a failed batch.
+    pub fn retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Sets the base of the paths of the files, so the stored paths are
+    /// relative to the repository rather than to the machine.
+    pub fn path_base(mut self, base: PathBase) -> Self {
+        self.path_base = Some(base);
+        self
+    }
+
+    // Returns the path of a file as stored
+    fn stored_path(&self, file_path: &str) -> String {
+        match &self.path_base {
+            Some(base) => base.relative(std::path::Path::new(file_path)),
+            None => file_path.to_string(),
+        }
+    }
+
+    /// Creates the tables written by the sink, if missing.
+    pub fn create_schema(&mut self) -> std::io::Result<()> {
+        let client = &mut self.client;
+        self.retry
+            .run(|| client.batch_execute(POSTGRES_SCHEMA), is_transient)
+            .map_err(std::io::Error::other)
+    }
+
+    /// Pushes records, writing the full batches.
+    ///
+    /// Returns the number of records written.
+    pub fn push(
+        &mut self,
+        records: impl IntoIterator<Item = FunctionRecord>,
+    ) -> std::io::Result<usize> {
+        let records = records
+            .into_iter()
+            .map(|record| FunctionRecord {
+                file_path: self.stored_path(&record.file_path),
+                ..record
+            })
+            .collect::<Vec<_>>();
+        self.pending.extend(records);
+        let mut written = 0;
+        while self.pending.len() >= self.batch_size {
+            let batch = self.pending.drain(..self.batch_size).collect::<Vec<_>>();
+            written += self.write(&batch)?;
+        }
+        Ok(written)
+    }
+
+    /// Writes the buffered records.
+    ///
+    /// Returns the number of records written.
+    pub fn flush(&mut self) -> std::io::Result<usize> {
+        let batch = std::mem::take(&mut self.pending);
+        if batch.is_empty() {
+            return Ok(0);
+        }
+        self.write(&batch)
+    }
+
+    /// Upserts complexity trends, in batches.
+    pub fn write_trends(&mut self, trends: &[ComplexityTrend]) -> std::io::Result<usize> {
+        let trends = trends
+            .iter()
+            .map(|trend| ComplexityTrend {
+                file_path: self.stored_path(&trend.file_path),
+                ..trend.clone()
+            })
+            .collect::<Vec<_>>();
+        for batch in trends.chunks(self.batch_size) {
+            let client = &mut self.client;
+            self.retry
+                .run(
+                    || {
+                        let mut transaction = client.transaction()?;
+                        let statement = transaction.prepare(UPSERT_TREND)?;
+                        for trend in batch {
+                            transaction.execute(
+                                &statement,
+                                &[
+                                    &trend.file_path,
+                                    &trend.commit_hash,
+                                    &trend.timestamp,
+                                    &trend.complexity_score,
+                                ],
+                            )?;
+                        }
+                        transaction.commit()
+                    },
+                    is_transient,
+                )
+                .map_err(std::io::Error::other)?;
+        }
+        Ok(trends.len())
+    }
+
+    fn write(&mut self, batch: &[FunctionRecord]) -> std::io::Result<usize> {
+        let client = &mut self.client;
+        self.retry
+            .run(
+                || {
+                    let mut transaction = client.transaction()?;
+                    let statement = transaction.prepare(UPSERT_FUNCTION)?;
+                    for record in batch {
+                        transaction.execute(
+                            &statement,
+                            &[
+                                &record.file_path,
+                                &(record.start_line as i64),
+                                &record.fingerprint,
+                                &record.name,
+                                &(record.end_line as i64),
+                                &record.metrics.cyclomatic,
+                                &record.metrics.cognitive,
+                                &record.metrics.sloc,
+                                &record.metrics.nargs,
+                                &record.metrics.nexits,
+                                &record.model,
+                                &record.embedding,
+                            ],
+                        )?;
+                    }
+                    transaction.commit()
+                },
+                is_transient,
+            )
+            .map_err(std::io::Error::other)?;
+        Ok(batch.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{embed_functions, metrics, ParserEngineRust, ParserTrait, StructuralEmbedding};
+
+    use super::*;
+
+    #[test]
+    fn records_with_embeddings() {
+        let code = "fn f(x: i32) -> i32 {\n    if x > 0 { x } else { 0 }\n}\n\nfn g() {}\n";
+        let path = Path::new("foo.rs");
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), path, None);
+        let space = metrics(&parser, path).unwrap();
+        let embeddings = embed_functions(&parser, path, &StructuralEmbedding)
+            .into_iter()
+            .filter(|embedding| embedding.name.as_deref() == Some("f"))
+            .collect::<Vec<_>>();
+
+        let records = function_records(&space, "src/foo.rs", &embeddings);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].name.as_deref(), Some("f"));
+        assert_eq!(records[0].file_path, "src/foo.rs");
+        assert_eq!(records[0].metrics.cyclomatic, Some(2.));
+        assert_eq!(records[0].model.as_deref(), Some("structural"));
+        assert_eq!(records[0].embedding.as_ref(), Some(&embeddings[0].vector));
+        assert_eq!(
+            (records[1].start_line, records[1].embedding.as_ref()),
+            (5, None)
+        );
+    }
+
+    #[test]
+    fn retries() {
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(3),
+        };
+        assert_eq!(policy.backoff(1), Duration::from_millis(1));
+        assert_eq!(policy.backoff(2), Duration::from_millis(2));
+        assert_eq!(policy.backoff(3), Duration::from_millis(3));
+
+        let mut attempts = 0;
+        let result = policy.run(
+            || {
+                attempts += 1;
+                if attempts < 3 {
+                    Err("transient")
+                } else {
+                    Ok(attempts)
+                }
+            },
+            |error| *error == "transient",
+        );
+        assert_eq!(result, Ok(3));
+
+        let mut attempts = 0;
+        let result: Result<(), _> = policy.run(
+            || {
+                attempts += 1;
+                Err("fatal")
+            },
+            |error| *error == "transient",
+        );
+        assert_eq!((result, attempts), (Err("fatal"), 1));
+
+        let mut attempts = 0;
+        let result: Result<(), _> = policy.run(
+            || {
+                attempts += 1;
+                Err("transient")
+            },
+            |error| *error == "transient",
+        );
+        assert_eq!((result, attempts), (Err("transient"), 3));
+    }
+}
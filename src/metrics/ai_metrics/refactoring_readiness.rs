@@ -2,11 +2,16 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::explanation::ScoreExplanation;
+use crate::UnusedReport;
+
 /// Refactoring readiness score statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RefactoringReadinessStats {
     pub readiness_score: f64,
     pub refactoring_opportunities: Vec<RefactoringOpportunity>,
+    #[serde(default)]
+    pub explanation: ScoreExplanation,
 }
 
 /// Refactoring opportunity
@@ -23,35 +28,63 @@ impl Default for RefactoringReadinessStats {
         Self {
             readiness_score: 0.0,
             refactoring_opportunities: Vec::new(),
+            explanation: ScoreExplanation::default(),
         }
     }
 }
 
 impl RefactoringReadinessStats {
     pub fn calculate_readiness_score(&mut self, code: &str) -> f64 {
+        let mut explanation = ScoreExplanation::new("100 - sum(penalties)", 100.0);
+        explanation.input("lines", code.lines().count() as f64);
         let mut score: f64 = 100.0;
 
-        // Analyze refactoring factors
-        if self.has_long_functions(code) {
-            score -= 20.0;
-        }
-
-        if self.has_duplicate_code(code) {
-            score -= 15.0;
-        }
-
-        if self.has_complex_conditionals(code) {
-            score -= 10.0;
+        // Analyze refactoring factors, each one found costing its penalty
+        let factors = [
+            ("Long Functions", self.has_long_functions(code), 20.0),
+            ("Duplicate Code", self.has_duplicate_code(code), 15.0),
+            (
+                "Complex Conditionals",
+                self.has_complex_conditionals(code),
+                10.0,
+            ),
+            ("Deep Nesting", self.has_deep_nesting(code), 15.0),
+        ];
+        for (name, found, penalty) in factors {
+            score += explanation.factor(name, if found { 1.0 } else { 0.0 }, -penalty);
         }
 
-        if self.has_deep_nesting(code) {
-            score -= 15.0;
-        }
+        // One point per opportunity found on the syntax tree
+        let opportunities = (self.refactoring_opportunities.len() as f64).min(10.0);
+        score += explanation.factor("Opportunities", opportunities, -1.0);
 
         self.readiness_score = score.max(0.0);
+        explanation.finish(self.readiness_score);
+        self.explanation = explanation;
         self.readiness_score
     }
 
+    /// Adds the unused parameters and variables of a code, found by
+    /// [`find_unused`](crate::find_unused), as refactoring opportunities.
+    pub fn add_unused_bindings(&mut self, report: &UnusedReport) {
+        self.refactoring_opportunities
+            .extend(
+                report
+                    .report
+                    .smells
+                    .iter()
+                    .map(|smell| RefactoringOpportunity {
+                        name: format!("Remove {}", smell.name),
+                        description: format!(
+                            "{} (line {})",
+                            smell.description, smell.location.line_start
+                        ),
+                        priority: 0.3,
+                        effort: 0.1,
+                    }),
+            );
+    }
+
     fn has_long_functions(&self, code: &str) -> bool {
         code.lines().count() > 50
     }
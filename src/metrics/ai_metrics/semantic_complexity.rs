@@ -3,6 +3,8 @@
 use crate::langs::LANG;
 use serde::{Deserialize, Serialize};
 
+use super::explanation::ScoreExplanation;
+
 /// Semantic complexity metric statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SemanticComplexityStats {
@@ -10,6 +12,8 @@ pub struct SemanticComplexityStats {
     pub average_complexity: f64,
     pub max_complexity: f64,
     pub min_complexity: f64,
+    #[serde(default)]
+    pub explanation: ScoreExplanation,
 }
 
 impl Default for SemanticComplexityStats {
@@ -19,13 +23,17 @@ impl Default for SemanticComplexityStats {
             average_complexity: 0.0,
             max_complexity: 0.0,
             min_complexity: f64::MAX,
+            explanation: ScoreExplanation::default(),
         }
     }
 }
 
 impl SemanticComplexityStats {
     pub fn calculate_semantic_complexity(&mut self, code: &str, language: LANG) -> f64 {
-        let patterns = self.analyze_semantic_patterns(code, language);
+        let (names, patterns): (Vec<_>, Vec<_>) = self
+            .analyze_semantic_patterns(code, language)
+            .into_iter()
+            .unzip();
         let total_complexity: f64 = patterns.iter().sum();
         let function_count = patterns.len();
 
@@ -40,16 +48,28 @@ impl SemanticComplexityStats {
         self.min_complexity = patterns.iter().fold(f64::MAX, |acc, value| acc.min(*value));
 
         self.semantic_score = (self.average_complexity / 100.0 * 100.0).min(100.0);
+
+        // The average is the sum of the complexities, each one weighted by
+        // the inverse of the number of functions
+        let mut explanation = ScoreExplanation::new("min(sum(complexity) / functions, 100)", 0.0);
+        explanation.input("functions", function_count as f64);
+        explanation.input("max_complexity", self.max_complexity);
+        for (name, complexity) in names.iter().zip(&patterns) {
+            explanation.factor(name, *complexity, 1.0 / function_count as f64);
+        }
+        explanation.finish(self.semantic_score);
+        self.explanation = explanation;
+
         self.semantic_score
     }
 
-    fn analyze_semantic_patterns(&self, code: &str, language: LANG) -> Vec<f64> {
+    fn analyze_semantic_patterns(&self, code: &str, language: LANG) -> Vec<(String, f64)> {
         let mut patterns = Vec::new();
         let functions = self.extract_functions(code, language);
 
         for func in functions {
             let complexity = self.analyze_function_complexity(&func, language);
-            patterns.push(complexity);
+            patterns.push((func.name, complexity));
         }
 
         patterns
@@ -2,11 +2,15 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::explanation::ScoreExplanation;
+
 /// Testability score statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TestabilityScoreStats {
     pub testability_score: f64,
     pub testability_factors: Vec<TestabilityFactor>,
+    #[serde(default)]
+    pub explanation: ScoreExplanation,
 }
 
 /// Testability factor
@@ -22,6 +26,7 @@ impl Default for TestabilityScoreStats {
         Self {
             testability_score: 0.0,
             testability_factors: Vec::new(),
+            explanation: ScoreExplanation::default(),
         }
     }
 }
@@ -71,6 +76,15 @@ impl TestabilityScoreStats {
             0.0
         };
 
+        let mut explanation =
+            ScoreExplanation::new("sum(factor score * weight) / sum(weights)", 0.0);
+        explanation.input("lines", code.lines().count() as f64);
+        for factor in &self.testability_factors {
+            explanation.factor(&factor.name, factor.score, factor.weight / total_weight);
+        }
+        explanation.finish(self.testability_score);
+        self.explanation = explanation;
+
         self.testability_score
     }
 
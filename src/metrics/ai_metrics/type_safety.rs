@@ -5,6 +5,8 @@
 
 use std::collections::HashMap;
 
+use super::explanation::ScoreExplanation;
+
 /// Type Safety Metrics
 #[derive(Debug, Clone, PartialEq)]
 pub struct TypeSafetyMetrics {
@@ -22,6 +24,8 @@ pub struct TypeSafetyMetrics {
     pub pattern_matching_score: f64,
     /// Per-language breakdown
     pub language_scores: HashMap<String, LanguageTypeSafety>,
+    /// The inputs and factors of the score
+    pub explanation: ScoreExplanation,
 }
 
 /// Language-specific type safety metrics
@@ -72,6 +76,19 @@ impl TypeSafetyMetrics {
             },
         );
 
+        let mut explanation = ScoreExplanation::new("sum(factor value * weight)", 0.0);
+        explanation.input("annotation_coverage", annotation_coverage);
+        explanation.input("generic_usage", generic_usage);
+        explanation.input("unsafe_ratio", unsafe_ratio);
+        explanation.input("explicit_type_ratio", explicit_type_ratio);
+        explanation.input("pattern_matching", pattern_matching);
+        explanation.factor("Annotation Coverage", annotation_coverage, 30.0);
+        explanation.factor("Generic Usage", generic_usage, 20.0);
+        explanation.factor("Safe Code", 1.0 - unsafe_ratio, 25.0);
+        explanation.factor("Explicit Types", explicit_type_ratio, 15.0);
+        explanation.factor("Pattern Matching", pattern_matching, 10.0);
+        explanation.finish(type_safety_score.clamp(0.0, 100.0));
+
         Self {
             type_safety_score: type_safety_score.clamp(0.0, 100.0),
             annotation_coverage: (annotation_coverage * 100.0).clamp(0.0, 100.0),
@@ -80,6 +97,7 @@ impl TypeSafetyMetrics {
             explicit_type_ratio: (explicit_type_ratio * 100.0).clamp(0.0, 100.0),
             pattern_matching_score: (pattern_matching * 100.0).clamp(0.0, 100.0),
             language_scores,
+            explanation,
         }
     }
 }
@@ -888,10 +888,10 @@ mod tests {
                     metric.cognitive,
                     @r#"
                 {
-                  "sum": 0.0,
-                  "average": null,
+                  "sum": 4.0,
+                  "average": 4.0,
                   "min": 0.0,
-                  "max": 0.0
+                  "max": 4.0
                 }
                 "#
                 );
@@ -1306,10 +1306,10 @@ mod tests {
                     metric.cognitive,
                     @r#"
                 {
-                  "sum": 0.0,
-                  "average": null,
+                  "sum": 3.0,
+                  "average": 3.0,
                   "min": 0.0,
-                  "max": 0.0
+                  "max": 3.0
                 }
                 "#
                 );
@@ -1473,10 +1473,10 @@ mod tests {
                     metric.cognitive,
                     @r#"
                 {
-                  "sum": 0.0,
-                  "average": null,
+                  "sum": 11.0,
+                  "average": 11.0,
                   "min": 0.0,
-                  "max": 0.0
+                  "max": 11.0
                 }
                 "#
                 );
@@ -1684,10 +1684,10 @@ mod tests {
                     metric.cognitive,
                     @r#"
                 {
-                  "sum": 0.0,
-                  "average": null,
+                  "sum": 7.0,
+                  "average": 7.0,
                   "min": 0.0,
-                  "max": 0.0
+                  "max": 7.0
                 }
                 "#
                 );
@@ -1720,10 +1720,10 @@ mod tests {
                     metric.cognitive,
                     @r#"
                 {
-                  "sum": 0.0,
-                  "average": null,
+                  "sum": 1.0,
+                  "average": 1.0,
                   "min": 0.0,
-                  "max": 0.0
+                  "max": 1.0
                 }
                 "#
                 );
@@ -410,10 +410,10 @@ mod tests {
                     metric.cyclomatic,
                     @r#"
                 {
-                  "sum": 0.0,
-                  "average": 0.0,
-                  "min": 179769313486231570000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000.0,
-                  "max": 0.0
+                  "sum": 5.0,
+                  "average": 2.5,
+                  "min": 1.0,
+                  "max": 4.0
                 }
                 "#
                 );
@@ -443,10 +443,10 @@ mod tests {
                     metric.cyclomatic,
                     @r#"
                 {
-                  "sum": 0.0,
-                  "average": 0.0,
-                  "min": 179769313486231570000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000.0,
-                  "max": 0.0
+                  "sum": 5.0,
+                  "average": 2.5,
+                  "min": 1.0,
+                  "max": 4.0
                 }
                 "#
                 );
@@ -486,10 +486,10 @@ mod tests {
                     metric.cyclomatic,
                     @r#"
                 {
-                  "sum": 0.0,
-                  "average": 0.0,
-                  "min": 179769313486231570000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000.0,
-                  "max": 0.0
+                  "sum": 7.0,
+                  "average": 3.5,
+                  "min": 3.0,
+                  "max": 4.0
                 }
                 "#
                 );
@@ -533,10 +533,10 @@ mod tests {
                     metric.cyclomatic,
                     @r#"
                 {
-                  "sum": 0.0,
-                  "average": 0.0,
-                  "min": 179769313486231570000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000.0,
-                  "max": 0.0
+                  "sum": 7.0,
+                  "average": 3.5,
+                  "min": 3.0,
+                  "max": 4.0
                 }
                 "#
                 );
@@ -183,6 +183,24 @@ impl Exit for JavaCode {
     }
 }
 
+impl Exit for GleamCode {
+    fn compute(node: &Node, stats: &mut Stats) {
+        // A Gleam function always returns its last expression, while
+        // `panic` and `todo` exit it early
+        if matches!(node.kind_id().into(), Gleam::Panic | Gleam::Todo) || Self::is_func(node) {
+            stats.exit += 1;
+        }
+    }
+}
+
+impl Exit for LuaCode {
+    fn compute(node: &Node, stats: &mut Stats) {
+        if matches!(node.kind_id().into(), Lua::ReturnStatement) {
+            stats.exit += 1;
+        }
+    }
+}
+
 implement_metric_trait!(
     Exit,
     KotlinCode,
@@ -190,8 +208,6 @@ implement_metric_trait!(
     CcommentCode,
     ElixirCode,
     ErlangCode,
-    GleamCode,
-    LuaCode,
     GoCode,
     CsharpCode
 );
@@ -261,8 +277,8 @@ mod tests {
                 @r#"
             {
               "sum": 0.0,
-              "average": 0.0,
-              "min": 18446744073709552000.0,
+              "average": null,
+              "min": 0.0,
               "max": 0.0
             }
             "#
@@ -435,4 +451,102 @@ mod tests {
             },
         );
     }
+
+    #[test]
+    fn gleam_simple_function() {
+        check_metrics::<GleamParser>(
+            "pub fn f(a) {
+                 case a {
+                     0 -> panic
+                     _ -> a
+                 }
+             }",
+            "foo.gleam",
+            |metric| {
+                // 1 function returning its last expression and 1 panic
+                insta::assert_json_snapshot!(
+                    metric.nexits,
+                    @r###"
+                    {
+                      "sum": 2.0,
+                      "average": 2.0,
+                      "min": 0.0,
+                      "max": 1.0
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn gleam_more_functions() {
+        check_metrics::<GleamParser>(
+            "fn f(a) {
+                 a + 1
+             }
+             fn g() {
+                 todo
+             }",
+            "foo.gleam",
+            |metric| {
+                // 2 functions
+                insta::assert_json_snapshot!(
+                    metric.nexits,
+                    @r###"
+                    {
+                      "sum": 3.0,
+                      "average": 1.5,
+                      "min": 0.0,
+                      "max": 1.0
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn lua_no_exit() {
+        check_metrics::<LuaParser>("local a = 42", "foo.lua", |metric| {
+            // 0 functions
+            insta::assert_json_snapshot!(
+                metric.nexits,
+                @r###"
+                {
+                  "sum": 0.0,
+                  "average": null,
+                  "min": 0.0,
+                  "max": 0.0
+                }"###
+            );
+        });
+    }
+
+    #[test]
+    fn lua_more_functions() {
+        check_metrics::<LuaParser>(
+            "function f(a, b)
+                 if a then
+                     return a
+                 end
+                 return b
+             end
+             local function g(a)
+                 return a
+             end",
+            "foo.lua",
+            |metric| {
+                // 2 functions
+                insta::assert_json_snapshot!(
+                    metric.nexits,
+                    @r###"
+                    {
+                      "sum": 3.0,
+                      "average": 1.5,
+                      "min": 0.0,
+                      "max": 2.0
+                    }"###
+                );
+            },
+        );
+    }
 }
@@ -0,0 +1,158 @@
+//! Language-specific metrics.
+//!
+//! Metrics that only make sense for one language live in a submodule of
+//! this module and are reported in a section of [`CodeMetrics`] named after
+//! the language they belong to.
+//!
+//! [`CodeMetrics`]: crate::CodeMetrics
+
+use std::fmt;
+
+use serde::{Serialize, Serializer};
+
+use crate::{checker::Checker, macros::implement_metric_trait, *};
+
+pub mod cpp;
+pub mod go;
+pub mod java;
+pub mod javascript;
+pub mod otp;
+pub mod python;
+
+macro_rules! mk_extras {
+    ( $( ($camel:ident, $module:ident, $module_mut:ident) ),* ) => {
+        /// The language-specific metrics of a space.
+        #[derive(Clone, Debug, Default)]
+        pub enum Stats {
+            /// The language has no specific metrics.
+            #[default]
+            None,
+            $(
+                #[doc = concat!("`", stringify!($camel), "` metrics.")]
+                $camel($module::Stats),
+            )*
+        }
+
+        impl Serialize for Stats {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                match self {
+                    Stats::None => serializer.serialize_none(),
+                    $(
+                        Stats::$camel(stats) => stats.serialize(serializer),
+                    )*
+                }
+            }
+        }
+
+        impl fmt::Display for Stats {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match self {
+                    Stats::None => Ok(()),
+                    $(
+                        Stats::$camel(stats) => stats.fmt(f),
+                    )*
+                }
+            }
+        }
+
+        impl Stats {
+            /// Merges a second language-specific metric into the first one
+            pub fn merge(&mut self, other: &Stats) {
+                match (self, other) {
+                    (this @ Stats::None, other) => *this = other.clone(),
+                    $(
+                        (Stats::$camel(this), Stats::$camel(other)) => this.merge(other),
+                    )*
+                    _ => {}
+                }
+            }
+
+            /// Returns the name of the section containing the metrics.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    Stats::None => "",
+                    $(
+                        Stats::$camel(_) => stringify!($module),
+                    )*
+                }
+            }
+
+            /// Returns the metric names and values, in serialization order.
+            pub fn values(&self) -> Vec<(&'static str, f64)> {
+                match self {
+                    Stats::None => Vec::new(),
+                    $(
+                        Stats::$camel(stats) => stats.values(),
+                    )*
+                }
+            }
+
+            $(
+                #[doc = concat!("Returns the `", stringify!($camel), "` metrics, if any.")]
+                pub fn $module(&self) -> Option<&$module::Stats> {
+                    match self {
+                        Stats::$camel(stats) => Some(stats),
+                        _ => None,
+                    }
+                }
+
+                pub(crate) fn $module_mut(&mut self) -> &mut $module::Stats {
+                    if !matches!(self, Stats::$camel(_)) {
+                        *self = Stats::$camel($module::Stats::default());
+                    }
+                    match self {
+                        Stats::$camel(stats) => stats,
+                        _ => unreachable!(),
+                    }
+                }
+            )*
+        }
+    };
+}
+
+mk_extras!(
+    (Python, python, python_mut),
+    (Go, go, go_mut),
+    (Javascript, javascript, javascript_mut),
+    (Cpp, cpp, cpp_mut),
+    (Otp, otp, otp_mut),
+    (Java, java, java_mut)
+);
+
+impl Stats {
+    // Checks if there are no language-specific metrics
+    #[inline(always)]
+    pub(crate) fn is_disabled(&self) -> bool {
+        matches!(self, Stats::None)
+    }
+
+    /// Returns the value of the language-specific metric called `name`,
+    /// so that thresholds can be checked against it.
+    pub fn get(&self, name: &str) -> Option<f64> {
+        self.values()
+            .into_iter()
+            .find(|(metric, _)| *metric == name)
+            .map(|(_, value)| value)
+    }
+}
+
+pub trait Extras
+where
+    Self: Checker,
+{
+    fn compute(node: &Node, stats: &mut Stats);
+}
+
+implement_metric_trait!(
+    Extras,
+    RustCode,
+    PreprocCode,
+    CcommentCode,
+    KotlinCode,
+    GleamCode,
+    LuaCode,
+    CsharpCode
+);
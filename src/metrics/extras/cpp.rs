@@ -0,0 +1,244 @@
+use std::fmt;
+
+use serde::{
+    ser::{SerializeStruct, Serializer},
+    Serialize,
+};
+
+use crate::{extras::Extras, *};
+
+/// The `C++` specific metrics.
+///
+/// This metric tracks the modernization of `C++` code: how many methods
+/// are `virtual` or `override` a base method, and how memory is managed,
+/// comparing raw `new`/`delete` expressions with smart pointers.
+#[derive(Clone, Debug, Default)]
+pub struct Stats {
+    classes: usize,
+    virtual_methods: usize,
+    overrides: usize,
+    raw_news: usize,
+    raw_deletes: usize,
+    smart_pointer_types: usize,
+    smart_pointer_factories: usize,
+}
+
+impl Serialize for Stats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let values = self.values();
+        let mut st = serializer.serialize_struct("cpp", values.len())?;
+        for (name, value) in values {
+            st.serialize_field(name, &value)?;
+        }
+        st.end()
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let values = self
+            .values()
+            .into_iter()
+            .map(|(name, value)| format!("{name}: {value}"))
+            .collect::<Vec<_>>();
+        write!(f, "{}", values.join(", "))
+    }
+}
+
+impl Stats {
+    /// Merges a second `C++` metric into the first one
+    pub fn merge(&mut self, other: &Stats) {
+        self.classes += other.classes;
+        self.virtual_methods += other.virtual_methods;
+        self.overrides += other.overrides;
+        self.raw_news += other.raw_news;
+        self.raw_deletes += other.raw_deletes;
+        self.smart_pointer_types += other.smart_pointer_types;
+        self.smart_pointer_factories += other.smart_pointer_factories;
+    }
+
+    /// Returns the number of classes and structs in a space.
+    #[inline(always)]
+    pub fn classes(&self) -> f64 {
+        self.classes as f64
+    }
+
+    /// Returns the number of `virtual` methods in a space.
+    #[inline(always)]
+    pub fn virtual_methods(&self) -> f64 {
+        self.virtual_methods as f64
+    }
+
+    /// Returns the number of methods marked `override` in a space.
+    #[inline(always)]
+    pub fn overrides(&self) -> f64 {
+        self.overrides as f64
+    }
+
+    /// Returns the number of raw `new` expressions in a space.
+    #[inline(always)]
+    pub fn raw_news(&self) -> f64 {
+        self.raw_news as f64
+    }
+
+    /// Returns the number of raw `delete` expressions in a space.
+    #[inline(always)]
+    pub fn raw_deletes(&self) -> f64 {
+        self.raw_deletes as f64
+    }
+
+    /// Returns the number of smart pointer types (`unique_ptr`,
+    /// `shared_ptr`, `weak_ptr`) used in a space.
+    #[inline(always)]
+    pub fn smart_pointer_types(&self) -> f64 {
+        self.smart_pointer_types as f64
+    }
+
+    /// Returns the number of smart pointer factory calls
+    /// (`make_unique`, `make_shared`, `allocate_shared`) in a space.
+    #[inline(always)]
+    pub fn smart_pointer_factories(&self) -> f64 {
+        self.smart_pointer_factories as f64
+    }
+
+    /// Returns the `RAII` ratio
+    ///
+    /// This value is computed dividing the number of smart pointer
+    /// factory calls by the total number of allocations, raw `new`
+    /// expressions included.
+    ///
+    /// If there are no allocations, its value is `NAN`.
+    #[inline(always)]
+    pub fn raii_ratio(&self) -> f64 {
+        self.smart_pointer_factories() / (self.smart_pointer_factories() + self.raw_news())
+    }
+
+    pub(crate) fn values(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("classes", self.classes()),
+            ("virtual_methods", self.virtual_methods()),
+            ("overrides", self.overrides()),
+            ("raw_news", self.raw_news()),
+            ("raw_deletes", self.raw_deletes()),
+            ("smart_pointer_types", self.smart_pointer_types()),
+            ("smart_pointer_factories", self.smart_pointer_factories()),
+            ("raii_ratio", self.raii_ratio()),
+        ]
+    }
+}
+
+const SMART_POINTERS: &[&str] = &["unique_ptr", "shared_ptr", "weak_ptr"];
+
+const SMART_POINTER_FACTORIES: &[&str] = &["make_unique", "make_shared", "allocate_shared"];
+
+impl Extras for CppCode {
+    fn compute(node: &Node, stats: &mut extras::Stats) {
+        let stats = stats.cpp_mut();
+
+        match node.kind() {
+            "class_specifier" | "struct_specifier"
+                if node.child_by_field_name("body").is_some() =>
+            {
+                stats.classes += 1;
+            }
+            // `virtual` in a base class clause is virtual inheritance
+            "virtual"
+                if node
+                    .parent()
+                    .is_some_and(|parent| parent.kind() != "base_class_clause") =>
+            {
+                stats.virtual_methods += 1;
+            }
+            "virtual_specifier" if node_text_equals_any(node, &["override"]) => {
+                stats.overrides += 1;
+            }
+            "new_expression" => {
+                stats.raw_news += 1;
+            }
+            "delete_expression" => {
+                stats.raw_deletes += 1;
+            }
+            "template_type"
+                if node
+                    .child_by_field_name("name")
+                    .is_some_and(|name| node_text_equals_any(&name, SMART_POINTERS)) =>
+            {
+                stats.smart_pointer_types += 1;
+            }
+            "template_function"
+                if node
+                    .child_by_field_name("name")
+                    .is_some_and(|name| node_text_equals_any(&name, SMART_POINTER_FACTORIES)) =>
+            {
+                stats.smart_pointer_factories += 1;
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tools::check_metrics;
+
+    use super::*;
+
+    #[test]
+    fn cpp_virtual_and_overrides() {
+        check_metrics::<CppParser>(
+            "class Base {
+                 virtual void f();
+                 virtual int g() const = 0;
+                 virtual ~Base() {}
+             };
+             struct Derived : virtual Base {
+                 void f() override;
+                 int g() const final { return 0; }
+             };
+             struct Forward;",
+            "foo.cpp",
+            |metric| {
+                let cpp = metric.extras.cpp().unwrap();
+                // `final` does not count as `override`
+                insta::assert_json_snapshot!(cpp.classes(), @"2.0");
+                insta::assert_json_snapshot!(cpp.virtual_methods(), @"3.0");
+                insta::assert_json_snapshot!(cpp.overrides(), @"1.0");
+            },
+        );
+    }
+
+    #[test]
+    fn cpp_raw_and_smart_pointers() {
+        check_metrics::<CppParser>(
+            "void f() {
+                 int *p = new int(1);
+                 int *q = new int[10];
+                 delete p;
+                 delete[] q;
+                 std::unique_ptr<Foo> u = std::make_unique<Foo>();
+                 auto s = std::make_shared<Bar>(1, 2);
+                 std::weak_ptr<Bar> w = s;
+             }",
+            "foo.cpp",
+            |metric| {
+                insta::assert_json_snapshot!(
+                    metric.extras.cpp().unwrap(),
+                    @r###"
+                    {
+                      "classes": 0.0,
+                      "virtual_methods": 0.0,
+                      "overrides": 0.0,
+                      "raw_news": 2.0,
+                      "raw_deletes": 2.0,
+                      "smart_pointer_types": 2.0,
+                      "smart_pointer_factories": 2.0,
+                      "raii_ratio": 0.5
+                    }"###
+                );
+            },
+        );
+    }
+}
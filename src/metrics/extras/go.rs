@@ -0,0 +1,295 @@
+use std::fmt;
+
+use serde::{
+    ser::{SerializeStruct, Serializer},
+    Serialize,
+};
+
+use crate::{extras::Extras, *};
+
+/// The `Go` specific metrics.
+///
+/// This metric counts the concurrency primitives used by a space
+/// (`go` statements, channel operations and `defer`s) and measures how
+/// often the errors returned by calls are checked with `if err != nil`.
+#[derive(Clone, Debug, Default)]
+pub struct Stats {
+    goroutines: usize,
+    channel_sends: usize,
+    channel_receives: usize,
+    defers: usize,
+    error_checks: usize,
+    error_calls: usize,
+}
+
+impl Serialize for Stats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let values = self.values();
+        let mut st = serializer.serialize_struct("go", values.len())?;
+        for (name, value) in values {
+            st.serialize_field(name, &value)?;
+        }
+        st.end()
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let values = self
+            .values()
+            .into_iter()
+            .map(|(name, value)| format!("{name}: {value}"))
+            .collect::<Vec<_>>();
+        write!(f, "{}", values.join(", "))
+    }
+}
+
+impl Stats {
+    /// Merges a second `Go` metric into the first one
+    pub fn merge(&mut self, other: &Stats) {
+        self.goroutines += other.goroutines;
+        self.channel_sends += other.channel_sends;
+        self.channel_receives += other.channel_receives;
+        self.defers += other.defers;
+        self.error_checks += other.error_checks;
+        self.error_calls += other.error_calls;
+    }
+
+    /// Returns the number of `go` statements in a space.
+    #[inline(always)]
+    pub fn goroutines(&self) -> f64 {
+        self.goroutines as f64
+    }
+
+    /// Returns the number of channel sends in a space.
+    #[inline(always)]
+    pub fn channel_sends(&self) -> f64 {
+        self.channel_sends as f64
+    }
+
+    /// Returns the number of channel receives in a space.
+    #[inline(always)]
+    pub fn channel_receives(&self) -> f64 {
+        self.channel_receives as f64
+    }
+
+    /// Returns the number of channel operations in a space.
+    #[inline(always)]
+    pub fn channel_ops(&self) -> f64 {
+        self.channel_sends() + self.channel_receives()
+    }
+
+    /// Returns the number of `defer` statements in a space.
+    #[inline(always)]
+    pub fn defers(&self) -> f64 {
+        self.defers as f64
+    }
+
+    /// Returns the number of `if err != nil` checks in a space.
+    #[inline(always)]
+    pub fn error_checks(&self) -> f64 {
+        self.error_checks as f64
+    }
+
+    /// Returns the number of calls whose error result is assigned
+    /// in a space.
+    #[inline(always)]
+    pub fn error_calls(&self) -> f64 {
+        self.error_calls as f64
+    }
+
+    /// Returns the error-check density
+    ///
+    /// This value is computed dividing the number of `if err != nil` checks
+    /// by the number of calls returning an error.
+    ///
+    /// If there are no calls returning an error, its value is `NAN`.
+    #[inline(always)]
+    pub fn error_check_density(&self) -> f64 {
+        self.error_checks() / self.error_calls()
+    }
+
+    pub(crate) fn values(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("goroutines", self.goroutines()),
+            ("channel_ops", self.channel_ops()),
+            ("channel_sends", self.channel_sends()),
+            ("channel_receives", self.channel_receives()),
+            ("defers", self.defers()),
+            ("error_checks", self.error_checks()),
+            ("error_calls", self.error_calls()),
+            ("error_check_density", self.error_check_density()),
+        ]
+    }
+}
+
+// `err`, `readErr`, ...
+fn is_error_identifier(node: &Node) -> bool {
+    node.kind() == "identifier"
+        && with_current_code(|code| {
+            node_text(node, code).is_some_and(|name| name == "err" || name.ends_with("Err"))
+        })
+        .unwrap_or(false)
+}
+
+// `err != nil` or `nil != err`
+fn is_error_check(node: &Node) -> bool {
+    let is_inequality = node.kind() == "binary_expression"
+        && node
+            .child_by_field_name("operator")
+            .is_some_and(|op| op.kind() == "!=");
+    if !is_inequality {
+        return false;
+    }
+    match (
+        node.child_by_field_name("left"),
+        node.child_by_field_name("right"),
+    ) {
+        (Some(left), Some(right)) => {
+            (is_error_identifier(&left) && right.kind() == "nil")
+                || (left.kind() == "nil" && is_error_identifier(&right))
+        }
+        _ => false,
+    }
+}
+
+// `x, err := f()` or `err = f()`
+fn is_error_call(node: &Node) -> bool {
+    let assigns_error = node.child_by_field_name("left").is_some_and(|left| {
+        left.children()
+            .filter(|child| child.is_named())
+            .last()
+            .is_some_and(|last| is_error_identifier(&last))
+    });
+    assigns_error
+        && node.child_by_field_name("right").is_some_and(|right| {
+            right
+                .children()
+                .any(|child| child.kind() == "call_expression")
+        })
+}
+
+impl Extras for GoCode {
+    fn compute(node: &Node, stats: &mut extras::Stats) {
+        let stats = stats.go_mut();
+
+        match node.kind() {
+            "go_statement" => {
+                stats.goroutines += 1;
+            }
+            "defer_statement" => {
+                stats.defers += 1;
+            }
+            "send_statement" => {
+                stats.channel_sends += 1;
+            }
+            "unary_expression"
+                if node
+                    .child_by_field_name("operator")
+                    .is_some_and(|op| op.kind() == "<-") =>
+            {
+                stats.channel_receives += 1;
+            }
+            "if_statement"
+                if node
+                    .child_by_field_name("condition")
+                    .is_some_and(|condition| is_error_check(&condition)) =>
+            {
+                stats.error_checks += 1;
+            }
+            "short_var_declaration" | "assignment_statement" if is_error_call(node) => {
+                stats.error_calls += 1;
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tools::check_func_space;
+
+    use super::*;
+
+    #[test]
+    fn go_concurrency() {
+        check_func_space::<GoParser, _>(
+            "package main
+
+             func produce(ch chan int) {
+                 defer close(ch)
+                 ch <- 1
+             }
+
+             func consume(ch chan int, done chan bool) {
+                 go produce(ch)
+                 go func() {
+                     for v := range ch {
+                         _ = v
+                     }
+                     done <- true
+                 }()
+                 select {
+                 case v := <-ch:
+                     _ = v
+                 case <-done:
+                 }
+             }",
+            "foo.go",
+            |func_space| {
+                let go = func_space.metrics.extras.go().unwrap();
+                insta::assert_json_snapshot!(go.goroutines(), @"2.0");
+                insta::assert_json_snapshot!(go.channel_sends(), @"2.0");
+                insta::assert_json_snapshot!(go.channel_receives(), @"2.0");
+                insta::assert_json_snapshot!(go.defers(), @"1.0");
+
+                // The metrics of each function are reported in its own space
+                let produce = func_space.spaces[0].metrics.extras.go().unwrap();
+                insta::assert_json_snapshot!(produce.channel_ops(), @"1.0");
+                insta::assert_json_snapshot!(produce.defers(), @"1.0");
+            },
+        );
+    }
+
+    #[test]
+    fn go_error_checks() {
+        check_func_space::<GoParser, _>(
+            "package main
+
+             func f() error {
+                 x, err := g()
+                 if err != nil {
+                     return err
+                 }
+                 if err := h(x); err != nil {
+                     return err
+                 }
+                 data, readErr := read()
+                 _ = data
+                 err = k()
+                 return err
+             }",
+            "foo.go",
+            |func_space| {
+                // 2 checks over 4 calls returning an error
+                insta::assert_json_snapshot!(
+                    func_space.metrics.extras,
+                    @r###"
+                    {
+                      "goroutines": 0.0,
+                      "channel_ops": 0.0,
+                      "channel_sends": 0.0,
+                      "channel_receives": 0.0,
+                      "defers": 0.0,
+                      "error_checks": 2.0,
+                      "error_calls": 4.0,
+                      "error_check_density": 0.5
+                    }"###
+                );
+            },
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use std::fmt;
+
+use serde::{
+    ser::{SerializeStruct, Serializer},
+    Serialize,
+};
+
+use crate::{extras::Extras, *};
+
+/// The `Java` specific metrics.
+///
+/// This metric measures how much framework-heavy code relies on
+/// annotations, how many checked exceptions methods and constructors
+/// declare in their `throws` clauses, and how many anonymous classes
+/// are instantiated.
+#[derive(Clone, Debug, Default)]
+pub struct Stats {
+    declarations: usize,
+    annotations: usize,
+    annotations_max: usize,
+    throws_clauses: usize,
+    thrown_types: usize,
+    thrown_types_max: usize,
+    anonymous_classes: usize,
+}
+
+impl Serialize for Stats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let values = self.values();
+        let mut st = serializer.serialize_struct("java", values.len())?;
+        for (name, value) in values {
+            st.serialize_field(name, &value)?;
+        }
+        st.end()
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let values = self
+            .values()
+            .into_iter()
+            .map(|(name, value)| format!("{name}: {value}"))
+            .collect::<Vec<_>>();
+        write!(f, "{}", values.join(", "))
+    }
+}
+
+impl Stats {
+    /// Merges a second `Java` metric into the first one
+    pub fn merge(&mut self, other: &Stats) {
+        self.declarations += other.declarations;
+        self.annotations += other.annotations;
+        self.annotations_max = self.annotations_max.max(other.annotations_max);
+        self.throws_clauses += other.throws_clauses;
+        self.thrown_types += other.thrown_types;
+        self.thrown_types_max = self.thrown_types_max.max(other.thrown_types_max);
+        self.anonymous_classes += other.anonymous_classes;
+    }
+
+    /// Returns the number of annotations applied to declarations
+    /// in a space.
+    #[inline(always)]
+    pub fn annotations(&self) -> f64 {
+        self.annotations as f64
+    }
+
+    /// Returns the annotation density
+    ///
+    /// This value is computed dividing the number of annotations by the
+    /// number of type, field, method and constructor declarations.
+    ///
+    /// If there are no declarations, its value is `NAN`.
+    #[inline(always)]
+    pub fn annotations_average(&self) -> f64 {
+        self.annotations() / self.declarations as f64
+    }
+
+    /// Returns the maximum number of annotations applied to a declaration
+    /// in a space.
+    #[inline(always)]
+    pub fn annotations_max(&self) -> f64 {
+        self.annotations_max as f64
+    }
+
+    /// Returns the number of `throws` clauses in a space.
+    #[inline(always)]
+    pub fn throws_clauses(&self) -> f64 {
+        self.throws_clauses as f64
+    }
+
+    /// Returns the number of exception types listed by the `throws`
+    /// clauses of a space.
+    #[inline(always)]
+    pub fn thrown_types(&self) -> f64 {
+        self.thrown_types as f64
+    }
+
+    /// Returns the average size of the `throws` clauses of a space.
+    ///
+    /// If there are no `throws` clauses, its value is `NAN`.
+    #[inline(always)]
+    pub fn thrown_types_average(&self) -> f64 {
+        self.thrown_types() / self.throws_clauses()
+    }
+
+    /// Returns the size of the largest `throws` clause of a space.
+    #[inline(always)]
+    pub fn thrown_types_max(&self) -> f64 {
+        self.thrown_types_max as f64
+    }
+
+    /// Returns the number of anonymous classes in a space.
+    #[inline(always)]
+    pub fn anonymous_classes(&self) -> f64 {
+        self.anonymous_classes as f64
+    }
+
+    pub(crate) fn values(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("annotations", self.annotations()),
+            ("annotations_average", self.annotations_average()),
+            ("annotations_max", self.annotations_max()),
+            ("throws_clauses", self.throws_clauses()),
+            ("thrown_types", self.thrown_types()),
+            ("thrown_types_average", self.thrown_types_average()),
+            ("thrown_types_max", self.thrown_types_max()),
+            ("anonymous_classes", self.anonymous_classes()),
+        ]
+    }
+}
+
+const DECLARATIONS: &[&str] = &[
+    "class_declaration",
+    "interface_declaration",
+    "enum_declaration",
+    "record_declaration",
+    "annotation_type_declaration",
+    "field_declaration",
+    "method_declaration",
+    "constructor_declaration",
+];
+
+impl Extras for JavaCode {
+    fn compute(node: &Node, stats: &mut extras::Stats) {
+        let stats = stats.java_mut();
+
+        match node.kind() {
+            kind if DECLARATIONS.contains(&kind) => {
+                stats.declarations += 1;
+                // Annotations are the first modifiers of a declaration
+                let annotations = node
+                    .children()
+                    .find(|child| child.kind() == "modifiers")
+                    .map_or(0, |modifiers| {
+                        modifiers
+                            .children()
+                            .filter(|child| {
+                                matches!(child.kind(), "annotation" | "marker_annotation")
+                            })
+                            .count()
+                    });
+                stats.annotations += annotations;
+                stats.annotations_max = stats.annotations_max.max(annotations);
+            }
+            // The `throws` keyword shares its name with the clause
+            "throws" if node.is_named() => {
+                let thrown_types = node.children().filter(|child| child.is_named()).count();
+                stats.throws_clauses += 1;
+                stats.thrown_types += thrown_types;
+                stats.thrown_types_max = stats.thrown_types_max.max(thrown_types);
+            }
+            "object_creation_expression"
+                if node.children().any(|child| child.kind() == "class_body") =>
+            {
+                stats.anonymous_classes += 1;
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tools::check_metrics;
+
+    use super::*;
+
+    #[test]
+    fn java_annotations() {
+        check_metrics::<JavaParser>(
+            "@Service
+             public class A {
+                 @Autowired @Qualifier(\"b\") private B b;
+                 private int count;
+
+                 @Override
+                 public String toString() { return \"\"; }
+
+                 public void run(@Nullable String s) {}
+             }",
+            "foo.java",
+            |metric| {
+                // Parameter annotations don't belong to a declaration
+                let java = metric.extras.java().unwrap();
+                insta::assert_json_snapshot!(java.annotations(), @"4.0");
+                insta::assert_json_snapshot!(java.annotations_average(), @"0.8");
+                insta::assert_json_snapshot!(java.annotations_max(), @"2.0");
+            },
+        );
+    }
+
+    #[test]
+    fn java_throws_and_anonymous_classes() {
+        check_metrics::<JavaParser>(
+            "class A {
+                 A() throws IOException {}
+
+                 void run() throws IOException, SQLException, TimeoutException {
+                     Runnable r = new Runnable() {
+                         public void run() {}
+                     };
+                     Object o = new Object();
+                 }
+             }",
+            "foo.java",
+            |metric| {
+                insta::assert_json_snapshot!(
+                    metric.extras,
+                    @r###"
+                    {
+                      "annotations": 0.0,
+                      "annotations_average": 0.0,
+                      "annotations_max": 0.0,
+                      "throws_clauses": 2.0,
+                      "thrown_types": 4.0,
+                      "thrown_types_average": 2.0,
+                      "thrown_types_max": 3.0,
+                      "anonymous_classes": 1.0
+                    }"###
+                );
+            },
+        );
+    }
+}
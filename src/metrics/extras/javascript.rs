@@ -0,0 +1,570 @@
+use std::fmt;
+
+use serde::{
+    ser::{SerializeStruct, Serializer},
+    Serialize,
+};
+
+use crate::{extras::Extras, *};
+
+/// The `JavaScript` and `TypeScript` specific metrics.
+///
+/// This metric measures the asynchronous style of a space: how deeply
+/// callbacks are nested, how long `.then()` chains grow and how many
+/// functions mix `await` with promise chains.
+///
+/// For `JSX` and `TSX` code, it also measures the `React` function
+/// components of a space: how many hooks they call, how many props they
+/// take and how deeply their `JSX` elements are nested.
+#[derive(Clone, Debug, Default)]
+pub struct Stats {
+    callbacks: usize,
+    callback_depth_max: usize,
+    promise_chains: usize,
+    promise_chain_max: usize,
+    mixed_async_functions: usize,
+    components: usize,
+    hooks: usize,
+    hooks_max: usize,
+    props: usize,
+    props_max: usize,
+    jsx_depth_max: usize,
+}
+
+impl Serialize for Stats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let values = self.values();
+        let mut st = serializer.serialize_struct("javascript", values.len())?;
+        for (name, value) in values {
+            st.serialize_field(name, &value)?;
+        }
+        st.end()
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let values = self
+            .values()
+            .into_iter()
+            .map(|(name, value)| format!("{name}: {value}"))
+            .collect::<Vec<_>>();
+        write!(f, "{}", values.join(", "))
+    }
+}
+
+impl Stats {
+    /// Merges a second `JavaScript` metric into the first one
+    pub fn merge(&mut self, other: &Stats) {
+        self.callbacks += other.callbacks;
+        self.callback_depth_max = self.callback_depth_max.max(other.callback_depth_max);
+        self.promise_chains += other.promise_chains;
+        self.promise_chain_max = self.promise_chain_max.max(other.promise_chain_max);
+        self.mixed_async_functions += other.mixed_async_functions;
+        self.components += other.components;
+        self.hooks += other.hooks;
+        self.hooks_max = self.hooks_max.max(other.hooks_max);
+        self.props += other.props;
+        self.props_max = self.props_max.max(other.props_max);
+        self.jsx_depth_max = self.jsx_depth_max.max(other.jsx_depth_max);
+    }
+
+    /// Returns the number of functions passed as arguments in a space.
+    #[inline(always)]
+    pub fn callbacks(&self) -> f64 {
+        self.callbacks as f64
+    }
+
+    /// Returns the maximum depth of nested callbacks in a space.
+    #[inline(always)]
+    pub fn callback_depth_max(&self) -> f64 {
+        self.callback_depth_max as f64
+    }
+
+    /// Returns the number of promise chains in a space.
+    #[inline(always)]
+    pub fn promise_chains(&self) -> f64 {
+        self.promise_chains as f64
+    }
+
+    /// Returns the maximum number of `.then()`, `.catch()` and
+    /// `.finally()` calls chained together in a space.
+    #[inline(always)]
+    pub fn promise_chain_max(&self) -> f64 {
+        self.promise_chain_max as f64
+    }
+
+    /// Returns the number of functions using both `await` and
+    /// promise chains in a space.
+    #[inline(always)]
+    pub fn mixed_async_functions(&self) -> f64 {
+        self.mixed_async_functions as f64
+    }
+
+    /// Returns the number of `React` function components in a space.
+    #[inline(always)]
+    pub fn components(&self) -> f64 {
+        self.components as f64
+    }
+
+    /// Returns the number of hooks called by the components of a space.
+    #[inline(always)]
+    pub fn hooks(&self) -> f64 {
+        self.hooks as f64
+    }
+
+    /// Returns the average number of hooks per component in a space.
+    ///
+    /// If there are no components, its value is `NAN`.
+    #[inline(always)]
+    pub fn hooks_average(&self) -> f64 {
+        self.hooks() / self.components()
+    }
+
+    /// Returns the maximum number of hooks called by a component
+    /// in a space.
+    #[inline(always)]
+    pub fn hooks_max(&self) -> f64 {
+        self.hooks_max as f64
+    }
+
+    /// Returns the average number of props per component in a space.
+    ///
+    /// If there are no components, its value is `NAN`.
+    #[inline(always)]
+    pub fn props_average(&self) -> f64 {
+        self.props as f64 / self.components()
+    }
+
+    /// Returns the maximum number of props taken by a component
+    /// in a space.
+    #[inline(always)]
+    pub fn props_max(&self) -> f64 {
+        self.props_max as f64
+    }
+
+    /// Returns the maximum nesting depth of `JSX` elements in a space.
+    #[inline(always)]
+    pub fn jsx_depth_max(&self) -> f64 {
+        self.jsx_depth_max as f64
+    }
+
+    pub(crate) fn values(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("callbacks", self.callbacks()),
+            ("callback_depth_max", self.callback_depth_max()),
+            ("promise_chains", self.promise_chains()),
+            ("promise_chain_max", self.promise_chain_max()),
+            ("mixed_async_functions", self.mixed_async_functions()),
+            ("components", self.components()),
+            ("hooks", self.hooks()),
+            ("hooks_average", self.hooks_average()),
+            ("hooks_max", self.hooks_max()),
+            ("props_average", self.props_average()),
+            ("props_max", self.props_max()),
+            ("jsx_depth_max", self.jsx_depth_max()),
+        ]
+    }
+}
+
+const FUNCTIONS: &[&str] = &[
+    "function_declaration",
+    "function_expression",
+    "function",
+    "arrow_function",
+    "method_definition",
+    "generator_function_declaration",
+    "generator_function",
+];
+
+const PROMISE_METHODS: &[&str] = &["then", "catch", "finally"];
+
+const JSX_ELEMENTS: &[&str] = &["jsx_element", "jsx_self_closing_element"];
+
+fn is_function(node: &Node) -> bool {
+    FUNCTIONS.contains(&node.kind())
+}
+
+// A function passed as an argument of a call
+fn is_callback(node: &Node) -> bool {
+    is_function(node)
+        && node
+            .parent()
+            .is_some_and(|parent| parent.kind() == "arguments")
+}
+
+// Returns the object `x` of a `x.then(...)` call
+fn promise_call_object<'a>(node: &Node<'a>) -> Option<Node<'a>> {
+    if node.kind() != "call_expression" {
+        return None;
+    }
+    let function = node.child(0).filter(|function| {
+        function.kind() == "member_expression"
+            && function
+                .child_by_field_name("property")
+                .is_some_and(|property| node_text_equals_any(&property, PROMISE_METHODS))
+    })?;
+    function.child(0)
+}
+
+// Returns true if the body of a function, excluding nested functions,
+// satisfies the predicate
+fn function_body_any(node: &Node, pred: fn(&Node) -> bool) -> bool {
+    node.children()
+        .any(|child| !is_function(&child) && (pred(&child) || function_body_any(&child, pred)))
+}
+
+// Collects the nodes of the body of a function, excluding nested functions
+fn function_body_nodes<'a>(node: &Node<'a>, nodes: &mut Vec<Node<'a>>) {
+    for child in node.children().filter(|child| !is_function(child)) {
+        nodes.push(child);
+        function_body_nodes(&child, nodes);
+    }
+}
+
+fn text(node: &Node) -> Option<String> {
+    with_current_code(|code| node_text(node, code).map(str::to_string)).flatten()
+}
+
+// `function Foo() {}`, `const Foo = () => {}` and `const Foo = function () {}`
+fn function_name(node: &Node) -> Option<String> {
+    match node.kind() {
+        "function_declaration" => text(&node.child_by_field_name("name")?),
+        "arrow_function" | "function_expression" | "function" => {
+            let parent = node.parent()?;
+            if parent.kind() != "variable_declarator" {
+                return None;
+            }
+            text(&parent.child_by_field_name("name")?)
+        }
+        _ => None,
+    }
+}
+
+// `useState`, `useEffect`, `React.useMemo`, ...
+fn is_hook_call(node: &Node) -> bool {
+    node.kind() == "call_expression"
+        && node
+            .child_by_field_name("function")
+            .is_some_and(|function| {
+                let name = match function.kind() {
+                    "member_expression" => function
+                        .child_by_field_name("property")
+                        .and_then(|property| text(&property)),
+                    _ => text(&function),
+                };
+                name.is_some_and(|name| {
+                    name.strip_prefix("use")
+                        .is_some_and(|rest| rest.chars().next().is_none_or(char::is_uppercase))
+                })
+            })
+}
+
+// Props are destructured in the first parameter or read from it
+fn count_props(node: &Node, body: &[Node]) -> usize {
+    let Some(props) = node
+        .child_by_field_name("parameters")
+        .and_then(|parameters| parameters.children().find(|child| child.is_named()))
+        .or_else(|| node.child_by_field_name("parameter"))
+    else {
+        return 0;
+    };
+    let props = match props.kind() {
+        "required_parameter" | "optional_parameter" => match props.child_by_field_name("pattern") {
+            Some(pattern) => pattern,
+            None => return 0,
+        },
+        _ => props,
+    };
+    match props.kind() {
+        "object_pattern" => props.children().filter(|child| child.is_named()).count(),
+        "identifier" => {
+            let name = text(&props);
+            let mut accessed = body
+                .iter()
+                .filter(|node| {
+                    node.kind() == "member_expression"
+                        && node
+                            .child_by_field_name("object")
+                            .is_some_and(|object| text(&object) == name)
+                })
+                .filter_map(|node| {
+                    node.child_by_field_name("property")
+                        .and_then(|property| text(&property))
+                })
+                .collect::<Vec<_>>();
+            accessed.sort();
+            accessed.dedup();
+            accessed.len()
+        }
+        _ => 0,
+    }
+}
+
+fn compute_react(node: &Node, stats: &mut Stats) {
+    if JSX_ELEMENTS.contains(&node.kind()) {
+        let mut depth = 1;
+        let mut current = node.parent();
+        while let Some(ancestor) = current {
+            if JSX_ELEMENTS.contains(&ancestor.kind()) {
+                depth += 1;
+            }
+            current = ancestor.parent();
+        }
+        stats.jsx_depth_max = stats.jsx_depth_max.max(depth);
+        return;
+    }
+
+    // A component is a capitalized function returning JSX
+    if !is_function(node)
+        || !function_name(node).is_some_and(|name| name.starts_with(char::is_uppercase))
+    {
+        return;
+    }
+    let mut body = Vec::new();
+    function_body_nodes(node, &mut body);
+    if !body.iter().any(|node| JSX_ELEMENTS.contains(&node.kind())) {
+        return;
+    }
+
+    let hooks = body.iter().filter(|node| is_hook_call(node)).count();
+    let props = count_props(node, &body);
+    stats.components += 1;
+    stats.hooks += hooks;
+    stats.hooks_max = stats.hooks_max.max(hooks);
+    stats.props += props;
+    stats.props_max = stats.props_max.max(props);
+}
+
+fn compute_javascript(node: &Node, stats: &mut extras::Stats) {
+    let stats = stats.javascript_mut();
+
+    if is_callback(node) {
+        stats.callbacks += 1;
+        let mut depth = 1;
+        let mut current = node.parent();
+        while let Some(ancestor) = current {
+            if is_callback(&ancestor) {
+                depth += 1;
+            }
+            current = ancestor.parent();
+        }
+        stats.callback_depth_max = stats.callback_depth_max.max(depth);
+    }
+
+    if is_function(node)
+        && function_body_any(node, |node| node.kind() == "await_expression")
+        && function_body_any(node, |node| promise_call_object(node).is_some())
+    {
+        stats.mixed_async_functions += 1;
+    }
+
+    // Chains are measured from their outermost call
+    if let Some(mut object) = promise_call_object(node) {
+        let is_outermost = !node
+            .parent()
+            .and_then(|parent| parent.parent())
+            .is_some_and(|call| {
+                promise_call_object(&call).is_some_and(|inner| inner.id() == node.id())
+            });
+        if is_outermost {
+            let mut length = 1;
+            while let Some(inner) = promise_call_object(&object) {
+                length += 1;
+                object = inner;
+            }
+            stats.promise_chains += 1;
+            stats.promise_chain_max = stats.promise_chain_max.max(length);
+        }
+    }
+
+    compute_react(node, stats);
+}
+
+impl Extras for JavascriptCode {
+    fn compute(node: &Node, stats: &mut extras::Stats) {
+        compute_javascript(node, stats);
+    }
+}
+
+impl Extras for MozjsCode {
+    fn compute(node: &Node, stats: &mut extras::Stats) {
+        compute_javascript(node, stats);
+    }
+}
+
+impl Extras for TypescriptCode {
+    fn compute(node: &Node, stats: &mut extras::Stats) {
+        compute_javascript(node, stats);
+    }
+}
+
+impl Extras for TsxCode {
+    fn compute(node: &Node, stats: &mut extras::Stats) {
+        compute_javascript(node, stats);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{tools::check_metrics, traits::ParserTrait};
+
+    use super::*;
+
+    #[test]
+    fn typescript_callback_depth() {
+        check_metrics::<TypescriptParser>(
+            "a(function (x: number) {
+                 b(() => {
+                     c((y: number) => y);
+                 });
+             });
+             d(() => 1);",
+            "foo.ts",
+            |metric| {
+                let javascript = metric.extras.javascript().unwrap();
+                insta::assert_json_snapshot!(javascript.callbacks(), @"4.0");
+                insta::assert_json_snapshot!(javascript.callback_depth_max(), @"3.0");
+            },
+        );
+    }
+
+    #[test]
+    fn typescript_promise_chains() {
+        check_metrics::<TypescriptParser>(
+            "fetch(url).then(r => r.json()).then(j => j.data).catch(e => null).finally(() => done());
+             p.then(x => x);",
+            "foo.ts",
+            |metric| {
+                let javascript = metric.extras.javascript().unwrap();
+                insta::assert_json_snapshot!(javascript.promise_chains(), @"2.0");
+                insta::assert_json_snapshot!(javascript.promise_chain_max(), @"4.0");
+            },
+        );
+    }
+
+    #[test]
+    fn typescript_mixed_async_styles() {
+        check_metrics::<TypescriptParser>(
+            "async function mixed() {
+                 const a = await load();
+                 return save(a).then(r => r.ok);
+             }
+             async function awaits() {
+                 return await load();
+             }
+             function chains() {
+                 return load().then(async a => await save(a));
+             }",
+            "foo.ts",
+            |metric| {
+                // The `await` in the callback of `chains` belongs to the callback
+                insta::assert_json_snapshot!(
+                    metric.extras,
+                    @r###"
+                    {
+                      "callbacks": 2.0,
+                      "callback_depth_max": 1.0,
+                      "promise_chains": 2.0,
+                      "promise_chain_max": 1.0,
+                      "mixed_async_functions": 1.0,
+                      "components": 0.0,
+                      "hooks": 0.0,
+                      "hooks_average": null,
+                      "hooks_max": 0.0,
+                      "props_average": null,
+                      "props_max": 0.0,
+                      "jsx_depth_max": 0.0
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn tsx_components() {
+        check_metrics::<TsxParser>(
+            "function App({ title, items }: Props) {
+                 const [open, setOpen] = useState(false);
+                 useEffect(() => {
+                     document.title = title;
+                 }, [title]);
+                 return (
+                     <main>
+                         <ul>
+                             {items.map((item) => <li key={item}><Item value={item} /></li>)}
+                         </ul>
+                     </main>
+                 );
+             }
+             const Item = (props: ItemProps) => {
+                 const theme = React.useContext(Theme);
+                 return <span className={theme}>{props.value}{props.value}</span>;
+             };
+             function helper() {
+                 return <br />;
+             }",
+            "foo.tsx",
+            |metric| {
+                // `helper` is not a component: its name is not capitalized
+                let javascript = metric.extras.javascript().unwrap();
+                insta::assert_json_snapshot!(javascript.components(), @"2.0");
+                insta::assert_json_snapshot!(javascript.hooks(), @"3.0");
+                insta::assert_json_snapshot!(javascript.hooks_max(), @"2.0");
+                insta::assert_json_snapshot!(javascript.props_average(), @"1.5");
+                insta::assert_json_snapshot!(javascript.props_max(), @"2.0");
+                insta::assert_json_snapshot!(javascript.jsx_depth_max(), @"4.0");
+                insta::assert_json_snapshot!(metric.extras.get("hooks_average"), @"1.5");
+            },
+        );
+    }
+
+    // The spaces of the `JavaScript` grammar are not detected yet,
+    // so the whole tree is walked
+    fn compute_all(node: &Node, stats: &mut extras::Stats) {
+        JavascriptCode::compute(node, stats);
+        for child in node.children() {
+            compute_all(&child, stats);
+        }
+    }
+
+    #[test]
+    fn jsx_components() {
+        let parser = JavascriptParser::new(
+            "const Button = props => <button onClick={props.onClick}>{props.label}</button>;
+             function Toolbar() {
+                 return <div><Button label=\"a\" /><Button label=\"b\" /></div>;
+             }"
+            .as_bytes()
+            .to_vec(),
+            Path::new("foo.jsx"),
+            None,
+        );
+        let _guard = enter_code_context(parser.get_code());
+        let mut stats = extras::Stats::default();
+        compute_all(&parser.get_root(), &mut stats);
+        insta::assert_json_snapshot!(
+            stats,
+            @r###"
+            {
+              "callbacks": 0.0,
+              "callback_depth_max": 0.0,
+              "promise_chains": 0.0,
+              "promise_chain_max": 0.0,
+              "mixed_async_functions": 0.0,
+              "components": 2.0,
+              "hooks": 0.0,
+              "hooks_average": 0.0,
+              "hooks_max": 0.0,
+              "props_average": 1.0,
+              "props_max": 2.0,
+              "jsx_depth_max": 2.0
+            }"###
+        );
+    }
+}
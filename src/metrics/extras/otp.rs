@@ -0,0 +1,542 @@
+use std::fmt;
+
+use serde::{
+    ser::{SerializeStruct, Serializer},
+    Serialize,
+};
+
+use crate::{extras::Extras, *};
+
+/// The `OTP` specific metrics of `Elixir` and `Erlang` code.
+///
+/// This metric detects the `OTP` constructs used by a module: the
+/// `GenServer` and `Supervisor` behaviours it implements, the callbacks
+/// it defines, the children it supervises and the messages it sends,
+/// receives and the processes it spawns.
+#[derive(Clone, Debug, Default)]
+pub struct Stats {
+    genservers: usize,
+    supervisors: usize,
+    callbacks: usize,
+    child_specs: usize,
+    receives: usize,
+    sends: usize,
+    spawns: usize,
+}
+
+impl Serialize for Stats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let values = self.values();
+        let mut st = serializer.serialize_struct("otp", values.len())?;
+        for (name, value) in values {
+            st.serialize_field(name, &value)?;
+        }
+        st.end()
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let values = self
+            .values()
+            .into_iter()
+            .map(|(name, value)| format!("{name}: {value}"))
+            .collect::<Vec<_>>();
+        write!(f, "{}", values.join(", "))
+    }
+}
+
+impl Stats {
+    /// Merges a second `OTP` metric into the first one
+    pub fn merge(&mut self, other: &Stats) {
+        self.genservers += other.genservers;
+        self.supervisors += other.supervisors;
+        self.callbacks += other.callbacks;
+        self.child_specs += other.child_specs;
+        self.receives += other.receives;
+        self.sends += other.sends;
+        self.spawns += other.spawns;
+    }
+
+    /// Returns the number of modules implementing the `GenServer`
+    /// behaviour in a space.
+    #[inline(always)]
+    pub fn genservers(&self) -> f64 {
+        self.genservers as f64
+    }
+
+    /// Returns the number of modules implementing the `Supervisor`
+    /// behaviour in a space.
+    #[inline(always)]
+    pub fn supervisors(&self) -> f64 {
+        self.supervisors as f64
+    }
+
+    /// Returns the number of `GenServer` callbacks (`init`, `handle_call`,
+    /// `handle_cast`, ...) defined in a space.
+    #[inline(always)]
+    pub fn callbacks(&self) -> f64 {
+        self.callbacks as f64
+    }
+
+    /// Returns the number of child specifications listed by the
+    /// supervisors of a space.
+    #[inline(always)]
+    pub fn child_specs(&self) -> f64 {
+        self.child_specs as f64
+    }
+
+    /// Returns the number of `receive` blocks in a space.
+    #[inline(always)]
+    pub fn receives(&self) -> f64 {
+        self.receives as f64
+    }
+
+    /// Returns the number of messages sent in a space, either directly
+    /// or through `GenServer` calls and casts.
+    #[inline(always)]
+    pub fn sends(&self) -> f64 {
+        self.sends as f64
+    }
+
+    /// Returns the number of processes spawned in a space.
+    #[inline(always)]
+    pub fn spawns(&self) -> f64 {
+        self.spawns as f64
+    }
+
+    /// Returns the supervision complexity
+    ///
+    /// This value weights the number of supervisors, `GenServer`s and
+    /// supervised children of a space, and is capped at `10`.
+    #[inline(always)]
+    pub fn supervision_complexity(&self) -> f64 {
+        (self.supervisors() * 0.5 + self.genservers() * 0.3 + self.child_specs() * 0.1).min(10.0)
+    }
+
+    /// Returns the actor complexity
+    ///
+    /// This value weights the number of spawned processes, sent messages
+    /// and message handlers (`receive` blocks and `GenServer` callbacks)
+    /// of a space, and is capped at `10`.
+    #[inline(always)]
+    pub fn actor_complexity(&self) -> f64 {
+        (self.spawns() * 0.4 + self.sends() * 0.3 + (self.receives() + self.callbacks()) * 0.3)
+            .min(10.0)
+    }
+
+    pub(crate) fn values(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("genservers", self.genservers()),
+            ("supervisors", self.supervisors()),
+            ("callbacks", self.callbacks()),
+            ("child_specs", self.child_specs()),
+            ("receives", self.receives()),
+            ("sends", self.sends()),
+            ("spawns", self.spawns()),
+            ("supervision_complexity", self.supervision_complexity()),
+            ("actor_complexity", self.actor_complexity()),
+        ]
+    }
+}
+
+const CALLBACKS: &[&str] = &[
+    "init",
+    "handle_call",
+    "handle_cast",
+    "handle_info",
+    "handle_continue",
+    "terminate",
+    "code_change",
+];
+
+const ELIXIR_SENDS: &[&str] = &[
+    "send",
+    "Kernel.send",
+    "Process.send",
+    "Process.send_after",
+    "GenServer.call",
+    "GenServer.cast",
+];
+
+const ELIXIR_SPAWNS: &[&str] = &[
+    "spawn",
+    "spawn_link",
+    "spawn_monitor",
+    "Process.spawn",
+    "Task.async",
+    "Task.start",
+    "Task.start_link",
+];
+
+const ERLANG_SENDS: &[&str] = &[
+    "erlang:send",
+    "erlang:send_after",
+    "gen_server:call",
+    "gen_server:cast",
+];
+
+const ERLANG_SPAWNS: &[&str] = &[
+    "spawn",
+    "spawn_link",
+    "spawn_monitor",
+    "erlang:spawn",
+    "erlang:spawn_link",
+    "erlang:spawn_monitor",
+    "proc_lib:spawn",
+    "proc_lib:spawn_link",
+];
+
+fn text(node: &Node) -> Option<String> {
+    with_current_code(|code| node_text(node, code).map(str::to_string)).flatten()
+}
+
+fn named_children_count(node: &Node) -> usize {
+    node.children().filter(|child| child.is_named()).count()
+}
+
+// Returns `f` for `f(...)` and `Module.f` for `Module.f(...)`
+fn elixir_call_name(node: &Node) -> Option<String> {
+    if node.kind() != "call" {
+        return None;
+    }
+    let target = node.child_by_field_name("target")?;
+    match target.kind() {
+        "identifier" => text(&target),
+        "dot" => {
+            let module = text(&target.child_by_field_name("left")?)?;
+            let function = text(&target.child_by_field_name("right")?)?;
+            Some(format!("{module}.{function}"))
+        }
+        _ => None,
+    }
+}
+
+fn elixir_first_argument<'a>(node: &Node<'a>) -> Option<Node<'a>> {
+    node.children()
+        .find(|child| child.kind() == "arguments")?
+        .children()
+        .find(|child| child.is_named())
+}
+
+// Returns the name of the function defined by a `def`, guards excluded
+fn elixir_def_name(node: &Node) -> Option<String> {
+    if elixir_call_name(node).is_none_or(|name| name != "def") {
+        return None;
+    }
+    let head = elixir_first_argument(node)?;
+    if head.kind() == "binary_operator" {
+        head.child_by_field_name("left")
+            .and_then(|left| elixir_call_name(&left))
+    } else {
+        elixir_call_name(&head)
+    }
+}
+
+// Returns `f` for `f(...)` and `module:f` for `module:f(...)`
+fn erlang_call_name(node: &Node) -> Option<String> {
+    if node.kind() != "call" {
+        return None;
+    }
+    let expr = node.child_by_field_name("expr")?;
+    match expr.kind() {
+        "atom" => text(&expr),
+        "remote" => {
+            let module = text(
+                &expr
+                    .child_by_field_name("module")?
+                    .child_by_field_name("module")?,
+            )?;
+            let function = text(&expr.child_by_field_name("fun")?)?;
+            Some(format!("{module}:{function}"))
+        }
+        _ => None,
+    }
+}
+
+fn is_one_of(name: Option<&str>, names: &[&str]) -> bool {
+    name.is_some_and(|name| names.contains(&name))
+}
+
+impl Extras for ElixirCode {
+    fn compute(node: &Node, stats: &mut extras::Stats) {
+        let stats = stats.otp_mut();
+
+        match node.kind() {
+            "call" => {
+                let name = elixir_call_name(node);
+                match name.as_deref() {
+                    Some("use") => match elixir_first_argument(node).and_then(|arg| text(&arg)) {
+                        Some(behaviour) if behaviour == "GenServer" => stats.genservers += 1,
+                        Some(behaviour) if behaviour == "Supervisor" => stats.supervisors += 1,
+                        _ => {}
+                    },
+                    Some("receive") => stats.receives += 1,
+                    Some("Supervisor.init" | "Supervisor.start_link") => {
+                        if let Some(children) =
+                            elixir_first_argument(node).filter(|arg| arg.kind() == "list")
+                        {
+                            stats.child_specs += named_children_count(&children);
+                        }
+                    }
+                    _ if is_one_of(name.as_deref(), ELIXIR_SENDS) => stats.sends += 1,
+                    _ if is_one_of(name.as_deref(), ELIXIR_SPAWNS) => stats.spawns += 1,
+                    _ => {}
+                }
+                // The clauses of a callback are counted once
+                let callback =
+                    elixir_def_name(node).filter(|name| CALLBACKS.contains(&name.as_str()));
+                if callback.is_some()
+                    && node
+                        .previous_named_sibling()
+                        .is_none_or(|previous| elixir_def_name(&previous) != callback)
+                {
+                    stats.callbacks += 1;
+                }
+            }
+            // `@behaviour GenServer`
+            "unary_operator" => {
+                if let Some(operand) = node.child_by_field_name("operand").filter(|operand| {
+                    elixir_call_name(operand).is_some_and(|name| name == "behaviour")
+                }) {
+                    match elixir_first_argument(&operand)
+                        .and_then(|arg| text(&arg))
+                        .as_deref()
+                    {
+                        Some("GenServer") => stats.genservers += 1,
+                        Some("Supervisor") => stats.supervisors += 1,
+                        _ => {}
+                    }
+                }
+            }
+            // `children = [...]`
+            "binary_operator"
+                if node
+                    .child_by_field_name("operator")
+                    .is_some_and(|op| op.kind() == "=")
+                    && node
+                        .child_by_field_name("left")
+                        .is_some_and(|left| text(&left).is_some_and(|name| name == "children")) =>
+            {
+                if let Some(children) = node
+                    .child_by_field_name("right")
+                    .filter(|right| right.kind() == "list")
+                {
+                    stats.child_specs += named_children_count(&children);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Extras for ErlangCode {
+    fn compute(node: &Node, stats: &mut extras::Stats) {
+        let stats = stats.otp_mut();
+
+        match node.kind() {
+            "behaviour_attribute" => {
+                match node
+                    .child_by_field_name("name")
+                    .and_then(|name| text(&name))
+                    .as_deref()
+                {
+                    Some("gen_server") => stats.genservers += 1,
+                    Some("supervisor") => stats.supervisors += 1,
+                    _ => {}
+                }
+            }
+            // Each clause is a declaration of its own, ended by `;` but
+            // for the last one
+            "fun_decl"
+                if node.previous_named_sibling().is_none_or(|previous| {
+                    previous.kind() != "fun_decl"
+                        || !previous.children().any(|child| child.kind() == ";")
+                }) && node.child_by_field_name("clause").is_some_and(|clause| {
+                    is_one_of(
+                        clause
+                            .child_by_field_name("name")
+                            .and_then(|name| text(&name))
+                            .as_deref(),
+                        CALLBACKS,
+                    )
+                }) =>
+            {
+                stats.callbacks += 1;
+            }
+            // `{ok, {SupFlags, [ChildSpec, ...]}}`
+            "tuple" => {
+                let elements = node
+                    .children()
+                    .filter(|child| child.is_named())
+                    .collect::<Vec<_>>();
+                if let [ok, spec] = elements.as_slice() {
+                    if text(ok).is_some_and(|ok| ok == "ok") && spec.kind() == "tuple" {
+                        if let Some(children) = spec
+                            .children()
+                            .filter(|child| child.is_named())
+                            .nth(1)
+                            .filter(|children| children.kind() == "list")
+                        {
+                            stats.child_specs += named_children_count(&children);
+                        }
+                    }
+                }
+            }
+            "receive_expr" => {
+                stats.receives += 1;
+            }
+            // `Pid ! Message`
+            "binary_op_expr" if node.children().any(|child| child.kind() == "!") => {
+                stats.sends += 1;
+            }
+            "call" => {
+                let name = erlang_call_name(node);
+                if is_one_of(name.as_deref(), ERLANG_SENDS) {
+                    stats.sends += 1;
+                } else if is_one_of(name.as_deref(), ERLANG_SPAWNS) {
+                    stats.spawns += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tools::check_func_space;
+
+    use super::*;
+
+    #[test]
+    fn elixir_genserver() {
+        check_func_space::<ElixirParser, _>(
+            "defmodule Counter do
+               use GenServer
+
+               def init(n), do: {:ok, n}
+
+               def handle_call(:get, _from, n), do: {:reply, n, n}
+
+               def handle_cast(:inc, n) when n < 10, do: {:noreply, n + 1}
+               def handle_cast(:inc, n), do: {:noreply, n}
+
+               def bump(pid), do: GenServer.cast(pid, :inc)
+
+               def ping(pid) do
+                 send(pid, :ping)
+                 spawn(fn -> :ok end)
+                 receive do
+                   :pong -> :ok
+                 end
+               end
+             end",
+            "foo.ex",
+            |func_space| {
+                // The two clauses of `handle_cast` are a single callback
+                insta::assert_json_snapshot!(
+                    func_space.metrics.extras,
+                    @r###"
+                    {
+                      "genservers": 1.0,
+                      "supervisors": 0.0,
+                      "callbacks": 3.0,
+                      "child_specs": 0.0,
+                      "receives": 1.0,
+                      "sends": 2.0,
+                      "spawns": 1.0,
+                      "supervision_complexity": 0.3,
+                      "actor_complexity": 2.2
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn elixir_supervisor_per_module() {
+        check_func_space::<ElixirParser, _>(
+            "defmodule App.Supervisor do
+               use Supervisor
+
+               def init(_) do
+                 children = [{App.Cache, []}, App.Worker, App.Server]
+                 Supervisor.init(children, strategy: :one_for_one)
+               end
+             end
+
+             defmodule App.Worker do
+               @behaviour GenServer
+             end",
+            "foo.ex",
+            |func_space| {
+                let otp = func_space.metrics.extras.otp().unwrap();
+                insta::assert_json_snapshot!(otp.supervisors(), @"1.0");
+                insta::assert_json_snapshot!(otp.genservers(), @"1.0");
+                insta::assert_json_snapshot!(otp.child_specs(), @"3.0");
+
+                // Each module reports its own metrics
+                let supervisor = func_space.spaces[0].metrics.extras.otp().unwrap();
+                insta::assert_json_snapshot!(supervisor.genservers(), @"0.0");
+                insta::assert_json_snapshot!(supervisor.supervision_complexity(), @"0.8");
+            },
+        );
+    }
+
+    #[test]
+    fn erlang_gen_server() {
+        check_func_space::<ErlangParser, _>(
+            "-module(server).
+             -behaviour(gen_server).
+
+             init([]) -> {ok, 0}.
+
+             handle_call(get, _From, N) -> {reply, N, N};
+             handle_call(_, _From, N) -> {reply, error, N}.
+
+             handle_cast(inc, N) -> {noreply, N + 1}.
+
+             bump(Pid) -> gen_server:cast(Pid, inc).
+
+             ping(Pid) ->
+                 Worker = spawn(fun() -> ok end),
+                 Pid ! {ping, Worker},
+                 receive
+                     pong -> ok
+                 end.",
+            "foo.erl",
+            |func_space| {
+                let otp = func_space.metrics.extras.otp().unwrap();
+                insta::assert_json_snapshot!(otp.genservers(), @"1.0");
+                insta::assert_json_snapshot!(otp.callbacks(), @"3.0");
+                insta::assert_json_snapshot!(otp.receives(), @"1.0");
+                insta::assert_json_snapshot!(otp.sends(), @"2.0");
+                insta::assert_json_snapshot!(otp.spawns(), @"1.0");
+            },
+        );
+    }
+
+    #[test]
+    fn erlang_supervisor_child_specs() {
+        check_func_space::<ErlangParser, _>(
+            "-module(sup).
+             -behaviour(supervisor).
+
+             init([]) ->
+                 {ok, {#{strategy => one_for_one},
+                       [#{id => cache, start => {cache, start_link, []}},
+                        #{id => worker, start => {worker, start_link, []}}]}}.",
+            "foo.erl",
+            |func_space| {
+                let otp = func_space.metrics.extras.otp().unwrap();
+                insta::assert_json_snapshot!(otp.supervisors(), @"1.0");
+                insta::assert_json_snapshot!(otp.child_specs(), @"2.0");
+                insta::assert_json_snapshot!(otp.supervision_complexity(), @"0.7");
+            },
+        );
+    }
+}
@@ -0,0 +1,301 @@
+use std::fmt;
+
+use serde::{
+    ser::{SerializeStruct, Serializer},
+    Serialize,
+};
+
+use crate::{extras::Extras, *};
+
+/// The `Python` specific metrics.
+///
+/// This metric counts the decorators applied to functions, the depth of
+/// nested comprehensions and the magic (`__dunder__`) methods defined by
+/// classes.
+#[derive(Clone, Debug, Default)]
+pub struct Stats {
+    functions: usize,
+    decorators: usize,
+    decorators_max: usize,
+    comprehensions: usize,
+    comprehension_depth_max: usize,
+    classes: usize,
+    magic_methods: usize,
+    magic_methods_max: usize,
+}
+
+impl Serialize for Stats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let values = self.values();
+        let mut st = serializer.serialize_struct("python", values.len())?;
+        for (name, value) in values {
+            st.serialize_field(name, &value)?;
+        }
+        st.end()
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let values = self
+            .values()
+            .into_iter()
+            .map(|(name, value)| format!("{name}: {value}"))
+            .collect::<Vec<_>>();
+        write!(f, "{}", values.join(", "))
+    }
+}
+
+impl Stats {
+    /// Merges a second `Python` metric into the first one
+    pub fn merge(&mut self, other: &Stats) {
+        self.functions += other.functions;
+        self.decorators += other.decorators;
+        self.decorators_max = self.decorators_max.max(other.decorators_max);
+        self.comprehensions += other.comprehensions;
+        self.comprehension_depth_max = self
+            .comprehension_depth_max
+            .max(other.comprehension_depth_max);
+        self.classes += other.classes;
+        self.magic_methods += other.magic_methods;
+        self.magic_methods_max = self.magic_methods_max.max(other.magic_methods_max);
+    }
+
+    /// Returns the number of decorators applied to functions in a space.
+    #[inline(always)]
+    pub fn decorators(&self) -> f64 {
+        self.decorators as f64
+    }
+
+    /// Returns the average number of decorators per function in a space.
+    #[inline(always)]
+    pub fn decorators_average(&self) -> f64 {
+        self.decorators() / self.functions as f64
+    }
+
+    /// Returns the maximum number of decorators applied to a function
+    /// in a space.
+    #[inline(always)]
+    pub fn decorators_max(&self) -> f64 {
+        self.decorators_max as f64
+    }
+
+    /// Returns the number of comprehensions and generator expressions
+    /// in a space.
+    #[inline(always)]
+    pub fn comprehensions(&self) -> f64 {
+        self.comprehensions as f64
+    }
+
+    /// Returns the maximum nesting depth of comprehensions in a space.
+    #[inline(always)]
+    pub fn comprehension_depth_max(&self) -> f64 {
+        self.comprehension_depth_max as f64
+    }
+
+    /// Returns the number of magic methods defined by classes in a space.
+    #[inline(always)]
+    pub fn magic_methods(&self) -> f64 {
+        self.magic_methods as f64
+    }
+
+    /// Returns the average number of magic methods per class in a space.
+    #[inline(always)]
+    pub fn magic_methods_average(&self) -> f64 {
+        self.magic_methods() / self.classes as f64
+    }
+
+    /// Returns the maximum number of magic methods defined by a class
+    /// in a space.
+    #[inline(always)]
+    pub fn magic_methods_max(&self) -> f64 {
+        self.magic_methods_max as f64
+    }
+
+    pub(crate) fn values(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("decorators", self.decorators()),
+            ("decorators_average", self.decorators_average()),
+            ("decorators_max", self.decorators_max()),
+            ("comprehensions", self.comprehensions()),
+            ("comprehension_depth_max", self.comprehension_depth_max()),
+            ("magic_methods", self.magic_methods()),
+            ("magic_methods_average", self.magic_methods_average()),
+            ("magic_methods_max", self.magic_methods_max()),
+        ]
+    }
+}
+
+const COMPREHENSIONS: &[&str] = &[
+    "list_comprehension",
+    "set_comprehension",
+    "dictionary_comprehension",
+    "generator_expression",
+];
+
+// Returns the definition wrapped by a decorated definition
+fn undecorate<'a>(node: &Node<'a>) -> Option<Node<'a>> {
+    match node.kind() {
+        "decorated_definition" => node
+            .children()
+            .find(|child| child.kind() != "decorator" && child.is_named()),
+        _ => Some(*node),
+    }
+}
+
+fn is_magic_method(node: &Node) -> bool {
+    node.kind() == "function_definition"
+        && node.child_by_field_name("name").is_some_and(|name| {
+            with_current_code(|code| {
+                node_text(&name, code).is_some_and(|name| {
+                    name.len() > 4 && name.starts_with("__") && name.ends_with("__")
+                })
+            })
+            .unwrap_or(false)
+        })
+}
+
+impl Extras for PythonCode {
+    fn compute(node: &Node, stats: &mut extras::Stats) {
+        let stats = stats.python_mut();
+
+        match node.kind() {
+            "function_definition" => {
+                stats.functions += 1;
+                if let Some(parent) = node
+                    .parent()
+                    .filter(|parent| parent.kind() == "decorated_definition")
+                {
+                    let decorators = parent
+                        .children()
+                        .filter(|child| child.kind() == "decorator")
+                        .count();
+                    stats.decorators += decorators;
+                    stats.decorators_max = stats.decorators_max.max(decorators);
+                }
+            }
+            "class_definition" => {
+                stats.classes += 1;
+                if let Some(body) = node.child_by_field_name("body") {
+                    let magic_methods = body
+                        .children()
+                        .filter_map(|child| undecorate(&child))
+                        .filter(is_magic_method)
+                        .count();
+                    stats.magic_methods += magic_methods;
+                    stats.magic_methods_max = stats.magic_methods_max.max(magic_methods);
+                }
+            }
+            kind if COMPREHENSIONS.contains(&kind) => {
+                stats.comprehensions += 1;
+                // Comprehensions nested in a function or a lambda defined
+                // inside a comprehension start a new nesting level
+                let mut depth = 1;
+                let mut current = node.parent();
+                while let Some(ancestor) = current {
+                    match ancestor.kind() {
+                        kind if COMPREHENSIONS.contains(&kind) => depth += 1,
+                        "function_definition" | "class_definition" | "lambda" => break,
+                        _ => {}
+                    }
+                    current = ancestor.parent();
+                }
+                stats.comprehension_depth_max = stats.comprehension_depth_max.max(depth);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tools::check_metrics;
+
+    use super::*;
+
+    #[test]
+    fn python_decorators() {
+        check_metrics::<PythonParser>(
+            "@a
+             @b.c(1)
+             def f():
+                 pass
+
+             @cache
+             def g():
+                 pass
+
+             def h():
+                 pass",
+            "foo.py",
+            |metric| {
+                // 3 decorators on 3 functions, f has the most
+                let python = metric.extras.python().unwrap();
+                insta::assert_json_snapshot!(python.decorators(), @"3.0");
+                insta::assert_json_snapshot!(python.decorators_average(), @"1.0");
+                insta::assert_json_snapshot!(python.decorators_max(), @"2.0");
+            },
+        );
+    }
+
+    #[test]
+    fn python_nested_comprehensions() {
+        check_metrics::<PythonParser>(
+            "def f(z):
+                 a = [x for x in {y: 1 for y in (w for w in z)}]
+                 b = [lambda: [i for i in z] for _ in z]
+                 return a, b",
+            "foo.py",
+            |metric| {
+                // The comprehension in the lambda is not nested
+                let python = metric.extras.python().unwrap();
+                insta::assert_json_snapshot!(python.comprehensions(), @"5.0");
+                insta::assert_json_snapshot!(python.comprehension_depth_max(), @"3.0");
+            },
+        );
+    }
+
+    #[test]
+    fn python_magic_methods() {
+        check_metrics::<PythonParser>(
+            "class A:
+                 def __init__(self):
+                     pass
+
+                 @property
+                 def __len__(self):
+                     return 0
+
+                 def _private(self):
+                     pass
+
+             class B:
+                 def __repr__(self):
+                     return ''
+
+             def __main__():
+                 pass",
+            "foo.py",
+            |metric| {
+                // Functions outside of a class are not magic methods
+                insta::assert_json_snapshot!(
+                    metric.extras,
+                    @r###"
+                    {
+                      "decorators": 1.0,
+                      "decorators_average": 0.2,
+                      "decorators_max": 1.0,
+                      "comprehensions": 0.0,
+                      "comprehension_depth_max": 0.0,
+                      "magic_methods": 3.0,
+                      "magic_methods_average": 1.5,
+                      "magic_methods_max": 2.0
+                    }"###
+                );
+            },
+        );
+    }
+}
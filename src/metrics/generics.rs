@@ -0,0 +1,482 @@
+use std::fmt;
+
+use serde::{
+    ser::{SerializeStruct, Serializer},
+    Serialize,
+};
+
+use crate::{checker::Checker, macros::implement_metric_trait, *};
+
+/// The `Generics` metric.
+///
+/// This metric counts the generic/template parameters declared by a
+/// function or class together with the bounds constraining them
+/// (trait bounds and `where` predicates in `Rust`, `requires` clauses in
+/// `C++`, `extends` constraints in `Java`/`TypeScript`, `where` constraints
+/// in `C#`). It is a proxy for the complexity of an interface.
+#[derive(Debug, Clone)]
+pub struct Stats {
+    params: usize,
+    bounds: usize,
+    params_sum: usize,
+    bounds_sum: usize,
+    params_max: usize,
+    bounds_max: usize,
+    total_space_functions: usize,
+}
+
+impl Default for Stats {
+    fn default() -> Self {
+        Self {
+            params: 0,
+            bounds: 0,
+            params_sum: 0,
+            bounds_sum: 0,
+            params_max: 0,
+            bounds_max: 0,
+            total_space_functions: 1,
+        }
+    }
+}
+
+impl Serialize for Stats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut st = serializer.serialize_struct("generics", 6)?;
+        st.serialize_field("parameters", &self.params_sum())?;
+        st.serialize_field("bounds", &self.bounds_sum())?;
+        st.serialize_field("total", &self.generics_total())?;
+        st.serialize_field("average", &self.generics_average())?;
+        st.serialize_field("parameters_max", &self.params_max())?;
+        st.serialize_field("bounds_max", &self.bounds_max())?;
+        st.end()
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "parameters: {}, bounds: {}, total: {}, average: {}, parameters_max: {}, bounds_max: {}",
+            self.params_sum(),
+            self.bounds_sum(),
+            self.generics_total(),
+            self.generics_average(),
+            self.params_max(),
+            self.bounds_max()
+        )
+    }
+}
+
+impl Stats {
+    /// Merges a second `Generics` metric into the first one
+    pub fn merge(&mut self, other: &Stats) {
+        self.params_max = self.params_max.max(other.params_max);
+        self.bounds_max = self.bounds_max.max(other.bounds_max);
+        self.params_sum += other.params_sum;
+        self.bounds_sum += other.bounds_sum;
+        self.total_space_functions += other.total_space_functions;
+    }
+
+    /// Returns the number of generic parameters declared in a space.
+    #[inline(always)]
+    pub fn params(&self) -> f64 {
+        self.params as f64
+    }
+
+    /// Returns the number of bounds on generic parameters declared in a space.
+    #[inline(always)]
+    pub fn bounds(&self) -> f64 {
+        self.bounds as f64
+    }
+
+    /// Returns the sum of generic parameters declared in a space
+    /// and in its subspaces.
+    #[inline(always)]
+    pub fn params_sum(&self) -> f64 {
+        self.params_sum as f64
+    }
+
+    /// Returns the sum of bounds on generic parameters declared in a space
+    /// and in its subspaces.
+    #[inline(always)]
+    pub fn bounds_sum(&self) -> f64 {
+        self.bounds_sum as f64
+    }
+
+    /// Returns the maximum number of generic parameters declared
+    /// by a single space.
+    #[inline(always)]
+    pub fn params_max(&self) -> f64 {
+        self.params_max as f64
+    }
+
+    /// Returns the maximum number of bounds declared by a single space.
+    #[inline(always)]
+    pub fn bounds_max(&self) -> f64 {
+        self.bounds_max as f64
+    }
+
+    /// Returns the total number of generic parameters and bounds in a space.
+    #[inline(always)]
+    pub fn generics_total(&self) -> f64 {
+        self.params_sum() + self.bounds_sum()
+    }
+
+    /// Returns the `Generics` metric average value
+    ///
+    /// This value is computed dividing the total number of generic
+    /// parameters and bounds for the total number of functions/closures
+    /// in a space.
+    ///
+    /// If there are no functions in a code, its value is `NAN`.
+    #[inline(always)]
+    pub fn generics_average(&self) -> f64 {
+        self.generics_total() / self.total_space_functions as f64
+    }
+
+    #[inline(always)]
+    pub(crate) fn compute_sum(&mut self) {
+        self.params_sum += self.params;
+        self.bounds_sum += self.bounds;
+    }
+
+    #[inline(always)]
+    pub(crate) fn compute_minmax(&mut self) {
+        self.params_max = self.params_max.max(self.params);
+        self.bounds_max = self.bounds_max.max(self.bounds);
+        self.compute_sum();
+    }
+
+    pub(crate) fn finalize(&mut self, total_space_functions: usize) {
+        self.total_space_functions = total_space_functions;
+    }
+}
+
+pub trait Generics
+where
+    Self: Checker,
+{
+    fn compute(node: &Node, stats: &mut Stats);
+}
+
+#[inline(always)]
+fn count_named_children(node: &Node) -> usize {
+    node.children().filter(|child| child.is_named()).count()
+}
+
+impl Generics for RustCode {
+    fn compute(node: &Node, stats: &mut Stats) {
+        use Rust::*;
+
+        match node.kind_id().into() {
+            // `for<'a>` lifetimes of higher-ranked bounds are part of
+            // a bound, not parameters of the enclosing item
+            TypeParameters
+                if node
+                    .parent()
+                    .is_none_or(|parent| parent.kind_id() != HigherRankedTraitBound) =>
+            {
+                stats.params += count_named_children(node);
+            }
+            // Covers both inline bounds (`T: Clone`) and `where` predicates
+            TraitBounds => {
+                stats.bounds += count_named_children(node);
+            }
+            _ => {}
+        }
+    }
+}
+
+// Template parameters live on the `template_declaration` wrapping a function
+// or a class, so they are counted when the templated space is entered.
+fn cpp_template_generics(template: &Node, stats: &mut Stats) {
+    for child in template.children() {
+        match child.kind() {
+            "template_parameter_list" => stats.params += count_named_children(&child),
+            "requires_clause" => stats.bounds += 1,
+            _ => {}
+        }
+    }
+}
+
+impl Generics for CppCode {
+    fn compute(node: &Node, stats: &mut Stats) {
+        let is_space = |node: &Node| Self::is_func(node) || Self::is_func_space(node);
+
+        if is_space(node) {
+            if let Some(template) = node
+                .parent()
+                .filter(|parent| parent.kind() == "template_declaration")
+            {
+                cpp_template_generics(&template, stats);
+            }
+        } else if node.kind() == "template_declaration"
+            && !node.children().any(|child| is_space(&child))
+        {
+            // Templated aliases, variables and declarations without a body
+            cpp_template_generics(node, stats);
+        }
+    }
+}
+
+impl Generics for JavaCode {
+    fn compute(node: &Node, stats: &mut Stats) {
+        use Java::*;
+
+        match node.kind_id().into() {
+            TypeParameters => {
+                stats.params += node
+                    .children()
+                    .filter(|child| child.kind_id() == TypeParameter)
+                    .count();
+            }
+            // `T extends A & B` has two bounds
+            TypeBound => {
+                stats.bounds += count_named_children(node);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Generics for TypescriptCode {
+    fn compute(node: &Node, stats: &mut Stats) {
+        use Typescript::*;
+
+        match node.kind_id().into() {
+            TypeParameters => {
+                stats.params += node
+                    .children()
+                    .filter(|child| child.kind_id() == TypeParameter)
+                    .count();
+            }
+            Constraint => {
+                stats.bounds += 1;
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Generics for TsxCode {
+    fn compute(node: &Node, stats: &mut Stats) {
+        use Tsx::*;
+
+        match node.kind_id().into() {
+            TypeParameters => {
+                stats.params += node
+                    .children()
+                    .filter(|child| child.kind_id() == TypeParameter)
+                    .count();
+            }
+            Constraint => {
+                stats.bounds += 1;
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Generics for CsharpCode {
+    fn compute(node: &Node, stats: &mut Stats) {
+        match node.kind() {
+            "type_parameter_list" => {
+                stats.params += node
+                    .children()
+                    .filter(|child| child.kind() == "type_parameter")
+                    .count();
+            }
+            // `where T : class, new()` has two constraints
+            "type_parameter_constraint" => {
+                stats.bounds += 1;
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Generics for GoCode {
+    fn compute(node: &Node, stats: &mut Stats) {
+        // `[A, B any]` declares two parameters sharing a single constraint
+        if node.kind() == "type_parameter_declaration" {
+            stats.params += node
+                .children()
+                .filter(|child| child.kind() == "identifier")
+                .count();
+            // `any` does not constrain anything
+            if node.children().any(|child| {
+                child.kind() == "type_constraint"
+                    && !node_text_equals_any(&child, &["any", "interface{}"])
+            }) {
+                stats.bounds += 1;
+            }
+        }
+    }
+}
+
+implement_metric_trait!(
+    Generics,
+    PythonCode,
+    MozjsCode,
+    JavascriptCode,
+    PreprocCode,
+    CcommentCode,
+    KotlinCode,
+    ElixirCode,
+    ErlangCode,
+    GleamCode,
+    LuaCode
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::check_metrics;
+
+    #[test]
+    fn python_no_generics() {
+        check_metrics::<PythonParser>("def f(a, b):\n    return a", "foo.py", |metric| {
+            insta::assert_json_snapshot!(
+                metric.generics,
+                @r###"
+                    {
+                      "parameters": 0.0,
+                      "bounds": 0.0,
+                      "total": 0.0,
+                      "average": 0.0,
+                      "parameters_max": 0.0,
+                      "bounds_max": 0.0
+                    }"###
+            );
+        });
+    }
+
+    #[test]
+    fn merged_average() {
+        let mut one = Stats {
+            params: 2,
+            ..Stats::default()
+        };
+        one.compute_minmax();
+        one.finalize(1);
+        let mut two = Stats {
+            params: 1,
+            ..Stats::default()
+        };
+        two.compute_minmax();
+        two.finalize(2);
+
+        one.merge(&two);
+        assert_eq!(one.generics_total(), 3.);
+        assert_eq!(one.generics_average(), 1.);
+    }
+
+    #[test]
+    fn rust_generic_function() {
+        check_metrics::<ParserEngineRust>(
+            "fn f<'a, T: Clone + Send, const N: usize>(x: &'a T) -> T
+             where
+                 T: for<'b> Fn(&'b u8),
+             {
+                 x.clone()
+             }",
+            "foo.rs",
+            |metric| {
+                // 3 parameters: 'a, T, N
+                // 3 bounds: Clone, Send, for<'b> Fn(&'b u8)
+                insta::assert_json_snapshot!(
+                    metric.generics,
+                    @r###"
+                    {
+                      "parameters": 3.0,
+                      "bounds": 3.0,
+                      "total": 6.0,
+                      "average": 6.0,
+                      "parameters_max": 3.0,
+                      "bounds_max": 3.0
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn rust_generic_impl() {
+        check_metrics::<ParserEngineRust>(
+            "impl<T: Default> Foo<T> {
+                 fn new() -> Self { Self(T::default()) }
+                 fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Foo<U> { Foo(f(self.0)) }
+             }",
+            "foo.rs",
+            |metric| {
+                // impl: 1 parameter, 1 bound
+                // map: 2 parameters, 1 bound
+                insta::assert_json_snapshot!(
+                    metric.generics,
+                    @r###"
+                    {
+                      "parameters": 3.0,
+                      "bounds": 2.0,
+                      "total": 5.0,
+                      "average": 2.5,
+                      "parameters_max": 2.0,
+                      "bounds_max": 1.0
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn java_generic_class() {
+        check_metrics::<JavaParser>(
+            "class Box<T extends Comparable<T> & Cloneable, U> {
+                 <V extends Number> V convert(T value) { return null; }
+             }",
+            "foo.java",
+            |metric| {
+                // Box: 2 parameters, 2 bounds
+                // convert: 1 parameter, 1 bound
+                insta::assert_json_snapshot!(
+                    metric.generics,
+                    @r###"
+                    {
+                      "parameters": 3.0,
+                      "bounds": 3.0,
+                      "total": 6.0,
+                      "average": 6.0,
+                      "parameters_max": 2.0,
+                      "bounds_max": 2.0
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn typescript_generic_function() {
+        check_metrics::<TypescriptParser>(
+            "function pick<T extends object, K extends keyof T, D = undefined>(obj: T, key: K): T[K] {
+                 return obj[key];
+             }",
+            "foo.ts",
+            |metric| {
+                insta::assert_json_snapshot!(
+                    metric.generics,
+                    @r###"
+                    {
+                      "parameters": 3.0,
+                      "bounds": 2.0,
+                      "total": 5.0,
+                      "average": 5.0,
+                      "parameters_max": 3.0,
+                      "bounds_max": 2.0
+                    }"###
+                );
+            },
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, fmt};
+use std::{collections::HashMap, fmt, sync::Arc};
 
 use serde::{
     ser::{SerializeStruct, Serializer},
@@ -30,13 +30,16 @@ pub enum HalsteadType {
 pub struct HalsteadMaps<'a> {
     pub(crate) operators: HashMap<u16, u64>,
     pub(crate) operands: HashMap<&'a [u8], u64>,
+    // The table classifying the nodes before the `Getter`, if any
+    pub(crate) table: Option<Arc<HalsteadTable>>,
 }
 
 impl<'a> HalsteadMaps<'a> {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(table: Option<Arc<HalsteadTable>>) -> Self {
         HalsteadMaps {
             operators: HashMap::default(),
             operands: HashMap::default(),
+            table,
         }
     }
 
@@ -263,7 +266,12 @@ fn compute_halstead<'a, T: Getter>(
     code: &'a [u8],
     halstead_maps: &mut HalsteadMaps<'a>,
 ) {
-    match T::get_op_type(node) {
+    // The data table takes precedence over the code
+    let op_type = halstead_maps
+        .table
+        .as_ref()
+        .and_then(|table| table.op_type(node));
+    match op_type.unwrap_or_else(|| T::get_op_type(node)) {
         HalsteadType::Operator => {
             *halstead_maps.operators.entry(node.kind_id()).or_insert(0) += 1;
         }
@@ -421,20 +429,20 @@ mod tests {
                     metric.halstead,
                     @r#"
                 {
-                  "n1": 0.0,
-                  "N1": 0.0,
-                  "n2": 0.0,
-                  "N2": 0.0,
-                  "length": 0.0,
-                  "estimated_program_length": null,
-                  "purity_ratio": null,
-                  "vocabulary": 0.0,
-                  "volume": null,
-                  "difficulty": null,
-                  "level": null,
-                  "effort": null,
-                  "time": null,
-                  "bugs": null
+                  "n1": 9.0,
+                  "N1": 24.0,
+                  "n2": 10.0,
+                  "N2": 18.0,
+                  "length": 42.0,
+                  "estimated_program_length": 61.74860596185444,
+                  "purity_ratio": 1.470204903853677,
+                  "vocabulary": 19.0,
+                  "volume": 178.41295556463058,
+                  "difficulty": 8.1,
+                  "level": 0.1234567901234568,
+                  "effort": 1445.1449400735075,
+                  "time": 80.28583000408375,
+                  "bugs": 0.04260752914034329
                 }
                 "#
                 );
@@ -725,4 +733,74 @@ mod tests {
             },
         );
     }
+
+    #[test]
+    fn gleam_operators_and_operands() {
+        check_metrics::<GleamParser>(
+            "fn foo(a) {
+                 let b = a + 1
+                 b |> bar
+             }",
+            "foo.gleam",
+            |metric| {
+                // unique operators: fn, (), {}, let, =, +, |>
+                // unique operands: foo, a, b, 1, bar
+                insta::assert_json_snapshot!(
+                    metric.halstead,
+                    @r###"
+                    {
+                      "n1": 7.0,
+                      "N1": 7.0,
+                      "n2": 5.0,
+                      "N2": 7.0,
+                      "length": 14.0,
+                      "estimated_program_length": 31.26112492884004,
+                      "purity_ratio": 2.2329374949171457,
+                      "vocabulary": 12.0,
+                      "volume": 50.18947501009619,
+                      "difficulty": 4.9,
+                      "level": 0.2040816326530612,
+                      "effort": 245.92842754947134,
+                      "time": 13.662690419415075,
+                      "bugs": 0.01308432231664305
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn lua_operators_and_operands() {
+        check_metrics::<LuaParser>(
+            "local function foo(a)
+                 local b = a + 1
+                 return b .. \"x\"
+             end",
+            "foo.lua",
+            |metric| {
+                // unique operators: local, function, (), =, +, return, ..
+                // unique operands: foo, a, b, 1, \"x\"
+                insta::assert_json_snapshot!(
+                    metric.halstead,
+                    @r###"
+                    {
+                      "n1": 7.0,
+                      "N1": 8.0,
+                      "n2": 5.0,
+                      "N2": 7.0,
+                      "length": 15.0,
+                      "estimated_program_length": 31.26112492884004,
+                      "purity_ratio": 2.0840749952560027,
+                      "vocabulary": 12.0,
+                      "volume": 53.77443751081734,
+                      "difficulty": 4.9,
+                      "level": 0.2040816326530612,
+                      "effort": 263.494743803005,
+                      "time": 14.638596877944723,
+                      "bugs": 0.013700193974307346
+                    }"###
+                );
+            },
+        );
+    }
 }
@@ -5,7 +5,7 @@ use serde::{
     Serialize,
 };
 
-use crate::{checker::Checker, macros::implement_metric_trait, *};
+use crate::{checker::Checker, lloc_rules::is_lloc_statement, macros::implement_metric_trait, *};
 
 /// The `SLoc` metric suite.
 #[derive(Debug, Clone)]
@@ -388,10 +388,12 @@ impl Stats {
     /// The `Blank` metric.
     ///
     /// Counts the number of blank lines in a scope.
-    /// Uses accumulated SLOC sum for accurate calculation across merged scopes.
+    /// The lines of a scope include the ones of its nested scopes, so its
+    /// own `Sloc` is used rather than the accumulated sum, which counts
+    /// the nested lines twice.
     #[inline(always)]
     pub fn blank(&self) -> f64 {
-        self.sloc.sloc_sum as f64 - self.ploc() - self.cloc.only_comment_lines as f64
+        self.sloc() - self.ploc() - self.cloc.only_comment_lines as f64
     }
 
     /// The `Sloc` metric average value.
@@ -516,6 +518,28 @@ where
     fn compute(node: &Node, stats: &mut Stats, is_func_space: bool, is_unit: bool);
 }
 
+/// Computes the `Loc` metric of a node, applying the `Lloc` overrides of
+/// its language on top of the built-in rules.
+#[inline(always)]
+pub(crate) fn compute_loc<T: Loc>(
+    language: LANG,
+    node: &Node,
+    stats: &mut Stats,
+    is_func_space: bool,
+    is_unit: bool,
+) {
+    let logical_lines = stats.lloc.logical_lines;
+    T::compute(node, stats, is_func_space, is_unit);
+    let builtin = stats.lloc.logical_lines > logical_lines;
+    if is_lloc_statement(language, node, builtin) != builtin {
+        stats.lloc.logical_lines = if builtin {
+            logical_lines
+        } else {
+            logical_lines + 1
+        };
+    }
+}
+
 #[inline(always)]
 fn init(node: &Node, stats: &mut Stats, is_func_space: bool, is_unit: bool) -> (usize, usize) {
     let start = node.start_row();
@@ -582,7 +606,9 @@ impl Loc for PythonCode {
                 add_cloc_lines(stats, start, end);
             }
             String => {
-                let parent = node.parent().unwrap();
+                let Some(parent) = node.parent() else {
+                    return;
+                };
                 if let ExpressionStatement = parent.kind_id().into() {
                     add_cloc_lines(stats, start, end);
                 } else if parent.start_row() != start {
@@ -864,6 +890,79 @@ impl Loc for JavaCode {
     }
 }
 
+impl Loc for GleamCode {
+    fn compute(node: &Node, stats: &mut Stats, is_func_space: bool, is_unit: bool) {
+        use Gleam::*;
+
+        let (start, end) = init(node, stats, is_func_space, is_unit);
+
+        match node.kind_id().into() {
+            SourceFile | FunctionBody | QuotedContent => {}
+            Comment | ModuleComment | StatementComment => {
+                add_cloc_lines(stats, start, end);
+            }
+            Import | Constant | TypeDefinition | TypeAlias | ExternalType | Let | LetAssert
+            | Use => {
+                stats.lloc.logical_lines += 1;
+            }
+            _ => {
+                check_comment_ends_on_code_line(stats, start);
+                stats.ploc.lines.insert(start);
+
+                // Gleam has no expression statements: each expression
+                // directly in a body is a statement of its own
+                if node.is_named()
+                    && node.parent().is_some_and(|parent| {
+                        matches!(parent.kind_id().into(), FunctionBody | Block)
+                    })
+                {
+                    stats.lloc.logical_lines += 1;
+                }
+            }
+        }
+    }
+}
+
+impl Loc for LuaCode {
+    fn compute(node: &Node, stats: &mut Stats, is_func_space: bool, is_unit: bool) {
+        use Lua::*;
+
+        let (start, end) = init(node, stats, is_func_space, is_unit);
+
+        match node.kind_id().into() {
+            Chunk | Block | StringContent => {}
+            Comment => {
+                add_cloc_lines(stats, start, end);
+            }
+            // The markers and the content of a comment
+            _ if node
+                .parent()
+                .is_some_and(|parent| matches!(parent.kind_id().into(), Comment)) => {}
+            ReturnStatement | BreakStatement | EmptyStatement | LabelStatement | GotoStatement
+            | DoStatement | WhileStatement | RepeatStatement | IfStatement | ForStatement
+            | VariableDeclaration | FunctionDeclaration | FunctionDeclaration2 => {
+                stats.lloc.logical_lines += 1;
+            }
+            AssignmentStatement | AssignmentStatement2 | FunctionCall => {
+                // The assignment of a `local` declaration and the calls
+                // in expressions aren't statements of their own
+                if node
+                    .parent()
+                    .is_some_and(|parent| matches!(parent.kind_id().into(), Chunk | Block))
+                {
+                    stats.lloc.logical_lines += 1;
+                }
+                check_comment_ends_on_code_line(stats, start);
+                stats.ploc.lines.insert(start);
+            }
+            _ => {
+                check_comment_ends_on_code_line(stats, start);
+                stats.ploc.lines.insert(start);
+            }
+        }
+    }
+}
+
 implement_metric_trait!(
     Loc,
     PreprocCode,
@@ -871,8 +970,6 @@ implement_metric_trait!(
     KotlinCode,
     ElixirCode,
     ErlangCode,
-    GleamCode,
-    LuaCode,
     GoCode,
     CsharpCode
 );
@@ -1055,26 +1152,26 @@ mod tests {
                     metric.loc,
                     @r#"
                 {
-                  "sloc": 1.0,
-                  "ploc": 0.0,
-                  "lloc": 0.0,
+                  "sloc": 3.0,
+                  "ploc": 2.0,
+                  "lloc": 2.0,
                   "cloc": 0.0,
-                  "blank": 0.0,
-                  "sloc_average": 0.0,
-                  "ploc_average": 0.0,
-                  "lloc_average": 0.0,
+                  "blank": 1.0,
+                  "sloc_average": 3.0,
+                  "ploc_average": 2.0,
+                  "lloc_average": 2.0,
                   "cloc_average": 0.0,
-                  "blank_average": 0.0,
-                  "sloc_min": 18446744073709552000.0,
-                  "sloc_max": 0.0,
-                  "cloc_min": 18446744073709552000.0,
+                  "blank_average": 1.0,
+                  "sloc_min": 3.0,
+                  "sloc_max": 3.0,
+                  "cloc_min": 0.0,
                   "cloc_max": 0.0,
-                  "ploc_min": 18446744073709552000.0,
-                  "ploc_max": 0.0,
-                  "lloc_min": 18446744073709552000.0,
-                  "lloc_max": 0.0,
-                  "blank_min": 18446744073709552000.0,
-                  "blank_max": 0.0
+                  "ploc_min": 2.0,
+                  "ploc_max": 2.0,
+                  "lloc_min": 2.0,
+                  "lloc_max": 2.0,
+                  "blank_min": 1.0,
+                  "blank_max": 1.0
                 }
                 "#
                 );
@@ -1350,26 +1447,26 @@ mod tests {
                     metric.loc,
                     @r#"
                 {
-                  "sloc": 1.0,
-                  "ploc": 0.0,
-                  "lloc": 0.0,
-                  "cloc": 0.0,
-                  "blank": 0.0,
-                  "sloc_average": 0.0,
-                  "ploc_average": 0.0,
-                  "lloc_average": 0.0,
-                  "cloc_average": 0.0,
-                  "blank_average": 0.0,
-                  "sloc_min": 18446744073709552000.0,
-                  "sloc_max": 0.0,
-                  "cloc_min": 18446744073709552000.0,
-                  "cloc_max": 0.0,
-                  "ploc_min": 18446744073709552000.0,
-                  "ploc_max": 0.0,
-                  "lloc_min": 18446744073709552000.0,
-                  "lloc_max": 0.0,
-                  "blank_min": 18446744073709552000.0,
-                  "blank_max": 0.0
+                  "sloc": 11.0,
+                  "ploc": 8.0,
+                  "lloc": 6.0,
+                  "cloc": 4.0,
+                  "blank": 1.0,
+                  "sloc_average": 5.5,
+                  "ploc_average": 4.0,
+                  "lloc_average": 3.0,
+                  "cloc_average": 2.0,
+                  "blank_average": 0.5,
+                  "sloc_min": 11.0,
+                  "sloc_max": 11.0,
+                  "cloc_min": 4.0,
+                  "cloc_max": 4.0,
+                  "ploc_min": 8.0,
+                  "ploc_max": 8.0,
+                  "lloc_min": 6.0,
+                  "lloc_max": 6.0,
+                  "blank_min": 1.0,
+                  "blank_max": 1.0
                 }
                 "#
                 );
@@ -1401,26 +1498,26 @@ mod tests {
                     metric.loc,
                     @r#"
                 {
-                  "sloc": 1.0,
-                  "ploc": 0.0,
-                  "lloc": 0.0,
-                  "cloc": 0.0,
-                  "blank": 0.0,
-                  "sloc_average": 0.0,
-                  "ploc_average": 0.0,
-                  "lloc_average": 0.0,
-                  "cloc_average": 0.0,
-                  "blank_average": 0.0,
-                  "sloc_min": 18446744073709552000.0,
-                  "sloc_max": 0.0,
-                  "cloc_min": 18446744073709552000.0,
-                  "cloc_max": 0.0,
-                  "ploc_min": 18446744073709552000.0,
-                  "ploc_max": 0.0,
-                  "lloc_min": 18446744073709552000.0,
-                  "lloc_max": 0.0,
-                  "blank_min": 18446744073709552000.0,
-                  "blank_max": 0.0
+                  "sloc": 12.0,
+                  "ploc": 8.0,
+                  "lloc": 6.0,
+                  "cloc": 5.0,
+                  "blank": 1.0,
+                  "sloc_average": 6.0,
+                  "ploc_average": 4.0,
+                  "lloc_average": 3.0,
+                  "cloc_average": 2.5,
+                  "blank_average": 0.5,
+                  "sloc_min": 12.0,
+                  "sloc_max": 12.0,
+                  "cloc_min": 5.0,
+                  "cloc_max": 5.0,
+                  "ploc_min": 8.0,
+                  "ploc_max": 8.0,
+                  "lloc_min": 6.0,
+                  "lloc_max": 6.0,
+                  "blank_min": 1.0,
+                  "blank_max": 1.0
                 }
                 "#
                 );
@@ -1453,26 +1550,26 @@ mod tests {
                     metric.loc,
                     @r#"
                 {
-                  "sloc": 1.0,
-                  "ploc": 0.0,
-                  "lloc": 0.0,
-                  "cloc": 0.0,
-                  "blank": 0.0,
-                  "sloc_average": 0.0,
-                  "ploc_average": 0.0,
-                  "lloc_average": 0.0,
-                  "cloc_average": 0.0,
-                  "blank_average": 0.0,
-                  "sloc_min": 18446744073709552000.0,
-                  "sloc_max": 0.0,
-                  "cloc_min": 18446744073709552000.0,
-                  "cloc_max": 0.0,
-                  "ploc_min": 18446744073709552000.0,
-                  "ploc_max": 0.0,
-                  "lloc_min": 18446744073709552000.0,
-                  "lloc_max": 0.0,
-                  "blank_min": 18446744073709552000.0,
-                  "blank_max": 0.0
+                  "sloc": 13.0,
+                  "ploc": 8.0,
+                  "lloc": 6.0,
+                  "cloc": 5.0,
+                  "blank": 1.0,
+                  "sloc_average": 6.5,
+                  "ploc_average": 4.0,
+                  "lloc_average": 3.0,
+                  "cloc_average": 2.5,
+                  "blank_average": 0.5,
+                  "sloc_min": 13.0,
+                  "sloc_max": 13.0,
+                  "cloc_min": 5.0,
+                  "cloc_max": 5.0,
+                  "ploc_min": 8.0,
+                  "ploc_max": 8.0,
+                  "lloc_min": 6.0,
+                  "lloc_max": 6.0,
+                  "blank_min": 1.0,
+                  "blank_max": 1.0
                 }
                 "#
                 );
@@ -1502,26 +1599,26 @@ mod tests {
                     metric.loc,
                     @r#"
                 {
-                  "sloc": 1.0,
-                  "ploc": 0.0,
-                  "lloc": 0.0,
-                  "cloc": 0.0,
-                  "blank": 0.0,
-                  "sloc_average": 0.0,
-                  "ploc_average": 0.0,
-                  "lloc_average": 0.0,
-                  "cloc_average": 0.0,
-                  "blank_average": 0.0,
-                  "sloc_min": 18446744073709552000.0,
-                  "sloc_max": 0.0,
-                  "cloc_min": 18446744073709552000.0,
-                  "cloc_max": 0.0,
-                  "ploc_min": 18446744073709552000.0,
-                  "ploc_max": 0.0,
-                  "lloc_min": 18446744073709552000.0,
-                  "lloc_max": 0.0,
-                  "blank_min": 18446744073709552000.0,
-                  "blank_max": 0.0
+                  "sloc": 10.0,
+                  "ploc": 8.0,
+                  "lloc": 6.0,
+                  "cloc": 3.0,
+                  "blank": 1.0,
+                  "sloc_average": 5.0,
+                  "ploc_average": 4.0,
+                  "lloc_average": 3.0,
+                  "cloc_average": 1.5,
+                  "blank_average": 0.5,
+                  "sloc_min": 10.0,
+                  "sloc_max": 10.0,
+                  "cloc_min": 3.0,
+                  "cloc_max": 3.0,
+                  "ploc_min": 8.0,
+                  "ploc_max": 8.0,
+                  "lloc_min": 6.0,
+                  "lloc_max": 6.0,
+                  "blank_min": 1.0,
+                  "blank_max": 1.0
                 }
                 "#
                 );
@@ -1553,26 +1650,26 @@ mod tests {
                     metric.loc,
                     @r#"
                 {
-                  "sloc": 1.0,
-                  "ploc": 0.0,
-                  "lloc": 0.0,
-                  "cloc": 0.0,
-                  "blank": 0.0,
-                  "sloc_average": 0.0,
-                  "ploc_average": 0.0,
-                  "lloc_average": 0.0,
-                  "cloc_average": 0.0,
-                  "blank_average": 0.0,
-                  "sloc_min": 18446744073709552000.0,
-                  "sloc_max": 0.0,
-                  "cloc_min": 18446744073709552000.0,
-                  "cloc_max": 0.0,
-                  "ploc_min": 18446744073709552000.0,
-                  "ploc_max": 0.0,
-                  "lloc_min": 18446744073709552000.0,
-                  "lloc_max": 0.0,
-                  "blank_min": 18446744073709552000.0,
-                  "blank_max": 0.0
+                  "sloc": 12.0,
+                  "ploc": 8.0,
+                  "lloc": 6.0,
+                  "cloc": 5.0,
+                  "blank": 1.0,
+                  "sloc_average": 6.0,
+                  "ploc_average": 4.0,
+                  "lloc_average": 3.0,
+                  "cloc_average": 2.5,
+                  "blank_average": 0.5,
+                  "sloc_min": 12.0,
+                  "sloc_max": 12.0,
+                  "cloc_min": 5.0,
+                  "cloc_max": 5.0,
+                  "ploc_min": 8.0,
+                  "ploc_max": 8.0,
+                  "lloc_min": 6.0,
+                  "lloc_max": 6.0,
+                  "blank_min": 1.0,
+                  "blank_max": 1.0
                 }
                 "#
                 );
@@ -1676,25 +1773,25 @@ mod tests {
                     metric.loc,
                     @r#"
                 {
-                  "sloc": 1.0,
-                  "ploc": 0.0,
-                  "lloc": 0.0,
-                  "cloc": 0.0,
+                  "sloc": 4.0,
+                  "ploc": 1.0,
+                  "lloc": 1.0,
+                  "cloc": 5.0,
                   "blank": 0.0,
-                  "sloc_average": 0.0,
-                  "ploc_average": 0.0,
-                  "lloc_average": 0.0,
-                  "cloc_average": 0.0,
+                  "sloc_average": 4.0,
+                  "ploc_average": 1.0,
+                  "lloc_average": 1.0,
+                  "cloc_average": 5.0,
                   "blank_average": 0.0,
-                  "sloc_min": 18446744073709552000.0,
-                  "sloc_max": 0.0,
-                  "cloc_min": 18446744073709552000.0,
-                  "cloc_max": 0.0,
-                  "ploc_min": 18446744073709552000.0,
-                  "ploc_max": 0.0,
-                  "lloc_min": 18446744073709552000.0,
-                  "lloc_max": 0.0,
-                  "blank_min": 18446744073709552000.0,
+                  "sloc_min": 4.0,
+                  "sloc_max": 4.0,
+                  "cloc_min": 5.0,
+                  "cloc_max": 5.0,
+                  "ploc_min": 1.0,
+                  "ploc_max": 1.0,
+                  "lloc_min": 1.0,
+                  "lloc_max": 1.0,
+                  "blank_min": 0.0,
                   "blank_max": 0.0
                 }
                 "#
@@ -1835,25 +1932,25 @@ mod tests {
                     metric.loc,
                     @r#"
                 {
-                  "sloc": 1.0,
-                  "ploc": 0.0,
-                  "lloc": 0.0,
+                  "sloc": 2.0,
+                  "ploc": 2.0,
+                  "lloc": 2.0,
                   "cloc": 0.0,
                   "blank": 0.0,
-                  "sloc_average": 0.0,
-                  "ploc_average": 0.0,
-                  "lloc_average": 0.0,
+                  "sloc_average": 2.0,
+                  "ploc_average": 2.0,
+                  "lloc_average": 2.0,
                   "cloc_average": 0.0,
                   "blank_average": 0.0,
-                  "sloc_min": 18446744073709552000.0,
-                  "sloc_max": 0.0,
-                  "cloc_min": 18446744073709552000.0,
+                  "sloc_min": 2.0,
+                  "sloc_max": 2.0,
+                  "cloc_min": 0.0,
                   "cloc_max": 0.0,
-                  "ploc_min": 18446744073709552000.0,
-                  "ploc_max": 0.0,
-                  "lloc_min": 18446744073709552000.0,
-                  "lloc_max": 0.0,
-                  "blank_min": 18446744073709552000.0,
+                  "ploc_min": 2.0,
+                  "ploc_max": 2.0,
+                  "lloc_min": 2.0,
+                  "lloc_max": 2.0,
+                  "blank_min": 0.0,
                   "blank_max": 0.0
                 }
                 "#
@@ -1877,25 +1974,25 @@ mod tests {
                     metric.loc,
                     @r#"
                 {
-                  "sloc": 1.0,
-                  "ploc": 0.0,
-                  "lloc": 0.0,
+                  "sloc": 4.0,
+                  "ploc": 4.0,
+                  "lloc": 3.0,
                   "cloc": 0.0,
                   "blank": 0.0,
-                  "sloc_average": 0.0,
-                  "ploc_average": 0.0,
-                  "lloc_average": 0.0,
+                  "sloc_average": 4.0,
+                  "ploc_average": 4.0,
+                  "lloc_average": 3.0,
                   "cloc_average": 0.0,
                   "blank_average": 0.0,
-                  "sloc_min": 18446744073709552000.0,
-                  "sloc_max": 0.0,
-                  "cloc_min": 18446744073709552000.0,
+                  "sloc_min": 4.0,
+                  "sloc_max": 4.0,
+                  "cloc_min": 0.0,
                   "cloc_max": 0.0,
-                  "ploc_min": 18446744073709552000.0,
-                  "ploc_max": 0.0,
-                  "lloc_min": 18446744073709552000.0,
-                  "lloc_max": 0.0,
-                  "blank_min": 18446744073709552000.0,
+                  "ploc_min": 4.0,
+                  "ploc_max": 4.0,
+                  "lloc_min": 3.0,
+                  "lloc_max": 3.0,
+                  "blank_min": 0.0,
                   "blank_max": 0.0
                 }
                 "#
@@ -1917,25 +2014,25 @@ mod tests {
                     metric.loc,
                     @r#"
                 {
-                  "sloc": 1.0,
-                  "ploc": 0.0,
-                  "lloc": 0.0,
+                  "sloc": 2.0,
+                  "ploc": 2.0,
+                  "lloc": 2.0,
                   "cloc": 0.0,
                   "blank": 0.0,
-                  "sloc_average": 0.0,
-                  "ploc_average": 0.0,
-                  "lloc_average": 0.0,
+                  "sloc_average": 2.0,
+                  "ploc_average": 2.0,
+                  "lloc_average": 2.0,
                   "cloc_average": 0.0,
                   "blank_average": 0.0,
-                  "sloc_min": 18446744073709552000.0,
-                  "sloc_max": 0.0,
-                  "cloc_min": 18446744073709552000.0,
+                  "sloc_min": 2.0,
+                  "sloc_max": 2.0,
+                  "cloc_min": 0.0,
                   "cloc_max": 0.0,
-                  "ploc_min": 18446744073709552000.0,
-                  "ploc_max": 0.0,
-                  "lloc_min": 18446744073709552000.0,
-                  "lloc_max": 0.0,
-                  "blank_min": 18446744073709552000.0,
+                  "ploc_min": 2.0,
+                  "ploc_max": 2.0,
+                  "lloc_min": 2.0,
+                  "lloc_max": 2.0,
+                  "blank_min": 0.0,
                   "blank_max": 0.0
                 }
                 "#
@@ -1959,25 +2056,25 @@ mod tests {
                     metric.loc,
                     @r#"
                 {
-                  "sloc": 1.0,
-                  "ploc": 0.0,
-                  "lloc": 0.0,
+                  "sloc": 4.0,
+                  "ploc": 4.0,
+                  "lloc": 4.0,
                   "cloc": 0.0,
                   "blank": 0.0,
-                  "sloc_average": 0.0,
-                  "ploc_average": 0.0,
-                  "lloc_average": 0.0,
+                  "sloc_average": 4.0,
+                  "ploc_average": 4.0,
+                  "lloc_average": 4.0,
                   "cloc_average": 0.0,
                   "blank_average": 0.0,
-                  "sloc_min": 18446744073709552000.0,
-                  "sloc_max": 0.0,
-                  "cloc_min": 18446744073709552000.0,
+                  "sloc_min": 4.0,
+                  "sloc_max": 4.0,
+                  "cloc_min": 0.0,
                   "cloc_max": 0.0,
-                  "ploc_min": 18446744073709552000.0,
-                  "ploc_max": 0.0,
-                  "lloc_min": 18446744073709552000.0,
-                  "lloc_max": 0.0,
-                  "blank_min": 18446744073709552000.0,
+                  "ploc_min": 4.0,
+                  "ploc_max": 4.0,
+                  "lloc_min": 4.0,
+                  "lloc_max": 4.0,
+                  "blank_min": 0.0,
                   "blank_max": 0.0
                 }
                 "#
@@ -2001,25 +2098,25 @@ mod tests {
                     metric.loc,
                     @r#"
                 {
-                  "sloc": 1.0,
-                  "ploc": 0.0,
-                  "lloc": 0.0,
+                  "sloc": 4.0,
+                  "ploc": 4.0,
+                  "lloc": 3.0,
                   "cloc": 0.0,
                   "blank": 0.0,
-                  "sloc_average": 0.0,
-                  "ploc_average": 0.0,
-                  "lloc_average": 0.0,
+                  "sloc_average": 4.0,
+                  "ploc_average": 4.0,
+                  "lloc_average": 3.0,
                   "cloc_average": 0.0,
                   "blank_average": 0.0,
-                  "sloc_min": 18446744073709552000.0,
-                  "sloc_max": 0.0,
-                  "cloc_min": 18446744073709552000.0,
+                  "sloc_min": 4.0,
+                  "sloc_max": 4.0,
+                  "cloc_min": 0.0,
                   "cloc_max": 0.0,
-                  "ploc_min": 18446744073709552000.0,
-                  "ploc_max": 0.0,
-                  "lloc_min": 18446744073709552000.0,
-                  "lloc_max": 0.0,
-                  "blank_min": 18446744073709552000.0,
+                  "ploc_min": 4.0,
+                  "ploc_max": 4.0,
+                  "lloc_min": 3.0,
+                  "lloc_max": 3.0,
+                  "blank_min": 0.0,
                   "blank_max": 0.0
                 }
                 "#
@@ -2077,7 +2174,7 @@ mod tests {
              foo.field;",
             "foo.rs",
             |metric| {
-                // Spaces: 1
+                // Spaces: 2
                 insta::assert_json_snapshot!(
                     metric.loc,
                     @r###"
@@ -2087,19 +2184,19 @@ mod tests {
                       "lloc": 2.0,
                       "cloc": 0.0,
                       "blank": 0.0,
-                      "sloc_average": 5.0,
-                      "ploc_average": 5.0,
-                      "lloc_average": 2.0,
+                      "sloc_average": 1.5,
+                      "ploc_average": 2.5,
+                      "lloc_average": 1.0,
                       "cloc_average": 0.0,
                       "blank_average": 0.0,
-                      "sloc_min": 5.0,
-                      "sloc_max": 5.0,
+                      "sloc_min": 3.0,
+                      "sloc_max": 3.0,
                       "cloc_min": 0.0,
                       "cloc_max": 0.0,
-                      "ploc_min": 5.0,
-                      "ploc_max": 5.0,
-                      "lloc_min": 2.0,
-                      "lloc_max": 2.0,
+                      "ploc_min": 3.0,
+                      "ploc_max": 3.0,
+                      "lloc_min": 0.0,
+                      "lloc_max": 0.0,
                       "blank_min": 0.0,
                       "blank_max": 0.0
                     }"###
@@ -2450,12 +2547,12 @@ mod tests {
                   "ploc": 3.0,
                   "lloc": 3.0,
                   "cloc": 3.0,
-                  "blank": -1.0,
+                  "blank": 0.0,
                   "sloc_average": 0.6666666666666666,
                   "ploc_average": 1.0,
                   "lloc_average": 1.0,
                   "cloc_average": 1.0,
-                  "blank_average": -0.3333333333333333,
+                  "blank_average": 0.0,
                   "sloc_min": 1.0,
                   "sloc_max": 1.0,
                   "cloc_min": 0.0,
@@ -2665,24 +2762,24 @@ mod tests {
                     @r#"
                 {
                   "sloc": 1.0,
-                  "ploc": 0.0,
+                  "ploc": 1.0,
                   "lloc": 0.0,
-                  "cloc": 0.0,
+                  "cloc": 1.0,
                   "blank": 0.0,
-                  "sloc_average": 0.0,
-                  "ploc_average": 0.0,
+                  "sloc_average": 0.5,
+                  "ploc_average": 0.5,
                   "lloc_average": 0.0,
-                  "cloc_average": 0.0,
+                  "cloc_average": 0.5,
                   "blank_average": 0.0,
-                  "sloc_min": 18446744073709552000.0,
-                  "sloc_max": 0.0,
-                  "cloc_min": 18446744073709552000.0,
+                  "sloc_min": 1.0,
+                  "sloc_max": 1.0,
+                  "cloc_min": 0.0,
                   "cloc_max": 0.0,
-                  "ploc_min": 18446744073709552000.0,
-                  "ploc_max": 0.0,
-                  "lloc_min": 18446744073709552000.0,
+                  "ploc_min": 1.0,
+                  "ploc_max": 1.0,
+                  "lloc_min": 0.0,
                   "lloc_max": 0.0,
-                  "blank_min": 18446744073709552000.0,
+                  "blank_min": 0.0,
                   "blank_max": 0.0
                 }
                 "#
@@ -3501,12 +3598,12 @@ mod tests {
           "ploc": 7.0,
           "lloc": 2.0,
           "cloc": 6.0,
-          "blank": -5.0,
+          "blank": 1.0,
           "sloc_average": 2.0,
           "ploc_average": 2.3333333333333335,
           "lloc_average": 0.6666666666666666,
           "cloc_average": 2.0,
-          "blank_average": -1.6666666666666667,
+          "blank_average": 0.3333333333333333,
           "sloc_min": 6.0,
           "sloc_max": 6.0,
           "cloc_min": 2.0,
@@ -3523,4 +3620,94 @@ mod tests {
       },
     );
     }
+
+    #[test]
+    fn gleam_general_loc() {
+        check_metrics::<GleamParser>(
+            "import gleam/io
+
+             // Prints the sum
+             pub fn add(a, b) {
+                 let c = a + b // sum
+                 io.println(c)
+                 c
+             }",
+            "foo.gleam",
+            |metric| {
+                // Spaces: 2
+                insta::assert_json_snapshot!(
+                    metric.loc,
+                    @r#"
+                {
+                  "sloc": 8.0,
+                  "ploc": 6.0,
+                  "lloc": 4.0,
+                  "cloc": 2.0,
+                  "blank": 1.0,
+                  "sloc_average": 1.6666666666666667,
+                  "ploc_average": 2.0,
+                  "lloc_average": 1.3333333333333333,
+                  "cloc_average": 0.6666666666666666,
+                  "blank_average": 0.3333333333333333,
+                  "sloc_min": 5.0,
+                  "sloc_max": 5.0,
+                  "cloc_min": 1.0,
+                  "cloc_max": 1.0,
+                  "ploc_min": 5.0,
+                  "ploc_max": 5.0,
+                  "lloc_min": 3.0,
+                  "lloc_max": 3.0,
+                  "blank_min": 0.0,
+                  "blank_max": 0.0
+                }
+                "#
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn lua_general_loc() {
+        check_metrics::<LuaParser>(
+            "--[[ Prints
+                  the sum ]]
+             local function add(a, b)
+                 local c = a + b -- sum
+
+                 print(c)
+                 return c
+             end",
+            "foo.lua",
+            |metric| {
+                // Spaces: 2
+                insta::assert_json_snapshot!(
+                    metric.loc,
+                    @r#"
+                {
+                  "sloc": 8.0,
+                  "ploc": 5.0,
+                  "lloc": 4.0,
+                  "cloc": 3.0,
+                  "blank": 1.0,
+                  "sloc_average": 3.0,
+                  "ploc_average": 2.5,
+                  "lloc_average": 2.0,
+                  "cloc_average": 1.5,
+                  "blank_average": 0.5,
+                  "sloc_min": 6.0,
+                  "sloc_max": 6.0,
+                  "cloc_min": 1.0,
+                  "cloc_max": 1.0,
+                  "ploc_min": 5.0,
+                  "ploc_max": 5.0,
+                  "lloc_min": 4.0,
+                  "lloc_max": 4.0,
+                  "blank_min": 1.0,
+                  "blank_max": 1.0
+                }
+                "#
+                );
+            },
+        );
+    }
 }
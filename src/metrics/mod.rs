@@ -1,7 +1,13 @@
+// The metrics are computed on arbitrary, even invalid, code, so they
+// must never panic
+#![cfg_attr(not(test), deny(clippy::unwrap_used))]
+
 pub mod abc;
 pub mod cognitive;
 pub mod cyclomatic;
 pub mod exit;
+pub mod extras;
+pub mod generics;
 pub mod halstead;
 pub mod loc;
 pub mod mi;
@@ -9,6 +15,7 @@ pub mod nargs;
 pub mod nom;
 pub mod npa;
 pub mod npm;
+pub mod unsafety;
 pub mod wmc;
 
 // AI/LLM-Powered Metrics for Best-in-Class Code Analysis
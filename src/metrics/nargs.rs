@@ -317,9 +317,9 @@ mod tests {
               "average_closures": 0.0,
               "total": 0.0,
               "average": 0.0,
-              "functions_min": 18446744073709552000.0,
+              "functions_min": 0.0,
               "functions_max": 0.0,
-              "closures_min": 18446744073709552000.0,
+              "closures_min": 0.0,
               "closures_max": 0.0
             }
             "#
@@ -427,15 +427,15 @@ mod tests {
                     metric.nargs,
                     @r#"
                 {
-                  "total_functions": 0.0,
+                  "total_functions": 2.0,
                   "total_closures": 0.0,
-                  "average_functions": 0.0,
+                  "average_functions": 2.0,
                   "average_closures": 0.0,
-                  "total": 0.0,
-                  "average": 0.0,
-                  "functions_min": 18446744073709552000.0,
-                  "functions_max": 0.0,
-                  "closures_min": 18446744073709552000.0,
+                  "total": 2.0,
+                  "average": 2.0,
+                  "functions_min": 0.0,
+                  "functions_max": 2.0,
+                  "closures_min": 0.0,
                   "closures_max": 0.0
                 }
                 "#
@@ -537,15 +537,15 @@ mod tests {
                     @r#"
                 {
                   "total_functions": 0.0,
-                  "total_closures": 0.0,
+                  "total_closures": 2.0,
                   "average_functions": 0.0,
-                  "average_closures": 0.0,
-                  "total": 0.0,
-                  "average": 0.0,
-                  "functions_min": 18446744073709552000.0,
+                  "average_closures": 2.0,
+                  "total": 2.0,
+                  "average": 2.0,
+                  "functions_min": 0.0,
                   "functions_max": 0.0,
-                  "closures_min": 18446744073709552000.0,
-                  "closures_max": 0.0
+                  "closures_min": 2.0,
+                  "closures_max": 2.0
                 }
                 "#
                 );
@@ -922,16 +922,16 @@ mod tests {
                     metric.nargs,
                     @r#"
                 {
-                  "total_functions": 0.0,
-                  "total_closures": 0.0,
-                  "average_functions": 0.0,
-                  "average_closures": 0.0,
-                  "total": 0.0,
-                  "average": 0.0,
-                  "functions_min": 18446744073709552000.0,
-                  "functions_max": 0.0,
-                  "closures_min": 18446744073709552000.0,
-                  "closures_max": 0.0
+                  "total_functions": 3.0,
+                  "total_closures": 3.0,
+                  "average_functions": 3.0,
+                  "average_closures": 1.5,
+                  "total": 6.0,
+                  "average": 2.0,
+                  "functions_min": 0.0,
+                  "functions_max": 3.0,
+                  "closures_min": 0.0,
+                  "closures_max": 3.0
                 }
                 "#
                 );
@@ -973,4 +973,68 @@ mod tests {
             },
         );
     }
+
+    #[test]
+    fn gleam_functions() {
+        check_metrics::<GleamParser>(
+            "pub fn f(a: Int, b: Int) -> Int {
+                 a + b
+             }
+             fn g() {
+                 fn(x, y, z) { x }
+             }",
+            "foo.gleam",
+            |metric| {
+                // 2 functions + 1 closure
+                insta::assert_json_snapshot!(
+                    metric.nargs,
+                    @r###"
+                    {
+                      "total_functions": 2.0,
+                      "total_closures": 3.0,
+                      "average_functions": 1.0,
+                      "average_closures": 3.0,
+                      "total": 5.0,
+                      "average": 1.6666666666666667,
+                      "functions_min": 0.0,
+                      "functions_max": 2.0,
+                      "closures_min": 0.0,
+                      "closures_max": 3.0
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn lua_functions() {
+        check_metrics::<LuaParser>(
+            "function f(a, b)
+                 return a
+             end
+             function t:m(a, ...)
+                 return function(x) return x end
+             end",
+            "foo.lua",
+            |metric| {
+                // 2 functions + 1 closure
+                insta::assert_json_snapshot!(
+                    metric.nargs,
+                    @r###"
+                    {
+                      "total_functions": 4.0,
+                      "total_closures": 1.0,
+                      "average_functions": 2.0,
+                      "average_closures": 1.0,
+                      "total": 5.0,
+                      "average": 1.6666666666666667,
+                      "functions_min": 0.0,
+                      "functions_max": 2.0,
+                      "closures_min": 0.0,
+                      "closures_max": 1.0
+                    }"###
+                );
+            },
+        );
+    }
 }
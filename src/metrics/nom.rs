@@ -269,17 +269,17 @@ mod tests {
              let closure = |i: i32| -> i32 { i + 42 };",
             "foo.rs",
             |metric| {
-                // Number of spaces = 4
+                // Number of spaces = 6
                 insta::assert_json_snapshot!(
                     metric.nom,
                     @r###"
                     {
                       "functions": 2.0,
                       "closures": 1.0,
-                      "functions_average": 0.5,
-                      "closures_average": 0.25,
+                      "functions_average": 0.3333333333333333,
+                      "closures_average": 0.16666666666666666,
                       "total": 3.0,
-                      "average": 0.75,
+                      "average": 0.5,
                       "functions_min": 0.0,
                       "functions_max": 1.0,
                       "closures_min": 0.0,
@@ -305,15 +305,15 @@ mod tests {
                     metric.nom,
                     @r#"
                 {
-                  "functions": 0.0,
+                  "functions": 1.0,
                   "closures": 0.0,
-                  "functions_average": 0.0,
+                  "functions_average": 0.5,
                   "closures_average": 0.0,
-                  "total": 0.0,
-                  "average": 0.0,
-                  "functions_min": 18446744073709552000.0,
-                  "functions_max": 0.0,
-                  "closures_min": 18446744073709552000.0,
+                  "total": 1.0,
+                  "average": 0.5,
+                  "functions_min": 0.0,
+                  "functions_max": 1.0,
+                  "closures_min": 0.0,
                   "closures_max": 0.0
                 }
                 "#
@@ -337,16 +337,16 @@ mod tests {
                     metric.nom,
                     @r#"
                 {
-                  "functions": 0.0,
-                  "closures": 0.0,
-                  "functions_average": 0.0,
-                  "closures_average": 0.0,
-                  "total": 0.0,
-                  "average": 0.0,
-                  "functions_min": 18446744073709552000.0,
-                  "functions_max": 0.0,
-                  "closures_min": 18446744073709552000.0,
-                  "closures_max": 0.0
+                  "functions": 2.0,
+                  "closures": 1.0,
+                  "functions_average": 0.5,
+                  "closures_average": 0.25,
+                  "total": 3.0,
+                  "average": 0.75,
+                  "functions_min": 0.0,
+                  "functions_max": 1.0,
+                  "closures_min": 0.0,
+                  "closures_max": 1.0
                 }
                 "#
                 );
@@ -0,0 +1,459 @@
+use std::fmt;
+
+use serde::{
+    ser::{SerializeStruct, Serializer},
+    Serialize,
+};
+
+use crate::{checker::Checker, macros::implement_metric_trait, *};
+
+/// The `Unsafety` metric.
+///
+/// This metric is a safety indicator for code dealing with raw memory.
+/// In `Rust` it counts `unsafe` blocks and functions, dereferences performed
+/// in an `unsafe` context and `extern "C"` items. In `C/C++` it counts casts
+/// and pointer arithmetic. The density relates the total to the number of
+/// source lines of a space.
+#[derive(Clone, Debug, Default)]
+pub struct Stats {
+    unsafe_blocks: usize,
+    unsafe_functions: usize,
+    raw_derefs: usize,
+    extern_items: usize,
+    casts: usize,
+    pointer_arithmetic: usize,
+    unsafe_blocks_sum: usize,
+    unsafe_functions_sum: usize,
+    raw_derefs_sum: usize,
+    extern_items_sum: usize,
+    casts_sum: usize,
+    pointer_arithmetic_sum: usize,
+    sloc: usize,
+    is_enabled: bool,
+}
+
+impl Serialize for Stats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut st = serializer.serialize_struct("unsafety", 8)?;
+        st.serialize_field("unsafe_blocks", &self.unsafe_blocks_sum())?;
+        st.serialize_field("unsafe_functions", &self.unsafe_functions_sum())?;
+        st.serialize_field("raw_derefs", &self.raw_derefs_sum())?;
+        st.serialize_field("extern_items", &self.extern_items_sum())?;
+        st.serialize_field("casts", &self.casts_sum())?;
+        st.serialize_field("pointer_arithmetic", &self.pointer_arithmetic_sum())?;
+        st.serialize_field("total", &self.total())?;
+        st.serialize_field("density", &self.density())?;
+        st.end()
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unsafe_blocks: {}, unsafe_functions: {}, raw_derefs: {}, extern_items: {}, casts: {}, pointer_arithmetic: {}, total: {}, density: {}",
+            self.unsafe_blocks_sum(),
+            self.unsafe_functions_sum(),
+            self.raw_derefs_sum(),
+            self.extern_items_sum(),
+            self.casts_sum(),
+            self.pointer_arithmetic_sum(),
+            self.total(),
+            self.density()
+        )
+    }
+}
+
+impl Stats {
+    /// Merges a second `Unsafety` metric into the first one
+    pub fn merge(&mut self, other: &Stats) {
+        self.unsafe_blocks_sum += other.unsafe_blocks_sum;
+        self.unsafe_functions_sum += other.unsafe_functions_sum;
+        self.raw_derefs_sum += other.raw_derefs_sum;
+        self.extern_items_sum += other.extern_items_sum;
+        self.casts_sum += other.casts_sum;
+        self.pointer_arithmetic_sum += other.pointer_arithmetic_sum;
+        self.is_enabled |= other.is_enabled;
+    }
+
+    /// Returns the number of `unsafe` blocks in a space.
+    #[inline(always)]
+    pub fn unsafe_blocks(&self) -> f64 {
+        self.unsafe_blocks as f64
+    }
+
+    /// Returns the number of `unsafe` functions in a space.
+    #[inline(always)]
+    pub fn unsafe_functions(&self) -> f64 {
+        self.unsafe_functions as f64
+    }
+
+    /// Returns the number of dereferences in an `unsafe` context in a space.
+    #[inline(always)]
+    pub fn raw_derefs(&self) -> f64 {
+        self.raw_derefs as f64
+    }
+
+    /// Returns the number of `extern` items in a space.
+    #[inline(always)]
+    pub fn extern_items(&self) -> f64 {
+        self.extern_items as f64
+    }
+
+    /// Returns the number of casts in a space.
+    #[inline(always)]
+    pub fn casts(&self) -> f64 {
+        self.casts as f64
+    }
+
+    /// Returns the number of pointer arithmetic operations in a space.
+    #[inline(always)]
+    pub fn pointer_arithmetic(&self) -> f64 {
+        self.pointer_arithmetic as f64
+    }
+
+    /// Returns the number of `unsafe` blocks sum in a space.
+    #[inline(always)]
+    pub fn unsafe_blocks_sum(&self) -> f64 {
+        self.unsafe_blocks_sum as f64
+    }
+
+    /// Returns the number of `unsafe` functions sum in a space.
+    #[inline(always)]
+    pub fn unsafe_functions_sum(&self) -> f64 {
+        self.unsafe_functions_sum as f64
+    }
+
+    /// Returns the number of dereferences in an `unsafe` context sum
+    /// in a space.
+    #[inline(always)]
+    pub fn raw_derefs_sum(&self) -> f64 {
+        self.raw_derefs_sum as f64
+    }
+
+    /// Returns the number of `extern` items sum in a space.
+    #[inline(always)]
+    pub fn extern_items_sum(&self) -> f64 {
+        self.extern_items_sum as f64
+    }
+
+    /// Returns the number of casts sum in a space.
+    #[inline(always)]
+    pub fn casts_sum(&self) -> f64 {
+        self.casts_sum as f64
+    }
+
+    /// Returns the number of pointer arithmetic operations sum in a space.
+    #[inline(always)]
+    pub fn pointer_arithmetic_sum(&self) -> f64 {
+        self.pointer_arithmetic_sum as f64
+    }
+
+    /// Returns the total number of unsafe constructs in a space.
+    #[inline(always)]
+    pub fn total(&self) -> f64 {
+        self.unsafe_blocks_sum()
+            + self.unsafe_functions_sum()
+            + self.raw_derefs_sum()
+            + self.extern_items_sum()
+            + self.casts_sum()
+            + self.pointer_arithmetic_sum()
+    }
+
+    /// Returns the `Unsafety` density value
+    ///
+    /// This value is computed dividing the total number of unsafe
+    /// constructs by the number of source lines of a space.
+    #[inline(always)]
+    pub fn density(&self) -> f64 {
+        self.total() / self.sloc as f64
+    }
+
+    #[inline(always)]
+    pub(crate) fn compute_sum(&mut self) {
+        self.unsafe_blocks_sum += self.unsafe_blocks;
+        self.unsafe_functions_sum += self.unsafe_functions;
+        self.raw_derefs_sum += self.raw_derefs;
+        self.extern_items_sum += self.extern_items;
+        self.casts_sum += self.casts;
+        self.pointer_arithmetic_sum += self.pointer_arithmetic;
+    }
+
+    pub(crate) fn finalize(&mut self, sloc: usize) {
+        self.sloc = sloc;
+    }
+
+    // Checks if the `Unsafety` metric is disabled
+    #[inline(always)]
+    pub(crate) fn is_disabled(&self) -> bool {
+        !self.is_enabled
+    }
+}
+
+pub trait Unsafety
+where
+    Self: Checker,
+{
+    fn compute(node: &Node, stats: &mut Stats);
+}
+
+// Returns true if the function has an `unsafe` qualifier
+fn is_rust_unsafe_fn(node: &Node) -> bool {
+    use Rust::*;
+
+    node.first_child(|id| id == FunctionModifiers)
+        .is_some_and(|modifiers| modifiers.first_child(|id| id == Unsafe).is_some())
+}
+
+// Returns true if the node is inside an `unsafe` block or function,
+// closures are transparent while other items are not
+fn in_rust_unsafe_context(node: &Node) -> bool {
+    use Rust::*;
+
+    let mut current = node.parent();
+    while let Some(ancestor) = current {
+        match ancestor.kind_id().into() {
+            UnsafeBlock => return true,
+            FunctionItem => return is_rust_unsafe_fn(&ancestor),
+            ImplItem | TraitItem | ModItem => return false,
+            _ => {}
+        }
+        current = ancestor.parent();
+    }
+    false
+}
+
+impl Unsafety for RustCode {
+    fn compute(node: &Node, stats: &mut Stats) {
+        use Rust::*;
+
+        stats.is_enabled = true;
+
+        match node.kind_id().into() {
+            UnsafeBlock => {
+                stats.unsafe_blocks += 1;
+            }
+            FunctionItem | FunctionSignatureItem => {
+                if is_rust_unsafe_fn(node) {
+                    stats.unsafe_functions += 1;
+                }
+                // `extern "C" fn` definitions exposed to foreign code
+                if node
+                    .first_child(|id| id == FunctionModifiers)
+                    .is_some_and(|modifiers| {
+                        modifiers.first_child(|id| id == ExternModifier).is_some()
+                    })
+                {
+                    stats.extern_items += 1;
+                }
+            }
+            // Every declaration of an `extern "C" { ... }` block
+            ForeignModItem => {
+                if let Some(body) = node.child_by_field_name("body") {
+                    stats.extern_items += body
+                        .children()
+                        .filter(|child| child.is_named() && !Self::is_comment(child))
+                        .count();
+                }
+            }
+            UnaryExpression
+                if node.child(0).is_some_and(|op| op.kind_id() == STAR)
+                    && in_rust_unsafe_context(node) =>
+            {
+                stats.raw_derefs += 1;
+            }
+            _ => {}
+        }
+    }
+}
+
+const CPP_CASTS: &[&str] = &[
+    "static_cast",
+    "dynamic_cast",
+    "const_cast",
+    "reinterpret_cast",
+];
+
+// Skips the parentheses around an expression
+fn cpp_unparenthesize<'a>(node: &Node<'a>) -> Node<'a> {
+    let mut node = *node;
+    while node.kind() == "parenthesized_expression" {
+        match node.child(1) {
+            Some(inner) => node = inner,
+            None => break,
+        }
+    }
+    node
+}
+
+// Returns true if the expression adds an offset (`a + b`, `a - b`)
+fn cpp_is_offset(node: &Node) -> bool {
+    node.kind() == "binary_expression"
+        && node
+            .child_by_field_name("operator")
+            .is_some_and(|op| matches!(op.kind(), "+" | "-"))
+}
+
+// Returns true if the expression evidently yields a pointer
+fn cpp_is_pointer_operand(node: &Node) -> bool {
+    let node = cpp_unparenthesize(node);
+    match node.kind() {
+        // `&x`
+        "pointer_expression" => node.child(0).is_some_and(|op| op.kind() == "&"),
+        // `(T *)x`
+        "cast_expression" => node.child_by_field_name("type").is_some_and(|ty| {
+            ty.child_by_field_name("declarator")
+                .is_some_and(|decl| decl.kind() == "abstract_pointer_declarator")
+        }),
+        _ => false,
+    }
+}
+
+impl Unsafety for CppCode {
+    fn compute(node: &Node, stats: &mut Stats) {
+        stats.is_enabled = true;
+
+        match node.kind() {
+            "cast_expression" => {
+                stats.casts += 1;
+            }
+            "call_expression"
+                if node
+                    .child_by_field_name("function")
+                    .filter(|function| function.kind() == "template_function")
+                    .is_some_and(|function| {
+                        function
+                            .child_by_field_name("name")
+                            .is_some_and(|name| node_text_equals_any(&name, CPP_CASTS))
+                    }) =>
+            {
+                stats.casts += 1;
+            }
+            // Types are unknown, so only the syntactically evident cases are
+            // counted: dereferences of an offset (`*(p + n)`) and offsets
+            // applied to an address or a pointer cast (`&x + 1`)
+            "pointer_expression"
+                if node.child(0).is_some_and(|op| op.kind() == "*")
+                    && node
+                        .child_by_field_name("argument")
+                        .is_some_and(|argument| cpp_is_offset(&cpp_unparenthesize(&argument))) =>
+            {
+                stats.pointer_arithmetic += 1;
+            }
+            "binary_expression"
+                if cpp_is_offset(node)
+                    && ["left", "right"].iter().any(|field| {
+                        node.child_by_field_name(field)
+                            .is_some_and(|operand| cpp_is_pointer_operand(&operand))
+                    }) =>
+            {
+                stats.pointer_arithmetic += 1;
+            }
+            _ => {}
+        }
+    }
+}
+
+implement_metric_trait!(
+    Unsafety,
+    PythonCode,
+    MozjsCode,
+    JavascriptCode,
+    TypescriptCode,
+    TsxCode,
+    PreprocCode,
+    CcommentCode,
+    JavaCode,
+    KotlinCode,
+    ElixirCode,
+    ErlangCode,
+    GleamCode,
+    LuaCode,
+    GoCode,
+    CsharpCode
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::check_metrics;
+
+    #[test]
+    fn python_no_unsafety() {
+        check_metrics::<PythonParser>("def f(a):\n    return a", "foo.py", |metric| {
+            assert!(metric.unsafety.is_disabled());
+        });
+    }
+
+    #[test]
+    fn rust_unsafe_blocks_and_functions() {
+        check_metrics::<ParserEngineRust>(
+            "unsafe fn read(p: *const u8) -> u8 {
+                 *p
+             }
+             fn write(p: *mut u8) {
+                 let r = &1;
+                 let _ = *r;
+                 unsafe {
+                     *p = 1;
+                     *p.add(1) = 2;
+                 }
+             }",
+            "foo.rs",
+            |metric| {
+                // 1 unsafe block, 1 unsafe function
+                // 3 derefs in an unsafe context, `*r` is not counted
+                insta::assert_json_snapshot!(
+                    metric.unsafety,
+                    @r###"
+                    {
+                      "unsafe_blocks": 1.0,
+                      "unsafe_functions": 1.0,
+                      "raw_derefs": 3.0,
+                      "extern_items": 0.0,
+                      "casts": 0.0,
+                      "pointer_arithmetic": 0.0,
+                      "total": 5.0,
+                      "density": 0.45454545454545453
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn rust_extern_items() {
+        check_metrics::<ParserEngineRust>(
+            "extern \"C\" {
+                 // libc
+                 fn abs(x: i32) -> i32;
+                 static errno: i32;
+             }
+             #[no_mangle]
+             pub extern \"C\" fn callback(x: i32) -> i32 {
+                 unsafe { abs(x) }
+             }",
+            "foo.rs",
+            |metric| {
+                // 2 foreign declarations, 1 exported function
+                insta::assert_json_snapshot!(
+                    metric.unsafety,
+                    @r###"
+                    {
+                      "unsafe_blocks": 1.0,
+                      "unsafe_functions": 0.0,
+                      "raw_derefs": 0.0,
+                      "extern_items": 3.0,
+                      "casts": 0.0,
+                      "pointer_arithmetic": 0.0,
+                      "total": 4.0,
+                      "density": 0.4444444444444444
+                    }"###
+                );
+            },
+        );
+    }
+}
@@ -114,7 +114,10 @@ impl Stats {
     // Checks if the `Wmc` metric is disabled
     #[inline(always)]
     pub(crate) fn is_disabled(&self) -> bool {
-        matches!(self.space_kind, SpaceKind::Function | SpaceKind::Unknown)
+        matches!(
+            self.space_kind,
+            SpaceKind::Function | SpaceKind::Closure | SpaceKind::Unknown
+        )
     }
 }
 
@@ -0,0 +1,160 @@
+use serde::{Deserialize, Serialize};
+
+/// A band of values of the maintainability index.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MiBand {
+    /// The grade of the values in the band, e.g. `A` or `green`
+    pub label: String,
+    /// The minimum value of the band
+    pub min: f64,
+}
+
+impl MiBand {
+    fn new(label: &str, min: f64) -> Self {
+        Self {
+            label: label.to_string(),
+            min,
+        }
+    }
+}
+
+/// The bands grading the maintainability index of a code, as computed
+/// by Visual Studio.
+///
+/// A value gets the grade of the best band whose minimum it reaches,
+/// or the one of the worst band when it reaches none.
+/// The default bands are the letters from `A` to `F`.
+///
+/// # Examples
+///
+/// ```
+/// use singularity_code_analysis::MiBands;
+///
+/// let bands = MiBands::traffic_light();
+/// assert_eq!(bands.grade(42.), Some("green"));
+/// assert_eq!(bands.grade(15.), Some("yellow"));
+/// assert_eq!(bands.grade(3.), Some("red"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "Vec<MiBand>", into = "Vec<MiBand>")]
+pub struct MiBands {
+    // Sorted from the best band to the worst one
+    bands: Vec<MiBand>,
+}
+
+impl Default for MiBands {
+    fn default() -> Self {
+        Self::letters()
+    }
+}
+
+impl From<Vec<MiBand>> for MiBands {
+    fn from(bands: Vec<MiBand>) -> Self {
+        Self::new(bands)
+    }
+}
+
+impl From<MiBands> for Vec<MiBand> {
+    fn from(bands: MiBands) -> Self {
+        bands.bands
+    }
+}
+
+impl MiBands {
+    /// Creates the bands, in any order.
+    pub fn new(mut bands: Vec<MiBand>) -> Self {
+        bands.sort_by(|a, b| b.min.total_cmp(&a.min));
+        Self { bands }
+    }
+
+    /// The letters from `A` to `F`, where `D` and `F` are the yellow
+    /// and red bands of Visual Studio.
+    pub fn letters() -> Self {
+        Self::new(vec![
+            MiBand::new("A", 40.),
+            MiBand::new("B", 30.),
+            MiBand::new("C", 20.),
+            MiBand::new("D", 10.),
+            MiBand::new("F", 0.),
+        ])
+    }
+
+    /// The green, yellow and red bands of Visual Studio.
+    pub fn traffic_light() -> Self {
+        Self::new(vec![
+            MiBand::new("green", 20.),
+            MiBand::new("yellow", 10.),
+            MiBand::new("red", 0.),
+        ])
+    }
+
+    /// Returns the bands, from the best to the worst.
+    pub fn bands(&self) -> &[MiBand] {
+        &self.bands
+    }
+
+    /// Returns the grade of a value, or `None` if the value is not
+    /// a number or there are no bands.
+    pub fn grade(&self, mi: f64) -> Option<&str> {
+        self.band(mi).map(|band| self.bands[band].label.as_str())
+    }
+
+    // Returns the index of the band of a value, the lower the better
+    pub(crate) fn band(&self, mi: f64) -> Option<usize> {
+        if mi.is_nan() || self.bands.is_empty() {
+            return None;
+        }
+        Some(
+            self.bands
+                .iter()
+                .position(|band| mi >= band.min)
+                .unwrap_or(self.bands.len() - 1),
+        )
+    }
+
+    // Returns the index of the band with a label
+    pub(crate) fn band_of(&self, label: &str) -> Option<usize> {
+        self.bands.iter().position(|band| band.label == label)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{metrics, ParserEngineRust, ParserTrait};
+
+    use super::*;
+
+    #[test]
+    fn mi_grades() {
+        let bands = MiBands::default();
+        assert_eq!(bands.grade(100.), Some("A"));
+        assert_eq!(bands.grade(40.), Some("A"));
+        assert_eq!(bands.grade(39.9), Some("B"));
+        assert_eq!(bands.grade(12.), Some("D"));
+        assert_eq!(bands.grade(0.), Some("F"));
+        assert_eq!(bands.grade(-5.), Some("F"));
+        assert_eq!(bands.grade(f64::NAN), None);
+
+        let bands = MiBands::new(vec![MiBand::new("bad", 0.), MiBand::new("good", 50.)]);
+        assert_eq!(bands.grade(60.), Some("good"));
+        assert_eq!(bands.grade(10.), Some("bad"));
+        assert_eq!(MiBands::new(Vec::new()).grade(10.), None);
+    }
+
+    #[test]
+    fn mi_grades_of_spaces() {
+        let path = Path::new("foo.rs");
+        let parser = ParserEngineRust::new(b"fn f() {}\nfn g() {}\n".to_vec(), path, None);
+        let mut space = metrics(&parser, path).unwrap();
+        assert_eq!(space.mi_grade, None);
+
+        space.set_mi_grades(&MiBands::traffic_light());
+        assert_eq!(space.mi_grade.as_deref(), Some("green"));
+        assert!(space
+            .spaces
+            .iter()
+            .all(|space| space.mi_grade.as_deref() == Some("green")));
+    }
+}
@@ -0,0 +1,491 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use serde::Serialize;
+
+use crate::{langs::*, preproc::*, spaces::FuncSpace};
+
+/// The maximum number of symbols combined by [`get_configurations`].
+///
+/// Symbols beyond this limit are left undefined in every configuration,
+/// so at most `2^MAX_CONFIGURATION_SYMBOLS` configurations are produced.
+pub const MAX_CONFIGURATION_SYMBOLS: usize = 8;
+
+// Directives understood by the Firefox build preprocessor
+const DIRECTIVES: &[&str] = &[
+    "define",
+    "undef",
+    "if",
+    "ifdef",
+    "ifndef",
+    "elif",
+    "elifdef",
+    "elifndef",
+    "else",
+    "endif",
+    "include",
+    "includesubst",
+    "expand",
+    "literal",
+    "filter",
+    "unfilter",
+    "error",
+];
+
+struct Directive<'a> {
+    name: &'a str,
+    args: &'a str,
+    // Offset of the first byte following the directive name
+    end: usize,
+}
+
+const fn is_identifier_part(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_'
+}
+
+fn parse_directive(line: &[u8]) -> Option<Directive<'_>> {
+    let line = std::str::from_utf8(line).ok()?;
+    let start = line.len() - line.trim_start().len();
+    let rest = line[start..].strip_prefix('#')?;
+    let name_len = rest
+        .bytes()
+        .position(|c| !c.is_ascii_lowercase())
+        .unwrap_or(rest.len());
+    let name = &rest[..name_len];
+    let args = &rest[name_len..];
+    // Private class members such as `#if = 0;` are not directives
+    if !DIRECTIVES.contains(&name)
+        || args.starts_with(|c: char| !c.is_whitespace())
+        || args.trim_start().starts_with(['=', ';', '(', '.'])
+    {
+        return None;
+    }
+    Some(Directive {
+        name,
+        args: args.trim(),
+        end: start + 1 + name_len,
+    })
+}
+
+fn first_identifier(args: &str) -> &str {
+    let len = args
+        .bytes()
+        .position(|c| !is_identifier_part(c))
+        .unwrap_or(args.len());
+    &args[..len]
+}
+
+fn tokenize(expr: &str) -> Vec<&str> {
+    let bytes = expr.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = bytes[i];
+        if c.is_ascii_whitespace() {
+            i += 1;
+        } else if is_identifier_part(c) {
+            let start = i;
+            while i < bytes.len() && is_identifier_part(bytes[i]) {
+                i += 1;
+            }
+            tokens.push(&expr[start..i]);
+        } else if i + 1 < bytes.len() && matches!(&bytes[i..i + 2], b"&&" | b"||" | b"==" | b"!=") {
+            tokens.push(&expr[i..i + 2]);
+            i += 2;
+        } else {
+            tokens.push(&expr[i..i + 1]);
+            i += 1;
+        }
+    }
+    tokens
+}
+
+// A recursive descent evaluator for `#if` and `#elif` conditions.
+//
+// A symbol is true when it is defined. Comparisons are not evaluated,
+// `A == B` is true when `A` is defined.
+struct Condition<'a, 'b> {
+    tokens: Vec<&'a str>,
+    pos: usize,
+    defines: &'b HashSet<String>,
+}
+
+impl<'a> Condition<'a, '_> {
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<&'a str> {
+        let token = self.tokens.get(self.pos).copied();
+        self.pos += 1;
+        token
+    }
+
+    fn or(&mut self) -> bool {
+        let mut value = self.and();
+        while self.peek() == Some("||") {
+            self.pos += 1;
+            value |= self.and();
+        }
+        value
+    }
+
+    fn and(&mut self) -> bool {
+        let mut value = self.comparison();
+        while self.peek() == Some("&&") {
+            self.pos += 1;
+            value &= self.comparison();
+        }
+        value
+    }
+
+    fn comparison(&mut self) -> bool {
+        let value = self.unary();
+        if matches!(self.peek(), Some("==" | "!=")) {
+            self.pos += 1;
+            self.unary();
+        }
+        value
+    }
+
+    fn unary(&mut self) -> bool {
+        match self.next() {
+            Some("!") => !self.unary(),
+            Some("(") => {
+                let value = self.or();
+                if self.peek() == Some(")") {
+                    self.pos += 1;
+                }
+                value
+            }
+            Some("defined") => {
+                let parenthesized = self.peek() == Some("(");
+                if parenthesized {
+                    self.pos += 1;
+                }
+                let value = self.next().is_some_and(|name| self.defines.contains(name));
+                if parenthesized && self.peek() == Some(")") {
+                    self.pos += 1;
+                }
+                value
+            }
+            Some(token) if token.bytes().all(|c| c.is_ascii_digit()) => token != "0",
+            Some(token) => self.defines.contains(token),
+            None => false,
+        }
+    }
+}
+
+fn evaluate(expr: &str, defines: &HashSet<String>) -> bool {
+    Condition {
+        tokens: tokenize(expr),
+        pos: 0,
+        defines,
+    }
+    .or()
+}
+
+fn split_lines(code: &[u8]) -> impl Iterator<Item = (&[u8], &[u8])> {
+    code.split_inclusive(|c| *c == b'\n').map(|line| {
+        let len = line
+            .iter()
+            .rposition(|c| *c != b'\n' && *c != b'\r')
+            .map_or(0, |i| i + 1);
+        line.split_at(len)
+    })
+}
+
+/// Checks if a `JavaScript` code contains a preprocessor directive of
+/// the Firefox build system, without copying it.
+pub fn has_directives(code: &[u8]) -> bool {
+    code.contains(&b'#') && split_lines(code).any(|(line, _)| parse_directive(line).is_some())
+}
+
+// The state of an `#if` block
+struct Branch {
+    // The enclosing block is active
+    parent: bool,
+    // The current branch is active
+    active: bool,
+    // A previous branch has already been taken
+    taken: bool,
+}
+
+impl Branch {
+    fn enter(&mut self, condition: bool) {
+        self.active = self.parent && !self.taken && condition;
+        self.taken |= self.active;
+    }
+}
+
+/// Removes the preprocessor directives of the Firefox build system
+/// from a `JavaScript` code.
+///
+/// Directive lines and the lines of the branches which are not taken
+/// with the given set of defined symbols are replaced by empty lines,
+/// so the positions of the remaining code are preserved.
+///
+/// Returns `None` if the code does not contain any directive.
+pub fn strip_directives<S: ::std::hash::BuildHasher>(
+    code: &[u8],
+    defines: &HashSet<String, S>,
+) -> Option<Vec<u8>> {
+    if !has_directives(code) {
+        return None;
+    }
+
+    let mut defines: HashSet<String> = defines.iter().cloned().collect();
+    let mut new_code = Vec::with_capacity(code.len());
+    let mut branches: Vec<Branch> = Vec::new();
+
+    for (line, eol) in split_lines(code) {
+        let active = branches.last().is_none_or(|b| b.active);
+        let Some(directive) = parse_directive(line) else {
+            if active {
+                new_code.extend(line);
+            }
+            new_code.extend(eol);
+            continue;
+        };
+
+        let name = first_identifier(directive.args);
+        match directive.name {
+            "if" | "ifdef" | "ifndef" => {
+                let condition = match directive.name {
+                    "if" => evaluate(directive.args, &defines),
+                    "ifdef" => defines.contains(name),
+                    _ => !defines.contains(name),
+                };
+                let mut branch = Branch {
+                    parent: active,
+                    active: false,
+                    taken: false,
+                };
+                branch.enter(condition);
+                branches.push(branch);
+            }
+            "elif" | "elifdef" | "elifndef" => {
+                let condition = match directive.name {
+                    "elif" => evaluate(directive.args, &defines),
+                    "elifdef" => defines.contains(name),
+                    _ => !defines.contains(name),
+                };
+                if let Some(branch) = branches.last_mut() {
+                    branch.enter(condition);
+                }
+            }
+            "else" => {
+                if let Some(branch) = branches.last_mut() {
+                    branch.enter(true);
+                }
+            }
+            "endif" => {
+                branches.pop();
+            }
+            "define" if active && !name.is_empty() => {
+                defines.insert(name.to_string());
+            }
+            "undef" if active => {
+                defines.remove(name);
+            }
+            // The rest of an `#expand` line is code
+            "expand" if active => {
+                new_code.resize(new_code.len() + directive.end, b' ');
+                new_code.extend(&line[directive.end..]);
+            }
+            _ => {}
+        }
+        new_code.extend(eol);
+    }
+
+    Some(new_code)
+}
+
+/// Returns the symbols tested by the conditional directives of a
+/// `JavaScript` code, in order of appearance.
+pub fn get_condition_symbols(code: &[u8]) -> Vec<String> {
+    let mut symbols: Vec<String> = Vec::new();
+    for (line, _) in split_lines(code) {
+        let Some(directive) = parse_directive(line) else {
+            continue;
+        };
+        let names = match directive.name {
+            "ifdef" | "ifndef" | "elifdef" | "elifndef" => vec![first_identifier(directive.args)],
+            "if" | "elif" => tokenize(directive.args)
+                .into_iter()
+                .filter(|token| {
+                    *token != "defined"
+                        && token.bytes().next().is_some_and(|c| !c.is_ascii_digit())
+                        && token.bytes().all(is_identifier_part)
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        for name in names {
+            if !name.is_empty() && !symbols.iter().any(|s| s == name) {
+                symbols.push(name.to_string());
+            }
+        }
+    }
+    symbols
+}
+
+/// Returns each combination of the symbols tested by the conditional
+/// directives of a `JavaScript` code.
+///
+/// Every configuration is the sorted list of its defined symbols, the
+/// first one defines none of them.
+/// Only the first [`MAX_CONFIGURATION_SYMBOLS`] symbols are combined.
+pub fn get_configurations(code: &[u8]) -> Vec<Vec<String>> {
+    let mut symbols = get_condition_symbols(code);
+    symbols.truncate(MAX_CONFIGURATION_SYMBOLS);
+    (0..1usize << symbols.len())
+        .map(|mask| {
+            let mut defines = symbols
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| mask & (1 << i) != 0)
+                .map(|(_, symbol)| symbol.clone())
+                .collect::<Vec<_>>();
+            defines.sort();
+            defines
+        })
+        .collect()
+}
+
+/// The metrics of a code analyzed with a set of defined symbols.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigurationSpace {
+    /// The symbols defined in this configuration
+    pub defines: Vec<String>,
+    /// The metrics of the code preprocessed with this configuration
+    pub space: FuncSpace,
+}
+
+/// Computes the metrics of a `JavaScript` code once for each
+/// configuration returned by [`get_configurations`].
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::get_function_spaces_by_configuration;
+///
+/// let source_code = "function f() {\n#ifdef RELEASE\n  return 1;\n#else\n  return 2;\n#endif\n}\n";
+/// let path = Path::new("foo.jsm");
+///
+/// let spaces = get_function_spaces_by_configuration(source_code.as_bytes(), path);
+/// assert_eq!(spaces.len(), 2);
+/// assert_eq!(spaces[1].defines, ["RELEASE"]);
+/// ```
+pub fn get_function_spaces_by_configuration(code: &[u8], path: &Path) -> Vec<ConfigurationSpace> {
+    get_configurations(code)
+        .into_iter()
+        .filter_map(|defines| {
+            let file =
+                PreprocFile::new_macros(&defines.iter().map(|d| d.as_str()).collect::<Vec<_>>());
+            let pr = PreprocResults {
+                files: HashMap::from([(PathBuf::from(path), file)]),
+            };
+            get_function_spaces(&LANG::Javascript, code.to_vec(), path, Some(Arc::new(pr)))
+                .map(|space| ConfigurationSpace { defines, space })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ParserTrait;
+
+    const SOURCE_CODE: &str = "var a = 1;\n\
+                               #ifdef MOZ_WIDGET_GTK\n\
+                               var b = 2;\n\
+                               #elif defined(XP_WIN) && !defined(XP_MACOSX)\n\
+                               var c = 3;\n\
+                               #else\n\
+                               var d = 4;\n\
+                               #endif\n\
+                               class A { #if = 0; }\n";
+
+    fn strip(code: &str, defines: &[&str]) -> String {
+        let defines = defines
+            .iter()
+            .map(|d| d.to_string())
+            .collect::<HashSet<_>>();
+        String::from_utf8(strip_directives(code.as_bytes(), &defines).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn mozjs_strip_directives() {
+        assert_eq!(
+            strip(SOURCE_CODE, &[]),
+            "var a = 1;\n\n\n\n\n\nvar d = 4;\n\nclass A { #if = 0; }\n"
+        );
+        assert_eq!(
+            strip(SOURCE_CODE, &["MOZ_WIDGET_GTK", "XP_WIN"]),
+            "var a = 1;\n\nvar b = 2;\n\n\n\n\n\nclass A { #if = 0; }\n"
+        );
+        assert_eq!(
+            strip(SOURCE_CODE, &["XP_WIN"]),
+            "var a = 1;\n\n\n\nvar c = 3;\n\n\n\nclass A { #if = 0; }\n"
+        );
+    }
+
+    #[test]
+    fn mozjs_nested_directives() {
+        let code = "#define NIGHTLY\n\
+                    #ifndef RELEASE\n\
+                    \x20 #if NIGHTLY || BETA\n\
+                    nightly();\n\
+                    \x20 #endif\n\
+                    #expand var v = \"__VERSION__\";\n\
+                    #endif\n";
+        assert_eq!(
+            strip(code, &[]),
+            "\n\n\nnightly();\n\n        var v = \"__VERSION__\";\n\n"
+        );
+        assert_eq!(strip(code, &["RELEASE"]), "\n\n\n\n\n\n\n");
+    }
+
+    #[test]
+    fn mozjs_no_directives() {
+        let code = "#!/usr/bin/env node\nclass A { #x = 1; #define() {} }\n";
+        assert!(!has_directives(code.as_bytes()));
+        assert!(has_directives(SOURCE_CODE.as_bytes()));
+        assert!(strip_directives(code.as_bytes(), &HashSet::<String>::new()).is_none());
+    }
+
+    #[test]
+    fn mozjs_configurations() {
+        assert_eq!(
+            get_condition_symbols(SOURCE_CODE.as_bytes()),
+            ["MOZ_WIDGET_GTK", "XP_WIN", "XP_MACOSX"]
+        );
+
+        let configurations = get_configurations(SOURCE_CODE.as_bytes());
+        assert_eq!(configurations.len(), 8);
+        assert!(configurations[0].is_empty());
+        assert_eq!(configurations[3], ["MOZ_WIDGET_GTK", "XP_WIN"]);
+    }
+
+    #[test]
+    fn mozjs_parse_without_errors() {
+        let path = Path::new("foo.jsm");
+        let parser = JavascriptParser::new(SOURCE_CODE.as_bytes().to_vec(), path, None);
+        assert!(!parser.get_root().has_error());
+        assert_eq!(parser.get_root().end_row(), 9);
+    }
+
+    #[test]
+    fn mozjs_configuration_spaces() {
+        let spaces =
+            get_function_spaces_by_configuration(SOURCE_CODE.as_bytes(), Path::new("foo.jsm"));
+        assert_eq!(spaces.len(), 8);
+        assert_eq!(spaces[7].defines, ["MOZ_WIDGET_GTK", "XP_MACOSX", "XP_WIN"]);
+    }
+}
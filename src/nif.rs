@@ -3,24 +3,89 @@
 //! This module provides Rustler NIF functions that expose the SCA library
 //! functionality to Elixir, following the "Rust calculates, Elixir orchestrates" pattern.
 
-use rustler::{Encoder, Env, Error, Term};
+use rustler::env::OwnedEnv;
+use rustler::types::map::map_new;
+use rustler::{Atom, Binary, Encoder, Env, Error, LocalPid, NifResult, ResourceArc, Term};
 use serde_json;
+use globset::{Glob, GlobSet, GlobSetBuilder};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
 
 use crate::ai::*;
+use crate::code_analyzer::{AnalyzeOptions, AnalyzerResult, SingularityCodeAnalyzer};
+use crate::concurrent_files::{ConcurrentRunner, FilesData};
+use crate::generated::GeneratedPolicy;
 use crate::langs::LANG;
+use crate::refactoring::TextEdit;
+
+/// The largest source accepted by the analysis NIFs, in bytes
+const MAX_SOURCE_BYTES: u64 = 16 * 1024 * 1024;
+
+mod atoms {
+    rustler::atoms! {
+        ok,
+        analysis_result,
+        analysis_error,
+        analysis_progress,
+        analysis_done,
+        analysis_skipped,
+        // Findings
+        smells,
+        suppressed,
+        rule_id,
+        name,
+        description,
+        severity,
+        location,
+        suggestion,
+        file_path,
+        line_start,
+        line_end,
+        column_start,
+        column_end,
+        priority,
+        effort,
+        benefits,
+        code_example,
+        edits,
+        start_line,
+        end_line,
+        replacement,
+        // Severity, priority and effort levels
+        low,
+        medium,
+        high,
+        critical,
+        urgent,
+        very_high,
+        // Errors
+        unsupported_language,
+        missing_field,
+        invalid_field,
+        invalid_utf8,
+        // Patterns
+        complexity_score,
+    }
+}
 
 /// Calculate AI-optimized complexity score for learning
 #[rustler::nif]
 pub fn calculate_ai_complexity_score(code: String, language_hint: String) -> Result<f64, Error> {
-    let language = parse_language_hint(&language_hint);
+    let language = SingularityCodeAnalyzer::new()
+        .language_from_str(&language_hint)
+        .ok_or_else(unsupported_language)?;
     Ok(calculate_ai_complexity_score(&code, language))
 }
 
 /// Extract complexity features from code
 #[rustler::nif]
 pub fn extract_complexity_features(code: String, language_hint: String) -> Result<HashMap<String, serde_json::Value>, Error> {
-    let language = parse_language_hint(&language_hint);
+    let language = SingularityCodeAnalyzer::new()
+        .language_from_str(&language_hint)
+        .ok_or_else(unsupported_language)?;
     let features = extract_complexity_features(&code, language);
     
     let mut result = HashMap::new();
@@ -30,9 +95,9 @@ pub fn extract_complexity_features(code: String, language_hint: String) -> Resul
     result.insert("control_flow_count".to_string(), serde_json::Value::Number(features.control_flow_count.into()));
     result.insert("nesting_depth".to_string(), serde_json::Value::Number(features.nesting_depth.into()));
     result.insert("operator_count".to_string(), serde_json::Value::Number(features.operator_count.into()));
-    result.insert("comment_ratio".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(features.comment_ratio).unwrap()));
-    result.insert("identifier_length_avg".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(features.identifier_length_avg).unwrap()));
-    result.insert("cyclomatic_complexity".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(features.cyclomatic_complexity).unwrap()));
+    result.insert("comment_ratio".to_string(), float_to_value(features.comment_ratio));
+    result.insert("identifier_quality".to_string(), float_to_value(features.identifier_quality));
+    result.insert("cyclomatic_complexity".to_string(), float_to_value(features.cyclomatic_complexity));
     
     Ok(result)
 }
@@ -57,14 +122,16 @@ pub fn calculate_evolution_trends(before_metrics: HashMap<String, serde_json::Va
 /// Predict AI-generated code quality
 #[rustler::nif]
 pub fn predict_ai_code_quality(code_features: HashMap<String, serde_json::Value>, language_hint: String, model_name: String) -> Result<HashMap<String, serde_json::Value>, Error> {
-    let language = parse_language_hint(&language_hint);
+    let language = SingularityCodeAnalyzer::new()
+        .language_from_str(&language_hint)
+        .ok_or_else(unsupported_language)?;
     let features = hashmap_to_code_features(&code_features)?;
     
     let prediction = predict_ai_code_quality(&features, language, &model_name);
     
     let mut result = HashMap::new();
-    result.insert("predicted_quality".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(prediction.predicted_quality.overall).unwrap()));
-    result.insert("confidence".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(prediction.confidence).unwrap()));
+    result.insert("predicted_quality".to_string(), float_to_value(prediction.predicted_quality.overall));
+    result.insert("confidence".to_string(), float_to_value(prediction.confidence));
     result.insert("risk_factors".to_string(), serde_json::Value::Array(
         prediction.risk_factors.iter().map(|rf| {
             let mut rf_map = HashMap::new();
@@ -87,125 +154,499 @@ pub fn calculate_pattern_effectiveness(pattern: String, metrics: HashMap<String,
 
 /// Calculate supervision complexity for BEAM languages
 #[rustler::nif]
-pub fn calculate_supervision_complexity(modules: Vec<String>) -> Result<f64, Error> {
-    Ok(calculate_supervision_complexity(&modules))
+pub fn calculate_supervision_complexity(code: String, language_hint: String) -> Result<f64, Error> {
+    let language = SingularityCodeAnalyzer::new()
+        .language_from_str(&language_hint)
+        .ok_or_else(unsupported_language)?;
+    Ok(calculate_supervision_complexity(&code, language))
 }
 
 /// Calculate actor complexity for BEAM languages
 #[rustler::nif]
-pub fn calculate_actor_complexity(functions: Vec<String>) -> Result<f64, Error> {
-    Ok(calculate_actor_complexity(&functions))
-}
-
-/// Parse language hint string to LANG enum
-fn parse_language_hint(hint: &str) -> LANG {
-    match hint.to_lowercase().as_str() {
-        "elixir" => LANG::Elixir,
-        "rust" => LANG::Rust,
-        "python" => LANG::Python,
-        "javascript" | "js" => LANG::Javascript,
-        "typescript" | "ts" => LANG::Typescript,
-        "java" => LANG::Java,
-        "cpp" | "c++" => LANG::Cpp,
-        "c" => LANG::C,
-        "go" | "golang" => LANG::Go,
-        "erlang" => LANG::Erlang,
-        "gleam" => LANG::Gleam,
-        "lua" => LANG::Lua,
-        _ => LANG::Rust, // Default fallback
+pub fn calculate_actor_complexity(code: String, language_hint: String) -> Result<f64, Error> {
+    let language = SingularityCodeAnalyzer::new()
+        .language_from_str(&language_hint)
+        .ok_or_else(unsupported_language)?;
+    Ok(calculate_actor_complexity(&code, language))
+}
+
+/// Analyze source code with the metrics engine and return the whole
+/// nested function-space structure
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn analyze_source<'a>(code: Binary<'a>, language_hint: String, path: String) -> Result<HashMap<String, serde_json::Value>, Error> {
+    check_source_size(code.len() as u64)?;
+    let analyzer = SingularityCodeAnalyzer::new();
+    let language = analyzer
+        .language_from_str(&language_hint)
+        .ok_or_else(unsupported_language)?;
+    let options = AnalyzeOptions {
+        virtual_path: (!path.is_empty()).then(|| Path::new(&path)),
+        ..Default::default()
+    };
+
+    let result = analyzer
+        .analyze_language(language, code.as_slice(), options)
+        .map_err(|err| Error::Term(Box::new(err.to_string())))?;
+    analyzer_result_to_hashmap(&result)
+}
+
+/// Analyze a file with the metrics engine, detecting its language from
+/// its extension, and return the whole nested function-space structure
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn analyze_file(path: String) -> Result<HashMap<String, serde_json::Value>, Error> {
+    analyze_path(Path::new(&path))
+}
+
+/// Analyze files on a background thread, so the calling scheduler is
+/// released at once, and send the result of each file to `pid` as
+/// `{:analysis_result, path, result}` or `{:analysis_error, path, reason}`,
+/// followed by `{:analysis_done, count}` once all files are analyzed
+#[rustler::nif]
+pub fn analyze_files_async(pid: LocalPid, paths: Vec<String>) -> rustler::Atom {
+    thread::spawn(move || {
+        let mut env = OwnedEnv::new();
+        for path in paths.iter() {
+            send_file_result(&mut env, &pid, path, analyze_path(Path::new(path)));
+        }
+        let _ = env.send_and_clear(&pid, |env| (atoms::analysis_done(), paths.len()).encode(env));
+    });
+
+    atoms::ok()
+}
+
+/// Analyze the files of a directory with a `ConcurrentRunner`, skipping
+/// hidden files, files matching one of `ignore_globs` and files in an
+/// unsupported language.
+///
+/// Generated, minified and vendored files are skipped and reported to `pid`
+/// as `{:analysis_skipped, path, kind}`, with `kind` one of `"generated"`,
+/// `"minified"` or `"vendored"`.
+///
+/// The result of each file is sent to `pid` as in `analyze_files_async`,
+/// followed by `{:analysis_progress, analyzed}`, and
+/// `{:analysis_done, analyzed}` is sent once the directory is analyzed.
+/// A failure of the walk is sent as `{:analysis_error, dir, reason}`.
+#[rustler::nif]
+pub fn analyze_repository(pid: LocalPid, dir: String, ignore_globs: Vec<String>, num_jobs: usize) -> Result<Atom, Error> {
+    let mut exclude = GlobSetBuilder::new();
+    for glob in ignore_globs.iter() {
+        let glob = Glob::new(glob).map_err(|err| Error::Term(Box::new(err.to_string())))?;
+        exclude.add(glob);
+    }
+    let exclude = exclude
+        .build()
+        .map_err(|err| Error::Term(Box::new(err.to_string())))?;
+
+    thread::spawn(move || {
+        let files_data = FilesData {
+            include: GlobSet::empty(),
+            exclude,
+            paths: vec![PathBuf::from(&dir)],
+        };
+        let config = RepositoryCfg {
+            pid,
+            generated: GeneratedPolicy::default(),
+        };
+        let analyzed = Arc::new(AtomicUsize::new(0));
+        let runner_analyzed = Arc::clone(&analyzed);
+
+        let result = ConcurrentRunner::new(num_jobs, move |path: PathBuf, cfg: &RepositoryCfg| {
+            if SingularityCodeAnalyzer::new().detect_language_from_path(&path).is_none() {
+                return Ok(());
+            }
+            let mut env = OwnedEnv::new();
+            let code = std::fs::read(&path)?;
+            let kind = cfg.generated.classify(&path, &code);
+            let Some(weight) = cfg.generated.weight_of(kind) else {
+                let kind = serde_json::to_value(kind).unwrap_or_default();
+                let kind = kind.as_str().unwrap_or_default();
+                let _ = env.send_and_clear(&cfg.pid, |env| (atoms::analysis_skipped(), path.to_string_lossy(), kind).encode(env));
+                return Ok(());
+            };
+            let result = analyze_path(&path).map(|mut result| {
+                if let Some(kind) = kind {
+                    result.insert("generated".to_string(), serde_json::to_value(kind).unwrap_or_default());
+                    result.insert("weight".to_string(), serde_json::Value::from(weight));
+                }
+                result
+            });
+            send_file_result(&mut env, &cfg.pid, &path.to_string_lossy(), result);
+            let analyzed = runner_analyzed.fetch_add(1, Ordering::Relaxed) + 1;
+            let _ = env.send_and_clear(&cfg.pid, |env| (atoms::analysis_progress(), analyzed).encode(env));
+            Ok(())
+        })
+        .run(config, files_data);
+
+        let mut env = OwnedEnv::new();
+        match result {
+            Ok(_) => {
+                let analyzed = analyzed.load(Ordering::Relaxed);
+                let _ = env.send_and_clear(&pid, |env| (atoms::analysis_done(), analyzed).encode(env));
+            }
+            Err(err) => {
+                let reason = format!("{:?}", err);
+                let _ = env.send_and_clear(&pid, |env| (atoms::analysis_error(), &dir, reason).encode(env));
+            }
+        }
+    });
+
+    Ok(atoms::ok())
+}
+
+/// The configuration shared by the workers of `analyze_repository`
+struct RepositoryCfg {
+    pid: LocalPid,
+    generated: GeneratedPolicy,
+}
+
+/// Send the result of the analysis of a file to `pid`
+fn send_file_result(env: &mut OwnedEnv, pid: &LocalPid, path: &str, result: Result<HashMap<String, serde_json::Value>, Error>) {
+    let _ = env.send_and_clear(pid, |env| match result {
+        Ok(result) => (atoms::analysis_result(), path, result).encode(env),
+        Err(Error::Term(reason)) => (atoms::analysis_error(), path, reason.encode(env)).encode(env),
+        Err(_) => (atoms::analysis_error(), path, "analysis failed").encode(env),
+    });
+}
+
+/// Analyze a file, detecting its language from its extension
+fn analyze_path(path: &Path) -> Result<HashMap<String, serde_json::Value>, Error> {
+    let analyzer = SingularityCodeAnalyzer::new();
+    let language = analyzer
+        .detect_language_from_path(path)
+        .ok_or_else(unsupported_language)?;
+    let size = std::fs::metadata(path)
+        .map_err(|err| Error::Term(Box::new(err.to_string())))?
+        .len();
+    check_source_size(size)?;
+    let code = std::fs::read(path).map_err(|err| Error::Term(Box::new(err.to_string())))?;
+    let options = AnalyzeOptions {
+        virtual_path: Some(path),
+        ..Default::default()
+    };
+
+    let result = analyzer
+        .analyze_language(language, code, options)
+        .map_err(|err| Error::Term(Box::new(err.to_string())))?;
+    analyzer_result_to_hashmap(&result)
+}
+
+/// Reject sources too large to be analyzed in a single call
+fn check_source_size(size: u64) -> Result<(), Error> {
+    if size > MAX_SOURCE_BYTES {
+        return Err(Error::Term(Box::new(format!(
+            "source too large: {} bytes, the limit is {} bytes",
+            size, MAX_SOURCE_BYTES
+        ))));
+    }
+    Ok(())
+}
+
+/// Detect code smells with the default catalog and return the reported
+/// and the suppressed smells as maps with severity atoms and locations
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn detect_code_smells<'a>(env: Env<'a>, code: Binary<'a>, language_hint: String) -> NifResult<Term<'a>> {
+    check_source_size(code.len() as u64)?;
+    let code = binary_to_str(&code)?;
+    let language = SingularityCodeAnalyzer::new()
+        .language_from_str(&language_hint)
+        .ok_or_else(unsupported_language)?;
+    let report = SemanticAnalyzer::new().detect_code_smells_with_catalog(code, Some(language), &SmellCatalog::default());
+
+    let smells = report
+        .smells
+        .iter()
+        .map(|smell| code_smell_to_term(env, smell))
+        .collect::<NifResult<Vec<_>>>()?;
+    let suppressed = report
+        .suppressed
+        .iter()
+        .map(|smell| code_smell_to_term(env, smell))
+        .collect::<NifResult<Vec<_>>>()?;
+
+    map_new(env)
+        .map_put(atoms::smells().encode(env), smells.encode(env))?
+        .map_put(atoms::suppressed().encode(env), suppressed.encode(env))
+}
+
+/// Suggest refactorings and return them as maps with priority and
+/// effort atoms
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn suggest_refactoring<'a>(env: Env<'a>, code: Binary<'a>, language_hint: String) -> NifResult<Term<'a>> {
+    check_source_size(code.len() as u64)?;
+    let language = SingularityCodeAnalyzer::new()
+        .language_from_str(&language_hint)
+        .ok_or_else(unsupported_language)?;
+    let suggestions = SemanticAnalyzer::new()
+        .suggest_refactoring(binary_to_str(&code)?, language)
+        .iter()
+        .map(|suggestion| refactoring_suggestion_to_term(env, suggestion))
+        .collect::<NifResult<Vec<_>>>()?;
+
+    Ok(suggestions.encode(env))
+}
+
+/// A `SemanticAnalyzer` living across NIF calls, so the patterns it
+/// learns persist on the Elixir side as a resource handle
+pub struct SemanticAnalyzerResource(Mutex<SemanticAnalyzer>);
+
+/// Create a semantic analyzer resource with the given similarity threshold
+#[rustler::nif]
+pub fn semantic_analyzer_new(similarity_threshold: f64) -> ResourceArc<SemanticAnalyzerResource> {
+    let analyzer = SemanticAnalyzer::with_threshold(similarity_threshold as f32);
+    ResourceArc::new(SemanticAnalyzerResource(Mutex::new(analyzer)))
+}
+
+/// Teach a code pattern to a semantic analyzer resource
+#[rustler::nif]
+pub fn semantic_analyzer_add_pattern<'a>(
+    resource: ResourceArc<SemanticAnalyzerResource>,
+    name: String,
+    pattern_type: String,
+    language_hint: String,
+    example: Binary<'a>,
+) -> Result<Atom, Error> {
+    let pattern_type = match pattern_type.as_str() {
+        "design_pattern" => PatternType::DesignPattern,
+        "anti_pattern" => PatternType::AntiPattern,
+        "code_smell" => PatternType::CodeSmell,
+        "best_practice" => PatternType::BestPractice,
+        "refactoring_opportunity" => PatternType::RefactoringOpportunity,
+        "learned_pattern" => PatternType::LearnedPattern,
+        _ => return Err(invalid_field("pattern_type")),
+    };
+    let pattern = CodePattern {
+        name,
+        description: String::new(),
+        pattern_type,
+        complexity_score: 0.0,
+        language: SingularityCodeAnalyzer::new()
+            .language_from_str(&language_hint)
+            .ok_or_else(unsupported_language)?,
+        example: binary_to_str(&example)?.to_string(),
+    };
+
+    lock_analyzer(&resource)?.add_pattern(pattern);
+    Ok(atoms::ok())
+}
+
+/// Find the patterns learned by a semantic analyzer resource which are
+/// similar to a piece of code
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn semantic_analyzer_find_similar<'a>(env: Env<'a>, resource: ResourceArc<SemanticAnalyzerResource>, query: Binary<'a>) -> NifResult<Term<'a>> {
+    check_source_size(query.len() as u64)?;
+    let patterns = lock_analyzer(&resource)?.find_similar_patterns(binary_to_str(&query)?);
+
+    let patterns = patterns
+        .iter()
+        .map(|pattern| {
+            map_new(env)
+                .map_put(atoms::name().encode(env), pattern.name.encode(env))?
+                .map_put(atoms::complexity_score().encode(env), pattern.complexity_score.encode(env))
+        })
+        .collect::<NifResult<Vec<_>>>()?;
+
+    Ok(patterns.encode(env))
+}
+
+/// Lock the analyzer of a resource
+fn lock_analyzer(resource: &SemanticAnalyzerResource) -> Result<std::sync::MutexGuard<'_, SemanticAnalyzer>, Error> {
+    resource
+        .0
+        .lock()
+        .map_err(|_| Error::Term(Box::new("semantic analyzer poisoned")))
+}
+
+/// Borrow the content of a binary as UTF-8 without copying it
+///
+/// Returns `{:error, :invalid_utf8}` when the binary isn't valid UTF-8
+fn binary_to_str<'a>(binary: &'a Binary) -> Result<&'a str, Error> {
+    std::str::from_utf8(binary.as_slice()).map_err(|_| Error::Term(Box::new(atoms::invalid_utf8())))
+}
+
+/// The `{:error, :unsupported_language}` error
+fn unsupported_language() -> Error {
+    Error::Term(Box::new(atoms::unsupported_language()))
+}
+
+/// The `{:error, {:missing_field, name}}` error
+fn missing_field(name: &str) -> Error {
+    Error::Term(Box::new((atoms::missing_field(), name.to_string())))
+}
+
+/// The `{:error, {:invalid_field, name}}` error
+fn invalid_field(name: &str) -> Error {
+    Error::Term(Box::new((atoms::invalid_field(), name.to_string())))
+}
+
+/// Get a required float field of a HashMap
+fn get_f64(map: &HashMap<String, serde_json::Value>, name: &str) -> Result<f64, Error> {
+    let value = map.get(name).ok_or_else(|| missing_field(name))?;
+    value.as_f64().ok_or_else(|| invalid_field(name))
+}
+
+/// Get a required non-negative integer field of a HashMap
+fn get_usize(map: &HashMap<String, serde_json::Value>, name: &str) -> Result<usize, Error> {
+    let value = map.get(name).ok_or_else(|| missing_field(name))?;
+    value
+        .as_u64()
+        .map(|value| value as usize)
+        .ok_or_else(|| invalid_field(name))
+}
+
+/// Convert a float to a JSON number, or to null when it isn't finite,
+/// since JSON has no NaN nor infinity
+fn float_to_value(value: f64) -> serde_json::Value {
+    serde_json::Number::from_f64(value).map_or(serde_json::Value::Null, serde_json::Value::Number)
+}
+
+/// Convert an analysis result to a HashMap holding its language and its
+/// root space, with subspaces and metrics as nested maps
+fn analyzer_result_to_hashmap(result: &AnalyzerResult) -> Result<HashMap<String, serde_json::Value>, Error> {
+    let root_space = serde_json::to_value(&result.root_space)
+        .map_err(|err| Error::Term(Box::new(err.to_string())))?;
+    let metadata = serde_json::to_value(&result.metadata)
+        .map_err(|err| Error::Term(Box::new(err.to_string())))?;
+
+    let mut map = HashMap::new();
+    map.insert("language".to_string(), serde_json::Value::String(result.language.get_name().to_string()));
+    map.insert("root_space".to_string(), root_space);
+    map.insert("encoding".to_string(), serde_json::Value::String(result.encoding.to_string()));
+    map.insert("metadata".to_string(), metadata);
+    map.insert("parse_errors".to_string(), serde_json::Value::from(result.root_space.parse_errors.len()));
+    if let Some(profile) = &result.profile {
+        let profile = serde_json::to_value(profile).map_err(|err| Error::Term(Box::new(err.to_string())))?;
+        map.insert("profile".to_string(), profile);
     }
+
+    Ok(map)
+}
+
+/// Convert a severity to an atom
+fn severity_to_atom(severity: &Severity) -> Atom {
+    match severity {
+        Severity::Low => atoms::low(),
+        Severity::Medium => atoms::medium(),
+        Severity::High => atoms::high(),
+        Severity::Critical => atoms::critical(),
+    }
+}
+
+/// Convert a code location to a map
+fn code_location_to_term<'a>(env: Env<'a>, location: &CodeLocation) -> NifResult<Term<'a>> {
+    map_new(env)
+        .map_put(atoms::file_path().encode(env), location.file_path.encode(env))?
+        .map_put(atoms::line_start().encode(env), location.line_start.encode(env))?
+        .map_put(atoms::line_end().encode(env), location.line_end.encode(env))?
+        .map_put(atoms::column_start().encode(env), location.column_start.encode(env))?
+        .map_put(atoms::column_end().encode(env), location.column_end.encode(env))
+}
+
+/// Convert a code smell to a map
+fn code_smell_to_term<'a>(env: Env<'a>, smell: &CodeSmell) -> NifResult<Term<'a>> {
+    map_new(env)
+        .map_put(atoms::rule_id().encode(env), smell.rule_id.encode(env))?
+        .map_put(atoms::name().encode(env), smell.name.encode(env))?
+        .map_put(atoms::description().encode(env), smell.description.encode(env))?
+        .map_put(atoms::severity().encode(env), severity_to_atom(&smell.severity).encode(env))?
+        .map_put(atoms::location().encode(env), code_location_to_term(env, &smell.location)?)?
+        .map_put(atoms::suggestion().encode(env), smell.suggestion.encode(env))
+}
+
+/// Convert a text edit to a map
+fn text_edit_to_term<'a>(env: Env<'a>, edit: &TextEdit) -> NifResult<Term<'a>> {
+    map_new(env)
+        .map_put(atoms::start_line().encode(env), edit.start_line.encode(env))?
+        .map_put(atoms::end_line().encode(env), edit.end_line.encode(env))?
+        .map_put(atoms::replacement().encode(env), edit.replacement.encode(env))
+}
+
+/// Convert a refactoring suggestion to a map
+fn refactoring_suggestion_to_term<'a>(env: Env<'a>, suggestion: &RefactoringSuggestion) -> NifResult<Term<'a>> {
+    let priority = match suggestion.priority {
+        Priority::Low => atoms::low(),
+        Priority::Medium => atoms::medium(),
+        Priority::High => atoms::high(),
+        Priority::Urgent => atoms::urgent(),
+    };
+    let effort = match suggestion.effort {
+        EffortLevel::Low => atoms::low(),
+        EffortLevel::Medium => atoms::medium(),
+        EffortLevel::High => atoms::high(),
+        EffortLevel::VeryHigh => atoms::very_high(),
+    };
+
+    let edits = suggestion
+        .edits
+        .iter()
+        .map(|edit| text_edit_to_term(env, edit))
+        .collect::<NifResult<Vec<_>>>()?;
+
+    map_new(env)
+        .map_put(atoms::name().encode(env), suggestion.name.encode(env))?
+        .map_put(atoms::description().encode(env), suggestion.description.encode(env))?
+        .map_put(atoms::priority().encode(env), priority.encode(env))?
+        .map_put(atoms::effort().encode(env), effort.encode(env))?
+        .map_put(atoms::benefits().encode(env), suggestion.benefits.encode(env))?
+        .map_put(atoms::code_example().encode(env), suggestion.code_example.encode(env))?
+        .map_put(atoms::edits().encode(env), edits.encode(env))
 }
 
 /// Convert HashMap to CodeMetrics struct
 fn hashmap_to_code_metrics(map: &HashMap<String, serde_json::Value>) -> Result<CodeMetrics, Error> {
     Ok(CodeMetrics {
-        cyclomatic_complexity: map.get("cyclomatic_complexity")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0),
-        maintainability_index: map.get("maintainability_index")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0),
-        lines_of_code: map.get("lines_of_code")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0) as usize,
-        cognitive_complexity: map.get("cognitive_complexity")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0),
-        halstead_difficulty: map.get("halstead_difficulty")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0),
+        cyclomatic_complexity: get_f64(map, "cyclomatic_complexity")?,
+        maintainability_index: get_f64(map, "maintainability_index")?,
+        lines_of_code: get_usize(map, "lines_of_code")?,
+        cognitive_complexity: get_f64(map, "cognitive_complexity")?,
+        halstead_difficulty: get_f64(map, "halstead_difficulty")?,
     })
 }
 
 /// Convert HashMap to CodeFeatures struct
 fn hashmap_to_code_features(map: &HashMap<String, serde_json::Value>) -> Result<CodeFeatures, Error> {
+    let complexity_level = map
+        .get("complexity_level")
+        .ok_or_else(|| missing_field("complexity_level"))?;
+    let complexity_level = match complexity_level.as_str() {
+        Some("simple") => ComplexityLevel::Simple,
+        Some("medium") => ComplexityLevel::Medium,
+        Some("complex") => ComplexityLevel::Complex,
+        _ => return Err(invalid_field("complexity_level")),
+    };
+
     Ok(CodeFeatures {
-        lines_of_code: map.get("lines_of_code")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0) as usize,
-        cyclomatic_complexity: map.get("cyclomatic_complexity")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0),
-        cognitive_complexity: map.get("cognitive_complexity")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0),
-        nesting_depth: map.get("nesting_depth")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0) as usize,
-        function_count: map.get("function_count")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0) as usize,
-        comment_ratio: map.get("comment_ratio")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0),
-        identifier_length_avg: map.get("identifier_length_avg")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0),
-        complexity_level: map.get("complexity_level")
-            .and_then(|v| v.as_str())
-            .map(|s| match s {
-                "simple" => ComplexityLevel::Simple,
-                "medium" => ComplexityLevel::Medium,
-                "complex" => ComplexityLevel::Complex,
-                _ => ComplexityLevel::Medium,
-            })
-            .unwrap_or(ComplexityLevel::Medium),
+        lines_of_code: get_usize(map, "lines_of_code")?,
+        cyclomatic_complexity: get_f64(map, "cyclomatic_complexity")?,
+        cognitive_complexity: get_f64(map, "cognitive_complexity")?,
+        nesting_depth: get_usize(map, "nesting_depth")?,
+        function_count: get_usize(map, "function_count")?,
+        comment_ratio: get_f64(map, "comment_ratio")?,
+        identifier_length_avg: get_f64(map, "identifier_length_avg")?,
+        complexity_level,
     })
 }
 
 /// Convert HashMap to ComplexityFeatures struct
 fn hashmap_to_complexity_features(map: &HashMap<String, serde_json::Value>) -> Result<ComplexityFeatures, Error> {
     Ok(ComplexityFeatures {
-        total_lines: map.get("total_lines")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0) as usize,
-        non_empty_lines: map.get("non_empty_lines")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0) as usize,
-        function_count: map.get("function_count")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0) as usize,
-        control_flow_count: map.get("control_flow_count")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0) as usize,
-        nesting_depth: map.get("nesting_depth")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0) as usize,
-        operator_count: map.get("operator_count")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0) as usize,
-        comment_ratio: map.get("comment_ratio")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0),
-        identifier_length_avg: map.get("identifier_length_avg")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0),
-        cyclomatic_complexity: map.get("cyclomatic_complexity")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0),
+        total_lines: get_usize(map, "total_lines")?,
+        non_empty_lines: get_usize(map, "non_empty_lines")?,
+        function_count: get_usize(map, "function_count")?,
+        control_flow_count: get_usize(map, "control_flow_count")?,
+        nesting_depth: get_usize(map, "nesting_depth")?,
+        operator_count: get_usize(map, "operator_count")?,
+        comment_ratio: get_f64(map, "comment_ratio")?,
+        identifier_quality: get_f64(map, "identifier_quality")?,
+        cyclomatic_complexity: get_f64(map, "cyclomatic_complexity")?,
     })
 }
 
+/// Register the resource types of the NIFs
+fn load(env: Env, _info: Term) -> bool {
+    rustler::resource!(SemanticAnalyzerResource, env);
+    true
+}
+
 rustler::init!(
     "Elixir.Singularity.CodeAnalyzer.Native",
     [
@@ -215,6 +656,16 @@ rustler::init!(
         predict_ai_code_quality,
         calculate_pattern_effectiveness,
         calculate_supervision_complexity,
-        calculate_actor_complexity
-    ]
+        calculate_actor_complexity,
+        analyze_source,
+        analyze_file,
+        analyze_files_async,
+        analyze_repository,
+        detect_code_smells,
+        suggest_refactoring,
+        semantic_analyzer_new,
+        semantic_analyzer_add_pattern,
+        semantic_analyzer_find_similar
+    ],
+    load = load
 );
\ No newline at end of file
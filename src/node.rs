@@ -1,4 +1,6 @@
-use tree_sitter::{Node as OtherNode, Parser, Tree as OtherTree, TreeCursor};
+use tree_sitter::{
+    Node as OtherNode, Parser, Query, QueryCursor, StreamingIterator, Tree as OtherTree, TreeCursor,
+};
 
 use crate::{
     checker::Checker,
@@ -34,49 +36,70 @@ impl<'a> Node<'a> {
         self.0.has_error()
     }
 
+    /// Checks if a node represents a syntax error.
+    pub fn is_error(&self) -> bool {
+        self.0.is_error()
+    }
+
+    /// Checks if a node was inserted by the parser to recover from a
+    /// syntax error, like a missing semicolon.
+    pub fn is_missing(&self) -> bool {
+        self.0.is_missing()
+    }
+
     pub(crate) fn id(&self) -> usize {
         self.0.id()
     }
 
-    pub(crate) fn kind(&self) -> &'static str {
+    /// Returns the kind of a node, as named in the grammar.
+    pub fn kind(&self) -> &'static str {
         self.0.kind()
     }
 
-    pub(crate) fn kind_id(&self) -> u16 {
+    /// Returns the numeric id of the kind of a node.
+    pub fn kind_id(&self) -> u16 {
         self.0.kind_id()
     }
 
-    pub(crate) fn utf8_text(&self, data: &'a [u8]) -> Option<&'a str> {
+    /// Returns the source code of a node.
+    pub fn utf8_text(&self, data: &'a [u8]) -> Option<&'a str> {
         self.0.utf8_text(data).ok()
     }
 
-    pub(crate) fn start_byte(&self) -> usize {
+    /// Returns the byte offset where a node starts.
+    pub fn start_byte(&self) -> usize {
         self.0.start_byte()
     }
 
-    pub(crate) fn end_byte(&self) -> usize {
+    /// Returns the byte offset where a node ends.
+    pub fn end_byte(&self) -> usize {
         self.0.end_byte()
     }
 
-    pub(crate) fn start_position(&self) -> (usize, usize) {
+    /// Returns the row and the column where a node starts, both zero-based.
+    pub fn start_position(&self) -> (usize, usize) {
         let temp = self.0.start_position();
         (temp.row, temp.column)
     }
 
-    pub(crate) fn end_position(&self) -> (usize, usize) {
+    /// Returns the row and the column where a node ends, both zero-based.
+    pub fn end_position(&self) -> (usize, usize) {
         let temp = self.0.end_position();
         (temp.row, temp.column)
     }
 
-    pub(crate) fn start_row(&self) -> usize {
+    /// Returns the zero-based row where a node starts.
+    pub fn start_row(&self) -> usize {
         self.0.start_position().row
     }
 
-    pub(crate) fn end_row(&self) -> usize {
+    /// Returns the zero-based row where a node ends.
+    pub fn end_row(&self) -> usize {
         self.0.end_position().row
     }
 
-    pub(crate) fn parent(&self) -> Option<Node<'a>> {
+    /// Returns the parent of a node.
+    pub fn parent(&self) -> Option<Node<'a>> {
         self.0.parent().map(Node)
     }
 
@@ -90,11 +113,13 @@ impl<'a> Node<'a> {
         })
     }
 
-    pub(crate) fn previous_sibling(&self) -> Option<Node<'a>> {
+    /// Returns the previous sibling of a node.
+    pub fn previous_sibling(&self) -> Option<Node<'a>> {
         self.0.prev_sibling().map(Node)
     }
 
-    pub(crate) fn next_sibling(&self) -> Option<Node<'a>> {
+    /// Returns the next sibling of a node.
+    pub fn next_sibling(&self) -> Option<Node<'a>> {
         self.0.next_sibling().map(Node)
     }
 
@@ -116,23 +141,38 @@ impl<'a> Node<'a> {
             .any(|child| child.kind_id() == id)
     }
 
-    pub(crate) fn is_named(&self) -> bool {
+    /// Checks if a node is named in the grammar, unlike the punctuation.
+    pub fn is_named(&self) -> bool {
         self.0.is_named()
     }
 
-    pub(crate) fn child_count(&self) -> usize {
+    /// Returns the number of children of a node.
+    pub fn child_count(&self) -> usize {
         self.0.child_count()
     }
 
-    pub(crate) fn child_by_field_name(&self, name: &str) -> Option<Node<'_>> {
+    /// Returns the child of a node for a grammar field.
+    pub fn child_by_field_name(&self, name: &str) -> Option<Node<'a>> {
         self.0.child_by_field_name(name).map(Node)
     }
 
-    pub(crate) fn child(&self, pos: usize) -> Option<Node<'a>> {
+    /// Returns the children of a node for a grammar field, which some
+    /// grammars repeat, like the `alternative` of the Python `if`.
+    pub(crate) fn children_by_field_name(&self, name: &str) -> Vec<Node<'a>> {
+        let mut cursor = self.0.walk();
+        self.0
+            .children_by_field_name(name, &mut cursor)
+            .map(Node)
+            .collect()
+    }
+
+    /// Returns the child of a node at a position.
+    pub fn child(&self, pos: usize) -> Option<Node<'a>> {
         self.0.child(pos).map(Node)
     }
 
-    pub(crate) fn children(&self) -> impl ExactSizeIterator<Item = Node<'a>> + use<'a> {
+    /// Iterates over the children of a node.
+    pub fn children(&self) -> impl ExactSizeIterator<Item = Node<'a>> + use<'a> {
         let mut cursor = self.cursor();
         cursor.goto_first_child();
         (0..self.child_count()).map(move |_| {
@@ -142,6 +182,19 @@ impl<'a> Node<'a> {
         })
     }
 
+    /// Returns the nodes captured by a query in a node, with the index of
+    /// their capture name, in the order of the code.
+    pub(crate) fn captures(&self, query: &Query, code: &[u8]) -> Vec<(Node<'a>, usize)> {
+        let mut cursor = QueryCursor::new();
+        let mut captures = cursor.captures(query, self.0, code);
+        let mut nodes = Vec::new();
+        while let Some((found, index)) = captures.next() {
+            let capture = found.captures[*index];
+            nodes.push((Node(capture.node), capture.index as usize));
+        }
+        nodes
+    }
+
     pub(crate) fn cursor(&self) -> Cursor<'a> {
         Cursor(self.0.walk())
     }
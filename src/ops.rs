@@ -10,8 +10,9 @@ use crate::{
     dump_ops::*,
     getter::Getter,
     halstead::{Halstead, HalsteadMaps},
+    halstead_table::halstead_table,
     node::Node,
-    spaces::SpaceKind,
+    spaces::{subtree_space, CodeMetrics, NestedAttribution, SpaceGranularity, SpaceKind},
     traits::*,
 };
 
@@ -164,6 +165,7 @@ pub fn operands_and_operators<'a, T: ParserTrait>(parser: &'a T, path: &'a Path)
     let mut children = Vec::new();
     let mut state_stack: Vec<State> = Vec::new();
     let mut last_level = 0;
+    let table = halstead_table(parser.get_language());
 
     stack.push((node, 0));
 
@@ -180,7 +182,7 @@ pub fn operands_and_operators<'a, T: ParserTrait>(parser: &'a T, path: &'a Path)
         let new_level = if func_space {
             let state = State {
                 ops: Ops::new::<T::Getter>(&node, code, kind),
-                halstead_maps: HalsteadMaps::new(),
+                halstead_maps: HalsteadMaps::new(table.clone()),
                 primitive_types: HashSet::new(),
             };
             state_stack.push(state);
@@ -220,7 +222,7 @@ pub fn operands_and_operators<'a, T: ParserTrait>(parser: &'a T, path: &'a Path)
     if state_stack.is_empty() {
         let mut root_state = State {
             ops: Ops::new::<T::Getter>(&node, code, SpaceKind::Unit),
-            halstead_maps: HalsteadMaps::new(),
+            halstead_maps: HalsteadMaps::new(table.clone()),
             primitive_types: HashSet::new(),
         };
 
@@ -263,6 +265,45 @@ pub fn operands_and_operators<'a, T: ParserTrait>(parser: &'a T, path: &'a Path)
     })
 }
 
+/// Returns the metrics of the code of a node and of its descendants,
+/// like an `if` block found with the query API, without computing the
+/// metrics of the whole code.
+///
+/// The node counts as a space of its own, and the metrics of the functions
+/// it contains are included in its metrics.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{node_metrics, ParserEngineRust, ParserTrait};
+///
+/// let code = "fn f(x: i32) {\n    if x > 0 {\n        g();\n    }\n}\n";
+/// let path = Path::new("foo.rs");
+/// let parser = ParserEngineRust::new(code.as_bytes().to_vec(), path, None);
+///
+/// let root = parser.get_root();
+/// let function = root.child(0).unwrap();
+/// let body = function.child_by_field_name("body").unwrap();
+/// let block = body.child(1).unwrap();
+///
+/// let metrics = node_metrics(&parser, &block).unwrap();
+/// assert_eq!(metrics.loc.sloc(), 3.);
+/// ```
+pub fn node_metrics<T: ParserTrait>(parser: &T, node: &Node) -> Option<CodeMetrics> {
+    subtree_space(
+        parser,
+        *node,
+        NestedAttribution::default(),
+        SpaceGranularity::File,
+        true,
+        false,
+        true,
+    )
+    .map(|space| space.metrics)
+}
+
 /// Configuration options for retrieving
 /// all the operands and operators in a code.
 #[derive(Debug)]
@@ -290,9 +331,9 @@ impl Callback for OpsCode {
 
 #[cfg(test)]
 mod tests {
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
 
-    use crate::{get_ops, LANG};
+    use crate::{get_ops, metrics, node_metrics, ParserEngineRust, ParserTrait, LANG};
 
     #[inline(always)]
     fn check_ops(
@@ -720,4 +761,36 @@ mod tests {
             ],
         );
     }
+    #[test]
+    fn subtree_metrics() {
+        let code = "fn f(x: i32) -> i32 {
+    if x > 0 {
+        if x > 1 {
+            return 1;
+        }
+    }
+    0
+}
+";
+        let path = Path::new("foo.rs");
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), path, None);
+
+        // The outer `if` expression
+        let root = parser.get_root();
+        let body = root.child(0).unwrap().child_by_field_name("body").unwrap();
+        let outer_if = body.child(1).unwrap().child(0).unwrap();
+        assert_eq!(outer_if.kind(), "if_expression");
+
+        let subtree = node_metrics(&parser, &outer_if).unwrap();
+        assert_eq!(subtree.cyclomatic.cyclomatic(), 3.);
+        assert_eq!(subtree.nexits.exit(), 1.);
+        assert_eq!(subtree.loc.sloc(), 5.);
+        assert_eq!(subtree.nom.functions_sum(), 0.);
+
+        // The function has the same branches, and a line more of code
+        let file = metrics(&parser, path).unwrap();
+        let function = &file.spaces[0].metrics;
+        assert_eq!(function.cyclomatic.cyclomatic(), 3.);
+        assert_eq!(function.loc.sloc(), 8.);
+    }
 }
@@ -0,0 +1,171 @@
+use std::{fmt, io::Write};
+
+use serde::Serialize;
+
+/// A compact binary serialization format.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BinaryFormat {
+    /// MessagePack, with the fields of the structs named
+    #[default]
+    MessagePack,
+    /// CBOR
+    Cbor,
+}
+
+/// The compression of the serialized results.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Compression {
+    /// The results aren't compressed.
+    #[default]
+    None,
+    /// The results are compressed with zstd, at this level.
+    #[cfg(feature = "zstd")]
+    Zstd(i32),
+}
+
+/// Error returned when results can't be serialized in a binary format.
+#[derive(Debug)]
+pub enum BinaryError {
+    /// The results can't be encoded in the format.
+    Encode(String),
+    /// The results can't be written.
+    Io(std::io::Error),
+}
+
+impl fmt::Display for BinaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BinaryError::Encode(err) => write!(f, "failed to encode the results: {err}"),
+            BinaryError::Io(err) => write!(f, "failed to write the results: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for BinaryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BinaryError::Io(err) => Some(err),
+            BinaryError::Encode(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for BinaryError {
+    fn from(value: std::io::Error) -> Self {
+        BinaryError::Io(value)
+    }
+}
+
+fn encode<T: Serialize, W: Write>(
+    value: &T,
+    format: BinaryFormat,
+    mut writer: W,
+) -> Result<(), BinaryError> {
+    match format {
+        BinaryFormat::MessagePack => {
+            // The fields are named, as the consumers don't know the order
+            // of the fields of the structs
+            let mut serializer = rmp_serde::Serializer::new(&mut writer).with_struct_map();
+            value
+                .serialize(&mut serializer)
+                .map_err(|err| BinaryError::Encode(err.to_string()))
+        }
+        BinaryFormat::Cbor => ciborium::into_writer(value, &mut writer).map_err(|err| match err {
+            ciborium::ser::Error::Io(err) => BinaryError::Io(err),
+            ciborium::ser::Error::Value(err) => BinaryError::Encode(err),
+        }),
+    }
+}
+
+/// Writes analysis results, like a [`FuncSpace`](crate::FuncSpace), in a
+/// compact binary format, for the storage and the transfer to the Elixir
+/// and HTTP consumers.
+pub fn write_binary<T: Serialize, W: Write>(
+    value: &T,
+    format: BinaryFormat,
+    compression: Compression,
+    mut writer: W,
+) -> Result<(), BinaryError> {
+    match compression {
+        Compression::None => encode(value, format, &mut writer)?,
+        #[cfg(feature = "zstd")]
+        Compression::Zstd(level) => {
+            let mut encoder = zstd::Encoder::new(&mut writer, level)?;
+            encode(value, format, &mut encoder)?;
+            encoder.finish()?;
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Serializes analysis results in a compact binary format.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{
+///     metrics, to_binary, BinaryFormat, Compression, ParserEngineRust, ParserTrait,
+/// };
+///
+/// let path = Path::new("foo.rs");
+/// let parser = ParserEngineRust::new(b"fn f() {}\n".to_vec(), path, None);
+/// let space = metrics(&parser, path).unwrap();
+///
+/// let bytes = to_binary(&space, BinaryFormat::MessagePack, Compression::None).unwrap();
+/// let decoded: serde_json::Value = rmp_serde::from_slice(&bytes).unwrap();
+/// assert_eq!(decoded["spaces"][0]["name"], "f");
+/// ```
+pub fn to_binary<T: Serialize>(
+    value: &T,
+    format: BinaryFormat,
+    compression: Compression,
+) -> Result<Vec<u8>, BinaryError> {
+    let mut bytes = Vec::new();
+    write_binary(value, format, compression, &mut bytes)?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{metrics, ParserEngineRust, ParserTrait};
+
+    use super::*;
+
+    fn space_json() -> (crate::FuncSpace, serde_json::Value) {
+        let path = Path::new("foo.rs");
+        let parser = ParserEngineRust::new(b"fn f(a: u32) -> u32 { a + 1 }\n".to_vec(), path, None);
+        let space = metrics(&parser, path).unwrap();
+        let json = serde_json::to_value(&space).unwrap();
+        (space, json)
+    }
+
+    #[test]
+    fn binary_formats() {
+        let (space, json) = space_json();
+
+        let bytes = to_binary(&space, BinaryFormat::MessagePack, Compression::None).unwrap();
+        let decoded: serde_json::Value = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(decoded["name"], json["name"]);
+        assert_eq!(decoded["metrics"]["nom"], json["metrics"]["nom"]);
+
+        let bytes = to_binary(&space, BinaryFormat::Cbor, Compression::None).unwrap();
+        let decoded: serde_json::Value = ciborium::from_reader(&bytes[..]).unwrap();
+        assert_eq!(decoded["spaces"][0]["name"], "f");
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn zstd_compression() {
+        let (space, json) = space_json();
+
+        let bytes = to_binary(&space, BinaryFormat::MessagePack, Compression::Zstd(3)).unwrap();
+        let decompressed = zstd::decode_all(&bytes[..]).unwrap();
+        let decoded: serde_json::Value = rmp_serde::from_slice(&decompressed).unwrap();
+        assert_eq!(decoded["spaces"][0]["name"], json["spaces"][0]["name"]);
+    }
+}
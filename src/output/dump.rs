@@ -4,6 +4,7 @@ use termcolor::{Color, ColorChoice, StandardStream, StandardStreamLock};
 
 use crate::{
     node::Node,
+    position::{OffsetEncoding, SourceMap},
     tools::{color, intense_color},
     traits::*,
 };
@@ -17,7 +18,7 @@ use crate::{
 /// ```
 /// use std::path::PathBuf;
 ///
-/// use rust_code_analysis::{dump_node, CppParser, ParserTrait};
+/// use rust_code_analysis::{dump_node, CppParser, OffsetEncoding, ParserTrait};
 ///
 /// let source_code = "int a = 42;";
 ///
@@ -31,8 +32,9 @@ use crate::{
 /// // The root of the AST
 /// let root = parser.get_root();
 ///
-/// // Dump the AST from the first line of code in a file to the last one
-/// dump_node(&source_as_vec, &root, -1, None, None).unwrap();
+/// // Dump the AST from the first line of code in a file to the last one,
+/// // with the columns counted in bytes
+/// dump_node(&source_as_vec, &root, -1, None, None, OffsetEncoding::Utf8).unwrap();
 /// ```
 ///
 /// [`Result`]: #variant.Result
@@ -42,11 +44,14 @@ pub fn dump_node(
     depth: i32,
     line_start: Option<usize>,
     line_end: Option<usize>,
+    encoding: OffsetEncoding,
 ) -> std::io::Result<()> {
     let stdout = StandardStream::stdout(ColorChoice::Always);
     let mut stdout = stdout.lock();
     let ret = dump_tree_helper(
         code,
+        &SourceMap::new(code),
+        encoding,
         node,
         "",
         true,
@@ -64,6 +69,8 @@ pub fn dump_node(
 #[allow(clippy::too_many_arguments)]
 fn dump_tree_helper(
     code: &[u8],
+    lines: &SourceMap,
+    encoding: OffsetEncoding,
     node: &Node,
     prefix: &str,
     last: bool,
@@ -105,14 +112,16 @@ fn dump_tree_helper(
 
         color(stdout, Color::Green)?;
         let (pos_row, pos_column) = node.start_position();
-        write!(stdout, "({}, {}) ", pos_row + 1, pos_column + 1)?;
+        let pos_column = lines.column(pos_row + 1, pos_column + 1, encoding);
+        write!(stdout, "({}, {}) ", pos_row + 1, pos_column)?;
 
         color(stdout, Color::White)?;
         write!(stdout, "to ")?;
 
         color(stdout, Color::Green)?;
         let (pos_row, pos_column) = node.end_position();
-        write!(stdout, "({}, {}) ", pos_row + 1, pos_column + 1)?;
+        let pos_column = lines.column(pos_row + 1, pos_column + 1, encoding);
+        write!(stdout, "({}, {}) ", pos_row + 1, pos_column)?;
 
         if node.start_row() == node.end_row() {
             color(stdout, Color::White)?;
@@ -141,6 +150,8 @@ fn dump_tree_helper(
             i -= 1;
             dump_tree_helper(
                 code,
+                lines,
+                encoding,
                 &cursor.node(),
                 &prefix,
                 i == 0,
@@ -171,6 +182,8 @@ pub struct DumpCfg {
     /// If `None`, the code is dumped until the last line of code
     /// in a file
     pub line_end: Option<usize>,
+    /// The unit used to count the columns of a node
+    pub offset_encoding: OffsetEncoding,
 }
 
 pub struct Dump {
@@ -188,6 +201,7 @@ impl Callback for Dump {
             -1,
             cfg.line_start,
             cfg.line_end,
+            cfg.offset_encoding,
         )
     }
 }
@@ -0,0 +1,58 @@
+use std::io::Write;
+
+use termcolor::{Color, ColorChoice, StandardStream};
+
+use crate::{
+    highlight::{HighlightClass, HighlightToken},
+    tools::{color, intense_color},
+};
+
+/// Dumps a code colored by the highlight of its tokens.
+///
+/// Returns a [`Result`] value, when an error occurs.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{dump_highlights, highlights, ParserEngineRust, ParserTrait};
+///
+/// let code = b"fn f() {}".to_vec();
+/// let parser = ParserEngineRust::new(code.clone(), Path::new("foo.rs"), None);
+///
+/// dump_highlights(&code, &highlights(&parser)).unwrap();
+/// ```
+///
+/// [`Result`]: #variant.Result
+pub fn dump_highlights(code: &[u8], tokens: &[HighlightToken]) -> std::io::Result<()> {
+    let stdout = StandardStream::stdout(ColorChoice::Always);
+    let mut stdout = stdout.lock();
+
+    let mut offset = 0;
+    for token in tokens {
+        color(&mut stdout, Color::White)?;
+        stdout.write_all(&code[offset..token.start_byte])?;
+        match token.class {
+            HighlightClass::Keyword => intense_color(&mut stdout, Color::Magenta)?,
+            HighlightClass::Function => intense_color(&mut stdout, Color::Blue)?,
+            HighlightClass::Type => intense_color(&mut stdout, Color::Yellow)?,
+            HighlightClass::Property | HighlightClass::Attribute => {
+                color(&mut stdout, Color::Cyan)?
+            }
+            HighlightClass::Constant | HighlightClass::Number => color(&mut stdout, Color::Red)?,
+            HighlightClass::String => color(&mut stdout, Color::Green)?,
+            HighlightClass::Comment => color(&mut stdout, Color::Blue)?,
+            HighlightClass::Variable
+            | HighlightClass::Operator
+            | HighlightClass::Punctuation
+            | HighlightClass::Plain => color(&mut stdout, Color::White)?,
+        }
+        stdout.write_all(&code[token.start_byte..token.end_byte])?;
+        offset = token.end_byte;
+    }
+    color(&mut stdout, Color::White)?;
+    stdout.write_all(&code[offset..])?;
+
+    Ok(())
+}
@@ -3,10 +3,10 @@ use std::io::Write;
 use termcolor::{Color, ColorChoice, StandardStream, StandardStreamLock};
 
 use crate::{
-    abc, cognitive, cyclomatic, exit, halstead, loc, mi, nargs, nom, npa, npm,
+    abc, cognitive, cyclomatic, exit, extras, generics, halstead, loc, mi, nargs, nom, npa, npm,
     spaces::{CodeMetrics, FuncSpace},
     tools::{color, intense_color},
-    wmc,
+    unsafety, wmc,
 };
 
 /// Dumps the metrics of a code.
@@ -41,11 +41,44 @@ pub fn dump_root(space: &FuncSpace) -> std::io::Result<()> {
     let stdout = StandardStream::stdout(ColorChoice::Always);
     let mut stdout = stdout.lock();
     dump_space(space, "", true, &mut stdout)?;
+    dump_parse_errors(space, &mut stdout)?;
     color(&mut stdout, Color::White)?;
 
     Ok(())
 }
 
+fn dump_parse_errors(space: &FuncSpace, stdout: &mut StandardStreamLock) -> std::io::Result<()> {
+    if space.parse_errors.is_empty() {
+        return Ok(());
+    }
+
+    intense_color(stdout, Color::Red)?;
+    writeln!(stdout, "parse errors: {}", space.parse_errors.len())?;
+
+    for (i, error) in space.parse_errors.iter().enumerate() {
+        let pref = if i + 1 == space.parse_errors.len() {
+            "`- "
+        } else {
+            "|- "
+        };
+
+        color(stdout, Color::Blue)?;
+        write!(stdout, "{pref}")?;
+
+        intense_color(stdout, Color::Yellow)?;
+        write!(stdout, "{:?}: ", error.kind)?;
+
+        color(stdout, Color::White)?;
+        writeln!(
+            stdout,
+            "({}, {}) - ({}, {})",
+            error.start_line, error.start_column, error.end_line, error.end_column
+        )?;
+    }
+
+    Ok(())
+}
+
 fn dump_space(
     space: &FuncSpace,
     prefix: &str,
@@ -64,9 +97,21 @@ fn dump_space(
     write!(stdout, "{}", space.name.as_ref().map_or("", |name| name))?;
 
     intense_color(stdout, Color::Red)?;
-    writeln!(stdout, " (@{})", space.start_line)?;
+    write!(stdout, " (@{})", space.start_line)?;
+
+    if let Some(grade) = &space.mi_grade {
+        intense_color(stdout, Color::Green)?;
+        write!(stdout, " [mi: {grade}]")?;
+    }
+    writeln!(stdout)?;
 
     let prefix = format!("{prefix}{pref_child}");
+    if let Some(snippet) = &space.snippet {
+        color(stdout, Color::White)?;
+        for line in snippet.to_string().lines() {
+            writeln!(stdout, "{prefix}  {line}")?;
+        }
+    }
     dump_metrics(&space.metrics, &prefix, space.spaces.is_empty(), stdout)?;
 
     if let Some((last, spaces)) = space.spaces.split_last() {
@@ -94,18 +139,30 @@ fn dump_metrics(
     writeln!(stdout, "metrics")?;
 
     let prefix = format!("{prefix}{pref_child}");
-    dump_cognitive(&metrics.cognitive, &prefix, false, stdout)?;
-    dump_cyclomatic(&metrics.cyclomatic, &prefix, false, stdout)?;
-    dump_nargs(&metrics.nargs, &prefix, false, stdout)?;
-    dump_nexits(&metrics.nexits, &prefix, false, stdout)?;
-    dump_halstead(&metrics.halstead, &prefix, false, stdout)?;
-    dump_loc(&metrics.loc, &prefix, false, stdout)?;
-    dump_nom(&metrics.nom, &prefix, false, stdout)?;
-    dump_mi(&metrics.mi, &prefix, false, stdout)?;
-    dump_abc(&metrics.abc, &prefix, false, stdout)?;
-    dump_wmc(&metrics.wmc, &prefix, false, stdout)?;
-    dump_npm(&metrics.npm, &prefix, false, stdout)?;
-    dump_npa(&metrics.npa, &prefix, true, stdout)
+    // The metrics suppressed by a `sca-ignore` comment are left out
+    macro_rules! dump {
+        ($dump:ident, $metric:ident, $last:expr) => {
+            if !metrics.is_suppressed(stringify!($metric)) {
+                $dump(&metrics.$metric, &prefix, $last, stdout)?;
+            }
+        };
+    }
+    dump!(dump_cognitive, cognitive, false);
+    dump!(dump_cyclomatic, cyclomatic, false);
+    dump!(dump_nargs, nargs, false);
+    dump!(dump_nexits, nexits, false);
+    dump!(dump_halstead, halstead, false);
+    dump!(dump_loc, loc, false);
+    dump!(dump_nom, nom, false);
+    dump!(dump_mi, mi, false);
+    dump!(dump_abc, abc, false);
+    dump!(dump_generics, generics, false);
+    dump!(dump_wmc, wmc, false);
+    dump!(dump_npm, npm, false);
+    dump!(dump_npa, npa, false);
+    dump!(dump_unsafety, unsafety, false);
+    dump!(dump_extras, extras, true);
+    Ok(())
 }
 
 fn dump_cognitive(
@@ -322,6 +379,27 @@ fn dump_abc(
     dump_value("magnitude", stats.magnitude_sum(), &prefix, true, stdout)
 }
 
+fn dump_generics(
+    stats: &generics::Stats,
+    prefix: &str,
+    last: bool,
+    stdout: &mut StandardStreamLock,
+) -> std::io::Result<()> {
+    let (pref_child, pref) = if last { ("   ", "`- ") } else { ("|  ", "|- ") };
+
+    color(stdout, Color::Blue)?;
+    write!(stdout, "{prefix}{pref}")?;
+
+    intense_color(stdout, Color::Green)?;
+    writeln!(stdout, "generics")?;
+
+    let prefix = format!("{prefix}{pref_child}");
+    dump_value("parameters", stats.params_sum(), &prefix, false, stdout)?;
+    dump_value("bounds", stats.bounds_sum(), &prefix, false, stdout)?;
+    dump_value("total", stats.generics_total(), &prefix, false, stdout)?;
+    dump_value("average", stats.generics_average(), &prefix, true, stdout)
+}
+
 fn dump_wmc(
     stats: &wmc::Stats,
     prefix: &str,
@@ -414,6 +492,87 @@ fn dump_npa(
     dump_value("average", stats.total_cda(), &prefix, true, stdout)
 }
 
+fn dump_unsafety(
+    stats: &unsafety::Stats,
+    prefix: &str,
+    last: bool,
+    stdout: &mut StandardStreamLock,
+) -> std::io::Result<()> {
+    if stats.is_disabled() {
+        return Ok(());
+    }
+
+    let (pref_child, pref) = if last { ("   ", "`- ") } else { ("|  ", "|- ") };
+
+    color(stdout, Color::Blue)?;
+    write!(stdout, "{prefix}{pref}")?;
+
+    intense_color(stdout, Color::Green)?;
+    writeln!(stdout, "unsafety")?;
+
+    let prefix = format!("{prefix}{pref_child}");
+    dump_value(
+        "unsafe_blocks",
+        stats.unsafe_blocks_sum(),
+        &prefix,
+        false,
+        stdout,
+    )?;
+    dump_value(
+        "unsafe_functions",
+        stats.unsafe_functions_sum(),
+        &prefix,
+        false,
+        stdout,
+    )?;
+    dump_value("raw_derefs", stats.raw_derefs_sum(), &prefix, false, stdout)?;
+    dump_value(
+        "extern_items",
+        stats.extern_items_sum(),
+        &prefix,
+        false,
+        stdout,
+    )?;
+    dump_value("casts", stats.casts_sum(), &prefix, false, stdout)?;
+    dump_value(
+        "pointer_arithmetic",
+        stats.pointer_arithmetic_sum(),
+        &prefix,
+        false,
+        stdout,
+    )?;
+    dump_value("total", stats.total(), &prefix, false, stdout)?;
+    dump_value("density", stats.density(), &prefix, true, stdout)
+}
+
+fn dump_extras(
+    stats: &extras::Stats,
+    prefix: &str,
+    last: bool,
+    stdout: &mut StandardStreamLock,
+) -> std::io::Result<()> {
+    if stats.is_disabled() {
+        return Ok(());
+    }
+
+    let (pref_child, pref) = if last { ("   ", "`- ") } else { ("|  ", "|- ") };
+
+    color(stdout, Color::Blue)?;
+    write!(stdout, "{prefix}{pref}")?;
+
+    intense_color(stdout, Color::Green)?;
+    writeln!(stdout, "{}", stats.name())?;
+
+    let prefix = format!("{prefix}{pref_child}");
+    let values = stats.values();
+    let count = values.len();
+    for (i, (name, value)) in values.into_iter().enumerate() {
+        dump_value(name, value, &prefix, i + 1 == count, stdout)?;
+    }
+
+    Ok(())
+}
+
 fn dump_value(
     name: &str,
     val: f64,
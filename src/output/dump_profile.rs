@@ -0,0 +1,63 @@
+use std::io::Write;
+
+use termcolor::{Color, ColorChoice, StandardStream};
+
+use crate::{
+    profile::ProfileReport,
+    tools::{color, intense_color},
+};
+
+/// Dumps the profiles of the `limit` slowest files of a profiling report.
+///
+/// Returns a [`Result`] value, when an error occurs.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+///
+/// use singularity_code_analysis::{dump_profile, profile_function_spaces, ProfileReport, LANG};
+///
+/// let path = PathBuf::from("foo.rs");
+/// let (_, profile) = profile_function_spaces(&LANG::Rust, b"fn f() {}".to_vec(), &path, None);
+///
+/// dump_profile(&ProfileReport::new(vec![profile]), 10).unwrap();
+/// ```
+///
+/// [`Result`]: #variant.Result
+pub fn dump_profile(report: &ProfileReport, limit: usize) -> std::io::Result<()> {
+    let stdout = StandardStream::stdout(ColorChoice::Always);
+    let mut stdout = stdout.lock();
+
+    intense_color(&mut stdout, Color::Yellow)?;
+    writeln!(
+        stdout,
+        "{} files in {:.2} ms",
+        report.files.len(),
+        report.total_ms
+    )?;
+
+    let files = report.slowest(limit);
+    for (i, file) in files.iter().enumerate() {
+        let pref = if i + 1 == files.len() { "`- " } else { "|- " };
+
+        color(&mut stdout, Color::Blue)?;
+        write!(stdout, "{pref}")?;
+
+        intense_color(&mut stdout, Color::Cyan)?;
+        write!(stdout, "{}", file.path.display())?;
+
+        intense_color(&mut stdout, Color::Red)?;
+        write!(stdout, " ({:.2} ms) ", file.total_ms())?;
+
+        color(&mut stdout, Color::White)?;
+        writeln!(
+            stdout,
+            "parse: {:.2} ms, metrics: {:.2} ms, nodes: {}, bytes: {}",
+            file.parse_ms, file.metrics_ms, file.node_count, file.bytes
+        )?;
+    }
+    color(&mut stdout, Color::White)?;
+
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use std::io::Write;
+
+use termcolor::{Color, ColorChoice, StandardStream, StandardStreamLock};
+
+use crate::{
+    risk::{RiskItem, RiskReport},
+    tools::{color, intense_color},
+};
+
+/// Dumps the files and functions of a risk report, from the riskiest
+/// to the least risky.
+///
+/// Returns a [`Result`] value, when an error occurs.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+///
+/// use singularity_code_analysis::{
+///     dump_risk, metrics, rank_risk, ParserEngineRust, ParserTrait, RiskInput, RiskPolicy,
+/// };
+///
+/// let path = PathBuf::from("foo.rs");
+/// let parser = ParserEngineRust::new(b"fn f() {}".to_vec(), &path, None);
+/// let space = metrics(&parser, &path).unwrap();
+///
+/// let input = RiskInput { path: &path, space: &space, smells: &[] };
+/// let report = rank_risk(&[input], &RiskPolicy::default());
+///
+/// dump_risk(&report).unwrap();
+/// ```
+///
+/// [`Result`]: #variant.Result
+pub fn dump_risk(report: &RiskReport) -> std::io::Result<()> {
+    let stdout = StandardStream::stdout(ColorChoice::Always);
+    let mut stdout = stdout.lock();
+    dump_items("files", &report.files, false, &mut stdout)?;
+    dump_items("functions", &report.functions, true, &mut stdout)?;
+    color(&mut stdout, Color::White)?;
+
+    Ok(())
+}
+
+fn dump_items(
+    name: &str,
+    items: &[RiskItem],
+    last: bool,
+    stdout: &mut StandardStreamLock,
+) -> std::io::Result<()> {
+    let (pref_child, pref) = if last { ("   ", "`- ") } else { ("|  ", "|- ") };
+
+    color(stdout, Color::Blue)?;
+    write!(stdout, "{pref}")?;
+
+    intense_color(stdout, Color::Yellow)?;
+    writeln!(stdout, "{name}")?;
+
+    if let Some((last, items)) = items.split_last() {
+        for item in items {
+            dump_item(item, pref_child, false, stdout)?;
+        }
+        dump_item(last, pref_child, true, stdout)?;
+    }
+
+    Ok(())
+}
+
+fn dump_item(
+    item: &RiskItem,
+    prefix: &str,
+    last: bool,
+    stdout: &mut StandardStreamLock,
+) -> std::io::Result<()> {
+    let (pref_child, pref) = if last { ("   ", "`- ") } else { ("|  ", "|- ") };
+
+    color(stdout, Color::Blue)?;
+    write!(stdout, "{prefix}{pref}")?;
+
+    intense_color(stdout, Color::Cyan)?;
+    write!(stdout, "{}", item.path)?;
+    if let Some(name) = &item.name {
+        write!(stdout, ": {name}")?;
+    }
+
+    intense_color(stdout, Color::Red)?;
+    write!(stdout, " (@{}) ", item.start_line)?;
+
+    color(stdout, Color::White)?;
+    writeln!(stdout, "score: {:.2}, smells: {}", item.score, item.smells)?;
+
+    let prefix = format!("{prefix}{pref_child}");
+    for (i, breach) in item.breaches.iter().enumerate() {
+        let pref = if i + 1 == item.breaches.len() {
+            "`- "
+        } else {
+            "|- "
+        };
+
+        color(stdout, Color::Blue)?;
+        write!(stdout, "{prefix}{pref}")?;
+
+        intense_color(stdout, Color::Magenta)?;
+        write!(stdout, "{}: ", breach.metric)?;
+
+        color(stdout, Color::White)?;
+        writeln!(stdout, "{} (threshold: {})", breach.value, breach.threshold)?;
+    }
+
+    Ok(())
+}
@@ -6,3 +6,17 @@ pub use dump_metrics::*;
 
 pub(crate) mod dump_ops;
 pub use dump_ops::*;
+
+pub(crate) mod dump_risk;
+pub use dump_risk::*;
+
+pub(crate) mod dump_profile;
+pub use dump_profile::*;
+
+pub(crate) mod dump_highlights;
+pub use dump_highlights::*;
+
+#[cfg(feature = "binary")]
+pub(crate) mod binary;
+#[cfg(feature = "binary")]
+pub use binary::*;
@@ -0,0 +1,493 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+use walkdir::WalkDir;
+
+use crate::{
+    language_breakdown::LanguageBreakdown, sampling::Sampling, spaces::FuncSpace,
+    third_party::is_third_party_dir, trends::directory,
+};
+
+/// The kind of manifest declaring a package.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PackageKind {
+    /// A `Cargo.toml` with a `[package]` table
+    Cargo,
+    /// A `package.json` with a `name`
+    Npm,
+    /// A `go.mod`
+    Go,
+    /// A `pom.xml`
+    Maven,
+}
+
+/// A package of a repository.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Package {
+    /// The name declared by the manifest
+    pub name: String,
+    /// The kind of manifest
+    pub kind: PackageKind,
+    /// The directory containing the manifest
+    pub root: PathBuf,
+}
+
+fn cargo_name(manifest: &str) -> Option<String> {
+    let manifest: toml::Table = toml::from_str(manifest).ok()?;
+    Some(manifest.get("package")?.get("name")?.as_str()?.to_string())
+}
+
+fn npm_name(manifest: &str) -> Option<String> {
+    let manifest: serde_json::Value = serde_json::from_str(manifest).ok()?;
+    Some(manifest.get("name")?.as_str()?.to_string())
+}
+
+fn go_name(manifest: &str) -> Option<String> {
+    manifest.lines().find_map(|line| {
+        let module = line.trim().strip_prefix("module ")?;
+        Some(module.trim().trim_matches('"').to_string())
+    })
+}
+
+fn maven_name(manifest: &str) -> Option<String> {
+    // The artifact of the parent comes first when there is one
+    let manifest = match (manifest.find("<parent>"), manifest.find("</parent>")) {
+        (Some(start), Some(end)) if start < end => {
+            format!("{}{}", &manifest[..start], &manifest[end..])
+        }
+        _ => manifest.to_string(),
+    };
+    let start = manifest.find("<artifactId>")? + "<artifactId>".len();
+    let end = start + manifest[start..].find("</artifactId>")?;
+    Some(manifest[start..end].trim().to_string())
+}
+
+/// Returns the package declared by a manifest, if the file is a
+/// manifest declaring a package.
+///
+/// Manifests which only declare a workspace, like a `Cargo.toml` without
+/// a `[package]` table, aren't packages: their members are found
+/// through their own manifests.
+pub fn read_package(manifest: &Path) -> Option<Package> {
+    let file_name = manifest.file_name()?.to_str()?;
+    let (parse, kind): (fn(&str) -> Option<String>, _) = match file_name {
+        "Cargo.toml" => (cargo_name, PackageKind::Cargo),
+        "package.json" => (npm_name, PackageKind::Npm),
+        "go.mod" => (go_name, PackageKind::Go),
+        "pom.xml" => (maven_name, PackageKind::Maven),
+        _ => return None,
+    };
+    let name = parse(&fs::read_to_string(manifest).ok()?)?;
+    Some(Package {
+        name,
+        kind,
+        root: manifest.parent()?.to_path_buf(),
+    })
+}
+
+/// Finds the packages of a repository, skipping hidden and third-party
+/// directories.
+pub fn find_packages(root: &Path) -> Vec<Package> {
+    WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0
+                || !(entry.file_name().to_string_lossy().starts_with('.')
+                    || entry.file_type().is_dir() && is_third_party_dir(entry.path()))
+        })
+        .filter_map(|entry| read_package(entry.ok()?.path()))
+        .collect()
+}
+
+/// The metrics of the files of a package.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct PackageMetrics {
+    /// The number of files
+    pub files: usize,
+    /// The number of source lines
+    pub sloc: f64,
+    /// The sum of the cyclomatic complexities
+    pub cyclomatic: f64,
+    /// The sum of the cognitive complexities
+    pub cognitive: f64,
+    /// The number of functions and closures
+    pub functions: f64,
+    /// The average maintainability index of the files, as computed by
+    /// Visual Studio
+    pub mi: f64,
+    /// The sum of the Halstead estimates of the delivered bugs of the
+    /// files
+    pub bugs: f64,
+    /// The delivered bugs per thousand source lines
+    pub defect_density: f64,
+}
+
+impl PackageMetrics {
+    fn add(&mut self, space: &FuncSpace) {
+        let metrics = &space.metrics;
+        let mi = metrics.mi.mi_visual_studio();
+        let bugs = metrics.halstead.bugs();
+        self.merge(&Self {
+            files: 1,
+            sloc: metrics.loc.sloc(),
+            cyclomatic: metrics.cyclomatic.cyclomatic_sum(),
+            cognitive: metrics.cognitive.cognitive_sum(),
+            functions: metrics.nom.total(),
+            mi: if mi.is_finite() { mi } else { 0. },
+            bugs: if bugs.is_finite() { bugs } else { 0. },
+            ..Default::default()
+        });
+    }
+
+    // Scales the counts, but not the average maintainability index and
+    // the defect density
+    fn scale(&mut self, factor: f64) {
+        self.files = (self.files as f64 * factor).round() as usize;
+        self.sloc *= factor;
+        self.cyclomatic *= factor;
+        self.cognitive *= factor;
+        self.functions *= factor;
+        self.bugs *= factor;
+    }
+
+    fn merge(&mut self, other: &Self) {
+        let files = self.files + other.files;
+        if files > 0 {
+            self.mi = (self.mi * self.files as f64 + other.mi * other.files as f64) / files as f64;
+        }
+        self.files = files;
+        self.sloc += other.sloc;
+        self.cyclomatic += other.cyclomatic;
+        self.cognitive += other.cognitive;
+        self.functions += other.functions;
+        self.bugs += other.bugs;
+        if self.sloc > 0. {
+            self.defect_density = self.bugs * 1000. / self.sloc;
+        }
+    }
+}
+
+/// The Halstead estimate of the delivered bugs of a module: a package or
+/// a directory.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ModuleDefects {
+    /// The name of the package, or the path of the directory
+    pub name: String,
+    /// The root of the module
+    pub root: PathBuf,
+    /// The number of files
+    pub files: usize,
+    /// The number of source lines
+    pub sloc: f64,
+    /// The sum of the delivered bugs of the files
+    pub bugs: f64,
+    /// The delivered bugs per thousand source lines
+    pub defect_density: f64,
+}
+
+impl ModuleDefects {
+    fn new(name: String, root: &Path, metrics: &PackageMetrics) -> Self {
+        Self {
+            name,
+            root: root.to_path_buf(),
+            files: metrics.files,
+            sloc: metrics.sloc,
+            bugs: metrics.bugs,
+            defect_density: metrics.defect_density,
+        }
+    }
+}
+
+// Ranks the modules with the most delivered bugs first
+fn rank_defects(mut modules: Vec<ModuleDefects>) -> Vec<ModuleDefects> {
+    modules.sort_by(|a, b| b.bugs.total_cmp(&a.bugs).then_with(|| a.root.cmp(&b.root)));
+    modules
+}
+
+/// The summary of a package and of the packages nested in its
+/// directory.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PackageSummary {
+    /// The package
+    pub package: Package,
+    /// The metrics of the files of the package, excluding the nested
+    /// packages
+    pub metrics: PackageMetrics,
+    /// The metrics of the files of the package and of the nested packages
+    pub total: PackageMetrics,
+    /// The nested packages
+    pub packages: Vec<PackageSummary>,
+}
+
+/// The summary tree of the packages of a repository.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct PackageTree {
+    /// The metrics of the files outside of any package
+    pub unpackaged: PackageMetrics,
+    /// The outermost packages
+    pub packages: Vec<PackageSummary>,
+    /// The breakdown of the files of the repository by language, which
+    /// [`package_tree`] leaves empty as it doesn't know their sizes
+    #[serde(skip_serializing_if = "LanguageBreakdown::is_empty")]
+    pub languages: LanguageBreakdown,
+    /// The sampling of the files, when the metrics are estimates
+    /// extrapolated from a sample
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sampling: Option<Sampling>,
+}
+
+fn scale_summary(summary: &mut PackageSummary, factor: f64) {
+    summary.metrics.scale(factor);
+    summary.total.scale(factor);
+    for child in summary.packages.iter_mut() {
+        scale_summary(child, factor);
+    }
+}
+
+impl PackageTree {
+    /// Sets the breakdown by language of the files, accumulated while
+    /// walking the repository.
+    pub fn with_languages(mut self, languages: LanguageBreakdown) -> Self {
+        self.languages = languages;
+        self
+    }
+
+    /// Extrapolates the metrics of a sample of the files, processed with
+    /// [`ConcurrentRunner::set_sampling`], to all the files, flagging them
+    /// as estimates.
+    ///
+    /// The counts and the sums are scaled, while the averages and the
+    /// shares are kept.
+    ///
+    /// [`ConcurrentRunner::set_sampling`]: crate::ConcurrentRunner::set_sampling
+    pub fn extrapolate(mut self, sampling: Sampling) -> Self {
+        let factor = sampling.factor();
+        self.unpackaged.scale(factor);
+        for summary in self.packages.iter_mut() {
+            scale_summary(summary, factor);
+        }
+        self.languages.scale(factor);
+        self.sampling = Some(sampling);
+        self
+    }
+
+    /// Ranks the packages by the delivered bugs of their own files,
+    /// excluding the nested packages, the most defective first.
+    pub fn defects(&self) -> Vec<ModuleDefects> {
+        let mut modules = Vec::new();
+        for summary in &self.packages {
+            collect_defects(summary, &mut modules);
+        }
+        rank_defects(modules)
+    }
+}
+
+/// Ranks the directories of some analyzed files by their delivered bugs,
+/// the most defective first, for the repositories without packages.
+///
+/// Directories are truncated to their first `depth` components, as in
+/// [`directory_series`](crate::directory_series).
+pub fn directory_defects(files: &[(PathBuf, FuncSpace)], depth: usize) -> Vec<ModuleDefects> {
+    let mut directories: BTreeMap<PathBuf, PackageMetrics> = BTreeMap::new();
+    for (path, space) in files {
+        directories
+            .entry(directory(path, depth))
+            .or_default()
+            .add(space);
+    }
+    rank_defects(
+        directories
+            .iter()
+            .map(|(root, metrics)| ModuleDefects::new(root.display().to_string(), root, metrics))
+            .collect(),
+    )
+}
+
+fn collect_defects(summary: &PackageSummary, modules: &mut Vec<ModuleDefects>) {
+    let package = &summary.package;
+    modules.push(ModuleDefects::new(
+        package.name.clone(),
+        &package.root,
+        &summary.metrics,
+    ));
+    for child in &summary.packages {
+        collect_defects(child, modules);
+    }
+}
+
+fn nest(mut packages: Vec<PackageSummary>) -> Vec<PackageSummary> {
+    // The deepest packages are moved into their parents first
+    packages.sort_by_key(|summary| summary.package.root.components().count());
+    let mut roots: Vec<PackageSummary> = Vec::new();
+    while let Some(mut summary) = packages.pop() {
+        let (mut children, others): (Vec<_>, _) = roots
+            .into_iter()
+            .partition(|child| child.package.root.starts_with(&summary.package.root));
+        children.sort_by(|a, b| a.package.root.cmp(&b.package.root));
+        roots = others;
+        summary.packages = children;
+        roots.push(summary);
+    }
+    roots.sort_by(|a, b| a.package.root.cmp(&b.package.root));
+    roots
+}
+
+fn compute_totals(summary: &mut PackageSummary) {
+    summary.total = summary.metrics.clone();
+    for child in summary.packages.iter_mut() {
+        compute_totals(child);
+        summary.total.merge(&child.total);
+    }
+}
+
+/// Aggregates the metrics of some analyzed files per package.
+///
+/// A file belongs to the package with the deepest root containing it.
+pub fn package_tree(packages: &[Package], files: &[(PathBuf, FuncSpace)]) -> PackageTree {
+    let mut tree = PackageTree::default();
+    let mut summaries = packages
+        .iter()
+        .map(|package| PackageSummary {
+            package: package.clone(),
+            metrics: PackageMetrics::default(),
+            total: PackageMetrics::default(),
+            packages: Vec::new(),
+        })
+        .collect::<Vec<_>>();
+
+    for (path, space) in files {
+        match summaries
+            .iter_mut()
+            .filter(|summary| path.starts_with(&summary.package.root))
+            .max_by_key(|summary| summary.package.root.components().count())
+        {
+            Some(summary) => summary.metrics.add(space),
+            None => tree.unpackaged.add(space),
+        }
+    }
+
+    tree.packages = nest(summaries);
+    for summary in tree.packages.iter_mut() {
+        compute_totals(summary);
+    }
+    tree
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{metrics, ParserEngineRust, ParserTrait};
+
+    use super::*;
+
+    #[test]
+    fn manifests() {
+        assert_eq!(
+            cargo_name("[package]\nname = \"foo\"\nversion = \"0.1.0\"\n").as_deref(),
+            Some("foo")
+        );
+        assert_eq!(cargo_name("[workspace]\nmembers = [\"foo\"]\n"), None);
+        assert_eq!(
+            npm_name("{\"name\": \"@scope/bar\", \"workspaces\": []}").as_deref(),
+            Some("@scope/bar")
+        );
+        assert_eq!(
+            go_name("module example.com/baz\n\ngo 1.22\n").as_deref(),
+            Some("example.com/baz")
+        );
+        assert_eq!(
+            maven_name(
+                "<project><parent><artifactId>parent</artifactId></parent>\
+                 <artifactId>qux</artifactId></project>"
+            )
+            .as_deref(),
+            Some("qux")
+        );
+    }
+
+    #[test]
+    fn packages_tree() {
+        let package = |name: &str, root: &str| Package {
+            name: name.to_string(),
+            kind: PackageKind::Cargo,
+            root: PathBuf::from(root),
+        };
+        let packages = [
+            package("app", "repo/app"),
+            package("core", "repo/crates/core"),
+            package("plugin", "repo/app/plugin"),
+        ];
+        let files = ["repo/app/a.rs", "repo/app/plugin/b.rs", "repo/build.rs"].map(|path| {
+            let path = PathBuf::from(path);
+            let parser = ParserEngineRust::new(b"fn f() {}\n".to_vec(), &path, None);
+            let space = metrics(&parser, &path).unwrap();
+            (path, space)
+        });
+
+        let tree = package_tree(&packages, &files);
+        assert_eq!(tree.unpackaged.files, 1);
+
+        let names = tree
+            .packages
+            .iter()
+            .map(|summary| summary.package.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["app", "core"]);
+
+        let app = &tree.packages[0];
+        assert_eq!(app.packages[0].package.name, "plugin");
+        assert_eq!((app.metrics.files, app.total.files), (1, 2));
+        assert_eq!(app.total.functions, 2.);
+        assert_eq!(tree.packages[1].total.files, 0);
+        assert_eq!(tree.sampling, None);
+
+        let estimate = tree.extrapolate(Sampling::new(25., 0));
+        let app = &estimate.packages[0];
+        assert_eq!((app.metrics.files, app.total.files), (4, 8));
+        assert_eq!(app.total.functions, 8.);
+        assert_eq!(estimate.unpackaged.files, 4);
+        assert_eq!(estimate.sampling.map(|sampling| sampling.rate), Some(0.25));
+    }
+
+    #[test]
+    fn module_defects() {
+        let file = |path: &str, code: &str| {
+            let path = PathBuf::from(path);
+            let parser = ParserEngineRust::new(code.as_bytes().to_vec(), &path, None);
+            let space = metrics(&parser, &path).unwrap();
+            (path, space)
+        };
+        let complex =
+            { "fn f(a: u32, b: u32) -> u32 {\n    if a > b { a * 2 + b } else { b - a / 3 }\n}\n" };
+        let files = [
+            file("repo/app/a.rs", "fn f() {}\n"),
+            file("repo/core/b.rs", complex),
+            file("repo/core/c.rs", complex),
+        ];
+
+        let tree = package_tree(
+            &[Package {
+                name: "core".to_string(),
+                kind: PackageKind::Cargo,
+                root: PathBuf::from("repo/core"),
+            }],
+            &files,
+        );
+        let core = &tree.packages[0].metrics;
+        assert_eq!(core.bugs, files[1].1.metrics.halstead.bugs() * 2.);
+        assert_eq!(core.defect_density, core.bugs * 1000. / core.sloc);
+        assert_eq!(tree.defects()[0].name, "core");
+
+        let directories = directory_defects(&files, 2);
+        let roots = directories
+            .iter()
+            .map(|module| module.root.as_path())
+            .collect::<Vec<_>>();
+        assert_eq!(roots, [Path::new("repo/core"), Path::new("repo/app")]);
+        assert!(directories[0].bugs > directories[1].bugs);
+    }
+}
@@ -1,4 +1,4 @@
-use std::{marker::PhantomData, path::Path, sync::Arc};
+use std::{collections::HashSet, marker::PhantomData, path::Path, sync::Arc};
 
 use crate::{
     abc::Abc,
@@ -8,18 +8,24 @@ use crate::{
     cognitive::Cognitive,
     cyclomatic::Cyclomatic,
     exit::Exit,
+    extras::Extras,
+    generics::Generics,
     getter::Getter,
     halstead::Halstead,
     langs::*,
     loc::Loc,
+    macros::trace_span,
     mi::Mi,
+    mozjs_preproc::{has_directives, strip_directives},
     nargs::NArgs,
     node::{Node, Tree},
     nom::Nom,
     npa::Npa,
     npm::Npm,
     preproc::{get_macros, PreprocResults},
+    tools::normalize_source,
     traits::*,
+    unsafety::Unsafety,
     wmc::Wmc,
 };
 
@@ -33,6 +39,8 @@ pub struct Parser<
         + Cognitive
         + Cyclomatic
         + Exit
+        + Extras
+        + Generics
         + Halstead
         + Loc
         + Mi
@@ -40,6 +48,7 @@ pub struct Parser<
         + Nom
         + Npa
         + Npm
+        + Unsafety
         + Wmc,
 > {
     code: Vec<u8>,
@@ -79,16 +88,17 @@ fn get_fake_code<T: LanguageInfo>(
     path: &Path,
     pr: Option<Arc<PreprocResults>>,
 ) -> Option<Vec<u8>> {
-    if let Some(pr) = pr {
-        match T::get_lang() {
-            LANG::Cpp => {
-                let macros = get_macros(path, &pr.files);
-                c_macro::replace(code, &macros)
-            }
-            _ => None,
+    match T::get_lang() {
+        LANG::Cpp => pr.and_then(|pr| {
+            let macros = get_macros(path, &pr.files);
+            c_macro::replace(code, &macros)
+        }),
+        // Most codes have no directive, so they aren't copied
+        LANG::Javascript if has_directives(code) => {
+            let defines = pr.map_or_else(HashSet::new, |pr| get_macros(path, &pr.files));
+            strip_directives(code, &defines)
         }
-    } else {
-        None
+        _ => None,
     }
 }
 
@@ -102,6 +112,8 @@ impl<
             + Cognitive
             + Cyclomatic
             + Exit
+            + Extras
+            + Generics
             + Halstead
             + Loc
             + Mi
@@ -109,6 +121,7 @@ impl<
             + Nom
             + Npa
             + Npm
+            + Unsafety
             + Wmc,
     > ParserTrait for Parser<T>
 {
@@ -122,12 +135,17 @@ impl<
     type Mi = T;
     type NArgs = T;
     type Exit = T;
+    type Extras = T;
+    type Generics = T;
     type Wmc = T;
     type Abc = T;
     type Npm = T;
     type Npa = T;
+    type Unsafety = T;
 
     fn new(code: Vec<u8>, path: &Path, pr: Option<Arc<PreprocResults>>) -> Self {
+        trace_span!("parse", language = ?T::get_lang(), path = %path.display(), bytes = code.len());
+        let code = normalize_source(code);
         let fake_code = get_fake_code::<T>(&code, path, pr);
         let code = if let Some(fake) = fake_code {
             fake
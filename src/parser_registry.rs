@@ -1,10 +1,12 @@
 use std::{collections::HashMap, path::Path, sync::Arc};
 
+use crate::macros::{trace_event, trace_span};
 use crate::traits::{LanguageInfo, ParserTrait};
 use crate::{
     abc::Abc, alterator::Alterator, checker::Checker, cognitive::Cognitive, cyclomatic::Cyclomatic,
-    exit::Exit, getter::Getter, halstead::Halstead, langs::*, loc::Loc, mi::Mi, nargs::NArgs,
-    nom::Nom, npa::Npa, npm::Npm, preproc::PreprocResults, wmc::Wmc,
+    exit::Exit, extras::Extras, generics::Generics, getter::Getter, grammars::GrammarVersion,
+    halstead::Halstead, langs::*, loc::Loc, mi::Mi, nargs::NArgs, nom::Nom, npa::Npa, npm::Npm,
+    preproc::PreprocResults, unsafety::Unsafety, wmc::Wmc,
 };
 
 /// A registry for managing parsers for different programming languages.
@@ -49,6 +51,8 @@ impl ParserRegistry {
             + Cognitive
             + Cyclomatic
             + Exit
+            + Extras
+            + Generics
             + Halstead
             + Loc
             + Mi
@@ -56,6 +60,7 @@ impl ParserRegistry {
             + Nom
             + Npa
             + Npm
+            + Unsafety
             + Wmc,
     {
         self.parsers.insert(language, factory);
@@ -74,6 +79,7 @@ impl ParserRegistry {
         path: &Path,
         pr: Option<Arc<PreprocResults>>,
     ) -> Result<Box<dyn std::any::Any>, Box<dyn std::error::Error>> {
+        trace_span!("create_parser", language = ?language);
         self.parsers
             .get(language)
             .ok_or_else(|| Box::<dyn std::error::Error>::from("Parser not found for language"))?
@@ -91,6 +97,7 @@ impl ParserRegistry {
             }
         }
 
+        trace_event!(trace, path = %path.display(), "no parser registered for the extension");
         None
     }
 
@@ -99,6 +106,18 @@ impl ParserRegistry {
         self.parsers.keys().cloned().collect()
     }
 
+    /// Get the grammars of the supported languages, sorted by language
+    /// name, with their versions and node kind mismatches.
+    pub fn grammar_info(&self) -> Vec<GrammarVersion> {
+        let mut grammars = self
+            .parsers
+            .keys()
+            .map(|language| GrammarVersion::new(*language))
+            .collect::<Vec<_>>();
+        grammars.sort_by_key(|grammar| grammar.language);
+        grammars
+    }
+
     /// Register all built-in parsers.
     fn register_builtin_parsers(&mut self) {
         // Register all built-in language parsers
@@ -131,6 +150,8 @@ impl ParserRegistry {
             + Cognitive
             + Cyclomatic
             + Exit
+            + Extras
+            + Generics
             + Halstead
             + Loc
             + Mi
@@ -138,6 +159,7 @@ impl ParserRegistry {
             + Nom
             + Npa
             + Npm
+            + Unsafety
             + Wmc,
     {
         let factory = Box::new(BuiltinParserFactory::<T>::new());
@@ -185,6 +207,8 @@ impl<
             + Cognitive
             + Cyclomatic
             + Exit
+            + Extras
+            + Generics
             + Halstead
             + Loc
             + Mi
@@ -192,6 +216,7 @@ impl<
             + Nom
             + Npa
             + Npm
+            + Unsafety
             + Wmc
             + Send
             + Sync,
@@ -255,6 +280,17 @@ mod tests {
         assert!(languages.contains(&LANG::Elixir));
     }
 
+    #[test]
+    fn test_grammar_info() {
+        assert!(ParserRegistry::new().grammar_info().is_empty());
+
+        let grammars = ParserRegistry::with_builtins().grammar_info();
+        assert_eq!(grammars.len(), LANG::into_enum_iter().count());
+        assert!(grammars.windows(2).all(|w| w[0].language < w[1].language));
+        let rust = grammars.iter().find(|g| g.language == "rust").unwrap();
+        assert_eq!(rust.grammar, "tree-sitter-rust");
+    }
+
     #[test]
     fn test_language_detection() {
         let registry = ParserRegistry::with_builtins();
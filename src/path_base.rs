@@ -0,0 +1,156 @@
+use std::path::{Component, Path, PathBuf};
+
+use crate::spaces::FuncSpace;
+
+// Normalizes a path without accessing the filesystem, removing the `.`
+// components and the `..` components following a directory, unlike
+// `tools::normalize_path` which drops the leading `..` components
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                // The parent of the root is the root
+                Some(Component::RootDir | Component::Prefix(_)) => {}
+                _ => normalized.push(".."),
+            },
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// The base of the paths written in the results, like the root of a
+/// repository.
+///
+/// The paths under the base are written relative to it, with `/`
+/// separators, so the results of a repository computed on different
+/// machines, or in different checkouts, have the same paths and can be
+/// shared and compared. The paths outside of the base are only
+/// normalized.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PathBase {
+    base: PathBuf,
+}
+
+impl PathBase {
+    /// Creates the base of the paths under a directory.
+    ///
+    /// A relative directory is relative to the current directory.
+    pub fn new(base: &Path) -> Self {
+        Self {
+            base: normalize(&absolute(base)),
+        }
+    }
+
+    /// Returns the directory of the base.
+    pub fn base(&self) -> &Path {
+        &self.base
+    }
+
+    /// Returns a path relative to the base, normalized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// use singularity_code_analysis::PathBase;
+    ///
+    /// let base = PathBase::new(Path::new("/home/ci/repo"));
+    /// assert_eq!(base.relative(Path::new("/home/ci/repo/src/./a.rs")), "src/a.rs");
+    /// assert_eq!(base.relative(Path::new("/tmp/b.rs")), "/tmp/b.rs");
+    /// ```
+    pub fn relative(&self, path: &Path) -> String {
+        let path = normalize(&absolute(path));
+        let path = path.strip_prefix(&self.base).unwrap_or(&path);
+        let mut relative = String::new();
+        for component in path.components() {
+            if !(relative.is_empty() || relative.ends_with('/')) {
+                relative.push('/');
+            }
+            relative.push_str(&component.as_os_str().to_string_lossy());
+        }
+        if relative.is_empty() {
+            relative.push('.');
+        }
+        relative
+    }
+
+    /// Writes the path of a file relative to the base in the name of the
+    /// root space of its metrics.
+    pub fn relativize_space(&self, path: &Path, space: &mut FuncSpace) {
+        space.name = Some(self.relative(path));
+    }
+
+    /// Writes the paths of the files of a run relative to the base, as
+    /// the baselines compared by
+    /// [`evaluate_gate`](crate::evaluate_gate) should be.
+    pub fn relativize_run(&self, run: &mut [(PathBuf, FuncSpace)]) {
+        for (path, space) in run {
+            let relative = self.relative(path);
+            *path = PathBuf::from(&relative);
+            space.name = Some(relative);
+        }
+    }
+}
+
+// Joins a relative path to the current directory
+fn absolute(path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        return path.to_path_buf();
+    }
+    std::env::current_dir()
+        .map(|dir| dir.join(path))
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{metrics, ParserEngineRust, ParserTrait};
+
+    use super::*;
+
+    #[test]
+    fn normalized_paths() {
+        assert_eq!(normalize(Path::new("a/./b/../c")), Path::new("a/c"));
+        assert_eq!(normalize(Path::new("/../a")), Path::new("/a"));
+        assert_eq!(
+            normalize(Path::new("./src/../lib/./a.rs")),
+            Path::new("lib/a.rs")
+        );
+        assert_eq!(normalize(Path::new("a/../../b")), Path::new("../b"));
+    }
+
+    #[test]
+    fn relative_paths() {
+        let base = PathBase::new(Path::new("/repo/./"));
+        assert_eq!(base.base(), Path::new("/repo"));
+        assert_eq!(base.relative(Path::new("/repo/src/lib.rs")), "src/lib.rs");
+        assert_eq!(base.relative(Path::new("/repo")), ".");
+        assert_eq!(
+            base.relative(Path::new("/repo/../other/a.rs")),
+            "/other/a.rs"
+        );
+
+        let current = std::env::current_dir().unwrap();
+        let base = PathBase::new(Path::new("."));
+        assert_eq!(base.relative(&current.join("src").join("a.rs")), "src/a.rs");
+        assert_eq!(base.relative(Path::new("src/a.rs")), "src/a.rs");
+    }
+
+    #[test]
+    fn relative_run() {
+        let path = PathBuf::from("/checkout/one/src/a.rs");
+        let parser = ParserEngineRust::new(b"fn f() {}".to_vec(), &path, None);
+        let mut run = vec![(path.clone(), metrics(&parser, &path).unwrap())];
+
+        PathBase::new(Path::new("/checkout/one")).relativize_run(&mut run);
+        assert_eq!(run[0].0, Path::new("src/a.rs"));
+        assert_eq!(run[0].1.name.as_deref(), Some("src/a.rs"));
+    }
+}
@@ -0,0 +1,496 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Serialize, Serializer};
+use walkdir::WalkDir;
+
+use crate::{
+    ai::{CodePattern, PatternType, SemanticAnalyzer},
+    checker::Checker,
+    embeddings::{EmbeddingInput, EmbeddingProvider},
+    getter::Getter,
+    langs::{action, LANG},
+    similarity::structural_vector,
+    third_party::is_third_party_dir,
+    tools::{guess_language, read_file},
+    traits::{Callback, ParserTrait},
+};
+
+/// The options of a [`PatternLearningJob`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PatternLearningOptions {
+    /// The minimum cosine similarity of a function to the centroid of
+    /// its cluster
+    pub similarity_threshold: f32,
+    /// The minimum number of functions of a pattern
+    pub min_frequency: usize,
+    /// The minimum number of lines of a clustered function, so the
+    /// trivial functions don't make patterns
+    pub min_lines: usize,
+    /// The maximum number of exemplars of a pattern
+    pub max_exemplars: usize,
+}
+
+impl Default for PatternLearningOptions {
+    fn default() -> Self {
+        Self {
+            similarity_threshold: 0.9,
+            min_frequency: 3,
+            min_lines: 3,
+            max_exemplars: 3,
+        }
+    }
+}
+
+/// A function exemplifying a learned pattern.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PatternExemplar {
+    /// The repository of the function
+    pub repository: PathBuf,
+    /// The path of the file of the function
+    pub path: PathBuf,
+    /// The name of the function
+    pub name: Option<String>,
+    /// The first line of the function
+    pub start_line: usize,
+    /// The last line of the function
+    pub end_line: usize,
+    /// The code of the function
+    pub code: String,
+    /// The cosine similarity of the function to the pattern
+    pub similarity: f32,
+}
+
+fn serialize_language<S: Serializer>(language: &LANG, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(language.get_name())
+}
+
+/// A candidate pattern of an organization: a cluster of similar
+/// functions of its repositories.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct LearnedPattern {
+    /// The most common name of the functions of the pattern
+    pub name: String,
+    /// The language of the functions
+    #[serde(serialize_with = "serialize_language")]
+    pub language: LANG,
+    /// The number of functions of the pattern
+    pub frequency: usize,
+    /// The number of repositories with a function of the pattern
+    pub repositories: usize,
+    /// The average cosine similarity of the functions to the pattern
+    pub cohesion: f32,
+    /// The functions most similar to the pattern, the most similar first
+    pub exemplars: Vec<PatternExemplar>,
+    /// The centroid of the embeddings of the functions
+    #[serde(skip)]
+    pub centroid: Vec<f32>,
+}
+
+impl LearnedPattern {
+    /// Returns the pattern as stored by a [`SemanticAnalyzer`], with its
+    /// most similar exemplar as example.
+    pub fn to_code_pattern(&self) -> CodePattern {
+        CodePattern {
+            name: self.name.clone(),
+            description: format!(
+                "Learned from {} functions of {} repositories",
+                self.frequency, self.repositories
+            ),
+            pattern_type: PatternType::LearnedPattern,
+            complexity_score: self.cohesion,
+            language: self.language,
+            example: self
+                .exemplars
+                .first()
+                .map(|exemplar| exemplar.code.clone())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+// A function of a scanned file, before its embedding
+struct ScannedFunction {
+    name: Option<String>,
+    start_line: usize,
+    end_line: usize,
+    code: String,
+    structure: Vec<f32>,
+}
+
+fn scan_functions<T: ParserTrait>(parser: &T) -> Vec<ScannedFunction> {
+    let code = parser.get_code();
+    let mut functions = Vec::new();
+    let mut stack = vec![parser.get_root()];
+    while let Some(node) = stack.pop() {
+        if T::Checker::is_func(&node) {
+            functions.push(ScannedFunction {
+                name: T::Getter::get_func_name(&node, code).map(|name| name.to_string()),
+                start_line: node.start_row() + 1,
+                end_line: node.end_row() + 1,
+                code: node.utf8_text(code).unwrap_or_default().to_string(),
+                structure: structural_vector::<T>(&node, code, true),
+            });
+        }
+        let mut children = node.children().collect::<Vec<_>>();
+        children.reverse();
+        stack.extend(children);
+    }
+    functions
+}
+
+struct ScanFunctions {
+    _guard: (),
+}
+
+impl Callback for ScanFunctions {
+    type Res = Vec<ScannedFunction>;
+    type Cfg = ();
+
+    fn call<T: ParserTrait>(_cfg: Self::Cfg, parser: &T) -> Self::Res {
+        scan_functions(parser)
+    }
+}
+
+// An embedded function of a repository
+struct Member {
+    repository: usize,
+    language: LANG,
+    path: PathBuf,
+    function: ScannedFunction,
+    vector: Vec<f32>,
+}
+
+fn normalize(mut vector: Vec<f32>) -> Vec<f32> {
+    let norm = vector.iter().map(|value| value * value).sum::<f32>().sqrt();
+    if norm > 0. {
+        vector.iter_mut().for_each(|value| *value /= norm);
+    }
+    vector
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    // The vectors are normalized
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+// A cluster of members, its centroid being the normalized sum of their
+// vectors
+struct Cluster {
+    language: LANG,
+    members: Vec<usize>,
+    sum: Vec<f32>,
+    centroid: Vec<f32>,
+}
+
+/// An offline job learning the patterns of an organization from its
+/// repositories.
+///
+/// The functions of the scanned repositories are embedded with a
+/// provider, and the similar functions of a language are clustered: the
+/// clusters with enough functions are the candidate patterns, which can
+/// be materialized into the pattern store of a [`SemanticAnalyzer`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{
+///     PatternLearningJob, PatternLearningOptions, SemanticAnalyzer, StructuralEmbedding,
+/// };
+///
+/// let mut job = PatternLearningJob::new(&StructuralEmbedding, PatternLearningOptions::default());
+/// for repository in ["../service-a", "../service-b"] {
+///     job.scan(Path::new(repository)).unwrap();
+/// }
+///
+/// let mut analyzer = SemanticAnalyzer::new();
+/// job.materialize(&job.learn(), &mut analyzer);
+/// ```
+pub struct PatternLearningJob<'a> {
+    provider: &'a dyn EmbeddingProvider,
+    options: PatternLearningOptions,
+    repositories: Vec<PathBuf>,
+    members: Vec<Member>,
+}
+
+impl<'a> PatternLearningJob<'a> {
+    /// Creates a job embedding the functions with a provider.
+    pub fn new(provider: &'a dyn EmbeddingProvider, options: PatternLearningOptions) -> Self {
+        Self {
+            provider,
+            options,
+            repositories: Vec::new(),
+            members: Vec::new(),
+        }
+    }
+
+    /// Scans the files of a repository, skipping the hidden and the
+    /// third-party directories.
+    ///
+    /// Returns the number of functions added to the job.
+    pub fn scan(&mut self, repository: &Path) -> io::Result<usize> {
+        let index = self.repositories.len();
+        self.repositories.push(repository.to_path_buf());
+        let before = self.members.len();
+        for entry in WalkDir::new(repository)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0
+                    || !(entry.file_name().to_string_lossy().starts_with('.')
+                        || entry.file_type().is_dir() && is_third_party_dir(entry.path()))
+            })
+        {
+            let entry = entry.map_err(io::Error::other)?;
+            if entry.file_type().is_file() {
+                self.add_file(index, entry.path())?;
+            }
+        }
+        Ok(self.members.len() - before)
+    }
+
+    fn add_file(&mut self, repository: usize, path: &Path) -> io::Result<()> {
+        let source = read_file(path)?;
+        let Some(language) = guess_language(&source, path).0 else {
+            return Ok(());
+        };
+        let relative = path
+            .strip_prefix(&self.repositories[repository])
+            .unwrap_or(path);
+        for function in action::<ScanFunctions>(&language, source, path, None, ()) {
+            if function.end_line + 1 - function.start_line < self.options.min_lines {
+                continue;
+            }
+            let vector = self.provider.embed(&EmbeddingInput {
+                path: relative,
+                language,
+                name: function.name.as_deref(),
+                code: &function.code,
+                structure: &function.structure,
+            });
+            self.members.push(Member {
+                repository,
+                language,
+                path: relative.to_path_buf(),
+                function,
+                vector: normalize(vector),
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns the number of functions of the scanned repositories.
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Returns `true` if no function was scanned.
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    // Adds each function to the most similar cluster of its language,
+    // or to a new cluster if none is similar enough
+    fn cluster(&self) -> Vec<Cluster> {
+        let mut clusters: Vec<Cluster> = Vec::new();
+        for (index, member) in self.members.iter().enumerate() {
+            let best = clusters
+                .iter()
+                .enumerate()
+                .filter(|(_, cluster)| cluster.language == member.language)
+                .map(|(position, cluster)| (position, cosine(&cluster.centroid, &member.vector)))
+                .filter(|(_, similarity)| *similarity >= self.options.similarity_threshold)
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+            match best {
+                Some((position, _)) => {
+                    let cluster = &mut clusters[position];
+                    cluster.members.push(index);
+                    cluster
+                        .sum
+                        .iter_mut()
+                        .zip(&member.vector)
+                        .for_each(|(sum, value)| *sum += value);
+                    cluster.centroid = normalize(cluster.sum.clone());
+                }
+                None => clusters.push(Cluster {
+                    language: member.language,
+                    members: vec![index],
+                    sum: member.vector.clone(),
+                    centroid: member.vector.clone(),
+                }),
+            }
+        }
+        clusters
+    }
+
+    fn pattern(&self, cluster: Cluster) -> LearnedPattern {
+        let mut similarities = cluster
+            .members
+            .iter()
+            .map(|index| {
+                (
+                    *index,
+                    cosine(&cluster.centroid, &self.members[*index].vector),
+                )
+            })
+            .collect::<Vec<_>>();
+        let cohesion = similarities
+            .iter()
+            .map(|(_, similarity)| similarity)
+            .sum::<f32>()
+            / similarities.len() as f32;
+
+        // The most common name, the first one seen on ties
+        let mut names: Vec<(&str, usize)> = Vec::new();
+        for index in &cluster.members {
+            let name = self.members[*index]
+                .function
+                .name
+                .as_deref()
+                .unwrap_or("<anonymous>");
+            match names.iter_mut().find(|(seen, _)| *seen == name) {
+                Some((_, count)) => *count += 1,
+                None => names.push((name, 1)),
+            }
+        }
+        let name = names
+            .iter()
+            .rev()
+            .max_by_key(|(_, count)| *count)
+            .map_or("<anonymous>", |(name, _)| name)
+            .to_string();
+
+        let mut repositories = cluster
+            .members
+            .iter()
+            .map(|index| self.members[*index].repository)
+            .collect::<Vec<_>>();
+        repositories.sort_unstable();
+        repositories.dedup();
+
+        similarities.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let exemplars = similarities
+            .iter()
+            .take(self.options.max_exemplars)
+            .map(|(index, similarity)| {
+                let member = &self.members[*index];
+                PatternExemplar {
+                    repository: self.repositories[member.repository].clone(),
+                    path: member.path.clone(),
+                    name: member.function.name.clone(),
+                    start_line: member.function.start_line,
+                    end_line: member.function.end_line,
+                    code: member.function.code.clone(),
+                    similarity: *similarity,
+                }
+            })
+            .collect();
+
+        LearnedPattern {
+            name,
+            language: cluster.language,
+            frequency: cluster.members.len(),
+            repositories: repositories.len(),
+            cohesion,
+            exemplars,
+            centroid: cluster.centroid,
+        }
+    }
+
+    /// Clusters the scanned functions, returning the candidate patterns,
+    /// the most frequent first.
+    pub fn learn(&self) -> Vec<LearnedPattern> {
+        let mut patterns = self
+            .cluster()
+            .into_iter()
+            .filter(|cluster| cluster.members.len() >= self.options.min_frequency)
+            .map(|cluster| self.pattern(cluster))
+            .collect::<Vec<_>>();
+        patterns.sort_by(|a, b| {
+            b.frequency
+                .cmp(&a.frequency)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        patterns
+    }
+
+    /// Adds learned patterns to the pattern store of a
+    /// [`SemanticAnalyzer`].
+    pub fn materialize(&self, patterns: &[LearnedPattern], analyzer: &mut SemanticAnalyzer) {
+        for pattern in patterns {
+            analyzer.add_pattern(pattern.to_code_pattern());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::StructuralEmbedding;
+
+    use super::*;
+
+    #[test]
+    fn learn_patterns() {
+        let root = std::env::temp_dir().join(format!("sca-patterns-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let loader = |name: &str| {
+            format!("def {name}(path):\n    with open(path) as f:\n        data = f.read()\n    return data.strip()\n")
+        };
+        for (repository, files) in [
+            (
+                "a",
+                vec![(
+                    "io.py",
+                    loader("load_config") + "\n" + &loader("load_rules"),
+                )],
+            ),
+            (
+                "b",
+                vec![
+                    ("util.py", loader("load_config")),
+                    ("m.py", "def add(x, y):\n    return x + y\n".to_string()),
+                ],
+            ),
+            ("b/node_modules", vec![("dep.py", loader("load_vendored"))]),
+        ] {
+            fs::create_dir_all(root.join(repository)).unwrap();
+            for (file, code) in files {
+                fs::write(root.join(repository).join(file), code).unwrap();
+            }
+        }
+
+        let mut job =
+            PatternLearningJob::new(&StructuralEmbedding, PatternLearningOptions::default());
+        let scanned = [
+            job.scan(&root.join("a")).unwrap(),
+            job.scan(&root.join("b")).unwrap(),
+        ];
+        fs::remove_dir_all(&root).unwrap();
+
+        // The one-line `add` is too short, and `node_modules` is skipped
+        assert_eq!(scanned, [2, 1]);
+        let patterns = job.learn();
+        assert_eq!(patterns.len(), 1);
+        let pattern = &patterns[0];
+        assert_eq!(pattern.name, "load_config");
+        assert_eq!((pattern.frequency, pattern.repositories), (3, 2));
+        assert_eq!(pattern.exemplars.len(), 3);
+        assert_eq!(pattern.exemplars[0].path, Path::new("io.py"));
+
+        let mut analyzer = SemanticAnalyzer::new();
+        job.materialize(&patterns, &mut analyzer);
+        let stored = analyzer.get_patterns_for_language(LANG::Python);
+        assert_eq!(stored.len(), 1);
+        assert!(matches!(
+            stored[0].pattern_type,
+            PatternType::LearnedPattern
+        ));
+        assert!(stored[0].example.starts_with("def load_config(path):"));
+    }
+}
@@ -0,0 +1,337 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::node::Node;
+
+/// The unit used to count the columns of a position in a code.
+///
+/// Tree-sitter reports columns in `UTF-8` bytes, while editors speaking
+/// the Language Server Protocol expect `UTF-16` code units by default.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OffsetEncoding {
+    /// Columns are counted in `UTF-8` bytes
+    #[default]
+    Utf8,
+    /// Columns are counted in `UTF-16` code units
+    Utf16,
+    /// Columns are counted in Unicode scalar values
+    Utf32,
+}
+
+impl FromStr for OffsetEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "utf8" | "utf-8" | "bytes" => Ok(Self::Utf8),
+            "utf16" | "utf-16" => Ok(Self::Utf16),
+            "utf32" | "utf-32" | "unicode" | "chars" => Ok(Self::Utf32),
+            _ => Err(format!("unknown offset encoding: {s}")),
+        }
+    }
+}
+
+impl OffsetEncoding {
+    /// Returns the number of units of a piece of code in this encoding.
+    ///
+    /// Invalid `UTF-8` sequences count as one replacement character.
+    pub fn count(self, code: &[u8]) -> usize {
+        match self {
+            Self::Utf8 => code.len(),
+            Self::Utf16 => String::from_utf8_lossy(code).encode_utf16().count(),
+            Self::Utf32 => String::from_utf8_lossy(code).chars().count(),
+        }
+    }
+}
+
+/// An excerpt of a code, made of whole lines.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Snippet {
+    /// The first line of the excerpt
+    pub start_line: usize,
+    /// The last line of the excerpt
+    pub end_line: usize,
+    /// The text of the excerpt
+    pub text: String,
+}
+
+impl fmt::Display for Snippet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = self.end_line.to_string().len();
+        for (line, text) in (self.start_line..).zip(self.text.lines()) {
+            writeln!(f, "{line:>width$} | {text}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the lines of a code going from `start_line` to `end_line`,
+/// surrounded by `context` lines before and after them.
+///
+/// Lines are 1-based, and the excerpt is clamped to the lines of the code.
+/// Returns `None` if `start_line` is zero or beyond the end of the code.
+///
+/// # Examples
+///
+/// ```
+/// use singularity_code_analysis::get_snippet;
+///
+/// let code = b"a\nb\nc\nd\n";
+///
+/// let snippet = get_snippet(code, 2, 2, 1).unwrap();
+/// assert_eq!(snippet.start_line, 1);
+/// assert_eq!(snippet.text, "a\nb\nc");
+/// ```
+pub fn get_snippet(
+    code: &[u8],
+    start_line: usize,
+    end_line: usize,
+    context: usize,
+) -> Option<Snippet> {
+    SourceMap::new(code).snippet(start_line, end_line, context)
+}
+
+/// A position in a code, with a 1-based line and a 1-based column.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct Position {
+    /// The line of the position
+    pub line: usize,
+    /// The column of the position
+    pub column: usize,
+}
+
+/// The index of the lines of a code, to convert the byte offsets of its
+/// nodes into positions, with columns in any [`OffsetEncoding`], and
+/// back.
+///
+/// The index is built once, with a single scan of the code, and each
+/// conversion only scans the line of the offset.
+///
+/// # Examples
+///
+/// ```
+/// use singularity_code_analysis::{OffsetEncoding, Position, SourceMap};
+///
+/// let map = SourceMap::new("let a = 1;\nlet é = a;\n".as_bytes());
+///
+/// // The offset of the second `a`
+/// let position = map.position(20, OffsetEncoding::Utf16).unwrap();
+/// assert_eq!(position, Position { line: 2, column: 9 });
+/// assert_eq!(map.offset(position, OffsetEncoding::Utf16), Some(20));
+/// ```
+#[derive(Clone, Debug)]
+pub struct SourceMap<'a> {
+    code: &'a [u8],
+    starts: Vec<usize>,
+}
+
+impl<'a> SourceMap<'a> {
+    /// Indexes the lines of a code.
+    pub fn new(code: &'a [u8]) -> Self {
+        let starts = std::iter::once(0)
+            .chain(
+                code.iter()
+                    .enumerate()
+                    .filter(|(_, c)| **c == b'\n')
+                    .map(|(i, _)| i + 1),
+            )
+            .collect();
+        Self { code, starts }
+    }
+
+    /// Returns the number of lines of the code.
+    ///
+    /// A trailing newline doesn't start a new line.
+    pub fn line_count(&self) -> usize {
+        self.starts.len() - usize::from(self.code.ends_with(b"\n"))
+    }
+
+    /// Returns the content of a 1-based line, without its terminator.
+    pub fn line(&self, line: usize) -> &'a [u8] {
+        let Some(start) = line.checked_sub(1).and_then(|row| self.starts.get(row)) else {
+            return &[];
+        };
+        let end = self.starts.get(line).map_or(self.code.len(), |end| end - 1);
+        let line = &self.code[*start..end];
+        line.strip_suffix(b"\r").unwrap_or(line)
+    }
+
+    /// Returns the byte offset of the start of a 1-based line.
+    pub fn line_start(&self, line: usize) -> Option<usize> {
+        line.checked_sub(1)
+            .and_then(|row| self.starts.get(row))
+            .copied()
+    }
+
+    /// Converts a byte offset into a position, with the column counted in
+    /// the given encoding.
+    ///
+    /// Returns `None` if the offset is beyond the end of the code.
+    pub fn position(&self, offset: usize, encoding: OffsetEncoding) -> Option<Position> {
+        if offset > self.code.len() {
+            return None;
+        }
+        let line = self.starts.partition_point(|start| *start <= offset);
+        let start = self.starts[line - 1];
+        Some(Position {
+            line,
+            column: encoding.count(&self.code[start..offset]) + 1,
+        })
+    }
+
+    /// Converts a position, with the column counted in the given
+    /// encoding, into a byte offset.
+    ///
+    /// A column beyond the end of its line is clamped to the end of the
+    /// line. Returns `None` if the line or the column is zero, or if the
+    /// line is beyond the end of the code.
+    pub fn offset(&self, position: Position, encoding: OffsetEncoding) -> Option<usize> {
+        let start = self.line_start(position.line)?;
+        let line = self.line(position.line);
+        let units = position.column.checked_sub(1)?;
+        let width = match encoding {
+            OffsetEncoding::Utf8 => units.min(line.len()),
+            _ => {
+                let text = String::from_utf8_lossy(line);
+                let mut counted = 0;
+                text.char_indices()
+                    .find(|(_, c)| {
+                        counted += match encoding {
+                            OffsetEncoding::Utf16 => c.len_utf16(),
+                            _ => 1,
+                        };
+                        counted > units
+                    })
+                    .map_or(text.len(), |(i, _)| i)
+                    .min(line.len())
+            }
+        };
+        Some(start + width)
+    }
+
+    /// Returns the start and the end positions of a node, with the
+    /// columns counted in the given encoding.
+    pub fn node_range(&self, node: &Node, encoding: OffsetEncoding) -> (Position, Position) {
+        let position = |offset: usize| {
+            self.position(offset.min(self.code.len()), encoding)
+                .unwrap_or_default()
+        };
+        (position(node.start_byte()), position(node.end_byte()))
+    }
+
+    pub(crate) fn snippet(
+        &self,
+        start_line: usize,
+        end_line: usize,
+        context: usize,
+    ) -> Option<Snippet> {
+        let count = self.line_count();
+        if start_line == 0 || start_line > count {
+            return None;
+        }
+        let start_line = start_line.saturating_sub(context).max(1);
+        let end_line = end_line.max(start_line).saturating_add(context).min(count);
+        let start = self.starts[start_line - 1];
+        let end = self.starts[end_line - 1] + self.line(end_line).len();
+        Some(Snippet {
+            start_line,
+            end_line,
+            text: String::from_utf8_lossy(&self.code[start..end]).into_owned(),
+        })
+    }
+
+    /// Converts a 1-based column counted in `UTF-8` bytes into a 1-based
+    /// column counted in the given encoding.
+    ///
+    /// A zero column is left untouched.
+    pub fn column(&self, line: usize, column: usize, encoding: OffsetEncoding) -> usize {
+        if column == 0 || encoding == OffsetEncoding::Utf8 {
+            return column;
+        }
+        let line = self.line(line);
+        encoding.count(&line[..(column - 1).min(line.len())]) + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offset_encoding_columns() {
+        let code = "let a = 1;\r\nlet é = \"😀\"; x\n".as_bytes();
+        let lines = SourceMap::new(code);
+
+        assert_eq!(lines.line(2), "let é = \"😀\"; x".as_bytes());
+        assert_eq!(lines.line(3), b"");
+
+        // The column of `x`
+        assert_eq!(lines.column(2, 18, OffsetEncoding::Utf8), 18);
+        assert_eq!(lines.column(2, 18, OffsetEncoding::Utf16), 15);
+        assert_eq!(lines.column(2, 18, OffsetEncoding::Utf32), 14);
+        assert_eq!(lines.column(1, 11, OffsetEncoding::Utf16), 11);
+    }
+
+    #[test]
+    fn snippet_context() {
+        let code = b"a\r\nb\nc\nd";
+
+        let snippet = get_snippet(code, 3, 3, 0).unwrap();
+        assert_eq!((snippet.start_line, snippet.end_line), (3, 3));
+        assert_eq!(snippet.text, "c");
+
+        let snippet = get_snippet(code, 2, 3, 5).unwrap();
+        assert_eq!((snippet.start_line, snippet.end_line), (1, 4));
+        assert_eq!(snippet.text, "a\r\nb\nc\nd");
+        assert_eq!(snippet.to_string(), "1 | a\n2 | b\n3 | c\n4 | d\n");
+
+        assert!(get_snippet(code, 0, 1, 1).is_none());
+        assert!(get_snippet(code, 5, 5, 1).is_none());
+        assert!(get_snippet(b"a\n", 2, 2, 0).is_none());
+    }
+
+    #[test]
+    fn offset_encoding_from_str() {
+        assert_eq!("UTF-16".parse(), Ok(OffsetEncoding::Utf16));
+        assert_eq!("unicode".parse(), Ok(OffsetEncoding::Utf32));
+        assert!("latin1".parse::<OffsetEncoding>().is_err());
+    }
+    #[test]
+    fn source_map_positions() {
+        let code = "a\r\né😀b\n".as_bytes();
+        let map = SourceMap::new(code);
+        assert_eq!(map.line_count(), 2);
+        assert_eq!(map.line_start(2), Some(3));
+        assert_eq!(map.line_start(4), None);
+
+        // The offset of `b`
+        let b = code.len() - 2;
+        let utf16 = Position { line: 2, column: 4 };
+        assert_eq!(map.position(b, OffsetEncoding::Utf16), Some(utf16));
+        assert_eq!(map.offset(utf16, OffsetEncoding::Utf16), Some(b));
+        let utf32 = Position { line: 2, column: 3 };
+        assert_eq!(map.position(b, OffsetEncoding::Utf32), Some(utf32));
+        assert_eq!(map.offset(utf32, OffsetEncoding::Utf32), Some(b));
+        assert_eq!(
+            map.position(b, OffsetEncoding::Utf8),
+            Some(Position { line: 2, column: 7 })
+        );
+
+        // The end of the code is on the line after the trailing newline
+        assert_eq!(
+            map.position(code.len(), OffsetEncoding::Utf8),
+            Some(Position { line: 3, column: 1 })
+        );
+        assert_eq!(map.position(code.len() + 1, OffsetEncoding::Utf8), None);
+        assert_eq!(
+            map.offset(Position { line: 1, column: 9 }, OffsetEncoding::Utf16),
+            Some(1)
+        );
+        assert_eq!(
+            map.offset(Position { line: 0, column: 1 }, OffsetEncoding::Utf8),
+            None
+        );
+    }
+}
@@ -8,9 +8,7 @@ use petgraph::{
 };
 use serde::{Deserialize, Serialize};
 
-use crate::{
-    c_langs_macros::is_specials, langs::*, languages::language_preproc::*, tools::*, traits::*,
-};
+use crate::{c_langs_macros::is_specials, langs::*, languages::Cpp, tools::*, traits::*};
 
 /// Preprocessor data of a `C/C++` file.
 #[derive(Debug, Default, Deserialize, Serialize)]
@@ -206,26 +204,40 @@ pub fn preprocess(parser: &PreprocParser, path: &Path, results: &mut PreprocResu
             }
         }
 
-        let id = Preproc::from(node.kind_id());
-        match id {
-            Preproc::Define | Preproc::Undef => {
-                cursor.reset(&node);
-                cursor.goto_first_child();
-                let identifier = cursor.node();
-
-                if identifier.kind_id() == Preproc::Identifier {
-                    let r#macro = identifier.utf8_text(code).unwrap();
+        // The preprocessor directives are parsed by the C++ grammar
+        match Cpp::from(node.kind_id()) {
+            Cpp::PreprocDef | Cpp::PreprocFunctionDef => {
+                if let Some(r#macro) = node
+                    .child_by_field_name("name")
+                    .and_then(|name| name.utf8_text(code))
+                {
+                    if !is_specials(r#macro) {
+                        file_result.macros.insert(r#macro.to_string());
+                    }
+                }
+            }
+            // `#undef` is a call of a directive
+            Cpp::PreprocCall => {
+                let directive = node
+                    .child_by_field_name("directive")
+                    .and_then(|directive| directive.utf8_text(code));
+                if let (Some("#undef"), Some(r#macro)) = (
+                    directive,
+                    node.child_by_field_name("argument")
+                        .and_then(|argument| argument.utf8_text(code)),
+                ) {
+                    let r#macro = r#macro.trim();
                     if !is_specials(r#macro) {
                         file_result.macros.insert(r#macro.to_string());
                     }
                 }
             }
-            Preproc::PreprocInclude => {
-                cursor.reset(&node);
-                cursor.goto_first_child();
-                let file = cursor.node();
+            Cpp::PreprocInclude => {
+                let Some(file) = node.child_by_field_name("path") else {
+                    continue;
+                };
 
-                if file.kind_id() == Preproc::StringLiteral {
+                if file.kind_id() == Cpp::StringLiteral {
                     // remove the starting/ending double quote
                     let file = &code[file.start_byte() + 1..file.end_byte() - 1];
                     let start = file.iter().position(|&c| c != b' ' && c != b'\t').unwrap();
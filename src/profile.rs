@@ -0,0 +1,180 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
+
+use serde::Serialize;
+
+use crate::{
+    count::count,
+    langs::*,
+    preproc::PreprocResults,
+    spaces::{file_space, FuncSpace, NestedAttribution, SpaceGranularity},
+    traits::*,
+};
+
+/// The time spent analyzing a file.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileProfile {
+    /// The path of the file
+    pub path: PathBuf,
+    /// The language of the file
+    pub language: &'static str,
+    /// The size of the file, in bytes
+    pub bytes: usize,
+    /// The time spent parsing the file, in milliseconds
+    pub parse_ms: f64,
+    /// The time spent computing the metrics of the file, in milliseconds
+    pub metrics_ms: f64,
+    /// The number of nodes of the syntax tree of the file, all kept in
+    /// memory while it is analyzed
+    pub node_count: usize,
+}
+
+impl FileProfile {
+    /// Returns the time spent analyzing the file, in milliseconds.
+    pub fn total_ms(&self) -> f64 {
+        self.parse_ms + self.metrics_ms
+    }
+}
+
+/// The profiles of the files of a code, from the slowest to the fastest.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ProfileReport {
+    /// The time spent analyzing all the files, in milliseconds
+    pub total_ms: f64,
+    /// The profiles of the files
+    pub files: Vec<FileProfile>,
+}
+
+impl ProfileReport {
+    /// Creates the report of some file profiles.
+    pub fn new(mut files: Vec<FileProfile>) -> Self {
+        files.sort_by(|a, b| b.total_ms().total_cmp(&a.total_ms()));
+        Self {
+            total_ms: files.iter().map(FileProfile::total_ms).sum(),
+            files,
+        }
+    }
+
+    /// Returns the `n` slowest files.
+    pub fn slowest(&self, n: usize) -> &[FileProfile] {
+        &self.files[..n.min(self.files.len())]
+    }
+}
+
+struct Profiled;
+
+impl Callback for Profiled {
+    // The function spaces, the end of the parsing, the metrics time
+    // and the node count
+    type Res = (Option<FuncSpace>, Instant, f64, usize);
+    type Cfg = (PathBuf, NestedAttribution, SpaceGranularity, bool);
+
+    fn call<T: ParserTrait>(
+        (path, nested, granularity, halstead): Self::Cfg,
+        parser: &T,
+    ) -> Self::Res {
+        let parsed = Instant::now();
+        let (_, node_count) = count(parser, &[]);
+        let start = Instant::now();
+        let space = file_space(parser, &path, nested, granularity, false, halstead);
+        (space, parsed, elapsed_ms(start), node_count)
+    }
+}
+
+fn elapsed_ms(start: Instant) -> f64 {
+    start.elapsed().as_secs_f64() * 1000.
+}
+
+/// Returns all function spaces data of a code, as
+/// [`get_function_spaces`] does, with the profile of their analysis.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+///
+/// use singularity_code_analysis::{profile_function_spaces, LANG};
+///
+/// let path = PathBuf::from("foo.rs");
+/// let (space, profile) = profile_function_spaces(&LANG::Rust, b"fn f() {}".to_vec(), &path, None);
+///
+/// assert!(space.is_some());
+/// assert!(profile.node_count > 1);
+/// ```
+pub fn profile_function_spaces(
+    lang: &LANG,
+    source: Vec<u8>,
+    path: &Path,
+    pr: Option<Arc<PreprocResults>>,
+) -> (Option<FuncSpace>, FileProfile) {
+    profile_with_nesting(
+        lang,
+        source,
+        path,
+        pr,
+        NestedAttribution::default(),
+        SpaceGranularity::default(),
+        true,
+    )
+}
+
+pub(crate) fn profile_with_nesting(
+    lang: &LANG,
+    source: Vec<u8>,
+    path: &Path,
+    pr: Option<Arc<PreprocResults>>,
+    nested: NestedAttribution,
+    granularity: SpaceGranularity,
+    halstead: bool,
+) -> (Option<FuncSpace>, FileProfile) {
+    let bytes = source.len();
+    let start = Instant::now();
+    let (space, parsed, metrics_ms, node_count) = action::<Profiled>(
+        lang,
+        source,
+        path,
+        pr,
+        (path.to_path_buf(), nested, granularity, halstead),
+    );
+    let parse_ms = parsed.duration_since(start).as_secs_f64() * 1000.;
+
+    let profile = FileProfile {
+        path: path.to_path_buf(),
+        language: lang.get_name(),
+        bytes,
+        parse_ms,
+        metrics_ms,
+        node_count,
+    };
+    (space, profile)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profile_report() {
+        let profiles = ["a.rs", "b.rs"]
+            .iter()
+            .zip([1, 50])
+            .map(|(path, functions)| {
+                let code = "fn f(x: i32) { if x > 0 {} }\n".repeat(functions);
+                profile_function_spaces(&LANG::Rust, code.into_bytes(), Path::new(path), None).1
+            })
+            .collect::<Vec<_>>();
+        assert!(profiles[1].node_count > profiles[0].node_count * 40);
+
+        let report = ProfileReport::new(profiles);
+        assert_eq!(report.files.len(), 2);
+        assert!(report.files[0].total_ms() >= report.files[1].total_ms());
+        assert_eq!(report.slowest(5).len(), 2);
+        assert_eq!(
+            report.total_ms,
+            report.files[0].total_ms() + report.files[1].total_ms()
+        );
+    }
+}
@@ -0,0 +1,1029 @@
+use std::collections::{BTreeSet, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    checker::Checker,
+    getter::Getter,
+    langs::LANG,
+    liveness::function_liveness,
+    metrics::{
+        cognitive::{self, Cognitive},
+        cyclomatic::{self, Cyclomatic},
+        loc,
+    },
+    node::Node,
+    traits::{Callback, ParserTrait},
+};
+
+/// The metrics of a function before or after an extraction.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+pub struct ExtractionMetrics {
+    /// The cyclomatic complexity
+    pub cyclomatic: f64,
+    /// The maximum number of nested control structures
+    pub nesting: usize,
+    /// The number of logical lines
+    pub lloc: f64,
+}
+
+/// The estimated effect of extracting some statements of a function
+/// into a new function.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ExtractionEstimate {
+    /// The name of the function
+    pub function: Option<String>,
+    /// The first line of the extracted statements
+    pub start_line: usize,
+    /// The last line of the extracted statements
+    pub end_line: usize,
+    /// The metrics of the function before the extraction
+    pub before: ExtractionMetrics,
+    /// The metrics of the extracted function
+    pub extracted: ExtractionMetrics,
+    /// The metrics of the function after the extraction, where the
+    /// statements are replaced by a call to the extracted function
+    pub remainder: ExtractionMetrics,
+}
+
+impl ExtractionEstimate {
+    /// Returns how much the extraction lowers the highest cyclomatic
+    /// complexity of the two resulting functions, compared to the
+    /// original one.
+    pub fn cyclomatic_reduction(&self) -> f64 {
+        self.before.cyclomatic - self.extracted.cyclomatic.max(self.remainder.cyclomatic)
+    }
+}
+
+// The metrics of a part of a function, accumulated node by node
+struct Accumulator {
+    cyclomatic: cyclomatic::Stats,
+    loc: loc::Stats,
+    nesting: usize,
+}
+
+impl Accumulator {
+    fn new() -> Self {
+        Self {
+            cyclomatic: cyclomatic::Stats::default(),
+            loc: loc::Stats::default(),
+            nesting: 0,
+        }
+    }
+
+    fn metrics(&self, nesting_base: usize) -> ExtractionMetrics {
+        ExtractionMetrics {
+            cyclomatic: self.cyclomatic.cyclomatic(),
+            nesting: self.nesting.saturating_sub(nesting_base),
+            lloc: self.loc.lloc(),
+        }
+    }
+}
+
+// The innermost function containing some 0-based rows
+fn enclosing_function<'a, T: ParserTrait>(
+    root: Node<'a>,
+    start: usize,
+    end: usize,
+) -> Option<Node<'a>> {
+    let mut function = None;
+    let mut node = root;
+    'descent: loop {
+        if T::Checker::is_func(&node) {
+            function = Some(node);
+        }
+        for child in node.children() {
+            if child.start_row() <= start && end <= child.end_row() {
+                node = child;
+                continue 'descent;
+            }
+        }
+        return function;
+    }
+}
+
+/// Estimates the metrics of the two functions resulting from the
+/// extraction of some lines of a function into a new function, without
+/// editing the code.
+///
+/// The lines are 1-based and inclusive, and must contain whole
+/// statements of the innermost function containing them: `None` is
+/// returned when no function contains the lines, when they contain no
+/// statement, or when a statement only partially overlaps them.
+///
+/// The nested functions and closures are left out of the metrics, as
+/// they are function spaces on their own.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{simulate_extraction, ParserEngineRust, ParserTrait};
+///
+/// let code = "fn f(x: i32) {\n    if x > 0 {\n        if x > 1 {\n            g();\n        }\n    }\n    h();\n}\n";
+/// let path = Path::new("foo.rs");
+/// let parser = ParserEngineRust::new(code.as_bytes().to_vec(), path, None);
+///
+/// let estimate = simulate_extraction(&parser, 2, 6).unwrap();
+/// assert_eq!(estimate.before.cyclomatic, 3.);
+/// assert_eq!(estimate.extracted.cyclomatic, 3.);
+/// assert_eq!(estimate.remainder.cyclomatic, 1.);
+/// assert_eq!(estimate.extracted.nesting, 2);
+/// ```
+pub fn simulate_extraction<T: ParserTrait>(
+    parser: &T,
+    start_line: usize,
+    end_line: usize,
+) -> Option<ExtractionEstimate> {
+    let (start, end) = (start_line.checked_sub(1)?, end_line.checked_sub(1)?);
+    if start > end {
+        return None;
+    }
+    let code = parser.get_code();
+    let language = parser.get_language();
+    let function = enclosing_function::<T>(parser.get_root(), start, end)?;
+
+    let mut nesting_map = HashMap::new();
+    let mut cognitive = cognitive::Stats::default();
+    let mut before = Accumulator::new();
+    let mut extracted = Accumulator::new();
+    let mut remainder = Accumulator::new();
+    // The nesting of the code enclosing the extracted statements
+    let mut nesting_base = None;
+    // The node containing the extracted statements
+    let mut block: Option<Node> = None;
+
+    let mut stack = vec![(function, false)];
+    while let Some((node, inside)) = stack.pop() {
+        let is_inside = inside || (start <= node.start_row() && node.end_row() <= end);
+        if is_inside && !inside && node.is_named() && !T::Checker::is_comment(&node) {
+            // The statements must be siblings
+            let parent = node.parent()?;
+            if block.is_some_and(|block| block.id() != parent.id()) {
+                return None;
+            }
+            block = Some(parent);
+            nesting_base.get_or_insert_with(|| {
+                nesting_map
+                    .get(&parent.id())
+                    .map_or(0, |nesting: &(usize, usize, usize)| nesting.0)
+            });
+        }
+
+        T::Cognitive::compute(&node, &mut cognitive, &mut nesting_map);
+        let nesting = nesting_map.get(&node.id()).map_or(0, |nesting| nesting.0);
+        let is_function = node.id() == function.id();
+        let part = if is_inside {
+            &mut extracted
+        } else {
+            &mut remainder
+        };
+        for part in [&mut before, part] {
+            T::Cyclomatic::compute(&node, &mut part.cyclomatic);
+            loc::compute_loc::<T::Loc>(language, &node, &mut part.loc, is_function, false);
+            part.nesting = part.nesting.max(nesting);
+        }
+
+        for child in node.children().collect::<Vec<_>>().into_iter().rev() {
+            if !T::Checker::is_func(&child) && !T::Checker::is_closure(&child) {
+                stack.push((child, is_inside));
+            }
+        }
+    }
+    // A statement crossing the boundaries of the lines can't be extracted
+    let straddling = block?.children().any(|child| {
+        child.is_named()
+            && child.start_row() <= end
+            && start <= child.end_row()
+            && (child.start_row() < start || end < child.end_row())
+    });
+    if straddling {
+        return None;
+    }
+
+    let nesting_base = nesting_base.unwrap_or_default();
+    let mut remainder = remainder.metrics(0);
+    // The extracted statements are replaced by a call
+    remainder.lloc += 1.;
+    Some(ExtractionEstimate {
+        function: T::Getter::get_func_space_name(&function, code).map(|name| name.to_string()),
+        start_line,
+        end_line,
+        before: before.metrics(0),
+        extracted: extracted.metrics(nesting_base),
+        remainder,
+    })
+}
+
+// The most statements of a candidate
+const MAX_STATEMENTS: usize = 10;
+
+// The fewest lines of a candidate
+const MIN_LINES: usize = 3;
+
+// The most variables crossing the boundaries of a candidate
+const MAX_CROSSING_VARIABLES: usize = 4;
+
+/// A block of statements which can be extracted into a function.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ExtractionCandidate {
+    /// The variables live at the start of the block and read by it,
+    /// which become the parameters of the extracted function
+    pub inputs: Vec<String>,
+    /// The variables defined by the block and live at its end, which
+    /// become the return value of the extracted function
+    pub outputs: Vec<String>,
+    /// The span and the metrics of the extraction
+    #[serde(flatten)]
+    pub estimate: ExtractionEstimate,
+}
+
+impl ExtractionCandidate {
+    /// Returns the number of variables crossing the boundaries of the
+    /// block.
+    pub fn crossing_variables(&self) -> usize {
+        self.inputs.len() + self.outputs.len()
+    }
+}
+
+fn is_block(node: &Node) -> bool {
+    let kind = node.kind();
+    kind.ends_with("block")
+        || kind.ends_with("body")
+        || matches!(kind, "compound_statement" | "statement_list")
+}
+
+fn is_loop(node: &Node) -> bool {
+    let kind = node.kind();
+    kind.contains("for")
+        || kind.contains("while")
+        || kind.contains("loop")
+        || kind.contains("switch")
+}
+
+// Returns `true` if the control flow can leave some statements other
+// than by their end, like a `return` or a `break` out of them
+fn has_exits<T: ParserTrait>(statements: &[Node]) -> bool {
+    let mut stack = statements
+        .iter()
+        .map(|statement| (*statement, false))
+        .collect::<Vec<_>>();
+    while let Some((node, in_loop)) = stack.pop() {
+        let kind = node.kind();
+        if kind.starts_with("return") || kind.starts_with("yield") || kind == "try_expression" {
+            return true;
+        }
+        if !in_loop && (kind.starts_with("break") || kind.starts_with("continue")) {
+            return true;
+        }
+        let in_loop = in_loop || is_loop(&node);
+        for child in node.children() {
+            if !T::Checker::is_func(&child) && !T::Checker::is_closure(&child) {
+                stack.push((child, in_loop));
+            }
+        }
+    }
+    false
+}
+
+// The statements of the blocks of a function, leaving out the nested
+// functions and closures
+fn statement_lists<'a, T: ParserTrait>(function: &Node<'a>) -> Vec<Vec<Node<'a>>> {
+    let mut lists = Vec::new();
+    let mut stack = vec![*function];
+    while let Some(node) = stack.pop() {
+        if is_block(&node) {
+            let statements = node
+                .children()
+                .filter(|child| child.is_named() && !T::Checker::is_comment(child))
+                .collect::<Vec<_>>();
+            if !statements.is_empty() {
+                lists.push(statements);
+            }
+        }
+        for child in node.children() {
+            if !T::Checker::is_func(&child) && !T::Checker::is_closure(&child) {
+                stack.push(child);
+            }
+        }
+    }
+    lists
+}
+
+fn function_candidates<T: ParserTrait>(parser: &T, function: &Node) -> Vec<ExtractionCandidate> {
+    let code = parser.get_code();
+    let liveness = function_liveness::<T>(function, code);
+    let mut candidates = Vec::new();
+    let mut spans = BTreeSet::new();
+    for statements in statement_lists::<T>(function) {
+        let body = statements[0].parent().is_some_and(|parent| {
+            parent
+                .parent()
+                .is_some_and(|grandparent| grandparent.id() == function.id())
+        });
+        for first in 0..statements.len() {
+            let last_max = (first + MAX_STATEMENTS).min(statements.len());
+            for last in first..last_max {
+                // The whole body of the function isn't worth extracting
+                if body && first == 0 && last == statements.len() - 1 {
+                    continue;
+                }
+                let (start, end) = (statements[first].start_row(), statements[last].end_row());
+                if end + 1 - start < MIN_LINES || !spans.insert((start, end)) {
+                    continue;
+                }
+                if has_exits::<T>(&statements[first..=last]) {
+                    continue;
+                }
+
+                let (inputs, outputs) = liveness.crossing_variables(start + 1, end + 1);
+                if outputs.len() > 1 || inputs.len() + outputs.len() > MAX_CROSSING_VARIABLES {
+                    continue;
+                }
+
+                let Some(estimate) = simulate_extraction(parser, start + 1, end + 1) else {
+                    continue;
+                };
+                if estimate.cyclomatic_reduction() > 0. {
+                    candidates.push(ExtractionCandidate {
+                        inputs: inputs.iter().map(|name| name.to_string()).collect(),
+                        outputs: outputs.iter().map(|name| name.to_string()).collect(),
+                        estimate,
+                    });
+                }
+            }
+        }
+    }
+
+    // The best candidates first, preferring the smallest blocks, without
+    // overlaps
+    candidates.sort_by(|a, b| {
+        b.estimate
+            .cyclomatic_reduction()
+            .total_cmp(&a.estimate.cyclomatic_reduction())
+            .then(a.crossing_variables().cmp(&b.crossing_variables()))
+            .then(
+                a.estimate
+                    .extracted
+                    .lloc
+                    .total_cmp(&b.estimate.extracted.lloc),
+            )
+            .then(a.estimate.start_line.cmp(&b.estimate.start_line))
+    });
+    let mut selected: Vec<ExtractionCandidate> = Vec::new();
+    for candidate in candidates {
+        let overlaps = selected.iter().any(|other| {
+            candidate.estimate.start_line <= other.estimate.end_line
+                && other.estimate.start_line <= candidate.estimate.end_line
+        });
+        if !overlaps {
+            selected.push(candidate);
+        }
+    }
+    selected
+}
+
+/// Finds the blocks of statements of the functions longer than
+/// `min_lines` lines which are worth extracting into new functions.
+///
+/// A candidate is a run of sibling statements which the control flow
+/// only enters at its start and only leaves at its end, so it contains
+/// no `return` nor any `break` out of it, which uses few variables of
+/// the rest of the function, defines at most one variable used after
+/// it, and whose extraction lowers the cyclomatic complexity, as
+/// estimated by [`simulate_extraction`].
+///
+/// The candidates of a function don't overlap, the ones lowering the
+/// complexity the most come first.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{extraction_candidates, ParserTrait, PythonParser};
+///
+/// let code = "def f(items):
+///     total = 0
+///     for item in items:
+///         if item > 0:
+///             total += item
+///     count = 0
+///     for item in items:
+///         if item < 0:
+///             count += 1
+///     return total, count
+/// ";
+/// let path = Path::new("foo.py");
+/// let parser = PythonParser::new(code.as_bytes().to_vec(), path, None);
+///
+/// let candidates = extraction_candidates(&parser, 5);
+/// assert_eq!(candidates.len(), 2);
+/// assert_eq!(candidates[0].estimate.start_line, 2);
+/// assert_eq!(candidates[0].estimate.end_line, 5);
+/// assert_eq!(candidates[0].inputs, ["items"]);
+/// assert_eq!(candidates[0].outputs, ["total"]);
+/// assert_eq!(candidates[0].estimate.cyclomatic_reduction(), 2.);
+/// ```
+pub fn extraction_candidates<T: ParserTrait>(
+    parser: &T,
+    min_lines: usize,
+) -> Vec<ExtractionCandidate> {
+    let mut candidates = Vec::new();
+    let mut stack = vec![parser.get_root()];
+    while let Some(node) = stack.pop() {
+        if T::Checker::is_func(&node) && node.end_row() - node.start_row() + 1 > min_lines {
+            candidates.extend(function_candidates(parser, &node));
+        }
+        let mut children = node.children().collect::<Vec<_>>();
+        children.reverse();
+        stack.extend(children);
+    }
+    candidates
+}
+
+/// Finds the extraction candidates of a code.
+///
+/// Its configuration is the number of lines above which a function is
+/// analyzed, as in [`extraction_candidates`].
+pub struct ExtractionCandidates {
+    _guard: (),
+}
+
+impl Callback for ExtractionCandidates {
+    type Res = Vec<ExtractionCandidate>;
+    type Cfg = usize;
+
+    fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
+        extraction_candidates(parser, cfg)
+    }
+}
+
+/// An edit replacing some lines of a code.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextEdit {
+    /// The first replaced line
+    pub start_line: usize,
+    /// The last replaced line
+    pub end_line: usize,
+    /// The text replacing the lines, without a final newline
+    pub replacement: String,
+}
+
+/// Applies some edits to a code.
+///
+/// The edits must not overlap, the lines of each one are the lines of
+/// the original code.
+pub fn apply_edits(code: &str, edits: &[TextEdit]) -> String {
+    let mut edits = edits.iter().collect::<Vec<_>>();
+    edits.sort_by_key(|edit| edit.start_line);
+    let lines = code.lines().collect::<Vec<_>>();
+    let mut edited = Vec::new();
+    let mut line = 1;
+    for edit in edits {
+        edited.extend(
+            lines
+                .iter()
+                .take(edit.start_line.saturating_sub(1))
+                .skip(line - 1),
+        );
+        edited.push(edit.replacement.as_str());
+        line = line.max(edit.end_line + 1);
+    }
+    edited.extend(lines.iter().skip(line - 1));
+    let mut edited = edited.join("\n");
+    if code.ends_with('\n') {
+        edited.push('\n');
+    }
+    edited
+}
+
+// The syntax of a guard clause, `None` for the languages without early
+// exits
+fn guard_clause(language: LANG, condition: &str, exit: &str, indent: &str) -> Option<Vec<String>> {
+    match language {
+        LANG::Python => Some(vec![format!("if {condition}:"), format!("{indent}{exit}")]),
+        LANG::Rust => Some(vec![
+            format!("if {condition} {{"),
+            format!("{indent}{exit};"),
+            "}".to_string(),
+        ]),
+        LANG::Go => Some(vec![
+            format!("if {condition} {{"),
+            format!("{indent}{exit}"),
+            "}".to_string(),
+        ]),
+        LANG::Javascript | LANG::Typescript | LANG::Tsx | LANG::Java | LANG::Cpp | LANG::Csharp => {
+            Some(vec![
+                format!("if ({condition}) {{"),
+                format!("{indent}{exit};"),
+                "}".to_string(),
+            ])
+        }
+        _ => None,
+    }
+}
+
+// Negates a condition, removing a negation or flipping an equality
+// when possible
+fn negate(condition: &Node, code: &[u8], language: LANG) -> Option<String> {
+    let mut condition = *condition;
+    // The parentheses around the conditions in Java, JavaScript or C++
+    while matches!(
+        condition.kind(),
+        "parenthesized_expression" | "condition_clause"
+    ) {
+        condition = condition.children().find(|child| child.is_named())?;
+    }
+    let text = condition.utf8_text(code)?;
+    let named = condition
+        .children()
+        .filter(|child| child.is_named())
+        .collect::<Vec<_>>();
+    let operators = condition
+        .children()
+        .filter(|child| !child.is_named())
+        .collect::<Vec<_>>();
+    let operator = match operators.as_slice() {
+        [operator] => operator.utf8_text(code),
+        _ => None,
+    };
+
+    let kind = condition.kind();
+    if (kind.contains("unary") || kind == "not_operator")
+        && matches!(operator, Some("!" | "not"))
+        && named.len() == 1
+    {
+        return named[0].utf8_text(code).map(|operand| operand.to_string());
+    }
+    if let (Some(flipped), [left, right]) = (
+        match operator {
+            Some("==") => Some("!="),
+            Some("!=") => Some("=="),
+            _ => None,
+        },
+        named.as_slice(),
+    ) {
+        return Some(format!(
+            "{} {flipped} {}",
+            left.utf8_text(code)?,
+            right.utf8_text(code)?
+        ));
+    }
+
+    let not = if language == LANG::Python {
+        "not "
+    } else {
+        "!"
+    };
+    let is_simple = kind.contains("identifier")
+        || kind.contains("call")
+        || kind.contains("invocation")
+        || kind.contains("field")
+        || kind.contains("member")
+        || kind == "attribute";
+    Some(if is_simple {
+        format!("{not}{text}")
+    } else {
+        format!("{not}({text})")
+    })
+}
+
+// The exit of a body which an `if` ends, `return` for a function
+// returning nothing and `continue` for a loop
+fn body_exit<T: ParserTrait>(statement: &Node, code: &[u8]) -> Option<&'static str> {
+    let body = statement.parent()?;
+    let last = body
+        .children()
+        .filter(|child| child.is_named() && !T::Checker::is_comment(child))
+        .last()?;
+    if last.id() != statement.id() {
+        return None;
+    }
+    let owner = body.parent()?;
+    if T::Checker::is_func(&owner) {
+        let returns_nothing = ["return_type", "result", "returns", "type"]
+            .iter()
+            .find_map(|field| owner.child_by_field_name(field))
+            .map_or(Some(true), |returns| {
+                let returns = returns.utf8_text(code)?;
+                let returns = returns.trim_start_matches([':', '-', '>']).trim();
+                Some(matches!(returns, "()" | "void" | "None"))
+            })?;
+        returns_nothing.then_some("return")
+    } else if is_loop(&owner) && !owner.kind().contains("switch") {
+        Some("continue")
+    } else {
+        None
+    }
+}
+
+// An `if` without alternatives whose statements can follow a guard clause
+struct Guardable<'a> {
+    // The `if`, or the statement made of it
+    statement: Node<'a>,
+    condition: Node<'a>,
+    // The statements and the comments of the consequence
+    children: Vec<Node<'a>>,
+    // The last row of the consequence
+    content_end: usize,
+}
+
+fn guardable<'a, T: ParserTrait>(statement: Node<'a>, lines: &[&str]) -> Option<Guardable<'a>> {
+    let node = if statement.kind() == "expression_statement" {
+        statement.children().find(|child| child.is_named())?
+    } else {
+        statement
+    };
+    if !node.kind().starts_with("if")
+        || node.child_by_field_name("alternative").is_some()
+        || node.child_by_field_name("initializer").is_some()
+    {
+        return None;
+    }
+    // `if let` in Rust
+    let condition = node
+        .child_by_field_name("condition")
+        .filter(|condition| !condition.kind().contains("let"))?;
+    let consequence = node.child_by_field_name("consequence")?;
+    let children = consequence
+        .children()
+        .filter(|child| child.is_named())
+        .collect::<Vec<_>>();
+    if !children.iter().any(|child| !T::Checker::is_comment(child)) {
+        return None;
+    }
+
+    // The `if` starts its line, and its statements start the next ones
+    let (row, column) = statement.start_position();
+    if !lines.get(row)?.get(..column)?.trim().is_empty()
+        || children[0].start_row() <= row
+        || statement.end_row() != consequence.end_row()
+    {
+        return None;
+    }
+    let content_end = if consequence.children().last()?.kind() == "}" {
+        // The closing brace stands alone on its line
+        if lines.get(consequence.end_row())?.trim() != "}"
+            || children.last()?.end_row() >= consequence.end_row()
+        {
+            return None;
+        }
+        consequence.end_row() - 1
+    } else {
+        consequence.end_row()
+    };
+
+    Some(Guardable {
+        statement,
+        condition,
+        children,
+        content_end,
+    })
+}
+
+// Rewrites an `if` into guard clauses followed by its statements, as
+// lines indented relatively to the `if`
+fn guard_lines<T: ParserTrait>(
+    outer: &Guardable,
+    code: &[u8],
+    lines: &[&str],
+    language: LANG,
+    exit: &str,
+) -> Option<Vec<String>> {
+    let column = outer.statement.start_position().1;
+    let first = outer.children[0];
+    let (first_row, body_column) = first.start_position();
+    let indent = lines[first_row]
+        .get(column..body_column)
+        .filter(|indent| indent.trim().is_empty())
+        .unwrap_or("    ");
+    let condition = negate(&outer.condition, code, language)?;
+    let mut rewritten = guard_clause(language, &condition, exit, indent)?;
+
+    let dedent = |row: usize| {
+        let line = lines[row];
+        match line.get(..body_column) {
+            Some(prefix) if prefix.trim().is_empty() => line[body_column..].to_string(),
+            _ => line.trim_start().to_string(),
+        }
+    };
+    // The last statement becomes a guard clause too
+    let last = outer
+        .children
+        .iter()
+        .rfind(|child| !T::Checker::is_comment(child))?;
+    let inner = guardable::<T>(*last, lines)
+        .filter(|inner| inner.statement.start_position().1 == body_column);
+    match inner {
+        Some(inner) => {
+            rewritten.extend((first_row..inner.statement.start_row()).map(dedent));
+            rewritten.extend(guard_lines::<T>(&inner, code, lines, language, exit)?);
+            rewritten.extend((inner.statement.end_row() + 1..=outer.content_end).map(dedent));
+        }
+        None => rewritten.extend((first_row..=outer.content_end).map(dedent)),
+    }
+    Some(rewritten)
+}
+
+// Returns `true` if some nodes are or contain a conditional or a loop
+fn has_control<T: ParserTrait>(nodes: &[Node]) -> bool {
+    let mut stack = nodes.to_vec();
+    while let Some(node) = stack.pop() {
+        if node.kind().starts_with("if_") || is_loop(&node) {
+            return true;
+        }
+        stack.extend(
+            node.children()
+                .filter(|child| !T::Checker::is_func(child) && !T::Checker::is_closure(child)),
+        );
+    }
+    false
+}
+
+/// Finds the nested conditionals which can be turned into guard clauses,
+/// and returns the edits doing so.
+///
+/// A conditional without `else` branches ending a function which returns
+/// nothing, or the body of a loop, is replaced by a negated conditional
+/// returning early, or continuing the loop, followed by its statements,
+/// and so on for the conditionals it ends itself. Only the conditionals
+/// containing other conditionals or loops are converted, and the code
+/// keeps its behavior.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{apply_edits, guard_clause_edits, ParserTrait, PythonParser};
+///
+/// let code = "def f(x):
+///     if x:
+///         if x > 1:
+///             print(x)
+/// ";
+/// let path = Path::new("foo.py");
+/// let parser = PythonParser::new(code.as_bytes().to_vec(), path, None);
+///
+/// let edits = guard_clause_edits(&parser);
+/// assert_eq!(
+///     apply_edits(code, &edits),
+///     "def f(x):
+///     if not x:
+///         return
+///     if not (x > 1):
+///         return
+///     print(x)
+/// "
+/// );
+/// ```
+pub fn guard_clause_edits<T: ParserTrait>(parser: &T) -> Vec<TextEdit> {
+    let code = parser.get_code();
+    let text = String::from_utf8_lossy(code);
+    let lines = text.lines().collect::<Vec<_>>();
+    let language = parser.get_language();
+
+    let mut edits = Vec::new();
+    let mut stack = vec![parser.get_root()];
+    while let Some(node) = stack.pop() {
+        let edit = body_exit::<T>(&node, code).and_then(|exit| {
+            let guardable = guardable::<T>(node, &lines)?;
+            if !has_control::<T>(&guardable.children) {
+                return None;
+            }
+            let column = node.start_position().1;
+            let indent = &lines[node.start_row()][..column];
+            let replacement = guard_lines::<T>(&guardable, code, &lines, language, exit)?
+                .iter()
+                .map(|line| {
+                    if line.is_empty() {
+                        String::new()
+                    } else {
+                        format!("{indent}{line}")
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            Some(TextEdit {
+                start_line: node.start_row() + 1,
+                end_line: node.end_row() + 1,
+                replacement,
+            })
+        });
+        match edit {
+            Some(edit) => edits.push(edit),
+            None => {
+                let mut children = node.children().collect::<Vec<_>>();
+                children.reverse();
+                stack.extend(children);
+            }
+        }
+    }
+    edits
+}
+
+/// Finds the guard clause edits of a code, see [`guard_clause_edits`].
+pub struct GuardClauseEdits {
+    _guard: (),
+}
+
+impl Callback for GuardClauseEdits {
+    type Res = Vec<TextEdit>;
+    type Cfg = ();
+
+    fn call<T: ParserTrait>(_cfg: Self::Cfg, parser: &T) -> Self::Res {
+        guard_clause_edits(parser)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{ParserEngineRust, PythonParser};
+
+    use super::*;
+
+    #[test]
+    fn rust_extraction() {
+        let code = "fn f(x: i32) -> i32 {\n\
+                    \x20   let mut y = 0;\n\
+                    \x20   for i in 0..x {\n\
+                    \x20       if i % 2 == 0 && i > 4 {\n\
+                    \x20           y += i;\n\
+                    \x20       }\n\
+                    \x20   }\n\
+                    \x20   y\n\
+                    }\n";
+        let path = Path::new("foo.rs");
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), path, None);
+
+        let estimate = simulate_extraction(&parser, 4, 6).unwrap();
+        assert_eq!(estimate.function.as_deref(), Some("f"));
+        assert_eq!(estimate.before.cyclomatic, 4.);
+        assert_eq!(estimate.extracted.cyclomatic, 3.);
+        assert_eq!(estimate.remainder.cyclomatic, 2.);
+        assert_eq!(estimate.cyclomatic_reduction(), 1.);
+        assert_eq!(estimate.before.nesting, 2);
+        assert_eq!(estimate.extracted.nesting, 1);
+        assert_eq!(estimate.remainder.nesting, 1);
+
+        // The lines cut the `for` loop
+        assert!(simulate_extraction(&parser, 3, 4).is_none());
+        // No function
+        assert!(simulate_extraction(&parser, 12, 12).is_none());
+    }
+
+    #[test]
+    fn python_extraction() {
+        let code = "def f(x):\n    y = 0\n    if x:\n        y = 1\n    while y:\n        y -= 1\n    return y\n";
+        let path = Path::new("foo.py");
+        let parser = PythonParser::new(code.as_bytes().to_vec(), path, None);
+
+        let estimate = simulate_extraction(&parser, 5, 6).unwrap();
+        assert_eq!(estimate.extracted.cyclomatic, 2.);
+        assert_eq!(estimate.remainder.cyclomatic, 2.);
+        assert_eq!(
+            estimate.before.lloc,
+            estimate.extracted.lloc + estimate.remainder.lloc - 1.
+        );
+    }
+
+    #[test]
+    fn rust_candidates() {
+        let code = "fn f(items: &[i32]) -> (i32, i32) {
+    let mut total = 0;
+    for item in items {
+        if *item > 0 {
+            total += item;
+        }
+    }
+    let mut count = 0;
+    for item in items {
+        if *item < 0 {
+            count += 1;
+        }
+    }
+    (total, count)
+}
+";
+        let path = Path::new("foo.rs");
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), path, None);
+
+        let candidates = extraction_candidates(&parser, 10);
+        let spans = candidates
+            .iter()
+            .map(|candidate| (candidate.estimate.start_line, candidate.estimate.end_line))
+            .collect::<Vec<_>>();
+        assert_eq!(spans, [(2, 7), (8, 13)]);
+        assert_eq!(candidates[0].estimate.function.as_deref(), Some("f"));
+        assert_eq!(candidates[0].outputs, ["total"]);
+        assert_eq!(candidates[0].inputs, ["items"]);
+        assert_eq!(candidates[1].inputs, ["items"]);
+        assert_eq!(candidates[1].outputs, ["count"]);
+
+        // The function isn't long enough
+        assert!(extraction_candidates(&parser, 20).is_empty());
+    }
+
+    #[test]
+    fn early_exits() {
+        let code = "def g(items):
+    for item in items:
+        if item:
+            return item
+    for item in items:
+        if item:
+            break
+    return None
+";
+        let path = Path::new("foo.py");
+        let parser = PythonParser::new(code.as_bytes().to_vec(), path, None);
+
+        let candidates = extraction_candidates(&parser, 0);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].estimate.start_line, 5);
+        assert_eq!(candidates[0].estimate.end_line, 7);
+    }
+
+    #[test]
+    fn rust_guard_clauses() {
+        let code = "fn f(items: &[i32]) {
+    for item in items {
+        if !item.is_empty() {
+            if *item == 0 {
+                process(item);
+            }
+        }
+    }
+}
+
+fn g(x: i32) -> i32 {
+    if x > 0 {
+        if x > 1 {
+            return 1;
+        }
+    }
+    0
+}
+
+fn h(x: i32) {
+    if x > 0 {
+        if x > 1 {
+            a();
+        }
+    } else {
+        b();
+    }
+}
+
+fn k(x: i32) {
+    if x > 0 {
+        a();
+    }
+}
+";
+        let path = Path::new("foo.rs");
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), path, None);
+
+        let edits = guard_clause_edits(&parser);
+        assert_eq!(edits.len(), 1);
+        assert_eq!((edits[0].start_line, edits[0].end_line), (3, 7));
+        let edited = apply_edits(code, &edits);
+        assert!(edited.starts_with(
+            "fn f(items: &[i32]) {
+    for item in items {
+        if item.is_empty() {
+            continue;
+        }
+        if *item != 0 {
+            continue;
+        }
+        process(item);
+    }
+}
+
+fn g"
+        ));
+    }
+
+    #[test]
+    fn edits() {
+        let code = "a\nb\nc\nd\n";
+        let edits = [
+            TextEdit {
+                start_line: 3,
+                end_line: 4,
+                replacement: "e".to_string(),
+            },
+            TextEdit {
+                start_line: 1,
+                end_line: 1,
+                replacement: "f\ng".to_string(),
+            },
+        ];
+        assert_eq!(apply_edits(code, &edits), "f\ng\nb\ne\n");
+    }
+}
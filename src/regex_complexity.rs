@@ -0,0 +1,477 @@
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::{
+    ai::{
+        CodeLocation, CodeSmell, SmellCatalog, SmellMetadata, SmellReport, CATASTROPHIC_REGEX,
+        COMPLEX_REGEX,
+    },
+    checker::Checker,
+    langs::LANG,
+    node::Node,
+    secrets::literal_content,
+    security::{callee, is_call, matches_api},
+    traits::ParserTrait,
+};
+
+/// The complexity of a regular expression.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct RegexMetrics {
+    /// The number of characters of the pattern
+    pub length: usize,
+    /// The number of groups
+    pub groups: usize,
+    /// The maximum depth of nested groups
+    pub nesting: usize,
+    /// The number of quantifiers, like `*` or `{2,3}`
+    pub quantifiers: usize,
+    /// The number of alternations
+    pub alternations: usize,
+    /// The number of quantified groups containing a quantifier, like
+    /// `(a+)+`, which can backtrack catastrophically
+    pub nested_quantifiers: usize,
+    /// The number of quantified wildcards, like `.*`
+    pub wildcards: usize,
+    /// The number of backreferences, like `\1`
+    pub backreferences: usize,
+}
+
+impl RegexMetrics {
+    /// Returns the complexity score of the pattern.
+    ///
+    /// Each group, quantifier and alternation adds one point, each level
+    /// of nesting, backreference and quantified wildcard beyond the first
+    /// adds two, each nested quantifier adds three, and every 20
+    /// characters add one.
+    pub fn score(&self) -> f64 {
+        self.length as f64 / 20.
+            + (self.groups + self.quantifiers + self.alternations) as f64
+            + 2. * (self.nesting + self.backreferences + self.wildcards.saturating_sub(1)) as f64
+            + 3. * self.nested_quantifiers as f64
+    }
+
+    /// Returns `true` if the pattern can backtrack catastrophically.
+    pub fn is_catastrophic(&self) -> bool {
+        self.nested_quantifiers > 0
+    }
+}
+
+// Returns the length of the quantifier starting a pattern, if any
+fn quantifier_len(chars: &[char]) -> Option<usize> {
+    match chars.first()? {
+        '*' | '+' | '?' => Some(1),
+        '{' => {
+            let end = chars.iter().position(|c| *c == '}')?;
+            let bounds = &chars[1..end];
+            (bounds.first().is_some_and(char::is_ascii_digit)
+                && bounds.iter().all(|c| c.is_ascii_digit() || *c == ','))
+            .then_some(end + 1)
+        }
+        _ => None,
+    }
+}
+
+/// Computes the complexity of a regular expression.
+///
+/// # Examples
+///
+/// ```
+/// use singularity_code_analysis::regex_metrics;
+///
+/// assert!(regex_metrics("^(a+)+$").is_catastrophic());
+/// assert!(!regex_metrics(r"^\d{3}-\d{4}$").is_catastrophic());
+/// ```
+pub fn regex_metrics(pattern: &str) -> RegexMetrics {
+    let chars = pattern.chars().collect::<Vec<_>>();
+    let mut metrics = RegexMetrics {
+        length: chars.len(),
+        ..Default::default()
+    };
+    // Whether each open group contains a quantifier
+    let mut groups: Vec<bool> = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let mut quantified_group = false;
+        let mut wildcard = false;
+        match chars[i] {
+            '\\' => {
+                if chars.get(i + 1).is_some_and(|c| ('1'..='9').contains(c)) {
+                    metrics.backreferences += 1;
+                }
+                i += 1;
+            }
+            '[' => {
+                i += 1;
+                if chars.get(i) == Some(&'^') {
+                    i += 1;
+                }
+                // A `]` right after the opening bracket is a character
+                if chars.get(i) == Some(&']') {
+                    i += 1;
+                }
+                while i < chars.len() && chars[i] != ']' {
+                    if chars[i] == '\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            '(' => {
+                groups.push(false);
+                metrics.groups += 1;
+                metrics.nesting = metrics.nesting.max(groups.len());
+                // `(?:`, `(?=` and the likes aren't quantifiers
+                i += if chars.get(i + 1) == Some(&'?') { 2 } else { 1 };
+                continue;
+            }
+            ')' => {
+                if let Some(quantified) = groups.pop() {
+                    quantified_group = quantified;
+                    if let Some(parent) = groups.last_mut() {
+                        *parent |= quantified;
+                    }
+                }
+            }
+            '|' => {
+                metrics.alternations += 1;
+                i += 1;
+                continue;
+            }
+            '.' => wildcard = true,
+            _ => {}
+        }
+        i += 1;
+
+        if let Some(len) = quantifier_len(&chars[i.min(chars.len())..]) {
+            metrics.quantifiers += 1;
+            metrics.nested_quantifiers += quantified_group as usize;
+            metrics.wildcards += wildcard as usize;
+            if let Some(group) = groups.last_mut() {
+                *group = true;
+            }
+            i += len;
+            // Lazy and possessive quantifiers
+            if matches!(chars.get(i), Some('?' | '+')) {
+                i += 1;
+            }
+        }
+    }
+    metrics
+}
+
+/// Returns the functions and constructors compiling the pattern of
+/// their first string argument in a language.
+pub fn regex_constructors(lang: LANG) -> &'static [&'static str] {
+    match lang {
+        LANG::Javascript | LANG::Typescript | LANG::Tsx => &["RegExp"],
+        LANG::Python => &[
+            "re.compile",
+            "re.match",
+            "re.search",
+            "re.fullmatch",
+            "re.sub",
+            "re.subn",
+            "re.findall",
+            "re.finditer",
+            "re.split",
+        ],
+        LANG::Rust => &["Regex::new", "RegexBuilder::new"],
+        LANG::Java => &["Pattern.compile", "Pattern.matches"],
+        LANG::Go => &[
+            "regexp.Compile",
+            "regexp.MustCompile",
+            "regexp.CompilePOSIX",
+            "regexp.MustCompilePOSIX",
+            "regexp.MatchString",
+        ],
+        LANG::Csharp => &[
+            "Regex",
+            "Regex.IsMatch",
+            "Regex.Match",
+            "Regex.Matches",
+            "Regex.Replace",
+            "Regex.Split",
+        ],
+        LANG::Cpp => &["std::regex", "std::wregex"],
+        LANG::Elixir => &["Regex.compile", "Regex.compile!"],
+        LANG::Erlang => &["re:compile", "re:run", "re:replace", "re:split"],
+        LANG::Lua | LANG::Gleam => &[],
+    }
+}
+
+/// A regular expression of a code.
+#[derive(Clone, Debug, Serialize)]
+pub struct RegexUsage {
+    /// The pattern
+    pub pattern: String,
+    /// The 1-based line of the pattern
+    pub line: usize,
+    /// The complexity of the pattern
+    pub metrics: RegexMetrics,
+    /// The complexity score of the pattern
+    pub score: f64,
+}
+
+/// The regular expressions of a code.
+#[derive(Clone, Debug, Serialize)]
+pub struct RegexReport {
+    /// The regular expressions, in order of appearance
+    pub regexes: Vec<RegexUsage>,
+    /// The risky regular expressions, reported as smells of the
+    /// [`COMPLEX_REGEX`] and [`CATASTROPHIC_REGEX`] rules
+    #[serde(flatten)]
+    pub report: SmellReport,
+}
+
+fn string_argument<T: ParserTrait>(node: &Node, code: &[u8]) -> Option<String> {
+    let arguments = node
+        .child_by_field_name("arguments")
+        .or_else(|| node.child_by_field_name("args"))
+        .or_else(|| {
+            node.children()
+                .find(|child| matches!(child.kind(), "arguments" | "argument_list"))
+        })?;
+    arguments.children().find_map(|argument| {
+        // C# wraps the arguments
+        let argument = if argument.kind() == "argument" {
+            argument.children().find(|child| child.is_named())?
+        } else {
+            argument
+        };
+        if !T::Checker::is_string(&argument) {
+            return None;
+        }
+        let text = argument.utf8_text(code)?;
+        let content = literal_content(text);
+        // Raw strings don't escape backslashes
+        Some(if text.starts_with(['r', 'R', '`', '@']) {
+            content.to_string()
+        } else {
+            content.replace("\\\\", "\\")
+        })
+    })
+}
+
+fn regex_pattern<T: ParserTrait>(
+    node: &Node,
+    code: &[u8],
+    constructors: &[&str],
+) -> Option<String> {
+    match node.kind() {
+        // `/a+/` in JavaScript
+        "regex" => Some(
+            node.child_by_field_name("pattern")?
+                .utf8_text(code)?
+                .to_string(),
+        ),
+        // `~r/a+/` in Elixir
+        "sigil" => {
+            node.children()
+                .find(|child| child.kind() == "sigil_name")
+                .filter(|name| matches!(name.utf8_text(code), Some("r" | "R")))?;
+            let content = node
+                .children()
+                .find(|child| child.kind() == "quoted_content")?;
+            Some(content.utf8_text(code)?.to_string())
+        }
+        "new_expression" | "object_creation_expression" => {
+            let constructor = node
+                .child_by_field_name("constructor")
+                .or_else(|| node.child_by_field_name("type"))?
+                .utf8_text(code)?
+                .to_string();
+            constructors
+                .iter()
+                .any(|name| matches_api(&constructor, name))
+                .then(|| string_argument::<T>(node, code))?
+        }
+        _ if is_call(node) => {
+            let callee = callee(node, code)?;
+            constructors
+                .iter()
+                .any(|name| matches_api(&callee, name))
+                .then(|| string_argument::<T>(node, code))?
+        }
+        _ => None,
+    }
+}
+
+/// Finds the regular expressions of a parsed code and scores their
+/// complexity.
+///
+/// The regular expressions are the literals of JavaScript and Elixir,
+/// and the first string argument of the [`regex_constructors`] of a
+/// language. A pattern with a score greater than the threshold of the
+/// [`COMPLEX_REGEX`] rule, or with more nested quantifiers than the
+/// threshold of the [`CATASTROPHIC_REGEX`] rule, is reported as a smell.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{find_regexes, ParserTrait, PythonParser, SmellCatalog};
+///
+/// let path = Path::new("foo.py");
+/// let code = b"import re\nre.compile(r\"^(\\w+\\s?)*$\")\n".to_vec();
+/// let parser = PythonParser::new(code, path, None);
+///
+/// let report = find_regexes(&parser, path, &SmellCatalog::default());
+/// assert!(report.regexes[0].metrics.is_catastrophic());
+/// assert_eq!(report.report.smells[0].rule_id, "catastrophic-regex");
+/// ```
+pub fn find_regexes<T: ParserTrait>(
+    parser: &T,
+    path: &Path,
+    catalog: &SmellCatalog,
+) -> RegexReport {
+    let code = parser.get_code();
+    let language = parser.get_language();
+    let constructors = regex_constructors(language);
+    let mut regexes = Vec::new();
+    let mut nodes = Vec::new();
+    let mut stack = vec![parser.get_root()];
+    while let Some(node) = stack.pop() {
+        if let Some(pattern) = regex_pattern::<T>(&node, code, constructors) {
+            let metrics = regex_metrics(&pattern);
+            regexes.push(RegexUsage {
+                score: metrics.score(),
+                line: node.start_row() + 1,
+                pattern,
+                metrics,
+            });
+            nodes.push(node);
+            continue;
+        }
+        let mut children = node.children().collect::<Vec<_>>();
+        children.reverse();
+        stack.extend(children);
+    }
+
+    let file_path = path.to_string_lossy().to_string();
+    let smell = |rule_id: &str, description: String, suggestion: &str, node: &Node| {
+        let rule = catalog.enabled(rule_id)?;
+        let (start_row, start_column) = node.start_position();
+        let (end_row, end_column) = node.end_position();
+        Some(CodeSmell {
+            rule_id: rule.id.clone(),
+            name: rule.name.clone(),
+            description,
+            severity: rule.severity.clone(),
+            location: CodeLocation {
+                file_path: file_path.clone(),
+                line_start: start_row + 1,
+                line_end: end_row + 1,
+                column_start: start_column + 1,
+                column_end: end_column + 1,
+            },
+            suggestion: suggestion.to_string(),
+        })
+    };
+
+    let mut metadata = SmellMetadata::for_catalog(catalog);
+    let mut smells = Vec::new();
+    if let Some(rule) = catalog.enabled(CATASTROPHIC_REGEX) {
+        let threshold = rule.threshold_for(Some(language));
+        let count = smells.len();
+        for (regex, node) in regexes.iter().zip(nodes.iter()) {
+            if regex.metrics.nested_quantifiers as f64 > threshold {
+                smells.extend(smell(
+                    CATASTROPHIC_REGEX,
+                    format!(
+                        "Regex `{}` has {} nested quantifiers and can backtrack catastrophically",
+                        regex.pattern, regex.metrics.nested_quantifiers
+                    ),
+                    "Remove the quantifier of the inner or the outer group, or use atomic groups",
+                    node,
+                ));
+            }
+        }
+        metadata.record(rule, threshold, smells.len() - count);
+    }
+    if let Some(rule) = catalog.enabled(COMPLEX_REGEX) {
+        let threshold = rule.threshold_for(Some(language));
+        let count = smells.len();
+        for (regex, node) in regexes.iter().zip(nodes.iter()) {
+            if regex.score > threshold {
+                smells.extend(smell(
+                    COMPLEX_REGEX,
+                    format!(
+                        "Regex `{}` has a complexity of {:.1}",
+                        regex.pattern, regex.score
+                    ),
+                    "Split the regex, or replace it with a parser",
+                    node,
+                ));
+            }
+        }
+        metadata.record(rule, threshold, smells.len() - count);
+    }
+
+    let (smells, suppressed) = metadata.suppress(smells, code, |smell| smell);
+
+    RegexReport {
+        regexes,
+        report: SmellReport {
+            smells,
+            suppressed,
+            metadata,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{JavascriptParser, ParserEngineRust};
+
+    use super::*;
+
+    #[test]
+    fn regex_complexity() {
+        let metrics = regex_metrics(r"^(?:[a-z\]]+|\d{2,3})(\.\1)?.*x.*$");
+        assert_eq!(metrics.groups, 2);
+        assert_eq!(metrics.nesting, 1);
+        assert_eq!(metrics.quantifiers, 5);
+        assert_eq!(metrics.alternations, 1);
+        assert_eq!(metrics.wildcards, 2);
+        assert_eq!(metrics.backreferences, 1);
+        assert!(!metrics.is_catastrophic());
+
+        assert_eq!(regex_metrics("(a*)*").nested_quantifiers, 1);
+        assert_eq!(regex_metrics("((ab)+c)*").nested_quantifiers, 1);
+        assert_eq!(regex_metrics("(a*?b)").nested_quantifiers, 0);
+        assert_eq!(regex_metrics("a{,").quantifiers, 0);
+    }
+
+    #[test]
+    fn javascript_regexes() {
+        let path = Path::new("foo.js");
+        let code = "const a = /^(a|b)+$/;\n\
+                    const b = new RegExp('^(\\\\d+)*$');\n\
+                    const c = /(x+)+/; // sca-ignore: catastrophic-regex\n";
+        let parser = JavascriptParser::new(code.as_bytes().to_vec(), path, None);
+        let report = find_regexes(&parser, path, &SmellCatalog::default());
+
+        let patterns = report
+            .regexes
+            .iter()
+            .map(|regex| regex.pattern.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(patterns, [r"^(a|b)+$", r"^(\d+)*$", "(x+)+"]);
+        assert_eq!(report.report.smells.len(), 1);
+        assert_eq!(report.report.smells[0].location.line_start, 2);
+        assert_eq!(report.report.suppressed.len(), 1);
+    }
+
+    #[test]
+    fn rust_regexes() {
+        let path = Path::new("foo.rs");
+        let code = "fn f() { let re = Regex::new(r\"(\\w+\\s*)+$\").unwrap(); }\n";
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), path, None);
+        let report = find_regexes(&parser, path, &SmellCatalog::default());
+        assert_eq!(report.regexes.len(), 1);
+        assert_eq!(report.regexes[0].pattern, r"(\w+\s*)+$");
+        assert!(report.regexes[0].metrics.is_catastrophic());
+    }
+}
@@ -0,0 +1,405 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ai::semantic_analyzer::{CodeSmell, Severity},
+    spaces::FuncSpace,
+};
+
+/// The thresholds and weights used to compute the risk of a code.
+///
+/// A function breaches a threshold when its metric is worse than it,
+/// and the breach adds the weight of the metric multiplied by how far
+/// the value is from the threshold, e.g. a cyclomatic complexity of 20
+/// adds twice the cyclomatic weight when the threshold is 10.
+/// Each code smell adds the weight of its severity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RiskPolicy {
+    /// The maximum cyclomatic complexity of a function
+    pub max_cyclomatic: f64,
+    /// The weight of a cyclomatic complexity breach
+    pub cyclomatic_weight: f64,
+    /// The maximum cognitive complexity of a function
+    pub max_cognitive: f64,
+    /// The weight of a cognitive complexity breach
+    pub cognitive_weight: f64,
+    /// The maximum number of source lines of a function
+    pub max_sloc: f64,
+    /// The weight of a source lines breach
+    pub sloc_weight: f64,
+    /// The minimum maintainability index of a function, as computed
+    /// by Visual Studio
+    pub min_mi: f64,
+    /// The weight of a maintainability index breach
+    pub mi_weight: f64,
+    /// The weights of the code smells, from low to critical severity
+    pub severity_weights: [f64; 4],
+}
+
+impl Default for RiskPolicy {
+    fn default() -> Self {
+        Self {
+            max_cyclomatic: 10.,
+            cyclomatic_weight: 1.,
+            max_cognitive: 15.,
+            cognitive_weight: 1.,
+            max_sloc: 50.,
+            sloc_weight: 0.5,
+            min_mi: 20.,
+            mi_weight: 1.,
+            severity_weights: [0.25, 0.5, 1., 2.],
+        }
+    }
+}
+
+impl RiskPolicy {
+    /// Returns the weight of a code smell.
+    pub fn severity_weight(&self, severity: &Severity) -> f64 {
+        match severity {
+            Severity::Low => self.severity_weights[0],
+            Severity::Medium => self.severity_weights[1],
+            Severity::High => self.severity_weights[2],
+            Severity::Critical => self.severity_weights[3],
+        }
+    }
+}
+
+/// A metric of a function worse than the threshold of a [`RiskPolicy`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Breach {
+    /// The name of the metric
+    pub metric: &'static str,
+    /// The value of the metric
+    pub value: f64,
+    /// The threshold breached by the value
+    pub threshold: f64,
+    /// The risk added by the breach
+    pub score: f64,
+}
+
+/// The risk of a file or of a function.
+#[derive(Debug, Clone, Serialize)]
+pub struct RiskItem {
+    /// The path of the file
+    pub path: String,
+    /// The name of the function
+    ///
+    /// It is `None` for files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The first line of the function or file
+    pub start_line: usize,
+    /// The last line of the function or file
+    pub end_line: usize,
+    /// The combined risk
+    pub score: f64,
+    /// The thresholds breached by the function, or by the functions
+    /// of the file
+    pub breaches: Vec<Breach>,
+    /// The number of code smells located in the function or file
+    pub smells: usize,
+}
+
+/// The files and functions of a code ranked by decreasing risk.
+///
+/// Files and functions without any risk are omitted.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RiskReport {
+    /// The risky files
+    pub files: Vec<RiskItem>,
+    /// The risky functions
+    pub functions: Vec<RiskItem>,
+}
+
+/// The data used to compute the risk of a file.
+#[derive(Debug, Clone, Copy)]
+pub struct RiskInput<'a> {
+    /// The path of the file
+    pub path: &'a Path,
+    /// The function spaces of the file
+    pub space: &'a FuncSpace,
+    /// The code smells detected in the file
+    pub smells: &'a [CodeSmell],
+}
+
+fn breach(metric: &'static str, value: f64, threshold: f64, weight: f64) -> Option<Breach> {
+    (value > threshold && threshold > 0.).then(|| Breach {
+        metric,
+        value,
+        threshold,
+        score: weight * value / threshold,
+    })
+}
+
+pub(crate) fn breaches(space: &FuncSpace, policy: &RiskPolicy) -> Vec<Breach> {
+    let metrics = &space.metrics;
+    let mi = metrics.mi.mi_visual_studio();
+    [
+        (
+            "cyclomatic",
+            breach(
+                "cyclomatic",
+                metrics.cyclomatic.cyclomatic_sum(),
+                policy.max_cyclomatic,
+                policy.cyclomatic_weight,
+            ),
+        ),
+        (
+            "cognitive",
+            breach(
+                "cognitive",
+                metrics.cognitive.cognitive_sum(),
+                policy.max_cognitive,
+                policy.cognitive_weight,
+            ),
+        ),
+        (
+            "loc",
+            breach(
+                "sloc",
+                metrics.loc.sloc(),
+                policy.max_sloc,
+                policy.sloc_weight,
+            ),
+        ),
+        // A lower maintainability index is worse
+        (
+            "mi",
+            (mi.is_finite() && mi < policy.min_mi && policy.min_mi > 0.).then(|| Breach {
+                metric: "mi",
+                value: mi,
+                threshold: policy.min_mi,
+                score: policy.mi_weight * (2. - mi / policy.min_mi),
+            }),
+        ),
+    ]
+    .into_iter()
+    .filter(|(id, _)| !space.is_suppressed(id))
+    .filter_map(|(_, breach)| breach)
+    .collect()
+}
+
+// The functions of a space, with whether they are nested in another
+// function
+fn collect_functions<'a>(
+    space: &'a FuncSpace,
+    nested: bool,
+    functions: &mut Vec<(&'a FuncSpace, bool)>,
+) {
+    let callable = space.kind.is_callable();
+    if callable {
+        functions.push((space, nested));
+    }
+    for space in space.spaces.iter() {
+        collect_functions(space, nested || callable, functions);
+    }
+}
+
+/// Ranks the files and functions of a code by decreasing risk.
+///
+/// The risk of a function combines the thresholds of the policy it
+/// breaches and the code smells located in it. The risk of a file is
+/// the sum of the risks of its functions and of the code smells located
+/// outside of them. The metrics of a nested function are already part
+/// of the ones of its parent, as with the default [`NestedAttribution`],
+/// so only its code smells are added to the risk of the file. Metrics
+/// suppressed by a `sca-ignore` comment don't breach any threshold.
+///
+/// [`NestedAttribution`]: crate::NestedAttribution
+pub fn rank_risk(inputs: &[RiskInput], policy: &RiskPolicy) -> RiskReport {
+    let mut report = RiskReport::default();
+
+    for input in inputs {
+        let path = input.path.to_string_lossy().into_owned();
+        let mut functions = Vec::new();
+        collect_functions(input.space, false, &mut functions);
+
+        let mut items = functions
+            .iter()
+            .map(|(space, _)| RiskItem {
+                path: path.clone(),
+                name: space.name.clone(),
+                start_line: space.start_line,
+                end_line: space.end_line,
+                score: 0.,
+                breaches: breaches(space, policy),
+                smells: 0,
+            })
+            .collect::<Vec<_>>();
+        let mut file = RiskItem {
+            path,
+            name: None,
+            start_line: input.space.start_line,
+            end_line: input.space.end_line,
+            score: 0.,
+            breaches: Vec::new(),
+            smells: input.smells.len(),
+        };
+
+        for smell in input.smells {
+            let weight = policy.severity_weight(&smell.severity);
+            let line = smell.location.line_start;
+            // The innermost function containing the smell
+            match items
+                .iter_mut()
+                .filter(|item| item.start_line <= line && line <= item.end_line)
+                .min_by_key(|item| item.end_line - item.start_line)
+            {
+                Some(item) => {
+                    item.smells += 1;
+                    item.score += weight;
+                }
+                None => file.score += weight,
+            }
+        }
+
+        for (item, (_, nested)) in items.iter_mut().zip(functions.iter()) {
+            let smells = item.score;
+            item.score += item.breaches.iter().map(|breach| breach.score).sum::<f64>();
+            if *nested {
+                file.score += smells;
+            } else {
+                file.score += item.score;
+                file.breaches.extend(item.breaches.iter().cloned());
+            }
+        }
+
+        report
+            .functions
+            .extend(items.into_iter().filter(|item| item.score > 0.));
+        if file.score > 0. {
+            report.files.push(file);
+        }
+    }
+
+    report.files.sort_by(|a, b| b.score.total_cmp(&a.score));
+    report.functions.sort_by(|a, b| b.score.total_cmp(&a.score));
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::{ai::semantic_analyzer::CodeLocation, metrics, ParserEngineRust, ParserTrait};
+
+    use super::*;
+
+    fn space(path: &Path, code: &str) -> FuncSpace {
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), path, None);
+        metrics(&parser, path).unwrap()
+    }
+
+    fn smell(line: usize, severity: Severity) -> CodeSmell {
+        CodeSmell {
+            rule_id: "test".to_string(),
+            name: "Test".to_string(),
+            description: String::new(),
+            severity,
+            location: CodeLocation {
+                file_path: String::new(),
+                line_start: line,
+                line_end: line,
+                column_start: 1,
+                column_end: 1,
+            },
+            suggestion: String::new(),
+        }
+    }
+
+    #[test]
+    fn rust_rank_risk() {
+        let policy = RiskPolicy {
+            max_cyclomatic: 2.,
+            ..Default::default()
+        };
+        let a_path = PathBuf::from("a.rs");
+        let a = space(
+            &a_path,
+            "fn simple() {}\n\
+             fn branchy(x: i32) {\n    if x > 0 {}\n    if x > 1 {}\n    if x > 2 {}\n}\n",
+        );
+        let b_path = PathBuf::from("b.rs");
+        let b = space(
+            &b_path,
+            "// sca-ignore-next-line: cc\n\
+             fn ignored(x: i32) {\n    if x > 0 {}\n    if x > 1 {}\n    if x > 2 {}\n}\n\
+             fn smelly() {\n}\n",
+        );
+        let b_smells = [smell(7, Severity::Critical), smell(1, Severity::Low)];
+
+        let report = rank_risk(
+            &[
+                RiskInput {
+                    path: &a_path,
+                    space: &a,
+                    smells: &[],
+                },
+                RiskInput {
+                    path: &b_path,
+                    space: &b,
+                    smells: &b_smells,
+                },
+            ],
+            &policy,
+        );
+
+        let functions = report
+            .functions
+            .iter()
+            .map(|item| (item.name.as_deref().unwrap(), item.score, item.smells))
+            .collect::<Vec<_>>();
+        assert_eq!(functions, [("branchy", 2., 0), ("smelly", 2., 1)]);
+        assert_eq!(report.functions[0].breaches[0].metric, "cyclomatic");
+        assert_eq!(report.functions[0].breaches[0].value, 4.);
+
+        let files = report
+            .files
+            .iter()
+            .map(|item| (item.path.as_str(), item.score, item.smells))
+            .collect::<Vec<_>>();
+        assert_eq!(files, [("b.rs", 2.25, 2), ("a.rs", 2., 0)]);
+    }
+
+    #[test]
+    fn rust_nested_risk() {
+        let policy = RiskPolicy {
+            max_cyclomatic: 2.,
+            ..Default::default()
+        };
+        let path = PathBuf::from("a.rs");
+        let space = space(
+            &path,
+            "fn outer() {\n\
+             \x20   fn inner(x: i32) {\n    if x > 0 {}\n    if x > 1 {}\n    if x > 2 {}\n}\n\
+             }\n",
+        );
+        let smells = [smell(3, Severity::Low)];
+
+        let report = rank_risk(
+            &[RiskInput {
+                path: &path,
+                space: &space,
+                smells: &smells,
+            }],
+            &policy,
+        );
+
+        let outer = report
+            .functions
+            .iter()
+            .find(|item| item.name.as_deref() == Some("outer"))
+            .unwrap();
+        let inner = report
+            .functions
+            .iter()
+            .find(|item| item.name.as_deref() == Some("inner"))
+            .unwrap();
+        assert_eq!(inner.smells, 1);
+        // The breaches of `inner` are counted once, in the ones of `outer`
+        assert_eq!(report.files[0].score, outer.score + 0.25);
+        assert_eq!(report.files[0].breaches.len(), outer.breaches.len());
+    }
+}
@@ -0,0 +1,103 @@
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::fingerprint::fnv1a;
+
+/// A deterministic sample of the files of a repository, to get quick
+/// estimates of the metrics of huge repositories.
+///
+/// A file is sampled according to the hash of its path and of the seed,
+/// so the same files are sampled on every run and on every machine.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::Sampling;
+///
+/// let sampling = Sampling::new(10., 42);
+/// let path = Path::new("src/main.rs");
+/// assert_eq!(sampling.includes(path), sampling.includes(path));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Sampling {
+    /// The share of the files which are sampled, between 0 and 1
+    pub rate: f64,
+    /// The seed of the hash choosing the files
+    pub seed: u64,
+}
+
+// The finalizer of SplitMix64, spreading the bits of the FNV-1a hashes of
+// the short paths over the whole range
+fn mix(mut hash: u64) -> u64 {
+    hash ^= hash >> 30;
+    hash = hash.wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    hash ^= hash >> 27;
+    hash = hash.wrapping_mul(0x94d0_49bb_1331_11eb);
+    hash ^ (hash >> 31)
+}
+
+impl Sampling {
+    /// Creates a sampling of a percentage of the files.
+    pub fn new(percent: f64, seed: u64) -> Self {
+        Self {
+            rate: (percent / 100.).clamp(0., 1.),
+            seed,
+        }
+    }
+
+    /// Returns `true` if a file is sampled.
+    ///
+    /// The path should be relative to the root of the repository, so the
+    /// sample doesn't depend on where the repository is.
+    pub fn includes(&self, path: &Path) -> bool {
+        // The paths are hashed with the same separators on all platforms
+        let path = path.to_string_lossy().replace('\\', "/");
+        let mut bytes = self.seed.to_le_bytes().to_vec();
+        bytes.extend_from_slice(path.as_bytes());
+        self.rate >= 1. || (mix(fnv1a(&bytes)) as f64) < self.rate * u64::MAX as f64
+    }
+
+    /// Returns the factor extrapolating a count over the sampled files to
+    /// all the files.
+    pub fn factor(&self) -> f64 {
+        if self.rate > 0. {
+            1. / self.rate
+        } else {
+            0.
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sampled_files() {
+        let paths = (0..1000)
+            .map(|i| format!("src/module_{i}/file.rs"))
+            .collect::<Vec<_>>();
+        let sampled = |sampling: Sampling| {
+            paths
+                .iter()
+                .filter(|path| sampling.includes(Path::new(path)))
+                .count()
+        };
+
+        let sampling = Sampling::new(20., 7);
+        let count = sampled(sampling);
+        assert!((150..250).contains(&count), "{count} files sampled");
+        assert_eq!(sampled(sampling), count);
+        assert_eq!(sampling.factor(), 5.);
+
+        assert_eq!(sampled(Sampling::new(0., 7)), 0);
+        assert_eq!(sampled(Sampling::new(100., 7)), 1000);
+        assert_eq!(
+            sampling.includes(Path::new("src/a.rs")),
+            sampling.includes(Path::new(r"src\a.rs"))
+        );
+    }
+}
@@ -0,0 +1,391 @@
+use std::{collections::BTreeMap, path::Path};
+
+use serde::Serialize;
+
+use crate::{
+    ai::{CodeLocation, CodeSmell, SmellCatalog, SmellMetadata, SmellReport, DANGEROUS_API},
+    langs::LANG,
+    node::Node,
+    traits::ParserTrait,
+};
+
+/// A security-sensitive API of a language.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct DangerousApi {
+    /// The name of the API, qualified as it is usually written
+    ///
+    /// A call matches when its callee is the name or ends with it after
+    /// a `.`, `::`, `:` or `->`.
+    pub name: &'static str,
+    /// `true` if assigning the API is dangerous, like `innerHTML`,
+    /// rather than calling it
+    pub property: bool,
+    /// The vulnerability the API can lead to
+    pub risk: &'static str,
+}
+
+const fn call(name: &'static str, risk: &'static str) -> DangerousApi {
+    DangerousApi {
+        name,
+        property: false,
+        risk,
+    }
+}
+
+const fn property(name: &'static str, risk: &'static str) -> DangerousApi {
+    DangerousApi {
+        name,
+        property: true,
+        risk,
+    }
+}
+
+const CODE_INJECTION: &str = "code injection";
+const COMMAND_INJECTION: &str = "command injection";
+const DESERIALIZATION: &str = "unsafe deserialization";
+const MEMORY: &str = "memory corruption";
+const XSS: &str = "cross-site scripting";
+
+const JAVASCRIPT: &[DangerousApi] = &[
+    call("eval", CODE_INJECTION),
+    call("document.write", XSS),
+    call("document.writeln", XSS),
+    call("insertAdjacentHTML", XSS),
+    call("child_process.exec", COMMAND_INJECTION),
+    call("execSync", COMMAND_INJECTION),
+    property("innerHTML", XSS),
+    property("outerHTML", XSS),
+];
+
+const PYTHON: &[DangerousApi] = &[
+    call("eval", CODE_INJECTION),
+    call("exec", CODE_INJECTION),
+    call("os.system", COMMAND_INJECTION),
+    call("os.popen", COMMAND_INJECTION),
+    call("subprocess.getoutput", COMMAND_INJECTION),
+    call("pickle.load", DESERIALIZATION),
+    call("pickle.loads", DESERIALIZATION),
+    call("marshal.loads", DESERIALIZATION),
+    call("yaml.load", DESERIALIZATION),
+];
+
+const CPP: &[DangerousApi] = &[
+    call("strcpy", MEMORY),
+    call("strcat", MEMORY),
+    call("sprintf", MEMORY),
+    call("vsprintf", MEMORY),
+    call("gets", MEMORY),
+    call("system", COMMAND_INJECTION),
+    call("popen", COMMAND_INJECTION),
+    call("execl", COMMAND_INJECTION),
+    call("execlp", COMMAND_INJECTION),
+    call("execv", COMMAND_INJECTION),
+    call("execvp", COMMAND_INJECTION),
+];
+
+const RUST: &[DangerousApi] = &[
+    call("transmute", MEMORY),
+    call("from_raw_parts", MEMORY),
+    call("Command::new", COMMAND_INJECTION),
+];
+
+const JAVA: &[DangerousApi] = &[
+    call("getRuntime().exec", COMMAND_INJECTION),
+    call("readObject", DESERIALIZATION),
+];
+
+const GO: &[DangerousApi] = &[
+    call("exec.Command", COMMAND_INJECTION),
+    call("exec.CommandContext", COMMAND_INJECTION),
+    call("unsafe.Pointer", MEMORY),
+    call("template.HTML", XSS),
+];
+
+const CSHARP: &[DangerousApi] = &[
+    call("Process.Start", COMMAND_INJECTION),
+    call("Assembly.Load", CODE_INJECTION),
+];
+
+const LUA: &[DangerousApi] = &[
+    call("load", CODE_INJECTION),
+    call("loadstring", CODE_INJECTION),
+    call("dofile", CODE_INJECTION),
+    call("os.execute", COMMAND_INJECTION),
+    call("io.popen", COMMAND_INJECTION),
+];
+
+const ELIXIR: &[DangerousApi] = &[
+    call("Code.eval_string", CODE_INJECTION),
+    call("Code.eval_quoted", CODE_INJECTION),
+    call(":os.cmd", COMMAND_INJECTION),
+    call(":erlang.binary_to_term", DESERIALIZATION),
+];
+
+const ERLANG: &[DangerousApi] = &[
+    call("os:cmd", COMMAND_INJECTION),
+    call("binary_to_term", DESERIALIZATION),
+];
+
+/// Returns the catalog of security-sensitive APIs of a language.
+pub fn dangerous_apis(lang: LANG) -> &'static [DangerousApi] {
+    match lang {
+        LANG::Javascript | LANG::Typescript | LANG::Tsx => JAVASCRIPT,
+        LANG::Python => PYTHON,
+        LANG::Cpp => CPP,
+        LANG::Rust => RUST,
+        LANG::Java => JAVA,
+        LANG::Go => GO,
+        LANG::Csharp => CSHARP,
+        LANG::Lua => LUA,
+        LANG::Elixir => ELIXIR,
+        LANG::Erlang => ERLANG,
+        LANG::Gleam => &[],
+    }
+}
+
+/// The usages of the security-sensitive APIs of a code.
+#[derive(Clone, Debug, Serialize)]
+pub struct SecurityReport {
+    /// The number of reported usages of each API
+    pub usages: BTreeMap<String, usize>,
+    /// The usages, reported as smells of the [`DANGEROUS_API`] rule
+    #[serde(flatten)]
+    pub report: SmellReport,
+}
+
+// The fields holding the callee of a call in the supported grammars
+const CALLEE_FIELDS: &[&str] = &["function", "name", "target", "expr"];
+
+pub(crate) fn is_call(node: &Node) -> bool {
+    matches!(
+        node.kind(),
+        "call"
+            | "call_expression"
+            | "method_invocation"
+            | "invocation_expression"
+            | "function_call"
+    )
+}
+
+fn is_assignment(node: &Node) -> bool {
+    matches!(
+        node.kind(),
+        "assignment_expression" | "augmented_assignment_expression"
+    )
+}
+
+// Returns the code from the start of a call to the end of its callee,
+// so `a.b().exec` for `a.b().exec(x)` in Java, without whitespaces
+pub(crate) fn callee(node: &Node, code: &[u8]) -> Option<String> {
+    let end = CALLEE_FIELDS
+        .iter()
+        .find_map(|field| {
+            let child = node.child_by_field_name(field)?;
+            // `transmute::<u32, f32>` in Rust
+            Some(
+                child
+                    .child_by_field_name("function")
+                    .map_or(child.end_byte(), |function| function.end_byte()),
+            )
+        })
+        .or_else(|| {
+            node.children()
+                .find(|child| child.is_named())
+                .map(|child| child.end_byte())
+        })?;
+    let text = std::str::from_utf8(code.get(node.start_byte()..end)?).ok()?;
+    Some(text.split_whitespace().collect())
+}
+
+pub(crate) fn matches_api(callee: &str, name: &str) -> bool {
+    callee.strip_suffix(name).is_some_and(|prefix| {
+        prefix.is_empty() || [".", ":", "->"].iter().any(|sep| prefix.ends_with(sep))
+    })
+}
+
+fn find_api(node: &Node, code: &[u8], apis: &[DangerousApi]) -> Option<DangerousApi> {
+    let (target, property) = if is_call(node) {
+        (callee(node, code)?, false)
+    } else if is_assignment(node) {
+        let left = node.child_by_field_name("left")?;
+        let text = left.utf8_text(code)?;
+        (text.split_whitespace().collect(), true)
+    } else {
+        return None;
+    };
+    apis.iter()
+        .find(|api| api.property == property && matches_api(&target, api.name))
+        .copied()
+}
+
+/// Finds the usages of the security-sensitive APIs of a parsed code.
+///
+/// The usages are reported as smells of the [`DANGEROUS_API`] rule of
+/// the catalog, so the rule can be disabled or given another severity,
+/// and a usage can be silenced by a `sca-ignore` comment.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{dangerous_api_usages, ParserTrait, PythonParser, SmellCatalog};
+///
+/// let path = Path::new("foo.py");
+/// let code = b"import os\nos.system(cmd)\n".to_vec();
+/// let parser = PythonParser::new(code, path, None);
+///
+/// let report = dangerous_api_usages(&parser, path, &SmellCatalog::default());
+/// assert_eq!(report.usages["os.system"], 1);
+/// assert_eq!(report.report.smells[0].location.line_start, 2);
+/// ```
+pub fn dangerous_api_usages<T: ParserTrait>(
+    parser: &T,
+    path: &Path,
+    catalog: &SmellCatalog,
+) -> SecurityReport {
+    let mut metadata = SmellMetadata::for_catalog(catalog);
+
+    let Some(rule) = catalog.enabled(DANGEROUS_API) else {
+        return SecurityReport {
+            usages: BTreeMap::new(),
+            report: SmellReport {
+                smells: Vec::new(),
+                suppressed: Vec::new(),
+                metadata,
+            },
+        };
+    };
+
+    let code = parser.get_code();
+    let apis = dangerous_apis(parser.get_language());
+    let file_path = path.to_string_lossy().to_string();
+    let mut found = Vec::new();
+    let mut stack = vec![parser.get_root()];
+    while let Some(node) = stack.pop() {
+        if let Some(api) = find_api(&node, code, apis) {
+            let (start_row, start_column) = node.start_position();
+            let (end_row, end_column) = node.end_position();
+            let smell = CodeSmell {
+                rule_id: rule.id.clone(),
+                name: rule.name.clone(),
+                description: format!("Usage of `{}`", api.name),
+                severity: rule.severity.clone(),
+                location: CodeLocation {
+                    file_path: file_path.clone(),
+                    line_start: start_row + 1,
+                    line_end: end_row + 1,
+                    column_start: start_column + 1,
+                    column_end: end_column + 1,
+                },
+                suggestion: format!(
+                    "`{}` can lead to {}, validate its input or use a safer API",
+                    api.name, api.risk
+                ),
+            };
+            found.push((api.name, smell));
+        }
+        // The children are pushed in reverse to find the usages in order
+        let mut children = node.children().collect::<Vec<_>>();
+        children.reverse();
+        stack.extend(children);
+    }
+
+    metadata.record(
+        rule,
+        rule.threshold_for(Some(parser.get_language())),
+        found.len(),
+    );
+    let (found, suppressed) = metadata.suppress(found, code, |(_, smell)| smell);
+    let mut usages = BTreeMap::new();
+    let mut smells = Vec::new();
+    for (name, smell) in found {
+        *usages.entry(name.to_string()).or_insert(0) += 1;
+        smells.push(smell);
+    }
+    let suppressed = suppressed.into_iter().map(|(_, smell)| smell).collect();
+
+    SecurityReport {
+        usages,
+        report: SmellReport {
+            smells,
+            suppressed,
+            metadata,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ai::{SmellRuleOverride, SmellRules},
+        JavascriptParser, ParserEngineRust, PythonParser,
+    };
+
+    use super::*;
+
+    fn usages<T: ParserTrait>(code: &str, path: &str) -> SecurityReport {
+        let path = Path::new(path);
+        let parser = T::new(code.as_bytes().to_vec(), path, None);
+        dangerous_api_usages(&parser, path, &SmellCatalog::default())
+    }
+
+    #[test]
+    fn python_usages() {
+        let code = "import os, pickle\n\
+                    data = pickle.loads(blob)\n\
+                    os.system(cmd)\n\
+                    os.system(other)  # sca-ignore: dangerous-api\n\
+                    evaluate(x)\n";
+        let report = usages::<PythonParser>(code, "foo.py");
+        assert_eq!(
+            report.usages,
+            BTreeMap::from([
+                ("os.system".to_string(), 1),
+                ("pickle.loads".to_string(), 1)
+            ])
+        );
+        let lines = report
+            .report
+            .smells
+            .iter()
+            .map(|smell| smell.location.line_start)
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [2, 3]);
+        assert_eq!(report.report.suppressed.len(), 1);
+        assert_eq!(report.report.metadata.rules_run[0].suppressed, 1);
+    }
+
+    #[test]
+    fn javascript_usages() {
+        let code = "el.innerHTML = html;\nconst s = el.innerHTML;\nwindow.eval(code);\n";
+        let report = usages::<JavascriptParser>(code, "foo.js");
+        assert_eq!(report.usages["innerHTML"], 1);
+        assert_eq!(report.usages["eval"], 1);
+    }
+
+    #[test]
+    fn disabled_rule() {
+        let rules = SmellRules::from([(
+            DANGEROUS_API.to_string(),
+            SmellRuleOverride {
+                enabled: Some(false),
+                ..Default::default()
+            },
+        )]);
+        let catalog = SmellCatalog::with_rules(&rules).unwrap();
+        let path = Path::new("foo.rs");
+        let code = b"fn f() { unsafe { std::mem::transmute::<u32, f32>(1) }; }\n".to_vec();
+        let parser = ParserEngineRust::new(code.clone(), path, None);
+        assert!(dangerous_api_usages(&parser, path, &catalog)
+            .report
+            .smells
+            .is_empty());
+
+        let report = dangerous_api_usages(&parser, path, &SmellCatalog::default());
+        assert_eq!(
+            report.usages,
+            BTreeMap::from([("transmute".to_string(), 1)])
+        );
+    }
+}
@@ -0,0 +1,363 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use serde::Serialize;
+use walkdir::WalkDir;
+
+use crate::{
+    code_analyzer::{AnalyzerError, AnalyzerResult, SingularityCodeAnalyzer},
+    spaces::{FuncSpace, SpaceKind},
+    third_party::is_third_party_dir,
+};
+
+/// A project root of a [`Session`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SessionRoot {
+    /// The name of the root, like `app` or `libfoo`
+    pub name: String,
+    /// The directory of the root
+    pub path: PathBuf,
+}
+
+/// A named function space of a file of a [`Session`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Symbol {
+    /// The name of the space
+    pub name: String,
+    /// The kind of the space
+    pub kind: SpaceKind,
+    /// The file of the space
+    pub path: PathBuf,
+    /// The first line of the space
+    pub start_line: usize,
+    /// The last line of the space
+    pub end_line: usize,
+}
+
+// The results of a file, with the state of the file they were computed
+// from
+#[derive(Debug)]
+struct CachedFile {
+    modified: Option<SystemTime>,
+    len: u64,
+    result: AnalyzerResult,
+}
+
+impl CachedFile {
+    fn is_fresh(&self, metadata: &fs::Metadata) -> bool {
+        self.len == metadata.len() && self.modified == metadata.modified().ok()
+    }
+}
+
+/// An analysis session over several project roots, like an application
+/// and the libraries it uses.
+///
+/// The roots share the analyzer, and so its parsers, the results of
+/// their files and the index of their symbols, so a file in two roots
+/// is analyzed once and the queries span all the roots. A refresh only
+/// analyzes again the files which changed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::Session;
+///
+/// let mut session = Session::new();
+/// session.add_root("app", Path::new("app"))?;
+/// session.add_root("vendor", Path::new("third_party/libfoo"))?;
+///
+/// for symbol in session.symbols("parse_config") {
+///     let root = session.root_of(&symbol.path).unwrap();
+///     println!("{}: {}:{}", root.name, symbol.path.display(), symbol.start_line);
+/// }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Default)]
+pub struct Session {
+    analyzer: SingularityCodeAnalyzer,
+    roots: Vec<SessionRoot>,
+    files: BTreeMap<PathBuf, CachedFile>,
+    symbols: BTreeMap<String, Vec<Symbol>>,
+}
+
+impl Session {
+    /// Creates a session without any root.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a session analyzing the files with an analyzer.
+    pub fn with_analyzer(analyzer: SingularityCodeAnalyzer) -> Self {
+        Self {
+            analyzer,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the roots, in the order they were added.
+    pub fn roots(&self) -> &[SessionRoot] {
+        &self.roots
+    }
+
+    /// Adds a project root, analyzing its files in the supported
+    /// languages and skipping the hidden and the third-party
+    /// directories.
+    ///
+    /// Returns the number of files analyzed, which excludes the files
+    /// already analyzed for another root.
+    pub fn add_root(&mut self, name: &str, path: &Path) -> io::Result<usize> {
+        if self.roots.iter().any(|root| root.name == name) {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("the session already has a root named `{name}`"),
+            ));
+        }
+        self.roots.push(SessionRoot {
+            name: name.to_string(),
+            path: path.to_path_buf(),
+        });
+        let mut analyzed = 0;
+        for file in root_files(path)? {
+            if self.update(&file)? {
+                analyzed += 1;
+            }
+        }
+        Ok(analyzed)
+    }
+
+    /// Removes a project root, with the results of the files which
+    /// aren't in any other root.
+    ///
+    /// Returns `false` if the session has no root with this name.
+    pub fn remove_root(&mut self, name: &str) -> bool {
+        let Some(index) = self.roots.iter().position(|root| root.name == name) else {
+            return false;
+        };
+        self.roots.remove(index);
+        let removed = self
+            .files
+            .keys()
+            .filter(|path| self.root_of(path).is_none())
+            .cloned()
+            .collect::<Vec<_>>();
+        for path in removed {
+            self.forget(&path);
+        }
+        true
+    }
+
+    /// Analyzes again the files of the roots which changed since they
+    /// were analyzed, and forgets the deleted files.
+    ///
+    /// Returns the number of files analyzed.
+    pub fn refresh(&mut self) -> io::Result<usize> {
+        let mut files = Vec::new();
+        for root in &self.roots {
+            files.extend(root_files(&root.path)?);
+        }
+        let deleted = self
+            .files
+            .keys()
+            .filter(|path| !files.contains(path))
+            .cloned()
+            .collect::<Vec<_>>();
+        for path in deleted {
+            self.forget(&path);
+        }
+        let mut analyzed = 0;
+        for file in files {
+            if self.update(&file)? {
+                analyzed += 1;
+            }
+        }
+        Ok(analyzed)
+    }
+
+    /// Returns the most specific root containing a path.
+    pub fn root_of(&self, path: &Path) -> Option<&SessionRoot> {
+        self.roots
+            .iter()
+            .filter(|root| path.starts_with(&root.path))
+            .max_by_key(|root| root.path.components().count())
+    }
+
+    /// Returns the results of an analyzed file.
+    pub fn file(&self, path: &Path) -> Option<&AnalyzerResult> {
+        self.files.get(path).map(|file| &file.result)
+    }
+
+    /// Returns the results of the analyzed files of all the roots, by
+    /// path.
+    pub fn files(&self) -> impl Iterator<Item = (&Path, &AnalyzerResult)> {
+        self.files
+            .iter()
+            .map(|(path, file)| (path.as_path(), &file.result))
+    }
+
+    /// Returns the number of analyzed files.
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Returns `true` if no file is analyzed.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Returns the symbols of all the roots with a name.
+    pub fn symbols(&self, name: &str) -> &[Symbol] {
+        self.symbols.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the symbols of all the roots whose name starts with a
+    /// prefix, by name.
+    pub fn symbols_with_prefix<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'a Symbol> {
+        self.symbols
+            .range(prefix.to_string()..)
+            .take_while(move |(name, _)| name.starts_with(prefix))
+            .flat_map(|(_, symbols)| symbols)
+    }
+
+    // Analyzes a file unless its results are fresh, returning `true` if
+    // it was analyzed
+    fn update(&mut self, path: &Path) -> io::Result<bool> {
+        let metadata = fs::metadata(path)?;
+        if self
+            .files
+            .get(path)
+            .is_some_and(|file| file.is_fresh(&metadata))
+        {
+            return Ok(false);
+        }
+        self.forget(path);
+        let result = match self.analyzer.analyze_file(path) {
+            Ok(result) => result,
+            Err(AnalyzerError::Io(err)) => return Err(err),
+            // The files in an unsupported language, or which can't be
+            // decoded or analyzed, have no results
+            Err(_) => return Ok(false),
+        };
+        let mut symbols = Vec::new();
+        collect_symbols(&result.root_space, path, &mut symbols);
+        for symbol in symbols {
+            self.symbols
+                .entry(symbol.name.clone())
+                .or_default()
+                .push(symbol);
+        }
+        self.files.insert(
+            path.to_path_buf(),
+            CachedFile {
+                modified: metadata.modified().ok(),
+                len: metadata.len(),
+                result,
+            },
+        );
+        Ok(true)
+    }
+
+    fn forget(&mut self, path: &Path) {
+        if self.files.remove(path).is_none() {
+            return;
+        }
+        self.symbols.retain(|_, symbols| {
+            symbols.retain(|symbol| symbol.path != path);
+            !symbols.is_empty()
+        });
+    }
+}
+
+// Returns the files of a root, skipping the hidden and the third-party
+// directories
+fn root_files(root: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0
+                || !(entry.file_name().to_string_lossy().starts_with('.')
+                    || entry.file_type().is_dir() && is_third_party_dir(entry.path()))
+        })
+    {
+        let entry = entry.map_err(io::Error::other)?;
+        if entry.file_type().is_file() {
+            files.push(entry.into_path());
+        }
+    }
+    Ok(files)
+}
+
+fn collect_symbols(space: &FuncSpace, path: &Path, symbols: &mut Vec<Symbol>) {
+    for space in &space.spaces {
+        if let Some(name) = &space.name {
+            symbols.push(Symbol {
+                name: name.clone(),
+                kind: space.kind,
+                path: path.to_path_buf(),
+                start_line: space.start_line,
+                end_line: space.end_line,
+            });
+        }
+        collect_symbols(space, path, symbols);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multi_root_session() {
+        let dir = std::env::temp_dir().join(format!("sca-session-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let app = dir.join("app");
+        let lib = dir.join("lib");
+        fs::create_dir_all(app.join("vendor")).unwrap();
+        fs::create_dir_all(&lib).unwrap();
+        fs::write(app.join("main.rs"), "fn main() { parse_config(); }\n").unwrap();
+        fs::write(app.join("vendor").join("dep.rs"), "fn dep() {}\n").unwrap();
+        fs::write(app.join("notes.txt"), "parse_config\n").unwrap();
+        fs::write(lib.join("config.py"), "def parse_config():\n    pass\n").unwrap();
+
+        let mut session = Session::new();
+        assert_eq!(session.add_root("app", &app).unwrap(), 1);
+        assert_eq!(session.add_root("lib", &lib).unwrap(), 1);
+        assert!(session.add_root("lib", &lib).is_err());
+        assert_eq!(session.len(), 2);
+
+        let symbols = session.symbols("parse_config");
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(session.root_of(&symbols[0].path).unwrap().name, "lib");
+        assert_eq!(
+            session
+                .symbols_with_prefix("ma")
+                .map(|symbol| symbol.name.as_str())
+                .collect::<Vec<_>>(),
+            ["main"]
+        );
+
+        // Only the changed file is analyzed again
+        assert_eq!(session.refresh().unwrap(), 0);
+        fs::write(
+            lib.join("config.py"),
+            "def parse_config():\n    pass\n\ndef load_config():\n    pass\n",
+        )
+        .unwrap();
+        assert_eq!(session.refresh().unwrap(), 1);
+        assert_eq!(session.symbols("load_config").len(), 1);
+        assert_eq!(session.symbols("parse_config").len(), 1);
+
+        assert!(session.remove_root("lib"));
+        assert!(!session.remove_root("lib"));
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(session.symbols("parse_config").is_empty());
+        assert_eq!(session.len(), 1);
+    }
+}
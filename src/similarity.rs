@@ -0,0 +1,349 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+
+use crate::{
+    checker::Checker,
+    fingerprint::{fingerprint, fnv1a},
+    getter::Getter,
+    langs::{action, LANG},
+    node::Node,
+    tools::{guess_language, read_file},
+    traits::{Callback, ParserTrait},
+};
+
+// The number of dimensions of the structural vectors
+pub(crate) const DIMENSIONS: usize = 256;
+
+// Returns `true` if the text of a leaf is specific to a code rather than
+// to its structure, like a name or a literal
+fn is_specific(node: &Node) -> bool {
+    let kind = node.kind();
+    [
+        "identifier",
+        "literal",
+        "string",
+        "integer",
+        "float",
+        "number",
+        "comment",
+    ]
+    .iter()
+    .any(|specific| kind.contains(specific))
+}
+
+// Computes the structural vector of a node: the counts of the kinds of
+// its descendants, of the kinds of their parents and grandparents, and
+// of the operators and keywords, hashed into a fixed number of
+// dimensions and normalized
+pub(crate) fn structural_vector<T: ParserTrait>(
+    node: &Node,
+    code: &[u8],
+    include_root: bool,
+) -> Vec<f32> {
+    let mut vector = vec![0f32; DIMENSIONS];
+    let mut add = |feature: &str| {
+        vector[(fnv1a(feature.as_bytes()) % DIMENSIONS as u64) as usize] += 1.;
+    };
+    let mut stack = if include_root {
+        vec![(*node, "", "")]
+    } else {
+        node.children().map(|child| (child, "", "")).collect()
+    };
+    while let Some((node, parent, grandparent)) = stack.pop() {
+        if T::Checker::is_comment(&node) {
+            continue;
+        }
+        let kind = node.kind();
+        add(kind);
+        add(&format!("{parent}>{kind}"));
+        add(&format!("{grandparent}>{parent}>{kind}"));
+        if node.child_count() == 0 && !is_specific(&node) {
+            if let Some(text) = node.utf8_text(code) {
+                add(&format!("'{text}"));
+            }
+        }
+        stack.extend(node.children().map(|child| (child, kind, parent)));
+    }
+
+    let norm = vector.iter().map(|value| value * value).sum::<f32>().sqrt();
+    if norm > 0. {
+        vector.iter_mut().for_each(|value| *value /= norm);
+    }
+    vector
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f64 {
+    // The vectors are normalized
+    a.iter().zip(b).map(|(a, b)| f64::from(a * b)).sum()
+}
+
+/// A function of the index.
+#[derive(Clone, Debug, Serialize)]
+pub struct IndexedFunction {
+    /// The path of the file of the function
+    pub path: PathBuf,
+    /// The language of the function
+    #[serde(skip)]
+    pub language: LANG,
+    /// The name of the function
+    pub name: Option<String>,
+    /// The first line of the function
+    pub start_line: usize,
+    /// The last line of the function
+    pub end_line: usize,
+    /// The fingerprint of the function, as computed for the function
+    /// spaces
+    pub fingerprint: String,
+    #[serde(skip)]
+    vector: Vec<f32>,
+}
+
+/// A function similar to a snippet.
+#[derive(Clone, Debug, Serialize)]
+pub struct SimilarFunction {
+    /// The function
+    #[serde(flatten)]
+    pub function: IndexedFunction,
+    /// The similarity of the function to the snippet, between 0 and 1
+    pub score: f64,
+    /// Whether the function has the same fingerprint as the snippet, so
+    /// it's the same code but for the names of the function, the
+    /// whitespace and the comments
+    pub exact: bool,
+}
+
+fn index_functions<T: ParserTrait>(parser: &T, path: &Path) -> Vec<IndexedFunction> {
+    let code = parser.get_code();
+    let mut functions = Vec::new();
+    let mut stack = vec![parser.get_root()];
+    while let Some(node) = stack.pop() {
+        if T::Checker::is_func(&node) {
+            functions.push(IndexedFunction {
+                path: path.to_path_buf(),
+                language: parser.get_language(),
+                name: T::Getter::get_func_name(&node, code).map(|name| name.to_string()),
+                start_line: node.start_row() + 1,
+                end_line: node.end_row() + 1,
+                fingerprint: fingerprint::<T::Checker>(&node, code),
+                vector: structural_vector::<T>(&node, code, true),
+            });
+        }
+        let mut children = node.children().collect::<Vec<_>>();
+        children.reverse();
+        stack.extend(children);
+    }
+    functions
+}
+
+// The fingerprint and the vector of a snippet: the ones of its function
+// when it's a single function, else the ones of its statements
+fn snippet_vector<T: ParserTrait>(parser: &T) -> (Option<String>, Vec<f32>) {
+    let code = parser.get_code();
+    let root = parser.get_root();
+    let mut parts = root
+        .children()
+        .filter(|child| child.is_named() && !T::Checker::is_comment(child));
+    match (parts.next(), parts.next()) {
+        (Some(function), None) if T::Checker::is_func(&function) => (
+            Some(fingerprint::<T::Checker>(&function, code)),
+            structural_vector::<T>(&function, code, true),
+        ),
+        _ => (None, structural_vector::<T>(&root, code, false)),
+    }
+}
+
+struct IndexFunctions {
+    _guard: (),
+}
+
+impl Callback for IndexFunctions {
+    type Res = Vec<IndexedFunction>;
+    type Cfg = PathBuf;
+
+    fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
+        index_functions(parser, &cfg)
+    }
+}
+
+struct SnippetVector {
+    _guard: (),
+}
+
+impl Callback for SnippetVector {
+    type Res = (Option<String>, Vec<f32>);
+    type Cfg = ();
+
+    fn call<T: ParserTrait>(_cfg: Self::Cfg, parser: &T) -> Self::Res {
+        snippet_vector(parser)
+    }
+}
+
+/// An index of the functions of a project, to find the functions
+/// similar to a snippet.
+///
+/// Each function is indexed with its fingerprint, which finds the
+/// copies of a snippet, and with a structural vector counting the kinds
+/// of its syntax nodes, their nesting and its operators and keywords,
+/// which finds the functions with the same shape, whatever their names
+/// and literals.
+#[derive(Clone, Debug, Default)]
+pub struct SimilarityIndex {
+    functions: Vec<IndexedFunction>,
+}
+
+impl SimilarityIndex {
+    /// Creates an empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Indexes the functions of a parsed code.
+    pub fn add<T: ParserTrait>(&mut self, parser: &T, path: &Path) {
+        self.functions.extend(index_functions(parser, path));
+    }
+
+    /// Indexes the functions of a file, returning `false` if its language
+    /// isn't supported.
+    pub fn add_file(&mut self, path: &Path) -> io::Result<bool> {
+        let source = read_file(path)?;
+        let Some(language) = guess_language(&source, path).0 else {
+            return Ok(false);
+        };
+        self.functions.extend(action::<IndexFunctions>(
+            &language,
+            source,
+            path,
+            None,
+            path.to_path_buf(),
+        ));
+        Ok(true)
+    }
+
+    /// Returns the indexed functions.
+    pub fn functions(&self) -> &[IndexedFunction] {
+        &self.functions
+    }
+
+    /// Returns the number of indexed functions.
+    pub fn len(&self) -> usize {
+        self.functions.len()
+    }
+
+    /// Returns `true` if no function is indexed.
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty()
+    }
+
+    /// Returns the `limit` indexed functions of a language most similar
+    /// to a snippet of that language, the most similar first.
+    ///
+    /// The snippet is either a function or some statements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// use singularity_code_analysis::{ParserTrait, PythonParser, SimilarityIndex, LANG};
+    ///
+    /// let code = "def total(items):\n    s = 0\n    for i in items:\n        s += i\n    return s\n\ndef greet(name):\n    print('Hello ' + name)\n";
+    /// let path = Path::new("foo.py");
+    /// let parser = PythonParser::new(code.as_bytes().to_vec(), path, None);
+    ///
+    /// let mut index = SimilarityIndex::new();
+    /// index.add(&parser, path);
+    ///
+    /// // The same function with another name
+    /// let snippet = "def add_up(items):\n    s = 0\n    for i in items:\n        s += i\n    return s\n";
+    /// let similar = index.search(snippet, LANG::Python, 1);
+    /// assert_eq!(similar[0].function.name.as_deref(), Some("total"));
+    /// assert!(similar[0].exact);
+    /// ```
+    pub fn search(&self, snippet: &str, language: LANG, limit: usize) -> Vec<SimilarFunction> {
+        let (fingerprint, vector) = action::<SnippetVector>(
+            &language,
+            snippet.as_bytes().to_vec(),
+            Path::new(""),
+            None,
+            (),
+        );
+        let mut similar = self
+            .functions
+            .iter()
+            .filter(|function| function.language == language)
+            .map(|function| {
+                let exact = fingerprint.as_ref() == Some(&function.fingerprint);
+                SimilarFunction {
+                    function: function.clone(),
+                    score: if exact {
+                        1.
+                    } else {
+                        cosine(&vector, &function.vector)
+                    },
+                    exact,
+                }
+            })
+            .filter(|similar| similar.score > 0.)
+            .collect::<Vec<_>>();
+        similar.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then(a.function.path.cmp(&b.function.path))
+                .then(a.function.start_line.cmp(&b.function.start_line))
+        });
+        similar.truncate(limit);
+        similar
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ParserEngineRust;
+
+    use super::*;
+
+    #[test]
+    fn rust_similarity() {
+        let code = "fn sum(v: &[i32]) -> i32 {
+    let mut s = 0;
+    for x in v {
+        if *x > 0 {
+            s += x;
+        }
+    }
+    s
+}
+
+fn name(p: &Person) -> String {
+    format!(\"{} {}\", p.first, p.last)
+}
+";
+        let path = Path::new("foo.rs");
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), path, None);
+        let mut index = SimilarityIndex::new();
+        index.add(&parser, path);
+        assert_eq!(index.len(), 2);
+
+        // The same loop, with other names and a comment
+        let snippet = "let mut total = 0; // the total
+for item in items {
+    if *item > 0 {
+        total += item;
+    }
+}
+";
+        let similar = index.search(snippet, LANG::Rust, 2);
+        assert_eq!(similar.len(), 2);
+        assert_eq!(similar[0].function.name.as_deref(), Some("sum"));
+        assert_eq!(similar[0].function.start_line, 1);
+        assert!(!similar[0].exact);
+        assert!(similar[0].score > similar[1].score);
+
+        // The other languages aren't searched
+        assert!(index.search(snippet, LANG::Python, 2).is_empty());
+    }
+}
@@ -1,10 +1,12 @@
+#![cfg_attr(not(test), deny(clippy::unwrap_used))]
+
 use std::{
     collections::HashMap,
     fmt,
     path::{Path, PathBuf},
 };
 
-use serde::Serialize;
+use serde::{ser::SerializeStruct, Serialize, Serializer};
 
 use crate::{
     abc::{self, Abc},
@@ -14,16 +16,29 @@ use crate::{
     dump_metrics::*,
     enter_code_context,
     exit::{self, Exit},
+    extras::{self, Extras},
+    fingerprint::fingerprint,
+    generics::{self, Generics},
     getter::Getter,
     halstead::{self, Halstead, HalsteadMaps},
-    loc::{self, Loc},
+    halstead_table::halstead_table,
+    langs::{CppParser, LANG},
+    loc,
+    macros::{trace_event, trace_span},
     mi::{self, Mi},
+    mi_grade::MiBands,
     nargs::{self, NArgs},
     node::Node,
     nom::{self, Nom},
     npa::{self, Npa},
     npm::{self, Npm},
+    path_base::PathBase,
+    position::{OffsetEncoding, Snippet, SourceMap},
+    suppression::{suppresses, Suppressions},
+    syntax_error::{parse_errors, ParseError},
+    third_party::is_third_party,
     traits::*,
+    unsafety::{self, Unsafety},
     wmc::{self, Wmc},
 };
 
@@ -36,9 +51,13 @@ pub enum SpaceKind {
     Unknown,
     /// A function space
     Function,
+    /// A closure, lambda or anonymous function
+    Closure,
     /// A class space
     Class,
-    /// A struct space
+    /// A struct space, like a `C/C++` or `C#` struct or a `Rust` struct,
+    /// enum or union, while the enums and the records of `Java` are
+    /// classes
     Struct,
     /// A `Rust` trait space
     Trait,
@@ -50,6 +69,83 @@ pub enum SpaceKind {
     Namespace,
     /// An interface
     Interface,
+    /// A module, like a `Rust` inline module or an `Elixir` module
+    Module,
+}
+
+impl SpaceKind {
+    /// Returns `true` if the space is a function or a closure.
+    pub fn is_callable(&self) -> bool {
+        matches!(self, SpaceKind::Function | SpaceKind::Closure)
+    }
+
+    fn is_class(&self) -> bool {
+        matches!(
+            self,
+            SpaceKind::Class | SpaceKind::Struct | SpaceKind::Interface
+        )
+    }
+}
+
+/// How the metrics of nested spaces are attributed.
+///
+/// A space is nested when it's defined in a function or a closure, or
+/// when it's a class defined in another class.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NestedAttribution {
+    /// The metrics of a nested space are reported both in its own space
+    /// and in the aggregate of its parent
+    #[default]
+    DoubleReported,
+    /// The metrics of a nested space are only included in the aggregate
+    /// of its parent, which doesn't list it as a subspace
+    Included,
+    /// The metrics of a nested space are only reported in its own space
+    Excluded,
+}
+
+/// The deepest function spaces which are listed.
+///
+/// The metrics of a deeper space are only included in the aggregate of
+/// its parent, which doesn't list it as a subspace, so a coarser
+/// granularity shrinks the output of big files.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpaceGranularity {
+    /// Only the space of the file
+    File,
+    /// The classes, structs, traits, implementations, namespaces,
+    /// interfaces and modules
+    Class,
+    /// The functions, and the classes
+    Function,
+    /// All the spaces, closures included
+    #[default]
+    Closure,
+}
+
+impl SpaceGranularity {
+    /// Returns `true` if the spaces of a kind are listed.
+    pub fn includes(self, kind: SpaceKind) -> bool {
+        let granularity = match kind {
+            SpaceKind::Unit => Self::File,
+            SpaceKind::Class
+            | SpaceKind::Struct
+            | SpaceKind::Trait
+            | SpaceKind::Impl
+            | SpaceKind::Namespace
+            | SpaceKind::Interface
+            | SpaceKind::Module => Self::Class,
+            SpaceKind::Function | SpaceKind::Unknown => Self::Function,
+            SpaceKind::Closure => Self::Closure,
+        };
+        granularity <= self
+    }
+}
+
+fn is_nested(parent: SpaceKind, child: SpaceKind) -> bool {
+    parent.is_callable() || (parent.is_class() && child.is_class())
 }
 
 impl fmt::Display for SpaceKind {
@@ -57,6 +153,7 @@ impl fmt::Display for SpaceKind {
         let s = match self {
             SpaceKind::Unknown => "unknown",
             SpaceKind::Function => "function",
+            SpaceKind::Closure => "closure",
             SpaceKind::Class => "class",
             SpaceKind::Struct => "struct",
             SpaceKind::Trait => "trait",
@@ -64,13 +161,17 @@ impl fmt::Display for SpaceKind {
             SpaceKind::Unit => "unit",
             SpaceKind::Namespace => "namespace",
             SpaceKind::Interface => "interface",
+            SpaceKind::Module => "module",
         };
         write!(f, "{s}")
     }
 }
 
 /// All metrics data.
-#[derive(Default, Debug, Clone, Serialize)]
+///
+/// The metrics which don't apply to the language of a code are omitted
+/// from the serialized metrics, and the suppressed ones are null.
+#[derive(Default, Debug, Clone)]
 pub struct CodeMetrics {
     /// `NArgs` data
     pub nargs: nargs::Stats,
@@ -89,15 +190,47 @@ pub struct CodeMetrics {
     pub mi: mi::Stats,
     /// `Abc` data
     pub abc: abc::Stats,
+    /// `Generics` data
+    pub generics: generics::Stats,
     /// `Wmc` data
-    #[serde(skip_serializing_if = "wmc::Stats::is_disabled")]
     pub wmc: wmc::Stats,
     /// `Npm` data
-    #[serde(skip_serializing_if = "npm::Stats::is_disabled")]
     pub npm: npm::Stats,
     /// `Npa` data
-    #[serde(skip_serializing_if = "npa::Stats::is_disabled")]
     pub npa: npa::Stats,
+    /// `Unsafety` data
+    pub unsafety: unsafety::Stats,
+    /// Language-specific data
+    pub extras: extras::Stats,
+    /// The ids of the metrics suppressed by a `sca-ignore` comment on the
+    /// first line of their function space, or which weren't computed
+    pub suppressed: Vec<String>,
+}
+
+impl Serialize for CodeMetrics {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let enabled = self.enabled_metrics();
+        let mut state = serializer.serialize_struct("CodeMetrics", enabled.len())?;
+        macro_rules! serialize_metrics {
+            ($($metric:ident),*) => {
+                $(
+                    let name = stringify!($metric);
+                    if !enabled.contains(&name) {
+                        state.skip_field(name)?;
+                    } else if self.is_suppressed(name) {
+                        state.serialize_field(name, &None::<()>)?;
+                    } else {
+                        state.serialize_field(name, &self.$metric)?;
+                    }
+                )*
+            };
+        }
+        serialize_metrics!(
+            nargs, nexits, cognitive, cyclomatic, halstead, loc, nom, mi, abc, generics, wmc, npm,
+            npa, unsafety, extras
+        );
+        state.end()
+    }
 }
 
 impl fmt::Display for CodeMetrics {
@@ -114,6 +247,11 @@ impl fmt::Display for CodeMetrics {
 }
 
 impl CodeMetrics {
+    /// Returns `true` if the metric with the given id is suppressed.
+    pub fn is_suppressed(&self, id: &str) -> bool {
+        suppresses(&self.suppressed, id)
+    }
+
     pub fn merge(&mut self, other: &CodeMetrics) {
         self.cognitive.merge(&other.cognitive);
         self.cyclomatic.merge(&other.cyclomatic);
@@ -124,9 +262,64 @@ impl CodeMetrics {
         self.nargs.merge(&other.nargs);
         self.nexits.merge(&other.nexits);
         self.abc.merge(&other.abc);
+        self.generics.merge(&other.generics);
         self.wmc.merge(&other.wmc);
         self.npm.merge(&other.npm);
         self.npa.merge(&other.npa);
+        self.unsafety.merge(&other.unsafety);
+        self.extras.merge(&other.extras);
+    }
+
+    /// Returns the names of all the metrics, as serialized, including the
+    /// ones which only apply to some languages or spaces.
+    pub fn all_metrics() -> &'static [&'static str] {
+        &[
+            "nargs",
+            "nexits",
+            "cognitive",
+            "cyclomatic",
+            "halstead",
+            "loc",
+            "nom",
+            "mi",
+            "abc",
+            "generics",
+            "wmc",
+            "npm",
+            "npa",
+            "unsafety",
+            "extras",
+        ]
+    }
+
+    /// Returns the names of the computed metrics, as serialized.
+    ///
+    /// The metrics which don't apply to the language of a code are omitted.
+    pub fn enabled_metrics(&self) -> Vec<&'static str> {
+        let mut metrics = vec![
+            "nargs",
+            "nexits",
+            "cognitive",
+            "cyclomatic",
+            "halstead",
+            "loc",
+            "nom",
+            "mi",
+            "abc",
+            "generics",
+        ];
+        for (name, disabled) in [
+            ("wmc", self.wmc.is_disabled()),
+            ("npm", self.npm.is_disabled()),
+            ("npa", self.npa.is_disabled()),
+            ("unsafety", self.unsafety.is_disabled()),
+            ("extras", self.extras.is_disabled()),
+        ] {
+            if !disabled {
+                metrics.push(name);
+            }
+        }
+        metrics
     }
 }
 
@@ -142,6 +335,32 @@ pub struct FuncSpace {
     pub start_line: usize,
     /// The last line of a function space
     pub end_line: usize,
+    /// The column of the first character of a function space
+    pub start_column: usize,
+    /// The column following the last character of a function space
+    pub end_column: usize,
+    /// The hash of the normalized code of a function space
+    ///
+    /// It ignores whitespace, comments and the name of the space,
+    /// so it identifies a function across renames and moves
+    pub fingerprint: String,
+    /// The source code of a function space
+    ///
+    /// It is only filled in by [`FuncSpace::set_snippets`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<Snippet>,
+    /// The syntax errors of the code, only filled in for the root space
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub parse_errors: Vec<ParseError>,
+    /// The grade of the maintainability index of a function space
+    ///
+    /// It is only filled in by [`FuncSpace::set_mi_grades`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mi_grade: Option<String>,
+    /// Whether the file is vendored code, a dependency or a git
+    /// submodule, only filled in for the root space
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub third_party: bool,
     /// The space kind
     pub kind: SpaceKind,
     /// All subspaces contained in a function space
@@ -162,6 +381,15 @@ impl FuncSpace {
             }
             _ => (node.start_row() + 1, node.end_row() + 1),
         };
+        let (start_column, end_column) = match node.end_position() {
+            _ if start_position == 0 => (0, 0),
+            // A unit ending with a newline ends on the previous line
+            (_, 0) if kind == SpaceKind::Unit => (
+                node.start_position().1 + 1,
+                SourceMap::new(code).line(end_position).len() + 1,
+            ),
+            (_, column) => (node.start_position().1 + 1, column + 1),
+        };
 
         Self {
             name: T::get_func_space_name(node, code)
@@ -171,6 +399,77 @@ impl FuncSpace {
             kind,
             start_line: start_position,
             end_line: end_position,
+            start_column,
+            end_column,
+            fingerprint: String::new(),
+            snippet: None,
+            parse_errors: Vec::new(),
+            mi_grade: None,
+            third_party: false,
+        }
+    }
+
+    /// Returns `true` if the metric with the given id is suppressed
+    /// for a function space.
+    pub fn is_suppressed(&self, id: &str) -> bool {
+        self.metrics.is_suppressed(id)
+    }
+
+    /// Records the metrics suppressed for a function space and for
+    /// all its subspaces.
+    ///
+    /// Unit spaces are never suppressed, since their first line
+    /// belongs to the code they contain.
+    pub fn set_suppressions(&mut self, suppressions: &Suppressions) {
+        if self.kind != SpaceKind::Unit {
+            self.metrics.suppressed = suppressions.rules(self.start_line).to_vec();
+        }
+        for space in self.spaces.iter_mut() {
+            space.set_suppressions(suppressions);
+        }
+    }
+
+    // Suppresses some metrics of a function space and of all its
+    // subspaces
+    fn suppress(&mut self, ids: &[&str]) {
+        self.metrics
+            .suppressed
+            .extend(ids.iter().map(|id| id.to_string()));
+        for space in self.spaces.iter_mut() {
+            space.suppress(ids);
+        }
+    }
+
+    /// Embeds the source code of a function space and of all its
+    /// subspaces, surrounded by `context` lines before and after it.
+    pub fn set_snippets(&mut self, code: &[u8], context: usize) {
+        self.set_snippets_helper(&SourceMap::new(code), context);
+    }
+
+    /// Grades the maintainability index of a function space and of all
+    /// its subspaces.
+    pub fn set_mi_grades(&mut self, bands: &MiBands) {
+        self.mi_grade = bands
+            .grade(self.metrics.mi.mi_visual_studio())
+            .filter(|_| !self.is_suppressed("mi"))
+            .map(str::to_string);
+        for space in self.spaces.iter_mut() {
+            space.set_mi_grades(bands);
+        }
+    }
+
+    fn set_snippets_helper(&mut self, lines: &SourceMap, context: usize) {
+        self.snippet = lines.snippet(self.start_line, self.end_line, context);
+        for space in self.spaces.iter_mut() {
+            space.set_snippets_helper(lines, context);
+        }
+    }
+
+    fn set_offset_encoding(&mut self, lines: &SourceMap, encoding: OffsetEncoding) {
+        self.start_column = lines.column(self.start_line, self.start_column, encoding);
+        self.end_column = lines.column(self.end_line, self.end_column, encoding);
+        for space in self.spaces.iter_mut() {
+            space.set_offset_encoding(lines, encoding);
         }
     }
 }
@@ -202,6 +501,14 @@ fn compute_averages(state: &mut State) {
     state.space.metrics.cognitive.finalize(nom_total);
     // Nexit average
     state.space.metrics.nexits.finalize(nom_total);
+    // Generics average
+    state.space.metrics.generics.finalize(nom_total);
+    // Unsafety density
+    state
+        .space
+        .metrics
+        .unsafety
+        .finalize(state.space.metrics.loc.sloc() as usize);
     // Nargs average
     state
         .space
@@ -219,6 +526,7 @@ fn compute_minmax(state: &mut State) {
     state.space.metrics.nom.compute_minmax();
     state.space.metrics.loc.compute_minmax();
     state.space.metrics.abc.compute_minmax();
+    state.space.metrics.generics.compute_minmax();
 }
 
 #[inline(always)]
@@ -226,34 +534,41 @@ fn compute_sum(state: &mut State) {
     state.space.metrics.wmc.compute_sum();
     state.space.metrics.npm.compute_sum();
     state.space.metrics.npa.compute_sum();
+    state.space.metrics.unsafety.compute_sum();
 }
 
-fn finalize<T: ParserTrait>(state_stack: &mut Vec<State>, diff_level: usize) {
-    if state_stack.is_empty() {
-        return;
-    }
+fn finalize<T: ParserTrait>(
+    state_stack: &mut Vec<State>,
+    diff_level: usize,
+    nested: NestedAttribution,
+    granularity: SpaceGranularity,
+) {
     for _ in 0..diff_level {
-        if state_stack.len() == 1 {
-            let last_state = state_stack.last_mut().unwrap();
-            compute_minmax(last_state);
-            compute_sum(last_state);
-            compute_halstead_mi_and_wmc::<T>(last_state);
-            compute_averages(last_state);
+        let Some(mut state) = state_stack.pop() else {
             break;
-        }
-        let mut state = state_stack.pop().unwrap();
+        };
         compute_minmax(&mut state);
         compute_sum(&mut state);
         compute_halstead_mi_and_wmc::<T>(&mut state);
         compute_averages(&mut state);
 
-        let last_state = state_stack.last_mut().unwrap();
-        last_state.halstead_maps.merge(&state.halstead_maps);
-        compute_halstead_mi_and_wmc::<T>(last_state);
+        // The root space stays on the stack
+        let Some(last_state) = state_stack.last_mut() else {
+            state_stack.push(state);
+            break;
+        };
+        let listed = granularity.includes(state.space.kind);
+        let is_nested = is_nested(last_state.space.kind, state.space.kind);
+        if !listed || !is_nested || nested != NestedAttribution::Excluded {
+            last_state.halstead_maps.merge(&state.halstead_maps);
+            compute_halstead_mi_and_wmc::<T>(last_state);
 
-        // Merge function spaces
-        last_state.space.metrics.merge(&state.space.metrics);
-        last_state.space.spaces.push(state.space);
+            // Merge function spaces
+            last_state.space.metrics.merge(&state.space.metrics);
+        }
+        if listed && (!is_nested || nested != NestedAttribution::Included) {
+            last_state.space.spaces.push(state.space);
+        }
     }
 }
 
@@ -286,9 +601,126 @@ struct State<'a> {
 /// metrics(&parser, &path).unwrap();
 /// ```
 pub fn metrics<'a, T: ParserTrait>(parser: &'a T, path: &'a Path) -> Option<FuncSpace> {
+    metrics_with_nesting(parser, path, NestedAttribution::default())
+}
+
+/// Returns all function spaces data of a code, attributing the metrics
+/// of the nested spaces as requested.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{metrics_with_nesting, NestedAttribution, ParserEngineRust, ParserTrait};
+///
+/// let path = Path::new("foo.rs");
+/// let parser = ParserEngineRust::new(b"fn f() { let g = || 1; }".to_vec(), path, None);
+///
+/// let space = metrics_with_nesting(&parser, path, NestedAttribution::Included).unwrap();
+/// assert!(space.spaces[0].spaces.is_empty());
+/// assert_eq!(space.spaces[0].metrics.nom.closures_sum(), 1.);
+/// ```
+pub fn metrics_with_nesting<'a, T: ParserTrait>(
+    parser: &'a T,
+    path: &'a Path,
+    nested: NestedAttribution,
+) -> Option<FuncSpace> {
+    metrics_with_granularity(parser, path, nested, SpaceGranularity::default())
+}
+
+/// Returns the function spaces data of a code down to a granularity,
+/// attributing the metrics of the nested spaces as requested.
+///
+/// The metrics of the spaces deeper than the granularity are included in
+/// their parent, whatever the attribution of the nested spaces.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{
+///     metrics_with_granularity, NestedAttribution, ParserEngineRust, ParserTrait,
+///     SpaceGranularity,
+/// };
+///
+/// let path = Path::new("foo.rs");
+/// let parser = ParserEngineRust::new(b"fn f() { if true {} }".to_vec(), path, None);
+///
+/// let space =
+///     metrics_with_granularity(&parser, path, NestedAttribution::default(), SpaceGranularity::File)
+///         .unwrap();
+/// assert!(space.spaces.is_empty());
+/// assert_eq!(space.metrics.nom.functions_sum(), 1.);
+/// ```
+pub fn metrics_with_granularity<'a, T: ParserTrait>(
+    parser: &'a T,
+    path: &'a Path,
+    nested: NestedAttribution,
+    granularity: SpaceGranularity,
+) -> Option<FuncSpace> {
+    file_space(parser, path, nested, granularity, false, true)
+}
+
+// Computes the spaces of a file, with a space for each function-like
+// macro definition if `macros` is set, and with the Halstead metrics and
+// the maintainability index, which are suppressed otherwise, if
+// `halstead` is set
+pub(crate) fn file_space<'a, T: ParserTrait>(
+    parser: &'a T,
+    path: &'a Path,
+    nested: NestedAttribution,
+    granularity: SpaceGranularity,
+    macros: bool,
+    halstead: bool,
+) -> Option<FuncSpace> {
+    trace_span!("metrics", language = ?parser.get_language(), path = %path.display());
+    let code = parser.get_code();
+    let space = subtree_space(
+        parser,
+        parser.get_root(),
+        nested,
+        granularity,
+        false,
+        macros,
+        halstead,
+    );
+
+    space.map(|mut space| {
+        space.name = path.to_str().map(|name| name.to_string());
+        let suppressions = Suppressions::new(code);
+        if !suppressions.is_empty() {
+            space.set_suppressions(&suppressions);
+        }
+        if !halstead {
+            space.suppress(&["halstead", "mi"]);
+        }
+        space.parse_errors = parse_errors(&parser.get_root());
+        space.third_party = is_third_party(path);
+        trace_event!(debug, spaces = space.spaces.len(), "metrics computed");
+        space
+    })
+}
+
+// Computes the spaces of the code of a node and of its descendants. The
+// node is a space of its own if `root_space` is set, even if it isn't a
+// function space, each function-like macro definition of a `C/C++`
+// code is a function space if `macros` is set, and the operators and
+// operands are only collected if `halstead` is set.
+pub(crate) fn subtree_space<T: ParserTrait>(
+    parser: &T,
+    node: Node,
+    nested: NestedAttribution,
+    granularity: SpaceGranularity,
+    root_space: bool,
+    macros: bool,
+    halstead: bool,
+) -> Option<FuncSpace> {
     let code = parser.get_code();
+    let language = parser.get_language();
     let _code_guard = enter_code_context(code);
-    let node = parser.get_root();
+    let root = node.id();
     let mut cursor = node.cursor();
     let mut stack = Vec::new();
     let mut children = Vec::new();
@@ -298,23 +730,41 @@ pub fn metrics<'a, T: ParserTrait>(parser: &'a T, path: &'a Path) -> Option<Func
     // Three type of nesting info: conditionals, functions and lambdas
     let mut nesting_map = HashMap::<usize, (usize, usize, usize)>::default();
     nesting_map.insert(node.id(), (0, 0, 0));
+    let table = if halstead {
+        halstead_table(language)
+    } else {
+        None
+    };
     stack.push((node, 0));
 
     while let Some((node, level)) = stack.pop() {
         if level < last_level {
-            finalize::<T>(&mut state_stack, last_level - level);
+            finalize::<T>(&mut state_stack, last_level - level, nested, granularity);
             last_level = level;
         }
 
+        // Missing nodes are inserted by the parser to recover from an error,
+        // so they aren't part of the code
+        if node.is_missing() {
+            continue;
+        }
+
         let kind = T::Getter::get_space_kind(&node);
 
-        let func_space = T::Checker::is_func(&node) || T::Checker::is_func_space(&node);
+        let func_space = T::Checker::is_func(&node)
+            || T::Checker::is_func_space(&node)
+            || root_space && node.id() == root;
         let unit = kind == SpaceKind::Unit;
 
         let new_level = if func_space {
+            let mut space = FuncSpace::new::<T::Getter>(&node, code, kind);
+            // The fingerprint of a space which isn't listed is never read
+            if granularity.includes(kind) || state_stack.is_empty() {
+                space.fingerprint = fingerprint::<T::Checker>(&node, code);
+            }
             let state = State {
-                space: FuncSpace::new::<T::Getter>(&node, code, kind),
-                halstead_maps: HalsteadMaps::new(),
+                space,
+                halstead_maps: HalsteadMaps::new(table.clone()),
             };
             state_stack.push(state);
             last_level = level + 1;
@@ -327,14 +777,31 @@ pub fn metrics<'a, T: ParserTrait>(parser: &'a T, path: &'a Path) -> Option<Func
             let last = &mut state.space;
             T::Cognitive::compute(&node, &mut last.metrics.cognitive, &mut nesting_map);
             T::Cyclomatic::compute(&node, &mut last.metrics.cyclomatic);
-            T::Halstead::compute(&node, code, &mut state.halstead_maps);
-            T::Loc::compute(&node, &mut last.metrics.loc, func_space, unit);
+            if halstead {
+                T::Halstead::compute(&node, code, &mut state.halstead_maps);
+            }
+            loc::compute_loc::<T::Loc>(language, &node, &mut last.metrics.loc, func_space, unit);
             T::Nom::compute(&node, &mut last.metrics.nom);
             T::NArgs::compute(&node, &mut last.metrics.nargs);
             T::Exit::compute(&node, &mut last.metrics.nexits);
             T::Abc::compute(&node, &mut last.metrics.abc);
+            T::Generics::compute(&node, &mut last.metrics.generics);
             T::Npm::compute(&node, &mut last.metrics.npm);
             T::Npa::compute(&node, &mut last.metrics.npa);
+            T::Unsafety::compute(&node, &mut last.metrics.unsafety);
+            T::Extras::compute(&node, &mut last.metrics.extras);
+
+            // The space of a macro is attached to its enclosing space as
+            // a nested space is, before the enclosing space is finalized
+            if macros && language == LANG::Cpp && node.kind() == "preproc_function_def" {
+                if let Some(space) = macro_space(&node, code) {
+                    last.metrics.merge(&space.metrics);
+                    if granularity.includes(space.kind) {
+                        last.spaces.push(space);
+                    }
+                }
+                continue;
+            }
         }
 
         cursor.reset(&node);
@@ -351,20 +818,143 @@ pub fn metrics<'a, T: ParserTrait>(parser: &'a T, path: &'a Path) -> Option<Func
         }
     }
 
-    finalize::<T>(&mut state_stack, usize::MAX);
+    finalize::<T>(&mut state_stack, usize::MAX, nested, granularity);
 
-    state_stack.pop().map(|mut state| {
-        state.space.name = path.to_str().map(|name| name.to_string());
-        state.space
+    state_stack.pop().map(|state| state.space)
+}
+
+/// Returns all function spaces data of a code, with the columns of
+/// their positions counted in the given [`OffsetEncoding`].
+///
+/// The [`metrics`] function counts columns in `UTF-8` bytes.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{metrics_with_encoding, OffsetEncoding, ParserTrait, ParserEngineRust};
+///
+/// let source_code = "fn f() {}\nfn g() { \"é\"; }\n";
+/// let path = Path::new("foo.rs");
+/// let parser = ParserEngineRust::new(source_code.as_bytes().to_vec(), &path, None);
+///
+/// let space = metrics_with_encoding(&parser, &path, OffsetEncoding::Utf16).unwrap();
+/// assert_eq!(space.spaces[1].end_column, 16);
+/// ```
+pub fn metrics_with_encoding<'a, T: ParserTrait>(
+    parser: &'a T,
+    path: &'a Path,
+    encoding: OffsetEncoding,
+) -> Option<FuncSpace> {
+    metrics(parser, path).map(|mut space| {
+        if encoding != OffsetEncoding::Utf8 {
+            space.set_offset_encoding(&SourceMap::new(parser.get_code()), encoding);
+        }
+        space
     })
 }
 
+impl FuncSpace {
+    pub(crate) fn shift_lines(&mut self, rows: usize) {
+        self.start_line += rows;
+        self.end_line += rows;
+        for space in self.spaces.iter_mut() {
+            space.shift_lines(rows);
+        }
+    }
+}
+
+// Returns the space of a function-like macro definition, computed by
+// parsing its body as the body of a function
+fn macro_space(node: &Node, code: &[u8]) -> Option<FuncSpace> {
+    let name = node.child_by_field_name("name")?.utf8_text(code)?;
+    let body = node.child_by_field_name("value")?;
+    // The line continuations are kept as line breaks, so the body is
+    // parsed on the lines of the macro
+    let text = body
+        .utf8_text(code)?
+        .replace("\\\r\n", " \n")
+        .replace("\\\n", " \n");
+    let wrapped = format!("void {name}() {{ {text} ;}}\n");
+
+    let path = Path::new("");
+    let parser = CppParser::new(wrapped.into_bytes(), path, None);
+    let mut space = metrics(&parser, path)?
+        .spaces
+        .into_iter()
+        .find(|space| space.kind == SpaceKind::Function)?;
+    space.shift_lines(body.start_row());
+    space.name = Some(name.to_string());
+    space.start_line = node.start_row() + 1;
+    space.start_column = node.start_position().1 + 1;
+    space.end_line = body.end_row() + 1;
+    space.end_column = body.end_position().1 + 1;
+    Some(space)
+}
+
+/// Returns all function spaces data of a code, with a function space for
+/// each function-like macro definition.
+///
+/// The body of a `C/C++` macro isn't parsed, so the control flow of a
+/// macro like `#define CHECK(x) do { if (!(x)) abort(); } while (0)`
+/// doesn't count in the metrics computed by [`metrics`]. This function
+/// parses the body of each function-like macro as the body of a function,
+/// and adds its space to the space enclosing the macro, like a nested
+/// function, so the metrics of the enclosing spaces include the ones of
+/// their macros. The other languages
+/// have the same metrics as with [`metrics`].
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{metrics_with_macros, CppParser, ParserTrait};
+///
+/// let path = Path::new("foo.c");
+/// let source_code = "#define MAX(a, b) ((a) > (b) ? (a) : (b))\n";
+/// let parser = CppParser::new(source_code.as_bytes().to_vec(), path, None);
+///
+/// let space = metrics_with_macros(&parser, path).unwrap();
+/// assert_eq!(space.spaces[0].name.as_deref(), Some("MAX"));
+/// assert_eq!(space.spaces[0].metrics.cyclomatic.cyclomatic_sum(), 2.);
+/// ```
+pub fn metrics_with_macros<'a, T: ParserTrait>(parser: &'a T, path: &'a Path) -> Option<FuncSpace> {
+    file_space(
+        parser,
+        path,
+        NestedAttribution::default(),
+        SpaceGranularity::default(),
+        true,
+        true,
+    )
+}
+
 /// Configuration options for computing
 /// the metrics of a code.
 #[derive(Debug)]
 pub struct MetricsCfg {
     /// Path to the file containing the code
     pub path: PathBuf,
+    /// The unit used to count the columns of a function space
+    pub offset_encoding: OffsetEncoding,
+    /// The number of context lines surrounding the source code
+    /// embedded in each function space
+    ///
+    /// If `None`, no source code is embedded
+    pub snippet_context: Option<usize>,
+    /// The bands grading the maintainability index of each function space
+    ///
+    /// If `None`, the spaces aren't graded
+    pub mi_bands: Option<MiBands>,
+    /// Whether each function-like macro definition is a function space,
+    /// as computed by [`metrics_with_macros`]
+    pub macro_spaces: bool,
+    /// The base of the path written in the results
+    ///
+    /// If `None`, the path is written as given
+    pub path_base: Option<PathBase>,
 }
 
 pub struct Metrics {
@@ -376,8 +966,30 @@ impl Callback for Metrics {
     type Cfg = MetricsCfg;
 
     fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
-        match metrics(parser, &cfg.path) {
-            Some(space) => dump_root(&space),
+        let space = if cfg.macro_spaces {
+            metrics_with_macros(parser, &cfg.path)
+        } else {
+            metrics(parser, &cfg.path)
+        };
+        match space {
+            Some(mut space) => {
+                if cfg.offset_encoding != OffsetEncoding::Utf8 {
+                    space.set_offset_encoding(
+                        &SourceMap::new(parser.get_code()),
+                        cfg.offset_encoding,
+                    );
+                }
+                if let Some(context) = cfg.snippet_context {
+                    space.set_snippets(parser.get_code(), context);
+                }
+                if let Some(bands) = &cfg.mi_bands {
+                    space.set_mi_grades(bands);
+                }
+                if let Some(base) = &cfg.path_base {
+                    base.relativize_space(&cfg.path, &mut space);
+                }
+                dump_root(&space)
+            }
             _ => Ok(()),
         }
     }
@@ -385,7 +997,14 @@ impl Callback for Metrics {
 
 #[cfg(test)]
 mod tests {
-    use crate::{check_func_space, CppParser};
+    use std::path::Path;
+
+    use crate::{
+        check_func_space, metrics, metrics_with_encoding, metrics_with_granularity,
+        metrics_with_macros, metrics_with_nesting, CppParser, FuncSpace, JavaParser,
+        NestedAttribution, OffsetEncoding, ParserEngineRust, ParserTrait, SpaceGranularity,
+        SpaceKind,
+    };
 
     #[test]
     fn c_scope_resolution_operator() {
@@ -402,4 +1021,297 @@ mod tests {
             },
         );
     }
+
+    #[test]
+    fn rust_space_columns() {
+        check_func_space::<ParserEngineRust, _>(
+            "fn f() {}
+             fn g() { \"é😀\"; }",
+            "foo.rs",
+            |func_space| {
+                insta::assert_json_snapshot!(
+                    (func_space.start_line, func_space.start_column, func_space.end_line, func_space.end_column),
+                    @r###"
+                    [
+                      1,
+                      1,
+                      2,
+                      34
+                    ]"###
+                );
+                let g = &func_space.spaces[1];
+                insta::assert_json_snapshot!(
+                    (g.start_line, g.start_column, g.end_line, g.end_column),
+                    @r###"
+                    [
+                      2,
+                      14,
+                      2,
+                      34
+                    ]"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn rust_space_columns_utf16() {
+        let code = "fn f() {}\n  fn g() { \"é😀\"; }\n";
+        let path = Path::new("foo.rs");
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), path, None);
+
+        let utf16 = metrics_with_encoding(&parser, path, OffsetEncoding::Utf16).unwrap();
+        let utf32 = metrics_with_encoding(&parser, path, OffsetEncoding::Utf32).unwrap();
+
+        assert_eq!(utf16.spaces[1].start_column, 3);
+        assert_eq!(utf16.spaces[1].end_column, 20);
+        assert_eq!(utf32.spaces[1].end_column, 19);
+        assert_eq!(utf16.end_column, 20);
+    }
+
+    #[test]
+    fn rust_space_snippets() {
+        let code = "// a\nfn f() {\n    g();\n}\n// b\n// c\n";
+        let path = Path::new("foo.rs");
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), path, None);
+        let mut space = metrics(&parser, path).unwrap();
+
+        assert!(space.spaces[0].snippet.is_none());
+
+        space.set_snippets(parser.get_code(), 1);
+        insta::assert_json_snapshot!(
+            space.spaces[0].snippet,
+            @r###"
+            {
+              "start_line": 1,
+              "end_line": 5,
+              "text": "// a\nfn f() {\n    g();\n}\n// b"
+            }"###
+        );
+        insta::assert_json_snapshot!(
+            space.snippet.as_ref().map(|s| (s.start_line, s.end_line)),
+            @r###"
+            [
+              1,
+              6
+            ]"###
+        );
+    }
+
+    #[test]
+    fn rust_space_unicode_crlf_bom() {
+        let code = "\u{FEFF}fn caf\u{e9}() {\r\n    let s = \"\u{1F600}\";\r\n}\r\nfn \u{540d}\u{524d}() {}\r\n";
+        let path = Path::new("foo.rs");
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), path, None);
+        assert!(!parser.get_code().contains(&b'\r'));
+
+        let space = metrics(&parser, path).unwrap();
+        let functions = space
+            .spaces
+            .iter()
+            .map(|space| {
+                (
+                    space.name.as_deref().unwrap(),
+                    space.start_line,
+                    space.end_line,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(functions, [("caf\u{e9}", 1, 3), ("\u{540d}\u{524d}", 4, 4)]);
+    }
+
+    #[test]
+    fn rust_space_suppressions() {
+        let code = "fn f() { // sca-ignore: cc\n    g();\n}\n// sca-ignore-next-line\nfn h() {}\nfn k() {}\n";
+        let path = Path::new("foo.rs");
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), path, None);
+        let space = metrics(&parser, path).unwrap();
+
+        assert_eq!(space.spaces[0].metrics.suppressed, ["cyclomatic"]);
+        assert!(space.spaces[0].is_suppressed("cc"));
+        assert!(!space.spaces[0].is_suppressed("cognitive"));
+        assert!(space.spaces[1].is_suppressed("halstead"));
+        assert!(space.spaces[2].metrics.suppressed.is_empty());
+        assert!(!space.is_suppressed("cyclomatic"));
+
+        let metrics = serde_json::to_value(&space.spaces[0].metrics).unwrap();
+        assert!(metrics["cyclomatic"].is_null());
+        assert!(metrics["cognitive"].is_object());
+        assert!(metrics.get("wmc").is_none());
+    }
+
+    #[test]
+    fn space_kinds() {
+        fn kinds(space: &FuncSpace, out: &mut Vec<SpaceKind>) {
+            out.push(space.kind);
+            for space in &space.spaces {
+                kinds(space, out);
+            }
+        }
+
+        let code = "mod m {\n    trait T { fn f(&self); }\n    impl T for u8 {\n        fn f(&self) { let g = |x: u8| x; }\n    }\n}\nmod n;\n";
+        let path = Path::new("foo.rs");
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), path, None);
+        let mut found = Vec::new();
+        kinds(&metrics(&parser, path).unwrap(), &mut found);
+
+        assert_eq!(
+            found,
+            [
+                SpaceKind::Unit,
+                SpaceKind::Module,
+                SpaceKind::Trait,
+                SpaceKind::Impl,
+                SpaceKind::Function,
+                SpaceKind::Closure
+            ]
+        );
+        assert!(SpaceKind::Closure.is_callable());
+        assert!(!SpaceKind::Module.is_callable());
+    }
+
+    #[test]
+    fn struct_kinds() {
+        fn kinds<T: ParserTrait>(code: &str, path: &str) -> Vec<SpaceKind> {
+            let path = Path::new(path);
+            let parser = T::new(code.as_bytes().to_vec(), path, None);
+            let space = metrics(&parser, path).unwrap();
+            space.spaces.iter().map(|space| space.kind).collect()
+        }
+
+        assert_eq!(
+            kinds::<ParserEngineRust>(
+                "struct S { a: u8 }\nenum E { A }\nunion U { a: u8 }\n",
+                "foo.rs"
+            ),
+            [SpaceKind::Struct; 3]
+        );
+        assert_eq!(
+            kinds::<CppParser>("struct S { int a; };\nclass C {};\n", "foo.cpp"),
+            [SpaceKind::Struct, SpaceKind::Class]
+        );
+        assert_eq!(
+            kinds::<JavaParser>("enum E { A }\nrecord R(int a) {}\n", "E.java"),
+            [SpaceKind::Class; 2]
+        );
+    }
+
+    #[test]
+    fn nested_attribution() {
+        let code = "fn f() {\n    fn g(x: bool) { if x {} }\n}\nfn h() {}\n";
+        let path = Path::new("foo.rs");
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), path, None);
+        let space = |nested| metrics_with_nesting(&parser, path, nested).unwrap();
+
+        let both = space(NestedAttribution::DoubleReported);
+        assert_eq!(both.spaces[0].spaces.len(), 1);
+        assert_eq!(both.spaces[0].metrics.nom.functions_sum(), 2.);
+
+        let included = space(NestedAttribution::Included);
+        assert!(included.spaces[0].spaces.is_empty());
+        assert_eq!(included.spaces[0].metrics.nom.functions_sum(), 2.);
+        assert_eq!(
+            included.spaces[0].metrics.cyclomatic.cyclomatic_sum(),
+            both.spaces[0].metrics.cyclomatic.cyclomatic_sum()
+        );
+
+        let excluded = space(NestedAttribution::Excluded);
+        assert_eq!(excluded.spaces[0].spaces.len(), 1);
+        assert_eq!(excluded.spaces[0].metrics.nom.functions_sum(), 1.);
+        assert!(
+            excluded.spaces[0].metrics.cyclomatic.cyclomatic_sum()
+                < both.spaces[0].metrics.cyclomatic.cyclomatic_sum()
+        );
+
+        // `h` isn't nested
+        assert_eq!(excluded.spaces.len(), 2);
+        assert_eq!(included.spaces.len(), 2);
+    }
+
+    #[test]
+    fn c_macro_spaces() {
+        let source_code = "#define CHECK(x) do { \\
+    if ((x) < 0) \\
+        return -1; \\
+} while (0)
+
+int f(int a) { CHECK(a); return a > 0 ? 1 : 0; }
+";
+        let path = Path::new("foo.c");
+        let parser = CppParser::new(source_code.as_bytes().to_vec(), path, None);
+        let without = metrics(&parser, path).unwrap();
+        let with = metrics_with_macros(&parser, path).unwrap();
+
+        assert_eq!(without.spaces.len(), 1);
+        assert_eq!(with.spaces.len(), 2);
+        let check = &with.spaces[0];
+        assert_eq!(check.name.as_deref(), Some("CHECK"));
+        assert_eq!(check.kind, SpaceKind::Function);
+        assert_eq!((check.start_line, check.end_line), (1, 4));
+        assert_eq!(check.metrics.cyclomatic.cyclomatic_sum(), 3.);
+        assert_eq!(with.spaces[1].name.as_deref(), Some("f"));
+        assert_eq!(
+            with.metrics.cyclomatic.cyclomatic_sum(),
+            without.metrics.cyclomatic.cyclomatic_sum() + 3.
+        );
+        assert_eq!(with.metrics.nom.functions_sum(), 2.);
+    }
+
+    #[test]
+    fn space_granularity() {
+        let code = "impl S {\n    fn f(&self) {\n        let g = |x: bool| if x {};\n    }\n}\nfn h() {}\n";
+        let path = Path::new("foo.rs");
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), path, None);
+        let space = |granularity| {
+            metrics_with_granularity(&parser, path, NestedAttribution::default(), granularity)
+                .unwrap()
+        };
+
+        let closure = space(SpaceGranularity::Closure);
+        assert_eq!(closure.spaces.len(), 2);
+        assert_eq!(closure.spaces[0].spaces[0].spaces.len(), 1);
+
+        let function = space(SpaceGranularity::Function);
+        assert_eq!(function.spaces.len(), 2);
+        let f = &function.spaces[0].spaces[0];
+        assert!(f.spaces.is_empty());
+        assert_eq!(f.metrics.nom.closures_sum(), 1.);
+        assert_eq!(
+            f.metrics.cyclomatic.cyclomatic_sum(),
+            closure.spaces[0].spaces[0]
+                .metrics
+                .cyclomatic
+                .cyclomatic_sum()
+        );
+
+        let class = space(SpaceGranularity::Class);
+        assert_eq!(class.spaces.len(), 1);
+        assert_eq!(class.spaces[0].kind, SpaceKind::Impl);
+        assert!(class.spaces[0].spaces.is_empty());
+
+        let file = space(SpaceGranularity::File);
+        assert!(file.spaces.is_empty());
+        assert_eq!(file.metrics.nom.functions_sum(), 2.);
+        assert_eq!(
+            file.metrics.cyclomatic.cyclomatic_sum(),
+            closure.metrics.cyclomatic.cyclomatic_sum()
+        );
+    }
+
+    #[test]
+    fn malformed_input_doesnt_panic() {
+        let inputs: [&[u8]; 6] = [
+            b"",
+            b"\0\xff\xfe",
+            b"fn f( {",
+            b"if ((((((((",
+            b"class A { def f(self: pass",
+            b"\"\xc3\xa9",
+        ];
+        for lang in crate::LANG::into_enum_iter() {
+            for input in inputs {
+                crate::get_function_spaces(&lang, input.to_vec(), Path::new("foo"), None);
+            }
+        }
+    }
 }
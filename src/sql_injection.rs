@@ -0,0 +1,306 @@
+use std::{collections::BTreeMap, path::Path, sync::OnceLock};
+
+use regex::Regex;
+use serde::Serialize;
+
+use crate::{
+    ai::{CodeLocation, CodeSmell, SmellCatalog, SmellMetadata, SmellReport, SQL_INJECTION},
+    checker::Checker,
+    node::Node,
+    secrets::literal_content,
+    security::{callee, is_call},
+    traits::ParserTrait,
+};
+
+/// How a SQL query is built from untrusted values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryBuilding {
+    /// The literal is concatenated with a value, like `"... id = " + id`
+    Concatenation,
+    /// The literal interpolates a value, like `f"... id = {id}"`
+    Interpolation,
+    /// The literal is a format string, like `"... id = %s" % id`
+    Formatting,
+}
+
+impl QueryBuilding {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Concatenation => "concatenation",
+            Self::Interpolation => "interpolation",
+            Self::Formatting => "formatting",
+        }
+    }
+}
+
+// The names of the functions and methods formatting their first argument
+const FORMAT_FUNCTIONS: &[&str] = &["format", "sprintf", "Sprintf", "Format"];
+
+// The binary operators concatenating strings, and `%` formatting them
+const CONCATENATIONS: &[&str] = &["+", "<>", "..", "++"];
+
+fn sql() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r"(?i)\b(select\s+(\*|distinct\b|[\w.]+\s*(,|\(|\s+from\b))|insert\s+into\s+[\w.]+|update\s+[\w.]+\s+set\s+\w+\s*=|delete\s+from\s+[\w.]+|where\s+[\w.]+\s*(=|<|>|!=|like\b|in\b))",
+        )
+        .expect("valid regex")
+    })
+}
+
+/// Returns `true` if a text looks like a SQL query or a fragment of it.
+///
+/// # Examples
+///
+/// ```
+/// use singularity_code_analysis::is_sql;
+///
+/// assert!(is_sql("SELECT id, name FROM users"));
+/// assert!(is_sql(" WHERE id = "));
+/// assert!(!is_sql("Please select a file from the list"));
+/// ```
+pub fn is_sql(text: &str) -> bool {
+    sql().is_match(text)
+}
+
+fn is_interpolated(node: &Node) -> bool {
+    node.children().any(|child| {
+        let kind = child.kind();
+        kind.contains("interpolation") || kind.contains("substitution")
+    })
+}
+
+fn binary_operator<'a>(node: &Node<'a>, code: &'a [u8]) -> Option<&'a str> {
+    node.children()
+        .find(|child| !child.is_named())?
+        .utf8_text(code)
+}
+
+// Returns how a literal containing SQL is built into a query, if it
+// isn't a constant
+fn query_building<T: ParserTrait>(node: &Node, code: &[u8]) -> Option<QueryBuilding> {
+    if is_interpolated(node) {
+        return Some(QueryBuilding::Interpolation);
+    }
+    let parent = node.parent()?;
+    match parent.kind() {
+        "binary_expression" | "binary_operator" => {
+            let operator = binary_operator(&parent, code)?;
+            let other = parent
+                .children()
+                .find(|child| child.is_named() && child.start_byte() != node.start_byte())?;
+            if operator == "%" {
+                Some(QueryBuilding::Formatting)
+            } else if CONCATENATIONS.contains(&operator) && !T::Checker::is_string(&other) {
+                Some(QueryBuilding::Concatenation)
+            } else {
+                None
+            }
+        }
+        // `"...".format(id)`
+        "attribute" | "member_expression" | "field_expression" | "selector_expression" => {
+            let call = parent.parent().filter(is_call)?;
+            let callee = callee(&call, code)?;
+            FORMAT_FUNCTIONS
+                .iter()
+                .any(|name| callee.ends_with(name))
+                .then_some(QueryBuilding::Formatting)
+        }
+        // `format!("...", id)` in Rust
+        "token_tree" => {
+            let invocation = parent.parent()?;
+            let name = invocation.child_by_field_name("macro")?.utf8_text(code)?;
+            (invocation.kind() == "macro_invocation" && FORMAT_FUNCTIONS.contains(&name))
+                .then_some(QueryBuilding::Formatting)
+        }
+        // `String.format("...", id)` and the likes
+        "arguments" | "argument_list" | "argument" => {
+            let call = parent
+                .parent()
+                .filter(|call| is_call(call) || call.kind() == "argument_list")?;
+            let call = if is_call(&call) { call } else { call.parent()? };
+            let callee = callee(&call, code)?;
+            let first = parent.children().find(|child| child.is_named())?;
+            (first.start_byte() == node.start_byte()
+                && FORMAT_FUNCTIONS.iter().any(|name| callee.ends_with(name)))
+            .then_some(QueryBuilding::Formatting)
+        }
+        _ => None,
+    }
+}
+
+/// The SQL queries of a code.
+#[derive(Clone, Debug, Serialize)]
+pub struct SqlReport {
+    /// The number of string literals containing SQL
+    pub queries: usize,
+    /// The number of reported queries built in each way
+    pub injections: BTreeMap<QueryBuilding, usize>,
+    /// The queries built from values, reported as smells of the
+    /// [`SQL_INJECTION`] rule
+    #[serde(flatten)]
+    pub report: SmellReport,
+}
+
+/// Finds the string literals containing SQL which are concatenated with,
+/// interpolate or format values, so they are potential injection sites.
+///
+/// Queries passing the values as parameters, like
+/// `cursor.execute("SELECT * FROM t WHERE id = %s", (id,))`, aren't
+/// reported.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{find_sql_injections, ParserTrait, PythonParser, SmellCatalog};
+///
+/// let path = Path::new("foo.py");
+/// let code = b"cursor.execute(\"SELECT * FROM users WHERE id = \" + user_id)\n".to_vec();
+/// let parser = PythonParser::new(code, path, None);
+///
+/// let report = find_sql_injections(&parser, path, &SmellCatalog::default());
+/// assert_eq!(report.queries, 1);
+/// assert_eq!(report.report.smells[0].rule_id, "sql-injection");
+/// ```
+pub fn find_sql_injections<T: ParserTrait>(
+    parser: &T,
+    path: &Path,
+    catalog: &SmellCatalog,
+) -> SqlReport {
+    let mut metadata = SmellMetadata::for_catalog(catalog);
+
+    let code = parser.get_code();
+    let mut queries = Vec::new();
+    let mut stack = vec![parser.get_root()];
+    while let Some(node) = stack.pop() {
+        if T::Checker::is_string(&node) {
+            if node
+                .utf8_text(code)
+                .is_some_and(|text| is_sql(literal_content(text)))
+            {
+                queries.push(node);
+            }
+            continue;
+        }
+        let mut children = node.children().collect::<Vec<_>>();
+        children.reverse();
+        stack.extend(children);
+    }
+
+    let mut injections = BTreeMap::new();
+    let mut smells = Vec::new();
+    let mut suppressed = Vec::new();
+    if let Some(rule) = catalog.enabled(SQL_INJECTION) {
+        let file_path = path.to_string_lossy().to_string();
+        let mut found = Vec::new();
+        for node in queries.iter() {
+            let Some(building) = query_building::<T>(node, code) else {
+                continue;
+            };
+            let (start_row, start_column) = node.start_position();
+            let (end_row, end_column) = node.end_position();
+            let smell = CodeSmell {
+                rule_id: rule.id.clone(),
+                name: rule.name.clone(),
+                description: format!("SQL query built by {}", building.name()),
+                severity: rule.severity.clone(),
+                location: CodeLocation {
+                    file_path: file_path.clone(),
+                    line_start: start_row + 1,
+                    line_end: end_row + 1,
+                    column_start: start_column + 1,
+                    column_end: end_column + 1,
+                },
+                suggestion: "Pass the values as parameters of a prepared statement".to_string(),
+            };
+            found.push((building, smell));
+        }
+        metadata.record(
+            rule,
+            rule.threshold_for(Some(parser.get_language())),
+            found.len(),
+        );
+        let (found, silenced) = metadata.suppress(found, code, |(_, smell)| smell);
+        for (building, smell) in found {
+            *injections.entry(building).or_insert(0) += 1;
+            smells.push(smell);
+        }
+        suppressed.extend(silenced.into_iter().map(|(_, smell)| smell));
+    }
+
+    SqlReport {
+        queries: queries.len(),
+        injections,
+        report: SmellReport {
+            smells,
+            suppressed,
+            metadata,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{JavascriptParser, PythonParser};
+
+    use super::*;
+
+    fn injections<T: ParserTrait>(code: &str, path: &str) -> SqlReport {
+        let path = Path::new(path);
+        let parser = T::new(code.as_bytes().to_vec(), path, None);
+        find_sql_injections(&parser, path, &SmellCatalog::default())
+    }
+
+    #[test]
+    fn sql_fragments() {
+        assert!(is_sql("select * from t"));
+        assert!(is_sql("SELECT COUNT(*) FROM t"));
+        assert!(is_sql("INSERT INTO users (name) VALUES (?)"));
+        assert!(is_sql("UPDATE users SET name = ?"));
+        assert!(is_sql("delete from users"));
+        assert!(!is_sql("Update the settings"));
+        assert!(!is_sql("Where are you?"));
+    }
+
+    #[test]
+    fn python_injections() {
+        let code = "q1 = \"SELECT * FROM users WHERE id = \" + user_id\n\
+                    q2 = f\"SELECT * FROM users WHERE name = '{name}'\"\n\
+                    q3 = \"DELETE FROM users WHERE id = %s\" % user_id\n\
+                    q4 = \"SELECT * FROM users WHERE id = {}\".format(user_id)\n\
+                    cursor.execute(\"SELECT * FROM users WHERE id = %s\", (user_id,))\n\
+                    q5 = \"SELECT * FROM users \" + \"WHERE id = 1\"\n";
+        let report = injections::<PythonParser>(code, "foo.py");
+        assert_eq!(report.queries, 7);
+        assert_eq!(
+            report.injections,
+            BTreeMap::from([
+                (QueryBuilding::Concatenation, 1),
+                (QueryBuilding::Interpolation, 1),
+                (QueryBuilding::Formatting, 2),
+            ])
+        );
+        let lines = report
+            .report
+            .smells
+            .iter()
+            .map(|smell| smell.location.line_start)
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn javascript_injections() {
+        let code = "db.query(`SELECT * FROM users WHERE id = ${id}`);\n\
+                    db.query('SELECT * FROM users WHERE id = ?', [id]);\n\
+                    db.query('UPDATE users SET name = ' + name); // sca-ignore\n";
+        let report = injections::<JavascriptParser>(code, "foo.js");
+        assert_eq!(report.queries, 3);
+        assert_eq!(report.report.smells.len(), 1);
+        assert_eq!(report.report.suppressed.len(), 1);
+    }
+}
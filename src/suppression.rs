@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+
+/// The marker of a suppression comment.
+pub const SUPPRESSION_MARKER: &str = "sca-ignore";
+
+/// The rule id suppressing every finding and metric of a line.
+pub const SUPPRESS_ALL: &str = "all";
+
+const NEXT_LINE: &str = "-next-line";
+
+// The tokens which can directly precede a marker, so markers
+// written outside of comments are ignored
+const COMMENT_TOKENS: &[&str] = &["//", "/*", "*", "#", "--", "%", ";"];
+
+/// The suppression comments of a code.
+///
+/// A comment containing `sca-ignore` suppresses the findings and metrics
+/// of its own line, while `sca-ignore-next-line` suppresses those of the
+/// following line. A marker can be restricted to some rules by listing
+/// their ids after a colon, otherwise it suppresses everything.
+/// A suppression placed on the first line of a function applies to
+/// the whole function.
+///
+/// # Examples
+///
+/// ```
+/// use singularity_code_analysis::Suppressions;
+///
+/// let code = b"fn f() { // sca-ignore: cc, long-function\n}\n# sca-ignore-next-line\nx = 1\n";
+/// let suppressions = Suppressions::new(code);
+///
+/// assert!(suppressions.is_suppressed(1, "cyclomatic"));
+/// assert!(!suppressions.is_suppressed(1, "cognitive"));
+/// assert!(suppressions.is_suppressed(4, "cognitive"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Suppressions {
+    lines: BTreeMap<usize, Vec<String>>,
+}
+
+impl Suppressions {
+    /// Collects the suppression comments of a code.
+    pub fn new(code: &[u8]) -> Self {
+        let mut suppressions = Self::default();
+        let text = String::from_utf8_lossy(code);
+        for (line, text) in (1..).zip(text.lines()) {
+            let mut rest = text;
+            while let Some(start) = rest.find(SUPPRESSION_MARKER) {
+                let before = rest[..start].trim_end();
+                rest = &rest[start + SUPPRESSION_MARKER.len()..];
+                if !COMMENT_TOKENS.iter().any(|token| before.ends_with(token)) {
+                    continue;
+                }
+                let target = match rest.strip_prefix(NEXT_LINE) {
+                    Some(next) => {
+                        rest = next;
+                        line + 1
+                    }
+                    None => line,
+                };
+                // `sca-ignored` or `sca-ignore_x` aren't markers
+                if rest.starts_with(|c: char| c.is_alphanumeric() || c == '-' || c == '_') {
+                    continue;
+                }
+                let rules = suppressions.lines.entry(target).or_default();
+                match rest.trim_start().strip_prefix(':') {
+                    Some(ids) => rules.extend(parse_ids(ids)),
+                    None => rules.push(SUPPRESS_ALL.to_string()),
+                }
+            }
+        }
+        suppressions
+    }
+
+    /// Returns `true` if the code contains no suppression comments.
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Returns the ids of the rules suppressed on a 1-based line.
+    pub fn rules(&self, line: usize) -> &[String] {
+        self.lines.get(&line).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns `true` if the finding or metric with the given id is
+    /// suppressed on a 1-based line.
+    pub fn is_suppressed(&self, line: usize, id: &str) -> bool {
+        suppresses(self.rules(line), id)
+    }
+}
+
+/// Returns `true` if a list of suppressed rules contains the given id.
+pub(crate) fn suppresses(rules: &[String], id: &str) -> bool {
+    let id = canonical_id(id);
+    rules.iter().any(|rule| rule == SUPPRESS_ALL || rule == id)
+}
+
+fn parse_ids(ids: &str) -> impl Iterator<Item = String> + '_ {
+    // The list ends at the first word which can't be an id, like `*/`
+    ids.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|id| !id.is_empty())
+        .take_while(|id| {
+            id.chars()
+                .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+        })
+        .map(|id| canonical_id(&id.to_lowercase()).to_string())
+}
+
+// Short names accepted by the markers
+fn canonical_id(id: &str) -> &str {
+    match id {
+        "cc" => "cyclomatic",
+        "exit" | "nexit" => "nexits",
+        _ => id,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suppression_markers() {
+        let code = "let a = 1; // sca-ignore\n\
+                    -- sca-ignore-next-line: cc, Long-Function */\n\
+                    let b = 2;\n\
+                    let s = \"sca-ignore\";\n\
+                    % sca-ignored\n\
+                    /* sca-ignore: nexit */ let c = 3; // sca-ignore: cognitive\n";
+        let suppressions = Suppressions::new(code.as_bytes());
+
+        assert_eq!(suppressions.rules(1), [SUPPRESS_ALL]);
+        assert!(suppressions.rules(2).is_empty());
+        assert_eq!(suppressions.rules(3), ["cyclomatic", "long-function"]);
+        assert!(suppressions.is_suppressed(3, "cc"));
+        assert!(!suppressions.is_suppressed(3, "halstead"));
+        assert!(suppressions.rules(4).is_empty());
+        assert!(suppressions.rules(5).is_empty());
+        assert_eq!(suppressions.rules(6), ["nexits", "cognitive"]);
+        assert!(Suppressions::new(b"fn f() {}").is_empty());
+    }
+}
@@ -0,0 +1,109 @@
+use serde::Serialize;
+
+use crate::node::Node;
+
+/// The kinds of syntax errors found by the parser.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParseErrorKind {
+    /// Some code which couldn't be parsed
+    Error,
+    /// A token which is missing, like a closing brace
+    Missing,
+}
+
+/// A syntax error of a code.
+///
+/// The metrics of a code with syntax errors are computed on its intact
+/// subtrees only, so they may be lower than the ones of the fixed code.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ParseError {
+    /// The kind of the error
+    pub kind: ParseErrorKind,
+    /// The first line of the error
+    pub start_line: usize,
+    /// The column of the first character of the error
+    pub start_column: usize,
+    /// The last line of the error
+    pub end_line: usize,
+    /// The column following the last character of the error
+    pub end_column: usize,
+}
+
+impl ParseError {
+    fn new(node: &Node, kind: ParseErrorKind) -> Self {
+        let (start_line, start_column) = node.start_position();
+        let (end_line, end_column) = node.end_position();
+        Self {
+            kind,
+            start_line: start_line + 1,
+            start_column: start_column + 1,
+            end_line: end_line + 1,
+            end_column: end_column + 1,
+        }
+    }
+}
+
+/// Returns the syntax errors of a tree, in source order.
+///
+/// The errors nested in an `ERROR` node aren't reported, since they are
+/// part of it.
+pub(crate) fn parse_errors(root: &Node) -> Vec<ParseError> {
+    let mut errors = Vec::new();
+    let mut stack = vec![*root];
+    while let Some(node) = stack.pop() {
+        if node.is_error() {
+            errors.push(ParseError::new(&node, ParseErrorKind::Error));
+        } else if node.is_missing() {
+            errors.push(ParseError::new(&node, ParseErrorKind::Missing));
+        } else if node.has_error() {
+            let mut children = node.children().collect::<Vec<_>>();
+            children.reverse();
+            stack.extend(children);
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{metrics, ParserEngineRust, ParserTrait};
+
+    use super::*;
+
+    fn errors(code: &str) -> Vec<ParseError> {
+        let parser = ParserEngineRust::new(code.as_bytes().to_vec(), Path::new("foo.rs"), None);
+        parse_errors(&parser.get_root())
+    }
+
+    #[test]
+    fn syntax_errors() {
+        assert!(errors("fn f() { let x = 1; }").is_empty());
+
+        let missing = errors("fn f() {\n    let x = 1\n}");
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].kind, ParseErrorKind::Missing);
+        assert_eq!(missing[0].start_line, 2);
+
+        let error = errors("fn f() { if }\nfn g() {}\n");
+        assert!(!error.is_empty());
+        assert_eq!(error[0].start_line, 1);
+    }
+
+    #[test]
+    fn partial_metrics() {
+        let path = Path::new("foo.rs");
+        let parser = ParserEngineRust::new(
+            b"fn f(x: i32) { if x > 0 {} }\nfn g( {\nfn h() {}\n".to_vec(),
+            path,
+            None,
+        );
+        let space = metrics(&parser, path).unwrap();
+
+        assert!(!space.parse_errors.is_empty());
+        assert!(space.metrics.nom.functions_sum() >= 1.);
+        assert!(space.metrics.cyclomatic.cyclomatic_sum() >= 2.);
+    }
+}
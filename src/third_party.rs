@@ -0,0 +1,93 @@
+use std::path::Path;
+
+/// The names of the directories containing vendored code or the
+/// dependencies installed by a package manager.
+pub const THIRD_PARTY_DIRS: &[&str] = &[
+    "vendor",
+    "vendored",
+    "third_party",
+    "third-party",
+    "thirdparty",
+    "external",
+    // JavaScript
+    "node_modules",
+    "bower_components",
+    // Python
+    "site-packages",
+    ".venv",
+    "venv",
+    // Elixir and Erlang
+    "deps",
+    "_build",
+    // Swift and Objective-C
+    "Pods",
+    "Carthage",
+    // Go
+    "Godeps",
+];
+
+// A git submodule has a `.git` file instead of a `.git` directory
+fn is_submodule(dir: &Path) -> bool {
+    dir.join(".git").is_file()
+}
+
+/// Returns `true` if a directory contains third-party code: a git
+/// submodule or one of the [`THIRD_PARTY_DIRS`].
+pub fn is_third_party_dir(dir: &Path) -> bool {
+    dir.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| THIRD_PARTY_DIRS.contains(&name))
+        || is_submodule(dir)
+}
+
+/// Returns `true` if a file is third-party code, i.e. one of its
+/// ancestor directories is.
+///
+/// The ancestors are checked up to the root of the git repository
+/// containing the file, if any.
+pub fn is_third_party(path: &Path) -> bool {
+    for dir in path.ancestors().skip(1) {
+        if dir.as_os_str().is_empty() {
+            break;
+        }
+        if is_third_party_dir(dir) {
+            return true;
+        }
+        if dir.join(".git").is_dir() {
+            break;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn third_party_paths() {
+        assert!(is_third_party(Path::new("src/vendor/lib/a.rs")));
+        assert!(is_third_party(Path::new("web/node_modules/react/index.js")));
+        assert!(is_third_party(Path::new("deps/jason/lib/jason.ex")));
+        assert!(!is_third_party(Path::new("src/vendors.rs")));
+        assert!(!is_third_party(Path::new("src/lib/a.rs")));
+        assert!(!is_third_party(Path::new("a.rs")));
+    }
+
+    #[test]
+    fn submodules() {
+        let root = std::env::temp_dir().join(format!("sca-third-party-{}", std::process::id()));
+        let submodule = root.join("libs/sub");
+        fs::create_dir_all(&submodule).unwrap();
+        fs::write(submodule.join(".git"), "gitdir: ../../.git/modules/sub\n").unwrap();
+
+        let third_party = is_third_party(&submodule.join("a.rs"));
+        let first_party = is_third_party(&root.join("libs/a.rs"));
+        fs::remove_dir_all(&root).unwrap();
+
+        assert!(third_party);
+        assert!(!first_party);
+    }
+}
@@ -0,0 +1,179 @@
+use serde::Serialize;
+
+use crate::{
+    chunking::estimate_tokens,
+    position::SourceMap,
+    spaces::{FuncSpace, SpaceKind},
+};
+
+/// A tokenizer of a language model, counting the tokens of a text.
+pub trait Tokenizer {
+    /// Returns the number of tokens of a text.
+    fn count_tokens(&self, text: &str) -> usize;
+}
+
+/// The tokenizer estimating the tokens as one every four characters,
+/// which doesn't need any vocabulary.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EstimateTokenizer;
+
+impl Tokenizer for EstimateTokenizer {
+    fn count_tokens(&self, text: &str) -> usize {
+        estimate_tokens(text)
+    }
+}
+
+/// A tokenizer of the `OpenAI` models, by `tiktoken`.
+#[cfg(feature = "tiktoken-rs")]
+pub struct TiktokenTokenizer(tiktoken_rs::CoreBPE);
+
+#[cfg(feature = "tiktoken-rs")]
+impl TiktokenTokenizer {
+    /// Creates the `cl100k_base` tokenizer, of the `GPT-4` and
+    /// `GPT-3.5` models.
+    pub fn cl100k() -> std::io::Result<Self> {
+        tiktoken_rs::cl100k_base()
+            .map(Self)
+            .map_err(std::io::Error::other)
+    }
+
+    /// Creates the `o200k_base` tokenizer, of the `GPT-4o` models.
+    pub fn o200k() -> std::io::Result<Self> {
+        tiktoken_rs::o200k_base()
+            .map(Self)
+            .map_err(std::io::Error::other)
+    }
+}
+
+#[cfg(feature = "tiktoken-rs")]
+impl Tokenizer for TiktokenTokenizer {
+    fn count_tokens(&self, text: &str) -> usize {
+        self.0.encode_ordinary(text).len()
+    }
+}
+
+/// A tokenizer of the `HuggingFace` models, loaded from a
+/// `tokenizer.json` file.
+#[cfg(feature = "tokenizers")]
+pub struct HuggingFaceTokenizer(tokenizers::Tokenizer);
+
+#[cfg(feature = "tokenizers")]
+impl HuggingFaceTokenizer {
+    /// Loads a tokenizer from a `tokenizer.json` file.
+    pub fn from_file(path: &std::path::Path) -> std::io::Result<Self> {
+        tokenizers::Tokenizer::from_file(path)
+            .map(Self)
+            .map_err(std::io::Error::other)
+    }
+}
+
+#[cfg(feature = "tokenizers")]
+impl Tokenizer for HuggingFaceTokenizer {
+    fn count_tokens(&self, text: &str) -> usize {
+        // Encoding fails only on texts the normalizer rejects
+        self.0
+            .encode(text, false)
+            .map_or(0, |encoding| encoding.len())
+    }
+}
+
+/// The tokens of a function space.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SpaceTokens {
+    /// The name of the function space
+    pub name: Option<String>,
+    /// The kind of the function space
+    pub kind: SpaceKind,
+    /// The first line of the function space
+    pub start_line: usize,
+    /// The last line of the function space
+    pub end_line: usize,
+    /// The number of tokens of the function space
+    pub tokens: usize,
+    /// The source lines of the function space
+    pub sloc: f64,
+    /// The tokens of the subspaces
+    pub spaces: Vec<SpaceTokens>,
+}
+
+fn space_tokens(space: &FuncSpace, lines: &SourceMap, tokenizer: &dyn Tokenizer) -> SpaceTokens {
+    let tokens = lines
+        .snippet(space.start_line, space.end_line, 0)
+        .map_or(0, |snippet| tokenizer.count_tokens(&snippet.text));
+    SpaceTokens {
+        name: space.name.clone(),
+        kind: space.kind,
+        start_line: space.start_line,
+        end_line: space.end_line,
+        tokens,
+        sloc: space.metrics.loc.sloc(),
+        spaces: space
+            .spaces
+            .iter()
+            .map(|space| space_tokens(space, lines, tokenizer))
+            .collect(),
+    }
+}
+
+/// Counts the tokens of a file and of each of its function spaces.
+///
+/// The tokens of the file are the ones of the whole code, and the tokens
+/// of a space are the ones of its lines.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{
+///     count_tokens, metrics, EstimateTokenizer, ParserEngineRust, ParserTrait,
+/// };
+///
+/// let path = Path::new("foo.rs");
+/// let code = b"fn main() {\n    println!(\"Hello\");\n}\n".to_vec();
+/// let parser = ParserEngineRust::new(code.clone(), path, None);
+/// let space = metrics(&parser, path).unwrap();
+///
+/// let tokens = count_tokens(&space, &code, &EstimateTokenizer);
+/// assert_eq!(tokens.tokens, 10);
+/// assert_eq!(tokens.spaces[0].tokens, 9);
+/// ```
+pub fn count_tokens(space: &FuncSpace, code: &[u8], tokenizer: &dyn Tokenizer) -> SpaceTokens {
+    let mut tokens = space_tokens(space, &SourceMap::new(code), tokenizer);
+    tokens.tokens = tokenizer.count_tokens(&String::from_utf8_lossy(code));
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{metrics, ParserTrait, PythonParser};
+
+    use super::*;
+
+    // Counts the words, as a stand-in for a real tokenizer
+    struct Words;
+
+    impl Tokenizer for Words {
+        fn count_tokens(&self, text: &str) -> usize {
+            text.split_whitespace().count()
+        }
+    }
+
+    #[test]
+    fn python_tokens() {
+        let code = "import os\n\nclass A:\n    def f(self):\n        return os.sep\n\ndef g():\n    pass\n";
+        let path = Path::new("foo.py");
+        let parser = PythonParser::new(code.as_bytes().to_vec(), path, None);
+        let space = metrics(&parser, path).unwrap();
+
+        let tokens = count_tokens(&space, code.as_bytes(), &Words);
+        assert_eq!(tokens.tokens, 11);
+        let class = &tokens.spaces[0];
+        assert_eq!((class.kind, class.tokens), (SpaceKind::Class, 6));
+        assert_eq!(class.spaces[0].name.as_deref(), Some("f"));
+        assert_eq!(class.spaces[0].tokens, 4);
+        assert_eq!(tokens.spaces[1].tokens, 3);
+    }
+}
@@ -29,6 +29,7 @@ pub fn read_file(path: &Path) -> std::io::Result<Vec<u8>> {
     let mut data = Vec::new();
     file.read_to_end(&mut data)?;
 
+    let mut data = normalize_source(data);
     remove_blank_lines(&mut data);
 
     Ok(data)
@@ -83,6 +84,7 @@ pub fn read_file_with_eol(path: &Path) -> std::io::Result<Option<Vec<u8>>> {
 
     file.read_to_end(&mut data)?;
 
+    let mut data = normalize_source(data);
     remove_blank_lines(&mut data);
 
     Ok(Some(data))
@@ -259,6 +261,50 @@ pub(crate) fn remove_blank_lines(data: &mut Vec<u8>) {
     data.push(b'\n');
 }
 
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Normalizes a code before its analysis.
+///
+/// A leading `UTF-8` byte order mark is removed, and `\r\n` and lone `\r`
+/// line terminators are replaced with `\n`, so that lines and node texts
+/// don't depend on the platform a file was written on.
+/// The code is returned untouched when there is nothing to normalize.
+///
+/// # Examples
+///
+/// ```
+/// use singularity_code_analysis::normalize_source;
+///
+/// let code = b"\xEF\xBB\xBFa\r\nb\rc\n".to_vec();
+/// assert_eq!(normalize_source(code), b"a\nb\nc\n");
+/// ```
+pub fn normalize_source(mut code: Vec<u8>) -> Vec<u8> {
+    if code.starts_with(UTF8_BOM) {
+        code.drain(..UTF8_BOM.len());
+    }
+    if !code.contains(&b'\r') {
+        return code;
+    }
+
+    let mut write = 0;
+    let mut read = 0;
+    while read < code.len() {
+        let c = code[read];
+        read += 1;
+        if c == b'\r' {
+            if code.get(read) == Some(&b'\n') {
+                continue;
+            }
+            code[write] = b'\n';
+        } else {
+            code[write] = c;
+        }
+        write += 1;
+    }
+    code.truncate(write);
+    code
+}
+
 pub(crate) fn normalize_path<P: AsRef<Path>>(path: P) -> PathBuf {
     // Copied from Cargo sources: https://github.com/rust-lang/cargo/blob/master/src/cargo/util/paths.rs#L65
     let mut components = path.as_ref().components().peekable();
@@ -400,6 +446,13 @@ pub(crate) fn check_func_space<T: crate::ParserTrait, F: Fn(crate::FuncSpace)>(
                 name: path.to_str().map(|name| name.to_string()),
                 start_line: 1,
                 end_line: line_count,
+                start_column: 1,
+                end_column: 1,
+                fingerprint: String::new(),
+                snippet: None,
+                parse_errors: Vec::new(),
+                mi_grade: None,
+                third_party: false,
                 kind: crate::SpaceKind::Unit,
                 spaces: Vec::new(),
                 metrics: crate::CodeMetrics::default(),
@@ -444,6 +497,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_normalize_source() {
+        let data = vec![
+            (b"abc\n".to_vec(), b"abc\n".to_vec()),
+            (b"\xEF\xBB\xBFabc".to_vec(), b"abc".to_vec()),
+            (b"a\r\nb\r\n".to_vec(), b"a\nb\n".to_vec()),
+            (b"a\rb\r\r\nc\r".to_vec(), b"a\nb\n\nc\n".to_vec()),
+            (
+                "\u{FEFF}let \u{1F600} = 1;\r\n".as_bytes().to_vec(),
+                "let \u{1F600} = 1;\n".as_bytes().to_vec(),
+            ),
+        ];
+        for (d, expected) in data {
+            assert_eq!(normalize_source(d), expected);
+        }
+    }
+
+    #[test]
+    fn test_read_crlf() {
+        let tmp_path = std::env::temp_dir().join("test_read_crlf");
+        write_file(&tmp_path, b"\xEF\xBB\xBFfn f() {\r\n}\r\n\r\n").unwrap();
+        assert_eq!(read_file(&tmp_path).unwrap(), b"fn f() {\n}\n");
+        assert_eq!(
+            read_file_with_eol(&tmp_path).unwrap(),
+            Some(b"fn f() {\n}\n".to_vec())
+        );
+    }
+
     #[test]
     fn test_guess_language() {
         let buf = b"// -*- foo: bar; mode: c++; hello: world\n";
@@ -2,8 +2,9 @@ use std::{path::Path, sync::Arc};
 
 use crate::{
     abc::Abc, alterator::Alterator, checker::Checker, cognitive::Cognitive, cyclomatic::Cyclomatic,
-    exit::Exit, getter::Getter, halstead::Halstead, langs::*, loc::Loc, mi::Mi, nargs::NArgs,
-    node::Node, nom::Nom, npa::Npa, npm::Npm, parser::Filter, preproc::PreprocResults, wmc::Wmc,
+    exit::Exit, extras::Extras, generics::Generics, getter::Getter, halstead::Halstead, langs::*,
+    loc::Loc, mi::Mi, nargs::NArgs, node::Node, nom::Nom, npa::Npa, npm::Npm, parser::Filter,
+    preproc::PreprocResults, unsafety::Unsafety, wmc::Wmc,
 };
 
 /// A trait for callback functions.
@@ -39,10 +40,13 @@ pub trait ParserTrait {
     type Mi: Mi;
     type NArgs: NArgs;
     type Exit: Exit;
+    type Extras: Extras;
+    type Generics: Generics;
     type Wmc: Wmc;
     type Abc: Abc;
     type Npm: Npm;
     type Npa: Npa;
+    type Unsafety: Unsafety;
 
     fn new(code: Vec<u8>, path: &Path, pr: Option<Arc<PreprocResults>>) -> Self;
     fn get_language(&self) -> LANG;
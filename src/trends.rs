@@ -0,0 +1,279 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::spaces::FuncSpace;
+
+/// A metric whose history can be queried.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrendMetric {
+    /// The maintainability index, as computed by Visual Studio
+    Mi,
+    /// The cyclomatic complexity
+    Cyclomatic,
+    /// The Halstead estimate of the delivered bugs
+    Bugs,
+}
+
+impl TrendMetric {
+    /// Returns `true` if a higher value of the metric is worse.
+    pub fn higher_is_worse(self) -> bool {
+        match self {
+            Self::Mi => false,
+            Self::Cyclomatic | Self::Bugs => true,
+        }
+    }
+}
+
+/// The metrics of a file stored by a run of the analysis.
+///
+/// The storage of the samples is left to the caller, these are the
+/// rows any backend has to load for the trend queries.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct FileSample {
+    /// The time of the run, e.g. a Unix timestamp or a commit index
+    pub timestamp: u64,
+    /// The path of the file
+    pub path: PathBuf,
+    /// The maintainability index of the file
+    pub mi: f64,
+    /// The cyclomatic complexity of the file
+    pub cyclomatic: f64,
+    /// The Halstead estimate of the delivered bugs of the file
+    #[serde(default)]
+    pub bugs: f64,
+}
+
+impl FileSample {
+    /// Creates the sample of a file from its function spaces.
+    pub fn new(timestamp: u64, path: &Path, space: &FuncSpace) -> Self {
+        Self {
+            timestamp,
+            path: path.to_path_buf(),
+            mi: space.metrics.mi.mi_visual_studio(),
+            cyclomatic: space.metrics.cyclomatic.cyclomatic_sum(),
+            bugs: space.metrics.halstead.bugs(),
+        }
+    }
+
+    /// Returns the value of a metric.
+    pub fn value(&self, metric: TrendMetric) -> f64 {
+        match metric {
+            TrendMetric::Mi => self.mi,
+            TrendMetric::Cyclomatic => self.cyclomatic,
+            TrendMetric::Bugs => self.bugs,
+        }
+    }
+}
+
+/// The value of a metric at a given time.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct TrendPoint {
+    /// The time of the run
+    pub timestamp: u64,
+    /// The value of the metric
+    pub value: f64,
+}
+
+/// A worsening of a metric between two consecutive points of a series.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Regression {
+    /// The directory whose metric worsened
+    pub directory: PathBuf,
+    /// The point before the regression
+    pub before: TrendPoint,
+    /// The point after the regression
+    pub after: TrendPoint,
+    /// The relative change of the metric, always positive
+    pub change: f64,
+}
+
+// The first `depth` components of the directory of a file
+pub(crate) fn directory(path: &Path, depth: usize) -> PathBuf {
+    path.parent()
+        .map(|parent| parent.components().take(depth).collect())
+        .unwrap_or_default()
+}
+
+/// Returns the time series of a metric for each directory, averaging
+/// the files of a directory in each run.
+///
+/// Directories are truncated to their first `depth` components, so a
+/// depth of zero aggregates the whole project under an empty path.
+/// Non-finite values, like the maintainability index of an empty file,
+/// are ignored.
+pub fn directory_series(
+    samples: &[FileSample],
+    metric: TrendMetric,
+    depth: usize,
+) -> BTreeMap<PathBuf, Vec<TrendPoint>> {
+    let mut sums: BTreeMap<PathBuf, BTreeMap<u64, (f64, usize)>> = BTreeMap::new();
+    for sample in samples {
+        let value = sample.value(metric);
+        if !value.is_finite() {
+            continue;
+        }
+        let sum = sums
+            .entry(directory(&sample.path, depth))
+            .or_default()
+            .entry(sample.timestamp)
+            .or_default();
+        sum.0 += value;
+        sum.1 += 1;
+    }
+
+    sums.into_iter()
+        .map(|(directory, runs)| {
+            let series = runs
+                .into_iter()
+                .map(|(timestamp, (sum, count))| TrendPoint {
+                    timestamp,
+                    value: sum / count as f64,
+                })
+                .collect();
+            (directory, series)
+        })
+        .collect()
+}
+
+/// Returns the rolling average of a series over the last `window`
+/// points, including the current one.
+///
+/// The first points average the fewer points available.
+pub fn rolling_average(series: &[TrendPoint], window: usize) -> Vec<TrendPoint> {
+    let window = window.max(1);
+    series
+        .iter()
+        .enumerate()
+        .map(|(i, point)| {
+            let points = &series[(i + 1).saturating_sub(window)..=i];
+            TrendPoint {
+                timestamp: point.timestamp,
+                value: points.iter().map(|point| point.value).sum::<f64>() / points.len() as f64,
+            }
+        })
+        .collect()
+}
+
+/// Returns the regressions of a metric in the series of each directory.
+///
+/// A regression is a worsening between two consecutive runs greater
+/// than `tolerance`, relative to the value of the first run, e.g. a
+/// tolerance of `0.1` ignores changes up to 10%.
+pub fn detect_regressions(
+    series: &BTreeMap<PathBuf, Vec<TrendPoint>>,
+    metric: TrendMetric,
+    tolerance: f64,
+) -> Vec<Regression> {
+    let mut regressions = Vec::new();
+    for (directory, points) in series {
+        for pair in points.windows(2) {
+            let (before, after) = (pair[0], pair[1]);
+            let delta = if metric.higher_is_worse() {
+                after.value - before.value
+            } else {
+                before.value - after.value
+            };
+            let change = if before.value == 0. {
+                if delta > 0. {
+                    f64::INFINITY
+                } else {
+                    0.
+                }
+            } else {
+                delta / before.value.abs()
+            };
+            if change > tolerance {
+                regressions.push(Regression {
+                    directory: directory.clone(),
+                    before,
+                    after,
+                    change,
+                });
+            }
+        }
+    }
+    regressions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(timestamp: u64, path: &str, mi: f64, cyclomatic: f64) -> FileSample {
+        FileSample {
+            timestamp,
+            path: PathBuf::from(path),
+            mi,
+            cyclomatic,
+            bugs: cyclomatic / 10.,
+        }
+    }
+
+    #[test]
+    fn trends_directory_series() {
+        let samples = [
+            sample(1, "src/a.rs", 80., 4.),
+            sample(1, "src/b.rs", 60., 8.),
+            sample(1, "tests/t.rs", 90., 1.),
+            sample(2, "src/a.rs", 70., 5.),
+            sample(2, "src/b.rs", 50., 13.),
+            sample(2, "tests/t.rs", f64::NAN, 1.),
+            sample(3, "src/a.rs", 72., 5.),
+        ];
+
+        let series = directory_series(&samples, TrendMetric::Cyclomatic, 1);
+        assert_eq!(
+            series[Path::new("src")]
+                .iter()
+                .map(|point| (point.timestamp, point.value))
+                .collect::<Vec<_>>(),
+            [(1, 6.), (2, 9.), (3, 5.)]
+        );
+        assert_eq!(series[Path::new("tests")].len(), 2);
+
+        let series = directory_series(&samples, TrendMetric::Mi, 0);
+        assert_eq!(series.len(), 1);
+        assert_eq!(series[Path::new("")][1].value, 60.);
+
+        let rolling = rolling_average(&series[Path::new("")], 2);
+        assert_eq!(
+            rolling.iter().map(|point| point.value).collect::<Vec<_>>(),
+            [
+                (80. + 60. + 90.) / 3.,
+                ((80. + 60. + 90.) / 3. + 60.) / 2.,
+                (60. + 72.) / 2.
+            ]
+        );
+    }
+
+    #[test]
+    fn trends_detect_regressions() {
+        let samples = [
+            sample(1, "src/a.rs", 80., 4.),
+            sample(2, "src/a.rs", 78., 6.),
+            sample(3, "src/a.rs", 60., 6.),
+        ];
+
+        let series = directory_series(&samples, TrendMetric::Cyclomatic, 1);
+        let regressions = detect_regressions(&series, TrendMetric::Cyclomatic, 0.1);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].directory, Path::new("src"));
+        assert_eq!(regressions[0].after.timestamp, 2);
+        assert_eq!(regressions[0].change, 0.5);
+
+        let series = directory_series(&samples, TrendMetric::Mi, 1);
+        let regressions = detect_regressions(&series, TrendMetric::Mi, 0.1);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].after.timestamp, 3);
+
+        let series = directory_series(&samples, TrendMetric::Bugs, 1);
+        let regressions = detect_regressions(&series, TrendMetric::Bugs, 0.1);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].after.timestamp, 2);
+    }
+}
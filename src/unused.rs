@@ -0,0 +1,634 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    path::Path,
+};
+
+use serde::Serialize;
+
+use crate::{
+    ai::{
+        CodeLocation, CodeSmell, SmellCatalog, SmellMetadata, SmellReport, UNUSED_PARAMETER,
+        UNUSED_VARIABLE,
+    },
+    checker::Checker,
+    getter::Getter,
+    liveness::function_liveness,
+    node::Node,
+    traits::ParserTrait,
+};
+
+/// The kind of a binding which is never read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BindingKind {
+    /// A parameter of a function
+    Parameter,
+    /// A local variable of a function
+    Variable,
+}
+
+// The nodes declaring local variables, with the field of their bound names
+pub(crate) const DECLARATIONS: &[(&str, &str)] = &[
+    // Rust
+    ("let_declaration", "pattern"),
+    ("for_expression", "pattern"),
+    // Python
+    ("assignment", "left"),
+    ("for_statement", "left"),
+    // JavaScript, Java and C#
+    ("variable_declarator", "name"),
+    ("for_in_statement", "left"),
+    ("enhanced_for_statement", "name"),
+    ("for_each_statement", "left"),
+    // C++
+    ("init_declarator", "declarator"),
+    ("declaration", "declarator"),
+    // Go
+    ("short_var_declaration", "left"),
+    ("var_spec", "name"),
+];
+
+// The fields of a declaration which contain no bound names
+const NOT_BOUND: &[&str] = &[
+    "type",
+    "value",
+    "default",
+    "default_value",
+    "right",
+    "return_type",
+    "body",
+];
+
+// The nodes reading an object rather than binding a name, like `a.b = 1`
+fn is_access(node: &Node) -> bool {
+    let kind = node.kind();
+    [
+        "attribute",
+        "subscript",
+        "member",
+        "field",
+        "index",
+        "selector",
+        "call",
+    ]
+    .iter()
+    .any(|access| kind.contains(access))
+}
+
+// The names bound by a pattern or a list of parameters
+pub(crate) fn bound_names<'a>(node: Node<'a>, names: &mut Vec<Node<'a>>) {
+    if node.kind() == "identifier" {
+        names.push(node);
+        return;
+    }
+    if is_access(&node) {
+        return;
+    }
+    let excluded = NOT_BOUND
+        .iter()
+        .filter_map(|field| node.child_by_field_name(field))
+        .map(|child| child.id())
+        .collect::<Vec<_>>();
+    for child in node.children() {
+        if !excluded.contains(&child.id()) {
+            bound_names(child, names);
+        }
+    }
+}
+
+pub(crate) fn parameters<'a>(function: &Node<'a>) -> Option<Node<'a>> {
+    function.child_by_field_name("parameters").or_else(|| {
+        // `void f(int x)` in C++
+        function
+            .child_by_field_name("declarator")?
+            .child_by_field_name("parameters")
+    })
+}
+
+// Returns `true` if the parameters of a function are imposed by an
+// overridden method or a trait
+fn is_override(function: &Node, code: &[u8]) -> bool {
+    let header_end = function
+        .child_by_field_name("body")
+        .map_or(function.end_byte(), |body| body.start_byte());
+    let header = String::from_utf8_lossy(&code[function.start_byte()..header_end]);
+    if header.contains("override") || header.contains("Override") {
+        return true;
+    }
+    let mut ancestor = function.parent();
+    while let Some(node) = ancestor {
+        match node.kind() {
+            // A method of a trait implementation in Rust
+            "impl_item" => return node.child_by_field_name("trait").is_some(),
+            "decorated_definition" => {
+                return node.utf8_text(code).is_some_and(|text| {
+                    text.contains("override") || text.contains("abstractmethod")
+                })
+            }
+            _ => ancestor = node.parent(),
+        }
+        if ancestor.is_some_and(|node| node.kind().contains("class")) {
+            break;
+        }
+    }
+    false
+}
+
+// Returns `true` if the body of a function is a placeholder, like `pass`
+// or `todo!()`
+fn is_stub<T: ParserTrait>(body: &Node, code: &[u8]) -> bool {
+    let statements = body
+        .children()
+        .filter(|child| child.is_named() && !T::Checker::is_comment(child))
+        .collect::<Vec<_>>();
+    match statements.as_slice() {
+        [] => true,
+        [statement] => statement.utf8_text(code).is_some_and(|text| {
+            [
+                "pass",
+                "...",
+                "raise",
+                "throw",
+                "todo!",
+                "unimplemented!",
+                "panic!",
+            ]
+            .iter()
+            .any(|stub| text.starts_with(stub))
+                || T::Checker::is_string(statement)
+                || is_docstring::<T>(statement)
+        }),
+        _ => false,
+    }
+}
+
+// Returns `true` if a statement is made of string literals only
+fn is_docstring<T: ParserTrait>(statement: &Node) -> bool {
+    let parts = statement
+        .children()
+        .filter(|child| child.is_named())
+        .collect::<Vec<_>>();
+    !parts.is_empty() && parts.iter().all(|part| T::Checker::is_string(part))
+}
+
+// The Rust macros whose format strings may capture variables, like
+// `println!("{x}")`
+const FORMAT_MACROS: &[&str] = &[
+    "format",
+    "format_args",
+    "print",
+    "println",
+    "eprint",
+    "eprintln",
+    "write",
+    "writeln",
+    "panic",
+    "assert",
+    "assert_eq",
+    "assert_ne",
+    "debug_assert",
+    "debug_assert_eq",
+    "debug_assert_ne",
+    "todo",
+    "unimplemented",
+    "unreachable",
+    "trace",
+    "debug",
+    "info",
+    "warn",
+    "error",
+];
+
+// The variables captured by a format string, like `x` and `width` in
+// `"{x:>width$}"`
+fn format_captures(text: &str) -> Vec<&str> {
+    let is_name = |name: &str| {
+        name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+    };
+    let mut captures = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start + 1..];
+        if let Some(escaped) = rest.strip_prefix('{') {
+            rest = escaped;
+            continue;
+        }
+        let Some(end) = rest.find('}') else {
+            break;
+        };
+        let (argument, spec) = rest[..end].split_once(':').unwrap_or((&rest[..end], ""));
+        captures.extend(Some(argument.trim()).filter(|name| is_name(name)));
+        captures.extend(
+            spec.split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+                .filter_map(|part| part.strip_suffix('$'))
+                .filter(|name| is_name(name)),
+        );
+        rest = &rest[end + 1..];
+    }
+    captures
+}
+
+// The variables read by the format strings of a Rust macro invocation
+pub(crate) fn macro_captures<'a>(invocation: &Node<'a>, code: &'a [u8]) -> Vec<&'a str> {
+    let is_format = invocation
+        .child_by_field_name("macro")
+        .and_then(|name| name.utf8_text(code))
+        .is_some_and(|name| FORMAT_MACROS.contains(&name.rsplit("::").next().unwrap_or(name)));
+    if !is_format {
+        return Vec::new();
+    }
+    let mut captures = Vec::new();
+    let mut stack = invocation.children().collect::<Vec<_>>();
+    while let Some(node) = stack.pop() {
+        if matches!(node.kind(), "string_literal" | "raw_string_literal") {
+            captures.extend(
+                node.utf8_text(code)
+                    .map(format_captures)
+                    .unwrap_or_default(),
+            );
+        } else {
+            stack.extend(node.children());
+        }
+    }
+    captures
+}
+
+// Returns `true` if an identifier is only assigned, like `x` in `x = 1`
+fn is_assigned(node: &Node) -> bool {
+    node.parent().is_some_and(|parent| {
+        matches!(parent.kind(), "assignment" | "assignment_expression")
+            && parent
+                .child_by_field_name("left")
+                .is_some_and(|left| left.id() == node.id())
+    })
+}
+
+// The bindings of a function which are never read
+fn unused_bindings<'a, T: ParserTrait>(
+    function: &Node<'a>,
+    code: &'a [u8],
+) -> Vec<(BindingKind, Node<'a>)> {
+    let mut bindings = Vec::new();
+    if let Some(parameters) = parameters(function) {
+        let stub = function
+            .child_by_field_name("body")
+            .is_none_or(|body| is_stub::<T>(&body, code));
+        if !stub && !is_override(function, code) {
+            let mut names = Vec::new();
+            bound_names(parameters, &mut names);
+            bindings.extend(names.into_iter().map(|name| (BindingKind::Parameter, name)));
+        }
+    }
+
+    // The locals of the nested functions and closures are theirs, and the
+    // names declared by Python `global` and `nonlocal` aren't locals
+    let mut outer = HashSet::new();
+    let mut stack = function
+        .child_by_field_name("body")
+        .into_iter()
+        .collect::<Vec<_>>();
+    while let Some(node) = stack.pop() {
+        if matches!(node.kind(), "global_statement" | "nonlocal_statement") {
+            outer.extend(
+                node.children()
+                    .filter(|child| child.kind() == "identifier")
+                    .filter_map(|child| child.utf8_text(code)),
+            );
+        }
+        if let Some((_, field)) = DECLARATIONS.iter().find(|(kind, _)| *kind == node.kind()) {
+            if let Some(target) = node.child_by_field_name(field) {
+                let mut names = Vec::new();
+                bound_names(target, &mut names);
+                bindings.extend(names.into_iter().map(|name| (BindingKind::Variable, name)));
+            }
+        }
+        stack.extend(
+            node.children()
+                .filter(|child| !T::Checker::is_func(child) && !T::Checker::is_closure(child)),
+        );
+    }
+
+    // The names read anywhere in the function, the nested functions
+    // included
+    let bound = bindings
+        .iter()
+        .map(|(_, name)| name.id())
+        .collect::<HashSet<_>>();
+    let mut read = HashSet::new();
+    let mut stack = vec![*function];
+    while let Some(node) = stack.pop() {
+        if matches!(node.kind(), "identifier" | "shorthand_property_identifier")
+            && !bound.contains(&node.id())
+            && !is_assigned(&node)
+        {
+            read.extend(node.utf8_text(code));
+        }
+        if node.kind() == "macro_invocation" {
+            read.extend(macro_captures(&node, code));
+        }
+        stack.extend(node.children());
+    }
+
+    // The variables whose values are never read, like the counters which
+    // are only incremented
+    let liveness = function_liveness::<T>(function, code);
+    let dead = liveness.dead_variables();
+
+    // A name bound several times is reported at its first binding
+    bindings.sort_by_key(|(_, name)| name.start_byte());
+    let mut seen = HashSet::new();
+    bindings.retain(|(kind, name)| {
+        name.utf8_text(code).is_some_and(|text| {
+            !text.starts_with('_')
+                && !matches!(text, "self" | "cls" | "this")
+                && !outer.contains(text)
+                && (!read.contains(text) || (*kind == BindingKind::Variable && dead.contains(text)))
+                && seen.insert(text)
+        })
+    });
+    bindings
+}
+
+/// The parameters and the local variables of a code which are never
+/// read.
+#[derive(Clone, Debug, Serialize)]
+pub struct UnusedReport {
+    /// The number of reported bindings of each kind
+    pub kinds: BTreeMap<BindingKind, usize>,
+    /// The bindings, reported as smells of the [`UNUSED_PARAMETER`] and
+    /// [`UNUSED_VARIABLE`] rules
+    #[serde(flatten)]
+    pub report: SmellReport,
+}
+
+/// Finds the parameters and the local variables of the functions of a
+/// code which are never read.
+///
+/// The names are resolved per function: a binding is used when its name
+/// is read anywhere in its function, the nested functions and closures
+/// included, so a shadowed binding is never reported. A variable is
+/// unused too when none of its values is read, like a counter which is
+/// only incremented, as found by the [`Liveness`](crate::Liveness) of its
+/// function. The variables captured by the format strings of the Rust
+/// formatting macros, like `x` in `println!("{x}")`, are read, and the
+/// names declared by Python `global` and `nonlocal` aren't local variables.
+/// The names starting with `_` are left out, and so are the
+/// parameters of the overriding methods, of the trait implementations and
+/// of the functions without a body or with a placeholder one, like `pass`
+/// or `todo!()`.
+///
+/// The declarations of Rust, Python, JavaScript, TypeScript, Java, C++,
+/// C# and Go are recognized.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use singularity_code_analysis::{find_unused, ParserTrait, PythonParser, SmellCatalog};
+///
+/// let path = Path::new("foo.py");
+/// let code = b"def f(x, y):\n    z = x + 1\n    return x\n".to_vec();
+/// let parser = PythonParser::new(code, path, None);
+///
+/// let report = find_unused(&parser, path, &SmellCatalog::default());
+/// let rules = report
+///     .report
+///     .smells
+///     .iter()
+///     .map(|smell| smell.rule_id.as_str())
+///     .collect::<Vec<_>>();
+/// assert_eq!(rules, ["unused-parameter", "unused-variable"]);
+/// ```
+pub fn find_unused<T: ParserTrait>(
+    parser: &T,
+    path: &Path,
+    catalog: &SmellCatalog,
+) -> UnusedReport {
+    let code = parser.get_code();
+    let language = parser.get_language();
+    let file_path = path.to_string_lossy().to_string();
+
+    let mut bindings = Vec::new();
+    let mut stack = vec![parser.get_root()];
+    while let Some(node) = stack.pop() {
+        if T::Checker::is_func(&node) {
+            let function = T::Getter::get_func_name(&node, code).unwrap_or("<anonymous>");
+            bindings.extend(
+                unused_bindings::<T>(&node, code)
+                    .into_iter()
+                    .map(|(kind, name)| (kind, name, function)),
+            );
+        }
+        let mut children = node.children().collect::<Vec<_>>();
+        children.reverse();
+        stack.extend(children);
+    }
+    bindings.sort_by_key(|(_, name, _)| name.start_byte());
+
+    let mut metadata = SmellMetadata::for_catalog(catalog);
+    let mut smells = Vec::new();
+    for (rule_id, kind) in [
+        (UNUSED_PARAMETER, BindingKind::Parameter),
+        (UNUSED_VARIABLE, BindingKind::Variable),
+    ] {
+        let Some(rule) = catalog.enabled(rule_id) else {
+            continue;
+        };
+        let count = smells.len();
+        for (_, name, function) in bindings.iter().filter(|binding| binding.0 == kind) {
+            let (start_row, start_column) = name.start_position();
+            let (end_row, end_column) = name.end_position();
+            let name = name.utf8_text(code).unwrap_or_default();
+            let (description, suggestion) = match kind {
+                BindingKind::Parameter => (
+                    format!("Parameter `{name}` of `{function}` is never read"),
+                    "Remove the parameter, or prefix it with `_` if a caller requires it",
+                ),
+                BindingKind::Variable => (
+                    format!("Variable `{name}` of `{function}` is never read"),
+                    "Remove the variable, or keep only the side effects of its value",
+                ),
+            };
+            smells.push(CodeSmell {
+                rule_id: rule.id.clone(),
+                name: rule.name.clone(),
+                description,
+                severity: rule.severity.clone(),
+                location: CodeLocation {
+                    file_path: file_path.clone(),
+                    line_start: start_row + 1,
+                    line_end: end_row + 1,
+                    column_start: start_column + 1,
+                    column_end: end_column + 1,
+                },
+                suggestion: suggestion.to_string(),
+            });
+        }
+        metadata.record(
+            rule,
+            rule.threshold_for(Some(language)),
+            smells.len() - count,
+        );
+    }
+    smells.sort_by_key(|smell| (smell.location.line_start, smell.location.column_start));
+
+    let (smells, suppressed) = metadata.suppress(smells, code, |smell| smell);
+
+    let mut kinds = BTreeMap::new();
+    for smell in smells.iter() {
+        let kind = if smell.rule_id == UNUSED_PARAMETER {
+            BindingKind::Parameter
+        } else {
+            BindingKind::Variable
+        };
+        *kinds.entry(kind).or_insert(0) += 1;
+    }
+
+    UnusedReport {
+        kinds,
+        report: SmellReport {
+            smells,
+            suppressed,
+            metadata,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{JavascriptParser, ParserEngineRust, PythonParser};
+
+    use super::*;
+
+    fn unused<T: ParserTrait>(code: &str, path: &str) -> Vec<(String, usize)> {
+        let path = Path::new(path);
+        let parser = T::new(code.as_bytes().to_vec(), path, None);
+        find_unused(&parser, path, &SmellCatalog::default())
+            .report
+            .smells
+            .iter()
+            .map(|smell| (smell.rule_id.clone(), smell.location.line_start))
+            .collect()
+    }
+
+    fn smell(rule_id: &str, line: usize) -> (String, usize) {
+        (rule_id.to_string(), line)
+    }
+
+    #[test]
+    fn rust_unused() {
+        let code = "fn f(a: i32, b: i32, _c: i32) -> i32 {
+    let x = a + 1;
+    let (y, z) = (1, 2);
+    let w = 3;
+    let g = |v: i32| v + w;
+    g(y) + x
+}
+
+impl Trait for S {
+    fn m(&self, unused: i32) {}
+}
+
+fn todo(a: i32) {
+    todo!()
+}
+";
+        assert_eq!(
+            unused::<ParserEngineRust>(code, "foo.rs"),
+            [smell(UNUSED_PARAMETER, 1), smell(UNUSED_VARIABLE, 3)]
+        );
+    }
+
+    #[test]
+    fn dead_variables() {
+        let code = "fn f(items: &[i32]) -> i32 {
+    let mut count = 0;
+    for item in items {
+        count += item;
+    }
+    let total = 1;
+    total
+}
+";
+        assert_eq!(
+            unused::<ParserEngineRust>(code, "foo.rs"),
+            [smell(UNUSED_VARIABLE, 2)]
+        );
+    }
+
+    #[test]
+    fn python_unused() {
+        let code = "class A:
+    def m(self, a, b):
+        c = a
+        c = 2
+        for i in range(b):
+            pass
+        return None
+
+    @override
+    def n(self, a):
+        return 1
+
+    def o(self, a):
+        pass
+";
+        assert_eq!(
+            unused::<PythonParser>(code, "foo.py"),
+            [smell(UNUSED_VARIABLE, 3), smell(UNUSED_VARIABLE, 5)]
+        );
+    }
+
+    #[test]
+    fn rust_format_captures() {
+        let code = "fn f(a: i32, b: usize) {
+    let x = 1;
+    let y = 2;
+    let z = 3;
+    println!(\"{x} {y:>b$} {{z}}\", a = a);
+}
+";
+        assert_eq!(
+            unused::<ParserEngineRust>(code, "foo.rs"),
+            [smell(UNUSED_VARIABLE, 4)]
+        );
+    }
+
+    #[test]
+    fn python_outer_names() {
+        let code = "def f():
+    global X
+    X = 1
+
+def g():
+    y = 0
+    def h():
+        nonlocal y
+        y = 1
+    h()
+    return y
+";
+        assert!(unused::<PythonParser>(code, "foo.py").is_empty());
+    }
+
+    #[test]
+    fn javascript_unused() {
+        let code = "function f(a, b) {
+    const x = 1; // sca-ignore
+    let y = a;
+    return [1].map((v) => v + y);
+}
+";
+        let path = Path::new("foo.js");
+        let parser = JavascriptParser::new(code.as_bytes().to_vec(), path, None);
+        let report = find_unused(&parser, path, &SmellCatalog::default());
+        assert_eq!(report.kinds, BTreeMap::from([(BindingKind::Parameter, 1)]));
+        assert_eq!(
+            report.report.smells[0].description,
+            "Parameter `b` of `f` is never read"
+        );
+        assert_eq!(report.report.suppressed.len(), 1);
+    }
+}
@@ -0,0 +1,84 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// An in-memory map from paths to contents.
+///
+/// It lets build systems and editors analyze generated code and unsaved
+/// buffers without writing them to disk: the paths are only used to detect
+/// the language of a source and to name it in the results.
+///
+/// # Examples
+///
+/// ```
+/// use singularity_code_analysis::{SingularityCodeAnalyzer, VirtualFs};
+///
+/// let mut vfs = VirtualFs::new();
+/// vfs.insert("src/lib.rs", "fn f() {}\n");
+/// vfs.insert("src/main.py", "def g():\n    pass\n");
+///
+/// let results = SingularityCodeAnalyzer::new().analyze_virtual_fs(&vfs);
+/// assert_eq!(results.len(), 2);
+/// assert!(results.values().all(|result| result.is_ok()));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VirtualFs {
+    files: BTreeMap<PathBuf, Vec<u8>>,
+}
+
+impl VirtualFs {
+    /// Creates an empty virtual filesystem.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a file, replacing the previous contents of its path.
+    ///
+    /// Returns the previous contents, if any.
+    pub fn insert(
+        &mut self,
+        path: impl Into<PathBuf>,
+        contents: impl Into<Vec<u8>>,
+    ) -> Option<Vec<u8>> {
+        self.files.insert(path.into(), contents.into())
+    }
+
+    /// Removes a file, returning its contents.
+    pub fn remove(&mut self, path: &Path) -> Option<Vec<u8>> {
+        self.files.remove(path)
+    }
+
+    /// Returns the contents of a file.
+    pub fn get(&self, path: &Path) -> Option<&[u8]> {
+        self.files.get(path).map(Vec::as_slice)
+    }
+
+    /// Returns the number of files.
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Returns `true` if there are no files.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Iterates over the paths and contents of the files, sorted by path.
+    pub fn iter(&self) -> impl Iterator<Item = (&Path, &[u8])> {
+        self.files
+            .iter()
+            .map(|(path, contents)| (path.as_path(), contents.as_slice()))
+    }
+}
+
+impl<P: Into<PathBuf>, C: Into<Vec<u8>>> FromIterator<(P, C)> for VirtualFs {
+    fn from_iter<I: IntoIterator<Item = (P, C)>>(iter: I) -> Self {
+        Self {
+            files: iter
+                .into_iter()
+                .map(|(path, contents)| (path.into(), contents.into()))
+                .collect(),
+        }
+    }
+}
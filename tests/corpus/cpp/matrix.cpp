@@ -0,0 +1,58 @@
+#include <stdexcept>
+#include <vector>
+
+namespace linalg {
+
+// A dense matrix of doubles stored in row-major order
+class Matrix {
+public:
+    Matrix(size_t rows, size_t cols) : rows_(rows), cols_(cols), data_(rows * cols, 0.0) {}
+
+    double& at(size_t row, size_t col) {
+        check(row, col);
+        return data_[row * cols_ + col];
+    }
+
+    double at(size_t row, size_t col) const {
+        check(row, col);
+        return data_[row * cols_ + col];
+    }
+
+    Matrix operator*(const Matrix& other) const {
+        if (cols_ != other.rows_) {
+            throw std::invalid_argument("dimension mismatch");
+        }
+        Matrix result(rows_, other.cols_);
+        for (size_t i = 0; i < rows_; ++i) {
+            for (size_t j = 0; j < other.cols_; ++j) {
+                double sum = 0.0;
+                for (size_t k = 0; k < cols_; ++k) {
+                    sum += at(i, k) * other.at(k, j);
+                }
+                result.at(i, j) = sum;
+            }
+        }
+        return result;
+    }
+
+    static Matrix identity(size_t size) {
+        Matrix result(size, size);
+        for (size_t i = 0; i < size; ++i) {
+            result.at(i, i) = 1.0;
+        }
+        return result;
+    }
+
+private:
+    void check(size_t row, size_t col) const {
+        if (row >= rows_ || col >= cols_) {
+            throw std::out_of_range("index out of range");
+        }
+    }
+
+    size_t rows_;
+    size_t cols_;
+    std::vector<double> data_;
+};
+
+}  // namespace linalg
@@ -0,0 +1,61 @@
+using System;
+using System.Collections.Generic;
+using System.Linq;
+
+namespace Shop.Orders
+{
+    public enum OrderStatus { Pending, Paid, Shipped, Cancelled }
+
+    public record OrderLine(string Sku, int Quantity, decimal Price);
+
+    public class Order
+    {
+        public Guid Id { get; } = Guid.NewGuid();
+        public List<OrderLine> Lines { get; } = new();
+        public OrderStatus Status { get; set; } = OrderStatus.Pending;
+
+        public decimal Total => Lines.Sum(line => line.Quantity * line.Price);
+    }
+
+    public class OrderService
+    {
+        private readonly Dictionary<Guid, Order> _orders = new();
+
+        public Order Create(IEnumerable<OrderLine> lines)
+        {
+            var order = new Order();
+            foreach (var line in lines)
+            {
+                if (line.Quantity <= 0)
+                {
+                    throw new ArgumentException($"invalid quantity for {line.Sku}");
+                }
+                order.Lines.Add(line);
+            }
+            _orders[order.Id] = order;
+            return order;
+        }
+
+        public bool Advance(Guid id)
+        {
+            if (!_orders.TryGetValue(id, out var order))
+            {
+                return false;
+            }
+            switch (order.Status)
+            {
+                case OrderStatus.Pending:
+                    order.Status = OrderStatus.Paid;
+                    return true;
+                case OrderStatus.Paid:
+                    order.Status = OrderStatus.Shipped;
+                    return true;
+                default:
+                    return false;
+            }
+        }
+
+        public decimal Revenue() =>
+            _orders.Values.Where(o => o.Status != OrderStatus.Cancelled).Sum(o => o.Total);
+    }
+}
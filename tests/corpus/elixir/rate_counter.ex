@@ -0,0 +1,43 @@
+defmodule RateCounter do
+  @moduledoc """
+  Counts events per key over a sliding window.
+  """
+
+  use GenServer
+
+  @window_ms 60_000
+
+  def start_link(opts \\ []) do
+    GenServer.start_link(__MODULE__, %{}, opts)
+  end
+
+  def hit(server, key), do: GenServer.cast(server, {:hit, key, now()})
+
+  def count(server, key), do: GenServer.call(server, {:count, key, now()})
+
+  @impl true
+  def init(state), do: {:ok, state}
+
+  @impl true
+  def handle_cast({:hit, key, at}, state) do
+    {:noreply, Map.update(state, key, [at], &[at | prune(&1, at)])}
+  end
+
+  @impl true
+  def handle_call({:count, key, at}, _from, state) do
+    case Map.fetch(state, key) do
+      {:ok, hits} ->
+        hits = prune(hits, at)
+        {:reply, length(hits), Map.put(state, key, hits)}
+
+      :error ->
+        {:reply, 0, state}
+    end
+  end
+
+  defp prune(hits, at) do
+    Enum.filter(hits, fn hit -> at - hit < @window_ms end)
+  end
+
+  defp now, do: System.monotonic_time(:millisecond)
+end
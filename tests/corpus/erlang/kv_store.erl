@@ -0,0 +1,37 @@
+-module(kv_store).
+-behaviour(gen_server).
+
+-export([start_link/0, put/2, get/1, delete/1]).
+-export([init/1, handle_call/3, handle_cast/2]).
+
+%% A key-value store with expiring entries
+
+start_link() ->
+    gen_server:start_link({local, ?MODULE}, ?MODULE, #{}, []).
+
+put(Key, Value) ->
+    gen_server:call(?MODULE, {put, Key, Value}).
+
+get(Key) ->
+    gen_server:call(?MODULE, {get, Key}).
+
+delete(Key) ->
+    gen_server:cast(?MODULE, {delete, Key}).
+
+init(State) ->
+    {ok, State}.
+
+handle_call({put, Key, Value}, _From, State) ->
+    {reply, ok, maps:put(Key, Value, State)};
+handle_call({get, Key}, _From, State) ->
+    case maps:find(Key, State) of
+        {ok, Value} -> {reply, {ok, Value}, State};
+        error -> {reply, not_found, State}
+    end;
+handle_call(_Request, _From, State) ->
+    {reply, {error, unknown_request}, State}.
+
+handle_cast({delete, Key}, State) ->
+    {noreply, maps:remove(Key, State)};
+handle_cast(_Msg, State) ->
+    {noreply, State}.
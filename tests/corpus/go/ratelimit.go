@@ -0,0 +1,60 @@
+// Package ratelimit implements a token bucket rate limiter.
+package ratelimit
+
+import (
+	"errors"
+	"sync"
+	"time"
+)
+
+var ErrInvalidRate = errors.New("ratelimit: rate must be positive")
+
+type Bucket struct {
+	mu       sync.Mutex
+	capacity float64
+	tokens   float64
+	rate     float64
+	last     time.Time
+}
+
+func New(capacity, rate float64) (*Bucket, error) {
+	if rate <= 0 || capacity <= 0 {
+		return nil, ErrInvalidRate
+	}
+	return &Bucket{capacity: capacity, tokens: capacity, rate: rate, last: time.Now()}, nil
+}
+
+func (b *Bucket) refill(now time.Time) {
+	elapsed := now.Sub(b.last).Seconds()
+	b.tokens += elapsed * b.rate
+	if b.tokens > b.capacity {
+		b.tokens = b.capacity
+	}
+	b.last = now
+}
+
+// Allow reports whether n tokens can be taken now.
+func (b *Bucket) Allow(n float64) bool {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+
+	b.refill(time.Now())
+	if b.tokens < n {
+		return false
+	}
+	b.tokens -= n
+	return true
+}
+
+// Wait blocks until n tokens are available.
+func (b *Bucket) Wait(n float64) {
+	for {
+		if b.Allow(n) {
+			return
+		}
+		b.mu.Lock()
+		missing := n - b.tokens
+		b.mu.Unlock()
+		time.Sleep(time.Duration(missing / b.rate * float64(time.Second)))
+	}
+}
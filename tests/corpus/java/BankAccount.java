@@ -0,0 +1,63 @@
+package com.example.bank;
+
+import java.util.ArrayList;
+import java.util.List;
+
+/** A bank account keeping the history of its transactions. */
+public class BankAccount {
+    private final String owner;
+    private long balance;
+    private final List<Long> history = new ArrayList<>();
+
+    public BankAccount(String owner, long initial) {
+        if (initial < 0) {
+            throw new IllegalArgumentException("negative initial balance");
+        }
+        this.owner = owner;
+        this.balance = initial;
+    }
+
+    public String getOwner() {
+        return owner;
+    }
+
+    public long getBalance() {
+        return balance;
+    }
+
+    public void deposit(long amount) {
+        if (amount <= 0) {
+            throw new IllegalArgumentException("amount must be positive");
+        }
+        balance += amount;
+        history.add(amount);
+    }
+
+    public boolean withdraw(long amount) {
+        // Overdrafts aren't allowed
+        if (amount <= 0 || amount > balance) {
+            return false;
+        }
+        balance -= amount;
+        history.add(-amount);
+        return true;
+    }
+
+    public boolean transfer(BankAccount to, long amount) {
+        if (to == this || !withdraw(amount)) {
+            return false;
+        }
+        to.deposit(amount);
+        return true;
+    }
+
+    public long largestWithdrawal() {
+        long largest = 0;
+        for (long amount : history) {
+            if (amount < 0 && -amount > largest) {
+                largest = -amount;
+            }
+        }
+        return largest;
+    }
+}
@@ -0,0 +1,59 @@
+'use strict';
+
+// A minimal HTTP router matching paths with parameters
+class Router {
+  constructor() {
+    this.routes = [];
+  }
+
+  add(method, pattern, handler) {
+    const keys = [];
+    const regex = new RegExp(
+      '^' +
+        pattern.replace(/:(\w+)/g, (_, key) => {
+          keys.push(key);
+          return '([^/]+)';
+        }) +
+        '$'
+    );
+    this.routes.push({ method, regex, keys, handler });
+    return this;
+  }
+
+  match(method, path) {
+    for (const route of this.routes) {
+      if (route.method !== method && route.method !== '*') {
+        continue;
+      }
+      const found = route.regex.exec(path);
+      if (found) {
+        const params = {};
+        route.keys.forEach((key, i) => {
+          params[key] = decodeURIComponent(found[i + 1]);
+        });
+        return { handler: route.handler, params };
+      }
+    }
+    return null;
+  }
+}
+
+function notFound(req, res) {
+  res.statusCode = 404;
+  res.end('Not found');
+}
+
+async function handle(router, req, res) {
+  const match = router.match(req.method, req.url.split('?')[0]);
+  if (!match) {
+    return notFound(req, res);
+  }
+  try {
+    await match.handler(req, res, match.params);
+  } catch (err) {
+    res.statusCode = err.status || 500;
+    res.end(err.message);
+  }
+}
+
+module.exports = { Router, handle };
@@ -0,0 +1,53 @@
+-- A stack based calculator for postfix expressions
+
+local Stack = {}
+Stack.__index = Stack
+
+function Stack.new()
+  return setmetatable({ items = {}, size = 0 }, Stack)
+end
+
+function Stack:push(value)
+  self.size = self.size + 1
+  self.items[self.size] = value
+end
+
+function Stack:pop()
+  if self.size == 0 then
+    error("stack underflow")
+  end
+  local value = self.items[self.size]
+  self.items[self.size] = nil
+  self.size = self.size - 1
+  return value
+end
+
+local operators = {
+  ["+"] = function(a, b) return a + b end,
+  ["-"] = function(a, b) return a - b end,
+  ["*"] = function(a, b) return a * b end,
+  ["/"] = function(a, b)
+    if b == 0 then
+      error("division by zero")
+    end
+    return a / b
+  end,
+}
+
+local function evaluate(expression)
+  local stack = Stack.new()
+  for token in expression:gmatch("%S+") do
+    local operator = operators[token]
+    if operator then
+      local b, a = stack:pop(), stack:pop()
+      stack:push(operator(a, b))
+    elseif tonumber(token) then
+      stack:push(tonumber(token))
+    else
+      error("unknown token " .. token)
+    end
+  end
+  return stack:pop()
+end
+
+return { Stack = Stack, evaluate = evaluate }
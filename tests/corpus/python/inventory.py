@@ -0,0 +1,61 @@
+"""Inventory management for a small shop."""
+
+from dataclasses import dataclass, field
+
+
+class OutOfStock(Exception):
+    pass
+
+
+@dataclass
+class Item:
+    name: str
+    price: float
+    quantity: int = 0
+    tags: list = field(default_factory=list)
+
+    def total(self):
+        return self.price * self.quantity
+
+
+class Inventory:
+    def __init__(self):
+        self._items = {}
+
+    def add(self, name, price, quantity=1):
+        # Merges the quantities of existing items
+        if name in self._items:
+            self._items[name].quantity += quantity
+        else:
+            self._items[name] = Item(name, price, quantity)
+
+    def remove(self, name, quantity=1):
+        item = self._items.get(name)
+        if item is None or item.quantity < quantity:
+            raise OutOfStock(name)
+        item.quantity -= quantity
+        if item.quantity == 0:
+            del self._items[name]
+
+    def value(self):
+        return sum(item.total() for item in self._items.values())
+
+    def cheapest(self, count=3):
+        items = sorted(self._items.values(), key=lambda item: item.price)
+        return [item.name for item in items[:count]]
+
+    def report(self):
+        lines = []
+        for name, item in sorted(self._items.items()):
+            status = "low" if item.quantity < 5 else "ok"
+            lines.append(f"{name}: {item.quantity} ({status})")
+        return "\n".join(lines)
+
+
+def restock(inventory, orders):
+    for name, price, quantity in orders:
+        try:
+            inventory.add(name, price, quantity)
+        except (TypeError, ValueError):
+            continue
+    return inventory
@@ -0,0 +1,80 @@
+//! A least recently used cache.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A cache keeping at most `capacity` entries.
+pub struct LruCache<K, V> {
+    capacity: usize,
+    tick: u64,
+    entries: HashMap<K, (V, u64)>,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity must be positive");
+        Self {
+            capacity,
+            tick: 0,
+            entries: HashMap::with_capacity(capacity),
+        }
+    }
+
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        self.tick += 1;
+        let tick = self.tick;
+        match self.entries.get_mut(key) {
+            Some((value, used)) => {
+                *used = tick;
+                Some(value)
+            }
+            None => None,
+        }
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.tick += 1;
+        if !self.entries.contains_key(&key) && self.entries.len() == self.capacity {
+            self.evict();
+        }
+        self.entries
+            .insert(key, (value, self.tick))
+            .map(|(old, _)| old)
+    }
+
+    fn evict(&mut self) {
+        // Removes the least recently used entry
+        let oldest = self
+            .entries
+            .iter()
+            .min_by_key(|(_, (_, used))| *used)
+            .map(|(key, _)| key.clone());
+        if let Some(key) = oldest {
+            self.entries.remove(&key);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_oldest() {
+        let mut cache = LruCache::new(2);
+        cache.insert(1, "a");
+        cache.insert(2, "b");
+        cache.get(&1);
+        cache.insert(3, "c");
+        assert!(cache.get(&2).is_none());
+        assert_eq!(cache.len(), 2);
+    }
+}
@@ -0,0 +1,52 @@
+import React, { useState } from "react";
+
+interface Todo {
+  id: number;
+  text: string;
+  done: boolean;
+}
+
+type Filter = "all" | "active" | "done";
+
+export function TodoList({ initial }: { initial: Todo[] }) {
+  const [todos, setTodos] = useState<Todo[]>(initial);
+  const [filter, setFilter] = useState<Filter>("all");
+  const [text, setText] = useState("");
+
+  const visible = todos.filter((todo) => {
+    if (filter === "active") {
+      return !todo.done;
+    }
+    return filter === "done" ? todo.done : true;
+  });
+
+  function add() {
+    if (text.trim() === "") {
+      return;
+    }
+    setTodos([...todos, { id: Date.now(), text, done: false }]);
+    setText("");
+  }
+
+  const toggle = (id: number) =>
+    setTodos(todos.map((todo) => (todo.id === id ? { ...todo, done: !todo.done } : todo)));
+
+  return (
+    <div>
+      <input value={text} onChange={(e) => setText(e.target.value)} />
+      <button onClick={add}>Add</button>
+      <select value={filter} onChange={(e) => setFilter(e.target.value as Filter)}>
+        <option value="all">All</option>
+        <option value="active">Active</option>
+        <option value="done">Done</option>
+      </select>
+      <ul>
+        {visible.map((todo) => (
+          <li key={todo.id} onClick={() => toggle(todo.id)}>
+            {todo.done ? <s>{todo.text}</s> : todo.text}
+          </li>
+        ))}
+      </ul>
+    </div>
+  );
+}
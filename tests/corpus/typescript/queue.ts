@@ -0,0 +1,56 @@
+export interface Job<T> {
+  id: string;
+  payload: T;
+  attempts: number;
+}
+
+export type Worker<T> = (job: Job<T>) => Promise<void>;
+
+/** A job queue retrying failed jobs with an exponential backoff. */
+export class RetryQueue<T> {
+  private readonly pending: Job<T>[] = [];
+  private running = 0;
+
+  constructor(
+    private readonly worker: Worker<T>,
+    private readonly concurrency = 2,
+    private readonly maxAttempts = 3,
+  ) {}
+
+  push(id: string, payload: T): void {
+    this.pending.push({ id, payload, attempts: 0 });
+    this.next();
+  }
+
+  get size(): number {
+    return this.pending.length + this.running;
+  }
+
+  private next(): void {
+    while (this.running < this.concurrency && this.pending.length > 0) {
+      const job = this.pending.shift()!;
+      this.running++;
+      this.run(job).finally(() => {
+        this.running--;
+        this.next();
+      });
+    }
+  }
+
+  private async run(job: Job<T>): Promise<void> {
+    try {
+      await this.worker(job);
+    } catch (error) {
+      job.attempts++;
+      if (job.attempts < this.maxAttempts) {
+        const delay = 2 ** job.attempts * 100;
+        setTimeout(() => {
+          this.pending.push(job);
+          this.next();
+        }, delay);
+      } else {
+        console.error(`job ${job.id} failed`, error);
+      }
+    }
+  }
+}
@@ -0,0 +1,110 @@
+use std::path::Path;
+
+use singularity_code_analysis::{get_function_spaces, guess_language, read_file_with_eol};
+
+const CORPUS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/corpus");
+
+/// Compares the metrics of the corpus files of a language with their
+/// committed snapshots.
+///
+/// Run `cargo insta test --accept` to update the snapshots after an
+/// intended change, e.g. a grammar bump.
+fn check_corpus(language: &str) {
+    let dir = Path::new(CORPUS).join(language);
+    let mut paths = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect::<Vec<_>>();
+    paths.sort();
+    assert!(!paths.is_empty(), "no corpus files for {language}");
+
+    for path in paths {
+        let source = read_file_with_eol(&path).unwrap().unwrap();
+        let lang = guess_language(&source, &path).0.unwrap();
+        let space = get_function_spaces(&lang, source, &path, None).unwrap();
+
+        insta::with_settings!({
+            snapshot_path => Path::new(CORPUS).join("snapshots").join(language),
+            prepend_module_to_snapshot => false,
+            sort_maps => true,
+        }, {
+            insta::assert_yaml_snapshot!(
+                path.file_name().unwrap().to_string_lossy().as_ref(),
+                space,
+                {
+                    // Round floating point values to three decimal places since the can differ from
+                    // system to system.
+                    ".spaces[].**.metrics.*.*" => insta::rounded_redaction(3),
+                    ".metrics.*.*" => insta::rounded_redaction(3),
+                    // Redact away the name since paths are different on different systems.
+                    ".name" => "[filepath]",
+                }
+            );
+        });
+    }
+}
+
+#[test]
+fn corpus_rust() {
+    check_corpus("rust");
+}
+
+#[test]
+fn corpus_python() {
+    check_corpus("python");
+}
+
+#[test]
+fn corpus_javascript() {
+    check_corpus("javascript");
+}
+
+#[test]
+fn corpus_typescript() {
+    check_corpus("typescript");
+}
+
+#[test]
+fn corpus_tsx() {
+    check_corpus("tsx");
+}
+
+#[test]
+fn corpus_java() {
+    check_corpus("java");
+}
+
+#[test]
+fn corpus_cpp() {
+    check_corpus("cpp");
+}
+
+#[test]
+fn corpus_go() {
+    check_corpus("go");
+}
+
+#[test]
+fn corpus_csharp() {
+    check_corpus("csharp");
+}
+
+#[test]
+fn corpus_lua() {
+    check_corpus("lua");
+}
+
+#[test]
+fn corpus_elixir() {
+    check_corpus("elixir");
+}
+
+#[test]
+fn corpus_erlang() {
+    check_corpus("erlang");
+}
+
+#[test]
+fn corpus_gleam() {
+    check_corpus("gleam");
+}
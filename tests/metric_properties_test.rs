@@ -0,0 +1,148 @@
+use std::path::Path;
+
+use proptest::prelude::*;
+use singularity_code_analysis::{metrics, FuncSpace, ParserEngineRust, ParserTrait, PythonParser};
+
+/// A function returning its argument after some `if` statements.
+#[derive(Clone, Debug)]
+struct Func {
+    name: usize,
+    ifs: Vec<u8>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Lang {
+    Rust,
+    Python,
+}
+
+impl Lang {
+    // A function whose statements are separated by `separator`
+    fn render(self, func: &Func, separator: &str) -> String {
+        let (signature, statement, last, end) = match self {
+            Lang::Rust => (
+                format!("fn f{}(x: i32) -> i32 {{\n", func.name),
+                "    if x > {} {\n        return x;\n    }\n",
+                "    x\n",
+                "}\n",
+            ),
+            Lang::Python => (
+                format!("def f{}(x):\n", func.name),
+                "    if x > {}:\n        return x\n",
+                "    return x\n",
+                "",
+            ),
+        };
+        let mut statements = func
+            .ifs
+            .iter()
+            .map(|bound| statement.replace("{}", &bound.to_string()))
+            .collect::<Vec<_>>();
+        statements.push(last.to_string());
+
+        format!("{signature}{}{end}", statements.join(separator))
+    }
+
+    fn comment(self) -> &'static str {
+        match self {
+            Lang::Rust => "// A comment\n",
+            Lang::Python => "# A comment\n",
+        }
+    }
+
+    fn space(self, code: &str) -> FuncSpace {
+        let code = code.as_bytes().to_vec();
+        match self {
+            Lang::Rust => {
+                let path = Path::new("foo.rs");
+                metrics(&ParserEngineRust::new(code, path, None), path).unwrap()
+            }
+            Lang::Python => {
+                let path = Path::new("foo.py");
+                metrics(&PythonParser::new(code, path, None), path).unwrap()
+            }
+        }
+    }
+}
+
+fn lang() -> impl Strategy<Value = Lang> {
+    prop_oneof![Just(Lang::Rust), Just(Lang::Python)]
+}
+
+// The bounds of the `if` statements of a function
+fn ifs() -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(any::<u8>(), 0..4)
+}
+
+fn funcs() -> impl Strategy<Value = Vec<Func>> {
+    prop::collection::vec(ifs(), 1..6).prop_map(|ifs| {
+        ifs.into_iter()
+            .enumerate()
+            .map(|(name, ifs)| Func { name, ifs })
+            .collect()
+    })
+}
+
+// Some functions, in their order and shuffled
+fn shuffled_funcs() -> impl Strategy<Value = (Vec<Func>, Vec<Func>)> {
+    funcs().prop_flat_map(|funcs| (Just(funcs.clone()), Just(funcs).prop_shuffle()))
+}
+
+// The functions of a code, separated by `separator`
+fn program(lang: Lang, funcs: &[Func], separator: &str) -> String {
+    funcs
+        .iter()
+        .map(|func| lang.render(func, ""))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+proptest! {
+    #[test]
+    fn comments_change_only_cloc(lang in lang(), funcs in funcs()) {
+        let code = lang.space(&program(lang, &funcs, ""));
+        let commented = lang.space(&program(lang, &funcs, lang.comment()));
+        let (code, commented) = (&code.metrics, &commented.metrics);
+
+        prop_assert_eq!(commented.loc.cloc(), code.loc.cloc() + (funcs.len() - 1) as f64);
+        prop_assert_eq!(commented.loc.ploc(), code.loc.ploc());
+        prop_assert_eq!(commented.cyclomatic.cyclomatic_sum(), code.cyclomatic.cyclomatic_sum());
+        prop_assert_eq!(commented.cognitive.cognitive_sum(), code.cognitive.cognitive_sum());
+        prop_assert_eq!(commented.nom.total(), code.nom.total());
+    }
+
+    #[test]
+    fn reordering_functions_keeps_metrics(lang in lang(), (funcs, shuffled) in shuffled_funcs()) {
+        let code = lang.space(&program(lang, &funcs, ""));
+        let reordered = lang.space(&program(lang, &shuffled, ""));
+        let (code, reordered) = (&code.metrics, &reordered.metrics);
+
+        prop_assert_eq!(reordered.loc.sloc(), code.loc.sloc());
+        prop_assert_eq!(reordered.loc.ploc(), code.loc.ploc());
+        prop_assert_eq!(reordered.cyclomatic.cyclomatic_sum(), code.cyclomatic.cyclomatic_sum());
+        prop_assert_eq!(reordered.halstead.length(), code.halstead.length());
+        prop_assert_eq!(reordered.halstead.volume(), code.halstead.volume());
+        prop_assert_eq!(reordered.nom.total(), code.nom.total());
+    }
+
+    #[test]
+    fn sloc_is_ploc_plus_blanks(lang in lang(), ifs in ifs(), blanks in 1..4usize) {
+        let func = Func { name: 0, ifs };
+        let space = lang.space(&lang.render(&func, &"\n".repeat(blanks)));
+        let loc = &space.spaces[0].metrics.loc;
+
+        prop_assert_eq!(loc.blank(), (func.ifs.len() * blanks) as f64);
+        prop_assert_eq!(loc.sloc(), loc.ploc() + loc.blank());
+    }
+
+    #[test]
+    fn mi_decreases_with_more_code(lang in lang(), funcs in funcs(), ifs in ifs()) {
+        let mut more = funcs.clone();
+        more.push(Func { name: funcs.len(), ifs: [&[0][..], &ifs].concat() });
+
+        let code = lang.space(&program(lang, &funcs, ""));
+        let more = lang.space(&program(lang, &more, ""));
+
+        prop_assert!(more.metrics.mi.mi_original() < code.metrics.mi.mi_original());
+    }
+}